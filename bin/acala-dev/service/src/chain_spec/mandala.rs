@@ -15,7 +15,13 @@ use crate::chain_spec::{
 pub type ChainSpec = sc_service::GenericChainSpec<mandala_runtime::GenesisConfig, Extensions>;
 
 fn mandala_session_keys(grandpa: GrandpaId, babe: BabeId) -> mandala_runtime::SessionKeys {
-	mandala_runtime::SessionKeys { grandpa, babe }
+	// the feeder key is the babe key until the authority rotates the session keys
+	let price_feeder = mandala_runtime::PriceFeederId::from(sr25519::Public::from(babe.clone()));
+	mandala_runtime::SessionKeys {
+		grandpa,
+		babe,
+		price_feeder,
+	}
 }
 
 /// Development testnet config (single validator Alice)
@@ -336,7 +342,7 @@ fn testnet_genesis(
 					10_000_000 * DOLLARS,
 				),
 			],
-			global_stability_fee: FixedU128::saturating_from_rational(618_850_393, 100_000_000_000_000_000_u128), /* 5% APR */
+			global_stability_fee: FixedU128::saturating_from_rational(1_547_125_957, 1_000_000_000_000_000_000_u128), /* 5% APR */
 		}),
 		module_airdrop: Some(AirDropConfig {
 			airdrop_accounts: vec![],
@@ -555,7 +561,7 @@ fn mandala_genesis(
 					10_000_000 * DOLLARS,
 				),
 			],
-			global_stability_fee: FixedU128::saturating_from_rational(618_850_393, 100_000_000_000_000_000_u128), /* 5% APR */
+			global_stability_fee: FixedU128::saturating_from_rational(1_547_125_957, 1_000_000_000_000_000_000_u128), /* 5% APR */
 		}),
 		module_airdrop: Some(AirDropConfig {
 			airdrop_accounts: {
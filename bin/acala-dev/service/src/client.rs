@@ -1,7 +1,9 @@
 //! Acala Client abstractions.
 
-use acala_primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Header, Nonce};
-use runtime_common::TimeStampedPrice;
+use acala_primitives::{
+	AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Header, Moment, Nonce,
+};
+use runtime_common::{Price, TimeStampedPrice};
 use sc_client_api::{Backend as BackendT, BlockchainEvents, KeyIterator};
 use sp_api::{CallApiAt, NumberFor, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
@@ -25,7 +27,13 @@ pub trait RuntimeApiCollection:
 	+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 	+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 	+ module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>
+	+ module_honzon_rpc::HonzonRuntimeApi<Block, AccountId, Balance>
+	+ module_cdp_treasury_rpc::CDPTreasuryRuntimeApi<Block, Balance>
+	+ module_prices_rpc::PricesRuntimeApi<Block, CurrencyId, Price, Moment>
+	+ module_nft_rpc::NFTRuntimeApi<Block, AccountId, u32, u64>
 	+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+	+ runtime_common_rpc::DryRunRuntimeApi<Block>
+	+ runtime_common_rpc::EventsRuntimeApi<Block>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
@@ -45,7 +53,13 @@ where
 		+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 		+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 		+ module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>
+		+ module_honzon_rpc::HonzonRuntimeApi<Block, AccountId, Balance>
+		+ module_cdp_treasury_rpc::CDPTreasuryRuntimeApi<Block, Balance>
+		+ module_prices_rpc::PricesRuntimeApi<Block, CurrencyId, Price, Moment>
+		+ module_nft_rpc::NFTRuntimeApi<Block, AccountId, u32, u64>
 		+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+		+ runtime_common_rpc::DryRunRuntimeApi<Block>
+		+ runtime_common_rpc::EventsRuntimeApi<Block>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>,
@@ -249,7 +249,7 @@ fn acala_genesis(
 					10_000_000 * DOLLARS,
 				),
 			],
-			global_stability_fee: FixedU128::saturating_from_rational(618_850_393, 100_000_000_000_000_000_u128), /* 5% APR */
+			global_stability_fee: FixedU128::saturating_from_rational(1_547_125_957, 1_000_000_000_000_000_000_u128), /* 5% APR */
 		}),
 		orml_oracle_Instance1: Some(AcalaOracleConfig {
 			members: Default::default(), // initialized by OperatorMembership
@@ -1,7 +1,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Encode;
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::{Get, Happened},
+};
 use frame_system::{self as system, ensure_none, ensure_signed};
 use orml_traits::BasicCurrency;
 use primitives::Balance;
@@ -32,6 +35,11 @@ pub trait Config: system::Config {
 	/// This is exposed so that it can be tuned for particular runtime, when
 	/// multiple modules send unsigned transactions.
 	type UnsignedPriority: Get<TransactionPriority>;
+	/// Handler for the account minted by the bridge deposit
+	type OnMinted: Happened<Self::AccountId>;
+	/// The min amount of the bridge deposit to trigger `OnMinted`, so that
+	/// the dust deposits can't drain the onboarding quota
+	type MinOnboardingAmount: Get<Balance>;
 }
 
 decl_storage! {
@@ -140,6 +148,9 @@ impl<T: Config> Module<T> {
 	fn do_mint(sender: T::AccountId, amount: Balance, sig: EcdsaSignature) -> DispatchResult {
 		T::Currency::deposit(&sender, amount)?;
 		Signatures::insert(&sig, ());
+		if amount >= T::MinOnboardingAmount::get() {
+			T::OnMinted::happened(&sender);
+		}
 
 		Self::deposit_event(RawEvent::Minted(sender, amount));
 		Ok(())
@@ -9,6 +9,7 @@ use orml_traits::parameter_type_with_key;
 use primitives::{Amount, CurrencyId, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+use std::cell::RefCell;
 
 pub type AccountId = H256;
 pub type BlockNumber = u64;
@@ -119,11 +120,33 @@ impl orml_currencies::Config for Runtime {
 	type WeightInfo = ();
 }
 
+thread_local! {
+	static MINTED_ACCOUNTS: RefCell<Vec<AccountId>> = RefCell::new(vec![]);
+}
+
+pub struct MockOnMinted;
+impl MockOnMinted {
+	pub fn minted_accounts() -> Vec<AccountId> {
+		MINTED_ACCOUNTS.with(|v| v.borrow().clone())
+	}
+}
+impl Happened<AccountId> for MockOnMinted {
+	fn happened(who: &AccountId) {
+		MINTED_ACCOUNTS.with(|v| v.borrow_mut().push(*who));
+	}
+}
+
+parameter_types! {
+	pub const MinOnboardingAmount: Balance = 90000;
+}
+
 impl Config for Runtime {
 	type Event = TestEvent;
 	type Currency = BasicCurrencyAdapter<Runtime, Balances, i128, BlockNumber>;
 	type CurrencyIdentifier = RENBTCIdentifier;
 	type UnsignedPriority = UnsignedPriority;
+	type OnMinted = MockOnMinted;
+	type MinOnboardingAmount = MinOnboardingAmount;
 }
 pub type RenVmBridge = Module<Runtime>;
 pub type System = frame_system::Module<Runtime>;
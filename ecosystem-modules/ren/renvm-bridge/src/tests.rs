@@ -5,7 +5,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok, unsigned::ValidateUnsigned};
 use hex_literal::hex;
-use mock::{AccountId, Balances, ExtBuilder, Origin, RenVmBridge, RenvmBridgeCall, System};
+use mock::{AccountId, Balances, ExtBuilder, MockOnMinted, Origin, RenVmBridge, RenvmBridgeCall, System};
 use sp_core::H256;
 use sp_runtime::transaction_validity::TransactionValidityError;
 
@@ -135,6 +135,7 @@ fn mint_works() {
 		);
 
 		assert_eq!(Balances::free_balance(to.clone()), 93963);
+		assert_eq!(MockOnMinted::minted_accounts(), vec![to.clone()]);
 
 		assert_ok!(
 			mint_ren_btc(
@@ -147,6 +148,8 @@ fn mint_works() {
 		);
 
 		assert_eq!(Balances::free_balance(to.clone()), 93963 + 87266);
+		// the deposit below `MinOnboardingAmount` doesn't trigger `OnMinted`
+		assert_eq!(MockOnMinted::minted_accounts(), vec![to.clone()]);
 
 		assert_noop!(
 			mint_ren_btc(
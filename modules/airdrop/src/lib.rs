@@ -1,20 +1,155 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_event, decl_module, decl_storage, transactional};
-use frame_system::{self as system, ensure_root};
-use primitives::{AirDropCurrencyId, Balance};
-use sp_runtime::traits::StaticLookup;
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	storage::IterableStorageDoubleMap,
+	traits::{Currency, ExistenceRequirement, Get, Happened},
+	transactional,
+	weights::Weight,
+};
+use frame_system::{self as system, ensure_none, ensure_root, ensure_signed};
+use module_evm_accounts::{eth_recover, to_ascii_hex, EcdsaSignature};
+use primitives::{evm::EvmAddress, AirDropCurrencyId, Balance};
+use sp_runtime::{
+	traits::{AccountIdConversion, Hash, Saturating, StaticLookup, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
+	},
+	DispatchError, DispatchResult, ModuleId, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+use support::VestedTransfer;
 
 mod mock;
 mod tests;
 
+pub type DropId = u32;
+
+/// The number of claims recorded by a word of the claimed bitmap
+pub const CLAIMED_BITMAP_WORD_BITS: u32 = 128;
+
+/// The extra data signed with the drop id and the target account to claim
+/// the airdrop of an Ethereum address
+pub const ETH_CLAIM_EXTRA: &[u8] = b"airdrop";
+
+/// The vesting profile applied to the claimed airdrop
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct VestingProfile<BlockNumber> {
+	/// The blocks the claimed airdrop is locked before vesting
+	pub cliff: BlockNumber,
+	/// The blocks the claimed airdrop is vested linearly over after the cliff
+	pub duration: BlockNumber,
+}
+
+/// The drop claimed by merkle proofs
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DropInfo<Hash, BlockNumber> {
+	/// The airdrop currency of the drop
+	pub currency_id: AirDropCurrencyId,
+	/// The merkle root of the claims of the drop
+	pub merkle_root: Hash,
+	/// The vesting profile of the drop. If some, the claimed airdrop is
+	/// transferred from the module account with the vesting schedule instead
+	/// of recorded
+	pub vesting: Option<VestingProfile<BlockNumber>>,
+	/// The total airdrop of the claims of the drop
+	pub total: Balance,
+	/// The claimed airdrop of the drop
+	pub claimed: Balance,
+	/// The block number after which the drop can not be claimed and can be
+	/// swept, `None` means the drop never expires
+	pub expire_at: Option<BlockNumber>,
+}
+
+/// The drop claimed from the list uploaded by governance in batches
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ListedDropInfo<BlockNumber> {
+	/// The airdrop currency of the drop
+	pub currency_id: AirDropCurrencyId,
+	/// The vesting profile of the drop
+	pub vesting: Option<VestingProfile<BlockNumber>>,
+	/// The total airdrop of the accounts in the list
+	pub total: Balance,
+	/// The claimed airdrop of the drop
+	pub claimed: Balance,
+	/// The block number after which the drop can not be claimed and can be
+	/// swept, `None` means the drop never expires
+	pub expire_at: Option<BlockNumber>,
+	/// The number of the accounts in the list
+	pub count: u32,
+	/// Whether the list is finalized. The list can only be appended before
+	/// finalized, and only be claimed after finalized
+	pub finalized: bool,
+}
+
+/// Merkle proof of the claim at `index` of a drop.
+///
+/// The leaf of the claim is the hash of the SCALE encoded `(index, account,
+/// amount)`, and the parent node is the hash of the SCALE encoded `(left,
+/// right)` children. The last node of a level with odd number of nodes is
+/// paired with itself.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MerkleProof<Hash> {
+	/// The index of the claim in the leaves
+	#[codec(compact)]
+	pub index: u32,
+	/// The sibling nodes from the leaf up to the root
+	pub path: Vec<Hash>,
+}
+
 pub trait Config: system::Config {
 	type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+	/// Handler for the account received the airdrop
+	type OnAirdrop: Happened<Self::AccountId>;
+	/// The airdrop module id, keeps the native currency claimed by the drops
+	/// with vesting profile
+	type ModuleId: Get<ModuleId>;
+	/// The airdrop currency id of the native currency
+	type NativeAirDropCurrencyId: Get<AirDropCurrencyId>;
+	/// Transfer the native currency with the vesting schedule
+	type VestedTransfer: VestedTransfer<Self::AccountId, Balance, Self::BlockNumber>;
+	/// The minimum amount of the vested transfer, the smaller claims of the
+	/// drops with vesting profile are transferred liquidly
+	type MinVestedTransfer: Get<Balance>;
+	/// The maximum number of the accounts appended to a listed drop by a
+	/// batch
+	type MaxAirdropBatch: Get<u32>;
+	/// The maximum number of the claim records of an expired drop cleared
+	/// by a sweep
+	type MaxSweepBatch: Get<u32>;
+	/// The native currency, the unclaimed airdrop of the expired drops with
+	/// vesting profile is returned from the module account by it
+	type Currency: Currency<Self::AccountId, Balance = Balance>;
+	/// The treasury account receives the unclaimed airdrop of the expired
+	/// drops
+	type TreasuryAccount: Get<Self::AccountId>;
+	/// A configuration for base priority of unsigned transactions.
+	///
+	/// This is exposed so that it can be tuned for particular runtime, when
+	/// multiple modules send unsigned transactions.
+	type UnsignedPriority: Get<TransactionPriority>;
 }
 
 decl_storage! {
 	trait Store for Module<T: Config> as AirDrop {
 		AirDrops get(fn airdrops): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) AirDropCurrencyId => Balance;
+
+		/// The drops claimed by merkle proofs
+		Drops get(fn drops): map hasher(twox_64_concat) DropId => Option<DropInfo<T::Hash, T::BlockNumber>>;
+		/// Next drop id
+		NextDropId get(fn next_drop_id): DropId;
+		/// The bitmap of the claimed indexes of the drops, each word records
+		/// `CLAIMED_BITMAP_WORD_BITS` claims
+		ClaimedBitmap get(fn claimed_bitmap): double_map hasher(twox_64_concat) DropId, hasher(twox_64_concat) u32 => u128;
+		/// The drops claimed from the lists uploaded in batches
+		ListedDrops get(fn listed_drops): map hasher(twox_64_concat) DropId => Option<ListedDropInfo<T::BlockNumber>>;
+		/// The unclaimed airdrop of the accounts in the lists of the listed
+		/// drops
+		DropList get(fn drop_list): double_map hasher(twox_64_concat) DropId, hasher(twox_64_concat) T::AccountId => Option<Balance>;
+		/// The unclaimed airdrop of the Ethereum addresses in the lists of the
+		/// listed drops
+		DropEthList get(fn drop_eth_list): double_map hasher(twox_64_concat) DropId, hasher(twox_64_concat) EvmAddress => Option<Balance>;
 	}
 
 	add_extra_genesis {
@@ -31,23 +166,77 @@ decl_storage! {
 decl_event!(
 	pub enum Event<T> where
 		<T as system::Config>::AccountId,
+		<T as system::Config>::Hash,
 		AirDropCurrencyId = AirDropCurrencyId,
 		Balance = Balance,
 	{
-		/// \[to, currency_id, amount\]
-		Airdrop(AccountId, AirDropCurrencyId, Balance),
 		/// \[to, currency_id, amount\]
 		UpdateAirdrop(AccountId, AirDropCurrencyId, Balance),
+		/// New drop created. \[drop_id, currency_id, merkle_root\]
+		NewDrop(DropId, AirDropCurrencyId, Hash),
+		/// Airdrop claimed. \[drop_id, who, currency_id, amount\]
+		Claimed(DropId, AccountId, AirDropCurrencyId, Balance),
+		/// New listed drop created. \[drop_id, currency_id\]
+		NewListedDrop(DropId, AirDropCurrencyId),
+		/// A batch appended to the list of the drop. \[drop_id, batch_count\]
+		AirdropBatchAdded(DropId, u32),
+		/// The list of the drop finalized. \[drop_id, count\]
+		DropFinalized(DropId, u32),
+		/// The expired drop swept. \[drop_id, unclaimed_amount\]
+		DropSwept(DropId, Balance),
+		/// A batch of the claim records of the expired drop cleared, the drop
+		/// is swept after all of them are cleared. \[drop_id, cleared_count\]
+		DropPartiallySwept(DropId, u32),
 	}
 );
 
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// The drop does not exist.
+		DropNotFound,
+		/// The merkle proof is invalid.
+		InvalidProof,
+		/// The claim has already been claimed.
+		AlreadyClaimed,
+		/// Drop ID overflow.
+		DropIdOverflow,
+		/// The vesting profile is only supported by the drops of the native
+		/// currency.
+		VestingNotSupported,
+		/// The batch exceeds the max airdrop batch.
+		TooLargeBatch,
+		/// The account is already in the list of the drop.
+		DuplicateAccount,
+		/// The list of the drop is already finalized.
+		DropFinalized,
+		/// The list of the drop is not finalized yet.
+		DropNotFinalized,
+		/// The account is not in the list of the drop, or has claimed.
+		NotInList,
+		/// The Ethereum signature is invalid.
+		BadSignature,
+		/// The claims exceed the total of the drop.
+		ExceedTotal,
+		/// The drop has expired.
+		DropExpired,
+		/// The drop has not expired yet.
+		DropNotExpired,
+	}
+}
+
 decl_module! {
 	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
 		fn deposit_event() = default;
 
+		/// The maximum number of the accounts appended to a listed drop by a
+		/// batch
+		const MaxAirdropBatch: u32 = T::MaxAirdropBatch::get();
+
 		#[weight = 10_000]
 		#[transactional]
-		pub fn airdrop(
+		pub fn update_airdrop(
 			origin,
 			to: <T::Lookup as StaticLookup>::Source,
 			currency_id: AirDropCurrencyId,
@@ -55,24 +244,401 @@ decl_module! {
 		) {
 			ensure_root(origin)?;
 			let to = T::Lookup::lookup(to)?;
-			<AirDrops<T>>::mutate(&to, currency_id, |balance| *balance += amount);
-			Self::deposit_event(RawEvent::Airdrop(to, currency_id, amount));
+			<AirDrops<T>>::insert(&to, currency_id, amount);
+			Self::deposit_event(RawEvent::UpdateAirdrop(to, currency_id, amount));
 		}
 
+		/// Create a new drop of the currency claimed by merkle proofs against
+		/// the merkle root, with the optional vesting profile applied to the
+		/// claimed airdrop of the native currency. The `total` is the sum of
+		/// the claims in the merkle tree, and the drop can't be claimed after
+		/// the optional `expire_at`.
+		///
+		/// The dispatch origin of this call must be _Root_.
 		#[weight = 10_000]
 		#[transactional]
-		pub fn update_airdrop(
+		pub fn new_drop(
 			origin,
-			to: <T::Lookup as StaticLookup>::Source,
 			currency_id: AirDropCurrencyId,
-			amount: Balance,
+			merkle_root: T::Hash,
+			#[compact] total: Balance,
+			vesting: Option<VestingProfile<T::BlockNumber>>,
+			expire_at: Option<T::BlockNumber>,
 		) {
 			ensure_root(origin)?;
-			let to = T::Lookup::lookup(to)?;
-			<AirDrops<T>>::insert(&to, currency_id, amount);
-			Self::deposit_event(RawEvent::UpdateAirdrop(to, currency_id, amount));
+			ensure!(
+				vesting.is_none() || currency_id == T::NativeAirDropCurrencyId::get(),
+				Error::<T>::VestingNotSupported
+			);
+
+			let drop_id = Self::allocate_drop_id()?;
+			<Drops<T>>::insert(drop_id, DropInfo {
+				currency_id,
+				merkle_root,
+				vesting,
+				total,
+				claimed: Zero::zero(),
+				expire_at,
+			});
+			Self::deposit_event(RawEvent::NewDrop(drop_id, currency_id, merkle_root));
+		}
+
+		/// Claim the airdrop of the drop by the merkle proof of the claim.
+		#[weight = 10_000 + 1_000 * proof.path.len() as Weight]
+		#[transactional]
+		pub fn claim(
+			origin,
+			drop_id: DropId,
+			#[compact] amount: Balance,
+			proof: MerkleProof<T::Hash>,
+		) {
+			let who = ensure_signed(origin)?;
+			let mut drop = Self::drops(drop_id).ok_or(Error::<T>::DropNotFound)?;
+			ensure!(!Self::is_expired(drop.expire_at), Error::<T>::DropExpired);
+
+			ensure!(!Self::is_claimed(drop_id, proof.index), Error::<T>::AlreadyClaimed);
+			ensure!(
+				Self::verify_proof(&drop.merkle_root, &who, amount, &proof),
+				Error::<T>::InvalidProof
+			);
+
+			ClaimedBitmap::mutate(drop_id, proof.index / CLAIMED_BITMAP_WORD_BITS, |word| {
+				*word |= 1u128 << (proof.index % CLAIMED_BITMAP_WORD_BITS)
+			});
+			drop.claimed = drop.claimed.saturating_add(amount);
+			ensure!(drop.claimed <= drop.total, Error::<T>::ExceedTotal);
+			<Drops<T>>::insert(drop_id, &drop);
+			Self::do_claim(drop_id, who, drop.currency_id, drop.vesting, amount)?;
+		}
+
+		/// Create a new drop of the currency claimed from the list uploaded
+		/// by `add_airdrop_batch`, with the optional vesting profile applied
+		/// to the claimed airdrop of the native currency. The drop can't be
+		/// claimed after the optional `expire_at`.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[weight = 10_000]
+		#[transactional]
+		pub fn new_listed_drop(
+			origin,
+			currency_id: AirDropCurrencyId,
+			vesting: Option<VestingProfile<T::BlockNumber>>,
+			expire_at: Option<T::BlockNumber>,
+		) {
+			ensure_root(origin)?;
+			ensure!(
+				vesting.is_none() || currency_id == T::NativeAirDropCurrencyId::get(),
+				Error::<T>::VestingNotSupported
+			);
+
+			let drop_id = Self::allocate_drop_id()?;
+			<ListedDrops<T>>::insert(drop_id, ListedDropInfo {
+				currency_id,
+				vesting,
+				total: Zero::zero(),
+				claimed: Zero::zero(),
+				expire_at,
+				count: 0,
+				finalized: false,
+			});
+			Self::deposit_event(RawEvent::NewListedDrop(drop_id, currency_id));
+		}
+
+		/// Append the batch of the accounts and their airdrop to the list of
+		/// the unfinalized drop, a large list can be uploaded by multiple
+		/// batches. The account already in the list is rejected.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[weight = 10_000 + 10_000 * batch.len() as Weight]
+		#[transactional]
+		pub fn add_airdrop_batch(origin, drop_id: DropId, batch: Vec<(T::AccountId, Balance)>) {
+			ensure_root(origin)?;
+			let batch_count = batch.len() as u32;
+			ensure!(batch_count <= T::MaxAirdropBatch::get(), Error::<T>::TooLargeBatch);
+
+			<ListedDrops<T>>::try_mutate(drop_id, |maybe_drop| -> DispatchResult {
+				let drop = maybe_drop.as_mut().ok_or(Error::<T>::DropNotFound)?;
+				ensure!(!drop.finalized, Error::<T>::DropFinalized);
+
+				for (who, amount) in batch {
+					ensure!(!<DropList<T>>::contains_key(drop_id, &who), Error::<T>::DuplicateAccount);
+					<DropList<T>>::insert(drop_id, who, amount);
+					drop.total = drop.total.saturating_add(amount);
+				}
+				drop.count = drop.count.saturating_add(batch_count);
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::AirdropBatchAdded(drop_id, batch_count));
+		}
+
+		/// Append the batch of the Ethereum addresses and their airdrop to the
+		/// list of the unfinalized drop, the airdrop of an address is claimed
+		/// by `claim_by_eth_signature` to a Substrate account. The address
+		/// already in the list is rejected.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[weight = 10_000 + 10_000 * batch.len() as Weight]
+		#[transactional]
+		pub fn add_eth_airdrop_batch(origin, drop_id: DropId, batch: Vec<(EvmAddress, Balance)>) {
+			ensure_root(origin)?;
+			let batch_count = batch.len() as u32;
+			ensure!(batch_count <= T::MaxAirdropBatch::get(), Error::<T>::TooLargeBatch);
+
+			<ListedDrops<T>>::try_mutate(drop_id, |maybe_drop| -> DispatchResult {
+				let drop = maybe_drop.as_mut().ok_or(Error::<T>::DropNotFound)?;
+				ensure!(!drop.finalized, Error::<T>::DropFinalized);
+
+				for (eth_address, amount) in batch {
+					ensure!(!DropEthList::contains_key(drop_id, eth_address), Error::<T>::DuplicateAccount);
+					DropEthList::insert(drop_id, eth_address, amount);
+					drop.total = drop.total.saturating_add(amount);
+				}
+				drop.count = drop.count.saturating_add(batch_count);
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::AirdropBatchAdded(drop_id, batch_count));
+		}
+
+		/// Finalize the list of the drop, the accounts in the list can claim
+		/// after that.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[weight = 10_000]
+		#[transactional]
+		pub fn finalize_drop(origin, drop_id: DropId) {
+			ensure_root(origin)?;
+			let count = <ListedDrops<T>>::try_mutate(drop_id, |maybe_drop| -> Result<u32, DispatchError> {
+				let drop = maybe_drop.as_mut().ok_or(Error::<T>::DropNotFound)?;
+				ensure!(!drop.finalized, Error::<T>::DropFinalized);
+				drop.finalized = true;
+				Ok(drop.count)
+			})?;
+			Self::deposit_event(RawEvent::DropFinalized(drop_id, count));
+		}
+
+		/// Claim the airdrop of the caller in the list of the finalized drop.
+		#[weight = 10_000]
+		#[transactional]
+		pub fn claim_listed(origin, drop_id: DropId) {
+			let who = ensure_signed(origin)?;
+			let mut drop = Self::listed_drops(drop_id).ok_or(Error::<T>::DropNotFound)?;
+			ensure!(drop.finalized, Error::<T>::DropNotFinalized);
+			ensure!(!Self::is_expired(drop.expire_at), Error::<T>::DropExpired);
+
+			let amount = <DropList<T>>::take(drop_id, &who).ok_or(Error::<T>::NotInList)?;
+			drop.claimed = drop.claimed.saturating_add(amount);
+			<ListedDrops<T>>::insert(drop_id, &drop);
+			Self::do_claim(drop_id, who, drop.currency_id, drop.vesting, amount)?;
+		}
+
+		/// Claim the airdrop of the Ethereum address in the list of the
+		/// finalized drop to the `target` account. The address is recovered
+		/// from `eth_signature`, which is signed by the Ethereum RPC's
+		/// `personal_sign` for the ASCII-encoded hex of the SCALE encoded
+		/// `(drop_id, target)` followed by `ETH_CLAIM_EXTRA`.
+		///
+		/// The dispatch origin of this call must be _None_.
+		///
+		/// Verify input by `validate_unsigned`
+		#[weight = 10_000]
+		#[transactional]
+		pub fn claim_by_eth_signature(
+			origin,
+			drop_id: DropId,
+			target: T::AccountId,
+			eth_signature: EcdsaSignature,
+		) {
+			ensure_none(origin)?;
+			let mut drop = Self::listed_drops(drop_id).ok_or(Error::<T>::DropNotFound)?;
+			ensure!(drop.finalized, Error::<T>::DropNotFinalized);
+			ensure!(!Self::is_expired(drop.expire_at), Error::<T>::DropExpired);
+
+			let eth_address = Self::eth_claimer(drop_id, &target, &eth_signature).ok_or(Error::<T>::BadSignature)?;
+			let amount = DropEthList::take(drop_id, eth_address).ok_or(Error::<T>::NotInList)?;
+			drop.claimed = drop.claimed.saturating_add(amount);
+			<ListedDrops<T>>::insert(drop_id, &drop);
+			Self::do_claim(drop_id, target, drop.currency_id, drop.vesting, amount)?;
+		}
+
+		/// Sweep the expired drop, clear at most `MaxSweepBatch` claim
+		/// records of the drop per call. After all of them are cleared,
+		/// return the unclaimed airdrop held by the module account to the
+		/// treasury and remove the drop.
+		///
+		/// The dispatch origin of this call must be _Signed_, anyone can
+		/// sweep.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(
+			T::MaxSweepBatch::get() as Weight + 4,
+			T::MaxSweepBatch::get() as Weight + 4,
+		)]
+		#[transactional]
+		pub fn sweep_expired(origin, drop_id: DropId) {
+			ensure_signed(origin)?;
+			let (vesting, unclaimed, expire_at) = if let Some(drop) = Self::drops(drop_id) {
+				(drop.vesting, drop.total.saturating_sub(drop.claimed), drop.expire_at)
+			} else if let Some(drop) = Self::listed_drops(drop_id) {
+				(drop.vesting, drop.total.saturating_sub(drop.claimed), drop.expire_at)
+			} else {
+				return Err(Error::<T>::DropNotFound.into());
+			};
+			ensure!(
+				expire_at.is_some() && Self::is_expired(expire_at),
+				Error::<T>::DropNotExpired
+			);
+
+			let max_count = T::MaxSweepBatch::get() as usize;
+			let indexes: Vec<u32> = ClaimedBitmap::iter_prefix(drop_id)
+				.take(max_count)
+				.map(|(index, _)| index)
+				.collect();
+			indexes.iter().for_each(|index| ClaimedBitmap::remove(drop_id, index));
+			let accounts: Vec<T::AccountId> = <DropList<T>>::iter_prefix(drop_id)
+				.take(max_count.saturating_sub(indexes.len()))
+				.map(|(who, _)| who)
+				.collect();
+			accounts.iter().for_each(|who| <DropList<T>>::remove(drop_id, who));
+			let addresses: Vec<EvmAddress> = DropEthList::iter_prefix(drop_id)
+				.take(max_count.saturating_sub(indexes.len()).saturating_sub(accounts.len()))
+				.map(|(address, _)| address)
+				.collect();
+			addresses.iter().for_each(|address| DropEthList::remove(drop_id, address));
+
+			// there may be more claim records to be cleared by the next sweep
+			let cleared_count = indexes.len() + accounts.len() + addresses.len();
+			if cleared_count == max_count {
+				Self::deposit_event(RawEvent::DropPartiallySwept(drop_id, cleared_count as u32));
+				return Ok(());
+			}
+
+			// only the drops with vesting profile hold the airdrop, the others
+			// are recorded
+			if vesting.is_some() && !unclaimed.is_zero() {
+				let module_account = Self::account_id();
+				T::Currency::transfer(
+					&module_account,
+					&T::TreasuryAccount::get(),
+					unclaimed.min(T::Currency::free_balance(&module_account)),
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+
+			<Drops<T>>::remove(drop_id);
+			<ListedDrops<T>>::remove(drop_id);
+			Self::deposit_event(RawEvent::DropSwept(drop_id, unclaimed));
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// The account keeps the native currency claimed by the drops with
+	/// vesting profile
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	fn allocate_drop_id() -> Result<DropId, DispatchError> {
+		NextDropId::try_mutate(|id| -> Result<DropId, DispatchError> {
+			let drop_id = *id;
+			*id = id.checked_add(1).ok_or(Error::<T>::DropIdOverflow)?;
+			Ok(drop_id)
+		})
+	}
+
+	fn do_claim(
+		drop_id: DropId,
+		who: T::AccountId,
+		currency_id: AirDropCurrencyId,
+		vesting: Option<VestingProfile<T::BlockNumber>>,
+		amount: Balance,
+	) -> DispatchResult {
+		if let Some(vesting) = vesting {
+			if amount >= T::MinVestedTransfer::get() {
+				T::VestedTransfer::vested_transfer_with_cliff(
+					&Self::account_id(),
+					&who,
+					amount,
+					vesting.cliff,
+					vesting.duration,
+				)?;
+			} else {
+				T::Currency::transfer(&Self::account_id(), &who, amount, ExistenceRequirement::AllowDeath)?;
+			}
+		} else {
+			<AirDrops<T>>::mutate(&who, currency_id, |balance| *balance += amount);
+		}
+		T::OnAirdrop::happened(&who);
+		Self::deposit_event(RawEvent::Claimed(drop_id, who, currency_id, amount));
+		Ok(())
+	}
+
+	/// Recover the Ethereum address which signed the claim of the drop to
+	/// the target account
+	pub fn eth_claimer(drop_id: DropId, target: &T::AccountId, eth_signature: &EcdsaSignature) -> Option<EvmAddress> {
+		eth_recover(
+			eth_signature,
+			&(drop_id, target).using_encoded(to_ascii_hex),
+			ETH_CLAIM_EXTRA,
+		)
+	}
+
+	/// Return whether the drop expires at `expire_at` has expired
+	fn is_expired(expire_at: Option<T::BlockNumber>) -> bool {
+		expire_at.map_or(false, |expire_at| <system::Module<T>>::block_number() > expire_at)
+	}
+
+	/// Return whether the claim at the index of the proof is in the merkle
+	/// tree of the root
+	pub fn verify_proof(root: &T::Hash, who: &T::AccountId, amount: Balance, proof: &MerkleProof<T::Hash>) -> bool {
+		let mut index = proof.index;
+		let mut node = T::Hashing::hash_of(&(proof.index, who, amount));
+		for sibling in proof.path.iter() {
+			node = if index % 2 == 0 {
+				T::Hashing::hash_of(&(node, sibling))
+			} else {
+				T::Hashing::hash_of(&(sibling, node))
+			};
+			index /= 2;
 		}
+		node == *root
+	}
+
+	/// Return whether the claim at the index of the drop has been claimed
+	pub fn is_claimed(drop_id: DropId, index: u32) -> bool {
+		Self::claimed_bitmap(drop_id, index / CLAIMED_BITMAP_WORD_BITS) & (1u128 << (index % CLAIMED_BITMAP_WORD_BITS))
+			!= 0
 	}
 }
 
-impl<T: Config> Module<T> {}
+impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		match call {
+			Call::claim_by_eth_signature(drop_id, target, eth_signature) => {
+				let claimable = Self::listed_drops(drop_id)
+					.map_or(false, |drop| drop.finalized && !Self::is_expired(drop.expire_at));
+				if !claimable {
+					return InvalidTransaction::Call.into();
+				}
+
+				// verify signature
+				let eth_address = match Self::eth_claimer(*drop_id, target, eth_signature) {
+					Some(eth_address) => eth_address,
+					None => return InvalidTransaction::BadProof.into(),
+				};
+				// check if already claimed
+				if !DropEthList::contains_key(drop_id, eth_address) {
+					return InvalidTransaction::Stale.into();
+				}
+
+				ValidTransaction::with_tag_prefix("airdrop")
+					.priority(T::UnsignedPriority::get())
+					.and_provides((drop_id, eth_address))
+					.longevity(64_u64)
+					.propagate(true)
+					.build()
+			}
+			_ => InvalidTransaction::Call.into(),
+		}
+	}
+}
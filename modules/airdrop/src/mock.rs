@@ -7,6 +7,7 @@ use frame_support::{construct_runtime, parameter_types};
 use sp_api_hidden_includes_decl_storage::hidden_include::inherent::BlockT;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
+use std::cell::RefCell;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -14,6 +15,7 @@ pub type BlockNumber = u64;
 pub const ALICE: AccountId = 0;
 pub const BOB: AccountId = 1;
 pub const CHARLIE: AccountId = 2;
+pub const TREASURY: AccountId = 10;
 pub const ACA: AirDropCurrencyId = AirDropCurrencyId::ACA;
 pub const KAR: AirDropCurrencyId = AirDropCurrencyId::KAR;
 
@@ -41,7 +43,7 @@ impl frame_system::Config for Runtime {
 	type BlockLength = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type DbWeight = ();
@@ -50,8 +52,64 @@ impl frame_system::Config for Runtime {
 	type SS58Prefix = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Module<Runtime>;
+	type MaxLocks = ();
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static VESTED_TRANSFERS: RefCell<Vec<(AccountId, AccountId, Balance, BlockNumber, BlockNumber)>> = RefCell::new(vec![]);
+}
+
+pub fn vested_transfers() -> Vec<(AccountId, AccountId, Balance, BlockNumber, BlockNumber)> {
+	VESTED_TRANSFERS.with(|v| v.borrow().clone())
+}
+
+pub struct MockVestedTransfer;
+impl VestedTransfer<AccountId, Balance, BlockNumber> for MockVestedTransfer {
+	fn vested_transfer_with_cliff(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+		cliff: BlockNumber,
+		duration: BlockNumber,
+	) -> DispatchResult {
+		VESTED_TRANSFERS.with(|v| v.borrow_mut().push((*from, *to, amount, cliff, duration)));
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const AirdropModuleId: ModuleId = ModuleId(*b"aca/aird");
+	pub const NativeAirDropCurrencyId: AirDropCurrencyId = ACA;
+	pub const MaxAirdropBatch: u32 = 2;
+	pub const MaxSweepBatch: u32 = 2;
+	pub const MinVestedTransfer: Balance = 150;
+	pub const TreasuryAccount: AccountId = TREASURY;
+	pub const UnsignedPriority: u64 = 1 << 20;
+}
+
 impl Config for Runtime {
 	type Event = Event;
+	type OnAirdrop = ();
+	type ModuleId = AirdropModuleId;
+	type NativeAirDropCurrencyId = NativeAirDropCurrencyId;
+	type VestedTransfer = MockVestedTransfer;
+	type MinVestedTransfer = MinVestedTransfer;
+	type MaxAirdropBatch = MaxAirdropBatch;
+	type MaxSweepBatch = MaxSweepBatch;
+	type Currency = Balances;
+	type TreasuryAccount = TreasuryAccount;
+	type UnsignedPriority = UnsignedPriority;
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -64,7 +122,8 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Module, Call, Storage, Config, Event<T>},
-		AirDrop: airdrop::{Module, Call, Storage, Event<T>, Config<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		AirDrop: airdrop::{Module, Call, Storage, Event<T>, Config<T>, ValidateUnsigned},
 	}
 );
 
@@ -89,6 +148,12 @@ impl ExtBuilder {
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(Airdrop::account_id(), 1000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
 		t.into()
 	}
 }
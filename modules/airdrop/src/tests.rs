@@ -3,36 +3,64 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
-use mock::{Airdrop, Event, ExtBuilder, Origin, System, ACA, ALICE, BOB, CHARLIE, KAR};
-use sp_runtime::traits::BadOrigin;
+use frame_support::{assert_noop, assert_ok, unsigned::ValidateUnsigned};
+use mock::{
+	vested_transfers, AccountId, Airdrop, Balances, Call, Event, ExtBuilder, Origin, Runtime, System, ACA, ALICE, BOB,
+	CHARLIE, KAR, TREASURY,
+};
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	traits::{BadOrigin, BlakeTwo256},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+};
 
-#[test]
-fn airdrop_work() {
-	ExtBuilder::default().build().execute_with(|| {
-		System::set_block_number(1);
-		assert_noop!(Airdrop::airdrop(Origin::signed(BOB), ALICE, KAR, 10000), BadOrigin,);
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, KAR, 10000));
-		let airdrop_event = Event::airdrop(RawEvent::Airdrop(ALICE, KAR, 10000));
-		assert!(System::events().iter().any(|record| record.event == airdrop_event));
-		assert_eq!(Airdrop::airdrops(ALICE, KAR), 10000);
-	});
+fn eth_key(seed: &[u8]) -> secp256k1::SecretKey {
+	secp256k1::SecretKey::parse(&keccak_256(seed)).unwrap()
+}
+
+fn eth_claim_signature(secret: &secp256k1::SecretKey, drop_id: DropId, target: AccountId) -> EcdsaSignature {
+	module_evm_accounts::eth_sign(secret, &(drop_id, target).encode(), ETH_CLAIM_EXTRA)
+}
+
+fn leaf(index: u32, who: AccountId, amount: Balance) -> H256 {
+	BlakeTwo256::hash_of(&(index, who, amount))
+}
+
+/// Build the merkle tree of the leaves, returns the root and the proof of each
+/// leaf
+fn merkle_tree(leaves: Vec<H256>) -> (H256, Vec<Vec<H256>>) {
+	let mut proofs = vec![vec![]; leaves.len()];
+	let mut positions: Vec<usize> = (0..leaves.len()).collect();
+	let mut level = leaves;
+	while level.len() > 1 {
+		if level.len() % 2 == 1 {
+			level.push(*level.last().unwrap());
+		}
+		for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+			proof.push(level[*position ^ 1]);
+			*position /= 2;
+		}
+		level = level
+			.chunks(2)
+			.map(|pair| BlakeTwo256::hash_of(&(pair[0], pair[1])))
+			.collect();
+	}
+	(level[0], proofs)
 }
 
 #[test]
 fn update_airdrop_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		System::set_block_number(1);
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, ACA, 10000));
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, ACA, 10000));
-		assert_eq!(Airdrop::airdrops(ALICE, ACA), 20000);
-		assert_noop!(Airdrop::update_airdrop(Origin::signed(BOB), ALICE, ACA, 0), BadOrigin,);
-		assert_ok!(Airdrop::update_airdrop(Origin::root(), ALICE, ACA, 0));
-		let update_airdrop_event = Event::airdrop(RawEvent::UpdateAirdrop(ALICE, ACA, 0));
+		assert_eq!(Airdrop::airdrops(CHARLIE, ACA), 80);
+		assert_noop!(Airdrop::update_airdrop(Origin::signed(BOB), CHARLIE, ACA, 0), BadOrigin,);
+		assert_ok!(Airdrop::update_airdrop(Origin::root(), CHARLIE, ACA, 0));
+		let update_airdrop_event = Event::airdrop(RawEvent::UpdateAirdrop(CHARLIE, ACA, 0));
 		assert!(System::events()
 			.iter()
 			.any(|record| record.event == update_airdrop_event));
-		assert_eq!(Airdrop::airdrops(ALICE, ACA), 0);
+		assert_eq!(Airdrop::airdrops(CHARLIE, ACA), 0);
 	});
 }
 
@@ -43,3 +71,393 @@ fn genesis_config_work() {
 		assert_eq!(Airdrop::airdrops(CHARLIE, ACA), 80);
 	});
 }
+
+#[test]
+fn new_drop_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let root = H256::repeat_byte(1);
+		assert_noop!(
+			Airdrop::new_drop(Origin::signed(BOB), KAR, root, 600, None, None),
+			BadOrigin
+		);
+		assert_ok!(Airdrop::new_drop(Origin::root(), KAR, root, 600, None, None));
+		let new_drop_event = Event::airdrop(RawEvent::NewDrop(0, KAR, root));
+		assert!(System::events().iter().any(|record| record.event == new_drop_event));
+		assert_eq!(
+			Airdrop::drops(0),
+			Some(DropInfo {
+				currency_id: KAR,
+				merkle_root: root,
+				vesting: None,
+				total: 600,
+				claimed: 0,
+				expire_at: None,
+			})
+		);
+		assert_eq!(Airdrop::next_drop_id(), 1);
+	});
+}
+
+#[test]
+fn claim_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let (root, proofs) = merkle_tree(vec![leaf(0, ALICE, 100), leaf(1, BOB, 200), leaf(2, CHARLIE, 300)]);
+		let proof = |index: u32| MerkleProof {
+			index,
+			path: proofs[index as usize].clone(),
+		};
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 0, 100, proof(0)),
+			Error::<Runtime>::DropNotFound
+		);
+		assert_ok!(Airdrop::new_drop(Origin::root(), KAR, root, 600, None, None));
+
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 0, 101, proof(0)),
+			Error::<Runtime>::InvalidProof
+		);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(BOB), 0, 100, proof(0)),
+			Error::<Runtime>::InvalidProof
+		);
+		assert_ok!(Airdrop::claim(Origin::signed(ALICE), 0, 100, proof(0)));
+		let claimed_event = Event::airdrop(RawEvent::Claimed(0, ALICE, KAR, 100));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+		assert_eq!(Airdrop::airdrops(ALICE, KAR), 100);
+		assert_eq!(Airdrop::is_claimed(0, 0), true);
+		assert_eq!(Airdrop::is_claimed(0, 1), false);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 0, 100, proof(0)),
+			Error::<Runtime>::AlreadyClaimed
+		);
+
+		// the last node of the odd level is paired with itself
+		assert_ok!(Airdrop::claim(Origin::signed(CHARLIE), 0, 300, proof(2)));
+		assert_eq!(Airdrop::airdrops(CHARLIE, KAR), 450);
+
+		// the index out of the leaves is invalid
+		assert_noop!(
+			Airdrop::claim(
+				Origin::signed(BOB),
+				0,
+				200,
+				MerkleProof {
+					index: 5,
+					path: proofs[1].clone()
+				}
+			),
+			Error::<Runtime>::InvalidProof
+		);
+		assert_ok!(Airdrop::claim(Origin::signed(BOB), 0, 200, proof(1)));
+		assert_eq!(Airdrop::airdrops(BOB, KAR), 200);
+		assert_eq!(Airdrop::claimed_bitmap(0, 0), 0b111);
+		assert_eq!(Airdrop::drops(0).unwrap().claimed, 600);
+	});
+}
+
+#[test]
+fn claim_with_vesting_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let (root, proofs) = merkle_tree(vec![leaf(0, ALICE, 100), leaf(1, BOB, 200)]);
+		let vesting = VestingProfile {
+			cliff: 10,
+			duration: 100,
+		};
+		assert_noop!(
+			Airdrop::new_drop(Origin::root(), KAR, root, 300, Some(vesting.clone()), None),
+			Error::<Runtime>::VestingNotSupported
+		);
+		assert_ok!(Airdrop::new_drop(Origin::root(), ACA, root, 300, Some(vesting), None));
+
+		assert_ok!(Airdrop::claim(
+			Origin::signed(BOB),
+			0,
+			200,
+			MerkleProof {
+				index: 1,
+				path: proofs[1].clone()
+			}
+		));
+		let claimed_event = Event::airdrop(RawEvent::Claimed(0, BOB, ACA, 200));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+		// vested transfer from the module account instead of recorded
+		assert_eq!(vested_transfers(), vec![(Airdrop::account_id(), BOB, 200, 10, 100)]);
+		assert_eq!(Airdrop::airdrops(BOB, ACA), 0);
+
+		// the claim below the min vested transfer is transferred liquidly
+		assert_ok!(Airdrop::claim(
+			Origin::signed(ALICE),
+			0,
+			100,
+			MerkleProof {
+				index: 0,
+				path: proofs[0].clone()
+			}
+		));
+		assert_eq!(vested_transfers(), vec![(Airdrop::account_id(), BOB, 200, 10, 100)]);
+		assert_eq!(Balances::free_balance(ALICE), 100);
+		assert_eq!(Airdrop::airdrops(ALICE, ACA), 0);
+	});
+}
+
+#[test]
+fn listed_drop_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Airdrop::add_airdrop_batch(Origin::root(), 0, vec![(ALICE, 100)]),
+			Error::<Runtime>::DropNotFound
+		);
+		assert_noop!(
+			Airdrop::new_listed_drop(Origin::signed(BOB), KAR, None, None),
+			BadOrigin
+		);
+		assert_ok!(Airdrop::new_listed_drop(Origin::root(), KAR, None, None));
+		let new_drop_event = Event::airdrop(RawEvent::NewListedDrop(0, KAR));
+		assert!(System::events().iter().any(|record| record.event == new_drop_event));
+
+		assert_noop!(
+			Airdrop::add_airdrop_batch(Origin::root(), 0, vec![(ALICE, 100), (BOB, 200), (CHARLIE, 300)]),
+			Error::<Runtime>::TooLargeBatch
+		);
+		assert_noop!(
+			Airdrop::add_airdrop_batch(Origin::root(), 0, vec![(ALICE, 100), (ALICE, 200)]),
+			Error::<Runtime>::DuplicateAccount
+		);
+		assert_ok!(Airdrop::add_airdrop_batch(
+			Origin::root(),
+			0,
+			vec![(ALICE, 100), (BOB, 200)]
+		));
+		let batch_event = Event::airdrop(RawEvent::AirdropBatchAdded(0, 2));
+		assert!(System::events().iter().any(|record| record.event == batch_event));
+		// duplicate across the batches
+		assert_noop!(
+			Airdrop::add_airdrop_batch(Origin::root(), 0, vec![(CHARLIE, 300), (BOB, 200)]),
+			Error::<Runtime>::DuplicateAccount
+		);
+		assert_ok!(Airdrop::add_airdrop_batch(Origin::root(), 0, vec![(CHARLIE, 300)]));
+		assert_eq!(Airdrop::listed_drops(0).unwrap().count, 3);
+
+		assert_noop!(
+			Airdrop::claim_listed(Origin::signed(ALICE), 0),
+			Error::<Runtime>::DropNotFinalized
+		);
+		assert_ok!(Airdrop::finalize_drop(Origin::root(), 0));
+		let finalize_event = Event::airdrop(RawEvent::DropFinalized(0, 3));
+		assert!(System::events().iter().any(|record| record.event == finalize_event));
+		assert_noop!(
+			Airdrop::add_airdrop_batch(Origin::root(), 0, vec![(3, 100)]),
+			Error::<Runtime>::DropFinalized
+		);
+		assert_noop!(
+			Airdrop::finalize_drop(Origin::root(), 0),
+			Error::<Runtime>::DropFinalized
+		);
+
+		assert_eq!(Airdrop::airdrops(ALICE, KAR), 0);
+		assert_ok!(Airdrop::claim_listed(Origin::signed(ALICE), 0));
+		let claimed_event = Event::airdrop(RawEvent::Claimed(0, ALICE, KAR, 100));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+		assert_eq!(Airdrop::airdrops(ALICE, KAR), 100);
+		assert_eq!(Airdrop::drop_list(0, ALICE), None);
+		assert_noop!(
+			Airdrop::claim_listed(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NotInList
+		);
+	});
+}
+
+#[test]
+fn claim_exceed_total_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (root, proofs) = merkle_tree(vec![leaf(0, ALICE, 100), leaf(1, BOB, 200)]);
+		assert_ok!(Airdrop::new_drop(Origin::root(), KAR, root, 250, None, None));
+		assert_ok!(Airdrop::claim(
+			Origin::signed(ALICE),
+			0,
+			100,
+			MerkleProof {
+				index: 0,
+				path: proofs[0].clone()
+			}
+		));
+		assert_noop!(
+			Airdrop::claim(
+				Origin::signed(BOB),
+				0,
+				200,
+				MerkleProof {
+					index: 1,
+					path: proofs[1].clone()
+				}
+			),
+			Error::<Runtime>::ExceedTotal
+		);
+	});
+}
+
+#[test]
+fn sweep_expired_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let (root, proofs) = merkle_tree(vec![leaf(0, ALICE, 100), leaf(1, BOB, 200)]);
+		let vesting = VestingProfile {
+			cliff: 10,
+			duration: 100,
+		};
+		assert_noop!(
+			Airdrop::sweep_expired(Origin::signed(ALICE), 0),
+			Error::<Runtime>::DropNotFound
+		);
+		assert_ok!(Airdrop::new_drop(
+			Origin::root(),
+			ACA,
+			root,
+			300,
+			Some(vesting),
+			Some(10)
+		));
+		assert_ok!(Airdrop::new_listed_drop(Origin::root(), KAR, None, None));
+		assert_ok!(Airdrop::add_airdrop_batch(Origin::root(), 1, vec![(ALICE, 100)]));
+		assert_ok!(Airdrop::finalize_drop(Origin::root(), 1));
+
+		assert_ok!(Airdrop::claim(
+			Origin::signed(ALICE),
+			0,
+			100,
+			MerkleProof {
+				index: 0,
+				path: proofs[0].clone()
+			}
+		));
+		System::set_block_number(10);
+		assert_noop!(
+			Airdrop::sweep_expired(Origin::signed(CHARLIE), 0),
+			Error::<Runtime>::DropNotExpired
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Airdrop::claim(
+				Origin::signed(BOB),
+				0,
+				200,
+				MerkleProof {
+					index: 1,
+					path: proofs[1].clone()
+				}
+			),
+			Error::<Runtime>::DropExpired
+		);
+		// the unclaimed airdrop is returned to the treasury
+		assert_ok!(Airdrop::sweep_expired(Origin::signed(CHARLIE), 0));
+		let sweep_event = Event::airdrop(RawEvent::DropSwept(0, 200));
+		assert!(System::events().iter().any(|record| record.event == sweep_event));
+		assert_eq!(Balances::free_balance(TREASURY), 200);
+		assert_eq!(Balances::free_balance(Airdrop::account_id()), 700);
+		assert_eq!(Airdrop::drops(0), None);
+		assert_eq!(Airdrop::claimed_bitmap(0, 0), 0);
+
+		// the drop never expires can't be swept
+		assert_noop!(
+			Airdrop::sweep_expired(Origin::signed(CHARLIE), 1),
+			Error::<Runtime>::DropNotExpired
+		);
+
+		// the claim records are cleared in batches
+		assert_ok!(Airdrop::new_listed_drop(Origin::root(), KAR, None, Some(20)));
+		assert_ok!(Airdrop::add_airdrop_batch(
+			Origin::root(),
+			2,
+			vec![(ALICE, 100), (BOB, 100)]
+		));
+		assert_ok!(Airdrop::add_airdrop_batch(Origin::root(), 2, vec![(CHARLIE, 100)]));
+		assert_ok!(Airdrop::finalize_drop(Origin::root(), 2));
+		System::set_block_number(21);
+		assert_ok!(Airdrop::sweep_expired(Origin::signed(CHARLIE), 2));
+		let partially_swept_event = Event::airdrop(RawEvent::DropPartiallySwept(2, 2));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == partially_swept_event));
+		assert_eq!(DropList::<Runtime>::iter_prefix(2).count(), 1);
+		assert!(Airdrop::listed_drops(2).is_some());
+
+		assert_ok!(Airdrop::sweep_expired(Origin::signed(CHARLIE), 2));
+		let sweep_event = Event::airdrop(RawEvent::DropSwept(2, 300));
+		assert!(System::events().iter().any(|record| record.event == sweep_event));
+		assert_eq!(DropList::<Runtime>::iter_prefix(2).count(), 0);
+		assert_eq!(Airdrop::listed_drops(2), None);
+	});
+}
+
+#[test]
+fn claim_by_eth_signature_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let alice_eth = eth_key(b"Alice");
+		let bob_eth = eth_key(b"Bob");
+		let alice_address = module_evm_accounts::eth_address(&alice_eth);
+		assert_ok!(Airdrop::new_listed_drop(Origin::root(), KAR, None, None));
+		assert_noop!(
+			Airdrop::add_eth_airdrop_batch(Origin::root(), 0, vec![(alice_address, 100), (alice_address, 200)]),
+			Error::<Runtime>::DuplicateAccount
+		);
+		assert_ok!(Airdrop::add_eth_airdrop_batch(
+			Origin::root(),
+			0,
+			vec![(alice_address, 100)]
+		));
+		assert_eq!(Airdrop::drop_eth_list(0, alice_address), Some(100));
+
+		let signature = eth_claim_signature(&alice_eth, 0, CHARLIE);
+		let call = Call::claim_by_eth_signature(0, CHARLIE, signature.clone());
+		// not claimable before finalized
+		assert_eq!(
+			Airdrop::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Call.into()
+		);
+		assert_noop!(
+			Airdrop::claim_by_eth_signature(Origin::none(), 0, CHARLIE, signature.clone()),
+			Error::<Runtime>::DropNotFinalized
+		);
+		assert_ok!(Airdrop::finalize_drop(Origin::root(), 0));
+		assert!(Airdrop::validate_unsigned(TransactionSource::External, &call).is_ok());
+
+		// the signature is bound to the target account
+		assert_noop!(
+			Airdrop::claim_by_eth_signature(Origin::none(), 0, BOB, signature.clone()),
+			Error::<Runtime>::NotInList
+		);
+		// the address not in the list
+		assert_eq!(
+			Airdrop::validate_unsigned(
+				TransactionSource::External,
+				&Call::claim_by_eth_signature(0, CHARLIE, eth_claim_signature(&bob_eth, 0, CHARLIE))
+			),
+			InvalidTransaction::Stale.into()
+		);
+		assert_noop!(
+			Airdrop::claim_by_eth_signature(Origin::signed(ALICE), 0, CHARLIE, signature.clone()),
+			BadOrigin
+		);
+
+		assert_ok!(Airdrop::claim_by_eth_signature(
+			Origin::none(),
+			0,
+			CHARLIE,
+			signature.clone()
+		));
+		let claimed_event = Event::airdrop(RawEvent::Claimed(0, CHARLIE, KAR, 100));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+		assert_eq!(Airdrop::airdrops(CHARLIE, KAR), 250);
+		assert_eq!(Airdrop::drop_eth_list(0, alice_address), None);
+		assert_eq!(
+			Airdrop::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Stale.into()
+		);
+	});
+}
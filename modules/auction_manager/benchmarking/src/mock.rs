@@ -4,7 +4,7 @@
 
 use super::*;
 use frame_support::{
-	impl_outer_dispatch, impl_outer_origin, ord_parameter_types, parameter_types, traits::GenesisBuild,
+	impl_outer_dispatch, impl_outer_origin, ord_parameter_types, parameter_types, traits::GenesisBuild, weights::Weight,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use orml_oracle::DefaultCombineData;
@@ -16,7 +16,7 @@ use sp_runtime::{
 	ModuleId,
 };
 use sp_std::vec;
-use support::{ExchangeRate, ExchangeRateProvider, Price, Rate};
+use support::{ExchangeRate, ExchangeRateProvider, Price, Rate, Ratio};
 
 impl_outer_dispatch! {
 	pub enum Call for Runtime where origin: Origin {
@@ -127,6 +127,13 @@ parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(1, 20);
 	pub const AuctionTimeToClose: u64 = 100;
 	pub const AuctionDurationSoftCap: u64 = 2000;
+	pub const AntiSnipingWindow: u64 = 10;
+	pub const AntiSnipingExtension: u64 = 20;
+	pub const MaxAntiSnipingExtension: u64 = 50;
+	pub const DutchAuctionDuration: u64 = 200;
+	pub const MaxPartialBids: u32 = 3;
+	pub MinPartialBidProportion: Ratio = Ratio::saturating_from_rational(1, 10);
+	pub const MaxSettlementWeight: Weight = 1_000_000_000_000;
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const UnsignedPriority: u64 = 1 << 20;
 }
@@ -138,6 +145,12 @@ impl auction_manager::Config for Runtime {
 	type MinimumIncrementSize = MinimumIncrementSize;
 	type AuctionTimeToClose = AuctionTimeToClose;
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
+	type AntiSnipingWindow = AntiSnipingWindow;
+	type AntiSnipingExtension = AntiSnipingExtension;
+	type MaxAntiSnipingExtension = MaxAntiSnipingExtension;
+	type DutchAuctionDuration = DutchAuctionDuration;
+	type MaxPartialBids = MaxPartialBids;
+	type MinPartialBidProportion = MinPartialBidProportion;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
@@ -145,6 +158,9 @@ impl auction_manager::Config for Runtime {
 	type PriceSource = prices::Module<Runtime>;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdownModule;
+	type MaxSettlementWeight = MaxSettlementWeight;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type CancelOrigin = EnsureSignedBy<One, AccountId>;
 	type WeightInfo = ();
 }
 pub type AuctionManagerModule = auction_manager::Module<Runtime>;
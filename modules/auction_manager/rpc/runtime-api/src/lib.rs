@@ -0,0 +1,21 @@
+//! Runtime API definition for auction manager module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionManagerApi<AuctionId, ActiveAuctionInfo> where
+		AuctionId: Codec,
+		ActiveAuctionInfo: Codec,
+	{
+		/// The active auctions ordered by id, at most `limit` auctions
+		/// starting from the auction id `start`, with the lot, the current
+		/// bid, the minimum next bid and the end block of each auction.
+		fn active_auctions(start: AuctionId, limit: u32) -> Vec<ActiveAuctionInfo>;
+	}
+}
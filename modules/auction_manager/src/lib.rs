@@ -10,6 +10,9 @@
 //!     burn by auction
 //!   - `debit auction`: inflation some native token to sell for getting stable
 //!     coin to eliminate excessive bad debit by auction
+//!
+//! The collateral auctions of a collateral type can be configured to be
+//! dutch auctions, and accept partial bids besides the whole lot bids.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -19,7 +22,7 @@ use frame_system::{
 	offchain::{SendTransactionTypes, SubmitTransaction},
 	pallet_prelude::*,
 };
-use orml_traits::{Auction, AuctionHandler, Change, MultiCurrency, OnNewBidResult};
+use orml_traits::{Auction, AuctionHandler, AuctionInfo, Change, MultiCurrency, OnNewBidResult};
 use orml_utilities::{IterableStorageMapExtended, OffchainErr};
 use primitives::{AuctionId, Balance, CurrencyId};
 use sp_runtime::{
@@ -28,14 +31,16 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{BlakeTwo256, CheckedDiv, Hash, Saturating, Zero},
+	traits::{BlakeTwo256, CheckedDiv, Hash, One, Saturating, UniqueSaturatedInto, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
 	DispatchError, DispatchResult, FixedPointNumber, RandomNumberGenerator, RuntimeDebug,
 };
-use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Rate};
+use sp_std::{prelude::*, vec};
+use support::{
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, Price, PriceProvider, Rate, Ratio,
+};
 
 mod default_weight;
 mod mock;
@@ -47,6 +52,13 @@ pub trait WeightInfo {
 	fn cancel_surplus_auction() -> Weight;
 	fn cancel_debit_auction() -> Weight;
 	fn cancel_collateral_auction() -> Weight;
+	fn set_dutch_auction_params() -> Weight;
+	fn take_collateral_auction() -> Weight;
+	fn set_minimum_increment() -> Weight;
+	fn settle_auction() -> Weight;
+	fn relist() -> Weight;
+	fn set_no_bid_sale_max_slippage() -> Weight;
+	fn partial_bid() -> Weight;
 }
 
 pub const OFFCHAIN_WORKER_DATA: &[u8] = b"acala/auction-manager/data/";
@@ -111,6 +123,175 @@ impl<AccountId, BlockNumber> CollateralAuctionItem<AccountId, BlockNumber> {
 			self.amount
 		}
 	}
+
+	/// Return the collateral amount and the payment amount of each partial
+	/// bid to be filled, and whether the target is reached by them. The
+	/// partial bids are filled in the order of their price per unit of
+	/// collateral until the lot is sold out, and all the filled bids pay the
+	/// uniform clearing price, which is the lowest price of them. The fills
+	/// are scaled down pro-rata if their payment exceeds the target.
+	fn partial_fills(&self, partial_bids: &[(AccountId, PartialBid)]) -> (Vec<(Balance, Balance)>, bool) {
+		// the earlier partial bid goes first at the same price
+		let mut indexes: Vec<usize> = (0..partial_bids.len()).collect();
+		indexes.sort_by(|a, b| partial_bids[*b].1.unit_price().cmp(&partial_bids[*a].1.unit_price()));
+
+		let mut amounts = vec![Balance::zero(); partial_bids.len()];
+		let mut remaining_amount = self.amount;
+		let mut clearing_price = Price::zero();
+		for index in indexes {
+			if remaining_amount.is_zero() {
+				break;
+			}
+			let partial_bid = &partial_bids[index].1;
+			amounts[index] = sp_std::cmp::min(partial_bid.amount, remaining_amount);
+			remaining_amount = remaining_amount.saturating_sub(amounts[index]);
+			clearing_price = partial_bid.unit_price();
+		}
+
+		let total_payment = clearing_price.saturating_mul_int(self.amount.saturating_sub(remaining_amount));
+		let target_reached = self.in_reverse_stage(total_payment);
+		let fill_ratio = if target_reached {
+			Ratio::checked_from_rational(self.target, total_payment).unwrap_or_else(Ratio::zero)
+		} else {
+			Ratio::one()
+		};
+
+		let fills = partial_bids
+			.iter()
+			.zip(amounts)
+			.map(|((_, partial_bid), amount)| {
+				let amount = fill_ratio.saturating_mul_int(amount);
+				let payment = sp_std::cmp::min(clearing_price.saturating_mul_int(amount), partial_bid.payment);
+				(amount, payment)
+			})
+			.collect();
+		(fills, target_reached)
+	}
+}
+
+/// Partial bid for a part of the lot of a collateral auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct PartialBid {
+	/// The collateral amount to buy
+	#[codec(compact)]
+	pub amount: Balance,
+	/// The stable currency amount to pay for the collateral
+	#[codec(compact)]
+	pub payment: Balance,
+}
+
+impl PartialBid {
+	/// Return the price per unit of collateral of the partial bid
+	fn unit_price(&self) -> Price {
+		Price::checked_from_rational(self.payment, self.amount).unwrap_or_else(Price::zero)
+	}
+}
+
+/// New parameters of the re-listed auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub enum RelistParams {
+	/// Re-list the collateral auction with the new target
+	Collateral {
+		#[codec(compact)]
+		target: Balance,
+	},
+	/// Re-list the debit auction with the new initial amount of native
+	/// currency for sale
+	Debit {
+		#[codec(compact)]
+		initial_amount: Balance,
+	},
+	/// Re-list the surplus auction with the same amount
+	Surplus,
+}
+
+/// Type of the auctions
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum AuctionType {
+	Collateral,
+	Debit,
+	Surplus,
+}
+
+/// Minimum increment of each bid of an auction type compared to the previous
+/// one
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct MinimumIncrement {
+	/// The minimum absolute increment
+	#[codec(compact)]
+	pub absolute: Balance,
+	/// The minimum increment rate, which will be doubled when the auction
+	/// reaches the soft cap
+	pub rate: Rate,
+}
+
+/// Parameters of the dutch collateral auctions of a collateral type
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct DutchAuctionParam {
+	/// The ratio of the start price to the oracle price
+	pub start_price_ratio: Ratio,
+	/// The decrement of the price ratio per block
+	pub decay_per_block: Ratio,
+	/// The ratio of the floor price to the oracle price, the price will not
+	/// decay below it
+	pub floor_price_ratio: Ratio,
+}
+
+/// Information of an dutch collateral auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct DutchCollateralAuctionItem<AccountId, BlockNumber> {
+	/// Refund recipient for may receive refund
+	refund_recipient: AccountId,
+	/// Collateral type for sale
+	currency_id: CurrencyId,
+	/// Collateral amount for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Target sales amount of this auction
+	/// if zero, all the collateral will be sold, otherwise, only the collateral
+	/// worth the target amount will be sold and the rest will be refunded
+	#[codec(compact)]
+	target: Balance,
+	/// Auction start time
+	start_time: BlockNumber,
+	/// The oracle price of the collateral in stable currency at the start
+	oracle_price: Price,
+	/// Price parameters of this auction
+	param: DutchAuctionParam,
+}
+
+impl<AccountId, BlockNumber> DutchCollateralAuctionItem<AccountId, BlockNumber> {
+	/// Return the price after the auction has lasted `elapsed` blocks
+	fn price_after(&self, elapsed: u128) -> Price {
+		let decay = self
+			.param
+			.decay_per_block
+			.saturating_mul(Ratio::saturating_from_integer(elapsed));
+		let ratio = sp_std::cmp::max(
+			self.param.start_price_ratio.saturating_sub(decay),
+			self.param.floor_price_ratio,
+		);
+		self.oracle_price.saturating_mul(ratio)
+	}
+
+	/// Return the payment amount and the collateral amount to be sold at
+	/// specific price
+	fn deal_amounts(&self, price: Price) -> (Balance, Balance) {
+		let value = price.saturating_mul_int(self.amount);
+		if !self.target.is_zero() && value > self.target {
+			let collateral_amount = Rate::checked_from_rational(self.target, value)
+				.and_then(|n| n.checked_mul_int(self.amount))
+				.unwrap_or(self.amount);
+			(self.target, collateral_amount)
+		} else {
+			(value, self.amount)
+		}
+	}
 }
 
 /// Information of an debit auction
@@ -155,6 +336,64 @@ pub struct SurplusAuctionItem<BlockNumber> {
 	start_time: BlockNumber,
 }
 
+/// The lot of an active auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub enum AuctionLot {
+	/// Collateral auction selling `amount` of `currency_id` for the `target`
+	/// amount of stable currency
+	Collateral {
+		currency_id: CurrencyId,
+		#[codec(compact)]
+		amount: Balance,
+		#[codec(compact)]
+		target: Balance,
+	},
+	/// Debit auction selling `amount` of native currency for the `fix`
+	/// amount of stable currency
+	Debit {
+		#[codec(compact)]
+		amount: Balance,
+		#[codec(compact)]
+		fix: Balance,
+	},
+	/// Surplus auction selling `amount` of stable currency for native
+	/// currency
+	Surplus {
+		#[codec(compact)]
+		amount: Balance,
+	},
+	/// Dutch collateral auction selling `amount` of `currency_id` at the
+	/// current `price`
+	DutchCollateral {
+		currency_id: CurrencyId,
+		#[codec(compact)]
+		amount: Balance,
+		#[codec(compact)]
+		target: Balance,
+		price: Price,
+	},
+}
+
+/// Information of an active auction for the keepers
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct ActiveAuctionInfo<AccountId, BlockNumber> {
+	/// Auction id
+	pub id: AuctionId,
+	/// Type and lot of the auction
+	pub lot: AuctionLot,
+	/// Current bidder and bid price, always `None` for dutch collateral
+	/// auction
+	pub bid: Option<(AccountId, Balance)>,
+	/// The minimum price of the next bid to be accepted, or the payment to
+	/// take the dutch collateral auction at the current block
+	#[codec(compact)]
+	pub minimum_next_bid: Balance,
+	/// The end block of the auction, `None` if there's no end yet
+	pub end: Option<BlockNumber>,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -164,7 +403,9 @@ pub mod module {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		#[pallet::constant]
-		/// The minimum increment size of each bid compared to the previous one
+		/// The default minimum increment size of each bid compared to the
+		/// previous one, used by the auction types without minimum increment
+		/// configured
 		type MinimumIncrementSize: Get<Rate>;
 
 		#[pallet::constant]
@@ -176,6 +417,36 @@ pub mod module {
 		/// the auction to end more faster
 		type AuctionDurationSoftCap: Get<Self::BlockNumber>;
 
+		#[pallet::constant]
+		/// The bids received within this period before the auction end will
+		/// extend the auction end
+		type AntiSnipingWindow: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The extended time for the auction to end after each bid within the
+		/// anti-sniping window
+		type AntiSnipingExtension: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The maximum total extended time of an auction by the anti-sniping
+		/// extensions
+		type MaxAntiSnipingExtension: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The duration of the dutch collateral auctions, the collateral of
+		/// the expired dutch auction is sold into DEX like the no-bid auction,
+		/// or re-listed as an english auction
+		type DutchAuctionDuration: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The maximum number of partial bids of a collateral auction
+		type MaxPartialBids: Get<u32>;
+
+		#[pallet::constant]
+		/// The minimum proportion of the lot of the collateral auction a
+		/// partial bid buys
+		type MinPartialBidProportion: Get<Ratio>;
+
 		#[pallet::constant]
 		/// The stable currency id
 		type GetStableCurrencyId: Get<CurrencyId>;
@@ -209,6 +480,17 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// The weight budget per block for settling the ended auctions.
+		#[pallet::constant]
+		type MaxSettlementWeight: Get<Weight>;
+
+		/// The origin which may update the dutch auction parameters and the
+		/// minimum increments
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may cancel or re-list the in-flight auctions
+		type CancelOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -227,6 +509,15 @@ pub mod module {
 		InvalidBidPrice,
 		/// Invalid input amount
 		InvalidAmount,
+		/// The dutch auction parameters are invalid
+		InvalidDutchAuctionParam,
+		/// The system has already been shutdown
+		AlreadyShutdown,
+		/// The auction has ended and is waiting for settlement
+		AuctionInSettlement,
+		/// The collateral auction has too many partial bids, and none has
+		/// lower price per unit to be evicted
+		TooManyPartialBids,
 	}
 
 	#[pallet::event]
@@ -254,6 +545,39 @@ pub mod module {
 		/// Dex take collateral auction. \[auction_id, collateral_type,
 		/// collateral_amount, turnover\]
 		DEXTakeCollateralAuction(AuctionId, CurrencyId, Balance, Balance),
+		/// Dutch collateral auction created. \[auction_id, collateral_type,
+		/// collateral_amount, target_bid_price, start_price\]
+		NewDutchCollateralAuction(AuctionId, CurrencyId, Balance, Balance, Price),
+		/// The dutch auction parameters updated. \[collateral_type,
+		/// new_params\]
+		DutchAuctionParamsUpdated(CurrencyId, Option<DutchAuctionParam>),
+		/// The minimum increment of an auction type updated. \[auction_type,
+		/// new_minimum_increment\]
+		MinimumIncrementUpdated(AuctionType, Option<MinimumIncrement>),
+		/// Auction end extended by the bid within the anti-sniping window.
+		/// \[auction_id, extension, new_end\]
+		AuctionEndExtended(AuctionId, T::BlockNumber, T::BlockNumber),
+		/// Auction cancelled and re-listed as a new auction. \[auction_id\]
+		AuctionRelisted(AuctionId),
+		/// The max slippage of selling the collateral of no-bid auctions into
+		/// DEX updated. \[new_max_slippage\]
+		NoBidSaleMaxSlippageUpdated(Option<Ratio>),
+		/// The collateral of the no-bid auction failed to be sold into DEX
+		/// within the max slippage and is parked in CDP treasury.
+		/// \[auction_id, collateral_type, collateral_amount\]
+		NoBidCollateralParked(AuctionId, CurrencyId, Balance),
+		/// Partial bid placed on the collateral auction. \[auction_id,
+		/// bidder, collateral_amount, payment_amount\]
+		PartialBidPlaced(AuctionId, T::AccountId, Balance, Balance),
+		/// Partial bid of the collateral auction evicted and refunded by a
+		/// partial bid with better price. \[auction_id, bidder,
+		/// payment_amount\]
+		PartialBidEvicted(AuctionId, T::AccountId, Balance),
+		/// Partial bid of the collateral auction dealt. \[auction_id,
+		/// collateral_type, collateral_amount, bidder, payment_amount\]
+		PartialBidDealt(AuctionId, CurrencyId, Balance, T::AccountId, Balance),
+		/// Dutch collateral auction expired without taker. \[auction_id\]
+		DutchCollateralAuctionExpired(AuctionId),
 	}
 
 	/// Mapping from auction id to collateral auction info
@@ -262,6 +586,63 @@ pub mod module {
 	pub type CollateralAuctions<T: Config> =
 		StorageMap<_, Twox64Concat, AuctionId, CollateralAuctionItem<T::AccountId, T::BlockNumber>, OptionQuery>;
 
+	/// Mapping from auction id to dutch collateral auction info
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_collateral_auctions)]
+	pub type DutchCollateralAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// The dutch auction parameters of collateral types, the collateral
+	/// auctions of the collateral types without parameters are english
+	/// auctions
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_auction_params)]
+	pub type DutchAuctionParams<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, DutchAuctionParam, OptionQuery>;
+
+	/// The minimum increments of each bid of auction types, the auction types
+	/// without minimum increment use `MinimumIncrementSize`
+	#[pallet::storage]
+	#[pallet::getter(fn minimum_increments)]
+	pub type MinimumIncrements<T: Config> = StorageMap<_, Twox64Concat, AuctionType, MinimumIncrement, OptionQuery>;
+
+	/// The max slippage against the oracle value of selling the collateral of
+	/// the collateral auctions ended without any bid into DEX, the collateral
+	/// is kept in CDP treasury without selling if `None`
+	#[pallet::storage]
+	#[pallet::getter(fn no_bid_sale_max_slippage)]
+	pub type NoBidSaleMaxSlippage<T: Config> = StorageValue<_, Ratio, OptionQuery>;
+
+	/// Mapping from the ended auction id to its winner waiting for
+	/// settlement, settled in the following blocks as many as
+	/// `MaxSettlementWeight` allows per block
+	#[pallet::storage]
+	#[pallet::getter(fn pending_settlements)]
+	pub type PendingSettlements<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, Option<(T::AccountId, Balance)>, OptionQuery>;
+
+	/// Mapping from the position in the settlement queue to the ended
+	/// auction id, the auctions are settled in the order they ended
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_queue)]
+	pub type SettlementQueue<T: Config> = StorageMap<_, Twox64Concat, u32, AuctionId, OptionQuery>;
+
+	/// The head and the tail positions of the settlement queue
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_queue_range)]
+	pub type SettlementQueueRange<T: Config> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	/// Mapping from collateral auction id to its partial bids
+	#[pallet::storage]
+	#[pallet::getter(fn partial_bids)]
+	pub type PartialBids<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, Vec<(T::AccountId, PartialBid)>, ValueQuery>;
+
+	/// Record of the total anti-sniping extension of active auctions, bounded
+	/// by `MaxAntiSnipingExtension`
+	#[pallet::storage]
+	#[pallet::getter(fn anti_sniping_extensions)]
+	pub type AntiSnipingExtensions<T: Config> = StorageMap<_, Twox64Concat, AuctionId, T::BlockNumber, ValueQuery>;
+
 	/// Mapping from auction id to debit auction info
 	#[pallet::storage]
 	#[pallet::getter(fn debit_auctions)]
@@ -300,6 +681,12 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Settle the ended auctions which are not settled within the weight
+		/// budget of previous blocks.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			Self::settle_pending_auctions(T::MaxSettlementWeight::get())
+		}
+
 		/// Start offchain worker in order to submit unsigned tx to cancel
 		/// active auction after system shutdown.
 		fn offchain_worker(now: T::BlockNumber) {
@@ -336,6 +723,302 @@ pub mod module {
 			Self::deposit_event(Event::CancelAuction(id));
 			Ok(().into())
 		}
+
+		/// Update the dutch auction parameters of the collateral type, the new
+		/// collateral auctions will be english auctions if `None`. The price
+		/// of a dutch auction starts above the oracle price and decays per
+		/// block until someone takes the collateral at the current price. The
+		/// dutch auction expires after `DutchAuctionDuration`, then its
+		/// collateral is sold into DEX within the no-bid max slippage, or
+		/// re-listed as an english auction.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `params`: the new dutch auction parameters.
+		#[pallet::weight(T::WeightInfo::set_dutch_auction_params())]
+		#[transactional]
+		pub fn set_dutch_auction_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: Option<DutchAuctionParam>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(param) = &params {
+				ensure!(
+					param.start_price_ratio >= param.floor_price_ratio && !param.decay_per_block.is_zero(),
+					Error::<T>::InvalidDutchAuctionParam
+				);
+			}
+			DutchAuctionParams::<T>::mutate_exists(currency_id, |maybe_params| *maybe_params = params.clone());
+			Self::deposit_event(Event::DutchAuctionParamsUpdated(currency_id, params));
+			Ok(().into())
+		}
+
+		/// Take all the collateral of the dutch collateral auction at the
+		/// current price, or the part worth the target if the target is
+		/// reached.
+		///
+		/// The dispatch origin of this call must be `Signed` by the taker.
+		///
+		/// - `id`: the dutch collateral auction id.
+		#[pallet::weight(T::WeightInfo::take_collateral_auction())]
+		#[transactional]
+		pub fn take_collateral_auction(origin: OriginFor<T>, id: AuctionId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(!Self::in_settlement(id), Error::<T>::AuctionInSettlement);
+			let dutch_auction = Self::dutch_collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+
+			let (payment_amount, collateral_amount) =
+				dutch_auction.deal_amounts(Self::current_dutch_auction_price(&dutch_auction));
+			ensure!(!payment_amount.is_zero(), Error::<T>::InvalidBidPrice);
+
+			// transfer payment from taker to CDP treasury and collateral from CDP
+			// treasury to taker
+			T::CDPTreasury::deposit_surplus(&who, payment_amount)?;
+			T::CDPTreasury::withdraw_collateral(&who, dutch_auction.currency_id, collateral_amount)?;
+
+			// refund the remain collateral to recipient
+			let refund_collateral_amount = dutch_auction.amount.saturating_sub(collateral_amount);
+			if !refund_collateral_amount.is_zero() {
+				T::CDPTreasury::withdraw_collateral(
+					&dutch_auction.refund_recipient,
+					dutch_auction.currency_id,
+					refund_collateral_amount,
+				)?;
+			}
+
+			// decrease account ref of refund recipient
+			frame_system::Module::<T>::dec_ref(&dutch_auction.refund_recipient);
+
+			// update auction records
+			TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+				*balance = balance.saturating_sub(dutch_auction.amount)
+			});
+			TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(dutch_auction.target));
+			DutchCollateralAuctions::<T>::remove(id);
+			T::Auction::remove_auction(id);
+
+			Self::deposit_event(Event::CollateralAuctionDealt(
+				id,
+				dutch_auction.currency_id,
+				collateral_amount,
+				who,
+				payment_amount,
+			));
+			Ok(().into())
+		}
+
+		/// Update the minimum increment of each bid of the auction type, the
+		/// auction type will use `MinimumIncrementSize` if `None`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `auction_type`: auction type.
+		/// - `minimum_increment`: the new minimum increment.
+		#[pallet::weight(T::WeightInfo::set_minimum_increment())]
+		#[transactional]
+		pub fn set_minimum_increment(
+			origin: OriginFor<T>,
+			auction_type: AuctionType,
+			minimum_increment: Option<MinimumIncrement>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MinimumIncrements::<T>::mutate_exists(auction_type, |maybe_minimum_increment| {
+				*maybe_minimum_increment = minimum_increment.clone()
+			});
+			Self::deposit_event(Event::MinimumIncrementUpdated(auction_type, minimum_increment));
+			Ok(().into())
+		}
+
+		/// Update the max slippage of selling the collateral of the
+		/// collateral auctions ended without any bid into DEX, the collateral
+		/// will not be sold if `None`. The collateral is parked in CDP treasury
+		/// if the received stable currency is below the oracle value of the
+		/// collateral deducted by the max slippage, or the oracle price is
+		/// unavailable.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `max_slippage`: the new max slippage.
+		#[pallet::weight(T::WeightInfo::set_no_bid_sale_max_slippage())]
+		#[transactional]
+		pub fn set_no_bid_sale_max_slippage(
+			origin: OriginFor<T>,
+			max_slippage: Option<Ratio>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			NoBidSaleMaxSlippage::<T>::set(max_slippage);
+			Self::deposit_event(Event::NoBidSaleMaxSlippageUpdated(max_slippage));
+			Ok(().into())
+		}
+
+		/// Cancel the in-flight auction and refund the current bidder.
+		///
+		/// The dispatch origin of this call must be `CancelOrigin`.
+		///
+		/// - `id`: the auction id.
+		#[pallet::weight(T::WeightInfo::cancel_collateral_auction())]
+		#[transactional]
+		pub fn force_cancel(origin: OriginFor<T>, id: AuctionId) -> DispatchResultWithPostInfo {
+			T::CancelOrigin::ensure_origin(origin)?;
+			<Self as AuctionManager<T::AccountId>>::cancel_auction(id)?;
+			Self::deposit_event(Event::CancelAuction(id));
+			Ok(().into())
+		}
+
+		/// Cancel the in-flight auction, refund the current bidder and re-list
+		/// the lot as a new auction with new parameters, e.g. to recover from
+		/// mispriced auctions. The dutch collateral auction is re-listed with
+		/// the current oracle price.
+		///
+		/// The dispatch origin of this call must be `CancelOrigin`.
+		///
+		/// - `id`: the auction id.
+		/// - `params`: the new parameters of the re-listed auction.
+		#[pallet::weight(T::WeightInfo::relist())]
+		#[transactional]
+		pub fn relist(origin: OriginFor<T>, id: AuctionId, params: RelistParams) -> DispatchResultWithPostInfo {
+			T::CancelOrigin::ensure_origin(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(!Self::in_settlement(id), Error::<T>::AuctionInSettlement);
+
+			match params {
+				RelistParams::Collateral { target } => {
+					if let Some(collateral_auction) = Self::collateral_auctions(id) {
+						Self::relist_collateral_auction(id, collateral_auction, target)?;
+					} else {
+						let dutch_auction = Self::dutch_collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+						Self::relist_dutch_collateral_auction(dutch_auction, target)?;
+					}
+				}
+				RelistParams::Debit { initial_amount } => {
+					let debit_auction = Self::debit_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+					let fix_debit = debit_auction.fix;
+					Self::cancel_debit_auction(id, debit_auction)?;
+					<Self as AuctionManager<T::AccountId>>::new_debit_auction(initial_amount, fix_debit)?;
+				}
+				RelistParams::Surplus => {
+					let surplus_auction = Self::surplus_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+					let amount = surplus_auction.amount;
+					Self::cancel_surplus_auction(id, surplus_auction)?;
+					<Self as AuctionManager<T::AccountId>>::new_surplus_auction(amount)?;
+				}
+			}
+
+			CollateralAuctions::<T>::remove(id);
+			DutchCollateralAuctions::<T>::remove(id);
+			DebitAuctions::<T>::remove(id);
+			SurplusAuctions::<T>::remove(id);
+			AntiSnipingExtensions::<T>::remove(id);
+			T::Auction::remove_auction(id);
+
+			Self::deposit_event(Event::AuctionRelisted(id));
+			Ok(().into())
+		}
+
+		/// Bid for a part of the lot of the collateral auction, or replace
+		/// the previous partial bid of the bidder. The amount must not be
+		/// lower than `MinPartialBidProportion` of the lot, and the price per
+		/// unit of collateral must not be lower than the whole lot bid. If
+		/// there're already `MaxPartialBids` partial bids, the partial bid with
+		/// the lowest price per unit is evicted and refunded if the new
+		/// partial bid has better price. The payment is charged now and the
+		/// unfilled part is refunded when the auction ends. When the auction ends, the partial bids are filled from the
+		/// best price per unit of collateral at the uniform clearing price,
+		/// and deal if their aggregate payment is higher than the payment of
+		/// the whole lot bid.
+		///
+		/// The dispatch origin of this call must be `Signed` by the bidder.
+		///
+		/// - `id`: the collateral auction id.
+		/// - `amount`: the collateral amount to buy.
+		/// - `payment`: the stable currency amount to pay for the collateral.
+		#[pallet::weight(T::WeightInfo::partial_bid())]
+		#[transactional]
+		pub fn partial_bid(
+			origin: OriginFor<T>,
+			id: AuctionId,
+			#[pallet::compact] amount: Balance,
+			#[pallet::compact] payment: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(!Self::in_settlement(id), Error::<T>::AuctionInSettlement);
+			let collateral_auction = Self::collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+			let auction_info = T::Auction::auction_info(id).ok_or(Error::<T>::AuctionNotExists)?;
+			ensure!(
+				!amount.is_zero()
+					&& amount >= T::MinPartialBidProportion::get().saturating_mul_int(collateral_auction.amount)
+					&& amount <= collateral_auction.amount,
+				Error::<T>::InvalidAmount
+			);
+			ensure!(!payment.is_zero(), Error::<T>::InvalidBidPrice);
+			let partial_bid = PartialBid { amount, payment };
+
+			// the partial bids can't beat the whole lot bid which has reached the target
+			if let Some((_, bid_price)) = &auction_info.bid {
+				ensure!(
+					!collateral_auction.in_reverse_stage(*bid_price),
+					Error::<T>::InReverseStage
+				);
+
+				let bid_unit_price = Price::checked_from_rational(
+					collateral_auction.payment_amount(*bid_price),
+					collateral_auction.amount,
+				)
+				.unwrap_or_else(Price::zero);
+				ensure!(partial_bid.unit_price() >= bid_unit_price, Error::<T>::InvalidBidPrice);
+			}
+
+			PartialBids::<T>::try_mutate(id, |partial_bids| -> DispatchResult {
+				if let Some(index) = partial_bids.iter().position(|(bidder, _)| *bidder == who) {
+					// refund the replaced partial bid
+					let (_, replaced) = partial_bids.remove(index);
+					T::CDPTreasury::issue_debit(&who, replaced.payment, false)?;
+				} else {
+					if (partial_bids.len() as u32) >= T::MaxPartialBids::get() {
+						// evict the partial bid with the lowest price per unit, the earliest one
+						// if there're several
+						let (index, (_, lowest)) = partial_bids
+							.iter()
+							.enumerate()
+							.min_by(|(_, (_, a)), (_, (_, b))| a.unit_price().cmp(&b.unit_price()))
+							.ok_or(Error::<T>::TooManyPartialBids)?;
+						ensure!(
+							partial_bid.unit_price() > lowest.unit_price(),
+							Error::<T>::TooManyPartialBids
+						);
+						let (evicted, evicted_bid) = partial_bids.remove(index);
+						T::CDPTreasury::issue_debit(&evicted, evicted_bid.payment, false)?;
+						frame_system::Module::<T>::dec_ref(&evicted);
+						Self::deposit_event(Event::PartialBidEvicted(id, evicted, evicted_bid.payment));
+					}
+					frame_system::Module::<T>::inc_ref(&who);
+				}
+
+				T::CDPTreasury::deposit_surplus(&who, payment)?;
+				partial_bids.push((who.clone(), partial_bid));
+				Ok(())
+			})?;
+
+			// the partial bid extends the auction end as the whole lot bid does
+			let now = <frame_system::Module<T>>::block_number();
+			let new_end = now.saturating_add(Self::get_auction_time_to_close(now, collateral_auction.start_time));
+			if auction_info.end.map_or(true, |end| end < new_end) {
+				T::Auction::update_auction(
+					id,
+					AuctionInfo {
+						end: Some(new_end),
+						..auction_info
+					},
+				)?;
+			}
+
+			Self::deposit_event(Event::PartialBidPlaced(id, who, amount, payment));
+			Ok(().into())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -347,6 +1030,10 @@ pub mod module {
 					return InvalidTransaction::Call.into();
 				}
 
+				if Self::in_settlement(*auction_id) {
+					return InvalidTransaction::Stale.into();
+				}
+
 				if let Some(collateral_auction) = Self::collateral_auctions(auction_id) {
 					if let Some((_, bid_price)) = Self::get_last_bid(*auction_id) {
 						// if collateral auction is in reverse stage, shouldn't cancel
@@ -356,6 +1043,7 @@ pub mod module {
 					}
 				} else if !<SurplusAuctions<T>>::contains_key(auction_id)
 					&& !<DebitAuctions<T>>::contains_key(auction_id)
+					&& !<DutchCollateralAuctions<T>>::contains_key(auction_id)
 				{
 					return InvalidTransaction::Stale.into();
 				}
@@ -407,7 +1095,7 @@ impl<T: Config> Pallet<T> {
 		} else {
 			let random_seed = sp_io::offchain::random_seed();
 			let mut rng = RandomNumberGenerator::<BlakeTwo256>::new(BlakeTwo256::hash(&random_seed[..]));
-			(rng.pick_u32(2), None)
+			(rng.pick_u32(3), None)
 		};
 
 		// get the max iterationns config
@@ -417,8 +1105,8 @@ impl<T: Config> Pallet<T> {
 
 		debug::debug!(target: "auction-manager offchain worker", "max iterations is {:?}", max_iterations);
 
-		// Randomly choose to start iterations to cancel collateral/surplus/debit
-		// auctions
+		// Randomly choose to start iterations to cancel collateral/surplus/debit/dutch
+		// collateral auctions
 		match auction_type_num {
 			0 => {
 				let mut iterator =
@@ -450,7 +1138,7 @@ impl<T: Config> Pallet<T> {
 					to_be_continue.set(&(auction_type_num, iterator.storage_map_iterator.previous_key));
 				}
 			}
-			_ => {
+			2 => {
 				let mut iterator =
 					<CollateralAuctions<T> as IterableStorageMapExtended<_, _>>::iter(max_iterations, start_key);
 				while let Some((collateral_auction_id, _)) = iterator.next() {
@@ -468,6 +1156,20 @@ impl<T: Config> Pallet<T> {
 					guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
 				}
 
+				if iterator.finished {
+					to_be_continue.clear();
+				} else {
+					to_be_continue.set(&(auction_type_num, iterator.storage_map_iterator.previous_key));
+				}
+			}
+			_ => {
+				let mut iterator =
+					<DutchCollateralAuctions<T> as IterableStorageMapExtended<_, _>>::iter(max_iterations, start_key);
+				while let Some((dutch_auction_id, _)) = iterator.next() {
+					Self::submit_cancel_auction_tx(dutch_auction_id);
+					guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
+				}
+
 				if iterator.finished {
 					to_be_continue.clear();
 				} else {
@@ -529,26 +1231,13 @@ impl<T: Config> Pallet<T> {
 			);
 		}
 
-		// calculate how much collateral to offset target in settle price
-		let stable_currency_id = T::GetStableCurrencyId::get();
-		let settle_price = T::PriceSource::get_relative_price(stable_currency_id, collateral_auction.currency_id)
-			.ok_or(Error::<T>::InvalidFeedPrice)?;
-		let confiscate_collateral_amount = if collateral_auction.always_forward() {
-			collateral_auction.amount
-		} else {
-			sp_std::cmp::min(
-				settle_price.saturating_mul_int(collateral_auction.target),
-				collateral_auction.amount,
-			)
-		};
-		let refund_collateral_amount = collateral_auction.amount.saturating_sub(confiscate_collateral_amount);
-
-		// refund remain collateral to refund recipient from CDP treasury
-		T::CDPTreasury::withdraw_collateral(
+		Self::refund_collateral_at_settle_price(
 			&collateral_auction.refund_recipient,
 			collateral_auction.currency_id,
-			refund_collateral_amount,
+			collateral_auction.amount,
+			collateral_auction.target,
 		)?;
+		Self::refund_partial_bids(id)?;
 
 		// if there's bid
 		if let Some((bidder, bid_price)) = last_bid {
@@ -571,36 +1260,175 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Return `true` if price increment rate is greater than or equal to
-	/// minimum.
+	/// Refund the bidder of the collateral auction and re-list the
+	/// collateral for sale, which remains in CDP treasury, with the new
+	/// target.
+	fn relist_collateral_auction(
+		id: AuctionId,
+		collateral_auction: CollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		target: Balance,
+	) -> DispatchResult {
+		// if there's bid
+		if let Some((bidder, bid_price)) = Self::get_last_bid(id) {
+			// collateral auction must not be in reverse stage
+			ensure!(
+				!collateral_auction.in_reverse_stage(bid_price),
+				Error::<T>::InReverseStage,
+			);
+
+			// refund stable token to the bidder
+			T::CDPTreasury::issue_debit(&bidder, bid_price, false)?;
+
+			// decrease account ref of bidder
+			frame_system::Module::<T>::dec_ref(&bidder);
+		}
+		Self::refund_partial_bids(id)?;
+
+		// decrease account ref of refund recipient
+		frame_system::Module::<T>::dec_ref(&collateral_auction.refund_recipient);
+
+		// decrease total collateral and target in auction
+		TotalCollateralInAuction::<T>::mutate(collateral_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(collateral_auction.amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
+
+		<Self as AuctionManager<T::AccountId>>::new_collateral_auction(
+			&collateral_auction.refund_recipient,
+			collateral_auction.currency_id,
+			collateral_auction.amount,
+			target,
+		)
+	}
+
+	/// Re-list the collateral of the dutch collateral auction, which remains
+	/// in CDP treasury, with the new target.
+	fn relist_dutch_collateral_auction(
+		dutch_auction: DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		target: Balance,
+	) -> DispatchResult {
+		// decrease account ref of refund recipient
+		frame_system::Module::<T>::dec_ref(&dutch_auction.refund_recipient);
+
+		// decrease total collateral and target in auction
+		TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(dutch_auction.amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(dutch_auction.target));
+
+		<Self as AuctionManager<T::AccountId>>::new_collateral_auction(
+			&dutch_auction.refund_recipient,
+			dutch_auction.currency_id,
+			dutch_auction.amount,
+			target,
+		)
+	}
+
+	fn cancel_dutch_collateral_auction(
+		dutch_auction: DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>,
+	) -> DispatchResult {
+		Self::refund_collateral_at_settle_price(
+			&dutch_auction.refund_recipient,
+			dutch_auction.currency_id,
+			dutch_auction.amount,
+			dutch_auction.target,
+		)?;
+
+		// decrease account ref of refund recipient
+		frame_system::Module::<T>::dec_ref(&dutch_auction.refund_recipient);
+
+		// decrease total collateral and target in auction
+		TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(dutch_auction.amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(dutch_auction.target));
+
+		Ok(())
+	}
+
+	/// Refund the collateral which is not needed to offset the target in
+	/// settle price to refund recipient from CDP treasury
+	fn refund_collateral_at_settle_price(
+		refund_recipient: &T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		target: Balance,
+	) -> DispatchResult {
+		// calculate how much collateral to offset target in settle price
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let settle_price =
+			T::PriceSource::get_relative_price(stable_currency_id, currency_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let confiscate_collateral_amount = if target.is_zero() {
+			amount
+		} else {
+			sp_std::cmp::min(settle_price.saturating_mul_int(target), amount)
+		};
+		let refund_collateral_amount = amount.saturating_sub(confiscate_collateral_amount);
+
+		// refund remain collateral to refund recipient from CDP treasury
+		T::CDPTreasury::withdraw_collateral(refund_recipient, currency_id, refund_collateral_amount)
+	}
+
+	/// Return `true` if price increment is greater than or equal to both
+	/// the minimum rate and the minimum absolute increment.
 	///
 	/// Formula: new_price - last_price >=
-	///     max(last_price, target_price) * minimum_increment
+	///     max(max(last_price, target_price) * minimum_increment,
+	/// minimum_absolute_increment)
 	fn check_minimum_increment(
 		new_price: Balance,
 		last_price: Balance,
 		target_price: Balance,
 		minimum_increment: Rate,
+		minimum_absolute_increment: Balance,
 	) -> bool {
 		if let (Some(target), Some(result)) = (
 			minimum_increment.checked_mul_int(sp_std::cmp::max(target_price, last_price)),
 			new_price.checked_sub(last_price),
 		) {
-			result >= target
+			result >= sp_std::cmp::max(target, minimum_absolute_increment)
 		} else {
 			false
 		}
 	}
 
-	fn get_minimum_increment_size(now: T::BlockNumber, start_block: T::BlockNumber) -> Rate {
+	fn get_minimum_increment_size(auction_type: AuctionType, now: T::BlockNumber, start_block: T::BlockNumber) -> Rate {
+		let minimum_increment_size = Self::minimum_increments(auction_type)
+			.map_or_else(T::MinimumIncrementSize::get, |minimum_increment| minimum_increment.rate);
 		if now >= start_block + T::AuctionDurationSoftCap::get() {
 			// double the minimum increment size when reach soft cap
-			T::MinimumIncrementSize::get().saturating_mul(Rate::saturating_from_integer(2))
+			minimum_increment_size.saturating_mul(Rate::saturating_from_integer(2))
 		} else {
-			T::MinimumIncrementSize::get()
+			minimum_increment_size
 		}
 	}
 
+	fn get_minimum_absolute_increment(auction_type: AuctionType) -> Balance {
+		Self::minimum_increments(auction_type).map_or(Zero::zero(), |minimum_increment| minimum_increment.absolute)
+	}
+
+	/// Return the minimum price of the next bid of an auction to pass
+	/// `check_minimum_increment`
+	fn get_minimum_next_bid(
+		auction_type: AuctionType,
+		last_price: Balance,
+		target_price: Balance,
+		now: T::BlockNumber,
+		start_block: T::BlockNumber,
+	) -> Balance {
+		let minimum_increment = Self::get_minimum_increment_size(auction_type, now, start_block)
+			.saturating_mul_int(sp_std::cmp::max(target_price, last_price));
+		let minimum_increment = sp_std::cmp::max(minimum_increment, Self::get_minimum_absolute_increment(auction_type));
+		// zero bid price is never accepted
+		sp_std::cmp::max(last_price.saturating_add(minimum_increment), 1)
+	}
+
+	fn current_dutch_auction_price(dutch_auction: &DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>) -> Price {
+		let now = <frame_system::Module<T>>::block_number();
+		let elapsed = UniqueSaturatedInto::<u128>::unique_saturated_into(now.saturating_sub(dutch_auction.start_time));
+		dutch_auction.price_after(elapsed)
+	}
+
 	fn get_auction_time_to_close(now: T::BlockNumber, start_block: T::BlockNumber) -> T::BlockNumber {
 		if now >= start_block + T::AuctionDurationSoftCap::get() {
 			// halve the extended time of bid when reach soft cap
@@ -612,6 +1440,36 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Return the new auction end time with the anti-sniping extension if the
+	/// bid is received within the anti-sniping window before the current
+	/// auction end.
+	fn extend_auction_end(now: T::BlockNumber, id: AuctionId, new_end: T::BlockNumber) -> T::BlockNumber {
+		let in_anti_sniping_window = T::Auction::auction_info(id)
+			.and_then(|auction_info| auction_info.end)
+			.map_or(false, |end| {
+				now <= end && now.saturating_add(T::AntiSnipingWindow::get()) >= end
+			});
+		if !in_anti_sniping_window {
+			return new_end;
+		}
+
+		let extension = AntiSnipingExtensions::<T>::mutate(id, |total_extension| {
+			let extension = sp_std::cmp::min(
+				T::AntiSnipingExtension::get(),
+				T::MaxAntiSnipingExtension::get().saturating_sub(*total_extension),
+			);
+			*total_extension = total_extension.saturating_add(extension);
+			extension
+		});
+		if extension.is_zero() {
+			return new_end;
+		}
+
+		let extended_end = new_end.saturating_add(extension);
+		Self::deposit_event(Event::AuctionEndExtended(id, extension, extended_end));
+		extended_end
+	}
+
 	/// Handles collateral auction new bid. Returns
 	/// `Ok(new_auction_end_time)` if bid accepted.
 	///
@@ -638,7 +1496,8 @@ impl<T: Config> Pallet<T> {
 						new_bid_price,
 						last_bid_price,
 						collateral_auction.target,
-						Self::get_minimum_increment_size(now, collateral_auction.start_time),
+						Self::get_minimum_increment_size(AuctionType::Collateral, now, collateral_auction.start_time),
+						Self::get_minimum_absolute_increment(AuctionType::Collateral),
 					),
 					Error::<T>::InvalidBidPrice
 				);
@@ -713,7 +1572,8 @@ impl<T: Config> Pallet<T> {
 						new_bid_price,
 						last_bid_price,
 						debit_auction.fix,
-						Self::get_minimum_increment_size(now, debit_auction.start_time),
+						Self::get_minimum_increment_size(AuctionType::Debit, now, debit_auction.start_time),
+						Self::get_minimum_absolute_increment(AuctionType::Debit),
 					) && new_bid_price >= debit_auction.fix,
 					Error::<T>::InvalidBidPrice,
 				);
@@ -765,7 +1625,8 @@ impl<T: Config> Pallet<T> {
 				new_bid_price,
 				last_bid_price,
 				Zero::zero(),
-				Self::get_minimum_increment_size(now, surplus_auction.start_time),
+				Self::get_minimum_increment_size(AuctionType::Surplus, now, surplus_auction.start_time),
+				Self::get_minimum_absolute_increment(AuctionType::Surplus),
 			),
 			Error::<T>::InvalidBidPrice,
 		);
@@ -793,7 +1654,18 @@ impl<T: Config> Pallet<T> {
 		collateral_auction: CollateralAuctionItem<T::AccountId, T::BlockNumber>,
 		winner: Option<(T::AccountId, Balance)>,
 	) {
-		if let Some((bidder, bid_price)) = winner {
+		let winner_payment = winner.as_ref().map_or(Zero::zero(), |(_, bid_price)| {
+			collateral_auction.payment_amount(*bid_price)
+		});
+
+		if Self::partial_bids_end_handler(auction_id, &collateral_auction, winner_payment) {
+			// the partial bids deal, refund stable currency to the winner. it shouldn't
+			// fail and affect the process. but even it failed, just the winner did not get
+			// the bid price. it can be fixed by treasury council.
+			if let Some((bidder, bid_price)) = winner {
+				let _ = T::CDPTreasury::issue_debit(&bidder, bid_price, false);
+			}
+		} else if let Some((bidder, bid_price)) = winner {
 			let mut should_deal = true;
 
 			// if bid_price doesn't reach target and trading with DEX will get better result
@@ -807,12 +1679,7 @@ impl<T: Config> Pallet<T> {
 					.unwrap_or_default()
 			{
 				// try swap collateral in auction with DEX to get stable
-				if let Ok(stable_amount) = T::CDPTreasury::swap_exact_collateral_in_auction_to_stable(
-					collateral_auction.currency_id,
-					collateral_auction.amount,
-					Zero::zero(),
-					None,
-				) {
+				if Self::dex_take_collateral_auction(auction_id, &collateral_auction, Zero::zero()) {
 					// swap successfully, will not deal
 					should_deal = false;
 
@@ -820,24 +1687,6 @@ impl<T: Config> Pallet<T> {
 					// process. but even it failed, just the winner did not get the bid price. it
 					// can be fixed by treasury council.
 					let _ = T::CDPTreasury::issue_debit(&bidder, bid_price, false);
-
-					if collateral_auction.in_reverse_stage(stable_amount) {
-						// refund extra stable currency to recipient
-						let refund_amount = stable_amount
-							.checked_sub(collateral_auction.target)
-							.expect("ensured stable_amount > target; qed");
-						// it shouldn't fail and affect the process.
-						// but even it failed, just the winner did not get the refund amount. it can be
-						// fixed by treasury council.
-						let _ = T::CDPTreasury::issue_debit(&collateral_auction.refund_recipient, refund_amount, false);
-					}
-
-					Self::deposit_event(Event::DEXTakeCollateralAuction(
-						auction_id,
-						collateral_auction.currency_id,
-						collateral_auction.amount,
-						stable_amount,
-					));
 				}
 			}
 
@@ -860,6 +1709,16 @@ impl<T: Config> Pallet<T> {
 					payment_amount,
 				));
 			}
+		} else if let Some(max_slippage) = Self::no_bid_sale_max_slippage() {
+			// sell the collateral into DEX within the max slippage, otherwise park it in
+			// CDP treasury
+			if !Self::no_bid_sale(auction_id, &collateral_auction, max_slippage) {
+				Self::deposit_event(Event::NoBidCollateralParked(
+					auction_id,
+					collateral_auction.currency_id,
+					collateral_auction.amount,
+				));
+			}
 		} else {
 			Self::deposit_event(Event::CancelAuction(auction_id));
 		}
@@ -874,6 +1733,190 @@ impl<T: Config> Pallet<T> {
 		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
 	}
 
+	/// Sell the collateral of the expired dutch collateral auction into DEX
+	/// within the no-bid max slippage, otherwise re-list it as an english
+	/// collateral auction.
+	fn dutch_collateral_auction_expired_handler(
+		auction_id: AuctionId,
+		dutch_auction: DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>,
+	) {
+		Self::deposit_event(Event::DutchCollateralAuctionExpired(auction_id));
+
+		let start_time = <frame_system::Module<T>>::block_number();
+		let collateral_auction = CollateralAuctionItem {
+			refund_recipient: dutch_auction.refund_recipient,
+			currency_id: dutch_auction.currency_id,
+			initial_amount: dutch_auction.amount,
+			amount: dutch_auction.amount,
+			target: dutch_auction.target,
+			start_time,
+		};
+
+		let sold = Self::no_bid_sale_max_slippage().map_or(false, |max_slippage| {
+			Self::no_bid_sale(auction_id, &collateral_auction, max_slippage)
+		});
+		if !sold {
+			// the re-listed english auction takes over the account reference of the
+			// recipient and the auction records.
+			if let Ok(new_auction_id) = T::Auction::new_auction(start_time, None) {
+				Self::deposit_event(Event::NewCollateralAuction(
+					new_auction_id,
+					collateral_auction.currency_id,
+					collateral_auction.amount,
+					collateral_auction.target,
+				));
+				<CollateralAuctions<T>>::insert(new_auction_id, collateral_auction);
+				return;
+			}
+
+			Self::deposit_event(Event::NoBidCollateralParked(
+				auction_id,
+				collateral_auction.currency_id,
+				collateral_auction.amount,
+			));
+		}
+
+		// decrement recipient account reference
+		frame_system::Module::<T>::dec_ref(&collateral_auction.refund_recipient);
+
+		// update auction records
+		TotalCollateralInAuction::<T>::mutate(collateral_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(collateral_auction.amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
+	}
+
+	/// Sell the collateral of the collateral auction without any bid into
+	/// DEX if the received stable currency is no less than the oracle value
+	/// of the collateral deducted by `max_slippage`. Return whether the
+	/// collateral is sold.
+	fn no_bid_sale(
+		auction_id: AuctionId,
+		collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		max_slippage: Ratio,
+	) -> bool {
+		T::PriceSource::get_relative_price(collateral_auction.currency_id, T::GetStableCurrencyId::get()).map_or(
+			false,
+			|oracle_price| {
+				let oracle_value = oracle_price.saturating_mul_int(collateral_auction.amount);
+				let min_stable_amount = Ratio::one()
+					.saturating_sub(max_slippage)
+					.saturating_mul_int(oracle_value);
+				Self::dex_take_collateral_auction(auction_id, collateral_auction, min_stable_amount)
+			},
+		)
+	}
+
+	/// Swap all the collateral of the collateral auction with DEX to get
+	/// at least `min_stable_amount` of stable currency, and refund the extra
+	/// stable currency beyond the target to the refund recipient. Return
+	/// whether the swap succeeded.
+	fn dex_take_collateral_auction(
+		auction_id: AuctionId,
+		collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		min_stable_amount: Balance,
+	) -> bool {
+		match T::CDPTreasury::swap_exact_collateral_in_auction_to_stable(
+			collateral_auction.currency_id,
+			collateral_auction.amount,
+			min_stable_amount,
+			None,
+		) {
+			Ok(stable_amount) => {
+				if collateral_auction.in_reverse_stage(stable_amount) {
+					// refund extra stable currency to recipient
+					let refund_amount = stable_amount
+						.checked_sub(collateral_auction.target)
+						.expect("ensured stable_amount > target; qed");
+					// it shouldn't fail and affect the process.
+					// but even it failed, just the winner did not get the refund amount. it can be
+					// fixed by treasury council.
+					let _ = T::CDPTreasury::issue_debit(&collateral_auction.refund_recipient, refund_amount, false);
+				}
+
+				Self::deposit_event(Event::DEXTakeCollateralAuction(
+					auction_id,
+					collateral_auction.currency_id,
+					collateral_auction.amount,
+					stable_amount,
+				));
+				true
+			}
+			Err(_) => false,
+		}
+	}
+
+	/// Deal the partial bids of the ended collateral auction if their
+	/// aggregate payment is higher than `winner_payment` of the whole lot
+	/// bid, otherwise refund them. Return whether the partial bids deal.
+	fn partial_bids_end_handler(
+		auction_id: AuctionId,
+		collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		winner_payment: Balance,
+	) -> bool {
+		let partial_bids = Self::partial_bids(auction_id);
+		let (fills, target_reached) = collateral_auction.partial_fills(&partial_bids);
+		let total_payment = fills
+			.iter()
+			.fold(Balance::zero(), |total, (_, payment)| total.saturating_add(*payment));
+		if total_payment <= winner_payment {
+			// it shouldn't fail and affect the process. but even it failed, just the
+			// partial bidders did not get the refund. it can be fixed by treasury council.
+			let _ = Self::refund_partial_bids(auction_id);
+			return false;
+		}
+
+		PartialBids::<T>::remove(auction_id);
+		let mut sold_amount: Balance = Zero::zero();
+		for ((bidder, partial_bid), (collateral_amount, payment_amount)) in partial_bids.into_iter().zip(fills) {
+			// transfer the filled collateral to the bidder and refund the unfilled
+			// payment, it shouldn't fail and affect the process. but even it failed, just
+			// the bidder did not get the amount. it can be fixed by treasury council.
+			let _ = T::CDPTreasury::withdraw_collateral(&bidder, collateral_auction.currency_id, collateral_amount);
+			let refund_amount = partial_bid.payment.saturating_sub(payment_amount);
+			if !refund_amount.is_zero() {
+				let _ = T::CDPTreasury::issue_debit(&bidder, refund_amount, false);
+			}
+			sold_amount = sold_amount.saturating_add(collateral_amount);
+
+			// decrease account ref of partial bidder
+			frame_system::Module::<T>::dec_ref(&bidder);
+
+			if !collateral_amount.is_zero() {
+				Self::deposit_event(Event::PartialBidDealt(
+					auction_id,
+					collateral_auction.currency_id,
+					collateral_amount,
+					bidder,
+					payment_amount,
+				));
+			}
+		}
+
+		// refund the unsold collateral to recipient if the target is reached,
+		// otherwise it remains in CDP treasury
+		let refund_collateral_amount = collateral_auction.amount.saturating_sub(sold_amount);
+		if target_reached && !refund_collateral_amount.is_zero() {
+			let _ = T::CDPTreasury::withdraw_collateral(
+				&collateral_auction.refund_recipient,
+				collateral_auction.currency_id,
+				refund_collateral_amount,
+			);
+		}
+		true
+	}
+
+	/// Refund the payments of the partial bids of the collateral auction
+	fn refund_partial_bids(id: AuctionId) -> DispatchResult {
+		for (bidder, partial_bid) in PartialBids::<T>::take(id) {
+			T::CDPTreasury::issue_debit(&bidder, partial_bid.payment, false)?;
+
+			// decrease account ref of partial bidder
+			frame_system::Module::<T>::dec_ref(&bidder);
+		}
+		Ok(())
+	}
+
 	fn debit_auction_end_handler(
 		auction_id: AuctionId,
 		debit_auction: DebitAuctionItem<T::BlockNumber>,
@@ -922,6 +1965,148 @@ impl<T: Config> Pallet<T> {
 		TotalSurplusInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(surplus_auction.amount));
 	}
 
+	/// Return whether the auction has ended and is waiting for settlement
+	fn in_settlement(id: AuctionId) -> bool {
+		PendingSettlements::<T>::contains_key(id)
+	}
+
+	/// Settle the queued ended auctions as many as `max_weight` allows, the
+	/// rest are carried over to the following blocks. Returns the consumed
+	/// weight.
+	pub fn settle_pending_auctions(max_weight: Weight) -> Weight {
+		let mut weight = T::DbWeight::get().reads(1);
+		let (head, tail) = Self::settlement_queue_range();
+		if head == tail {
+			return weight;
+		}
+
+		let settle_weight = T::WeightInfo::settle_auction();
+		let mut new_head = head;
+		while new_head != tail {
+			if weight.saturating_add(settle_weight) > max_weight {
+				break;
+			}
+			if let Some(id) = SettlementQueue::<T>::take(new_head) {
+				if let Some(winner) = PendingSettlements::<T>::take(id) {
+					Self::settle_auction(id, winner);
+				}
+			}
+			weight = weight.saturating_add(settle_weight);
+			new_head = new_head.wrapping_add(1);
+		}
+
+		if new_head != head {
+			SettlementQueueRange::<T>::put((new_head, tail));
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+		}
+
+		weight
+	}
+
+	fn settle_auction(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
+		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
+			Self::collateral_auction_end_handler(id, collateral_auction, winner.clone());
+		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
+			Self::debit_auction_end_handler(id, debit_auction, winner.clone());
+		} else if let Some(surplus_auction) = <SurplusAuctions<T>>::take(id) {
+			Self::surplus_auction_end_handler(id, surplus_auction, winner.clone());
+		} else if let Some(dutch_auction) = <DutchCollateralAuctions<T>>::take(id) {
+			Self::dutch_collateral_auction_expired_handler(id, dutch_auction);
+		}
+		AntiSnipingExtensions::<T>::remove(id);
+
+		if let Some((bidder, _)) = &winner {
+			// decrease account ref of winner
+			frame_system::Module::<T>::dec_ref(bidder);
+		}
+	}
+
+	/// Return the information of at most `limit` active auctions ordered by
+	/// id, starting from the auction id `start`. The ended auctions waiting
+	/// for settlement are excluded.
+	pub fn active_auctions(start: AuctionId, limit: u32) -> Vec<ActiveAuctionInfo<T::AccountId, T::BlockNumber>> {
+		let now = <frame_system::Module<T>>::block_number();
+		let mut ids: Vec<AuctionId> = <CollateralAuctions<T>>::iter_keys()
+			.chain(<DebitAuctions<T>>::iter_keys())
+			.chain(<SurplusAuctions<T>>::iter_keys())
+			.chain(<DutchCollateralAuctions<T>>::iter_keys())
+			.filter(|id| *id >= start)
+			.collect();
+		ids.sort_unstable();
+
+		ids.into_iter()
+			.filter_map(|id| {
+				let auction_info = T::Auction::auction_info(id)?;
+				let last_bid_price = auction_info.bid.as_ref().map_or(Zero::zero(), |(_, price)| *price);
+				let (lot, minimum_next_bid) = if let Some(collateral_auction) = Self::collateral_auctions(id) {
+					let minimum_next_bid = Self::get_minimum_next_bid(
+						AuctionType::Collateral,
+						last_bid_price,
+						collateral_auction.target,
+						now,
+						collateral_auction.start_time,
+					);
+					let lot = AuctionLot::Collateral {
+						currency_id: collateral_auction.currency_id,
+						amount: collateral_auction.amount,
+						target: collateral_auction.target,
+					};
+					(lot, minimum_next_bid)
+				} else if let Some(debit_auction) = Self::debit_auctions(id) {
+					let minimum_next_bid = sp_std::cmp::max(
+						Self::get_minimum_next_bid(
+							AuctionType::Debit,
+							last_bid_price,
+							debit_auction.fix,
+							now,
+							debit_auction.start_time,
+						),
+						debit_auction.fix,
+					);
+					let lot = AuctionLot::Debit {
+						amount: debit_auction.amount,
+						fix: debit_auction.fix,
+					};
+					(lot, minimum_next_bid)
+				} else if let Some(surplus_auction) = Self::surplus_auctions(id) {
+					let minimum_next_bid = Self::get_minimum_next_bid(
+						AuctionType::Surplus,
+						last_bid_price,
+						Zero::zero(),
+						now,
+						surplus_auction.start_time,
+					);
+					(
+						AuctionLot::Surplus {
+							amount: surplus_auction.amount,
+						},
+						minimum_next_bid,
+					)
+				} else {
+					let dutch_auction = Self::dutch_collateral_auctions(id)?;
+					let price = Self::current_dutch_auction_price(&dutch_auction);
+					let (payment_amount, _) = dutch_auction.deal_amounts(price);
+					let lot = AuctionLot::DutchCollateral {
+						currency_id: dutch_auction.currency_id,
+						amount: dutch_auction.amount,
+						target: dutch_auction.target,
+						price,
+					};
+					(lot, payment_amount)
+				};
+
+				Some(ActiveAuctionInfo {
+					id,
+					lot,
+					bid: auction_info.bid,
+					minimum_next_bid,
+					end: auction_info.end,
+				})
+			})
+			.take(limit as usize)
+			.collect()
+	}
+
 	/// increment `new_bidder` reference and decrement `last_bidder`
 	/// reference if any
 	fn swap_bidders(new_bidder: &T::AccountId, last_bidder: Option<&T::AccountId>) {
@@ -953,7 +2138,7 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 		match bid_result {
 			Ok(new_auction_end_time) => OnNewBidResult {
 				accept_bid: true,
-				auction_end_change: Change::NewValue(Some(new_auction_end_time)),
+				auction_end_change: Change::NewValue(Some(Self::extend_auction_end(now, id, new_auction_end_time))),
 			},
 			Err(_) => OnNewBidResult {
 				accept_bid: false,
@@ -963,18 +2148,12 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 	}
 
 	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
-		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
-			Self::collateral_auction_end_handler(id, collateral_auction, winner.clone());
-		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
-			Self::debit_auction_end_handler(id, debit_auction, winner.clone());
-		} else if let Some(surplus_auction) = <SurplusAuctions<T>>::take(id) {
-			Self::surplus_auction_end_handler(id, surplus_auction, winner.clone());
-		}
-
-		if let Some((bidder, _)) = &winner {
-			// decrease account ref of winner
-			frame_system::Module::<T>::dec_ref(bidder);
-		}
+		// queue the settlement, it will be settled within the weight budget
+		PendingSettlements::<T>::insert(id, winner);
+		SettlementQueueRange::<T>::mutate(|(_, tail)| {
+			SettlementQueue::<T>::insert(*tail, id);
+			*tail = tail.wrapping_add(1);
+		});
 	}
 }
 
@@ -1005,25 +2184,59 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 
 		let start_time = <frame_system::Module<T>>::block_number();
 
-		// do not set end time for collateral auction
-		let auction_id = T::Auction::new_auction(start_time, None)?;
+		// create dutch auction if the collateral type is configured for it and the
+		// oracle price is available, otherwise fallback to english auction
+		let dutch_auction = Self::dutch_auction_params(currency_id).and_then(|param| {
+			T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
+				.map(|oracle_price| (param, oracle_price))
+		});
 
-		<CollateralAuctions<T>>::insert(
-			auction_id,
-			CollateralAuctionItem {
+		if let Some((param, oracle_price)) = dutch_auction {
+			// the dutch auction expires after the duration
+			let end_block = start_time.saturating_add(T::DutchAuctionDuration::get());
+			let auction_id = T::Auction::new_auction(start_time, Some(end_block))?;
+
+			let dutch_auction = DutchCollateralAuctionItem {
 				refund_recipient: refund_recipient.clone(),
 				currency_id,
-				initial_amount: amount,
 				amount,
 				target,
 				start_time,
-			},
-		);
+				oracle_price,
+				param,
+			};
+			let start_price = dutch_auction.price_after(0);
+			<DutchCollateralAuctions<T>>::insert(auction_id, dutch_auction);
+
+			Self::deposit_event(Event::NewDutchCollateralAuction(
+				auction_id,
+				currency_id,
+				amount,
+				target,
+				start_price,
+			));
+		} else {
+			// do not set end time for collateral auction
+			let auction_id = T::Auction::new_auction(start_time, None)?;
+
+			<CollateralAuctions<T>>::insert(
+				auction_id,
+				CollateralAuctionItem {
+					refund_recipient: refund_recipient.clone(),
+					currency_id,
+					initial_amount: amount,
+					amount,
+					target,
+					start_time,
+				},
+			);
+
+			Self::deposit_event(Event::NewCollateralAuction(auction_id, currency_id, amount, target));
+		}
 
 		// increment recipient account reference
 		frame_system::Module::<T>::inc_ref(&refund_recipient);
 
-		Self::deposit_event(Event::NewCollateralAuction(auction_id, currency_id, amount, target));
 		Ok(())
 	}
 
@@ -1076,15 +2289,19 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 	}
 
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult {
+		ensure!(!Self::in_settlement(id), Error::<T>::AuctionInSettlement);
 		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
 			Self::cancel_collateral_auction(id, collateral_auction)?;
 		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
 			Self::cancel_debit_auction(id, debit_auction)?;
 		} else if let Some(surplus_auction) = <SurplusAuctions<T>>::take(id) {
 			Self::cancel_surplus_auction(id, surplus_auction)?;
+		} else if let Some(dutch_auction) = <DutchCollateralAuctions<T>>::take(id) {
+			Self::cancel_dutch_collateral_auction(dutch_auction)?;
 		} else {
 			return Err(Error::<T>::AuctionNotExists.into());
 		}
+		AntiSnipingExtensions::<T>::remove(id);
 		T::Auction::remove_auction(id);
 		Ok(())
 	}
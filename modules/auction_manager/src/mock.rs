@@ -94,6 +94,7 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryModuleId: ModuleId = ModuleId(*b"aca/cdpt");
+	pub const BadDebtDelay: BlockNumber = 10;
 }
 
 impl cdp_treasury::Config for Runtime {
@@ -105,6 +106,10 @@ impl cdp_treasury::Config for Runtime {
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type BadDebtDelay = BadDebtDelay;
+	type DebitAuctionFixedSize = ();
+	type DebitAuctionInitialAmount = ();
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type WeightInfo = ();
 }
 
@@ -136,6 +141,7 @@ parameter_types! {
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const TwapPeriod: BlockNumber = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC)];
 }
 
@@ -144,6 +150,7 @@ impl module_dex::Config for Runtime {
 	type Currency = Tokens;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TwapPeriod = TwapPeriod;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = ();
 	type WeightInfo = ();
@@ -169,6 +176,13 @@ parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(1, 20);
 	pub const AuctionTimeToClose: u64 = 100;
 	pub const AuctionDurationSoftCap: u64 = 2000;
+	pub const AntiSnipingWindow: u64 = 10;
+	pub const AntiSnipingExtension: u64 = 20;
+	pub const MaxAntiSnipingExtension: u64 = 50;
+	pub const DutchAuctionDuration: u64 = 200;
+	pub const MaxPartialBids: u32 = 3;
+	pub MinPartialBidProportion: Ratio = Ratio::saturating_from_rational(1, 10);
+	pub const MaxSettlementWeight: Weight = 1_000_000_000_000;
 	pub const GetNativeCurrencyId: CurrencyId = ACA;
 	pub const UnsignedPriority: u64 = 1 << 20;
 }
@@ -180,6 +194,12 @@ impl Config for Runtime {
 	type MinimumIncrementSize = MinimumIncrementSize;
 	type AuctionTimeToClose = AuctionTimeToClose;
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
+	type AntiSnipingWindow = AntiSnipingWindow;
+	type AntiSnipingExtension = AntiSnipingExtension;
+	type MaxAntiSnipingExtension = MaxAntiSnipingExtension;
+	type DutchAuctionDuration = DutchAuctionDuration;
+	type MaxPartialBids = MaxPartialBids;
+	type MinPartialBidProportion = MinPartialBidProportion;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
@@ -187,6 +207,9 @@ impl Config for Runtime {
 	type PriceSource = MockPriceSource;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type MaxSettlementWeight = MaxSettlementWeight;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type CancelOrigin = EnsureSignedBy<One, AccountId>;
 	type WeightInfo = ();
 }
 
@@ -5,6 +5,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn get_auction_time_to_close_work() {
@@ -351,6 +352,7 @@ fn collateral_auction_end_handler_without_bid() {
 
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), true);
 		AuctionManagerModule::on_auction_ended(0, None);
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
 		let auction_passed_event = Event::auction_manager(crate::Event::CancelAuction(0));
 		assert!(System::events()
 			.iter()
@@ -365,6 +367,83 @@ fn collateral_auction_end_handler_without_bid() {
 	});
 }
 
+#[test]
+fn collateral_auction_end_handler_without_bid_sell_into_dex() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_no_bid_sale_max_slippage(
+				Origin::signed(5),
+				Some(Ratio::saturating_from_rational(2, 5))
+			),
+			BadOrigin,
+		);
+		assert_ok!(AuctionManagerModule::set_no_bid_sale_max_slippage(
+			Origin::signed(1),
+			Some(Ratio::saturating_from_rational(2, 5))
+		));
+		let update_event = Event::auction_manager(crate::Event::NoBidSaleMaxSlippageUpdated(Some(
+			Ratio::saturating_from_rational(2, 5),
+		)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+
+		// the received stable currency is below the oracle value deducted by the
+		// slippage, the collateral is parked in CDP treasury
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(10)));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		AuctionManagerModule::on_auction_ended(0, None);
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		let parked_event = Event::auction_manager(crate::Event::NoBidCollateralParked(0, BTC, 100));
+		assert!(System::events().iter().any(|record| record.event == parked_event));
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+
+		// sold into DEX within the slippage, the extra stable currency beyond the
+		// target is refunded
+		assert_ok!(AuctionManagerModule::set_no_bid_sale_max_slippage(
+			Origin::signed(1),
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		let alice_ref_count_0 = System::refs(&ALICE);
+		AuctionManagerModule::on_auction_ended(1, None);
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		let dex_take_collateral_auction =
+			Event::auction_manager(crate::Event::DEXTakeCollateralAuction(1, BTC, 100, 500));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == dex_take_collateral_auction));
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1300);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 300);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(System::refs(&ALICE), alice_ref_count_0 - 1);
+
+		// parked without the oracle price
+		MockPriceSource::set_relative_price(None);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		AuctionManagerModule::on_auction_ended(2, None);
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		let parked_event = Event::auction_manager(crate::Event::NoBidCollateralParked(2, BTC, 100));
+		assert!(System::events().iter().any(|record| record.event == parked_event));
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 200);
+	});
+}
+
 #[test]
 fn collateral_auction_end_handler_in_reverse_stage() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -387,6 +466,7 @@ fn collateral_auction_end_handler_in_reverse_stage() {
 
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), true);
 		AuctionManagerModule::on_auction_ended(0, Some((BOB, 400)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
 		let auction_dealt_event = Event::auction_manager(crate::Event::CollateralAuctionDealt(0, BTC, 50, BOB, 200));
 		assert!(System::events()
 			.iter()
@@ -429,6 +509,7 @@ fn collateral_auction_end_handler_by_dealing_which_target_not_zero() {
 
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), true);
 		AuctionManagerModule::on_auction_ended(0, Some((BOB, 100)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
 		let auction_dealt_event = Event::auction_manager(crate::Event::CollateralAuctionDealt(0, BTC, 100, BOB, 100));
 		assert!(System::events()
 			.iter()
@@ -482,6 +563,7 @@ fn collateral_auction_end_handler_by_dex_which_target_not_zero() {
 
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), true);
 		AuctionManagerModule::on_auction_ended(0, Some((BOB, 20)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
 		let dex_take_collateral_auction =
 			Event::auction_manager(crate::Event::DEXTakeCollateralAuction(0, BTC, 100, 500));
 		assert!(System::events()
@@ -514,6 +596,7 @@ fn debit_auction_end_handler_without_bid() {
 
 		assert_eq!(AuctionManagerModule::debit_auctions(0).is_some(), true);
 		AuctionManagerModule::on_auction_ended(0, None);
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
 		let auction_passed_event = Event::auction_manager(crate::Event::CancelAuction(0));
 		assert!(System::events()
 			.iter()
@@ -541,6 +624,7 @@ fn debit_auction_end_handler_with_bid() {
 
 		assert_eq!(AuctionManagerModule::debit_auctions(0).is_some(), true);
 		AuctionManagerModule::on_auction_ended(0, Some((BOB, 100)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
 		let debit_auction_deal_event = Event::auction_manager(crate::Event::DebitAuctionDealt(0, 300, BOB, 100));
 		assert!(System::events()
 			.iter()
@@ -565,6 +649,7 @@ fn surplus_auction_end_handler_without_bid() {
 
 		assert_eq!(AuctionManagerModule::surplus_auctions(0).is_some(), true);
 		AuctionManagerModule::on_auction_ended(0, None);
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
 		let auction_passed_event = Event::auction_manager(crate::Event::CancelAuction(0));
 		assert!(System::events()
 			.iter()
@@ -595,6 +680,7 @@ fn surplus_auction_end_handler_with_bid() {
 
 		assert_eq!(AuctionManagerModule::surplus_auctions(0).is_some(), true);
 		AuctionManagerModule::on_auction_ended(0, Some((BOB, 500)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
 		let surplus_auction_deal_event = Event::auction_manager(crate::Event::SurplusAuctionDealt(0, 100, BOB, 500));
 		assert!(System::events()
 			.iter()
@@ -776,3 +862,936 @@ fn cancel_collateral_auction_work() {
 		assert_eq!(bob_ref_count_1, bob_ref_count_0 - 1);
 	});
 }
+
+fn dutch_auction_param() -> DutchAuctionParam {
+	DutchAuctionParam {
+		start_price_ratio: Ratio::saturating_from_rational(6, 5),
+		decay_per_block: Ratio::saturating_from_rational(1, 100),
+		floor_price_ratio: Ratio::saturating_from_rational(4, 5),
+	}
+}
+
+#[test]
+fn set_dutch_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(Origin::signed(ALICE), BTC, Some(dutch_auction_param())),
+			BadOrigin,
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				Origin::signed(1),
+				BTC,
+				Some(DutchAuctionParam {
+					floor_price_ratio: Ratio::saturating_from_integer(2),
+					..dutch_auction_param()
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParam,
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				Origin::signed(1),
+				BTC,
+				Some(DutchAuctionParam {
+					decay_per_block: Ratio::zero(),
+					..dutch_auction_param()
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParam,
+		);
+
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			Some(dutch_auction_param())
+		));
+		let update_event = Event::auction_manager(crate::Event::DutchAuctionParamsUpdated(
+			BTC,
+			Some(dutch_auction_param()),
+		));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			AuctionManagerModule::dutch_auction_params(BTC),
+			Some(dutch_auction_param())
+		);
+
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			None
+		));
+		assert_eq!(AuctionManagerModule::dutch_auction_params(BTC), None);
+	});
+}
+
+#[test]
+fn dutch_collateral_auction_methods() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			Some(dutch_auction_param())
+		));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		let dutch_auction = AuctionManagerModule::dutch_collateral_auctions(0).unwrap();
+		assert_eq!(dutch_auction.price_after(0), Price::saturating_from_rational(6, 5));
+		assert_eq!(dutch_auction.price_after(10), Price::saturating_from_rational(11, 10));
+		assert_eq!(dutch_auction.price_after(1000), Price::saturating_from_rational(4, 5));
+		assert_eq!(
+			dutch_auction.deal_amounts(Price::saturating_from_rational(11, 10)),
+			(60, 54)
+		);
+		assert_eq!(
+			dutch_auction.deal_amounts(Price::saturating_from_rational(1, 2)),
+			(50, 100)
+		);
+
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 0));
+		let dutch_auction_with_zero_target = AuctionManagerModule::dutch_collateral_auctions(1).unwrap();
+		assert_eq!(
+			dutch_auction_with_zero_target.deal_amounts(Price::saturating_from_rational(11, 10)),
+			(110, 100)
+		);
+	});
+}
+
+#[test]
+fn new_dutch_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			Some(dutch_auction_param())
+		));
+		let ref_count_0 = System::refs(&ALICE);
+
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		let new_dutch_auction_event = Event::auction_manager(crate::Event::NewDutchCollateralAuction(
+			0,
+			BTC,
+			100,
+			60,
+			Price::saturating_from_rational(6, 5),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == new_dutch_auction_event));
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).is_some(), true);
+		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 60);
+		assert_eq!(System::refs(&ALICE), ref_count_0 + 1);
+
+		// dutch collateral auction can not be bid
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(BOB), 0, 100),
+			orml_auction::Error::<Runtime>::BidNotAccepted,
+		);
+
+		// fallback to english auction without oracle price
+		MockPriceSource::set_relative_price(None);
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(1).is_some(), false);
+		assert_eq!(AuctionManagerModule::collateral_auctions(1).is_some(), true);
+	});
+}
+
+#[test]
+fn take_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::take_collateral_auction(Origin::signed(BOB), 0),
+			Error::<Runtime>::AuctionNotExists,
+		);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			Some(dutch_auction_param())
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		let alice_ref_count_0 = System::refs(&ALICE);
+
+		// the price decays to 1.1 after 10 blocks
+		System::set_block_number(11);
+		assert_ok!(AuctionManagerModule::take_collateral_auction(Origin::signed(BOB), 0));
+		let dealt_event = Event::auction_manager(crate::Event::CollateralAuctionDealt(0, BTC, 54, BOB, 60));
+		assert!(System::events().iter().any(|record| record.event == dealt_event));
+
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 940);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1054);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1046);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 60);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
+		assert_eq!(System::refs(&ALICE), alice_ref_count_0 - 1);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		mock_shutdown();
+		assert_noop!(
+			AuctionManagerModule::take_collateral_auction(Origin::signed(BOB), 1),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
+
+#[test]
+fn dutch_collateral_auction_expire_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			Some(dutch_auction_param())
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		assert_eq!(AuctionModule::auction_info(0).unwrap().end, Some(201));
+		let alice_ref_count_0 = System::refs(&ALICE);
+
+		// the expired dutch auction can not be taken
+		AuctionManagerModule::on_auction_ended(0, None);
+		assert_noop!(
+			AuctionManagerModule::take_collateral_auction(Origin::signed(BOB), 0),
+			Error::<Runtime>::AuctionInSettlement,
+		);
+
+		// re-listed as an english auction without the no-bid max slippage
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		let expired_event = Event::auction_manager(crate::Event::DutchCollateralAuctionExpired(0));
+		assert!(System::events().iter().any(|record| record.event == expired_event));
+		let new_collateral_auction_event = Event::auction_manager(crate::Event::NewCollateralAuction(1, BTC, 100, 60));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == new_collateral_auction_event));
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::collateral_auctions(1).is_some(), true);
+		assert_eq!(AuctionModule::auction_info(1).unwrap().end, None);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 60);
+		assert_eq!(System::refs(&ALICE), alice_ref_count_0);
+
+		// sold into DEX within the no-bid max slippage
+		assert_ok!(AuctionManagerModule::set_no_bid_sale_max_slippage(
+			Origin::signed(1),
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(2).is_some(), true);
+		AuctionManagerModule::on_auction_ended(2, None);
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		let dex_take_collateral_auction =
+			Event::auction_manager(crate::Event::DEXTakeCollateralAuction(2, BTC, 100, 500));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == dex_take_collateral_auction));
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(2).is_some(), false);
+		assert_eq!(AuctionManagerModule::collateral_auctions(3).is_some(), false);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1440);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 60);
+		assert_eq!(System::refs(&ALICE), alice_ref_count_0);
+	});
+}
+
+#[test]
+fn cancel_dutch_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			Some(dutch_auction_param())
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		let alice_ref_count_0 = System::refs(&ALICE);
+
+		mock_shutdown();
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 0));
+		let cancel_auction_event = Event::auction_manager(crate::Event::CancelAuction(0));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == cancel_auction_event));
+
+		// refund the collateral not needed to offset the target in settle price
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1040);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 60);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
+		assert_eq!(System::refs(&ALICE), alice_ref_count_0 - 1);
+	});
+}
+
+#[test]
+fn set_minimum_increment_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let minimum_increment = MinimumIncrement {
+			absolute: 20,
+			rate: Rate::saturating_from_rational(1, 10),
+		};
+		assert_noop!(
+			AuctionManagerModule::set_minimum_increment(
+				Origin::signed(ALICE),
+				AuctionType::Collateral,
+				Some(minimum_increment.clone())
+			),
+			BadOrigin,
+		);
+
+		assert_ok!(AuctionManagerModule::set_minimum_increment(
+			Origin::signed(1),
+			AuctionType::Collateral,
+			Some(minimum_increment.clone())
+		));
+		let update_event = Event::auction_manager(crate::Event::MinimumIncrementUpdated(
+			AuctionType::Collateral,
+			Some(minimum_increment.clone()),
+		));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			AuctionManagerModule::minimum_increments(AuctionType::Collateral),
+			Some(minimum_increment)
+		);
+		assert_eq!(AuctionManagerModule::minimum_increments(AuctionType::Debit), None);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(AuctionType::Collateral, 1, 1),
+			Rate::saturating_from_rational(1, 10)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(AuctionType::Collateral, 2001, 1),
+			Rate::saturating_from_rational(1, 5)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(AuctionType::Debit, 1, 1),
+			Rate::saturating_from_rational(1, 20)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_absolute_increment(AuctionType::Collateral),
+			20
+		);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_absolute_increment(AuctionType::Debit),
+			0
+		);
+
+		assert_ok!(AuctionManagerModule::set_minimum_increment(
+			Origin::signed(1),
+			AuctionType::Collateral,
+			None
+		));
+		assert_eq!(AuctionManagerModule::minimum_increments(AuctionType::Collateral), None);
+	});
+}
+
+#[test]
+fn bid_with_minimum_increment_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AuctionManagerModule::set_minimum_increment(
+			Origin::signed(1),
+			AuctionType::Collateral,
+			Some(MinimumIncrement {
+				absolute: 20,
+				rate: Rate::saturating_from_rational(1, 10),
+			})
+		));
+		assert_ok!(AuctionManagerModule::set_minimum_increment(
+			Origin::signed(1),
+			AuctionType::Surplus,
+			Some(MinimumIncrement {
+				absolute: 50,
+				rate: Rate::zero(),
+			})
+		));
+
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		// the increment must reach the absolute minimum
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(1, 0, (BOB, 19), None).accept_bid,
+			false
+		);
+		assert_eq!(AuctionManagerModule::on_new_bid(1, 0, (BOB, 20), None).accept_bid, true);
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(2, 0, (CAROL, 39), Some((BOB, 20))).accept_bid,
+			false
+		);
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(2, 0, (CAROL, 40), Some((BOB, 20))).accept_bid,
+			true
+		);
+
+		// the increment must reach the rate minimum
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 500));
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(1, 1, (BOB, 49), None).accept_bid,
+			false
+		);
+		assert_eq!(AuctionManagerModule::on_new_bid(1, 1, (BOB, 50), None).accept_bid, true);
+
+		// the auction types without minimum increment use the default
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(1, 2, (BOB, 100), None).accept_bid,
+			true
+		);
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(2, 2, (CAROL, 105), Some((BOB, 100))).accept_bid,
+			true
+		);
+
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(1, 3, (BOB, 49), None).accept_bid,
+			false
+		);
+		assert_eq!(AuctionManagerModule::on_new_bid(1, 3, (BOB, 50), None).accept_bid, true);
+	});
+}
+
+#[test]
+fn anti_sniping_extension_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_eq!(AuctionModule::auction_info(0).unwrap().end, Some(101));
+
+		// the bid out of the anti-sniping window
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(50, 0, (BOB, 100), None).auction_end_change,
+			Change::NewValue(Some(150))
+		);
+		assert_eq!(AuctionManagerModule::anti_sniping_extensions(0), 0);
+
+		// the bids within the anti-sniping window
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(95, 0, (CAROL, 105), Some((BOB, 100))).auction_end_change,
+			Change::NewValue(Some(215))
+		);
+		let extend_event = Event::auction_manager(crate::Event::AuctionEndExtended(0, 20, 215));
+		assert!(System::events().iter().any(|record| record.event == extend_event));
+		assert_eq!(AuctionManagerModule::anti_sniping_extensions(0), 20);
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(96, 0, (BOB, 111), Some((CAROL, 105))).auction_end_change,
+			Change::NewValue(Some(216))
+		);
+		assert_eq!(AuctionManagerModule::anti_sniping_extensions(0), 40);
+
+		// the total extension is bounded
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(97, 0, (CAROL, 117), Some((BOB, 111))).auction_end_change,
+			Change::NewValue(Some(207))
+		);
+		assert_eq!(AuctionManagerModule::anti_sniping_extensions(0), 50);
+		assert_eq!(
+			AuctionManagerModule::on_new_bid(98, 0, (BOB, 123), Some((CAROL, 117))).auction_end_change,
+			Change::NewValue(Some(198))
+		);
+		assert_eq!(AuctionManagerModule::anti_sniping_extensions(0), 50);
+
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 123)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		assert_eq!(AuctionManagerModule::anti_sniping_extensions(0), 0);
+	});
+}
+
+#[test]
+fn settle_pending_auctions_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let settle_weight = <() as WeightInfo>::settle_auction();
+		let read_weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+		let write_weight = <Runtime as frame_system::Config>::DbWeight::get().writes(1);
+
+		// nothing to settle
+		assert_eq!(
+			AuctionManagerModule::settle_pending_auctions(settle_weight),
+			read_weight
+		);
+
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 300);
+
+		AuctionManagerModule::on_auction_ended(0, None);
+		AuctionManagerModule::on_auction_ended(1, None);
+		AuctionManagerModule::on_auction_ended(2, None);
+		assert_eq!(AuctionManagerModule::settlement_queue_range(), (0, 3));
+		assert_eq!(AuctionManagerModule::settlement_queue(2), Some(2));
+		assert_eq!(AuctionManagerModule::pending_settlements(2), Some(None));
+		// the ended auctions are not settled yet
+		assert_eq!(AuctionManagerModule::surplus_auctions(0).is_some(), true);
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 300);
+
+		// the auction waiting for settlement can not be cancelled
+		assert_noop!(
+			AuctionManagerModule::cancel_auction(0),
+			Error::<Runtime>::AuctionInSettlement,
+		);
+
+		// settle as many as the weight budget allows
+		let max_weight = read_weight + settle_weight * 2 + write_weight;
+		assert_eq!(AuctionManagerModule::settle_pending_auctions(max_weight), max_weight);
+		assert_eq!(AuctionManagerModule::settlement_queue_range(), (2, 3));
+		assert_eq!(AuctionManagerModule::settlement_queue(0), None);
+		assert_eq!(AuctionManagerModule::pending_settlements(0), None);
+		assert_eq!(AuctionManagerModule::pending_settlements(2), Some(None));
+		assert_eq!(AuctionManagerModule::surplus_auctions(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::surplus_auctions(1).is_some(), false);
+		assert_eq!(AuctionManagerModule::surplus_auctions(2).is_some(), true);
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 100);
+
+		// the rest are carried over to the next block
+		assert_eq!(
+			<AuctionManagerModule as Hooks<u64>>::on_initialize(2),
+			read_weight + settle_weight + write_weight
+		);
+		assert_eq!(AuctionManagerModule::settlement_queue_range(), (3, 3));
+		assert_eq!(AuctionManagerModule::pending_settlements(2), None);
+		assert_eq!(AuctionManagerModule::surplus_auctions(2).is_some(), false);
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 0);
+	});
+}
+
+#[test]
+fn force_cancel_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 100));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 900);
+		assert_noop!(AuctionManagerModule::force_cancel(Origin::signed(BOB), 0), BadOrigin);
+
+		// cancel without shutdown
+		assert_ok!(AuctionManagerModule::force_cancel(Origin::signed(1), 0));
+		let cancel_auction_event = Event::auction_manager(crate::Event::CancelAuction(0));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == cancel_auction_event));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(AuctionManagerModule::debit_auctions(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 0);
+		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
+
+		assert_noop!(
+			AuctionManagerModule::force_cancel(Origin::signed(1), 0),
+			Error::<Runtime>::AuctionNotExists,
+		);
+	});
+}
+
+#[test]
+fn relist_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 80));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 920);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 80);
+		let alice_ref_count_0 = System::refs(&ALICE);
+		let bob_ref_count_0 = System::refs(&BOB);
+
+		assert_noop!(
+			AuctionManagerModule::relist(Origin::signed(BOB), 0, RelistParams::Collateral { target: 200 }),
+			BadOrigin,
+		);
+		assert_noop!(
+			AuctionManagerModule::relist(Origin::signed(1), 0, RelistParams::Surplus),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		assert_ok!(AuctionManagerModule::relist(
+			Origin::signed(1),
+			0,
+			RelistParams::Collateral { target: 200 }
+		));
+		let relist_event = Event::auction_manager(crate::Event::AuctionRelisted(0));
+		assert!(System::events().iter().any(|record| record.event == relist_event));
+		let new_collateral_auction_event = Event::auction_manager(crate::Event::NewCollateralAuction(1, BTC, 10, 200));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == new_collateral_auction_event));
+
+		// the bidder is refunded and the collateral remains in CDP treasury
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 80);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 10);
+		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::collateral_auctions(1).unwrap().target, 200);
+		assert_eq!(AuctionModule::auction_info(1).is_some(), true);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 10);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 200);
+		assert_eq!(System::refs(&ALICE), alice_ref_count_0);
+		assert_eq!(System::refs(&BOB), bob_ref_count_0 - 1);
+
+		// the collateral auction in reverse stage can not be re-listed
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 1, 200));
+		assert_noop!(
+			AuctionManagerModule::relist(Origin::signed(1), 1, RelistParams::Collateral { target: 300 }),
+			Error::<Runtime>::InReverseStage,
+		);
+
+		mock_shutdown();
+		assert_noop!(
+			AuctionManagerModule::relist(Origin::signed(1), 1, RelistParams::Collateral { target: 300 }),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
+
+#[test]
+fn relist_dutch_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			Some(dutch_auction_param())
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		let alice_ref_count_0 = System::refs(&ALICE);
+
+		// re-listed with the current oracle price
+		System::set_block_number(11);
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(2)));
+		assert_ok!(AuctionManagerModule::relist(
+			Origin::signed(1),
+			0,
+			RelistParams::Collateral { target: 80 }
+		));
+		let relist_event = Event::auction_manager(crate::Event::AuctionRelisted(0));
+		assert!(System::events().iter().any(|record| record.event == relist_event));
+		let new_dutch_auction_event = Event::auction_manager(crate::Event::NewDutchCollateralAuction(
+			1,
+			BTC,
+			100,
+			80,
+			Price::saturating_from_rational(12, 5),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == new_dutch_auction_event));
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(1).is_some(), true);
+		assert_eq!(AuctionModule::auction_info(1).unwrap().end, Some(211));
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 80);
+		assert_eq!(System::refs(&ALICE), alice_ref_count_0);
+	});
+}
+
+#[test]
+fn relist_debit_and_surplus_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 100));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 900);
+
+		assert_ok!(AuctionManagerModule::relist(
+			Origin::signed(1),
+			0,
+			RelistParams::Debit { initial_amount: 300 }
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(AuctionManagerModule::debit_auctions(0).is_some(), false);
+		let debit_auction = AuctionManagerModule::debit_auctions(1).unwrap();
+		assert_eq!(debit_auction.initial_amount, 300);
+		assert_eq!(debit_auction.fix, 100);
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 100);
+
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 2, 50));
+		assert_eq!(Tokens::free_balance(ACA, &BOB), 950);
+
+		assert_ok!(AuctionManagerModule::relist(
+			Origin::signed(1),
+			2,
+			RelistParams::Surplus
+		));
+		assert_eq!(Tokens::free_balance(ACA, &BOB), 1000);
+		assert_eq!(AuctionManagerModule::surplus_auctions(2).is_some(), false);
+		assert_eq!(AuctionManagerModule::surplus_auctions(3).unwrap().amount, 100);
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 100);
+	});
+}
+
+#[test]
+fn active_auctions_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(AuctionManagerModule::active_auctions(0, 10), vec![]);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 20));
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(1),
+			BTC,
+			Some(dutch_auction_param())
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+
+		assert_eq!(
+			AuctionManagerModule::active_auctions(0, 10),
+			vec![
+				ActiveAuctionInfo {
+					id: 0,
+					lot: AuctionLot::Collateral {
+						currency_id: BTC,
+						amount: 10,
+						target: 100,
+					},
+					bid: Some((BOB, 20)),
+					minimum_next_bid: 25,
+					end: Some(101),
+				},
+				ActiveAuctionInfo {
+					id: 1,
+					lot: AuctionLot::Debit { amount: 200, fix: 100 },
+					bid: None,
+					minimum_next_bid: 100,
+					end: Some(101),
+				},
+				ActiveAuctionInfo {
+					id: 2,
+					lot: AuctionLot::Surplus { amount: 100 },
+					bid: None,
+					minimum_next_bid: 1,
+					end: None,
+				},
+				ActiveAuctionInfo {
+					id: 3,
+					lot: AuctionLot::DutchCollateral {
+						currency_id: BTC,
+						amount: 100,
+						target: 60,
+						price: Price::saturating_from_rational(6, 5),
+					},
+					bid: None,
+					minimum_next_bid: 60,
+					end: Some(201),
+				},
+			]
+		);
+
+		// paginated by the start id and limit
+		assert_eq!(
+			AuctionManagerModule::active_auctions(1, 2)
+				.iter()
+				.map(|info| info.id)
+				.collect::<Vec<_>>(),
+			vec![1, 2]
+		);
+
+		// the ended auction waiting for settlement is excluded
+		AuctionManagerModule::on_auction_ended(1, None);
+		AuctionModule::remove_auction(1);
+		assert_eq!(
+			AuctionManagerModule::active_auctions(0, 10)
+				.iter()
+				.map(|info| info.id)
+				.collect::<Vec<_>>(),
+			vec![0, 2, 3]
+		);
+	});
+}
+
+#[test]
+fn partial_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 10, 10),
+			Error::<Runtime>::AuctionNotExists,
+		);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 0, 10),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 101, 10),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 10, 0),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		// lower than the min proportion of the lot
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 9, 10),
+			Error::<Runtime>::InvalidAmount,
+		);
+
+		// the price per unit of collateral is lower than the whole lot bid
+		assert_ok!(AuctionModule::bid(Origin::signed(CAROL), 0, 20));
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 50, 5),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		let bob_ref_count_0 = System::refs(&BOB);
+
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 50, 20));
+		let partial_bid_event = Event::auction_manager(crate::Event::PartialBidPlaced(0, BOB, 50, 20));
+		assert!(System::events().iter().any(|record| record.event == partial_bid_event));
+		assert_eq!(
+			AuctionManagerModule::partial_bids(0),
+			vec![(
+				BOB,
+				PartialBid {
+					amount: 50,
+					payment: 20
+				}
+			)]
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 980);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 40);
+		assert_eq!(System::refs(&BOB), bob_ref_count_0 + 1);
+		assert_eq!(AuctionModule::auction_info(0).unwrap().end, Some(101));
+
+		// replace the previous partial bid
+		System::set_block_number(2);
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 40, 30));
+		assert_eq!(
+			AuctionManagerModule::partial_bids(0),
+			vec![(
+				BOB,
+				PartialBid {
+					amount: 40,
+					payment: 30
+				}
+			)]
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 970);
+		assert_eq!(System::refs(&BOB), bob_ref_count_0 + 1);
+		assert_eq!(AuctionModule::auction_info(0).unwrap().end, Some(102));
+
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(ALICE), 0, 10, 10));
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(CAROL), 0, 10, 10));
+		assert_ok!(CDPTreasuryModule::issue_debit(&4, 100, false));
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(4), 0, 10, 7),
+			Error::<Runtime>::TooManyPartialBids,
+		);
+
+		// the lowest partial bid is evicted and refunded by the better one
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(4), 0, 10, 10));
+		let evicted_event = Event::auction_manager(crate::Event::PartialBidEvicted(0, BOB, 30));
+		assert!(System::events().iter().any(|record| record.event == evicted_event));
+		assert_eq!(
+			AuctionManagerModule::partial_bids(0)
+				.into_iter()
+				.map(|(bidder, _)| bidder)
+				.collect::<Vec<_>>(),
+			vec![ALICE, CAROL, 4]
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(System::refs(&BOB), bob_ref_count_0);
+
+		// the whole lot bid has reached the target
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 200));
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(ALICE), 0, 10, 20),
+			Error::<Runtime>::InReverseStage,
+		);
+	});
+}
+
+#[test]
+fn partial_bids_end_handler_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		// the oversubscribed partial bids are filled from the best price at the
+		// uniform clearing price, and beat the whole lot bid
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 1000));
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 140));
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 60, 120));
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(CAROL), 0, 60, 90));
+		let bob_ref_count_0 = System::refs(&BOB);
+		AuctionManagerModule::on_auction_ended(0, Some((ALICE, 140)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		let bob_dealt_event = Event::auction_manager(crate::Event::PartialBidDealt(0, BTC, 60, BOB, 90));
+		assert!(System::events().iter().any(|record| record.event == bob_dealt_event));
+		let carol_dealt_event = Event::auction_manager(crate::Event::PartialBidDealt(0, BTC, 40, CAROL, 60));
+		assert!(System::events().iter().any(|record| record.event == carol_dealt_event));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 910);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1060);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 940);
+		assert_eq!(Tokens::free_balance(BTC, &CAROL), 940);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1000);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 350);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 200);
+		assert_eq!(AuctionManagerModule::partial_bids(0), vec![]);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(System::refs(&BOB), bob_ref_count_0 - 1);
+
+		// the partial bids reaching the target are scaled down
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 1, 30));
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(BOB), 1, 50, 100));
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(CAROL), 1, 50, 60));
+		AuctionManagerModule::on_auction_ended(1, Some((ALICE, 30)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		let bob_dealt_event = Event::auction_manager(crate::Event::PartialBidDealt(1, BTC, 41, BOB, 49));
+		assert!(System::events().iter().any(|record| record.event == bob_dealt_event));
+		let carol_dealt_event = Event::auction_manager(crate::Event::PartialBidDealt(1, BTC, 41, CAROL, 49));
+		assert!(System::events().iter().any(|record| record.event == carol_dealt_event));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 861);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1101);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 891);
+		assert_eq!(Tokens::free_balance(BTC, &CAROL), 881);
+		// the whole lot bidder is refunded, and the unsold collateral is refunded to
+		// the recipient
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1000);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1018);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+
+		// the partial bids no better than the whole lot bid are refunded
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 100));
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(BOB), 2, 100, 40));
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 2, 50));
+		AuctionManagerModule::on_auction_ended(2, Some((ALICE, 50)));
+		AuctionManagerModule::settle_pending_auctions(MaxSettlementWeight::get());
+		let auction_dealt_event = Event::auction_manager(crate::Event::CollateralAuctionDealt(2, BTC, 100, ALICE, 50));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == auction_dealt_event));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 861);
+		assert_eq!(AuctionManagerModule::partial_bids(2), vec![]);
+	});
+}
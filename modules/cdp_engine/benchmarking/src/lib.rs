@@ -10,10 +10,10 @@ use sp_std::prelude::*;
 use sp_std::vec;
 
 use frame_benchmarking::{account, benchmarks};
-use frame_support::traits::Get;
+use frame_support::traits::{Get, OnInitialize};
 use frame_system::RawOrigin;
 use sp_runtime::{
-	traits::{StaticLookup, UniqueSaturatedInto},
+	traits::{Saturating, StaticLookup, UniqueSaturatedInto},
 	FixedPointNumber,
 };
 
@@ -133,8 +133,9 @@ benchmarks! {
 		// adjust position
 		CdpEngine::<T>::adjust_position(&owner, currency_id, collateral_amount, min_debit_amount)?;
 
-		// modify liquidation rate to make the cdp unsafe
-		CdpEngine::<T>::set_collateral_params(
+		// modify liquidation rate to make the cdp unsafe, the tightening takes effect
+		// after the delay
+		CdpEngine::<T>::schedule_set_collateral_params(
 			RawOrigin::Root.into(),
 			currency_id,
 			Change::NoChange,
@@ -143,6 +144,9 @@ benchmarks! {
 			Change::NoChange,
 			Change::NoChange,
 		)?;
+		CdpEngine::<T>::on_initialize(
+			frame_system::Module::<T>::block_number().saturating_add(T::ParameterChangeDelay::get())
+		);
 	}: liquidate(RawOrigin::None, currency_id, owner_lookup)
 
 	// `liquidate` by dex
@@ -187,8 +191,9 @@ benchmarks! {
 		// adjust position
 		CdpEngine::<T>::adjust_position(&owner, currency_id, collateral_amount, min_debit_amount)?;
 
-		// modify liquidation rate to make the cdp unsafe
-		CdpEngine::<T>::set_collateral_params(
+		// modify liquidation rate to make the cdp unsafe, the tightening takes effect
+		// after the delay
+		CdpEngine::<T>::schedule_set_collateral_params(
 			RawOrigin::Root.into(),
 			currency_id,
 			Change::NoChange,
@@ -197,6 +202,9 @@ benchmarks! {
 			Change::NoChange,
 			Change::NoChange,
 		)?;
+		CdpEngine::<T>::on_initialize(
+			frame_system::Module::<T>::block_number().saturating_add(T::ParameterChangeDelay::get())
+		);
 	}: liquidate(RawOrigin::None, currency_id, owner_lookup)
 	verify {
 		let (other_currency_amount, base_currency_amount) = Dex::<T>::get_liquidity_pool(base_currency_id, currency_id);
@@ -9,13 +9,17 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{pallet_prelude::*, transactional};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Happened, UnixTime},
+	transactional,
+};
 use frame_system::{
 	offchain::{SendTransactionTypes, SubmitTransaction},
 	pallet_prelude::*,
 };
 use loans::Position;
-use orml_traits::Change;
+use orml_traits::{BasicReservableCurrency, Change};
 use orml_utilities::{IterableStorageDoubleMapExtended, OffchainErr};
 use primitives::{Amount, Balance, CurrencyId};
 use sp_runtime::{
@@ -24,7 +28,9 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{BlakeTwo256, Bounded, Convert, Hash, Saturating, StaticLookup, Zero},
+	traits::{
+		BlakeTwo256, Bounded, CheckedDiv, Convert, Hash, One, Saturating, StaticLookup, UniqueSaturatedInto, Zero,
+	},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
@@ -46,17 +52,29 @@ pub use module::*;
 
 pub trait WeightInfo {
 	fn set_collateral_params() -> Weight;
+	fn schedule_set_collateral_params() -> Weight;
+	fn cancel_collateral_params_change() -> Weight;
 	fn set_global_params() -> Weight;
 	fn liquidate_by_auction() -> Weight;
 	fn liquidate_by_dex() -> Weight;
 	fn settle() -> Weight;
+	fn bond_liquidator() -> Weight;
+	fn unbond_liquidator() -> Weight;
+	fn withdraw_liquidator_bond() -> Weight;
+	fn slash_liquidator() -> Weight;
 }
 
 pub const OFFCHAIN_WORKER_DATA: &[u8] = b"acala/cdp-engine/data/";
 pub const OFFCHAIN_WORKER_LOCK: &[u8] = b"acala/cdp-engine/lock/";
 pub const OFFCHAIN_WORKER_MAX_ITERATIONS: &[u8] = b"acala/cdp-engine/max-iterations/";
+pub const OFFCHAIN_WORKER_MAX_SUBMISSIONS: &[u8] = b"acala/cdp-engine/max-submissions/";
+pub const OFFCHAIN_WORKER_SHARDS: &[u8] = b"acala/cdp-engine/shards/";
+pub const OFFCHAIN_WORKER_ROTATION_OFFSET: &[u8] = b"acala/cdp-engine/rotation-offset/";
 pub const LOCK_DURATION: u64 = 100;
 pub const DEFAULT_MAX_ITERATIONS: u32 = 1000;
+pub const DEFAULT_MAX_SUBMISSIONS: u32 = 100;
+pub const DEFAULT_SHARDS: u32 = 4;
+pub const MAX_SHARDS: u32 = 256;
 
 pub type LoansOf<T> = loans::Module<T>;
 
@@ -68,7 +86,7 @@ pub struct RiskManagementParams {
 	/// type.
 	pub maximum_total_debit_value: Balance,
 
-	/// Extra stability fee rate, `None` value means not set
+	/// Extra stability fee rate per second, `None` value means not set
 	pub stability_fee: Option<Rate>,
 
 	/// Liquidation ratio, when the collateral ratio of
@@ -93,6 +111,50 @@ type ChangeOptionRate = Change<Option<Rate>>;
 type ChangeOptionRatio = Change<Option<Ratio>>;
 type ChangeBalance = Change<Balance>;
 
+/// The scheduled change of risk management params, which takes effect
+/// after the parameter change delay
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct CollateralParamsChange<BlockNumber> {
+	/// The change of extra stability fee rate
+	pub stability_fee: ChangeOptionRate,
+	/// The change of liquidation ratio
+	pub liquidation_ratio: ChangeOptionRatio,
+	/// The change of liquidation penalty rate
+	pub liquidation_penalty: ChangeOptionRate,
+	/// The change of required collateral ratio
+	pub required_collateral_ratio: ChangeOptionRatio,
+	/// The change of maximum total debit value
+	pub maximum_total_debit_value: ChangeBalance,
+	/// The block number at which the change takes effect
+	pub effective_at: BlockNumber,
+}
+
+/// The registry info of a bonded liquidator
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct LiquidatorInfo<BlockNumber> {
+	/// The bonded native currency amount
+	pub bond: Balance,
+	/// The block number after which the bond can be withdrawn, `None` means
+	/// the liquidator has not started unbonding
+	pub unlock_at: Option<BlockNumber>,
+}
+
+/// The storage version of the module
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum Releases {
+	/// The debit exchange rates are accumulated at every block
+	V1_0_0,
+	/// The debit exchange rates are accumulated lazily per second, from
+	/// `LastAccumulationSecs`
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
 /// Liquidation strategy available
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
 pub enum LiquidationStrategy {
@@ -114,6 +176,11 @@ pub mod module {
 		/// always do this.
 		type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
+		#[pallet::constant]
+		/// The delay of the scheduled risk management parameters change,
+		/// gives CDP owners notice before the change takes effect
+		type ParameterChangeDelay: Get<Self::BlockNumber>;
+
 		#[pallet::constant]
 		/// The list of valid collateral currency types
 		type CollateralCurrencyIds: Get<Vec<CurrencyId>>;
@@ -130,6 +197,16 @@ pub mod module {
 		/// The default liquidation penalty rate when liquidate unsafe CDP
 		type DefaultLiquidationPenalty: Get<Rate>;
 
+		#[pallet::constant]
+		/// The maximum change of the liquidation penalty rate in a single
+		/// update by `UpdateOrigin`, Root is not limited
+		type MaxLiquidationPenaltyStep: Get<Rate>;
+
+		#[pallet::constant]
+		/// The ratio of seized collateral rewarded to the keeper who triggers
+		/// the liquidation, capped by the liquidation penalty
+		type KeeperRewardRatio: Get<Rate>;
+
 		#[pallet::constant]
 		/// The minimum debit value to avoid debit dust
 		type MinimumDebitValue: Get<Balance>;
@@ -162,6 +239,33 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// Time used for accumulating the stability fee.
+		type UnixTime: UnixTime;
+
+		/// Currency for bonding of liquidators
+		type Currency: BasicReservableCurrency<Self::AccountId, Balance = Balance>;
+
+		#[pallet::constant]
+		/// The native currency amount a liquidator must bond to get priority
+		/// access to liquidations
+		type LiquidatorBond: Get<Balance>;
+
+		#[pallet::constant]
+		/// The length of the window in which only bonded liquidators can
+		/// liquidate an unsafe CDP, 0 means the window is disabled
+		type LiquidatorExclusiveWindow: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The delay before an unbonding liquidator can withdraw the bond,
+		/// it stays slashable during the delay
+		type LiquidatorUnbondingDelay: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The max confidence interval of the collateral price in proportion
+		/// to it, the liquidation ratio is widened by the confidence interval
+		/// beyond it
+		type MaxPriceConfidence: Get<Ratio>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -189,16 +293,46 @@ pub mod module {
 		AlreadyShutdown,
 		/// Must after system shutdown
 		MustAfterShutdown,
+		/// The CDP must be safe to be closed by its owner
+		MustBeSafe,
+		/// The change of liquidation penalty exceeds the max step
+		ExceedLiquidationPenaltyStep,
+		/// There's no pending risk management parameters change
+		NoPendingParameterChange,
+		/// The liquidator has bonded already
+		AlreadyBonded,
+		/// The liquidator has not bonded
+		NotBonded,
+		/// The liquidator is unbonding already
+		AlreadyUnbonding,
+		/// The bond of the liquidator is still locked
+		BondStillLocked,
+		/// Only bonded liquidators can liquidate the CDP in the exclusive
+		/// window, and only by swap with DEX
+		InLiquidatorExclusiveWindow,
+		/// The liquidations of the collateral are paused as its price is
+		/// locked
+		LiquidationPaused,
+		/// Tightening the liquidation ratio of the collateral type with debits
+		/// must be scheduled
+		TighteningMustBeScheduled,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Liquidate the unsafe CDP. \[collateral_type, owner,
-		/// collateral_amount, bad_debt_value, liquidation_strategy\]
-		LiquidateUnsafeCDP(CurrencyId, T::AccountId, Balance, Balance, LiquidationStrategy),
+		/// collateral_amount, bad_debt_value, penalty_amount,
+		/// liquidation_strategy\]
+		LiquidateUnsafeCDP(CurrencyId, T::AccountId, Balance, Balance, Balance, LiquidationStrategy),
 		/// Settle the CDP has debit. [collateral_type, owner]
 		SettleCDPInDebit(CurrencyId, T::AccountId),
+		/// Close the CDP has debit by DEX. \[collateral_type, owner,
+		/// sold_collateral_amount, refund_collateral_amount, debit_value\]
+		CloseCDPInDebitByDEX(CurrencyId, T::AccountId, Balance, Balance, Balance),
+		/// Reward the keeper who triggered the liquidation. \[collateral_type,
+		/// keeper, reward_collateral_amount\]
+		LiquidationKeeperRewarded(CurrencyId, T::AccountId, Balance),
 		/// The stability fee for specific collateral type updated.
 		/// \[collateral_type, new_stability_fee\]
 		StabilityFeeUpdated(CurrencyId, Option<Rate>),
@@ -217,15 +351,44 @@ pub mod module {
 		/// The global stability fee for all types of collateral updated.
 		/// \[new_global_stability_fee\]
 		GlobalStabilityFeeUpdated(Rate),
+		/// The risk management parameters change for specific collateral type
+		/// is scheduled. \[collateral_type, pending_change\]
+		PendingParameterChange(CurrencyId, CollateralParamsChange<T::BlockNumber>),
+		/// The pending risk management parameters change for specific
+		/// collateral type is cancelled. \[collateral_type\]
+		PendingParameterChangeCancelled(CurrencyId),
+		/// Liquidator bonded. \[liquidator, bond_amount\]
+		LiquidatorBonded(T::AccountId, Balance),
+		/// Liquidator started unbonding. \[liquidator, unlock_at\]
+		LiquidatorUnbonding(T::AccountId, T::BlockNumber),
+		/// Liquidator withdrew the bond. \[liquidator, bond_amount\]
+		LiquidatorBondWithdrawn(T::AccountId, Balance),
+		/// Liquidator was slashed. \[liquidator, slashed_amount\]
+		LiquidatorSlashed(T::AccountId, Balance),
+		/// The exclusive window of bonded liquidators for the unsafe CDP
+		/// started. \[collateral_type, owner, window_end\]
+		LiquidatorExclusiveWindowStarted(CurrencyId, T::AccountId, T::BlockNumber),
 	}
 
 	/// Mapping from collateral type to its exchange rate of debit units and
-	/// debit value
+	/// debit value, which accrues the stability fee per second and is
+	/// accumulated lazily whenever a CDP under the collateral type is touched
 	#[pallet::storage]
 	#[pallet::getter(fn debit_exchange_rate)]
 	pub type DebitExchangeRate<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, ExchangeRate, OptionQuery>;
 
-	/// Global stability fee rate for all types of collateral
+	/// Mapping from collateral type to the timestamp (in seconds) of the last
+	/// accumulation of its debit exchange rate
+	#[pallet::storage]
+	#[pallet::getter(fn last_accumulation_secs)]
+	pub type LastAccumulationSecs<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, u64, ValueQuery>;
+
+	/// The storage version of the module, `V2_0_0` for the new chains.
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	/// Global stability fee rate per second for all types of collateral
 	#[pallet::storage]
 	#[pallet::getter(fn global_stability_fee)]
 	pub type GlobalStabilityFee<T: Config> = StorageValue<_, Rate, ValueQuery>;
@@ -235,6 +398,26 @@ pub mod module {
 	#[pallet::getter(fn collateral_params)]
 	pub type CollateralParams<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, RiskManagementParams, ValueQuery>;
 
+	/// Mapping from collateral type to its pending risk management params
+	/// change
+	#[pallet::storage]
+	#[pallet::getter(fn pending_collateral_params)]
+	pub type PendingCollateralParams<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, CollateralParamsChange<T::BlockNumber>, OptionQuery>;
+
+	/// The registry of bonded liquidators
+	#[pallet::storage]
+	#[pallet::getter(fn liquidators)]
+	pub type Liquidators<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, LiquidatorInfo<T::BlockNumber>, OptionQuery>;
+
+	/// Mapping from unsafe CDP to the end of its exclusive window for bonded
+	/// liquidators
+	#[pallet::storage]
+	#[pallet::getter(fn liquidator_exclusive_window_end)]
+	pub type LiquidatorExclusiveWindowEnd<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		#[allow(clippy::type_complexity)]
@@ -284,6 +467,7 @@ pub mod module {
 				},
 			);
 			GlobalStabilityFee::<T>::put(self.global_stability_fee);
+			StorageVersion::<T>::put(Releases::V2_0_0);
 		}
 	}
 
@@ -292,37 +476,60 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		/// Issue interest in stable currency for all types of collateral has
-		/// debit when block end, and update their debit exchange rate
-		fn on_finalize(_now: T::BlockNumber) {
-			// collect stability fee for all types of collateral
-			if !T::EmergencyShutdown::is_shutdown() {
-				for currency_id in T::CollateralCurrencyIds::get() {
-					let debit_exchange_rate = Self::get_debit_exchange_rate(currency_id);
-					let stability_fee_rate = Self::get_stability_fee(currency_id);
-					let total_debits = <LoansOf<T>>::total_positions(currency_id).debit;
-					if !stability_fee_rate.is_zero() && !total_debits.is_zero() {
-						let debit_exchange_rate_increment = debit_exchange_rate.saturating_mul(stability_fee_rate);
-						let total_debit_value = Self::get_debit_value(currency_id, total_debits);
-						let issued_stable_coin_balance =
-							debit_exchange_rate_increment.saturating_mul_int(total_debit_value);
-
-						// issue stablecoin to surplus pool
-						if <T as Config>::CDPTreasury::on_system_surplus(issued_stable_coin_balance).is_ok() {
-							// update exchange rate when issue success
-							let new_debit_exchange_rate =
-								debit_exchange_rate.saturating_add(debit_exchange_rate_increment);
-							DebitExchangeRate::<T>::insert(currency_id, new_debit_exchange_rate);
-						}
+		/// Checkpoint the debit exchange rates of all collaterals at the
+		/// upgrade to the lazy accumulation, so the stability fee accrues from
+		/// now on rather than from the first touch.
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() != Releases::V1_0_0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let collateral_currency_ids = T::CollateralCurrencyIds::get();
+			for currency_id in collateral_currency_ids.iter() {
+				Self::accumulate_interest(*currency_id);
+			}
+			StorageVersion::<T>::put(Releases::V2_0_0);
+
+			let count = collateral_currency_ids.len() as Weight;
+			T::DbWeight::get().reads_writes(
+				count.saturating_mul(5).saturating_add(2),
+				count.saturating_mul(3).saturating_add(1),
+			)
+		}
+
+		/// Apply the pending risk management parameters changes which take
+		/// effect at this block.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let collateral_currency_ids = T::CollateralCurrencyIds::get();
+			let mut weight = T::DbWeight::get().reads(collateral_currency_ids.len() as Weight);
+			for currency_id in collateral_currency_ids {
+				if let Some(change) = Self::pending_collateral_params(currency_id) {
+					if change.effective_at <= now {
+						PendingCollateralParams::<T>::remove(currency_id);
+						Self::do_set_collateral_params(
+							currency_id,
+							change.stability_fee,
+							change.liquidation_ratio,
+							change.liquidation_penalty,
+							change.required_collateral_ratio,
+							change.maximum_total_debit_value,
+						);
+						weight = weight.saturating_add(T::WeightInfo::set_collateral_params());
 					}
 				}
 			}
+			weight
 		}
 
 		/// Runs after every block. Start offchain worker to check CDP and
 		/// submit unsigned tx to trigger liquidation or settlement.
+		///
+		/// The positions of each collateral type are split into shards by the
+		/// account hash, and every run scans one shard chosen by rotating with
+		/// the block number. A run stops at the iteration or submission limit
+		/// and resumes from there at the next visit of the shard.
 		fn offchain_worker(now: T::BlockNumber) {
-			if let Err(e) = Self::_offchain_worker() {
+			if let Err(e) = Self::_offchain_worker(now) {
 				debug::info!(
 					target: "cdp-engine offchain worker",
 					"cannot run offchain worker at {:?}: {:?}",
@@ -343,7 +550,17 @@ pub mod module {
 	impl<T: Config> Pallet<T> {
 		/// Liquidate unsafe CDP
 		///
-		/// The dispatch origin of this call must be _None_.
+		/// The dispatch origin of this call can be _None_ (submitted by
+		/// offchain worker) or _Signed_ by any keeper, who will be rewarded
+		/// with part of the seized collateral from the liquidation penalty.
+		///
+		/// If `LiquidatorExclusiveWindow` is set, the first liquidation of an
+		/// unsafe CDP by others than bonded liquidators starts the exclusive
+		/// window rather than liquidates it. In the window, only bonded
+		/// liquidators can liquidate the CDP, and only by swap with DEX. After
+		/// the window, anyone can liquidate the CDP, until the window is stale
+		/// for another `LiquidatorExclusiveWindow` and the next liquidation
+		/// starts a new window.
 		///
 		/// - `currency_id`: CDP's collateral type.
 		/// - `who`: CDP's owner.
@@ -354,10 +571,42 @@ pub mod module {
 			currency_id: CurrencyId,
 			who: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResultWithPostInfo {
-			ensure_none(origin)?;
+			let keeper = ensure_signed(origin.clone()).ok();
+			if keeper.is_none() {
+				ensure_none(origin)?;
+			}
 			let who = T::Lookup::lookup(who)?;
 			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-			Self::liquidate_unsafe_cdp(who, currency_id)?;
+			ensure!(!Self::is_liquidation_paused(currency_id), Error::<T>::LiquidationPaused);
+
+			let mut dex_only = false;
+			let exclusive_window = T::LiquidatorExclusiveWindow::get();
+			if !exclusive_window.is_zero() {
+				let now = <frame_system::Module<T>>::block_number();
+				let is_bonded_liquidator = keeper
+					.as_ref()
+					.map_or(false, |keeper| Self::is_bonded_liquidator(keeper));
+				let window_end = Self::active_exclusive_window_end(currency_id, &who);
+				let in_window = window_end.map_or(true, |window_end| now < window_end);
+
+				if is_bonded_liquidator {
+					dex_only = in_window;
+				} else if window_end.is_some() {
+					ensure!(!in_window, Error::<T>::InLiquidatorExclusiveWindow);
+				} else {
+					let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
+					ensure!(
+						Self::is_cdp_unsafe(currency_id, collateral, debit),
+						Error::<T>::MustBeUnsafe
+					);
+					let window_end = now.saturating_add(exclusive_window);
+					LiquidatorExclusiveWindowEnd::<T>::insert(currency_id, &who, window_end);
+					Self::deposit_event(Event::LiquidatorExclusiveWindowStarted(currency_id, who, window_end));
+					return Ok(().into());
+				}
+			}
+
+			Self::do_liquidate_unsafe_cdp(who, currency_id, keeper, dex_only)?;
 			Ok(().into())
 		}
 
@@ -390,13 +639,20 @@ pub mod module {
 		#[transactional]
 		pub fn set_global_params(origin: OriginFor<T>, global_stability_fee: Rate) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
+
+			// settle the interest accrued under the old stability fee
+			for currency_id in T::CollateralCurrencyIds::get() {
+				Self::accumulate_interest(currency_id);
+			}
 			GlobalStabilityFee::<T>::put(global_stability_fee);
 			Self::deposit_event(Event::GlobalStabilityFeeUpdated(global_stability_fee));
 			Ok(().into())
 		}
 
 		/// Update parameters related to risk management of CDP under specific
-		/// collateral type
+		/// collateral type. Raising the liquidation ratio of the collateral
+		/// type with debits must go through `schedule_set_collateral_params`,
+		/// only the loosening changes take effect immediately.
 		///
 		/// The dispatch origin of this call must be `UpdateOrigin`.
 		///
@@ -421,34 +677,195 @@ pub mod module {
 			required_collateral_ratio: ChangeOptionRatio,
 			maximum_total_debit_value: ChangeBalance,
 		) -> DispatchResultWithPostInfo {
+			let is_root = ensure_root(origin.clone()).is_ok();
 			T::UpdateOrigin::ensure_origin(origin)?;
 			ensure!(
 				T::CollateralCurrencyIds::get().contains(&currency_id),
 				Error::<T>::InvalidCollateralType,
 			);
 
-			let mut collateral_params = Self::collateral_params(currency_id);
-			if let Change::NewValue(update) = stability_fee {
-				collateral_params.stability_fee = update;
-				Self::deposit_event(Event::StabilityFeeUpdated(currency_id, update));
+			if !is_root {
+				if let Change::NewValue(update) = liquidation_penalty {
+					Self::check_liquidation_penalty_step(currency_id, update)?;
+				}
 			}
 			if let Change::NewValue(update) = liquidation_ratio {
-				collateral_params.liquidation_ratio = update;
-				Self::deposit_event(Event::LiquidationRatioUpdated(currency_id, update));
+				Self::check_liquidation_ratio_loosening(currency_id, update)?;
 			}
-			if let Change::NewValue(update) = liquidation_penalty {
-				collateral_params.liquidation_penalty = update;
-				Self::deposit_event(Event::LiquidationPenaltyUpdated(currency_id, update));
-			}
-			if let Change::NewValue(update) = required_collateral_ratio {
-				collateral_params.required_collateral_ratio = update;
-				Self::deposit_event(Event::RequiredCollateralRatioUpdated(currency_id, update));
-			}
-			if let Change::NewValue(val) = maximum_total_debit_value {
-				collateral_params.maximum_total_debit_value = val;
-				Self::deposit_event(Event::MaximumTotalDebitValueUpdated(currency_id, val));
+
+			Self::do_set_collateral_params(
+				currency_id,
+				stability_fee,
+				liquidation_ratio,
+				liquidation_penalty,
+				required_collateral_ratio,
+				maximum_total_debit_value,
+			);
+			Ok(().into())
+		}
+
+		/// Schedule to update parameters related to risk management of CDP
+		/// under specific collateral type, the change takes effect after
+		/// `ParameterChangeDelay` and replaces the pending change if exists,
+		/// so CDP owners get notice before liquidation ratios tighten.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `stability_fee`: extra stability fee rate, `None` means do not
+		///   update, `Some(None)` means update it to `None`.
+		/// - `liquidation_ratio`: liquidation ratio, `None` means do not
+		///   update, `Some(None)` means update it to `None`.
+		/// - `liquidation_penalty`: liquidation penalty, `None` means do not
+		///   update, `Some(None)` means update it to `None`.
+		/// - `required_collateral_ratio`: required collateral ratio, `None`
+		///   means do not update, `Some(None)` means update it to `None`.
+		/// - `maximum_total_debit_value`: maximum total debit value.
+		#[pallet::weight((T::WeightInfo::schedule_set_collateral_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn schedule_set_collateral_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			stability_fee: ChangeOptionRate,
+			liquidation_ratio: ChangeOptionRatio,
+			liquidation_penalty: ChangeOptionRate,
+			required_collateral_ratio: ChangeOptionRatio,
+			maximum_total_debit_value: ChangeBalance,
+		) -> DispatchResultWithPostInfo {
+			let is_root = ensure_root(origin.clone()).is_ok();
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			if !is_root {
+				if let Change::NewValue(update) = liquidation_penalty {
+					Self::check_liquidation_penalty_step(currency_id, update)?;
+				}
 			}
-			CollateralParams::<T>::insert(currency_id, collateral_params);
+
+			let change = CollateralParamsChange {
+				stability_fee,
+				liquidation_ratio,
+				liquidation_penalty,
+				required_collateral_ratio,
+				maximum_total_debit_value,
+				effective_at: <frame_system::Module<T>>::block_number().saturating_add(T::ParameterChangeDelay::get()),
+			};
+			PendingCollateralParams::<T>::insert(currency_id, change.clone());
+			Self::deposit_event(Event::PendingParameterChange(currency_id, change));
+			Ok(().into())
+		}
+
+		/// Cancel the pending risk management parameters change under
+		/// specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[pallet::weight((T::WeightInfo::cancel_collateral_params_change(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn cancel_collateral_params_change(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			PendingCollateralParams::<T>::take(currency_id).ok_or(Error::<T>::NoPendingParameterChange)?;
+			Self::deposit_event(Event::PendingParameterChangeCancelled(currency_id));
+			Ok(().into())
+		}
+
+		/// Bond native currency to register as a liquidator, who has
+		/// priority access to liquidations. The bond can be slashed by
+		/// governance for misbehaving, and stays slashable during the
+		/// unbonding delay.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::bond_liquidator())]
+		#[transactional]
+		pub fn bond_liquidator(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Liquidators::<T>::contains_key(&who), Error::<T>::AlreadyBonded);
+
+			let bond = T::LiquidatorBond::get();
+			<T as Config>::Currency::reserve(&who, bond)?;
+			Liquidators::<T>::insert(&who, LiquidatorInfo { bond, unlock_at: None });
+			Self::deposit_event(Event::LiquidatorBonded(who, bond));
+			Ok(().into())
+		}
+
+		/// Start unbonding, the liquidator loses the priority access
+		/// immediately, and can withdraw the bond after
+		/// `LiquidatorUnbondingDelay`.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::unbond_liquidator())]
+		#[transactional]
+		pub fn unbond_liquidator(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let unlock_at =
+				Liquidators::<T>::try_mutate(&who, |maybe_info| -> Result<T::BlockNumber, DispatchError> {
+					let info = maybe_info.as_mut().ok_or(Error::<T>::NotBonded)?;
+					ensure!(info.unlock_at.is_none(), Error::<T>::AlreadyUnbonding);
+					let unlock_at =
+						<frame_system::Module<T>>::block_number().saturating_add(T::LiquidatorUnbondingDelay::get());
+					info.unlock_at = Some(unlock_at);
+					Ok(unlock_at)
+				})?;
+			Self::deposit_event(Event::LiquidatorUnbonding(who, unlock_at));
+			Ok(().into())
+		}
+
+		/// Withdraw the bond after the unbonding delay.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::withdraw_liquidator_bond())]
+		#[transactional]
+		pub fn withdraw_liquidator_bond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let info = Self::liquidators(&who).ok_or(Error::<T>::NotBonded)?;
+			ensure!(
+				info.unlock_at.map_or(false, |unlock_at| unlock_at
+					<= <frame_system::Module<T>>::block_number()),
+				Error::<T>::BondStillLocked
+			);
+
+			<T as Config>::Currency::unreserve(&who, info.bond);
+			Liquidators::<T>::remove(&who);
+			Self::deposit_event(Event::LiquidatorBondWithdrawn(who, info.bond));
+			Ok(().into())
+		}
+
+		/// Slash the bond of the misbehaving liquidator, the liquidator loses
+		/// the priority access if the remaining bond is below
+		/// `LiquidatorBond`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `liquidator`: the liquidator to slash.
+		/// - `amount`: the amount to slash, capped by the bond.
+		#[pallet::weight((T::WeightInfo::slash_liquidator(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn slash_liquidator(
+			origin: OriginFor<T>,
+			liquidator: <T::Lookup as StaticLookup>::Source,
+			amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let liquidator = T::Lookup::lookup(liquidator)?;
+			let slashed_amount =
+				Liquidators::<T>::try_mutate(&liquidator, |maybe_info| -> Result<Balance, DispatchError> {
+					let info = maybe_info.as_mut().ok_or(Error::<T>::NotBonded)?;
+					let amount = sp_std::cmp::min(amount, info.bond);
+					let remain = <T as Config>::Currency::slash_reserved(&liquidator, amount);
+					let slashed_amount = amount.saturating_sub(remain);
+					info.bond = info.bond.saturating_sub(slashed_amount);
+					Ok(slashed_amount)
+				})?;
+			Self::deposit_event(Event::LiquidatorSlashed(liquidator, slashed_amount));
 			Ok(().into())
 		}
 	}
@@ -462,7 +879,16 @@ pub mod module {
 				Call::liquidate(currency_id, who) => {
 					let account = T::Lookup::lookup(who.clone())?;
 					let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &account);
-					if !Self::is_cdp_unsafe(*currency_id, collateral, debit) || T::EmergencyShutdown::is_shutdown() {
+					if !Self::is_cdp_unsafe(*currency_id, collateral, debit)
+						|| T::EmergencyShutdown::is_shutdown()
+						|| Self::is_liquidation_paused(*currency_id)
+					{
+						return InvalidTransaction::Stale.into();
+					}
+					// wait for the end of the exclusive window of bonded liquidators
+					if Self::active_exclusive_window_end(*currency_id, &account).map_or(false, |window_end| {
+						<frame_system::Module<T>>::block_number() < window_end
+					}) {
 						return InvalidTransaction::Stale.into();
 					}
 
@@ -494,6 +920,69 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	fn check_liquidation_penalty_step(currency_id: CurrencyId, update: Option<Rate>) -> DispatchResult {
+		let old_penalty = Self::collateral_params(currency_id)
+			.liquidation_penalty
+			.unwrap_or_else(T::DefaultLiquidationPenalty::get);
+		let new_penalty = update.unwrap_or_else(T::DefaultLiquidationPenalty::get);
+		let step = if new_penalty > old_penalty {
+			new_penalty.saturating_sub(old_penalty)
+		} else {
+			old_penalty.saturating_sub(new_penalty)
+		};
+		ensure!(
+			step <= T::MaxLiquidationPenaltyStep::get(),
+			Error::<T>::ExceedLiquidationPenaltyStep
+		);
+		Ok(())
+	}
+
+	/// The CDP owners get notice before the liquidation ratio tightens, unless
+	/// there're no debits under the collateral type.
+	fn check_liquidation_ratio_loosening(currency_id: CurrencyId, update: Option<Ratio>) -> DispatchResult {
+		let new_ratio = update.unwrap_or_else(T::DefaultLiquidationRatio::get);
+		ensure!(
+			new_ratio <= Self::get_liquidation_ratio(currency_id)
+				|| <LoansOf<T>>::total_positions(currency_id).debit.is_zero(),
+			Error::<T>::TighteningMustBeScheduled
+		);
+		Ok(())
+	}
+
+	fn do_set_collateral_params(
+		currency_id: CurrencyId,
+		stability_fee: ChangeOptionRate,
+		liquidation_ratio: ChangeOptionRatio,
+		liquidation_penalty: ChangeOptionRate,
+		required_collateral_ratio: ChangeOptionRatio,
+		maximum_total_debit_value: ChangeBalance,
+	) {
+		let mut collateral_params = Self::collateral_params(currency_id);
+		if let Change::NewValue(update) = stability_fee {
+			// settle the interest accrued under the old stability fee
+			Self::accumulate_interest(currency_id);
+			collateral_params.stability_fee = update;
+			Self::deposit_event(Event::StabilityFeeUpdated(currency_id, update));
+		}
+		if let Change::NewValue(update) = liquidation_ratio {
+			collateral_params.liquidation_ratio = update;
+			Self::deposit_event(Event::LiquidationRatioUpdated(currency_id, update));
+		}
+		if let Change::NewValue(update) = liquidation_penalty {
+			collateral_params.liquidation_penalty = update;
+			Self::deposit_event(Event::LiquidationPenaltyUpdated(currency_id, update));
+		}
+		if let Change::NewValue(update) = required_collateral_ratio {
+			collateral_params.required_collateral_ratio = update;
+			Self::deposit_event(Event::RequiredCollateralRatioUpdated(currency_id, update));
+		}
+		if let Change::NewValue(val) = maximum_total_debit_value {
+			collateral_params.maximum_total_debit_value = val;
+			Self::deposit_event(Event::MaximumTotalDebitValueUpdated(currency_id, val));
+		}
+		CollateralParams::<T>::insert(currency_id, collateral_params);
+	}
+
 	fn submit_unsigned_liquidation_tx(currency_id: CurrencyId, who: T::AccountId) {
 		let who = T::Lookup::unlookup(who);
 		let call = Call::<T>::liquidate(currency_id, who.clone());
@@ -518,7 +1007,22 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	fn _offchain_worker() -> Result<(), OffchainErr> {
+	/// Get the local rotation offset of this node, so that validators running
+	/// at the same block scan different shards.
+	fn rotation_offset() -> u32 {
+		let rotation_offset = StorageValueRef::persistent(&OFFCHAIN_WORKER_ROTATION_OFFSET);
+		if let Some(Some(offset)) = rotation_offset.get::<u32>() {
+			return offset;
+		}
+
+		let random_seed = sp_io::offchain::random_seed();
+		let mut rng = RandomNumberGenerator::<BlakeTwo256>::new(BlakeTwo256::hash(&random_seed[..]));
+		let offset = rng.pick_u32(u16::max_value().into());
+		rotation_offset.set(&offset);
+		offset
+	}
+
+	fn _offchain_worker(now: T::BlockNumber) -> Result<(), OffchainErr> {
 		let collateral_currency_ids = T::CollateralCurrencyIds::get();
 		if collateral_currency_ids.len().is_zero() {
 			return Ok(());
@@ -534,31 +1038,62 @@ impl<T: Config> Pallet<T> {
 		let mut lock = StorageLock::<'_, Time>::with_deadline(&OFFCHAIN_WORKER_LOCK, lock_expiration);
 		let mut guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
 
-		let collateral_currency_ids = T::CollateralCurrencyIds::get();
-		let to_be_continue = StorageValueRef::persistent(&OFFCHAIN_WORKER_DATA);
-
-		// get to_be_continue record
-		let (collateral_position, start_key) =
-			if let Some(Some((last_collateral_position, maybe_last_iterator_previous_key))) =
-				to_be_continue.get::<(u32, Option<Vec<u8>>)>()
-			{
-				(last_collateral_position, maybe_last_iterator_previous_key)
-			} else {
-				let random_seed = sp_io::offchain::random_seed();
-				let mut rng = RandomNumberGenerator::<BlakeTwo256>::new(BlakeTwo256::hash(&random_seed[..]));
-				(
-					rng.pick_u32(collateral_currency_ids.len().saturating_sub(1) as u32),
-					None,
-				)
-			};
-
-		// get the max iterationns config
+		// get the offchain worker configs
 		let max_iterations = StorageValueRef::persistent(&OFFCHAIN_WORKER_MAX_ITERATIONS)
 			.get::<u32>()
 			.unwrap_or(Some(DEFAULT_MAX_ITERATIONS));
-
+		let max_submissions = StorageValueRef::persistent(&OFFCHAIN_WORKER_MAX_SUBMISSIONS)
+			.get::<u32>()
+			.unwrap_or(Some(DEFAULT_MAX_SUBMISSIONS))
+			.unwrap_or(DEFAULT_MAX_SUBMISSIONS);
+		let shards_count = StorageValueRef::persistent(&OFFCHAIN_WORKER_SHARDS)
+			.get::<u32>()
+			.unwrap_or(Some(DEFAULT_SHARDS))
+			.unwrap_or(DEFAULT_SHARDS)
+			.max(1)
+			.min(MAX_SHARDS);
+
+		// every (collateral, shard) pair is a unit of work, rotate over the units
+		// by the block number, so that all positions are scanned in turn however
+		// many positions there are.
+		let units_count = (collateral_currency_ids.len() as u32).saturating_mul(shards_count);
+		let rotation = UniqueSaturatedInto::<u32>::unique_saturated_into(now).wrapping_add(Self::rotation_offset());
+		let collateral_position = (rotation % units_count) / shards_count;
+		let shard = (rotation % units_count) % shards_count;
 		let currency_id = collateral_currency_ids[(collateral_position as usize)];
+
+		// the shard is the range of the first byte of the account hash in the
+		// storage key of positions.
+		let prefix = <loans::Positions<T> as frame_support::storage::generator::StorageDoubleMap<
+			CurrencyId,
+			T::AccountId,
+			Position,
+		>>::storage_double_map_final_key1(currency_id);
+		let shard_byte_index = prefix.len();
+		let shard_start = shard * MAX_SHARDS / shards_count;
+		let shard_end = (shard + 1) * MAX_SHARDS / shards_count;
+
+		// get to_be_continue record of the shard, otherwise start from the
+		// beginning of the shard
+		let mut to_be_continue = StorageValueRef::persistent(
+			&[
+				OFFCHAIN_WORKER_DATA,
+				&(shards_count, collateral_position, shard).encode(),
+			]
+			.concat(),
+		);
+		let start_key = to_be_continue.get::<Vec<u8>>().unwrap_or(None).or_else(|| {
+			if shard_start.is_zero() {
+				None
+			} else {
+				let mut shard_start_key = prefix.clone();
+				shard_start_key.push(shard_start as u8);
+				Some(shard_start_key)
+			}
+		});
+
 		let is_shutdown = T::EmergencyShutdown::is_shutdown();
+		let is_liquidation_paused = Self::is_liquidation_paused(currency_id);
 		let mut map_iterator = <loans::Positions<T> as IterableStorageDoubleMapExtended<_, _, _>>::iter_prefix(
 			currency_id,
 			max_iterations,
@@ -566,46 +1101,65 @@ impl<T: Config> Pallet<T> {
 		);
 
 		let mut iteration_count = 0;
+		let mut submission_count = 0;
+		let mut shard_finished = false;
 		let iteration_start_time = sp_io::offchain::timestamp();
 		while let Some((who, Position { collateral, debit })) = map_iterator.next() {
-			if !is_shutdown && Self::is_cdp_unsafe(currency_id, collateral, debit) {
+			// stop at the first position of the next shard
+			if map_iterator
+				.map_iterator
+				.previous_key
+				.get(shard_byte_index)
+				.map_or(true, |byte| u32::from(*byte) >= shard_end)
+			{
+				shard_finished = true;
+				break;
+			}
+
+			if !is_shutdown && !is_liquidation_paused && Self::is_cdp_unsafe(currency_id, collateral, debit) {
 				// liquidate unsafe CDPs before emergency shutdown occurs
 				Self::submit_unsigned_liquidation_tx(currency_id, who);
+				submission_count += 1;
 			} else if is_shutdown && !debit.is_zero() {
 				// settle CDPs with debit after emergency shutdown occurs.
 				Self::submit_unsigned_settlement_tx(currency_id, who);
+				submission_count += 1;
 			}
 
 			iteration_count += 1;
 
 			// extend offchain worker lock
 			guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
+
+			// stop after the submission cap, and continue after this position at
+			// the next run of the shard
+			if submission_count >= max_submissions {
+				break;
+			}
 		}
 		let iteration_end_time = sp_io::offchain::timestamp();
 		debug::debug!(
 			target: "cdp-engine offchain worker",
-			"iteration info:\n max iterations is {:?}\n currency id: {:?}, start key: {:?}, iterate count: {:?}\n iteration start at: {:?}, end at: {:?}, execution time: {:?}\n",
+			"iteration info:\n max iterations is {:?}, max submissions is {:?}\n currency id: {:?}, shard: {:?}/{:?}, start key: {:?}, iterate count: {:?}, submit count: {:?}\n iteration start at: {:?}, end at: {:?}, execution time: {:?}\n",
 			max_iterations,
+			max_submissions,
 			currency_id,
+			shard,
+			shards_count,
 			start_key,
 			iteration_count,
+			submission_count,
 			iteration_start_time,
 			iteration_end_time,
 			iteration_end_time.diff(&iteration_start_time)
 		);
 
-		// if iteration for map storage finished, clear to be continue record
+		// if iteration for the shard finished, clear to be continue record
 		// otherwise, update to be continue record
-		if map_iterator.finished {
-			let next_collateral_position =
-				if collateral_position < collateral_currency_ids.len().saturating_sub(1) as u32 {
-					collateral_position + 1
-				} else {
-					0
-				};
-			to_be_continue.set(&(next_collateral_position, Option::<Vec<u8>>::None));
+		if map_iterator.finished || shard_finished {
+			to_be_continue.clear();
 		} else {
-			to_be_continue.set(&(collateral_position, Some(map_iterator.map_iterator.previous_key)));
+			to_be_continue.set(&map_iterator.map_iterator.previous_key);
 		}
 
 		// Consume the guard but **do not** unlock the underlying lock.
@@ -614,12 +1168,20 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The liquidations of the collateral are paused while its price is
+	/// locked by governance.
+	pub fn is_liquidation_paused(currency_id: CurrencyId) -> bool {
+		T::PriceSource::is_price_locked(currency_id)
+	}
+
 	pub fn is_cdp_unsafe(currency_id: CurrencyId, collateral: Balance, debit: Balance) -> bool {
 		let stable_currency_id = T::GetStableCurrencyId::get();
 
-		if let Some(feed_price) = T::PriceSource::get_relative_price(currency_id, stable_currency_id) {
+		// use the smoothed price so that a single spike of feed can't trigger
+		// liquidation
+		if let Some(feed_price) = T::PriceSource::get_relative_twap_price(currency_id, stable_currency_id) {
 			let collateral_ratio = Self::calculate_collateral_ratio(currency_id, collateral, debit, feed_price);
-			collateral_ratio < Self::get_liquidation_ratio(currency_id)
+			collateral_ratio < Self::get_effective_liquidation_ratio(currency_id)
 		} else {
 			false
 		}
@@ -646,14 +1208,78 @@ impl<T: Config> Pallet<T> {
 			.unwrap_or_else(T::DefaultLiquidationRatio::get)
 	}
 
+	/// The liquidation ratio widened by the confidence interval of the
+	/// collateral price once it's beyond `MaxPriceConfidence`, so that the
+	/// CDPs need more collateral while the market is volatile.
+	pub fn get_effective_liquidation_ratio(currency_id: CurrencyId) -> Ratio {
+		let liquidation_ratio = Self::get_liquidation_ratio(currency_id);
+		match T::PriceSource::get_price_confidence(currency_id) {
+			Some(confidence) if confidence > T::MaxPriceConfidence::get() => {
+				liquidation_ratio.saturating_mul(Ratio::one().saturating_add(confidence))
+			}
+			_ => liquidation_ratio,
+		}
+	}
+
 	pub fn get_liquidation_penalty(currency_id: CurrencyId) -> Rate {
 		Self::collateral_params(currency_id)
 			.liquidation_penalty
 			.unwrap_or_else(T::DefaultLiquidationPenalty::get)
 	}
 
+	/// Get the debit exchange rate of `currency_id` with the stability fee
+	/// accrued since the last checkpoint, without updating the storage.
 	pub fn get_debit_exchange_rate(currency_id: CurrencyId) -> ExchangeRate {
-		Self::debit_exchange_rate(currency_id).unwrap_or_else(T::DefaultDebitExchangeRate::get)
+		let debit_exchange_rate =
+			Self::debit_exchange_rate(currency_id).unwrap_or_else(T::DefaultDebitExchangeRate::get);
+		if T::EmergencyShutdown::is_shutdown() {
+			return debit_exchange_rate;
+		}
+
+		let last_accumulation_secs = Self::last_accumulation_secs(currency_id);
+		let now_secs = T::UnixTime::now().as_secs();
+		let stability_fee_rate = Self::get_stability_fee(currency_id);
+		let total_debits = <LoansOf<T>>::total_positions(currency_id).debit;
+		if last_accumulation_secs.is_zero()
+			|| now_secs <= last_accumulation_secs
+			|| stability_fee_rate.is_zero()
+			|| total_debits.is_zero()
+		{
+			return debit_exchange_rate;
+		}
+
+		let elapsed_secs: usize = (now_secs - last_accumulation_secs).unique_saturated_into();
+		let accumulated_rate = Rate::one()
+			.saturating_add(stability_fee_rate)
+			.saturating_pow(elapsed_secs);
+		debit_exchange_rate.saturating_mul(accumulated_rate)
+	}
+
+	/// Checkpoint the debit exchange rate of `currency_id`, issue the
+	/// stability fee accrued since the last checkpoint to the surplus pool.
+	pub fn accumulate_interest(currency_id: CurrencyId) {
+		let now_secs = T::UnixTime::now().as_secs();
+		if now_secs <= Self::last_accumulation_secs(currency_id) {
+			return;
+		}
+
+		let debit_exchange_rate =
+			Self::debit_exchange_rate(currency_id).unwrap_or_else(T::DefaultDebitExchangeRate::get);
+		let new_debit_exchange_rate = Self::get_debit_exchange_rate(currency_id);
+		if new_debit_exchange_rate > debit_exchange_rate {
+			let total_debits = <LoansOf<T>>::total_positions(currency_id).debit;
+			let issued_stable_coin_balance = new_debit_exchange_rate
+				.saturating_sub(debit_exchange_rate)
+				.saturating_mul_int(total_debits);
+
+			// issue stablecoin to surplus pool, keep the checkpoint to retry
+			// at the next touch if it fails
+			if <T as Config>::CDPTreasury::on_system_surplus(issued_stable_coin_balance).is_err() {
+				return;
+			}
+			DebitExchangeRate::<T>::insert(currency_id, new_debit_exchange_rate);
+		}
+		LastAccumulationSecs::<T>::insert(currency_id, now_secs);
 	}
 
 	pub fn get_debit_value(currency_id: CurrencyId, debit_balance: Balance) -> Balance {
@@ -672,6 +1298,73 @@ impl<T: Config> Pallet<T> {
 		Ratio::checked_from_rational(locked_collateral_value, debit_value).unwrap_or_else(Rate::max_value)
 	}
 
+	/// The lower of the spot and the smoothed price of the collateral, so
+	/// that neither a spike of feed nor the lag of the smoothed price can
+	/// open a position which is unsafe by the other. `None` if there's no
+	/// feed price for the collateral.
+	fn get_validation_price(currency_id: CurrencyId) -> Option<Price> {
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let spot_price = T::PriceSource::get_relative_price(currency_id, stable_currency_id)?;
+		Some(
+			T::PriceSource::get_relative_twap_price(currency_id, stable_currency_id)
+				.map_or(spot_price, |twap_price| spot_price.min(twap_price)),
+		)
+	}
+
+	/// Get the collateral ratio of the CDP at the lower of the spot and the
+	/// smoothed price, `None` if there's no feed price for the collateral.
+	pub fn get_collateral_ratio(currency_id: CurrencyId, collateral: Balance, debit: Balance) -> Option<Ratio> {
+		let feed_price = Self::get_validation_price(currency_id)?;
+		Some(Self::calculate_collateral_ratio(
+			currency_id,
+			collateral,
+			debit,
+			feed_price,
+		))
+	}
+
+	/// Get the collateral price at which the CDP becomes unsafe, `None` if
+	/// the CDP has no collateral.
+	pub fn get_liquidation_price(currency_id: CurrencyId, collateral: Balance, debit: Balance) -> Option<Price> {
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		Price::checked_from_rational(debit_value, collateral)
+			.map(|price| price.saturating_mul(Self::get_effective_liquidation_ratio(currency_id)))
+	}
+
+	/// Get the stability fee accrued by `debit` since the last accumulation
+	/// of the debit exchange rate, which is not yet issued to surplus pool.
+	pub fn get_accrued_stability_fee(currency_id: CurrencyId, debit: Balance) -> Balance {
+		let debit_exchange_rate =
+			Self::debit_exchange_rate(currency_id).unwrap_or_else(T::DefaultDebitExchangeRate::get);
+		Self::get_debit_exchange_rate(currency_id)
+			.saturating_sub(debit_exchange_rate)
+			.saturating_mul_int(debit)
+	}
+
+	/// Get the maximum debit value the CDP can additionally issue without
+	/// violating the liquidation ratio, the required collateral ratio or the
+	/// hard cap of the collateral type.
+	pub fn get_max_additional_debit_value(currency_id: CurrencyId, collateral: Balance, debit: Balance) -> Balance {
+		let feed_price = match T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get()) {
+			Some(price) => price,
+			None => return Zero::zero(),
+		};
+		let liquidation_ratio = Self::get_effective_liquidation_ratio(currency_id);
+		let required_ratio = sp_std::cmp::max(
+			liquidation_ratio,
+			Self::required_collateral_ratio(currency_id).unwrap_or(liquidation_ratio),
+		);
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		let max_debit_value = feed_price
+			.checked_div(&required_ratio)
+			.map_or(Zero::zero(), |price| price.saturating_mul_int(collateral));
+
+		let total_debit_value = Self::get_debit_value(currency_id, <LoansOf<T>>::total_positions(currency_id).debit);
+		let remain_debit_value_cap = Self::maximum_total_debit_value(currency_id).saturating_sub(total_debit_value);
+
+		sp_std::cmp::min(max_debit_value.saturating_sub(debit_value), remain_debit_value_cap)
+	}
+
 	pub fn adjust_position(
 		who: &T::AccountId,
 		currency_id: CurrencyId,
@@ -682,14 +1375,28 @@ impl<T: Config> Pallet<T> {
 			T::CollateralCurrencyIds::get().contains(&currency_id),
 			Error::<T>::InvalidCollateralType,
 		);
+		Self::accumulate_interest(currency_id);
 		<LoansOf<T>>::adjust_position(who, currency_id, collateral_adjustment, debit_adjustment)?;
+		// the owner has responded to the liquidation
+		LiquidatorExclusiveWindowEnd::<T>::remove(currency_id, who);
 		Ok(())
 	}
 
+	/// Get the end of the exclusive window of the CDP, a window which has
+	/// been over for another `LiquidatorExclusiveWindow` is stale and treated
+	/// as no window, e.g. the CDP recovered without adjustment and becomes
+	/// unsafe again.
+	fn active_exclusive_window_end(currency_id: CurrencyId, who: &T::AccountId) -> Option<T::BlockNumber> {
+		let now = <frame_system::Module<T>>::block_number();
+		Self::liquidator_exclusive_window_end(currency_id, who)
+			.filter(|window_end| now < window_end.saturating_add(T::LiquidatorExclusiveWindow::get()))
+	}
+
 	// settle cdp has debit when emergency shutdown
 	pub fn settle_cdp_has_debit(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 		ensure!(!debit.is_zero(), Error::<T>::NoDebitValue);
+		LiquidatorExclusiveWindowEnd::<T>::remove(currency_id, &who);
 
 		// confiscate collateral in cdp to cdp treasury
 		// and decrease CDP's debit to zero
@@ -706,10 +1413,75 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	// close cdp has debit by swap collateral to exact debit value with DEX
+	#[transactional]
+	pub fn close_cdp_has_debit_by_dex(
+		who: T::AccountId,
+		currency_id: CurrencyId,
+		max_collateral_amount: Balance,
+	) -> DispatchResult {
+		Self::accumulate_interest(currency_id);
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
+		ensure!(!debit.is_zero(), Error::<T>::NoDebitValue);
+		// unsafe cdp must be liquidated with penalty rather than closed by its owner
+		ensure!(
+			!Self::is_cdp_unsafe(currency_id, collateral, debit),
+			Error::<T>::MustBeSafe
+		);
+
+		// confiscate all collateral and debit of cdp to cdp treasury
+		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
+
+		// swap exact stable to offset the whole debit value, the bad debt is
+		// offset by the surplus from the swap at the end of block
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		let actual_supply_collateral = <T as Config>::CDPTreasury::swap_collateral_not_in_auction_with_exact_stable(
+			currency_id,
+			debit_value,
+			sp_std::cmp::min(max_collateral_amount, collateral),
+			None,
+		)?;
+
+		// refund remain collateral to CDP owner
+		let refund_collateral_amount = collateral
+			.checked_sub(actual_supply_collateral)
+			.expect("swap succecced means collateral >= actual_supply_collateral; qed");
+		<T as Config>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_collateral_amount)?;
+
+		Self::deposit_event(Event::CloseCDPInDebitByDEX(
+			currency_id,
+			who,
+			actual_supply_collateral,
+			refund_collateral_amount,
+			debit_value,
+		));
+		Ok(())
+	}
+
 	// liquidate unsafe cdp
 	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		Self::do_liquidate_unsafe_cdp(who, currency_id, None, false)
+	}
+
+	/// Check if `who` is a bonded liquidator with priority access
+	pub fn is_bonded_liquidator(who: &T::AccountId) -> bool {
+		Self::liquidators(who).map_or(false, |info| {
+			info.unlock_at.is_none() && info.bond >= T::LiquidatorBond::get()
+		})
+	}
+
+	// liquidate unsafe cdp, reward part of the penalty to `keeper` if any,
+	// fail rather than create collateral auctions if `dex_only`
+	fn do_liquidate_unsafe_cdp(
+		who: T::AccountId,
+		currency_id: CurrencyId,
+		keeper: Option<T::AccountId>,
+		dex_only: bool,
+	) -> DispatchResult {
+		Self::accumulate_interest(currency_id);
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 
+		ensure!(!Self::is_liquidation_paused(currency_id), Error::<T>::LiquidationPaused);
 		// ensure the cdp is unsafe
 		ensure!(
 			Self::is_cdp_unsafe(currency_id, collateral, debit),
@@ -718,9 +1490,34 @@ impl<T: Config> Pallet<T> {
 
 		// confiscate all collateral and debit of unsafe cdp to cdp treasury
 		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
+		LiquidatorExclusiveWindowEnd::<T>::remove(currency_id, &who);
 
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
-		let target_stable_amount = Self::get_liquidation_penalty(currency_id).saturating_mul_acc_int(bad_debt_value);
+		let mut target_stable_amount =
+			Self::get_liquidation_penalty(currency_id).saturating_mul_acc_int(bad_debt_value);
+		let penalty_amount = target_stable_amount.saturating_sub(bad_debt_value);
+		let mut collateral_to_sell = collateral;
+
+		// reward the keeper with collateral, the reward is paid from the penalty
+		if let Some(keeper) = keeper {
+			let stable_currency_id = T::GetStableCurrencyId::get();
+			let max_reward_by_penalty = T::PriceSource::get_relative_price(stable_currency_id, currency_id)
+				.map_or(Zero::zero(), |price| price.saturating_mul_int(penalty_amount));
+			let reward_amount = sp_std::cmp::min(
+				T::KeeperRewardRatio::get().saturating_mul_int(collateral),
+				max_reward_by_penalty,
+			);
+
+			if !reward_amount.is_zero() {
+				<T as Config>::CDPTreasury::withdraw_collateral(&keeper, currency_id, reward_amount)?;
+				let reward_value = T::PriceSource::get_relative_price(currency_id, stable_currency_id)
+					.map_or(Zero::zero(), |price| price.saturating_mul_int(reward_amount));
+				target_stable_amount =
+					sp_std::cmp::max(target_stable_amount.saturating_sub(reward_value), bad_debt_value);
+				collateral_to_sell = collateral_to_sell.saturating_sub(reward_amount);
+				Self::deposit_event(Event::LiquidationKeeperRewarded(currency_id, keeper, reward_amount));
+			}
+		}
 
 		// try use collateral to swap enough native token in DEX when the price impact
 		// is below the limit, otherwise create collateral auctions.
@@ -730,23 +1527,24 @@ impl<T: Config> Pallet<T> {
 				<T as Config>::CDPTreasury::swap_collateral_not_in_auction_with_exact_stable(
 					currency_id,
 					target_stable_amount,
-					collateral,
+					collateral_to_sell,
 					Some(T::MaxSlippageSwapWithDEX::get()),
 				) {
 				// refund remain collateral to CDP owner
-				let refund_collateral_amount = collateral
+				let refund_collateral_amount = collateral_to_sell
 					.checked_sub(actual_supply_collateral)
-					.expect("swap succecced means collateral >= actual_supply_collateral; qed");
+					.expect("swap succecced means collateral_to_sell >= actual_supply_collateral; qed");
 
 				<T as Config>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_collateral_amount)?;
 
 				return Ok(LiquidationStrategy::Exchange);
 			}
+			ensure!(!dex_only, Error::<T>::InLiquidatorExclusiveWindow);
 
 			// create collateral auctions by cdp treasury
 			<T as Config>::CDPTreasury::create_collateral_auctions(
 				currency_id,
-				collateral,
+				collateral_to_sell,
 				target_stable_amount,
 				who.clone(),
 				true,
@@ -760,12 +1558,24 @@ impl<T: Config> Pallet<T> {
 			who,
 			collateral,
 			bad_debt_value,
+			penalty_amount,
 			liquidation_strategy,
 		));
 		Ok(())
 	}
 }
 
+/// Accumulate the stability fees of all collaterals before the emergency
+/// shutdown freezes the debit exchange rates.
+pub struct OnEmergencyShutdown<T>(PhantomData<T>);
+impl<T: Config> Happened<()> for OnEmergencyShutdown<T> {
+	fn happened(_: &()) {
+		for currency_id in T::CollateralCurrencyIds::get() {
+			Pallet::<T>::accumulate_interest(currency_id);
+		}
+	}
+}
+
 impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pallet<T> {
 	fn get_bad_debt_value(currency_id: CurrencyId, debit_balance: Balance) -> Balance {
 		Self::get_debit_value(currency_id, debit_balance)
@@ -778,8 +1588,7 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 	) -> DispatchResult {
 		if !debit_balance.is_zero() {
 			let debit_value = Self::get_debit_value(currency_id, debit_balance);
-			let feed_price = <T as Config>::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
-				.ok_or(Error::<T>::InvalidFeedPrice)?;
+			let feed_price = Self::get_validation_price(currency_id).ok_or(Error::<T>::InvalidFeedPrice)?;
 			let collateral_ratio =
 				Self::calculate_collateral_ratio(currency_id, collateral_balance, debit_balance, feed_price);
 
@@ -793,7 +1602,7 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 
 			// check the liquidation ratio
 			ensure!(
-				collateral_ratio >= Self::get_liquidation_ratio(currency_id),
+				collateral_ratio >= Self::get_effective_liquidation_ratio(currency_id),
 				Error::<T>::BelowLiquidationRatio
 			);
 
@@ -4,7 +4,7 @@
 
 use super::*;
 use crate::mock::sp_api_hidden_includes_construct_runtime::hidden_include::inherent::BlockT;
-use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types, traits::UnixTime};
 use frame_system::EnsureSignedBy;
 use orml_traits::parameter_type_with_key;
 use primitives::{TokenSymbol, TradingPair};
@@ -14,7 +14,7 @@ use sp_runtime::{
 	traits::IdentityLookup,
 	ModuleId,
 };
-use sp_std::cell::RefCell;
+use sp_std::{cell::RefCell, time::Duration};
 use support::{AuctionManager, EmergencyShutdown};
 
 pub type AccountId = u128;
@@ -122,6 +122,9 @@ impl loans::Config for Runtime {
 
 thread_local! {
 	static RELATIVE_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+	static RELATIVE_TWAP_PRICE: RefCell<Option<Price>> = RefCell::new(None);
+	static PRICE_LOCKED: RefCell<bool> = RefCell::new(false);
+	static PRICE_CONFIDENCE: RefCell<Option<Ratio>> = RefCell::new(None);
 }
 
 pub struct MockPriceSource;
@@ -129,6 +132,19 @@ impl MockPriceSource {
 	pub fn set_relative_price(price: Option<Price>) {
 		RELATIVE_PRICE.with(|v| *v.borrow_mut() = price);
 	}
+
+	/// Override the relative TWAP price, `None` follows the spot price.
+	pub fn set_relative_twap_price(price: Option<Price>) {
+		RELATIVE_TWAP_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+
+	pub fn set_price_locked(locked: bool) {
+		PRICE_LOCKED.with(|v| *v.borrow_mut() = locked);
+	}
+
+	pub fn set_price_confidence(confidence: Option<Ratio>) {
+		PRICE_CONFIDENCE.with(|v| *v.borrow_mut() = confidence);
+	}
 }
 impl PriceProvider<CurrencyId> for MockPriceSource {
 	fn get_relative_price(base: CurrencyId, quote: CurrencyId) -> Option<Price> {
@@ -143,9 +159,23 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 		Some(Price::one())
 	}
 
+	fn get_relative_twap_price(base: CurrencyId, quote: CurrencyId) -> Option<Price> {
+		RELATIVE_TWAP_PRICE
+			.with(|v| *v.borrow())
+			.or_else(|| Self::get_relative_price(base, quote))
+	}
+
 	fn lock_price(_currency_id: CurrencyId) {}
 
 	fn unlock_price(_currency_id: CurrencyId) {}
+
+	fn is_price_locked(_currency_id: CurrencyId) -> bool {
+		PRICE_LOCKED.with(|v| *v.borrow())
+	}
+
+	fn get_price_confidence(_currency_id: CurrencyId) -> Option<Ratio> {
+		PRICE_CONFIDENCE.with(|v| *v.borrow())
+	}
 }
 
 pub struct MockAuctionManager;
@@ -196,6 +226,7 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryModuleId: ModuleId = ModuleId(*b"aca/cdpt");
+	pub const BadDebtDelay: BlockNumber = 10;
 }
 
 impl cdp_treasury::Config for Runtime {
@@ -207,6 +238,10 @@ impl cdp_treasury::Config for Runtime {
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type BadDebtDelay = BadDebtDelay;
+	type DebitAuctionFixedSize = ();
+	type DebitAuctionInitialAmount = ();
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type WeightInfo = ();
 }
 
@@ -214,6 +249,7 @@ parameter_types! {
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const TwapPeriod: BlockNumber = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC), TradingPair::new(AUSD, DOT)];
 }
 
@@ -222,6 +258,7 @@ impl module_dex::Config for Runtime {
 	type Currency = Currencies;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TwapPeriod = TwapPeriod;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = ();
 	type WeightInfo = ();
@@ -243,6 +280,36 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+thread_local! {
+	static NOW_SECS: RefCell<u64> = RefCell::new(0);
+}
+
+pub fn mock_set_now_secs(secs: u64) {
+	NOW_SECS.with(|v| *v.borrow_mut() = secs)
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> Duration {
+		Duration::from_secs(NOW_SECS.with(|v| *v.borrow()))
+	}
+}
+
+thread_local! {
+	static LIQUIDATOR_EXCLUSIVE_WINDOW: RefCell<BlockNumber> = RefCell::new(0);
+}
+
+pub fn mock_set_liquidator_exclusive_window(window: BlockNumber) {
+	LIQUIDATOR_EXCLUSIVE_WINDOW.with(|v| *v.borrow_mut() = window)
+}
+
+pub struct LiquidatorExclusiveWindow;
+impl Get<BlockNumber> for LiquidatorExclusiveWindow {
+	fn get() -> BlockNumber {
+		LIQUIDATOR_EXCLUSIVE_WINDOW.with(|v| *v.borrow())
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -251,9 +318,15 @@ parameter_types! {
 	pub DefaultLiquidationRatio: Ratio = Ratio::saturating_from_rational(3, 2);
 	pub DefaultDebitExchangeRate: ExchangeRate = ExchangeRate::one();
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(10, 100);
+	pub const ParameterChangeDelay: BlockNumber = 10;
+	pub MaxLiquidationPenaltyStep: Rate = Rate::saturating_from_rational(10, 100);
+	pub KeeperRewardRatio: Rate = Rate::saturating_from_rational(1, 100);
 	pub const MinimumDebitValue: Balance = 2;
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const LiquidatorBond: Balance = 100;
+	pub const LiquidatorUnbondingDelay: BlockNumber = 10;
+	pub MaxPriceConfidence: Ratio = Ratio::saturating_from_rational(5, 100);
 	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
 }
 
@@ -264,6 +337,9 @@ impl Config for Runtime {
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
+	type ParameterChangeDelay = ParameterChangeDelay;
+	type MaxLiquidationPenaltyStep = MaxLiquidationPenaltyStep;
+	type KeeperRewardRatio = KeeperRewardRatio;
 	type MinimumDebitValue = MinimumDebitValue;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
@@ -272,6 +348,12 @@ impl Config for Runtime {
 	type DEX = DEXModule;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type UnixTime = MockUnixTime;
+	type Currency = AdaptedBasicCurrency;
+	type LiquidatorBond = LiquidatorBond;
+	type LiquidatorExclusiveWindow = LiquidatorExclusiveWindow;
+	type LiquidatorUnbondingDelay = LiquidatorUnbondingDelay;
+	type MaxPriceConfidence = MaxPriceConfidence;
 	type WeightInfo = ();
 }
 
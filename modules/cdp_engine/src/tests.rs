@@ -5,8 +5,99 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
-use orml_traits::MultiCurrency;
+use orml_traits::{BasicCurrency, BasicCurrencyExtended, MultiCurrency};
+use parking_lot::RwLock;
+use sp_core::offchain::{
+	testing::{self, PoolState},
+	OffchainExt, TransactionPoolExt,
+};
 use sp_runtime::traits::BadOrigin;
+use sp_std::sync::Arc;
+
+/// Tighten the liquidation ratio of the collateral type with debits by the
+/// timelocked change.
+fn tighten_liquidation_ratio(currency_id: CurrencyId, ratio: Ratio) {
+	assert_ok!(CDPEngineModule::schedule_set_collateral_params(
+		Origin::signed(1),
+		currency_id,
+		Change::NoChange,
+		Change::NewValue(Some(ratio)),
+		Change::NoChange,
+		Change::NoChange,
+		Change::NoChange,
+	));
+	<CDPEngineModule as Hooks<u64>>::on_initialize(System::block_number() + ParameterChangeDelay::get());
+}
+
+fn offchain_ext() -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>) {
+	let mut ext = ExtBuilder::default().build();
+	let (offchain, _) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	(ext, pool_state)
+}
+
+/// Configure the offchain worker, without rotation offset so that the unit of
+/// work is picked by the block number only.
+fn set_offchain_worker_configs(max_iterations: u32, max_submissions: u32, shards: u32) {
+	StorageValueRef::persistent(&OFFCHAIN_WORKER_MAX_ITERATIONS).set(&max_iterations);
+	StorageValueRef::persistent(&OFFCHAIN_WORKER_MAX_SUBMISSIONS).set(&max_submissions);
+	StorageValueRef::persistent(&OFFCHAIN_WORKER_SHARDS).set(&shards);
+	StorageValueRef::persistent(&OFFCHAIN_WORKER_ROTATION_OFFSET).set(&0u32);
+}
+
+/// Insert the positions with debit, so that they are all settled by the
+/// offchain worker after emergency shutdown.
+fn insert_positions(currency_id: CurrencyId, accounts: &[AccountId]) {
+	for who in accounts {
+		loans::Positions::<Runtime>::insert(
+			currency_id,
+			who,
+			Position {
+				collateral: 100,
+				debit: 10,
+			},
+		);
+	}
+}
+
+/// The positions sorted by the storage key, that's the iteration order of the
+/// offchain worker.
+fn sorted_by_storage_key(currency_id: CurrencyId, accounts: &[AccountId]) -> Vec<AccountId> {
+	let mut accounts = accounts.to_vec();
+	accounts.sort_by_key(|who| loans::Positions::<Runtime>::hashed_key_for(currency_id, who));
+	accounts
+}
+
+/// The shard of the position, by the first byte of the account hash in the
+/// storage key.
+fn shard_of(currency_id: CurrencyId, who: &AccountId, shards_count: u32) -> u32 {
+	let prefix = <loans::Positions<Runtime> as frame_support::storage::generator::StorageDoubleMap<
+		CurrencyId,
+		AccountId,
+		Position,
+	>>::storage_double_map_final_key1(currency_id);
+	let byte = loans::Positions::<Runtime>::hashed_key_for(currency_id, who)[prefix.len()];
+	u32::from(byte) * shards_count / MAX_SHARDS
+}
+
+/// Run the offchain worker at `now`, returns the settled positions in turn.
+fn run_offchain_worker(now: BlockNumber, pool_state: &Arc<RwLock<PoolState>>) -> Vec<(CurrencyId, AccountId)> {
+	// release the lock held by the last run
+	StorageValueRef::persistent(&OFFCHAIN_WORKER_LOCK).clear();
+	assert_ok!(CDPEngineModule::_offchain_worker(now));
+
+	pool_state
+		.write()
+		.transactions
+		.drain(..)
+		.map(|tx| match Extrinsic::decode(&mut &*tx).unwrap().call {
+			mock::Call::CDPEngineModule(crate::Call::settle(currency_id, who)) => (currency_id, who),
+			call => panic!("unexpected call {:?}", call),
+		})
+		.collect()
+}
 
 #[test]
 fn is_cdp_unsafe_work() {
@@ -28,15 +119,7 @@ fn is_cdp_unsafe_work() {
 		assert_eq!(is_user_safe(BTC, &ALICE), false);
 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
 		assert_eq!(is_user_safe(BTC, &ALICE), false);
-		assert_ok!(CDPEngineModule::set_collateral_params(
-			Origin::signed(1),
-			BTC,
-			Change::NoChange,
-			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
-			Change::NoChange,
-			Change::NoChange,
-			Change::NoChange,
-		));
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(3, 1));
 		assert_eq!(is_user_safe(BTC, &ALICE), true);
 	});
 }
@@ -97,6 +180,49 @@ fn get_liquidation_ratio_work() {
 	});
 }
 
+#[test]
+fn get_effective_liquidation_ratio_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_eq!(
+			CDPEngineModule::get_effective_liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(3, 2)
+		);
+
+		// not widened within the max confidence interval
+		MockPriceSource::set_price_confidence(Some(Ratio::saturating_from_rational(5, 100)));
+		assert_eq!(
+			CDPEngineModule::get_effective_liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(3, 2)
+		);
+		assert_eq!(CDPEngineModule::is_cdp_unsafe(BTC, 100, 50), false);
+
+		// widened by the confidence interval beyond the max
+		MockPriceSource::set_price_confidence(Some(Ratio::saturating_from_rational(40, 100)));
+		assert_eq!(
+			CDPEngineModule::get_effective_liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(21, 10)
+		);
+		assert_eq!(CDPEngineModule::is_cdp_unsafe(BTC, 100, 50), true);
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, 0, 1),
+			Error::<Runtime>::BelowLiquidationRatio
+		);
+
+		MockPriceSource::set_price_confidence(None);
+		assert_eq!(CDPEngineModule::is_cdp_unsafe(BTC, 100, 50), false);
+	});
+}
+
 #[test]
 fn set_global_params_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -230,26 +356,50 @@ fn set_collateral_params_work() {
 }
 
 #[test]
-fn calculate_collateral_ratio_work() {
+fn set_liquidation_penalty_exceed_max_step() {
 	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				BTC,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(Some(Rate::saturating_from_rational(21, 100))),
+				Change::NoChange,
+				Change::NoChange,
+			),
+			Error::<Runtime>::ExceedLiquidationPenaltyStep
+		);
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NoChange,
+			Change::NoChange,
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
-			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NoChange,
 		));
+		assert_noop!(
+			CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				BTC,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(Some(Rate::saturating_from_rational(5, 100))),
+				Change::NoChange,
+				Change::NoChange,
+			),
+			Error::<Runtime>::ExceedLiquidationPenaltyStep
+		);
 		assert_eq!(
-			CDPEngineModule::calculate_collateral_ratio(BTC, 100, 50, Price::saturating_from_rational(1, 1)),
-			Ratio::saturating_from_rational(100, 50)
+			CDPEngineModule::get_liquidation_penalty(BTC),
+			Rate::saturating_from_rational(2, 10)
 		);
 	});
 }
 
 #[test]
-fn check_debit_cap_work() {
+fn set_collateral_params_only_loosen_liquidation_ratio_with_debits() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
@@ -260,78 +410,177 @@ fn check_debit_cap_work() {
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
-		assert_ok!(CDPEngineModule::check_debit_cap(BTC, 9999));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+
 		assert_noop!(
-			CDPEngineModule::check_debit_cap(BTC, 10001),
-			Error::<Runtime>::ExceedDebitValueHardCap,
+			CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				BTC,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			),
+			Error::<Runtime>::TighteningMustBeScheduled
+		);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(6, 5))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(6, 5)
+		);
+
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(2, 1));
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(2, 1)
 		);
 	});
 }
 
 #[test]
-fn check_position_valid_work() {
+fn schedule_set_collateral_params_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(CDPEngineModule::set_collateral_params(
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::schedule_set_collateral_params(
+				Origin::signed(5),
+				BTC,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::schedule_set_collateral_params(
+				Origin::signed(1),
+				LDOT,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::schedule_set_collateral_params(
+				Origin::signed(1),
+				BTC,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(Some(Rate::saturating_from_rational(21, 100))),
+				Change::NoChange,
+				Change::NoChange,
+			),
+			Error::<Runtime>::ExceedLiquidationPenaltyStep
+		);
+
+		assert_ok!(CDPEngineModule::schedule_set_collateral_params(
 			Origin::signed(1),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(1, 1))),
-			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NoChange,
+			Change::NoChange,
 			Change::NewValue(10000),
 		));
+		let change = CollateralParamsChange {
+			stability_fee: Change::NoChange,
+			liquidation_ratio: Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			liquidation_penalty: Change::NoChange,
+			required_collateral_ratio: Change::NoChange,
+			maximum_total_debit_value: Change::NewValue(10000),
+			effective_at: 11,
+		};
+		let pending_event = Event::cdp_engine(crate::Event::PendingParameterChange(BTC, change.clone()));
+		assert!(System::events().iter().any(|record| record.event == pending_event));
+		assert_eq!(CDPEngineModule::pending_collateral_params(BTC), Some(change));
 
-		MockPriceSource::set_relative_price(None);
-		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 100, 50),
-			Error::<Runtime>::InvalidFeedPrice
+		// the change does not take effect before the delay
+		CDPEngineModule::on_initialize(10);
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(3, 2)
 		);
-		MockPriceSource::set_relative_price(Some(Price::one()));
+		assert_eq!(CDPEngineModule::collateral_params(BTC).maximum_total_debit_value, 0);
 
-		assert_ok!(CDPEngineModule::check_position_valid(BTC, 100, 50));
+		CDPEngineModule::on_initialize(11);
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(2, 1)
+		);
+		assert_eq!(CDPEngineModule::collateral_params(BTC).maximum_total_debit_value, 10000);
+		assert_eq!(CDPEngineModule::pending_collateral_params(BTC), None);
 	});
 }
 
 #[test]
-fn check_position_valid_failed_when_remain_debit_value_too_small() {
+fn cancel_collateral_params_change_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(CDPEngineModule::set_collateral_params(
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::cancel_collateral_params_change(Origin::signed(1), BTC),
+			Error::<Runtime>::NoPendingParameterChange
+		);
+		assert_ok!(CDPEngineModule::schedule_set_collateral_params(
 			Origin::signed(1),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(1, 1))),
-			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
-			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 2, 1),
-			Error::<Runtime>::RemainDebitValueTooSmall,
+			CDPEngineModule::cancel_collateral_params_change(Origin::signed(5), BTC),
+			BadOrigin
+		);
+		assert_ok!(CDPEngineModule::cancel_collateral_params_change(Origin::signed(1), BTC));
+		let cancel_event = Event::cdp_engine(crate::Event::PendingParameterChangeCancelled(BTC));
+		assert!(System::events().iter().any(|record| record.event == cancel_event));
+		assert_eq!(CDPEngineModule::pending_collateral_params(BTC), None);
+
+		CDPEngineModule::on_initialize(11);
+		assert_eq!(
+			CDPEngineModule::get_liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(3, 2)
 		);
 	});
 }
 
 #[test]
-fn check_position_valid_ratio_below_liquidate_ratio() {
+fn calculate_collateral_ratio_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
 			BTC,
 			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(10, 2))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
-		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 91, 50),
-			Error::<Runtime>::BelowLiquidationRatio,
+		assert_eq!(
+			CDPEngineModule::calculate_collateral_ratio(BTC, 100, 50, Price::saturating_from_rational(1, 1)),
+			Ratio::saturating_from_rational(100, 50)
 		);
 	});
 }
 
 #[test]
-fn check_position_valid_ratio_below_required_ratio() {
+fn get_liquidation_price_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
@@ -342,15 +591,16 @@ fn check_position_valid_ratio_below_required_ratio() {
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
-		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 89, 50),
-			Error::<Runtime>::BelowRequiredCollateralRatio
+		assert_eq!(
+			CDPEngineModule::get_liquidation_price(BTC, 100, 50),
+			Some(Price::saturating_from_rational(75, 100))
 		);
+		assert_eq!(CDPEngineModule::get_liquidation_price(BTC, 0, 50), None);
 	});
 }
 
 #[test]
-fn adjust_position_work() {
+fn get_max_additional_debit_value_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
@@ -361,30 +611,28 @@ fn adjust_position_work() {
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
-		assert_noop!(
-			CDPEngineModule::adjust_position(&ALICE, ACA, 100, 50),
-			Error::<Runtime>::InvalidCollateralType,
-		);
-		assert_eq!(Currencies::free_balance(BTC, &ALICE), 1000);
-		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 0);
-		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
-		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
-		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
-		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
-		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
-		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
-		assert_eq!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, 20).is_ok(), false);
-		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, -20));
-		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
-		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 30);
-		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 30);
-		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(CDPEngineModule::get_max_additional_debit_value(BTC, 100, 50), 5);
+
+		// limited by the hard cap
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(52),
+		));
+		assert_eq!(CDPEngineModule::get_max_additional_debit_value(BTC, 100, 50), 2);
+
+		MockPriceSource::set_relative_price(None);
+		assert_eq!(CDPEngineModule::get_max_additional_debit_value(BTC, 100, 50), 0);
 	});
 }
 
 #[test]
-fn remain_debit_value_too_small_check() {
+fn check_debit_cap_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
@@ -395,72 +643,508 @@ fn remain_debit_value_too_small_check() {
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
-		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
-		assert_eq!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, -49).is_ok(), false);
-		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, -100, -50));
+		assert_ok!(CDPEngineModule::check_debit_cap(BTC, 9999));
+		assert_noop!(
+			CDPEngineModule::check_debit_cap(BTC, 10001),
+			Error::<Runtime>::ExceedDebitValueHardCap,
+		);
 	});
 }
 
 #[test]
-fn liquidate_unsafe_cdp_by_collateral_auction() {
+fn check_position_valid_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		System::set_block_number(1);
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
 			BTC,
 			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(1, 1))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(10000),
 		));
-		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
-		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
-		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
-		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
-		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+
+		MockPriceSource::set_relative_price(None);
 		assert_noop!(
-			CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
-			Error::<Runtime>::MustBeUnsafe,
+			CDPEngineModule::check_position_valid(BTC, 100, 50),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+		MockPriceSource::set_relative_price(Some(Price::one()));
+
+		assert_ok!(CDPEngineModule::check_position_valid(BTC, 100, 50));
+	});
+}
+
+#[test]
+fn check_position_valid_by_the_lower_of_spot_and_twap_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(1, 1))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::check_position_valid(BTC, 100, 50));
+		let spot_ratio = Some(Ratio::saturating_from_rational(2, 1));
+		assert_eq!(CDPEngineModule::get_collateral_ratio(BTC, 100, 50), spot_ratio);
+
+		// the smoothed price lags behind a spike of the spot price
+		MockPriceSource::set_relative_twap_price(Some(Price::saturating_from_rational(1, 20)));
+		assert_noop!(
+			CDPEngineModule::check_position_valid(BTC, 100, 50),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+		assert_eq!(
+			CDPEngineModule::get_collateral_ratio(BTC, 100, 50),
+			Some(Ratio::saturating_from_rational(1, 10))
+		);
+
+		// a smoothed price above the spot price is ignored
+		MockPriceSource::set_relative_twap_price(Some(Price::saturating_from_integer(2)));
+		assert_eq!(CDPEngineModule::get_collateral_ratio(BTC, 100, 50), spot_ratio);
+	});
+}
+
+#[test]
+fn check_position_valid_failed_when_remain_debit_value_too_small() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(1, 1))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::check_position_valid(BTC, 2, 1),
+			Error::<Runtime>::RemainDebitValueTooSmall,
+		);
+	});
+}
+
+#[test]
+fn check_position_valid_ratio_below_liquidate_ratio() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(10, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::check_position_valid(BTC, 91, 50),
+			Error::<Runtime>::BelowLiquidationRatio,
+		);
+	});
+}
+
+#[test]
+fn check_position_valid_ratio_below_required_ratio() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::check_position_valid(BTC, 89, 50),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+	});
+}
+
+#[test]
+fn adjust_position_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, ACA, 100, 50),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, 20).is_ok(), false);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, -20));
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 30);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 30);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+	});
+}
+
+#[test]
+fn remain_debit_value_too_small_check() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_eq!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, -49).is_ok(), false);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, -100, -50));
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cdp_by_collateral_auction() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::MustBeUnsafe,
+		);
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(3, 1));
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+
+		let liquidate_unsafe_cdp_event = Event::cdp_engine(crate::Event::LiquidateUnsafeCDP(
+			BTC,
+			ALICE,
+			100,
+			50,
+			10,
+			LiquidationStrategy::Auction,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidate_unsafe_cdp_event));
+
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+
+		mock_shutdown();
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::none(), BTC, ALICE),
+			Error::<Runtime>::AlreadyShutdown
+		);
+	});
+}
+
+#[test]
+fn liquidate_paused_when_price_locked() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(3, 1));
+		assert!(CDPEngineModule::is_cdp_unsafe(BTC, 100, 50));
+
+		MockPriceSource::set_price_locked(true);
+		assert!(CDPEngineModule::is_liquidation_paused(BTC));
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::none(), BTC, ALICE),
+			Error::<Runtime>::LiquidationPaused
+		);
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::LiquidationPaused
+		);
+
+		MockPriceSource::set_price_locked(false);
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+	});
+}
+
+#[test]
+fn liquidate_by_keeper_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::MustBeUnsafe,
+		);
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(3, 1));
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(BOB), BTC, ALICE));
+
+		let keeper_rewarded_event = Event::cdp_engine(crate::Event::LiquidationKeeperRewarded(BTC, BOB, 1));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == keeper_rewarded_event));
+		let liquidate_unsafe_cdp_event = Event::cdp_engine(crate::Event::LiquidateUnsafeCDP(
+			BTC,
+			ALICE,
+			100,
+			50,
+			10,
+			LiquidationStrategy::Auction,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidate_unsafe_cdp_event));
+
+		assert_eq!(Currencies::free_balance(BTC, &BOB), 1001);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 99);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
+
+#[test]
+fn bond_liquidator_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AdaptedBasicCurrency::update_balance(&BOB, 1000));
+		assert_ok!(CDPEngineModule::bond_liquidator(Origin::signed(BOB)));
+		let bond_event = Event::cdp_engine(crate::Event::LiquidatorBonded(BOB, 100));
+		assert!(System::events().iter().any(|record| record.event == bond_event));
+		assert_eq!(AdaptedBasicCurrency::reserved_balance(&BOB), 100);
+		assert!(CDPEngineModule::is_bonded_liquidator(&BOB));
+		assert_noop!(
+			CDPEngineModule::bond_liquidator(Origin::signed(BOB)),
+			Error::<Runtime>::AlreadyBonded
+		);
+
+		// lose the priority access when the bond is slashed
+		assert_noop!(
+			CDPEngineModule::slash_liquidator(Origin::signed(BOB), BOB, 30),
+			BadOrigin
+		);
+		assert_ok!(CDPEngineModule::slash_liquidator(Origin::signed(1), BOB, 30));
+		let slash_event = Event::cdp_engine(crate::Event::LiquidatorSlashed(BOB, 30));
+		assert!(System::events().iter().any(|record| record.event == slash_event));
+		assert_eq!(AdaptedBasicCurrency::reserved_balance(&BOB), 70);
+		assert_eq!(AdaptedBasicCurrency::free_balance(&BOB), 900);
+		assert!(!CDPEngineModule::is_bonded_liquidator(&BOB));
+
+		assert_ok!(CDPEngineModule::unbond_liquidator(Origin::signed(BOB)));
+		let unbond_event = Event::cdp_engine(crate::Event::LiquidatorUnbonding(BOB, 11));
+		assert!(System::events().iter().any(|record| record.event == unbond_event));
+		assert_noop!(
+			CDPEngineModule::unbond_liquidator(Origin::signed(BOB)),
+			Error::<Runtime>::AlreadyUnbonding
+		);
+		assert_noop!(
+			CDPEngineModule::withdraw_liquidator_bond(Origin::signed(BOB)),
+			Error::<Runtime>::BondStillLocked
+		);
+
+		// still slashable during the unbonding delay
+		assert_ok!(CDPEngineModule::slash_liquidator(Origin::signed(1), BOB, 100));
+		assert_eq!(CDPEngineModule::liquidators(BOB).map(|info| info.bond), Some(0));
+
+		System::set_block_number(11);
+		assert_ok!(CDPEngineModule::withdraw_liquidator_bond(Origin::signed(BOB)));
+		let withdraw_event = Event::cdp_engine(crate::Event::LiquidatorBondWithdrawn(BOB, 0));
+		assert!(System::events().iter().any(|record| record.event == withdraw_event));
+		assert_eq!(CDPEngineModule::liquidators(BOB), None);
+		assert_eq!(AdaptedBasicCurrency::reserved_balance(&BOB), 0);
+		assert_noop!(
+			CDPEngineModule::withdraw_liquidator_bond(Origin::signed(BOB)),
+			Error::<Runtime>::NotBonded
+		);
+	});
+}
+
+#[test]
+fn liquidate_in_exclusive_window_by_bonded_liquidator() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_set_liquidator_exclusive_window(5);
+		assert_ok!(AdaptedBasicCurrency::update_balance(&BOB, 1000));
+		assert_ok!(CDPEngineModule::bond_liquidator(Origin::signed(BOB)));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE),
+			Error::<Runtime>::MustBeUnsafe,
 		);
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(3, 1));
+
+		// the first liquidation by others starts the exclusive window
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE));
+		let window_event = Event::cdp_engine(crate::Event::LiquidatorExclusiveWindowStarted(BTC, ALICE, 6));
+		assert!(System::events().iter().any(|record| record.event == window_event));
+		assert_eq!(CDPEngineModule::liquidator_exclusive_window_end(BTC, ALICE), Some(6));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE),
+			Error::<Runtime>::InLiquidatorExclusiveWindow,
+		);
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::none(), BTC, ALICE),
+			Error::<Runtime>::InLiquidatorExclusiveWindow,
+		);
+
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(BOB), BTC, ALICE));
+		let liquidate_unsafe_cdp_event = Event::cdp_engine(crate::Event::LiquidateUnsafeCDP(
+			BTC,
+			ALICE,
+			100,
+			50,
+			10,
+			LiquidationStrategy::Exchange,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidate_unsafe_cdp_event));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(CDPEngineModule::liquidator_exclusive_window_end(BTC, ALICE), None);
+	});
+}
+
+#[test]
+fn liquidate_after_exclusive_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_set_liquidator_exclusive_window(5);
+		assert_ok!(AdaptedBasicCurrency::update_balance(&BOB, 1000));
+		assert_ok!(CDPEngineModule::bond_liquidator(Origin::signed(BOB)));
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
 			BTC,
-			Change::NoChange,
-			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
-			Change::NoChange,
-			Change::NoChange,
-			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
 		));
-		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(3, 1));
+		assert_ok!(CDPEngineModule::liquidate(Origin::none(), BTC, ALICE));
+		assert_eq!(CDPEngineModule::liquidator_exclusive_window_end(BTC, ALICE), Some(6));
+
+		// bonded liquidators can only liquidate by swap with DEX in the window
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::InLiquidatorExclusiveWindow,
+		);
 
+		System::set_block_number(6);
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE));
 		let liquidate_unsafe_cdp_event = Event::cdp_engine(crate::Event::LiquidateUnsafeCDP(
 			BTC,
 			ALICE,
 			100,
 			50,
+			10,
 			LiquidationStrategy::Auction,
 		));
 		assert!(System::events()
 			.iter()
 			.any(|record| record.event == liquidate_unsafe_cdp_event));
-
-		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
-		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
-		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
-		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
 		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
 
-		mock_shutdown();
+#[test]
+fn stale_exclusive_window_is_treated_as_no_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_set_liquidator_exclusive_window(5);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(3, 1));
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE));
+		assert_eq!(CDPEngineModule::liquidator_exclusive_window_end(BTC, ALICE), Some(6));
+
+		// the window is stale after another exclusive window duration
+		System::set_block_number(11);
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE));
+		let window_event = Event::cdp_engine(crate::Event::LiquidatorExclusiveWindowStarted(BTC, ALICE, 16));
+		assert!(System::events().iter().any(|record| record.event == window_event));
+		assert_eq!(CDPEngineModule::liquidator_exclusive_window_end(BTC, ALICE), Some(16));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
 		assert_noop!(
-			CDPEngineModule::liquidate(Origin::none(), BTC, ALICE),
-			Error::<Runtime>::AlreadyShutdown
+			CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE),
+			Error::<Runtime>::InLiquidatorExclusiveWindow,
 		);
 	});
 }
 
 #[test]
-fn on_finalize_work() {
+fn accumulate_interest_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
@@ -480,36 +1164,117 @@ fn on_finalize_work() {
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
-		CDPEngineModule::on_finalize(1);
-		assert_eq!(CDPEngineModule::debit_exchange_rate(BTC), None);
-		assert_eq!(CDPEngineModule::debit_exchange_rate(DOT), None);
+		mock_set_now_secs(1);
 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 30));
 		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 30);
-		CDPEngineModule::on_finalize(2);
+		assert_eq!(CDPEngineModule::last_accumulation_secs(BTC), 1);
+		assert_eq!(CDPEngineModule::last_accumulation_secs(DOT), 0);
+		assert_eq!(CDPEngineModule::debit_exchange_rate(BTC), None);
+
+		// accrue lazily, storage is untouched until the position is touched
+		mock_set_now_secs(2);
 		assert_eq!(
-			CDPEngineModule::debit_exchange_rate(BTC),
-			Some(ExchangeRate::saturating_from_rational(101, 100))
+			CDPEngineModule::get_debit_exchange_rate(BTC),
+			ExchangeRate::saturating_from_rational(101, 100)
 		);
-		assert_eq!(CDPEngineModule::debit_exchange_rate(DOT), None);
-		CDPEngineModule::on_finalize(3);
+		assert_eq!(CDPEngineModule::debit_exchange_rate(BTC), None);
+		assert_eq!(CDPEngineModule::get_debit_exchange_rate(DOT), ExchangeRate::one());
+
+		mock_set_now_secs(3);
 		assert_eq!(
-			CDPEngineModule::debit_exchange_rate(BTC),
-			Some(ExchangeRate::saturating_from_rational(10201, 10000))
+			CDPEngineModule::get_debit_exchange_rate(BTC),
+			ExchangeRate::saturating_from_rational(10201, 10000)
 		);
-		assert_eq!(CDPEngineModule::debit_exchange_rate(DOT), None);
 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, -30));
 		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
 		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 0);
-		CDPEngineModule::on_finalize(4);
 		assert_eq!(
 			CDPEngineModule::debit_exchange_rate(BTC),
 			Some(ExchangeRate::saturating_from_rational(10201, 10000))
 		);
+		assert_eq!(CDPEngineModule::last_accumulation_secs(BTC), 3);
+
+		// no interest accrues without debit
+		mock_set_now_secs(4);
+		assert_eq!(
+			CDPEngineModule::get_debit_exchange_rate(BTC),
+			ExchangeRate::saturating_from_rational(10201, 10000)
+		);
 		assert_eq!(CDPEngineModule::debit_exchange_rate(DOT), None);
 	});
 }
 
+#[test]
+fn accumulate_interest_issue_surplus_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		mock_set_now_secs(1);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 1000, 100));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+
+		mock_set_now_secs(3);
+		assert_eq!(CDPEngineModule::get_accrued_stability_fee(BTC, 100), 21);
+		CDPEngineModule::accumulate_interest(BTC);
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(121, 100))
+		);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 21);
+		assert_eq!(CDPEngineModule::get_accrued_stability_fee(BTC, 100), 0);
+
+		// accumulate again in the same second is no-op
+		CDPEngineModule::accumulate_interest(BTC);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 21);
+	});
+}
+
+#[test]
+fn update_stability_fee_accumulate_interest_first() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		mock_set_now_secs(1);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 30));
+
+		mock_set_now_secs(2);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 100))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(101, 100))
+		);
+
+		mock_set_now_secs(3);
+		assert_eq!(
+			CDPEngineModule::get_debit_exchange_rate(BTC),
+			ExchangeRate::saturating_from_rational(10302, 10000)
+		);
+	});
+}
+
 #[test]
 fn on_emergency_shutdown_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -522,15 +1287,22 @@ fn on_emergency_shutdown_work() {
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
+		mock_set_now_secs(1);
 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 30));
-		CDPEngineModule::on_finalize(1);
+		mock_set_now_secs(2);
+		CDPEngineModule::accumulate_interest(BTC);
 		assert_eq!(
 			CDPEngineModule::debit_exchange_rate(BTC),
 			Some(ExchangeRate::saturating_from_rational(101, 100))
 		);
 		mock_shutdown();
 		assert_eq!(<Runtime as Config>::EmergencyShutdown::is_shutdown(), true);
-		CDPEngineModule::on_finalize(2);
+		mock_set_now_secs(3);
+		assert_eq!(
+			CDPEngineModule::get_debit_exchange_rate(BTC),
+			ExchangeRate::saturating_from_rational(101, 100)
+		);
+		CDPEngineModule::accumulate_interest(BTC);
 		assert_eq!(
 			CDPEngineModule::debit_exchange_rate(BTC),
 			Some(ExchangeRate::saturating_from_rational(101, 100))
@@ -538,6 +1310,49 @@ fn on_emergency_shutdown_work() {
 	});
 }
 
+#[test]
+fn accumulate_interest_before_emergency_shutdown() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		mock_set_now_secs(1);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 30));
+
+		mock_set_now_secs(3);
+		OnEmergencyShutdown::<Runtime>::happened(&());
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(10201, 10000))
+		);
+		assert_eq!(CDPEngineModule::last_accumulation_secs(BTC), 3);
+		assert_eq!(CDPEngineModule::last_accumulation_secs(DOT), 3);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_checkpoint_all_collaterals() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(CDPEngineModule::storage_version(), Releases::V1_0_0);
+		mock_set_now_secs(5);
+		<CDPEngineModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(CDPEngineModule::storage_version(), Releases::V2_0_0);
+		assert_eq!(CDPEngineModule::last_accumulation_secs(BTC), 5);
+		assert_eq!(CDPEngineModule::last_accumulation_secs(DOT), 5);
+
+		// only runs once
+		mock_set_now_secs(6);
+		<CDPEngineModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(CDPEngineModule::last_accumulation_secs(BTC), 5);
+	});
+}
+
 #[test]
 fn settle_cdp_has_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -580,3 +1395,166 @@ fn settle_cdp_has_debit_work() {
 		);
 	});
 }
+
+#[test]
+fn close_cdp_has_debit_by_dex_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 100),
+			Error::<Runtime>::NoDebitValue,
+		);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+
+		assert_noop!(
+			CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 5),
+			module_dex::Error::<Runtime>::ExcessiveSupplyAmount,
+		);
+
+		assert_ok!(CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 6));
+		let close_cdp_event = Event::cdp_engine(crate::Event::CloseCDPInDebitByDEX(BTC, ALICE, 6, 94, 50));
+		assert!(System::events().iter().any(|record| record.event == close_cdp_event));
+
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 994);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 50);
+	});
+}
+
+#[test]
+fn close_cdp_has_debit_by_dex_fails_on_unsafe_cdp() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		tighten_liquidation_ratio(BTC, Ratio::saturating_from_rational(3, 1));
+		assert_noop!(
+			CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 100),
+			Error::<Runtime>::MustBeSafe,
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_selects_shard_by_rotation() {
+	let (mut ext, pool_state) = offchain_ext();
+	ext.execute_with(|| {
+		set_offchain_worker_configs(1000, 1000, 2);
+		let accounts = (1..=20).collect::<Vec<AccountId>>();
+		insert_positions(BTC, &accounts);
+		insert_positions(DOT, &accounts);
+		mock_shutdown();
+
+		let shard_positions = |currency_id: CurrencyId, shard: u32| {
+			sorted_by_storage_key(currency_id, &accounts)
+				.into_iter()
+				.filter(|who| shard_of(currency_id, who, 2) == shard)
+				.map(|who| (currency_id, who))
+				.collect::<Vec<_>>()
+		};
+		assert!(!shard_positions(BTC, 0).is_empty());
+		assert!(!shard_positions(BTC, 1).is_empty());
+
+		// 2 collaterals with 2 shards, the units of work rotate by the block number
+		assert_eq!(run_offchain_worker(0, &pool_state), shard_positions(BTC, 0));
+		assert_eq!(run_offchain_worker(1, &pool_state), shard_positions(BTC, 1));
+		assert_eq!(run_offchain_worker(2, &pool_state), shard_positions(DOT, 0));
+		assert_eq!(run_offchain_worker(3, &pool_state), shard_positions(DOT, 1));
+		assert_eq!(run_offchain_worker(5, &pool_state), shard_positions(BTC, 1));
+
+		// the rotation offset of the node shifts the unit of work
+		StorageValueRef::persistent(&OFFCHAIN_WORKER_ROTATION_OFFSET).set(&2u32);
+		assert_eq!(run_offchain_worker(0, &pool_state), shard_positions(DOT, 0));
+	});
+}
+
+#[test]
+fn offchain_worker_wraps_cursor_around() {
+	let (mut ext, pool_state) = offchain_ext();
+	ext.execute_with(|| {
+		set_offchain_worker_configs(3, 1000, 1);
+		let accounts = sorted_by_storage_key(BTC, &[1, 2, 3, 4, 5]);
+		insert_positions(BTC, &accounts);
+		mock_shutdown();
+
+		let settled = |accounts: &[AccountId]| accounts.iter().map(|who| (BTC, *who)).collect::<Vec<_>>();
+		let cursor = StorageValueRef::persistent(&[OFFCHAIN_WORKER_DATA, &(1u32, 0u32, 0u32).encode()].concat());
+
+		// continue from the cursor after the max iterations
+		assert_eq!(run_offchain_worker(0, &pool_state), settled(&accounts[..3]));
+		assert_eq!(
+			cursor.get::<Vec<u8>>(),
+			Some(Some(loans::Positions::<Runtime>::hashed_key_for(BTC, accounts[2])))
+		);
+		assert_eq!(run_offchain_worker(2, &pool_state), settled(&accounts[3..]));
+		assert_eq!(cursor.get::<Vec<u8>>(), None);
+
+		// start over from the beginning of the shard
+		assert_eq!(run_offchain_worker(4, &pool_state), settled(&accounts[..3]));
+	});
+}
+
+#[test]
+fn offchain_worker_caps_submissions() {
+	let (mut ext, pool_state) = offchain_ext();
+	ext.execute_with(|| {
+		set_offchain_worker_configs(1000, 2, 1);
+		let accounts = sorted_by_storage_key(BTC, &[1, 2, 3, 4, 5]);
+		insert_positions(BTC, &accounts);
+		// the positions without debit are iterated but not submitted
+		loans::Positions::<Runtime>::insert(
+			BTC,
+			6,
+			Position {
+				collateral: 100,
+				debit: 0,
+			},
+		);
+		mock_shutdown();
+
+		let mut settled = vec![];
+		for now in (0..6).step_by(2) {
+			let submissions = run_offchain_worker(now, &pool_state);
+			assert!(submissions.len() <= 2);
+			settled.extend(submissions);
+		}
+		assert_eq!(settled, accounts.iter().map(|who| (BTC, *who)).collect::<Vec<_>>());
+	});
+}
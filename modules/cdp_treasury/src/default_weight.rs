@@ -25,4 +25,7 @@ impl crate::WeightInfo for () {
 	fn set_collateral_auction_maximum_size() -> Weight {
 		(54_430_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
 	}
+	fn set_collateral_auction_maximum_target() -> Weight {
+		(54_430_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -18,7 +18,7 @@ use sp_runtime::{
 	traits::{AccountIdConversion, One, Zero},
 	DispatchError, DispatchResult, FixedPointNumber, ModuleId,
 };
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Ratio};
+use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, Ratio};
 
 mod benchmarking;
 mod default_weight;
@@ -32,6 +32,7 @@ pub trait WeightInfo {
 	fn auction_debit() -> Weight;
 	fn auction_collateral() -> Weight;
 	fn set_collateral_auction_maximum_size() -> Weight;
+	fn set_collateral_auction_maximum_target() -> Weight;
 }
 
 #[frame_support::pallet]
@@ -72,6 +73,26 @@ pub mod module {
 		/// from liquidation.
 		type ModuleId: Get<ModuleId>;
 
+		#[pallet::constant]
+		/// The delay in blocks before the system debit becomes bad debt and
+		/// can be covered by debit auctions.
+		type BadDebtDelay: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The fixed amount of stable currency to raise per debit auction
+		/// created for bad debt. If set to 0, does not create debit auctions
+		/// for bad debt automatically.
+		type DebitAuctionFixedSize: Get<Balance>;
+
+		#[pallet::constant]
+		/// The initial amount of native currency to mint per debit auction
+		/// created for bad debt.
+		type DebitAuctionInitialAmount: Get<Balance>;
+
+		/// Emergency shutdown, stop creating debit auctions for bad debt after
+		/// shutdown.
+		type EmergencyShutdown: EmergencyShutdown;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -94,6 +115,15 @@ pub mod module {
 		/// The fixed size for collateral auction under specific collateral type
 		/// updated. \[collateral_type, new_size\]
 		CollateralAuctionMaximumSizeUpdated(CurrencyId, Balance),
+		/// The maximum target of stable currency per collateral auction under
+		/// specific collateral type updated. \[collateral_type, new_target\]
+		CollateralAuctionMaximumTargetUpdated(CurrencyId, Balance),
+		/// System debit is queued and will become bad debt at the release
+		/// block. \[release_block_number, debit_amount\]
+		DebitQueued(T::BlockNumber, Balance),
+		/// Queued system debit matured into bad debt.
+		/// \[release_block_number, debit_amount\]
+		DebitMatured(T::BlockNumber, Balance),
 	}
 
 	/// The maximum amount of collateral amount for sale per collateral auction
@@ -101,12 +131,30 @@ pub mod module {
 	#[pallet::getter(fn collateral_auction_maximum_size)]
 	pub type CollateralAuctionMaximumSize<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
 
+	/// The maximum target amount of stable currency per collateral auction
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_auction_maximum_target)]
+	pub type CollateralAuctionMaximumTarget<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	/// Current total debit value of system. It's not same as debit in CDP
 	/// engine, it is the bad debt of the system.
 	#[pallet::storage]
 	#[pallet::getter(fn debit_pool)]
 	pub type DebitPool<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// The queued system debit by the block at which it becomes bad debt,
+	/// `BadDebtDelay` blocks after it's queued. The queued debit can be
+	/// offset by the surplus, e.g. the proceeds of collateral auctions, and
+	/// the bad debt is covered by the debit auctions which mint native token.
+	#[pallet::storage]
+	#[pallet::getter(fn debit_queue)]
+	pub type DebitQueue<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Balance, ValueQuery>;
+
+	/// Total system debit in the queue.
+	#[pallet::storage]
+	#[pallet::getter(fn total_queued_debit)]
+	pub type TotalQueuedDebit<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub collateral_auction_maximum_size: Vec<(CurrencyId, Balance)>,
@@ -137,10 +185,15 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		/// Handle excessive surplus or debits of system when block end
-		fn on_finalize(_now: T::BlockNumber) {
+		/// Handle excessive surplus or debits of system when block begin
+		fn on_initialize(now: T::BlockNumber) -> Weight {
 			// offset the same amount between debit pool and surplus pool
 			Self::offset_surplus_and_debit();
+			Self::release_matured_debit(now);
+			let auctions_count = Self::auction_bad_debt();
+			T::DbWeight::get()
+				.reads_writes(6, 4)
+				.saturating_add(T::WeightInfo::auction_debit().saturating_mul(auctions_count.into()))
 		}
 	}
 
@@ -213,6 +266,26 @@ pub mod module {
 			Self::deposit_event(Event::CollateralAuctionMaximumSizeUpdated(currency_id, size));
 			Ok(().into())
 		}
+
+		/// Update the maximum target of stable currency per collateral
+		/// auction under specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `target`: collateral auction maximum target, 0 means no limit
+		#[pallet::weight((T::WeightInfo::set_collateral_auction_maximum_target(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_collateral_auction_maximum_target(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			target: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			CollateralAuctionMaximumTarget::<T>::insert(currency_id, target);
+			Self::deposit_event(Event::CollateralAuctionMaximumTargetUpdated(currency_id, target));
+			Ok(().into())
+		}
 	}
 }
 
@@ -222,6 +295,25 @@ impl<T: Config> Pallet<T> {
 		T::ModuleId::get().into_account()
 	}
 
+	/// Get lots count to split `total` into lots of at most `maximum_per_lot`,
+	/// zero `maximum_per_lot` means no limit.
+	fn lots_count_of(total: Balance, maximum_per_lot: Balance) -> Balance {
+		if maximum_per_lot.is_zero() || total <= maximum_per_lot {
+			return One::one();
+		}
+
+		let mut count = total
+			.checked_div(maximum_per_lot)
+			.expect("maximum per lot is not zero; qed");
+		let remainder = total
+			.checked_rem(maximum_per_lot)
+			.expect("maximum per lot is not zero; qed");
+		if !remainder.is_zero() {
+			count = count.saturating_add(One::one());
+		}
+		count
+	}
+
 	/// Get current total surplus of system.
 	pub fn surplus_pool() -> Balance {
 		T::Currency::free_balance(T::GetStableCurrencyId::get(), &Self::account_id())
@@ -238,6 +330,46 @@ impl<T: Config> Pallet<T> {
 			.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
 	}
 
+	/// Get the system debit which is out of the queue and not in debit
+	/// auction, it's the bad debt not covered yet.
+	pub fn bad_debt() -> Balance {
+		Self::debit_pool()
+			.saturating_sub(Self::total_queued_debit())
+			.saturating_sub(T::AuctionManagerHandler::get_total_debit_in_auction())
+	}
+
+	fn release_matured_debit(now: T::BlockNumber) {
+		let matured_debit = DebitQueue::<T>::take(now);
+		if !matured_debit.is_zero() {
+			TotalQueuedDebit::<T>::mutate(|total| *total = total.saturating_sub(matured_debit));
+			Self::deposit_event(Event::DebitMatured(now, matured_debit));
+		}
+	}
+
+	/// Create debit auctions for the bad debt, return the count of attempted
+	/// auctions.
+	fn auction_bad_debt() -> u32 {
+		let fixed_size = T::DebitAuctionFixedSize::get();
+		if fixed_size.is_zero() || T::EmergencyShutdown::is_shutdown() {
+			return 0;
+		}
+
+		let lots_count = Self::bad_debt()
+			.checked_div(fixed_size)
+			.expect("fixed size is not zero; qed");
+		let lots_count = sp_std::cmp::min(lots_count, T::MaxAuctionsCount::get().into());
+		let mut created_lots: Balance = Zero::zero();
+		while created_lots < lots_count {
+			if T::AuctionManagerHandler::new_debit_auction(T::DebitAuctionInitialAmount::get(), fixed_size).is_err() {
+				// count the failed attempt as well
+				created_lots += One::one();
+				break;
+			}
+			created_lots += One::one();
+		}
+		created_lots as u32
+	}
+
 	fn offset_surplus_and_debit() {
 		let offset_amount = sp_std::cmp::min(Self::debit_pool(), Self::surplus_pool());
 
@@ -279,7 +411,17 @@ impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
 		DebitPool::<T>::try_mutate(|debit_pool| -> DispatchResult {
 			*debit_pool = debit_pool.checked_add(amount).ok_or(Error::<T>::DebitPoolOverflow)?;
 			Ok(())
-		})
+		})?;
+
+		// the debit is bad debt immediately if there's no delay
+		let bad_debt_delay = T::BadDebtDelay::get();
+		if !amount.is_zero() && !bad_debt_delay.is_zero() {
+			let release_at = <frame_system::Module<T>>::block_number().saturating_add(bad_debt_delay);
+			DebitQueue::<T>::mutate(release_at, |queued| *queued = queued.saturating_add(amount));
+			TotalQueuedDebit::<T>::mutate(|total| *total = total.saturating_add(amount));
+			Self::deposit_event(Event::DebitQueued(release_at, amount));
+		}
+		Ok(())
 	}
 
 	fn on_system_surplus(amount: Self::Balance) -> DispatchResult {
@@ -304,6 +446,10 @@ impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
 		T::Currency::transfer(T::GetStableCurrencyId::get(), from, &Self::account_id(), surplus)
 	}
 
+	fn withdraw_surplus(to: &T::AccountId, surplus: Self::Balance) -> DispatchResult {
+		T::Currency::transfer(T::GetStableCurrencyId::get(), &Self::account_id(), to, surplus)
+	}
+
 	fn deposit_collateral(from: &T::AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
 		T::Currency::transfer(currency_id, from, &Self::account_id(), amount)
 	}
@@ -373,25 +519,15 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 
 		let mut unhandled_collateral_amount = amount;
 		let mut unhandled_target = target;
-		let collateral_auction_maximum_size = Self::collateral_auction_maximum_size(currency_id);
 		let max_auctions_count: Balance = T::MaxAuctionsCount::get().into();
-		let lots_count = if !splited
-			|| max_auctions_count.is_zero()
-			|| collateral_auction_maximum_size.is_zero()
-			|| amount <= collateral_auction_maximum_size
-		{
+		let lots_count = if !splited || max_auctions_count.is_zero() {
 			One::one()
 		} else {
-			let mut count = amount
-				.checked_div(collateral_auction_maximum_size)
-				.expect("collateral auction maximum size is not zero; qed");
-
-			let remainder = amount
-				.checked_rem(collateral_auction_maximum_size)
-				.expect("collateral auction maximum size is not zero; qed");
-			if !remainder.is_zero() {
-				count = count.saturating_add(One::one());
-			}
+			// split by both the maximum collateral size and the maximum target per lot
+			let count = sp_std::cmp::max(
+				Self::lots_count_of(amount, Self::collateral_auction_maximum_size(currency_id)),
+				Self::lots_count_of(target, Self::collateral_auction_maximum_target(currency_id)),
+			);
 			sp_std::cmp::min(count, max_auctions_count)
 		};
 		let average_amount_per_lot = amount.checked_div(lots_count).expect("lots count is at least 1; qed");
@@ -103,6 +103,7 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const TwapPeriod: BlockNumber = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC)];
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 }
@@ -112,6 +113,7 @@ impl module_dex::Config for Runtime {
 	type Currency = Currencies;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TwapPeriod = TwapPeriod;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = ();
 	type WeightInfo = ();
@@ -122,6 +124,7 @@ thread_local! {
 	pub static TOTAL_COLLATERAL_AUCTION: RefCell<u32> = RefCell::new(0);
 	pub static TOTAL_COLLATERAL_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
 	pub static TOTAL_DEBIT_AUCTION: RefCell<u32> = RefCell::new(0);
+	pub static TOTAL_DEBIT_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
 	pub static TOTAL_SURPLUS_AUCTION: RefCell<u32> = RefCell::new(0);
 }
 
@@ -142,8 +145,9 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
-	fn new_debit_auction(_amount: Self::Balance, _fix: Self::Balance) -> DispatchResult {
+	fn new_debit_auction(_amount: Self::Balance, fix: Self::Balance) -> DispatchResult {
 		TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut() += 1);
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut() += fix);
 		Ok(())
 	}
 
@@ -165,7 +169,7 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 	}
 
 	fn get_total_debit_in_auction() -> Self::Balance {
-		Default::default()
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut())
 	}
 
 	fn get_total_target_in_auction() -> Self::Balance {
@@ -180,12 +184,26 @@ ord_parameter_types! {
 
 parameter_types! {
 	pub const CDPTreasuryModuleId: ModuleId = ModuleId(*b"aca/cdpt");
+	pub const BadDebtDelay: BlockNumber = 10;
+	pub const DebitAuctionFixedSize: Balance = 100;
+	pub const DebitAuctionInitialAmount: Balance = 200;
 }
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
 }
 
+pub fn mock_shutdown() {
+	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
+}
+
+pub struct MockEmergencyShutdown;
+impl EmergencyShutdown for MockEmergencyShutdown {
+	fn is_shutdown() -> bool {
+		IS_SHUTDOWN.with(|v| *v.borrow_mut())
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
@@ -195,6 +213,10 @@ impl Config for Runtime {
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type BadDebtDelay = BadDebtDelay;
+	type DebitAuctionFixedSize = DebitAuctionFixedSize;
+	type DebitAuctionInitialAmount = DebitAuctionInitialAmount;
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type WeightInfo = ();
 }
 
@@ -54,7 +54,7 @@ fn on_system_surplus_work() {
 }
 
 #[test]
-fn offset_surplus_and_debit_on_finalize_work() {
+fn offset_surplus_and_debit_on_initialize_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 0);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
@@ -62,19 +62,19 @@ fn offset_surplus_and_debit_on_finalize_work() {
 		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 1000);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
-		CDPTreasuryModule::on_finalize(1);
+		CDPTreasuryModule::on_initialize(1);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 1000);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_ok!(CDPTreasuryModule::on_system_debit(300));
 		assert_eq!(CDPTreasuryModule::debit_pool(), 300);
-		CDPTreasuryModule::on_finalize(2);
+		CDPTreasuryModule::on_initialize(2);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 700);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 700);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_ok!(CDPTreasuryModule::on_system_debit(800));
 		assert_eq!(CDPTreasuryModule::debit_pool(), 800);
-		CDPTreasuryModule::on_finalize(3);
+		CDPTreasuryModule::on_initialize(3);
 		assert_eq!(Currencies::free_balance(AUSD, &CDPTreasuryModule::account_id()), 0);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
@@ -121,6 +121,18 @@ fn deposit_surplus_work() {
 	});
 }
 
+#[test]
+fn withdraw_surplus_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::deposit_surplus(&ALICE, 300));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 300);
+		assert!(CDPTreasuryModule::withdraw_surplus(&BOB, 301).is_err());
+		assert_ok!(CDPTreasuryModule::withdraw_surplus(&BOB, 200));
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+	});
+}
+
 #[test]
 fn deposit_collateral_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -278,6 +290,46 @@ fn create_collateral_auctions_work() {
 	});
 }
 
+#[test]
+fn create_collateral_auctions_split_by_target_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Currencies::deposit(BTC, &CDPTreasuryModule::account_id(), 10000));
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_maximum_target(
+			Origin::signed(1),
+			BTC,
+			300
+		));
+
+		// target < collateral auction maximum target
+		// auction + 1
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 1000, 200, ALICE, true
+		));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 1);
+
+		// split by target
+		// auction + 4
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 1000, 1000, ALICE, true
+		));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 5);
+		assert_eq!(TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut()), 2000);
+
+		// the larger lots count of size and target is used
+		// auction + 5
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_maximum_size(
+			Origin::signed(1),
+			BTC,
+			200
+		));
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 1000, 600, ALICE, true
+		));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 10);
+		assert_eq!(TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut()), 3000);
+	});
+}
+
 #[test]
 fn auction_surplus_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -330,3 +382,103 @@ fn set_collateral_auction_maximum_size_work() {
 			.any(|record| record.event == update_collateral_auction_maximum_size_event));
 	});
 }
+
+#[test]
+fn set_collateral_auction_maximum_target_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::collateral_auction_maximum_target(BTC), 0);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_auction_maximum_target(Origin::signed(5), BTC, 200),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_maximum_target(
+			Origin::signed(1),
+			BTC,
+			200
+		));
+		assert_eq!(CDPTreasuryModule::collateral_auction_maximum_target(BTC), 200);
+
+		let update_collateral_auction_maximum_target_event =
+			Event::cdp_treasury(crate::Event::CollateralAuctionMaximumTargetUpdated(BTC, 200));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_collateral_auction_maximum_target_event));
+	});
+}
+
+#[test]
+fn on_system_debit_queue_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_debit(300));
+		assert_ok!(CDPTreasuryModule::on_system_debit(50));
+		assert_eq!(CDPTreasuryModule::debit_queue(11), 350);
+		assert_eq!(CDPTreasuryModule::total_queued_debit(), 350);
+		assert_eq!(CDPTreasuryModule::bad_debt(), 0);
+
+		let debit_queued_event = Event::cdp_treasury(crate::Event::DebitQueued(11, 300));
+		assert!(System::events().iter().any(|record| record.event == debit_queued_event));
+
+		// not mature until the delay has passed
+		CDPTreasuryModule::on_initialize(10);
+		assert_eq!(CDPTreasuryModule::total_queued_debit(), 350);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 0);
+
+		CDPTreasuryModule::on_initialize(11);
+		assert_eq!(CDPTreasuryModule::debit_queue(11), 0);
+		assert_eq!(CDPTreasuryModule::total_queued_debit(), 0);
+		let debit_matured_event = Event::cdp_treasury(crate::Event::DebitMatured(11, 350));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == debit_matured_event));
+
+		// only full lots are auctioned
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 3);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 300);
+		assert_eq!(CDPTreasuryModule::bad_debt(), 50);
+	});
+}
+
+#[test]
+fn queued_debit_offset_by_surplus_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_debit(300));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(200));
+		CDPTreasuryModule::on_initialize(1);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+
+		CDPTreasuryModule::on_initialize(11);
+		assert_eq!(CDPTreasuryModule::bad_debt(), 0);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 1);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 100);
+	});
+}
+
+#[test]
+fn auction_bad_debt_capped_by_max_auctions_count() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_debit(1000));
+		CDPTreasuryModule::on_initialize(11);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 5);
+		assert_eq!(CDPTreasuryModule::bad_debt(), 500);
+
+		CDPTreasuryModule::on_initialize(12);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 10);
+		assert_eq!(CDPTreasuryModule::bad_debt(), 0);
+	});
+}
+
+#[test]
+fn auction_bad_debt_stopped_after_shutdown() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_debit(300));
+		mock_shutdown();
+		CDPTreasuryModule::on_initialize(11);
+		assert_eq!(CDPTreasuryModule::bad_debt(), 300);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 0);
+	});
+}
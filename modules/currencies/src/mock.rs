@@ -122,6 +122,7 @@ ord_parameter_types! {
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const TransactionIndexPeriod: u64 = 10;
 }
 
 impl module_evm::Config for Runtime {
@@ -143,6 +144,7 @@ impl module_evm::Config for Runtime {
 	type DeveloperDeposit = DeveloperDeposit;
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
+	type TransactionIndexPeriod = TransactionIndexPeriod;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type WeightInfo = ();
@@ -7,7 +7,10 @@
 //! trading, DEX also participates in CDP liquidation, which is faster than
 //! liquidation by auction when the liquidity is sufficient. And providing
 //! market making liquidity for DEX will also receive stable currency as
-//! additional reward for its participation in the CDP liquidation.
+//! additional reward for its participation in the CDP liquidation. The
+//! cumulative prices of the liquidity pools are accumulated before every
+//! change of the pools, to provide the time weighted average prices which are
+//! resistant to the manipulation within a short period.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments)]
@@ -75,6 +78,19 @@ impl<Balance, BlockNumber> Default for TradingPairStatus<Balance, BlockNumber> {
 	}
 }
 
+/// Cumulative prices of TradingPair, the sums of the prices weighted by the
+/// number of blocks they last. They wrap around on overflow, only the
+/// differences between them are meaningful.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct CumulativePrices<BlockNumber> {
+	/// cumulative price of token_0 in token_1.
+	pub price_0_cumulative: Price,
+	/// cumulative price of token_1 in token_0.
+	pub price_1_cumulative: Price,
+	/// The block number that the prices are accumulated to.
+	pub updated_at: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -102,6 +118,11 @@ pub mod module {
 		#[pallet::constant]
 		type ModuleId: Get<ModuleId>;
 
+		/// The min number of blocks that the time weighted average price is
+		/// averaged over.
+		#[pallet::constant]
+		type TwapPeriod: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 
@@ -190,6 +211,26 @@ pub mod module {
 	pub type ProvisioningPool<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, T::AccountId, (Balance, Balance), ValueQuery>;
 
+	/// Cumulative prices of TradingPair, accumulated before every change of
+	/// the liquidity pool.
+	#[pallet::storage]
+	#[pallet::getter(fn price_cumulatives)]
+	pub type PriceCumulatives<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, CumulativePrices<T::BlockNumber>, ValueQuery>;
+
+	/// The older and newer observations of the cumulative prices of
+	/// TradingPair, the newer one is rotated to the older one once it's at
+	/// least `TwapPeriod` old. The TWAP is measured from the older one.
+	#[pallet::storage]
+	#[pallet::getter(fn price_observations)]
+	pub type PriceObservations<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TradingPair,
+		(CumulativePrices<T::BlockNumber>, CumulativePrices<T::BlockNumber>),
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub initial_listing_trading_pairs: Vec<(TradingPair, (Balance, Balance), (Balance, Balance), T::BlockNumber)>,
@@ -550,6 +591,7 @@ impl<T: Config> Pallet<T> {
 				}
 
 				// inject provision to liquidity pool
+				Self::accumulate_prices(trading_pair);
 				LiquidityPool::<T>::mutate(trading_pair, |(pool_0, pool_1)| {
 					*pool_0 = pool_0.saturating_add(provision_parameters.accumulated_provision.0);
 					*pool_1 = pool_1.saturating_sub(provision_parameters.accumulated_provision.1);
@@ -654,6 +696,7 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::MustBeEnabled,
 		);
 
+		Self::accumulate_prices(trading_pair);
 		LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
 			let total_shares = T::Currency::total_issuance(lp_share_currency_id);
 			let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.0 {
@@ -734,6 +777,7 @@ impl<T: Config> Pallet<T> {
 			.get_dex_share_currency_id()
 			.ok_or(Error::<T>::InvalidCurrencyId)?;
 
+		Self::accumulate_prices(trading_pair);
 		LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
 			let total_shares = T::Currency::total_issuance(lp_share_currency_id);
 			let proportion = Ratio::checked_from_rational(remove_share, total_shares).unwrap_or_default();
@@ -773,6 +817,84 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The cumulative prices of trading_pair accumulated to `now` with the
+	/// current prices of the liquidity pool, nothing is accumulated while
+	/// the pool is empty.
+	fn cumulative_prices_at(trading_pair: TradingPair, now: T::BlockNumber) -> CumulativePrices<T::BlockNumber> {
+		let mut cumulative_prices = Self::price_cumulatives(trading_pair);
+		let elapsed: u128 = now.saturating_sub(cumulative_prices.updated_at).unique_saturated_into();
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		if !elapsed.is_zero() && !pool_0.is_zero() && !pool_1.is_zero() {
+			if let (Some(price_0), Some(price_1)) = (
+				Price::checked_from_rational(pool_1, pool_0),
+				Price::checked_from_rational(pool_0, pool_1),
+			) {
+				let accumulate = |cumulative: Price, price: Price| {
+					Price::from_inner(
+						cumulative
+							.into_inner()
+							.wrapping_add(price.into_inner().wrapping_mul(elapsed)),
+					)
+				};
+				cumulative_prices.price_0_cumulative = accumulate(cumulative_prices.price_0_cumulative, price_0);
+				cumulative_prices.price_1_cumulative = accumulate(cumulative_prices.price_1_cumulative, price_1);
+			}
+		}
+		cumulative_prices.updated_at = now;
+		cumulative_prices
+	}
+
+	/// Accumulate the cumulative prices of trading_pair with the liquidity
+	/// pool before it's changed, and rotate the observations.
+	fn accumulate_prices(trading_pair: TradingPair) {
+		let now = frame_system::Module::<T>::block_number();
+		let cumulative_prices = Self::cumulative_prices_at(trading_pair, now);
+		PriceCumulatives::<T>::insert(trading_pair, cumulative_prices);
+
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		if pool_0.is_zero() || pool_1.is_zero() {
+			// no price while the pool is empty, observe afresh from now on
+			PriceObservations::<T>::insert(trading_pair, (cumulative_prices, cumulative_prices));
+		} else {
+			PriceObservations::<T>::mutate(trading_pair, |maybe_observations| match maybe_observations {
+				Some((older, newer)) => {
+					if now.saturating_sub(newer.updated_at) >= T::TwapPeriod::get() {
+						*older = *newer;
+						*newer = cumulative_prices;
+					}
+				}
+				None => *maybe_observations = Some((cumulative_prices, cumulative_prices)),
+			});
+		}
+	}
+
+	/// The time weighted average price of currency_id_a in currency_id_b
+	/// since the older observation, `None` if it's not `TwapPeriod` old yet.
+	fn get_twap(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Option<Price> {
+		let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)?;
+		let (older, _) = Self::price_observations(trading_pair)?;
+		let now = frame_system::Module::<T>::block_number();
+		let period = now.saturating_sub(older.updated_at);
+		if period.is_zero() || period < T::TwapPeriod::get() {
+			return None;
+		}
+
+		let current = Self::cumulative_prices_at(trading_pair, now);
+		let (current_cumulative, older_cumulative) = if currency_id_a == trading_pair.0 {
+			(current.price_0_cumulative, older.price_0_cumulative)
+		} else {
+			(current.price_1_cumulative, older.price_1_cumulative)
+		};
+		let period: u128 = period.unique_saturated_into();
+		let twap = Price::from_inner(
+			current_cumulative
+				.into_inner()
+				.wrapping_sub(older_cumulative.into_inner())
+				/ period,
+		);
+		Some(twap).filter(|price| !price.is_zero())
+	}
+
 	/// Get how much target amount will be got for specific supply amount
 	/// and price impact
 	fn get_target_amount(supply_pool: Balance, target_pool: Balance, supply_amount: Balance) -> Balance {
@@ -909,6 +1031,7 @@ impl<T: Config> Pallet<T> {
 		target_decrement: Balance,
 	) {
 		if let Some(trading_pair) = TradingPair::from_token_currency_ids(supply_currency_id, target_currency_id) {
+			Self::accumulate_prices(trading_pair);
 			LiquidityPool::<T>::mutate(trading_pair, |(pool_0, pool_1)| {
 				if supply_currency_id == trading_pair.0 {
 					*pool_0 = pool_0.saturating_add(supply_increment);
@@ -999,6 +1122,10 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 		Self::get_liquidity(currency_id_a, currency_id_b)
 	}
 
+	fn get_twap_price(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Option<Price> {
+		Self::get_twap(currency_id_a, currency_id_b)
+	}
+
 	fn get_swap_target_amount(
 		path: &[CurrencyId],
 		supply_amount: Balance,
@@ -1038,4 +1165,22 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 	) -> sp_std::result::Result<Balance, DispatchError> {
 		Self::do_swap_with_exact_target(who, path, target_amount, max_supply_amount, price_impact_limit)
 	}
+
+	fn add_liquidity(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		deposit_increment_share: bool,
+	) -> DispatchResult {
+		Self::do_add_liquidity(
+			who,
+			currency_id_a,
+			currency_id_b,
+			max_amount_a,
+			max_amount_b,
+			deposit_increment_share,
+		)
+	}
 }
@@ -92,6 +92,7 @@ ord_parameter_types! {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const TwapPeriod: BlockNumber = 10;
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 }
 
@@ -100,6 +101,7 @@ impl Config for Runtime {
 	type Currency = Tokens;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TwapPeriod = TwapPeriod;
 	type ModuleId = DEXModuleId;
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
@@ -591,6 +591,63 @@ fn _swap_work() {
 		});
 }
 
+#[test]
+fn get_twap_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			LiquidityPool::<Runtime>::insert(AUSD_DOT_PAIR, (50000, 10000));
+			assert_eq!(DexModule::get_twap(DOT, AUSD), None);
+
+			DexModule::_swap(AUSD, DOT, 1000, 1000);
+			assert_eq!(
+				DexModule::price_cumulatives(AUSD_DOT_PAIR),
+				CumulativePrices {
+					price_0_cumulative: Price::saturating_from_rational(1, 5),
+					price_1_cumulative: Price::saturating_from_integer(5),
+					updated_at: 1,
+				}
+			);
+			assert_eq!(DexModule::get_twap(DOT, AUSD), None);
+
+			// the TWAP is unavailable within the TWAP period
+			System::set_block_number(10);
+			assert_eq!(DexModule::get_twap(DOT, AUSD), None);
+
+			System::set_block_number(11);
+			assert_eq!(
+				DexModule::get_twap(DOT, AUSD),
+				Price::checked_from_rational(51000, 9000)
+			);
+			assert_eq!(
+				DexModule::get_twap(AUSD, DOT),
+				Price::checked_from_rational(9000, 51000)
+			);
+			assert_eq!(
+				<DexModule as DEXManager<_, _, _>>::get_twap_price(DOT, AUSD),
+				Price::checked_from_rational(51000, 9000)
+			);
+
+			// the swap within a block doesn't affect the TWAP
+			DexModule::_swap(DOT, AUSD, 1000, 5100);
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (45900, 10000));
+			assert_eq!(
+				DexModule::get_twap(DOT, AUSD),
+				Price::checked_from_rational(51000, 9000)
+			);
+
+			System::set_block_number(21);
+			assert_eq!(
+				DexModule::get_twap(DOT, AUSD),
+				Some(Price::from_inner(5_128_333_333_333_333_333))
+			);
+
+			assert_eq!(DexModule::get_twap(AUSD, XBTC), None);
+		});
+}
+
 #[test]
 fn _swap_by_path_work() {
 	ExtBuilder::default()
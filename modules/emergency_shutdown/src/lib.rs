@@ -14,7 +14,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{pallet_prelude::*, transactional};
+use frame_support::{pallet_prelude::*, traits::Happened, transactional};
 use frame_system::{ensure_signed, pallet_prelude::*};
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{traits::Zero, FixedPointNumber};
@@ -59,6 +59,11 @@ pub mod module {
 		/// this.
 		type ShutdownOrigin: EnsureOrigin<Self::Origin>;
 
+		/// Handler to run before the shutdown takes effect, e.g. to
+		/// accumulate the stability fees before the debit exchange rates are
+		/// frozen
+		type OnShutdown: Happened<()>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -98,6 +103,12 @@ pub mod module {
 	#[pallet::getter(fn can_refund)]
 	pub type CanRefund<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+	/// The remaining collateral assets to be refunded, snapshotted from CDP
+	/// treasury when final redemption opened
+	#[pallet::storage]
+	#[pallet::getter(fn refund_basket)]
+	pub type RefundBasket<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -123,6 +134,7 @@ pub mod module {
 				<T as Config>::PriceSource::lock_price(currency_id);
 			}
 
+			T::OnShutdown::happened(&());
 			IsShutdown::<T>::put(true);
 			Self::deposit_event(Event::Shutdown(<frame_system::Module<T>>::block_number()));
 			Ok(().into())
@@ -164,6 +176,14 @@ pub mod module {
 				);
 			}
 
+			// snapshot the collateral basket, all refunds are paid out from it pro-rata
+			for currency_id in T::CollateralCurrencyIds::get() {
+				RefundBasket::<T>::insert(
+					currency_id,
+					<T as Config>::CDPTreasury::get_total_collaterals(currency_id),
+				);
+			}
+
 			// Open refund stage
 			CanRefund::<T>::put(true);
 			Self::deposit_event(Event::OpenRefund(<frame_system::Module<T>>::block_number()));
@@ -172,6 +192,10 @@ pub mod module {
 
 		/// Refund a basket of remaining collateral assets to caller
 		///
+		/// The caller receives the proportion of the refund basket that
+		/// `amount` takes of the stable currency total supply, so claims in
+		/// any order drain the basket pro-rata.
+		///
 		/// - `amount`: stable currency amount used to refund.
 		#[pallet::weight(T::WeightInfo::refund_collaterals(T::CollateralCurrencyIds::get().len() as u32))]
 		#[transactional]
@@ -191,11 +215,12 @@ pub mod module {
 			let mut refund_assets: Vec<(CurrencyId, Balance)> = vec![];
 			// refund collaterals to caller by CDP treasury
 			for currency_id in collateral_currency_ids {
-				let refund_amount =
-					refund_ratio.saturating_mul_int(<T as Config>::CDPTreasury::get_total_collaterals(currency_id));
+				let remaining = Self::refund_basket(currency_id);
+				let refund_amount = sp_std::cmp::min(refund_ratio.saturating_mul_int(remaining), remaining);
 
 				if !refund_amount.is_zero() {
 					<T as Config>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_amount)?;
+					RefundBasket::<T>::insert(currency_id, remaining.saturating_sub(refund_amount));
 					refund_assets.push((currency_id, refund_amount));
 				}
 			}
@@ -192,6 +192,7 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryModuleId: ModuleId = ModuleId(*b"aca/cdpt");
+	pub const BadDebtDelay: BlockNumber = 10;
 }
 
 impl cdp_treasury::Config for Runtime {
@@ -203,6 +204,10 @@ impl cdp_treasury::Config for Runtime {
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type BadDebtDelay = BadDebtDelay;
+	type DebitAuctionFixedSize = ();
+	type DebitAuctionInitialAmount = ();
+	type EmergencyShutdown = EmergencyShutdownModule;
 	type WeightInfo = ();
 }
 
@@ -217,6 +222,7 @@ impl Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type AuctionManagerHandler = MockAuctionManager;
 	type ShutdownOrigin = EnsureSignedBy<One, AccountId>;
+	type OnShutdown = ();
 	type WeightInfo = ();
 }
 
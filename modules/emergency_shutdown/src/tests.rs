@@ -5,6 +5,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
+use orml_traits::MultiCurrency;
 use sp_runtime::traits::BadOrigin;
 
 #[test]
@@ -68,3 +69,39 @@ fn refund_collaterals_fail() {
 		);
 	});
 }
+
+#[test]
+fn refund_collaterals_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 100));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, DOT, 200));
+		assert_ok!(CDPTreasuryModule::issue_debit(&ALICE, 600, true));
+		assert_ok!(CDPTreasuryModule::issue_debit(&BOB, 400, true));
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
+		assert_ok!(EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)));
+		assert_eq!(EmergencyShutdownModule::refund_basket(BTC), 100);
+		assert_eq!(EmergencyShutdownModule::refund_basket(DOT), 200);
+
+		assert_ok!(EmergencyShutdownModule::refund_collaterals(Origin::signed(BOB), 400));
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 0);
+		assert_eq!(Currencies::free_balance(BTC, &BOB), 1040);
+		assert_eq!(Currencies::free_balance(DOT, &BOB), 1080);
+		assert_eq!(EmergencyShutdownModule::refund_basket(BTC), 60);
+		assert_eq!(EmergencyShutdownModule::refund_basket(DOT), 120);
+
+		// donations after the snapshot do not change the basket
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, BTC, 100));
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 160);
+
+		assert_ok!(EmergencyShutdownModule::refund_collaterals(Origin::signed(ALICE), 600));
+		let refund_event = Event::emergency_shutdown(crate::Event::Refund(ALICE, 600, vec![(BTC, 60), (DOT, 120)]));
+		assert!(System::events().iter().any(|record| record.event == refund_event));
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 960);
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 920);
+		assert_eq!(EmergencyShutdownModule::refund_basket(BTC), 0);
+		assert_eq!(EmergencyShutdownModule::refund_basket(DOT), 0);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
+		assert_eq!(CDPTreasuryModule::total_collaterals(DOT), 0);
+	});
+}
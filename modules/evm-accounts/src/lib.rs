@@ -4,6 +4,12 @@
 //!
 //! Evm Accounts module provide a two way mapping between Substrate accounts and
 //! EVM accounts so user only have deal with one account / private key.
+//!
+//! The native balance sent to the padded Substrate account of an EVM address
+//! linked to an account, e.g. the default EVM address before it's mapped, is
+//! not visible as the balance of the account. It's exposed by
+//! `LinkedEvmBalance`, so that it can be pulled to the account, e.g. to pay
+//! the transaction fee.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -12,11 +18,12 @@ use codec::Encode;
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
-	traits::{Currency, Happened, IsType, OnKilledAccount, ReservableCurrency, StoredMap},
+	traits::{Currency, ExistenceRequirement, Happened, IsType, OnKilledAccount, ReservableCurrency, StoredMap},
 	transactional,
 	weights::Weight,
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
+use module_support::LinkedEvmBalance;
 use orml_traits::account::MergeAccount;
 use primitives::{
 	evm::{AddressMapping, EvmAddress},
@@ -28,8 +35,8 @@ use sp_io::{
 	hashing::{blake2_256, keccak_256},
 };
 use sp_runtime::{
-	traits::{LookupError, StaticLookup},
-	MultiAddress,
+	traits::{LookupError, Saturating, StaticLookup, Zero},
+	DispatchResult, MultiAddress,
 };
 use sp_std::{marker::PhantomData, vec::Vec};
 
@@ -46,6 +53,8 @@ pub trait WeightInfo {
 
 pub type EcdsaSignature = ecdsa::Signature;
 
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -93,6 +102,8 @@ pub mod module {
 		NonZeroRefCount,
 		/// Account still has active reserved
 		StillHasActiveReserved,
+		/// The free balance of the linked EVM addresses is not enough
+		LinkedBalanceTooLow,
 	}
 
 	#[pallet::storage]
@@ -171,54 +182,88 @@ impl<T: Config> Pallet<T> {
 	// Constructs the message that Ethereum RPC's `personal_sign` and `eth_sign`
 	// would sign.
 	pub fn ethereum_signable_message(what: &[u8], extra: &[u8]) -> Vec<u8> {
-		let prefix = b"acala evm:";
-		let mut l = prefix.len() + what.len() + extra.len();
-		let mut rev = Vec::new();
-		while l > 0 {
-			rev.push(b'0' + (l % 10) as u8);
-			l /= 10;
-		}
-		let mut v = b"\x19Ethereum Signed Message:\n".to_vec();
-		v.extend(rev.into_iter().rev());
-		v.extend_from_slice(&prefix[..]);
-		v.extend_from_slice(what);
-		v.extend_from_slice(extra);
-		v
+		ethereum_signable_message(what, extra)
 	}
 
 	// Attempts to recover the Ethereum address from a message signature signed by
 	// using the Ethereum RPC's `personal_sign` and `eth_sign`.
 	pub fn eth_recover(s: &EcdsaSignature, what: &[u8], extra: &[u8]) -> Option<EvmAddress> {
-		let msg = keccak_256(&Self::ethereum_signable_message(what, extra));
-		let mut res = EvmAddress::default();
-		res.0
-			.copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, &msg).ok()?[..])[12..]);
-		Some(res)
+		eth_recover(s, what, extra)
 	}
 
 	pub fn eth_public(secret: &secp256k1::SecretKey) -> secp256k1::PublicKey {
-		secp256k1::PublicKey::from_secret_key(secret)
+		eth_public(secret)
 	}
 
 	pub fn eth_address(secret: &secp256k1::SecretKey) -> EvmAddress {
-		EvmAddress::from_slice(&keccak_256(&Self::eth_public(secret).serialize()[1..65])[12..])
+		eth_address(secret)
 	}
 
 	pub fn eth_sign(secret: &secp256k1::SecretKey, what: &[u8], extra: &[u8]) -> EcdsaSignature {
-		let msg = keccak_256(&Self::ethereum_signable_message(&to_ascii_hex(what)[..], extra));
-		let (sig, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(&msg), secret);
-		let mut r = [0u8; 65];
-		r[0..64].copy_from_slice(&sig.serialize()[..]);
-		r[64] = recovery_id.serialize();
-		EcdsaSignature::from_slice(&r)
+		eth_sign(secret, what, extra)
 	}
 }
 
+/// Constructs the message that Ethereum RPC's `personal_sign` and `eth_sign`
+/// would sign.
+pub fn ethereum_signable_message(what: &[u8], extra: &[u8]) -> Vec<u8> {
+	let prefix = b"acala evm:";
+	let mut l = prefix.len() + what.len() + extra.len();
+	let mut rev = Vec::new();
+	while l > 0 {
+		rev.push(b'0' + (l % 10) as u8);
+		l /= 10;
+	}
+	let mut v = b"\x19Ethereum Signed Message:\n".to_vec();
+	v.extend(rev.into_iter().rev());
+	v.extend_from_slice(&prefix[..]);
+	v.extend_from_slice(what);
+	v.extend_from_slice(extra);
+	v
+}
+
+/// Attempts to recover the Ethereum address from a message signature signed
+/// by using the Ethereum RPC's `personal_sign` and `eth_sign`.
+pub fn eth_recover(s: &EcdsaSignature, what: &[u8], extra: &[u8]) -> Option<EvmAddress> {
+	let msg = keccak_256(&ethereum_signable_message(what, extra));
+	let mut res = EvmAddress::default();
+	res.0
+		.copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, &msg).ok()?[..])[12..]);
+	Some(res)
+}
+
+pub fn eth_public(secret: &secp256k1::SecretKey) -> secp256k1::PublicKey {
+	secp256k1::PublicKey::from_secret_key(secret)
+}
+
+pub fn eth_address(secret: &secp256k1::SecretKey) -> EvmAddress {
+	EvmAddress::from_slice(&keccak_256(&eth_public(secret).serialize()[1..65])[12..])
+}
+
+/// Sign the ASCII-encoded hex of `what` and `extra` as the Ethereum RPC's
+/// `personal_sign` would.
+pub fn eth_sign(secret: &secp256k1::SecretKey, what: &[u8], extra: &[u8]) -> EcdsaSignature {
+	let msg = keccak_256(&ethereum_signable_message(&to_ascii_hex(what)[..], extra));
+	let (sig, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(&msg), secret);
+	let mut r = [0u8; 65];
+	r[0..64].copy_from_slice(&sig.serialize()[..]);
+	r[64] = recovery_id.serialize();
+	EcdsaSignature::from_slice(&r)
+}
+
 fn account_to_default_evm_address(account_id: &impl Encode) -> EvmAddress {
 	let payload = (b"evm:", account_id);
 	EvmAddress::from_slice(&payload.using_encoded(blake2_256)[0..20])
 }
 
+/// The padded Substrate account of the EVM address which is not mapped.
+fn evm_address_to_padded_account(address: &EvmAddress) -> AccountId32 {
+	let mut data: [u8; 32] = [0u8; 32];
+	data[0..4].copy_from_slice(b"evm:");
+	data[4..24].copy_from_slice(&address[..]);
+	AccountId32::from(data)
+}
+
 pub struct EvmAddressMapping<T>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config> AddressMapping<T::AccountId> for EvmAddressMapping<T>
@@ -229,10 +274,7 @@ where
 		if let Some(acc) = Accounts::<T>::get(address) {
 			acc
 		} else {
-			let mut data: [u8; 32] = [0u8; 32];
-			data[0..4].copy_from_slice(b"evm:");
-			data[4..24].copy_from_slice(&address[..]);
-			AccountId32::from(data).into()
+			evm_address_to_padded_account(address).into()
 		}
 	}
 
@@ -269,6 +311,61 @@ where
 	}
 }
 
+impl<T: Config> Pallet<T>
+where
+	T::AccountId: IsType<AccountId32>,
+{
+	/// The padded accounts of the EVM addresses linked to `who`, i.e. the
+	/// mapped EVM address and the default EVM address.
+	fn linked_padded_accounts(who: &T::AccountId) -> Vec<T::AccountId> {
+		let mut accounts: Vec<T::AccountId> = Vec::new();
+		let addresses = Self::evm_addresses(who)
+			.into_iter()
+			.chain(Some(account_to_default_evm_address(who.into_ref())));
+		for address in addresses {
+			let account: T::AccountId = evm_address_to_padded_account(&address).into();
+			if &account != who && !accounts.contains(&account) {
+				accounts.push(account);
+			}
+		}
+		accounts
+	}
+}
+
+impl<T: Config> LinkedEvmBalance<T::AccountId, BalanceOf<T>> for Pallet<T>
+where
+	T::AccountId: IsType<AccountId32>,
+{
+	fn free_balance(who: &T::AccountId) -> BalanceOf<T> {
+		Self::linked_padded_accounts(who)
+			.iter()
+			.fold(Zero::zero(), |total: BalanceOf<T>, account| {
+				total.saturating_add(T::Currency::free_balance(account))
+			})
+	}
+
+	#[transactional]
+	fn pull(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		ensure!(
+			<Self as LinkedEvmBalance<_, _>>::free_balance(who) >= amount,
+			Error::<T>::LinkedBalanceTooLow
+		);
+
+		let mut remaining = amount;
+		for account in Self::linked_padded_accounts(who) {
+			if remaining.is_zero() {
+				break;
+			}
+			let value = T::Currency::free_balance(&account).min(remaining);
+			if !value.is_zero() {
+				T::Currency::transfer(&account, who, value, ExistenceRequirement::AllowDeath)?;
+				remaining = remaining.saturating_sub(value);
+			}
+		}
+		Ok(())
+	}
+}
+
 pub struct CallKillAccount<T>(PhantomData<T>);
 impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
 	fn on_killed_account(who: &T::AccountId) {
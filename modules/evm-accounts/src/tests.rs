@@ -4,7 +4,9 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{alice, bob, Event, EvmAccountsModule, ExtBuilder, Origin, Runtime, System, ALICE, BOB};
+use mock::{
+	alice, bob, bob_account_id, Balances, Event, EvmAccountsModule, ExtBuilder, Origin, Runtime, System, ALICE, BOB,
+};
 use std::str::FromStr;
 
 #[test]
@@ -174,3 +176,35 @@ fn account_to_evm_with_create_default() {
 		);
 	});
 }
+
+#[test]
+fn linked_evm_balance_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let default_evm_account = EvmAddressMapping::<Runtime>::get_default_evm_address(&ALICE);
+		let padded_account = EvmAddressMapping::<Runtime>::get_account_id(&default_evm_account);
+		assert_ok!(<Balances as Currency<_>>::transfer(
+			&bob_account_id(),
+			&padded_account,
+			100,
+			ExistenceRequirement::AllowDeath
+		));
+		assert_eq!(<EvmAccountsModule as LinkedEvmBalance<_, _>>::free_balance(&ALICE), 100);
+		assert_eq!(<EvmAccountsModule as LinkedEvmBalance<_, _>>::free_balance(&BOB), 0);
+
+		assert_noop!(
+			<EvmAccountsModule as LinkedEvmBalance<_, _>>::pull(&ALICE, 101),
+			Error::<Runtime>::LinkedBalanceTooLow
+		);
+		assert_ok!(<EvmAccountsModule as LinkedEvmBalance<_, _>>::pull(&ALICE, 60));
+		assert_eq!(Balances::free_balance(&ALICE), 60);
+		assert_eq!(Balances::free_balance(&padded_account), 40);
+
+		// the balance of the default EVM address is still linked after it's mapped
+		assert_ok!(EvmAccountsModule::claim_default_account(Origin::signed(ALICE)));
+		assert_eq!(
+			EvmAddressMapping::<Runtime>::get_account_id(&default_evm_account),
+			ALICE
+		);
+		assert_eq!(<EvmAccountsModule as LinkedEvmBalance<_, _>>::free_balance(&ALICE), 40);
+	});
+}
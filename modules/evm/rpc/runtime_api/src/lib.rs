@@ -1,8 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use ethereum_types::H160;
-use primitives::evm::{CallInfo, CreateInfo};
+use ethereum_types::{H160, H256};
+use primitives::{
+	evm::{CallInfo, CreateInfo, EstimateResourcesInfo},
+	BlockNumber,
+};
 use sp_runtime::{
 	codec::Codec,
 	traits::{MaybeDisplay, MaybeFromStr},
@@ -31,5 +34,21 @@ sp_api::decl_runtime_apis! {
 			storage_limit: u32,
 			estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError>;
+
+		/// The resources needed by the call, or the create if `to` is not
+		/// set, with the given limits.
+		fn estimate_resources(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u32,
+			storage_limit: u32,
+		) -> Result<EstimateResourcesInfo<Balance>, sp_runtime::DispatchError>;
+
+		/// The block number and the extrinsic index of the EVM transaction
+		/// with the hash, the transactions are kept for
+		/// `TransactionIndexPeriod` blocks.
+		fn transaction_by_hash(hash: H256) -> Option<(BlockNumber, u32)>;
 	}
 }
@@ -0,0 +1,16 @@
+use ethereum_types::U256;
+use serde::Serialize;
+
+/// Estimate resources response
+#[derive(Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateResourcesResponse {
+	/// Gas Limit
+	pub gas: U256,
+	/// Storage Limit
+	pub storage: u32,
+	/// Storage Deposit
+	pub storage_deposit: U256,
+	/// Fee in native currency
+	pub fee: U256,
+}
@@ -1,13 +1,15 @@
 //! EVM rpc interface.
 
-use ethereum_types::U256;
+use ethereum_types::{H256, U256};
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_core::Bytes;
 
 pub use rpc_impl_EVMApi::gen_server::EVMApi as EVMApiServer;
 
-use crate::call_request::CallRequest;
+use crate::{
+	call_request::CallRequest, estimate_resources::EstimateResourcesResponse, transaction_info::TransactionInfo,
+};
 
 /// EVM rpc interface.
 #[rpc(server)]
@@ -19,4 +21,14 @@ pub trait EVMApi<BlockHash> {
 	/// Estimate gas needed for execution of given contract.
 	#[rpc(name = "evm_estimateGas")]
 	fn estimate_gas(&self, _: CallRequest, _: Option<BlockHash>) -> Result<U256>;
+
+	/// Estimate the gas limit, the storage limit, the storage deposit and the
+	/// fee in native currency needed for execution of given contract.
+	#[rpc(name = "evm_estimateResources")]
+	fn estimate_resources(&self, _: CallRequest, _: Option<BlockHash>) -> Result<EstimateResourcesResponse>;
+
+	/// Get the block and the extrinsic index of the EVM transaction by its
+	/// hash, i.e. the hash of the extrinsic.
+	#[rpc(name = "evm_getTransactionByHash")]
+	fn transaction_by_hash(&self, _: H256) -> Result<Option<TransactionInfo>>;
 }
@@ -1,6 +1,6 @@
 use std::{marker::PhantomData, sync::Arc};
 
-use ethereum_types::U256;
+use ethereum_types::{H256, U256};
 use jsonrpc_core::{Error, ErrorCode, Result, Value};
 use rustc_hex::ToHex;
 use sp_api::ProvideRuntimeApi;
@@ -16,13 +16,17 @@ use sp_runtime::{
 use std::convert::{TryFrom, TryInto};
 
 use call_request::CallRequest;
+pub use estimate_resources::EstimateResourcesResponse;
 pub use module_evm::ExitReason;
 pub use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+pub use transaction_info::TransactionInfo;
 
 pub use crate::evm_api::{EVMApi as EVMApiT, EVMApiServer};
 
 mod call_request;
+mod estimate_resources;
 mod evm_api;
+mod transaction_info;
 
 fn internal_err<T: ToString>(message: T) -> Error {
 	Error {
@@ -96,7 +100,7 @@ where
 	B: BlockT,
 	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
 	C::Api: EVMRuntimeRPCApi<B, Balance>,
-	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
 {
 	fn call(&self, request: CallRequest, _: Option<B>) -> Result<Bytes> {
 		let hash = self.client.info().best_hash;
@@ -241,6 +245,85 @@ where
 
 		Ok(used_gas)
 	}
+
+	fn estimate_resources(&self, request: CallRequest, _: Option<B>) -> Result<EstimateResourcesResponse> {
+		let hash = self.client.info().best_hash;
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+		} = request;
+
+		let gas_limit = gas_limit.unwrap_or_else(u32::max_value); // TODO: set a limit
+		let storage_limit = storage_limit.unwrap_or_else(u32::max_value); // TODO: set a limit
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let api = self.client.runtime_api();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value = balance_value.map_err(|_| Error {
+			code: ErrorCode::InvalidParams,
+			message: format!("Invalid parameter value: {:?}", value),
+			data: None,
+		})?;
+
+		let info = api
+			.estimate_resources(
+				&BlockId::Hash(hash),
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {}", Into::<&str>::into(err))))?;
+
+		error_on_execution_failure(&info.exit_reason, &info.output)?;
+
+		Ok(EstimateResourcesResponse {
+			gas: info.gas,
+			storage: info.storage,
+			storage_deposit: info.storage_deposit.into(),
+			fee: info.fee.into(),
+		})
+	}
+
+	fn transaction_by_hash(&self, hash: H256) -> Result<Option<TransactionInfo>> {
+		let best_hash = self.client.info().best_hash;
+
+		let (block_number, extrinsic_index) = match self
+			.client
+			.runtime_api()
+			.transaction_by_hash(&BlockId::Hash(best_hash), hash)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+		{
+			Some(index) => index,
+			None => return Ok(None),
+		};
+
+		let block_hash = self
+			.client
+			.hash(block_number.into())
+			.map_err(|err| internal_err(format!("fetch block hash failed: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("block {} not found", block_number)))?;
+
+		Ok(Some(TransactionInfo {
+			block_hash: H256::from_slice(block_hash.as_ref()),
+			block_number,
+			extrinsic_index,
+		}))
+	}
 }
 
 #[test]
@@ -0,0 +1,14 @@
+use ethereum_types::H256;
+use serde::Serialize;
+
+/// Transaction location response
+#[derive(Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInfo {
+	/// Block Hash
+	pub block_hash: H256,
+	/// Block Number
+	pub block_number: u32,
+	/// Extrinsic Index
+	pub extrinsic_index: u32,
+}
@@ -21,7 +21,7 @@ use primitive_types::{H256, U256};
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use sp_runtime::{
-	traits::{Convert, DispatchInfoOf, One, PostDispatchInfoOf, SignedExtension, UniqueSaturatedInto},
+	traits::{Convert, DispatchInfoOf, Hash, One, PostDispatchInfoOf, SignedExtension, UniqueSaturatedInto},
 	transaction_validity::TransactionValidityError,
 	Either, TransactionOutcome,
 };
@@ -32,7 +32,9 @@ pub use crate::precompiles::{Precompile, Precompiles};
 pub use crate::runner::Runner;
 pub use evm::{Context, ExitError, ExitFatal, ExitReason, ExitRevert, ExitSucceed};
 pub use orml_traits::account::MergeAccount;
-pub use primitives::evm::{Account, AddressMapping, CallInfo, CreateInfo, EvmAddress, Log, Vicinity};
+pub use primitives::evm::{
+	Account, AddressMapping, CallInfo, CreateInfo, EstimateResourcesInfo, EvmAddress, Log, Vicinity,
+};
 
 pub mod precompiles;
 pub mod runner;
@@ -56,6 +58,7 @@ pub trait WeightInfo {
 	fn disable_contract_development() -> Weight;
 	fn set_code() -> Weight;
 	fn selfdestruct() -> Weight;
+	fn note_transaction() -> Weight;
 }
 
 // Initially based on Istanbul hard fork configuration.
@@ -165,6 +168,11 @@ pub mod module {
 
 		type FreeDeploymentOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The number of blocks the EVM transactions are kept in the
+		/// transaction index.
+		#[pallet::constant]
+		type TransactionIndexPeriod: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -241,6 +249,18 @@ pub mod module {
 	#[pallet::getter(fn extrinsic_origin)]
 	pub type ExtrinsicOrigin<T: Config> = StorageValue<_, T::AccountId>;
 
+	/// Mapping from the hash of the EVM transaction, i.e. the hash of the
+	/// extrinsic, to the block number and the index of the extrinsic.
+	#[pallet::storage]
+	#[pallet::getter(fn transaction_index)]
+	pub type TransactionIndex<T: Config> = StorageMap<_, Identity, T::Hash, (T::BlockNumber, u32), OptionQuery>;
+
+	/// The hashes of the EVM transactions indexed at the block, removed from
+	/// the transaction index after `TransactionIndexPeriod` blocks.
+	#[pallet::storage]
+	#[pallet::getter(fn block_transactions)]
+	pub type BlockTransactions<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Vec<T::Hash>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		//TODO: use `T::Index` once `Deserialize` bound available https://github.com/paritytech/substrate/pull/8035
@@ -362,13 +382,19 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Remove the transactions indexed `TransactionIndexPeriod` blocks ago
+		/// from the transaction index.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::prune_transaction_index(now)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Issue an EVM call operation. This is similar to a message call
 		/// transaction in Ethereum.
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(<T as Config>::WeightInfo::note_transaction()))]
 		pub fn call(
 			origin: OriginFor<T>,
 			target: EvmAddress,
@@ -390,6 +416,7 @@ pub mod module {
 				storage_limit,
 				T::config(),
 			)?;
+			Self::note_transaction();
 
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Executed(target));
@@ -400,7 +427,9 @@ pub mod module {
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(
+					T::GasToWeight::convert(used_gas).saturating_add(<T as Config>::WeightInfo::note_transaction()),
+				),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -462,7 +491,7 @@ pub mod module {
 
 		/// Issue an EVM create operation. This is similar to a contract
 		/// creation transaction in Ethereum.
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(<T as Config>::WeightInfo::note_transaction()))]
 		pub fn create(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -474,6 +503,7 @@ pub mod module {
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
 			let info = Runner::<T>::create(source, init, value, gas_limit, storage_limit, T::config())?;
+			Self::note_transaction();
 
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
@@ -484,13 +514,15 @@ pub mod module {
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(
+					T::GasToWeight::convert(used_gas).saturating_add(<T as Config>::WeightInfo::note_transaction()),
+				),
 				pays_fee: Pays::Yes,
 			})
 		}
 
 		/// Issue an EVM create2 operation.
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(<T as Config>::WeightInfo::note_transaction()))]
 		pub fn create2(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -503,6 +535,7 @@ pub mod module {
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
 			let info = Runner::<T>::create2(source, init, salt, value, gas_limit, storage_limit, T::config())?;
+			Self::note_transaction();
 
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
@@ -513,7 +546,9 @@ pub mod module {
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(
+					T::GasToWeight::convert(used_gas).saturating_add(<T as Config>::WeightInfo::note_transaction()),
+				),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -660,6 +695,40 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Map the hash of the applying extrinsic to its block number and index.
+	fn note_transaction() {
+		if let Some(index) = frame_system::Module::<T>::extrinsic_index() {
+			let extrinsic = frame_system::Module::<T>::extrinsic_data(index);
+			if !extrinsic.is_empty() {
+				let hash = T::Hashing::hash(&extrinsic);
+				let block_number = frame_system::Module::<T>::block_number();
+				TransactionIndex::<T>::insert(hash, (block_number, index));
+				BlockTransactions::<T>::append(block_number, hash);
+			}
+		}
+	}
+
+	/// Remove the transactions indexed at the block `TransactionIndexPeriod`
+	/// blocks before `now` from the transaction index.
+	fn prune_transaction_index(now: T::BlockNumber) -> Weight {
+		let period = T::TransactionIndexPeriod::get();
+		if now <= period {
+			return 0;
+		}
+
+		let expired = now - period;
+		let hashes = BlockTransactions::<T>::take(expired);
+		for hash in hashes.iter() {
+			// the same extrinsic may be indexed again at a later block
+			if matches!(Self::transaction_index(hash), Some((block_number, _)) if block_number == expired) {
+				TransactionIndex::<T>::remove(hash);
+			}
+		}
+
+		let count = hashes.len() as Weight;
+		T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_add(1))
+	}
+
 	/// Remove an account.
 	pub fn remove_account(address: &EvmAddress) -> Result<u32, ExitError> {
 		let mut size = 0u32;
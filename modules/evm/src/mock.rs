@@ -121,6 +121,7 @@ ord_parameter_types! {
 	pub const StorageDepositPerByte: u64 = 10;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const TransactionIndexPeriod: u64 = 10;
 	pub const MaxCodeSize: u32 = 1000;
 	pub const ChainId: u64 = 1;
 }
@@ -144,6 +145,7 @@ impl Config for Test {
 	type DeveloperDeposit = DeveloperDeposit;
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
+	type TransactionIndexPeriod = TransactionIndexPeriod;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type WeightInfo = ();
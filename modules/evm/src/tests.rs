@@ -25,6 +25,37 @@ fn fail_call_return_ok() {
 	});
 }
 
+#[test]
+fn should_note_transaction() {
+	new_test_ext().execute_with(|| {
+		let mut data = [0u8; 32];
+		data[0..4].copy_from_slice(b"evm:");
+		let signer: AccountId32 = AccountId32::from(data).into();
+
+		System::set_block_number(2);
+		System::set_extrinsic_index(1);
+		System::note_extrinsic(vec![1, 2, 3]);
+		assert_ok!(EVM::call(
+			Origin::signed(signer),
+			contract_a(),
+			Vec::new(),
+			0,
+			1000000,
+			0
+		));
+		let hash = <Runtime as frame_system::Config>::Hashing::hash(&[1, 2, 3]);
+		assert_eq!(EVM::transaction_index(hash), Some((2, 1)));
+		assert_eq!(EVM::block_transactions(2), vec![hash]);
+
+		// pruned after `TransactionIndexPeriod` blocks
+		EVM::on_initialize(11);
+		assert_eq!(EVM::transaction_index(hash), Some((2, 1)));
+		EVM::on_initialize(12);
+		assert_eq!(EVM::transaction_index(hash), None);
+		assert_eq!(EVM::block_transactions(2), vec![]);
+	});
+}
+
 #[test]
 fn should_calculate_contract_address() {
 	new_test_ext().execute_with(|| {
@@ -0,0 +1,198 @@
+//! # Flash Mint Module
+//!
+//! ## Overview
+//!
+//! Flash mint allows anyone to mint stable currency without collateral, as
+//! long as the minted amount plus a fee is burned before the end of the same
+//! extrinsic. The minted stable currency is issued to the caller, then the
+//! call that the caller provided is dispatched with the caller as the signed
+//! origin, and finally the principal is burned and the fee is deposited to
+//! the surplus pool of CDP treasury. If the caller can not afford the
+//! repayment, the whole extrinsic is reverted. This makes refinancing and
+//! liquidation possible without upfront capital.
+//!
+//! The maximum outstanding amount of flash minted stable currency and the fee
+//! rate are set by governance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	dispatch::PostDispatchInfo,
+	pallet_prelude::*,
+	transactional,
+	weights::{extract_actual_weight, GetDispatchInfo},
+};
+use frame_system::pallet_prelude::*;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{Dispatchable, Zero},
+	FixedPointNumber,
+};
+use sp_std::prelude::*;
+use support::{CDPTreasury, Rate};
+
+mod default_weight;
+mod mock;
+mod tests;
+
+pub use module::*;
+
+pub trait WeightInfo {
+	fn flash_mint() -> Weight;
+	fn set_max_flash_mint() -> Weight;
+	fn set_fee_rate() -> Weight;
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The call dispatched by the borrower while holding the flash minted
+		/// stable currency.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// CDP treasury to issue and burn stable currency, and to receive the
+		/// flash mint fee as surplus
+		type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
+		/// The origin which may update the flash mint cap and fee rate. Root
+		/// can always do this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The flash mint amount is zero
+		InvalidAmount,
+		/// The outstanding flash minted amount exceeds the cap
+		ExceedMaxFlashMint,
+		/// The borrower can not afford to burn the principal plus fee
+		NotRepaid,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Stable currency is flash minted and repaid. \[who, amount, fee\]
+		FlashMinted(T::AccountId, Balance, Balance),
+		/// The cap of outstanding flash minted amount updated. \[new_cap\]
+		MaxFlashMintUpdated(Balance),
+		/// The flash mint fee rate updated. \[new_fee_rate\]
+		FeeRateUpdated(Rate),
+	}
+
+	/// The cap of outstanding flash minted stable currency, 0 means flash
+	/// mint is disabled.
+	#[pallet::storage]
+	#[pallet::getter(fn max_flash_mint)]
+	pub type MaxFlashMint<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The fee rate charged on the flash minted amount.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_rate)]
+	pub type FeeRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+
+	/// The flash minted amount which has not been repaid yet, only non-zero
+	/// during the dispatch of flash mint.
+	#[pallet::storage]
+	#[pallet::getter(fn outstanding_flash_mint)]
+	pub type OutstandingFlashMint<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Mint `amount` stable currency to the caller, dispatch `call` with
+		/// the caller as the signed origin, and then burn `amount` plus fee
+		/// from the caller.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `amount`: stable currency amount to flash mint.
+		/// - `call`: the call to dispatch while holding the minted amount.
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(T::WeightInfo::flash_mint().saturating_add(dispatch_info.weight), dispatch_info.class)
+		})]
+		#[transactional]
+		pub fn flash_mint(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+
+			let outstanding = Self::outstanding_flash_mint()
+				.checked_add(amount)
+				.filter(|outstanding| *outstanding <= Self::max_flash_mint())
+				.ok_or(Error::<T>::ExceedMaxFlashMint)?;
+			let fee = Self::fee_rate().saturating_mul_int(amount);
+
+			OutstandingFlashMint::<T>::put(outstanding);
+			// the minted amount is backed by the repayment in this extrinsic
+			T::CDPTreasury::issue_debit(&who, amount, true)?;
+
+			let dispatch_info = call.get_dispatch_info();
+			let post_info = call
+				.dispatch(frame_system::RawOrigin::Signed(who.clone()).into())
+				.map_err(|e| e.error)?;
+
+			T::CDPTreasury::burn_debit(&who, amount).map_err(|_| Error::<T>::NotRepaid)?;
+			if !fee.is_zero() {
+				T::CDPTreasury::deposit_surplus(&who, fee).map_err(|_| Error::<T>::NotRepaid)?;
+			}
+			OutstandingFlashMint::<T>::mutate(|outstanding| *outstanding = outstanding.saturating_sub(amount));
+
+			Self::deposit_event(Event::FlashMinted(who, amount, fee));
+			Ok(
+				Some(T::WeightInfo::flash_mint().saturating_add(extract_actual_weight(&post_info, &dispatch_info)))
+					.into(),
+			)
+		}
+
+		/// Update the cap of outstanding flash minted stable currency
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `amount`: the new cap, 0 means flash mint is disabled.
+		#[pallet::weight((T::WeightInfo::set_max_flash_mint(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_max_flash_mint(origin: OriginFor<T>, amount: Balance) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MaxFlashMint::<T>::put(amount);
+			Self::deposit_event(Event::MaxFlashMintUpdated(amount));
+			Ok(().into())
+		}
+
+		/// Update the flash mint fee rate
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `fee_rate`: the new fee rate.
+		#[pallet::weight((T::WeightInfo::set_fee_rate(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_fee_rate(origin: OriginFor<T>, fee_rate: Rate) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			FeeRate::<T>::put(fee_rate);
+			Self::deposit_event(Event::FeeRateUpdated(fee_rate));
+			Ok(().into())
+		}
+	}
+}
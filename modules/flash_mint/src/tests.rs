@@ -0,0 +1,141 @@
+//! Unit tests for the flash mint module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use orml_traits::MultiCurrency;
+use sp_runtime::traits::BadOrigin;
+
+fn remark_call() -> Box<mock::Call> {
+	Box::new(mock::Call::System(frame_system::Call::remark(vec![])))
+}
+
+fn transfer_call(to: AccountId, amount: Balance) -> Box<mock::Call> {
+	Box::new(mock::Call::TokensModule(orml_tokens::Call::transfer(to, AUSD, amount)))
+}
+
+#[test]
+fn set_max_flash_mint_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			FlashMintModule::set_max_flash_mint(Origin::signed(ALICE), 500),
+			BadOrigin
+		);
+		assert_ok!(FlashMintModule::set_max_flash_mint(Origin::signed(4), 500));
+		let update_event = Event::flash_mint(crate::Event::MaxFlashMintUpdated(500));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(FlashMintModule::max_flash_mint(), 500);
+	});
+}
+
+#[test]
+fn set_fee_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			FlashMintModule::set_fee_rate(Origin::signed(ALICE), Rate::saturating_from_rational(1, 100)),
+			BadOrigin
+		);
+		assert_ok!(FlashMintModule::set_fee_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 100)
+		));
+		let update_event = Event::flash_mint(crate::Event::FeeRateUpdated(Rate::saturating_from_rational(1, 100)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(FlashMintModule::fee_rate(), Rate::saturating_from_rational(1, 100));
+	});
+}
+
+#[test]
+fn flash_mint_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(FlashMintModule::set_max_flash_mint(Origin::signed(4), 500));
+		assert_ok!(FlashMintModule::set_fee_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 100)
+		));
+
+		assert_ok!(FlashMintModule::flash_mint(Origin::signed(ALICE), 100, remark_call()));
+		let flash_mint_event = Event::flash_mint(crate::Event::FlashMinted(ALICE, 100, 1));
+		assert!(System::events().iter().any(|record| record.event == flash_mint_event));
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 999);
+		assert_eq!(TokensModule::free_balance(AUSD, &TREASURY), 1);
+		assert_eq!(TokensModule::total_issuance(AUSD), 2000);
+		assert_eq!(FlashMintModule::outstanding_flash_mint(), 0);
+
+		// the minted amount is spendable by the dispatched call
+		assert_ok!(FlashMintModule::flash_mint(
+			Origin::signed(ALICE),
+			500,
+			transfer_call(BOB, 400)
+		));
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 594);
+		assert_eq!(TokensModule::free_balance(AUSD, &BOB), 1400);
+		assert_eq!(TokensModule::free_balance(AUSD, &TREASURY), 6);
+		assert_eq!(TokensModule::total_issuance(AUSD), 2000);
+	});
+}
+
+#[test]
+fn flash_mint_reverts_when_not_repaid() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FlashMintModule::set_max_flash_mint(Origin::signed(4), 500));
+		assert_ok!(FlashMintModule::set_fee_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 100)
+		));
+
+		// can not burn the principal
+		assert_noop!(
+			FlashMintModule::flash_mint(Origin::signed(ALICE), 100, transfer_call(BOB, 1001)),
+			Error::<Runtime>::NotRepaid,
+		);
+		// can not pay the fee
+		assert_noop!(
+			FlashMintModule::flash_mint(Origin::signed(ALICE), 100, transfer_call(BOB, 1000)),
+			Error::<Runtime>::NotRepaid,
+		);
+		// the error of the dispatched call reverts the whole flash mint
+		assert_noop!(
+			FlashMintModule::flash_mint(Origin::signed(ALICE), 100, transfer_call(BOB, 1101)),
+			orml_tokens::Error::<Runtime>::BalanceTooLow,
+		);
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 1000);
+		assert_eq!(TokensModule::total_issuance(AUSD), 2000);
+	});
+}
+
+#[test]
+fn flash_mint_exceed_max_flash_mint() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			FlashMintModule::flash_mint(Origin::signed(ALICE), 100, remark_call()),
+			Error::<Runtime>::ExceedMaxFlashMint,
+		);
+
+		assert_ok!(FlashMintModule::set_max_flash_mint(Origin::signed(4), 500));
+		assert_noop!(
+			FlashMintModule::flash_mint(Origin::signed(ALICE), 0, remark_call()),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			FlashMintModule::flash_mint(Origin::signed(ALICE), 501, remark_call()),
+			Error::<Runtime>::ExceedMaxFlashMint,
+		);
+
+		// nested flash mints count towards the same cap
+		let nested_call = Box::new(mock::Call::FlashMintModule(crate::Call::flash_mint(300, remark_call())));
+		assert_noop!(
+			FlashMintModule::flash_mint(Origin::signed(ALICE), 300, nested_call),
+			Error::<Runtime>::ExceedMaxFlashMint,
+		);
+		let nested_call = Box::new(mock::Call::FlashMintModule(crate::Call::flash_mint(200, remark_call())));
+		assert_ok!(FlashMintModule::flash_mint(Origin::signed(ALICE), 300, nested_call));
+		assert_eq!(FlashMintModule::outstanding_flash_mint(), 0);
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 1000);
+	});
+}
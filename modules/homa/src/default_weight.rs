@@ -6,10 +6,13 @@ use super::RedeemStrategy;
 use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 
 impl crate::WeightInfo for () {
-	fn mint() -> Weight {
+	fn mint(c: u32) -> Weight {
 		(71_000_000 as Weight)
+			.saturating_add((32_000_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
 			.saturating_add(DbWeight::get().writes(6 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
 	}
 	fn redeem(strategy: &RedeemStrategy) -> Weight {
 		match strategy {
@@ -29,4 +32,12 @@ impl crate::WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(6 as Weight))
 			.saturating_add(DbWeight::get().writes(2 as Weight))
 	}
+	fn emergency_unbond() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
+	}
+	fn unfreeze_mint() -> Weight {
+		(10_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
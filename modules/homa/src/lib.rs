@@ -6,6 +6,9 @@
 //! pool and get LDOT, which is the redemption voucher for DOT owned by the
 //! staking pool. The staking pool will staking these DOT to get staking
 //! rewards. Holders of LDOT can choose different ways to redeem DOT.
+//!
+//! In response to relaychain incidents, governance can unbond the bonded DOT
+//! immediately and freeze new minting until it's unfrozen again.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -17,13 +20,17 @@ use sp_runtime::RuntimeDebug;
 use support::HomaProtocol;
 
 mod default_weight;
+mod mock;
+mod tests;
 
 pub use module::*;
 
 pub trait WeightInfo {
-	fn mint() -> Weight;
+	fn mint(c: u32) -> Weight;
 	fn redeem(strategy: &RedeemStrategy) -> Weight;
 	fn withdraw_redemption() -> Weight;
+	fn emergency_unbond() -> Weight;
+	fn unfreeze_mint() -> Weight;
 }
 
 /// Redemption modes:
@@ -47,13 +54,45 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
 		/// The core of Homa protocol.
 		type Homa: HomaProtocol<Self::AccountId, Balance, EraIndex>;
 
+		/// The origin which may unbond for emergency and unfreeze minting.
+		/// Root can always do this.
+		type EmergencyOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of redemption requests that a mint can be fast
+		/// matched with by staking pool.
+		#[pallet::constant]
+		type MaxFastMatchCount: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Minting is frozen for emergency.
+		MintFrozen,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The bonded DOT is unbonded for emergency and minting is frozen.
+		/// \[unbond_amount\]
+		EmergencyUnbonded(Balance),
+		/// Minting is unfrozen.
+		MintUnfrozen,
+	}
+
+	/// Whether minting is frozen for emergency.
+	#[pallet::storage]
+	#[pallet::getter(fn is_mint_frozen)]
+	pub type IsMintFrozen<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -66,10 +105,11 @@ pub mod module {
 		/// decided by staking pool.
 		///
 		/// - `amount`: the DOT amount to inject into staking pool.
-		#[pallet::weight(<T as Config>::WeightInfo::mint())]
+		#[pallet::weight(<T as Config>::WeightInfo::mint(T::MaxFastMatchCount::get()))]
 		#[transactional]
 		pub fn mint(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_mint_frozen(), Error::<T>::MintFrozen);
 			T::Homa::mint(&who, amount)?;
 			Ok(().into())
 		}
@@ -108,5 +148,34 @@ pub mod module {
 			T::Homa::withdraw_redemption(&who)?;
 			Ok(().into())
 		}
+
+		/// Unbond DOT from relaychain immediately and freeze minting, the
+		/// staking pool reports the progress at each era until the unbonding
+		/// is completed.
+		///
+		/// The dispatch origin of this call must be `EmergencyOrigin`.
+		///
+		/// - `amount`: the DOT amount to unbond, `None` means all.
+		#[pallet::weight((<T as Config>::WeightInfo::emergency_unbond(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn emergency_unbond(origin: OriginFor<T>, amount: Option<Balance>) -> DispatchResultWithPostInfo {
+			T::EmergencyOrigin::ensure_origin(origin)?;
+			let unbonded = T::Homa::emergency_unbond(amount)?;
+			IsMintFrozen::<T>::put(true);
+			Self::deposit_event(Event::EmergencyUnbonded(unbonded));
+			Ok(().into())
+		}
+
+		/// Unfreeze minting.
+		///
+		/// The dispatch origin of this call must be `EmergencyOrigin`.
+		#[pallet::weight((<T as Config>::WeightInfo::unfreeze_mint(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn unfreeze_mint(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::EmergencyOrigin::ensure_origin(origin)?;
+			IsMintFrozen::<T>::put(false);
+			Self::deposit_event(Event::MintUnfrozen);
+			Ok(().into())
+		}
 	}
 }
@@ -0,0 +1,142 @@
+//! Mocks for the homa module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, DispatchResult};
+use std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+
+mod homa {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HomaCall {
+	Mint(AccountId, Balance),
+	RedeemByUnbond(AccountId, Balance),
+	RedeemByFreeUnbonded(AccountId, Balance),
+	RedeemByClaimUnbonding(AccountId, Balance, EraIndex),
+	WithdrawRedemption(AccountId),
+	EmergencyUnbond(Option<Balance>),
+}
+
+thread_local! {
+	static HOMA_CALLS: RefCell<Vec<HomaCall>> = RefCell::new(vec![]);
+}
+
+pub fn homa_calls() -> Vec<HomaCall> {
+	HOMA_CALLS.with(|v| v.borrow().clone())
+}
+
+fn record(call: HomaCall) {
+	HOMA_CALLS.with(|v| v.borrow_mut().push(call));
+}
+
+pub struct MockHomaProtocol;
+impl HomaProtocol<AccountId, Balance, EraIndex> for MockHomaProtocol {
+	type Balance = Balance;
+
+	fn mint(who: &AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		record(HomaCall::Mint(*who, amount));
+		Ok(amount)
+	}
+
+	fn redeem_by_unbond(who: &AccountId, amount: Balance) -> DispatchResult {
+		record(HomaCall::RedeemByUnbond(*who, amount));
+		Ok(())
+	}
+
+	fn redeem_by_free_unbonded(who: &AccountId, amount: Balance) -> DispatchResult {
+		record(HomaCall::RedeemByFreeUnbonded(*who, amount));
+		Ok(())
+	}
+
+	fn redeem_by_claim_unbonding(who: &AccountId, amount: Balance, target_era: EraIndex) -> DispatchResult {
+		record(HomaCall::RedeemByClaimUnbonding(*who, amount, target_era));
+		Ok(())
+	}
+
+	fn withdraw_redemption(who: &AccountId) -> Result<Balance, DispatchError> {
+		record(HomaCall::WithdrawRedemption(*who));
+		Ok(Default::default())
+	}
+
+	fn emergency_unbond(amount: Option<Balance>) -> Result<Balance, DispatchError> {
+		record(HomaCall::EmergencyUnbond(amount));
+		Ok(amount.unwrap_or(1000))
+	}
+}
+
+ord_parameter_types! {
+	pub const Four: AccountId = 4;
+}
+
+parameter_types! {
+	pub const MaxFastMatchCount: u32 = 10;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Homa = MockHomaProtocol;
+	type EmergencyOrigin = EnsureSignedBy<Four, AccountId>;
+	type MaxFastMatchCount = MaxFastMatchCount;
+	type WeightInfo = ();
+}
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Storage, Config, Event<T>},
+		HomaModule: homa::{Module, Call, Storage, Event<T>},
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap()
+		.into()
+}
@@ -0,0 +1,84 @@
+//! Unit tests for the homa module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn mint_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(HomaModule::mint(Origin::signed(ALICE), 100));
+		assert_eq!(homa_calls(), vec![HomaCall::Mint(ALICE, 100)]);
+	});
+}
+
+#[test]
+fn redeem_by_strategy_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(HomaModule::redeem(
+			Origin::signed(ALICE),
+			100,
+			RedeemStrategy::Immediately
+		));
+		assert_ok!(HomaModule::redeem(
+			Origin::signed(ALICE),
+			200,
+			RedeemStrategy::Target(3)
+		));
+		assert_ok!(HomaModule::redeem(
+			Origin::signed(ALICE),
+			300,
+			RedeemStrategy::WaitForUnbonding
+		));
+		assert_eq!(
+			homa_calls(),
+			vec![
+				HomaCall::RedeemByFreeUnbonded(ALICE, 100),
+				HomaCall::RedeemByClaimUnbonding(ALICE, 200, 3),
+				HomaCall::RedeemByUnbond(ALICE, 300),
+			]
+		);
+	});
+}
+
+#[test]
+fn withdraw_redemption_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(HomaModule::withdraw_redemption(Origin::signed(ALICE)));
+		assert_eq!(homa_calls(), vec![HomaCall::WithdrawRedemption(ALICE)]);
+	});
+}
+
+#[test]
+fn emergency_unbond_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(HomaModule::emergency_unbond(Origin::signed(ALICE), None), BadOrigin);
+		assert_ok!(HomaModule::emergency_unbond(Origin::signed(4), Some(500)));
+		let unbond_event = Event::homa(crate::Event::EmergencyUnbonded(500));
+		assert!(System::events().iter().any(|record| record.event == unbond_event));
+		assert_eq!(homa_calls(), vec![HomaCall::EmergencyUnbond(Some(500))]);
+		assert!(HomaModule::is_mint_frozen());
+
+		assert_noop!(
+			HomaModule::mint(Origin::signed(ALICE), 100),
+			Error::<Runtime>::MintFrozen
+		);
+		// redemption is still available
+		assert_ok!(HomaModule::redeem(
+			Origin::signed(ALICE),
+			100,
+			RedeemStrategy::WaitForUnbonding
+		));
+
+		assert_noop!(HomaModule::unfreeze_mint(Origin::signed(ALICE)), BadOrigin);
+		assert_ok!(HomaModule::unfreeze_mint(Origin::signed(4)));
+		let unfreeze_event = Event::homa(crate::Event::MintUnfrozen);
+		assert!(System::events().iter().any(|record| record.event == unfreeze_event));
+		assert!(!HomaModule::is_mint_frozen());
+		assert_ok!(HomaModule::mint(Origin::signed(ALICE), 100));
+	});
+}
@@ -0,0 +1,180 @@
+//! # Homa Lite Module
+//!
+//! ## Overview
+//!
+//! The simplified Homa protocol for the early launch. Users lock staking
+//! currency(DOT) in this module and receive liquid currency(LDOT) at the
+//! exchange rate derived from the oracle prices. The total locked staking
+//! currency is capped by governance, and there is no redemption, the holders
+//! of liquid currency can swap it back through DEX. The liquid currency is the
+//! same one of the full Homa protocol, so the locked staking currency and the
+//! issued liquid currency can be migrated to it later. It must not run
+//! together with the staking pool of the full Homa protocol, which doesn't
+//! count the staking currency locked here.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::{DataProvider, MultiCurrency};
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{AccountIdConversion, CheckedDiv},
+	FixedPointNumber, ModuleId,
+};
+use support::{ExchangeRate, Price};
+
+mod default_weight;
+mod mock;
+mod tests;
+
+pub use module::*;
+
+pub trait WeightInfo {
+	fn mint() -> Weight;
+	fn set_mint_cap() -> Weight;
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency for locking staking currency and issuing liquid currency
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		#[pallet::constant]
+		/// The staking currency id(should be DOT in acala)
+		type StakingCurrencyId: Get<CurrencyId>;
+
+		#[pallet::constant]
+		/// The liquid currency id(should be LDOT in acala)
+		type LiquidCurrencyId: Get<CurrencyId>;
+
+		#[pallet::constant]
+		/// The minimum staking currency amount to mint
+		type MinimumMintThreshold: Get<Balance>;
+
+		/// The origin which may update the mint cap. Root can always do this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The oracle prices, the exchange rate from liquid currency to
+		/// staking currency is the price of liquid currency relative to
+		/// staking currency.
+		type PriceSource: DataProvider<CurrencyId, Price>;
+
+		#[pallet::constant]
+		/// The homa lite module id, keep the locked staking currency.
+		type ModuleId: Get<ModuleId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The mint amount is below the minimum threshold
+		BelowMinimumMintThreshold,
+		/// The total locked staking currency exceeds the mint cap
+		ExceedMintCap,
+		/// The oracle price of staking or liquid currency is unavailable
+		ExchangeRateUnavailable,
+		/// The exchange rate is zero
+		InvalidExchangeRate,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Lock staking currency and mint liquid currency. \[who,
+		/// staking_amount_locked, liquid_amount_issued\]
+		Minted(T::AccountId, Balance, Balance),
+		/// The mint cap updated. \[new_mint_cap\]
+		MintCapUpdated(Balance),
+	}
+
+	/// The cap of the total locked staking currency.
+	#[pallet::storage]
+	#[pallet::getter(fn mint_cap)]
+	pub type MintCap<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The total locked staking currency.
+	#[pallet::storage]
+	#[pallet::getter(fn total_staking_locked)]
+	pub type TotalStakingLocked<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Lock staking currency and mint liquid currency at the exchange
+		/// rate derived from the oracle prices.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `amount`: staking currency amount to lock.
+		#[pallet::weight(<T as Config>::WeightInfo::mint())]
+		#[transactional]
+		pub fn mint(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				amount >= T::MinimumMintThreshold::get(),
+				Error::<T>::BelowMinimumMintThreshold
+			);
+
+			let total_staking_locked = Self::total_staking_locked()
+				.checked_add(amount)
+				.filter(|total| *total <= Self::mint_cap())
+				.ok_or(Error::<T>::ExceedMintCap)?;
+			let liquid_amount = Self::liquid_exchange_rate()
+				.ok_or(Error::<T>::ExchangeRateUnavailable)?
+				.reciprocal()
+				.ok_or(Error::<T>::InvalidExchangeRate)?
+				.saturating_mul_int(amount);
+
+			T::Currency::transfer(T::StakingCurrencyId::get(), &who, &Self::account_id(), amount)?;
+			T::Currency::deposit(T::LiquidCurrencyId::get(), &who, liquid_amount)?;
+			TotalStakingLocked::<T>::put(total_staking_locked);
+
+			Self::deposit_event(Event::Minted(who, amount, liquid_amount));
+			Ok(().into())
+		}
+
+		/// Update the cap of the total locked staking currency
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `mint_cap`: the new mint cap.
+		#[pallet::weight((<T as Config>::WeightInfo::set_mint_cap(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_mint_cap(origin: OriginFor<T>, #[pallet::compact] mint_cap: Balance) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MintCap::<T>::put(mint_cap);
+			Self::deposit_event(Event::MintCapUpdated(mint_cap));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	/// Get the exchange rate from liquid currency to staking currency by the
+	/// oracle prices.
+	pub fn liquid_exchange_rate() -> Option<ExchangeRate> {
+		let staking_price = T::PriceSource::get(&T::StakingCurrencyId::get())?;
+		let liquid_price = T::PriceSource::get(&T::LiquidCurrencyId::get())?;
+		liquid_price.checked_div(&staking_price)
+	}
+}
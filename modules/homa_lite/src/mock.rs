@@ -0,0 +1,155 @@
+//! Mocks for the homa lite module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::sp_api_hidden_includes_construct_runtime::hidden_include::inherent::BlockT;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
+
+mod homa_lite {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+thread_local! {
+	static LIQUID_PRICE: RefCell<Option<Price>> = RefCell::new(None);
+}
+
+pub fn mock_set_liquid_price(price: Option<Price>) {
+	LIQUID_PRICE.with(|v| *v.borrow_mut() = price)
+}
+
+pub struct MockPriceSource;
+impl DataProvider<CurrencyId, Price> for MockPriceSource {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		match *currency_id {
+			DOT => Some(Price::saturating_from_integer(10)),
+			LDOT => LIQUID_PRICE.with(|v| *v.borrow()),
+			_ => None,
+		}
+	}
+}
+
+ord_parameter_types! {
+	pub const Four: AccountId = 4;
+}
+
+parameter_types! {
+	pub const StakingCurrencyId: CurrencyId = DOT;
+	pub const LiquidCurrencyId: CurrencyId = LDOT;
+	pub const MinimumMintThreshold: Balance = 10;
+	pub const HomaLiteModuleId: ModuleId = ModuleId(*b"aca/hmlt");
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = TokensModule;
+	type StakingCurrencyId = StakingCurrencyId;
+	type LiquidCurrencyId = LiquidCurrencyId;
+	type MinimumMintThreshold = MinimumMintThreshold;
+	type UpdateOrigin = EnsureSignedBy<Four, AccountId>;
+	type PriceSource = MockPriceSource;
+	type ModuleId = HomaLiteModuleId;
+	type WeightInfo = ();
+}
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Storage, Config, Event<T>},
+		HomaLiteModule: homa_lite::{Module, Storage, Call, Event<T>},
+		TokensModule: orml_tokens::{Module, Storage, Event<T>, Config<T>},
+	}
+);
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![(ALICE, DOT, 1000), (BOB, DOT, 1000)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
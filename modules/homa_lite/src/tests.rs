@@ -0,0 +1,89 @@
+//! Unit tests for the homa lite module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::{BadOrigin, Zero};
+
+#[test]
+fn set_mint_cap_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(HomaLiteModule::set_mint_cap(Origin::signed(ALICE), 500), BadOrigin);
+		assert_ok!(HomaLiteModule::set_mint_cap(Origin::signed(4), 500));
+		let update_event = Event::homa_lite(crate::Event::MintCapUpdated(500));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(HomaLiteModule::mint_cap(), 500);
+	});
+}
+
+#[test]
+fn liquid_exchange_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(HomaLiteModule::liquid_exchange_rate(), None);
+		mock_set_liquid_price(Some(Price::saturating_from_integer(1)));
+		assert_eq!(
+			HomaLiteModule::liquid_exchange_rate(),
+			Some(ExchangeRate::saturating_from_rational(1, 10))
+		);
+	});
+}
+
+#[test]
+fn mint_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(HomaLiteModule::set_mint_cap(Origin::signed(4), 500));
+		assert_noop!(
+			HomaLiteModule::mint(Origin::signed(ALICE), 100),
+			Error::<Runtime>::ExchangeRateUnavailable
+		);
+		mock_set_liquid_price(Some(Price::zero()));
+		assert_noop!(
+			HomaLiteModule::mint(Origin::signed(ALICE), 100),
+			Error::<Runtime>::InvalidExchangeRate
+		);
+		mock_set_liquid_price(Some(Price::saturating_from_integer(1)));
+		assert_noop!(
+			HomaLiteModule::mint(Origin::signed(ALICE), 9),
+			Error::<Runtime>::BelowMinimumMintThreshold
+		);
+
+		assert_ok!(HomaLiteModule::mint(Origin::signed(ALICE), 100));
+		let mint_event = Event::homa_lite(crate::Event::Minted(ALICE, 100, 1000));
+		assert!(System::events().iter().any(|record| record.event == mint_event));
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE), 900);
+		assert_eq!(TokensModule::free_balance(LDOT, &ALICE), 1000);
+		assert_eq!(TokensModule::free_balance(DOT, &HomaLiteModule::account_id()), 100);
+		assert_eq!(HomaLiteModule::total_staking_locked(), 100);
+
+		// mint at the updated oracle price
+		mock_set_liquid_price(Some(Price::saturating_from_rational(5, 4)));
+		assert_ok!(HomaLiteModule::mint(Origin::signed(BOB), 400));
+		assert_eq!(TokensModule::free_balance(LDOT, &BOB), 3200);
+		assert_eq!(HomaLiteModule::total_staking_locked(), 500);
+	});
+}
+
+#[test]
+fn mint_exceed_mint_cap() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_set_liquid_price(Some(Price::saturating_from_integer(1)));
+		assert_noop!(
+			HomaLiteModule::mint(Origin::signed(ALICE), 100),
+			Error::<Runtime>::ExceedMintCap
+		);
+
+		assert_ok!(HomaLiteModule::set_mint_cap(Origin::signed(4), 500));
+		assert_ok!(HomaLiteModule::mint(Origin::signed(ALICE), 300));
+		assert_noop!(
+			HomaLiteModule::mint(Origin::signed(BOB), 201),
+			Error::<Runtime>::ExceedMintCap
+		);
+		assert_ok!(HomaLiteModule::mint(Origin::signed(BOB), 200));
+		assert_eq!(TokensModule::free_balance(LDOT, &BOB), 2000);
+		assert_eq!(HomaLiteModule::total_staking_locked(), 500);
+	});
+}
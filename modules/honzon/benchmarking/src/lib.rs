@@ -62,25 +62,20 @@ benchmarks! {
 
 	unauthorize_all {
 		let u in 0 .. 1000;
-		let v in 0 .. 100;
-		let c in 0 .. <T as cdp_engine::Config>::CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let c in 0 .. 100;
 
 		let caller: T::AccountId = account("caller", u, SEED);
-		let currency_ids = <T as cdp_engine::Config>::CollateralCurrencyIds::get();
 
-		for i in 0 .. v {
+		for i in 0 .. c {
 			let to: T::AccountId = account("to", i, SEED);
 			let to_lookup = T::Lookup::unlookup(to);
-
-			for j in 0 .. c {
-				Honzon::<T>::authorize(
-					RawOrigin::Signed(caller.clone()).into(),
-					currency_ids[j as usize],
-					to_lookup.clone(),
-				)?;
-			}
+			Honzon::<T>::authorize(
+				RawOrigin::Signed(caller.clone()).into(),
+				CurrencyId::Token(TokenSymbol::DOT),
+				to_lookup,
+			)?;
 		}
-	}: _(RawOrigin::Signed(caller))
+	}: _(RawOrigin::Signed(caller), c)
 
 	// `adjust_loan`, best case:
 	// adjust both collateral and debit
@@ -156,7 +151,7 @@ benchmarks! {
 		)?;
 
 		// authorize receiver
-		Honzon::<T>::authorize(
+		Honzon::<T>::authorize_transfer(
 			RawOrigin::Signed(sender).into(),
 			currency_id,
 			receiver_lookup
@@ -0,0 +1,85 @@
+//! RPC interface for the honzon module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_honzon_rpc_runtime_api::LoanHealth;
+use primitives::CurrencyId;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as HonzonClient;
+pub use module_honzon_rpc_runtime_api::HonzonApi as HonzonRuntimeApi;
+
+#[rpc]
+pub trait HonzonApi<BlockHash, AccountId, ResponseType> {
+	#[rpc(name = "honzon_getLoanHealth")]
+	fn get_loan_health(
+		&self,
+		account: AccountId,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ResponseType>>;
+}
+
+/// A struct that implements the [`HonzonApi`].
+pub struct Honzon<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Honzon<C, B> {
+	/// Create new `Honzon` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Honzon {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, Balance> HonzonApi<<Block as BlockT>::Hash, AccountId, LoanHealth<Balance>>
+	for Honzon<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: HonzonRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	fn get_loan_health(
+		&self,
+		account: AccountId,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<LoanHealth<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_loan_health(&at, account, currency_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get loan health.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
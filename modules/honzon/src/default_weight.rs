@@ -8,22 +8,47 @@ use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 
 impl crate::WeightInfo for () {
 	fn authorize() -> Weight {
-		(54_182_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+		(110_993_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
 	}
 	fn unauthorize() -> Weight {
-		(51_443_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+		(102_377_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
 	}
 	fn unauthorize_all(c: u32) -> Weight {
-		(79_165_000 as Weight).saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+		(92_406_000 as Weight)
+			.saturating_add((14_923_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
 	fn adjust_loan() -> Weight {
 		(544_147_000 as Weight)
 			.saturating_add(DbWeight::get().reads(24 as Weight))
 			.saturating_add(DbWeight::get().writes(10 as Weight))
 	}
+	fn adjust_loan_on_behalf() -> Weight {
+		(561_730_000 as Weight)
+			.saturating_add(DbWeight::get().reads(25 as Weight))
+			.saturating_add(DbWeight::get().writes(10 as Weight))
+	}
+	fn adjust_loans(c: u32) -> Weight {
+		(21_345_000 as Weight)
+			.saturating_add((544_147_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads((24 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((10 as Weight).saturating_mul(c as Weight)))
+	}
 	fn transfer_loan_from() -> Weight {
 		(682_312_000 as Weight)
 			.saturating_add(DbWeight::get().reads(21 as Weight))
 			.saturating_add(DbWeight::get().writes(8 as Weight))
 	}
+	fn close_loan_by_dex() -> Weight {
+		(1_006_451_000 as Weight)
+			.saturating_add(DbWeight::get().reads(28 as Weight))
+			.saturating_add(DbWeight::get().writes(14 as Weight))
+	}
 }
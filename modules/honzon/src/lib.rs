@@ -4,21 +4,25 @@
 //!
 //! The entry of the Honzon protocol for users, user can manipulate their CDP
 //! position to loan/payback, and can also authorize others to manage the their
-//! CDP under specific collateral type.
+//! CDP under specific collateral type. Transferring the whole CDP away needs a
+//! separate transfer authorization.
 //!
 //! After system shutdown, some operations will be restricted.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
+use codec::Input;
 use frame_support::{pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
-use primitives::{Amount, CurrencyId};
+use orml_traits::BasicReservableCurrency;
+use primitives::{Amount, Balance, CurrencyId};
 use sp_runtime::{
 	traits::{StaticLookup, Zero},
-	DispatchResult,
+	DispatchResult, RuntimeDebug,
 };
-use support::EmergencyShutdown;
+use sp_std::prelude::*;
+use support::{EmergencyShutdown, HonzonManager};
 
 mod default_weight;
 mod mock;
@@ -26,12 +30,31 @@ mod tests;
 
 pub use module::*;
 
+/// The storage version of the module
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum Releases {
+	/// The authorizations are stored as `bool`
+	V1_0_0,
+	/// The authorizations reserve `DepositPerAuthorization` from the
+	/// authorizer
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
 pub trait WeightInfo {
 	fn authorize() -> Weight;
 	fn unauthorize() -> Weight;
 	fn unauthorize_all(c: u32) -> Weight;
 	fn adjust_loan() -> Weight;
+	fn adjust_loan_on_behalf() -> Weight;
+	fn adjust_loans(c: u32) -> Weight;
 	fn transfer_loan_from() -> Weight;
+	fn close_loan_by_dex() -> Weight;
 }
 
 #[frame_support::pallet]
@@ -42,6 +65,13 @@ pub mod module {
 	pub trait Config: frame_system::Config + cdp_engine::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
+		/// Currency type for reserve/unreserve the deposit of authorization
+		type Currency: BasicReservableCurrency<Self::AccountId, Balance = Balance>;
+
+		/// Reserved amount per authorization.
+		#[pallet::constant]
+		type DepositPerAuthorization: Get<Balance>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -52,6 +82,12 @@ pub mod module {
 		NoAuthorization,
 		// The system has been shutdown
 		AlreadyShutdown,
+		// Authorization not exists
+		AuthorizationNotExists,
+		// Have authorized already
+		AlreadyAuthorized,
+		// The number of the authorizations exceeds the given one
+		TooManyAuthorizations,
 	}
 
 	#[pallet::event]
@@ -63,22 +99,82 @@ pub mod module {
 		/// Cancel the authorization of specific collateral for someone.
 		/// \[authorizer, authorizee, collateral_type\]
 		UnAuthorization(T::AccountId, T::AccountId, CurrencyId),
+		/// Authorize someone to transfer the loan of specific collateral.
+		/// \[authorizer, authorizee, collateral_type\]
+		TransferAuthorization(T::AccountId, T::AccountId, CurrencyId),
+		/// Cancel the transfer authorization of specific collateral for
+		/// someone. \[authorizer, authorizee, collateral_type\]
+		TransferUnAuthorization(T::AccountId, T::AccountId, CurrencyId),
 		/// Cancel all authorization. \[authorizer\]
 		UnAuthorizationAll(T::AccountId),
+		/// Adjust the loan of authorizer by authorizee. \[authorizer,
+		/// authorizee, collateral_type, collateral_adjustment,
+		/// debit_adjustment\]
+		LoanAdjustedOnBehalf(T::AccountId, T::AccountId, CurrencyId, Amount, Amount),
 	}
 
 	/// The authorization relationship map from
-	/// Authorizer -> (CollateralType, Authorizee) -> Authorized
+	/// Authorizer -> (CollateralType, Authorizee) -> ReservedDeposit
 	#[pallet::storage]
 	#[pallet::getter(fn authorization)]
-	pub type Authorization<T: Config> =
-		StorageDoubleMap<_, Twox64Concat, T::AccountId, Blake2_128Concat, (CurrencyId, T::AccountId), bool, ValueQuery>;
+	pub type Authorization<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(CurrencyId, T::AccountId),
+		Balance,
+		OptionQuery,
+	>;
+
+	/// The transfer authorization relationship map from
+	/// Authorizer -> (CollateralType, Authorizee) -> ReservedDeposit
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_authorization)]
+	pub type TransferAuthorization<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(CurrencyId, T::AccountId),
+		Balance,
+		OptionQuery,
+	>;
+
+	/// The storage version of the module
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Migrate the authorizations stored as `bool` before
+		/// `DepositPerAuthorization` was introduced: reserve the deposit from
+		/// the authorizer, or drop the authorization if it can't be reserved.
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() != Releases::V1_0_0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut migrated: Weight = 0;
+			<Authorization<T>>::translate::<LegacyAuthorized, _>(|from, _, _| {
+				migrated += 1;
+				let reserve_amount = T::DepositPerAuthorization::get();
+				<T as Config>::Currency::reserve(&from, reserve_amount)
+					.ok()
+					.map(|_| reserve_amount)
+			});
+			StorageVersion::<T>::put(Releases::V2_0_0);
+
+			T::DbWeight::get().reads_writes(
+				migrated.saturating_mul(2).saturating_add(1),
+				migrated.saturating_mul(2).saturating_add(1),
+			)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -111,9 +207,79 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Adjust the loans of caller under multiple collateral types in a
+		/// single atomic call, all adjustments fail if any of them fails.
+		///
+		/// - `adjustments`: list of `(currency_id, collateral_adjustment,
+		///   debit_adjustment)`, applied in order, each has the same meaning
+		///   as the params of `adjust_loan`.
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loans(adjustments.len() as u32))]
+		#[transactional]
+		pub fn adjust_loans(
+			origin: OriginFor<T>,
+			adjustments: Vec<(CurrencyId, Amount, Amount)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			// not allowed to adjust the debit after system shutdown
+			if adjustments
+				.iter()
+				.any(|(_, _, debit_adjustment)| !debit_adjustment.is_zero())
+			{
+				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			}
+			for (currency_id, collateral_adjustment, debit_adjustment) in adjustments {
+				<cdp_engine::Module<T>>::adjust_position(&who, currency_id, collateral_adjustment, debit_adjustment)?;
+			}
+			Ok(().into())
+		}
+
+		/// Adjust the loans of `owner` under `currency_id` on behalf of it,
+		/// caller must have the authorization of `owner` for the specific
+		/// collateral type. The collateral and stablecoin are always
+		/// transferred from/to `owner`, caller can not withdraw them to itself.
+		///
+		/// - `owner`: authorizer account
+		/// - `currency_id`: collateral currency id.
+		/// - `collateral_adjustment`: signed amount, positive means to deposit
+		///   collateral currency into CDP, negative means withdraw collateral
+		///   currency from CDP.
+		/// - `debit_adjustment`: signed amount, positive means to issue some
+		///   amount of stablecoin to owner according to the debit adjustment,
+		///   negative means owner will payback some amount of stablecoin to
+		///   CDP according to to the debit adjustment.
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan_on_behalf())]
+		#[transactional]
+		pub fn adjust_loan_on_behalf(
+			origin: OriginFor<T>,
+			owner: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyId,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			Self::check_authorization(&owner, &who, currency_id)?;
+
+			// not allowed to adjust the debit after system shutdown
+			if !debit_adjustment.is_zero() {
+				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			}
+			<cdp_engine::Module<T>>::adjust_position(&owner, currency_id, collateral_adjustment, debit_adjustment)?;
+
+			Self::deposit_event(Event::LoanAdjustedOnBehalf(
+				owner,
+				who,
+				currency_id,
+				collateral_adjustment,
+				debit_adjustment,
+			));
+			Ok(().into())
+		}
+
 		/// Transfer the whole CDP of `from` under `currency_id` to caller's CDP
-		/// under the same `currency_id`, caller must have the authorization of
-		/// `from` for the specific collateral type
+		/// under the same `currency_id`, caller must have the transfer
+		/// authorization of `from` for the specific collateral type
 		///
 		/// - `currency_id`: collateral currency id.
 		/// - `from`: authorizer account
@@ -127,12 +293,33 @@ pub mod module {
 			let to = ensure_signed(origin)?;
 			let from = T::Lookup::lookup(from)?;
 			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-			Self::check_authorization(&from, &to, currency_id)?;
+			Self::check_transfer_authorization(&from, &to, currency_id)?;
 			<loans::Module<T>>::transfer_loan(&from, &to, currency_id)?;
 			Ok(().into())
 		}
 
-		/// Authorize `to` to manipulate the loan under `currency_id`
+		/// Close caller's CDP which has debit but still in safe by use
+		/// collateral to swap stable token on DEX for clearing debit, and
+		/// refund the remaining collateral to caller.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `max_collateral_amount`: the max collateral amount which is
+		///   allowed to be sold on DEX, acts as the slippage bound.
+		#[pallet::weight(<T as Config>::WeightInfo::close_loan_by_dex())]
+		#[transactional]
+		pub fn close_loan_by_dex(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] max_collateral_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			<cdp_engine::Module<T>>::close_cdp_has_debit_by_dex(who, currency_id, max_collateral_amount)?;
+			Ok(().into())
+		}
+
+		/// Authorize `to` to manipulate the loan under `currency_id`, caller
+		/// will reserve `DepositPerAuthorization` for each authorization.
 		///
 		/// - `currency_id`: collateral currency id.
 		/// - `to`: authorizee account
@@ -145,7 +332,19 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(to)?;
-			<Authorization<T>>::insert(&from, (currency_id, &to), true);
+			if from == to {
+				return Ok(().into());
+			}
+
+			<Authorization<T>>::try_mutate_exists(&from, (currency_id, &to), |maybe_reserved| -> DispatchResult {
+				ensure!(maybe_reserved.is_none(), Error::<T>::AlreadyAuthorized);
+
+				let reserve_amount = T::DepositPerAuthorization::get();
+				<T as Config>::Currency::reserve(&from, reserve_amount)?;
+				*maybe_reserved = Some(reserve_amount);
+				Ok(())
+			})?;
+
 			Self::deposit_event(Event::Authorization(from, to, currency_id));
 			Ok(().into())
 		}
@@ -163,20 +362,109 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(to)?;
-			<Authorization<T>>::remove(&from, (currency_id, &to));
+			let reserved =
+				<Authorization<T>>::take(&from, (currency_id, &to)).ok_or(Error::<T>::AuthorizationNotExists)?;
+			<T as Config>::Currency::unreserve(&from, reserved);
 			Self::deposit_event(Event::UnAuthorization(from, to, currency_id));
 			Ok(().into())
 		}
 
-		/// Cancel all authorization of caller
-		#[pallet::weight(<T as Config>::WeightInfo::unauthorize_all(<T as cdp_engine::Config>::CollateralCurrencyIds::get().len() as u32))]
+		/// Authorize `to` to transfer the whole loan under `currency_id` to
+		/// itself, caller will reserve `DepositPerAuthorization` for each
+		/// transfer authorization.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `to`: authorizee account
+		#[pallet::weight(<T as Config>::WeightInfo::authorize())]
 		#[transactional]
-		pub fn unauthorize_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+		pub fn authorize_transfer(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
-			<Authorization<T>>::remove_prefix(&from);
-			Self::deposit_event(Event::UnAuthorizationAll(from));
+			let to = T::Lookup::lookup(to)?;
+			if from == to {
+				return Ok(().into());
+			}
+
+			<TransferAuthorization<T>>::try_mutate_exists(
+				&from,
+				(currency_id, &to),
+				|maybe_reserved| -> DispatchResult {
+					ensure!(maybe_reserved.is_none(), Error::<T>::AlreadyAuthorized);
+
+					let reserve_amount = T::DepositPerAuthorization::get();
+					<T as Config>::Currency::reserve(&from, reserve_amount)?;
+					*maybe_reserved = Some(reserve_amount);
+					Ok(())
+				},
+			)?;
+
+			Self::deposit_event(Event::TransferAuthorization(from, to, currency_id));
 			Ok(().into())
 		}
+
+		/// Cancel the transfer authorization for `to` under `currency_id`
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `to`: authorizee account
+		#[pallet::weight(<T as Config>::WeightInfo::unauthorize())]
+		#[transactional]
+		pub fn unauthorize_transfer(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			let reserved = <TransferAuthorization<T>>::take(&from, (currency_id, &to))
+				.ok_or(Error::<T>::AuthorizationNotExists)?;
+			<T as Config>::Currency::unreserve(&from, reserved);
+			Self::deposit_event(Event::TransferUnAuthorization(from, to, currency_id));
+			Ok(().into())
+		}
+
+		/// Cancel all authorization and transfer authorization of caller
+		///
+		/// - `authorizations`: the number of the authorizations and the
+		///   transfer authorizations of caller, the weight is charged by it.
+		#[pallet::weight(<T as Config>::WeightInfo::unauthorize_all(*authorizations))]
+		#[transactional]
+		pub fn unauthorize_all(
+			origin: OriginFor<T>,
+			#[pallet::compact] authorizations: u32,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+
+			// only count up to the given number, so the reads are covered by the weight
+			let limit = authorizations.saturating_add(1) as usize;
+			let count = <Authorization<T>>::iter_prefix(&from)
+				.take(limit)
+				.count()
+				.saturating_add(<TransferAuthorization<T>>::iter_prefix(&from).take(limit).count());
+			ensure!(count <= authorizations as usize, Error::<T>::TooManyAuthorizations);
+
+			let reserved = <Authorization<T>>::drain_prefix(&from)
+				.chain(<TransferAuthorization<T>>::drain_prefix(&from))
+				.fold(Zero::zero(), |acc: Balance, (_, reserved)| acc.saturating_add(reserved));
+			<T as Config>::Currency::unreserve(&from, reserved);
+			Self::deposit_event(Event::UnAuthorizationAll(from));
+			Ok(Some(<T as Config>::WeightInfo::unauthorize_all(count as u32)).into())
+		}
+	}
+}
+
+/// The authorization value before `DepositPerAuthorization` was introduced,
+/// only decodes from a single encoded `bool` so migrated entries are skipped.
+struct LegacyAuthorized;
+
+impl Decode for LegacyAuthorized {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		if input.remaining_len()? != Some(1) {
+			return Err("not a legacy authorization".into());
+		}
+		bool::decode(input).map(|_| LegacyAuthorized)
 	}
 }
 
@@ -184,9 +472,53 @@ impl<T: Config> Pallet<T> {
 	/// Check if `from` has the authorization of `to` under `currency_id`
 	fn check_authorization(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
 		ensure!(
-			from == to || Self::authorization(from, (currency_id, to)),
+			from == to || Self::authorization(from, (currency_id, to)).is_some(),
+			Error::<T>::NoAuthorization
+		);
+		Ok(())
+	}
+
+	/// Check if `from` has the transfer authorization of `to` under
+	/// `currency_id`
+	fn check_transfer_authorization(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		ensure!(
+			from == to || Self::transfer_authorization(from, (currency_id, to)).is_some(),
 			Error::<T>::NoAuthorization
 		);
 		Ok(())
 	}
 }
+
+impl<T: Config> HonzonManager<T::AccountId, CurrencyId, Amount, Balance> for Pallet<T> {
+	fn adjust_loan(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> DispatchResult {
+		// not allowed to adjust the debit after system shutdown
+		if !debit_adjustment.is_zero() {
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+		}
+		<cdp_engine::Module<T>>::adjust_position(who, currency_id, collateral_adjustment, debit_adjustment)
+	}
+
+	fn get_position(who: &T::AccountId, currency_id: CurrencyId) -> (Balance, Balance) {
+		let loans::Position { collateral, debit } = <loans::Module<T>>::positions(currency_id, who);
+		(collateral, debit)
+	}
+
+	fn get_debit_value(who: &T::AccountId, currency_id: CurrencyId) -> Balance {
+		let (_, debit) = Self::get_position(who, currency_id);
+		<cdp_engine::Module<T>>::get_debit_value(currency_id, debit)
+	}
+
+	fn get_total_debit_value(currency_id: CurrencyId) -> Balance {
+		let loans::Position { debit, .. } = <loans::Module<T>>::total_positions(currency_id);
+		<cdp_engine::Module<T>>::get_debit_value(currency_id, debit)
+	}
+
+	fn check_authorization(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		Self::check_authorization(from, to, currency_id)
+	}
+}
@@ -4,7 +4,7 @@
 
 use super::*;
 use crate::mock::sp_api_hidden_includes_construct_runtime::hidden_include::inherent::BlockT;
-use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types, traits::UnixTime};
 use frame_system::{offchain::SendTransactionTypes, EnsureSignedBy};
 use orml_traits::parameter_type_with_key;
 use primitives::{Balance, TokenSymbol};
@@ -14,7 +14,7 @@ use sp_runtime::{
 	traits::IdentityLookup,
 	FixedPointNumber, ModuleId,
 };
-use sp_std::cell::RefCell;
+use sp_std::{cell::RefCell, time::Duration};
 use support::{AuctionManager, ExchangeRate, Price, PriceProvider, Rate, Ratio};
 
 mod honzon {
@@ -193,6 +193,13 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> Duration {
+		Duration::from_secs(0)
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -201,6 +208,7 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryModuleId: ModuleId = ModuleId(*b"aca/cdpt");
+	pub const BadDebtDelay: BlockNumber = 10;
 }
 
 impl cdp_treasury::Config for Runtime {
@@ -212,6 +220,10 @@ impl cdp_treasury::Config for Runtime {
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type BadDebtDelay = BadDebtDelay;
+	type DebitAuctionFixedSize = ();
+	type DebitAuctionInitialAmount = ();
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type WeightInfo = ();
 }
 
@@ -220,6 +232,13 @@ parameter_types! {
 	pub DefaultLiquidationRatio: Ratio = Ratio::saturating_from_rational(3, 2);
 	pub DefaultDebitExchangeRate: ExchangeRate = ExchangeRate::one();
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(10, 100);
+	pub const ParameterChangeDelay: BlockNumber = 10;
+	pub const LiquidatorBond: Balance = 100;
+	pub const LiquidatorExclusiveWindow: BlockNumber = 0;
+	pub const LiquidatorUnbondingDelay: BlockNumber = 10;
+	pub MaxPriceConfidence: Ratio = Ratio::saturating_from_rational(5, 100);
+	pub MaxLiquidationPenaltyStep: Rate = Rate::saturating_from_rational(10, 100);
+	pub KeeperRewardRatio: Rate = Rate::saturating_from_rational(1, 100);
 	pub const MinimumDebitValue: Balance = 2;
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
 	pub const UnsignedPriority: u64 = 1 << 20;
@@ -232,6 +251,9 @@ impl cdp_engine::Config for Runtime {
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
+	type ParameterChangeDelay = ParameterChangeDelay;
+	type MaxLiquidationPenaltyStep = MaxLiquidationPenaltyStep;
+	type KeeperRewardRatio = KeeperRewardRatio;
 	type MinimumDebitValue = MinimumDebitValue;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
@@ -240,6 +262,12 @@ impl cdp_engine::Config for Runtime {
 	type DEX = ();
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type UnixTime = MockUnixTime;
+	type Currency = AdaptedBasicCurrency;
+	type LiquidatorBond = LiquidatorBond;
+	type LiquidatorExclusiveWindow = LiquidatorExclusiveWindow;
+	type LiquidatorUnbondingDelay = LiquidatorUnbondingDelay;
+	type MaxPriceConfidence = MaxPriceConfidence;
 	type WeightInfo = ();
 }
 
@@ -274,8 +302,14 @@ where
 	type Extrinsic = Extrinsic;
 }
 
+parameter_types! {
+	pub const DepositPerAuthorization: Balance = 100;
+}
+
 impl Config for Runtime {
 	type Event = Event;
+	type Currency = AdaptedBasicCurrency;
+	type DepositPerAuthorization = DepositPerAuthorization;
 	type WeightInfo = ();
 }
 pub type HonzonModule = Module<Runtime>;
@@ -288,6 +322,8 @@ impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			endowed_accounts: vec![
+				(ALICE, ACA, 1000),
+				(BOB, ACA, 1000),
 				(ALICE, BTC, 1000),
 				(BOB, BTC, 1000),
 				(ALICE, DOT, 1000),
@@ -303,8 +339,24 @@ impl ExtBuilder {
 			.build_storage::<Runtime>()
 			.unwrap();
 
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: self
+				.endowed_accounts
+				.clone()
+				.into_iter()
+				.filter(|(_, currency_id, _)| *currency_id == ACA)
+				.map(|(account_id, _, initial_balance)| (account_id, initial_balance))
+				.collect::<Vec<_>>(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		orml_tokens::GenesisConfig::<Runtime> {
-			endowed_accounts: self.endowed_accounts,
+			endowed_accounts: self
+				.endowed_accounts
+				.into_iter()
+				.filter(|(_, currency_id, _)| *currency_id != ACA)
+				.collect::<Vec<_>>(),
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -13,7 +13,9 @@ use support::{Rate, Ratio};
 fn authorize_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		System::set_block_number(1);
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
 		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), DepositPerAuthorization::get());
 
 		let authorization_event = Event::honzon(crate::Event::Authorization(ALICE, BOB, BTC));
 		assert!(System::events()
@@ -21,6 +23,10 @@ fn authorize_should_work() {
 			.any(|record| record.event == authorization_event));
 
 		assert_ok!(HonzonModule::check_authorization(&ALICE, &BOB, BTC));
+		assert_noop!(
+			HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB),
+			Error::<Runtime>::AlreadyAuthorized,
+		);
 	});
 }
 
@@ -29,8 +35,10 @@ fn unauthorize_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		System::set_block_number(1);
 		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), DepositPerAuthorization::get());
 		assert_ok!(HonzonModule::check_authorization(&ALICE, &BOB, BTC));
 		assert_ok!(HonzonModule::unauthorize(Origin::signed(ALICE), BTC, BOB));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
 
 		let unauthorization_event = Event::honzon(crate::Event::UnAuthorization(ALICE, BOB, BTC));
 		assert!(System::events()
@@ -41,6 +49,10 @@ fn unauthorize_should_work() {
 			HonzonModule::check_authorization(&ALICE, &BOB, BTC),
 			Error::<Runtime>::NoAuthorization
 		);
+		assert_noop!(
+			HonzonModule::unauthorize(Origin::signed(ALICE), BTC, BOB),
+			Error::<Runtime>::AuthorizationNotExists
+		);
 	});
 }
 
@@ -50,7 +62,15 @@ fn unauthorize_all_should_work() {
 		System::set_block_number(1);
 		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB));
 		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), DOT, CAROL));
-		assert_ok!(HonzonModule::unauthorize_all(Origin::signed(ALICE)));
+		assert_ok!(HonzonModule::authorize_transfer(Origin::signed(ALICE), BTC, BOB));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 300);
+		assert_noop!(
+			HonzonModule::unauthorize_all(Origin::signed(ALICE), 2),
+			Error::<Runtime>::TooManyAuthorizations
+		);
+		assert_ok!(HonzonModule::unauthorize_all(Origin::signed(ALICE), 3));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+		assert_eq!(HonzonModule::transfer_authorization(ALICE, (BTC, BOB)), None);
 
 		let unauthorization_all_event = Event::honzon(crate::Event::UnAuthorizationAll(ALICE));
 		assert!(System::events()
@@ -68,9 +88,85 @@ fn unauthorize_all_should_work() {
 	});
 }
 
+#[test]
+fn on_runtime_upgrade_should_migrate_legacy_authorization() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), DOT, CAROL));
+		frame_support::storage::unhashed::put(&Authorization::<Runtime>::hashed_key_for(&ALICE, (BTC, BOB)), &true);
+		frame_support::storage::unhashed::put(&Authorization::<Runtime>::hashed_key_for(&CAROL, (BTC, BOB)), &true);
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 100);
+
+		<HonzonModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 200);
+		assert_eq!(HonzonModule::authorization(ALICE, (BTC, BOB)), Some(100));
+		assert_eq!(HonzonModule::authorization(ALICE, (DOT, CAROL)), Some(100));
+		assert_eq!(HonzonModule::authorization(CAROL, (BTC, BOB)), None);
+
+		assert_eq!(HonzonModule::storage_version(), Releases::V2_0_0);
+
+		// only migrated once
+		frame_support::storage::unhashed::put(&Authorization::<Runtime>::hashed_key_for(&ALICE, (DOT, BOB)), &true);
+		<HonzonModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 200);
+		assert_eq!(HonzonModule::authorization(ALICE, (BTC, BOB)), Some(100));
+		assert!(frame_support::storage::unhashed::exists(
+			&Authorization::<Runtime>::hashed_key_for(&ALICE, (DOT, BOB))
+		));
+	});
+}
+
+#[test]
+fn adjust_loan_on_behalf_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			HonzonModule::adjust_loan_on_behalf(Origin::signed(BOB), ALICE, BTC, 100, 50),
+			Error::<Runtime>::NoAuthorization,
+		);
+		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB));
+		assert_ok!(HonzonModule::adjust_loan_on_behalf(
+			Origin::signed(BOB),
+			ALICE,
+			BTC,
+			100,
+			50
+		));
+
+		let adjust_on_behalf_event = Event::honzon(crate::Event::LoanAdjustedOnBehalf(ALICE, BOB, BTC, 100, 50));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == adjust_on_behalf_event));
+
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(BTC, BOB).collateral, 0);
+
+		// collateral withdrawn by authorizee goes back to the owner
+		assert_ok!(HonzonModule::adjust_loan_on_behalf(
+			Origin::signed(BOB),
+			ALICE,
+			BTC,
+			-10,
+			0
+		));
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 910);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1000);
+	});
+}
+
 #[test]
 fn transfer_loan_from_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
 			BTC,
@@ -81,13 +177,49 @@ fn transfer_loan_from_should_work() {
 			Change::NewValue(10000),
 		));
 		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+
+		// the authorization to adjust the loan doesn't allow the transfer
 		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB));
+		assert_noop!(
+			HonzonModule::transfer_loan_from(Origin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::NoAuthorization,
+		);
+
+		assert_ok!(HonzonModule::authorize_transfer(Origin::signed(ALICE), BTC, BOB));
+		let authorization_event = Event::honzon(crate::Event::TransferAuthorization(ALICE, BOB, BTC));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == authorization_event));
 		assert_ok!(HonzonModule::transfer_loan_from(Origin::signed(BOB), BTC, ALICE));
 		assert_eq!(LoansModule::positions(BTC, BOB).collateral, 100);
 		assert_eq!(LoansModule::positions(BTC, BOB).debit, 50);
 	});
 }
 
+#[test]
+fn unauthorize_transfer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(HonzonModule::authorize_transfer(Origin::signed(ALICE), BTC, BOB));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), DepositPerAuthorization::get());
+		assert_noop!(
+			HonzonModule::authorize_transfer(Origin::signed(ALICE), BTC, BOB),
+			Error::<Runtime>::AlreadyAuthorized,
+		);
+
+		assert_ok!(HonzonModule::unauthorize_transfer(Origin::signed(ALICE), BTC, BOB));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+		let unauthorization_event = Event::honzon(crate::Event::TransferUnAuthorization(ALICE, BOB, BTC));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == unauthorization_event));
+		assert_noop!(
+			HonzonModule::unauthorize_transfer(Origin::signed(ALICE), BTC, BOB),
+			Error::<Runtime>::AuthorizationNotExists
+		);
+	});
+}
+
 #[test]
 fn transfer_unauthorization_loans_should_not_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -116,6 +248,112 @@ fn adjust_loan_should_work() {
 	});
 }
 
+#[test]
+fn honzon_manager_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(<HonzonModule as HonzonManager<_, _, _, _>>::adjust_loan(
+			&ALICE, BTC, 100, 50
+		));
+		assert_ok!(<HonzonModule as HonzonManager<_, _, _, _>>::adjust_loan(
+			&BOB, BTC, 100, 30
+		));
+		assert_eq!(
+			<HonzonModule as HonzonManager<_, _, _, _>>::get_position(&ALICE, BTC),
+			(100, 50)
+		);
+		assert_eq!(
+			<HonzonModule as HonzonManager<_, _, _, _>>::get_debit_value(&ALICE, BTC),
+			50
+		);
+		assert_eq!(
+			<HonzonModule as HonzonManager<_, _, _, _>>::get_total_debit_value(BTC),
+			80
+		);
+
+		mock_shutdown();
+		assert_noop!(
+			<HonzonModule as HonzonManager<_, _, _, _>>::adjust_loan(&ALICE, BTC, 0, -10),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
+
+#[test]
+fn adjust_loans_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+
+		// all adjustments are reverted if any of them fails
+		assert_noop!(
+			HonzonModule::adjust_loans(Origin::signed(ALICE), vec![(BTC, 100, 50), (DOT, 100, 50)]),
+			cdp_engine::Error::<Runtime>::ExceedDebitValueHardCap,
+		);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			DOT,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loans(
+			Origin::signed(ALICE),
+			vec![(BTC, 100, 50), (DOT, 200, 30)]
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(DOT, ALICE).collateral, 200);
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 30);
+
+		mock_shutdown();
+		assert_noop!(
+			HonzonModule::adjust_loans(Origin::signed(ALICE), vec![(BTC, 0, 10)]),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_ok!(HonzonModule::adjust_loans(Origin::signed(ALICE), vec![(DOT, -100, 0)]));
+		assert_eq!(LoansModule::positions(DOT, ALICE).collateral, 100);
+	});
+}
+
+#[test]
+fn close_loan_by_dex_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+		assert_ok!(HonzonModule::close_loan_by_dex(Origin::signed(ALICE), BTC, 100));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+	});
+}
+
 #[test]
 fn on_emergency_shutdown_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -128,5 +366,9 @@ fn on_emergency_shutdown_should_work() {
 			HonzonModule::transfer_loan_from(Origin::signed(ALICE), BTC, BOB),
 			Error::<Runtime>::AlreadyShutdown,
 		);
+		assert_noop!(
+			HonzonModule::close_loan_by_dex(Origin::signed(ALICE), BTC, 100),
+			Error::<Runtime>::AlreadyShutdown,
+		);
 	});
 }
@@ -0,0 +1,31 @@
+//! Runtime API definition for incentives module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_runtime::FixedU128;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait IncentivesApi<AccountId, PoolId, CurrencyId, Balance> where
+		AccountId: Codec,
+		PoolId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+	{
+		/// The share of the claimed rewards of the pool forfeited back into
+		/// the pool.
+		fn claim_reward_deduction_rate(pool_id: PoolId) -> FixedU128;
+
+		/// The pending rewards of `who` in the pool for each reward currency,
+		/// the actual rewards to receive and the deductions if claimed now.
+		fn pending_rewards(pool_id: PoolId, who: AccountId) -> Vec<(CurrencyId, Balance, Balance)>;
+
+		/// The pools `who` participates in, with the share of `who`, the
+		/// pending rewards and the claim reward deduction rate of each pool.
+		fn participated_pools(who: AccountId) -> Vec<(PoolId, Balance, Vec<(CurrencyId, Balance, Balance)>, FixedU128)>;
+	}
+}
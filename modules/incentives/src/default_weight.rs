@@ -40,4 +40,53 @@ impl crate::WeightInfo for () {
 			.saturating_add((5_340_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
+	fn update_extra_incentive_rewards(c: u32) -> Weight {
+		(4_846_000 as Weight)
+			.saturating_add((4_851_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_claim_reward_deduction_rates(c: u32) -> Weight {
+		(3_896_000 as Weight)
+			.saturating_add((5_340_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_emission_schedules(c: u32) -> Weight {
+		(4_312_000 as Weight)
+			.saturating_add((6_102_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+	fn add_rewards() -> Weight {
+		(98_214_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn compound() -> Weight {
+		(712_430_000 as Weight)
+			.saturating_add(DbWeight::get().reads(19 as Weight))
+			.saturating_add(DbWeight::get().writes(15 as Weight))
+	}
+	fn update_reward_vesting_durations(c: u32) -> Weight {
+		(3_712_000 as Weight)
+			.saturating_add((5_108_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_pool_lifetimes(c: u32) -> Weight {
+		(3_985_000 as Weight)
+			.saturating_add((6_327_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_surplus_reward_rate() -> Weight {
+		(4_127_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_referrer() -> Weight {
+		(64_361_000 as Weight)
+			.saturating_add(DbWeight::get().reads(30 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_referral_rewards() -> Weight {
+		(53_806_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
 }
@@ -1,16 +1,29 @@
+//! # Incentives Module
+//!
+//! ## Overview
+//!
+//! Incentives module rewards the users who open CDP, provide dex liquidity
+//! or stake by Homa protocol. The shares of the pools and the rewards in
+//! the incentive currency (or the saving currency for DexSaving pools) are
+//! accounted by orml-rewards. Besides, the pools can be rewarded by extra
+//! currencies, donations and the surplus of CDP treasury, following the
+//! emission schedules and lifetimes set by governance.
+
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
 use frame_support::{pallet_prelude::*, traits::Happened, transactional};
 use frame_system::pallet_prelude::*;
 use orml_traits::{MultiCurrency, RewardHandler};
+use orml_utilities::IterableStorageDoubleMapExtended;
 use primitives::{Amount, Balance, CurrencyId};
 use sp_runtime::{
-	traits::{AccountIdConversion, UniqueSaturatedInto, Zero},
-	DispatchResult, FixedPointNumber, ModuleId, RuntimeDebug,
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, CheckedSub, Saturating, UniqueSaturatedInto, Zero},
+	DispatchResult, FixedPointNumber, FixedU128, ModuleId, RuntimeDebug,
 };
 use sp_std::prelude::*;
-use support::{CDPTreasury, DEXIncentives, DEXManager, EmergencyShutdown, Rate};
+use support::{CDPTreasury, DEXIncentives, DEXManager, EmergencyShutdown, Rate, Ratio, VestedTransfer};
 
 mod default_weight;
 mod mock;
@@ -26,8 +39,21 @@ pub trait WeightInfo {
 	fn update_dex_incentive_rewards(c: u32) -> Weight;
 	fn update_homa_incentive_reward() -> Weight;
 	fn update_dex_saving_rates(c: u32) -> Weight;
+	fn update_extra_incentive_rewards(c: u32) -> Weight;
+	fn update_claim_reward_deduction_rates(c: u32) -> Weight;
+	fn update_emission_schedules(c: u32) -> Weight;
+	fn add_rewards() -> Weight;
+	fn compound() -> Weight;
+	fn update_reward_vesting_durations(c: u32) -> Weight;
+	fn update_pool_lifetimes(c: u32) -> Weight;
+	fn update_surplus_reward_rate() -> Weight;
+	fn set_referrer() -> Weight;
+	fn claim_referral_rewards() -> Weight;
 }
 
+/// The max length of the referrer chain walked to detect the referral cycles.
+pub const MAX_REFERRER_CHAIN_DEPTH: u32 = 8;
+
 /// PoolId for various rewards pools
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
 pub enum PoolId {
@@ -42,6 +68,55 @@ pub enum PoolId {
 	Homa,
 }
 
+/// The schedule of the incentive reward per period of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum EmissionSchedule {
+	/// The reward is fixed per period
+	Fixed,
+	/// The reward decreases by `decrement` every period until zero
+	LinearDecay { decrement: Balance },
+	/// The reward halves every `periods` periods
+	Halving { periods: u32 },
+}
+
+/// The donated rewards of a pool to be distributed.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct RewardDonation {
+	/// The remaining amount to distribute
+	pub remaining_amount: Balance,
+	/// The remaining periods to distribute the remaining amount over
+	pub remaining_periods: u32,
+}
+
+/// The lifetime of a pool, the rewards are accumulated to the pool from the
+/// `start` block until the `end` block.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct PoolLifetime<BlockNumber> {
+	/// The block to start accumulating the rewards
+	pub start: BlockNumber,
+	/// The block to stop accumulating the rewards
+	pub end: BlockNumber,
+}
+
+/// The accounting of the extra reward currency of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct ExtraRewardInfo {
+	/// Total rewards accumulated to the pool
+	pub total_rewards: Balance,
+	/// Total rewards withdrawn by the share holders of the pool
+	pub total_withdrawn_rewards: Balance,
+}
+
+/// The progress of cleaning up the reward accounting of an expired pool, with
+/// the raw storage key to continue the iteration from.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum CleanUpCursor {
+	/// Paying out the unvested rewards of the share holders
+	UnvestedRewards(Option<Vec<u8>>),
+	/// Resetting the withdrawn rewards of the share holders
+	Shares(Option<Vec<u8>>),
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -89,9 +164,52 @@ pub mod module {
 		/// DEX to supply liquidity info
 		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
+		/// The price impact limit of the swap with DEX to compound the
+		/// rewards
+		#[pallet::constant]
+		type CompoundPriceImpactLimit: Get<Ratio>;
+
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// The native currency id, the only currency of the rewards which can
+		/// be vested
+		#[pallet::constant]
+		type NativeCurrencyId: Get<CurrencyId>;
+
+		/// Transfer the rewards in the native currency with the vesting
+		/// schedule
+		type VestedTransfer: VestedTransfer<Self::AccountId, Balance, Self::BlockNumber>;
+
+		/// The period after the end of a pool to claim the rewards, the pool
+		/// is expired after it
+		#[pallet::constant]
+		type ClaimGracePeriod: Get<Self::BlockNumber>;
+
+		/// The rate of the claimed incentive rewards of the referred account
+		/// accrued to its referrer as the referral bonus
+		#[pallet::constant]
+		type ReferralRewardRate: Get<Rate>;
+
+		/// The number of the accumulation periods to keep the issued incentive
+		/// rewards of
+		#[pallet::constant]
+		type EmissionHistoryDepth: Get<u32>;
+
+		/// The max number of the periods to distribute a donation over
+		#[pallet::constant]
+		type MaxDonationPeriods: Get<u32>;
+
+		/// The max number of the accounts of the expired pools to clean up per
+		/// block
+		#[pallet::constant]
+		type MaxCleanUpBatch: Get<u32>;
+
+		/// The treasury account to receive the leftover rewards of the expired
+		/// pools
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
 		/// The module id, keep DEXShare LP.
 		#[pallet::constant]
 		type ModuleId: Get<ModuleId>;
@@ -106,6 +224,34 @@ pub mod module {
 		NotEnough,
 		/// Invalid currency id
 		InvalidCurrencyId,
+		/// Invalid rate
+		InvalidRate,
+		/// Invalid pool id
+		InvalidPoolId,
+		/// Invalid emission schedule
+		InvalidEmissionSchedule,
+		/// Invalid amount
+		InvalidAmount,
+		/// Invalid periods
+		InvalidPeriods,
+		/// Invalid pool lifetime
+		InvalidPoolLifetime,
+		/// The pool is expired
+		PoolExpired,
+		/// The referrer is set already
+		ReferrerAlreadySet,
+		/// Invalid referrer, e.g. the caller itself or the referrer referred
+		/// by the caller
+		InvalidReferrer,
+		/// The caller has shares or claimed rewards in the pools already
+		AlreadyParticipated,
+		/// No referral rewards to claim
+		NoReferralRewards,
+		/// The native currency rewards of the pool are vested and can't be
+		/// compounded
+		VestedRewardsNotCompoundable,
+		/// The expired pool is still being cleaned up and can't be re-opened
+		PoolCleaningUp,
 	}
 
 	#[pallet::event]
@@ -115,6 +261,28 @@ pub mod module {
 		DepositDEXShare(T::AccountId, CurrencyId, Balance),
 		/// Withdraw DEX share. \[who, dex_share_type, withdraw_amount\]
 		WithdrawDEXShare(T::AccountId, CurrencyId, Balance),
+		/// Rewards accumulated to the pool. \[pool_id, reward_currency_id,
+		/// amount\]
+		RewardAccumulated(PoolId, CurrencyId, Balance),
+		/// Rewards claimed from the pool. \[who, pool_id, reward_currency_id,
+		/// actual_amount, deduction_amount\]
+		RewardClaimed(T::AccountId, PoolId, CurrencyId, Balance, Balance),
+		/// Rewards donated to the pool, distributed over the remaining periods
+		/// of the merged donation. \[who, pool_id, reward_currency_id, amount,
+		/// periods\]
+		RewardDonated(T::AccountId, PoolId, CurrencyId, Balance, u32),
+		/// Rewards compounded to the liquidity of the pool. \[who, pool_id,
+		/// compounded_amount_a, compounded_amount_b\]
+		RewardCompounded(T::AccountId, PoolId, Balance, Balance),
+		/// The expired pool is cleaned up. \[pool_id\]
+		PoolCleanedUp(PoolId),
+		/// The leftover rewards of the expired pool are returned to the
+		/// treasury. \[pool_id, currency_id, amount\]
+		LeftoverRewardsReturned(PoolId, CurrencyId, Balance),
+		/// Referrer set. \[who, referrer\]
+		ReferrerSet(T::AccountId, T::AccountId),
+		/// Referral rewards claimed. \[referrer, amount\]
+		ReferralRewardsClaimed(T::AccountId, Balance),
 	}
 
 	/// Mapping from collateral currency type to its loans incentive reward
@@ -139,11 +307,139 @@ pub mod module {
 	#[pallet::getter(fn dex_saving_rates)]
 	pub type DEXSavingRates<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, ValueQuery>;
 
+	/// The proportion of the surplus of CDP treasury distributed to the
+	/// DexSaving pools every period
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_reward_rate)]
+	pub type SurplusRewardRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+
+	/// Mapping from pool and extra reward currency type to the extra
+	/// reward amount per period
+	#[pallet::storage]
+	#[pallet::getter(fn extra_incentive_rewards)]
+	pub type ExtraIncentiveRewards<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// Mapping from pool and extra reward currency type to the accounting of
+	/// the extra rewards of the pool
+	#[pallet::storage]
+	#[pallet::getter(fn extra_reward_infos)]
+	pub type ExtraRewardInfos<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, CurrencyId, ExtraRewardInfo, ValueQuery>;
+
+	/// Mapping from pool, share holder and extra reward currency type to the
+	/// extra rewards withdrawn by the share holder
+	#[pallet::storage]
+	#[pallet::getter(fn extra_withdrawn_rewards)]
+	pub type ExtraWithdrawnRewards<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, (PoolId, T::AccountId), Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// Mapping from pool to its claim reward deduction rate
+	#[pallet::storage]
+	#[pallet::getter(fn claim_reward_deduction_rates)]
+	pub type ClaimRewardDeductionRates<T: Config> = StorageMap<_, Twox64Concat, PoolId, Rate, ValueQuery>;
+
+	/// Mapping from pool to the deducted rewards in the incentive currency
+	/// (or the saving currency for DexSaving pools) to be distributed to the
+	/// pool
+	#[pallet::storage]
+	#[pallet::getter(fn pending_deductions)]
+	pub type PendingDeductions<T: Config> = StorageMap<_, Twox64Concat, PoolId, Balance, ValueQuery>;
+
+	/// Mapping from pool to the emission schedule of its incentive reward
+	#[pallet::storage]
+	#[pallet::getter(fn emission_schedules)]
+	pub type EmissionSchedules<T: Config> = StorageMap<_, Twox64Concat, PoolId, EmissionSchedule, OptionQuery>;
+
+	/// Mapping from pool to the number of the periods elapsed since its
+	/// emission schedule is set
+	#[pallet::storage]
+	#[pallet::getter(fn elapsed_emission_periods)]
+	pub type ElapsedEmissionPeriods<T: Config> = StorageMap<_, Twox64Concat, PoolId, u32, ValueQuery>;
+
+	/// Mapping from pool and reward currency type to the donated rewards to
+	/// be distributed
+	#[pallet::storage]
+	#[pallet::getter(fn reward_donations)]
+	pub type RewardDonations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, CurrencyId, RewardDonation, OptionQuery>;
+
+	/// Mapping from pool to the vesting duration of its rewards in the native
+	/// currency, the rewards are transferred liquidly if zero
+	#[pallet::storage]
+	#[pallet::getter(fn reward_vesting_durations)]
+	pub type RewardVestingDurations<T: Config> = StorageMap<_, Twox64Concat, PoolId, T::BlockNumber, ValueQuery>;
+
+	/// Mapping from pool and account to the claimed rewards in the native
+	/// currency which are failed to be vested, they are vested with the next
+	/// claim
+	#[pallet::storage]
+	#[pallet::getter(fn unvested_rewards)]
+	pub type UnvestedRewards<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// Mapping from pool to its lifetime, the pool without the lifetime
+	/// accumulates the rewards forever
+	#[pallet::storage]
+	#[pallet::getter(fn pool_lifetimes)]
+	pub type PoolLifetimes<T: Config> = StorageMap<_, Twox64Concat, PoolId, PoolLifetime<T::BlockNumber>, OptionQuery>;
+
+	/// The expired pools which are cleaned up, until they're re-opened by
+	/// updating the lifetimes
+	#[pallet::storage]
+	#[pallet::getter(fn expired_pools)]
+	pub type ExpiredPools<T: Config> = StorageMap<_, Twox64Concat, PoolId, bool, ValueQuery>;
+
+	/// Mapping from the expired pool being cleaned up to the progress of it
+	#[pallet::storage]
+	#[pallet::getter(fn pool_clean_up_cursors)]
+	pub type PoolCleanUpCursors<T: Config> = StorageMap<_, Twox64Concat, PoolId, CleanUpCursor, OptionQuery>;
+
+	/// Mapping from account to its referrer
+	#[pallet::storage]
+	#[pallet::getter(fn referrers)]
+	pub type Referrers<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// Mapping from referrer to its accrued referral rewards in the incentive
+	/// currency
+	#[pallet::storage]
+	#[pallet::getter(fn referral_rewards)]
+	pub type ReferralRewards<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// Mapping from the block of the accumulation period to the incentive
+	/// rewards issued at it, only kept for the last `EmissionHistoryDepth`
+	/// periods
+	#[pallet::storage]
+	#[pallet::getter(fn incentive_emissions)]
+	pub type IncentiveEmissions<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Balance, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Clean up the reward accounting of the expired pools, at most
+		/// `MaxCleanUpBatch` accounts per block.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let max_batch = T::MaxCleanUpBatch::get();
+			let mut remaining = max_batch;
+			let mut weight = T::DbWeight::get().reads(1);
+			for pool_id in PoolCleanUpCursors::<T>::iter()
+				.map(|(pool_id, _)| pool_id)
+				.collect::<Vec<_>>()
+			{
+				if remaining.is_zero() {
+					break;
+				}
+				remaining = remaining.saturating_sub(Self::clean_up_accounts(pool_id, remaining));
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 3));
+			}
+
+			// paying out the unvested rewards reads and writes up to 4 items
+			let cleaned = max_batch.saturating_sub(remaining) as Weight;
+			weight.saturating_add(T::DbWeight::get().reads_writes(cleaned * 4, cleaned * 4))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -171,11 +467,115 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Claim the rewards of the pool. The deduction rate share of the
+		/// claimed rewards is forfeited back into the pool, and the rewards in
+		/// the native currency are vested if the pool has a vesting duration.
+		/// The rewards can still be claimed in the `ClaimGracePeriod` after
+		/// the end of the pool lifetime.
 		#[pallet::weight(<T as Config>::WeightInfo::claim_rewards())]
 		#[transactional]
 		pub fn claim_rewards(origin: OriginFor<T>, pool_id: T::PoolId) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			<orml_rewards::Module<T>>::claim_rewards(&who, pool_id);
+			ensure!(!Self::is_expired(pool_id), Error::<T>::PoolExpired);
+			Self::do_claim_rewards(&who, pool_id);
+			Ok(().into())
+		}
+
+		/// Record the referrer of the caller, which can be set only once
+		/// before the caller has shares or claimed rewards in any pool. The
+		/// `ReferralRewardRate` of the incentive rewards claimed by the caller
+		/// are accrued to the referrer, which are not deducted from the
+		/// rewards of the caller. The referrer can't be referred by the caller
+		/// within `MAX_REFERRER_CHAIN_DEPTH` referrals.
+		#[pallet::weight(<T as Config>::WeightInfo::set_referrer())]
+		#[transactional]
+		pub fn set_referrer(origin: OriginFor<T>, referrer: T::AccountId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(who != referrer, Error::<T>::InvalidReferrer);
+			ensure!(!Referrers::<T>::contains_key(&who), Error::<T>::ReferrerAlreadySet);
+			ensure!(!Self::has_participated(&who), Error::<T>::AlreadyParticipated);
+
+			// reject the referral cycle, the chain deeper than the max depth is rejected
+			// too as the cycle can't be ruled out
+			let mut ancestor = referrer.clone();
+			let mut depth = 0u32;
+			while let Some(next) = Self::referrers(&ancestor) {
+				depth = depth.saturating_add(1);
+				ensure!(
+					next != who && depth < MAX_REFERRER_CHAIN_DEPTH,
+					Error::<T>::InvalidReferrer
+				);
+				ancestor = next;
+			}
+
+			Referrers::<T>::insert(&who, &referrer);
+			Self::deposit_event(Event::ReferrerSet(who, referrer));
+			Ok(().into())
+		}
+
+		/// Claim the accrued referral rewards of the caller.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_referral_rewards())]
+		#[transactional]
+		pub fn claim_referral_rewards(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let amount = ReferralRewards::<T>::take(&who);
+			ensure!(!amount.is_zero(), Error::<T>::NoReferralRewards);
+
+			// TODO: transfer from RESERVED TREASURY instead of issuing
+			T::Currency::deposit(T::IncentiveCurrencyId::get(), &who, amount)?;
+			Self::deposit_event(Event::ReferralRewardsClaimed(who, amount));
+			Ok(().into())
+		}
+
+		/// Donate `amount` of `currency_id` to the pool, e.g. a partner
+		/// project co-incentivizing the pool, which is distributed over
+		/// `periods` periods in which the pool has shares, at most
+		/// `MaxDonationPeriods`. The donation is merged with the remaining
+		/// donation of the same currency, and distributed over its remaining
+		/// periods, which are never extended by the later donations.
+		#[pallet::weight(<T as Config>::WeightInfo::add_rewards())]
+		#[transactional]
+		pub fn add_rewards(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			periods: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			ensure!(
+				!periods.is_zero() && periods <= T::MaxDonationPeriods::get(),
+				Error::<T>::InvalidPeriods
+			);
+
+			T::Currency::transfer(currency_id, &who, &Self::pool_account(pool_id), amount)?;
+			let periods = RewardDonations::<T>::mutate(pool_id, currency_id, |maybe_donation| {
+				let donation = maybe_donation.get_or_insert(RewardDonation {
+					remaining_amount: Zero::zero(),
+					remaining_periods: periods,
+				});
+				donation.remaining_amount = donation.remaining_amount.saturating_add(amount);
+				donation.remaining_periods
+			});
+
+			Self::deposit_event(Event::RewardDonated(who, pool_id, currency_id, amount, periods));
+			Ok(().into())
+		}
+
+		/// Claim the rewards of the DexIncentive or DexSaving pool, and add
+		/// the rewards in the currencies of the trading pair, swapped with DEX
+		/// if needed, to the liquidity and deposit the share increment to the
+		/// pool. It's rejected if the pool vests the rewards in the native
+		/// currency and the trading pair contains it, as the vested rewards are
+		/// locked.
+		#[pallet::weight(<T as Config>::WeightInfo::compound())]
+		#[transactional]
+		pub fn compound(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_expired(pool_id), Error::<T>::PoolExpired);
+			Self::do_compound(&who, pool_id)?;
 			Ok(().into())
 		}
 
@@ -227,6 +627,137 @@ pub mod module {
 			}
 			Ok(().into())
 		}
+
+		/// Update the proportion of the surplus of CDP treasury distributed
+		/// every period to the DexSaving pools of the trading pairs with the
+		/// saving currency, by the saving currency amount of their liquidity
+		/// pools.
+		#[pallet::weight(<T as Config>::WeightInfo::update_surplus_reward_rate())]
+		#[transactional]
+		pub fn update_surplus_reward_rate(origin: OriginFor<T>, rate: Rate) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(rate <= Rate::one(), Error::<T>::InvalidRate);
+			SurplusRewardRate::<T>::put(rate);
+			Ok(().into())
+		}
+
+		/// Update the extra rewards per period of the pools in the extra
+		/// reward currencies, e.g. a partner token besides ACA. The extra
+		/// rewards are transferred from the module account, which should be
+		/// funded in advance, to the vault account of the pool every
+		/// `AccumulatePeriod` blocks.
+		#[pallet::weight(<T as Config>::WeightInfo::update_extra_incentive_rewards(updates.len() as u32))]
+		#[transactional]
+		pub fn update_extra_incentive_rewards(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId, CurrencyId, Balance)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, currency_id, amount) in updates {
+				ensure!(!currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+				if amount.is_zero() {
+					ExtraIncentiveRewards::<T>::remove(pool_id, currency_id);
+				} else {
+					ExtraIncentiveRewards::<T>::insert(pool_id, currency_id, amount);
+				}
+			}
+			Ok(().into())
+		}
+
+		/// Update the claim deduction rates of the pools to reward the loyal
+		/// share holders, the deducted rewards are distributed to all the share
+		/// holders of the pool.
+		#[pallet::weight(<T as Config>::WeightInfo::update_claim_reward_deduction_rates(updates.len() as u32))]
+		#[transactional]
+		pub fn update_claim_reward_deduction_rates(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId, Rate)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, rate) in updates {
+				ensure!(rate <= Rate::one(), Error::<T>::InvalidRate);
+				if rate.is_zero() {
+					ClaimRewardDeductionRates::<T>::remove(pool_id);
+				} else {
+					ClaimRewardDeductionRates::<T>::insert(pool_id, rate);
+				}
+			}
+			Ok(().into())
+		}
+
+		/// Update the durations over which the rewards in the native currency
+		/// of the pools are vested linearly, zero means the liquid transfer.
+		#[pallet::weight(<T as Config>::WeightInfo::update_reward_vesting_durations(updates.len() as u32))]
+		#[transactional]
+		pub fn update_reward_vesting_durations(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId, T::BlockNumber)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, duration) in updates {
+				if duration.is_zero() {
+					RewardVestingDurations::<T>::remove(pool_id);
+				} else {
+					RewardVestingDurations::<T>::insert(pool_id, duration);
+				}
+			}
+			Ok(().into())
+		}
+
+		/// Set the lifetimes of the pools by the start and end blocks, or
+		/// remove the lifetimes by `None`. The rewards are only accumulated to
+		/// the pool during the lifetime, and the pool is expired and cleaned up
+		/// after the `ClaimGracePeriod` since the end. Updating the lifetime of
+		/// the expired pool re-opens it with the fresh reward accounting once
+		/// it's cleaned up, its reward configs need to be set again.
+		#[pallet::weight(<T as Config>::WeightInfo::update_pool_lifetimes(updates.len() as u32))]
+		#[transactional]
+		pub fn update_pool_lifetimes(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId, Option<(T::BlockNumber, T::BlockNumber)>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, lifetime) in updates {
+				ensure!(
+					!PoolCleanUpCursors::<T>::contains_key(pool_id),
+					Error::<T>::PoolCleaningUp
+				);
+				ExpiredPools::<T>::remove(pool_id);
+				match lifetime {
+					Some((start, end)) => {
+						ensure!(start < end, Error::<T>::InvalidPoolLifetime);
+						PoolLifetimes::<T>::insert(pool_id, PoolLifetime { start, end });
+					}
+					None => PoolLifetimes::<T>::remove(pool_id),
+				}
+			}
+			Ok(().into())
+		}
+
+		/// Set the emission schedules of the incentive rewards of the pools,
+		/// e.g. decaying linearly or halving every few periods, or remove the
+		/// schedules by `None`. The schedule is applied to the reward at each
+		/// period rollover.
+		#[pallet::weight(<T as Config>::WeightInfo::update_emission_schedules(updates.len() as u32))]
+		#[transactional]
+		pub fn update_emission_schedules(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId, Option<EmissionSchedule>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, schedule) in updates {
+				// the reward of DexSaving pools is determined by the saving rate
+				ensure!(!matches!(pool_id, PoolId::DexSaving(_)), Error::<T>::InvalidPoolId);
+				ensure!(
+					!matches!(schedule, Some(EmissionSchedule::Halving { periods: 0 })),
+					Error::<T>::InvalidEmissionSchedule
+				);
+
+				EmissionSchedules::<T>::mutate_exists(pool_id, |maybe_schedule| *maybe_schedule = schedule);
+				ElapsedEmissionPeriods::<T>::remove(pool_id);
+			}
+			Ok(().into())
+		}
 	}
 }
 
@@ -234,6 +765,571 @@ impl<T: Config> Pallet<T> {
 	pub fn account_id() -> T::AccountId {
 		T::ModuleId::get().into_account()
 	}
+
+	/// The vault account to keep the rewards of the pool
+	fn pool_account(pool_id: PoolId) -> T::AccountId {
+		match pool_id {
+			PoolId::Loans(_) => T::LoansIncentivePool::get(),
+			PoolId::DexIncentive(_) | PoolId::DexSaving(_) => T::DexIncentivePool::get(),
+			PoolId::Homa => T::HomaIncentivePool::get(),
+		}
+	}
+
+	/// The reward currency accounted by orml-rewards of the pool
+	fn reward_currency_id(pool_id: PoolId) -> CurrencyId {
+		match pool_id {
+			PoolId::DexSaving(_) => T::SavingCurrencyId::get(),
+			_ => T::IncentiveCurrencyId::get(),
+		}
+	}
+
+	/// Split the claimed reward of the pool to the actual reward and the
+	/// deduction.
+	fn split_deduction(pool_id: PoolId, reward: Balance) -> (Balance, Balance) {
+		let deduction = Self::claim_reward_deduction_rates(pool_id)
+			.saturating_mul_int(reward)
+			.min(reward);
+		(reward.saturating_sub(deduction), deduction)
+	}
+
+	/// The amount of the saving currency in the liquidity pool of the DEX
+	/// share, zero if the trading pair is not of the saving currency.
+	fn saving_currency_amount(lp_currency_id: CurrencyId) -> Balance {
+		let saving_currency_id = T::SavingCurrencyId::get();
+		match lp_currency_id.split_dex_share_currency_id() {
+			Some((currency_id_a, currency_id_b)) if currency_id_a == saving_currency_id => {
+				T::DEX::get_liquidity_pool(saving_currency_id, currency_id_b).0
+			}
+			Some((currency_id_a, currency_id_b)) if currency_id_b == saving_currency_id => {
+				T::DEX::get_liquidity_pool(saving_currency_id, currency_id_a).0
+			}
+			_ => Zero::zero(),
+		}
+	}
+
+	/// Whether the rewards are accumulated to the pool at `now`.
+	fn is_active(pool_id: PoolId, now: T::BlockNumber) -> bool {
+		if Self::expired_pools(pool_id) {
+			return false;
+		}
+		Self::pool_lifetimes(pool_id).map_or(true, |lifetime| lifetime.start <= now && now < lifetime.end)
+	}
+
+	/// Whether the claim grace period after the end of the pool is passed.
+	pub fn is_expired(pool_id: PoolId) -> bool {
+		Self::expired_pools(pool_id)
+			|| Self::pool_lifetimes(pool_id).map_or(false, |lifetime| {
+				<frame_system::Module<T>>::block_number() >= lifetime.end.saturating_add(T::ClaimGracePeriod::get())
+			})
+	}
+
+	/// Start cleaning up the expired pool. The reward configs are removed and
+	/// the leftover rewards are returned to the treasury, the reward
+	/// accounting of the share holders is cleaned up in batches by
+	/// `on_initialize`.
+	fn clean_up_pool(pool_id: PoolId) {
+		match pool_id {
+			PoolId::Loans(currency_id) => LoansIncentiveRewards::<T>::remove(currency_id),
+			PoolId::DexIncentive(currency_id) => DEXIncentiveRewards::<T>::remove(currency_id),
+			PoolId::DexSaving(currency_id) => DEXSavingRates::<T>::remove(currency_id),
+			PoolId::Homa => HomaIncentiveReward::<T>::kill(),
+		}
+		ExtraIncentiveRewards::<T>::remove_prefix(pool_id);
+		ClaimRewardDeductionRates::<T>::remove(pool_id);
+		EmissionSchedules::<T>::remove(pool_id);
+		ElapsedEmissionPeriods::<T>::remove(pool_id);
+		PoolLifetimes::<T>::remove(pool_id);
+
+		// the rewards not withdrawn by the share holders, the unvested rewards are
+		// withdrawn already and paid out by the batches.
+		let mut leftovers = vec![(Self::reward_currency_id(pool_id), PendingDeductions::<T>::take(pool_id))];
+		if orml_rewards::Pools::<T>::contains_key(pool_id) {
+			orml_rewards::Pools::<T>::mutate(pool_id, |pool_info| {
+				leftovers[0].1 = leftovers[0].1.saturating_add(
+					pool_info
+						.total_rewards
+						.saturating_sub(pool_info.total_withdrawn_rewards),
+				);
+				pool_info.total_rewards = Zero::zero();
+				pool_info.total_withdrawn_rewards = Zero::zero();
+			});
+		}
+		for (currency_id, info) in ExtraRewardInfos::<T>::drain_prefix(pool_id) {
+			leftovers.push((
+				currency_id,
+				info.total_rewards.saturating_sub(info.total_withdrawn_rewards),
+			));
+		}
+		for (currency_id, donation) in RewardDonations::<T>::drain_prefix(pool_id) {
+			leftovers.push((currency_id, donation.remaining_amount));
+		}
+
+		// the vault account may be shared by the pools, never return more than it
+		// keeps.
+		let pool_account = Self::pool_account(pool_id);
+		for (currency_id, amount) in leftovers {
+			let amount = amount.min(T::Currency::free_balance(currency_id, &pool_account));
+			if !amount.is_zero()
+				&& T::Currency::transfer(currency_id, &pool_account, &T::TreasuryAccount::get(), amount).is_ok()
+			{
+				Self::deposit_event(Event::LeftoverRewardsReturned(pool_id, currency_id, amount));
+			}
+		}
+
+		ExpiredPools::<T>::insert(pool_id, true);
+		PoolCleanUpCursors::<T>::insert(pool_id, CleanUpCursor::UnvestedRewards(None));
+	}
+
+	/// Clean up the accounts of the expired pool from its cursor, at most
+	/// `limit` accounts, return the number of the cleaned up accounts. The
+	/// unvested rewards are paid out first, then the withdrawn rewards of the
+	/// share holders are reset, the shares are kept as they're backed by the
+	/// positions.
+	fn clean_up_accounts(pool_id: PoolId, limit: u32) -> u32 {
+		let mut cursor = match Self::pool_clean_up_cursors(pool_id) {
+			Some(cursor) => cursor,
+			None => return 0,
+		};
+		let mut cleaned: u32 = 0;
+
+		if let CleanUpCursor::UnvestedRewards(start_key) = cursor {
+			let vesting_duration = Self::reward_vesting_durations(pool_id);
+			let mut iterator = <UnvestedRewards<T> as IterableStorageDoubleMapExtended<_, _, _>>::iter_prefix(
+				pool_id,
+				Some(limit),
+				start_key,
+			);
+			while let Some((who, amount)) = iterator.next() {
+				Self::payout_unvested_rewards(&who, pool_id, amount, vesting_duration);
+				cleaned += 1;
+			}
+			cursor = if iterator.finished {
+				CleanUpCursor::Shares(None)
+			} else {
+				CleanUpCursor::UnvestedRewards(Some(iterator.map_iterator.previous_key))
+			};
+		}
+
+		if let CleanUpCursor::Shares(start_key) = cursor.clone() {
+			if cleaned < limit {
+				let mut iterator = <orml_rewards::ShareAndWithdrawnReward<T> as IterableStorageDoubleMapExtended<
+					_,
+					_,
+					_,
+				>>::iter_prefix(pool_id, Some(limit - cleaned), start_key);
+				while let Some((who, (share, _))) = iterator.next() {
+					orml_rewards::ShareAndWithdrawnReward::<T>::insert(pool_id, &who, (share, Balance::zero()));
+					ExtraWithdrawnRewards::<T>::remove_prefix((pool_id, who));
+					cleaned += 1;
+				}
+				if iterator.finished {
+					PoolCleanUpCursors::<T>::remove(pool_id);
+					RewardVestingDurations::<T>::remove(pool_id);
+					Self::deposit_event(Event::PoolCleanedUp(pool_id));
+					return cleaned;
+				}
+				cursor = CleanUpCursor::Shares(Some(iterator.map_iterator.previous_key));
+			}
+		}
+
+		PoolCleanUpCursors::<T>::insert(pool_id, cursor);
+		cleaned
+	}
+
+	/// Pay out the unvested rewards of the expired pool, liquidly if they
+	/// still fail to be vested. They're kept if they can't be paid out.
+	fn payout_unvested_rewards(who: &T::AccountId, pool_id: PoolId, amount: Balance, vesting_duration: T::BlockNumber) {
+		let pool_account = Self::pool_account(pool_id);
+		let currency_id = T::NativeCurrencyId::get();
+		let paid = (!vesting_duration.is_zero()
+			&& T::VestedTransfer::vested_transfer(&pool_account, who, amount, vesting_duration).is_ok())
+			|| T::Currency::transfer(currency_id, &pool_account, who, amount).is_ok();
+		if paid {
+			UnvestedRewards::<T>::remove(pool_id, who);
+			Self::deposit_event(Event::RewardClaimed(
+				who.clone(),
+				pool_id,
+				currency_id,
+				amount,
+				Zero::zero(),
+			));
+			if currency_id == T::IncentiveCurrencyId::get() {
+				Self::accrue_referral_reward(who, amount);
+			}
+		}
+	}
+
+	/// Payout the reward to user from the pool after the deduction, return the
+	/// deduction which is kept in the pool.
+	fn payout_reward(who: &T::AccountId, pool_id: PoolId, currency_id: CurrencyId, amount: Balance) -> Balance {
+		// the rewards of the expired pool are not paid out anymore
+		if Self::is_expired(pool_id) {
+			return Zero::zero();
+		}
+
+		let (actual_amount, deduction) = Self::split_deduction(pool_id, amount);
+
+		let vesting_duration = Self::reward_vesting_durations(pool_id);
+		let (actual_amount, paid) = if !vesting_duration.is_zero() && currency_id == T::NativeCurrencyId::get() {
+			let actual_amount = actual_amount.saturating_add(UnvestedRewards::<T>::take(pool_id, who));
+			if actual_amount.is_zero() {
+				return deduction;
+			}
+
+			// vest the reward from the pool, keep it to be vested with the next claim if
+			// it fails.
+			let vested =
+				T::VestedTransfer::vested_transfer(&Self::pool_account(pool_id), who, actual_amount, vesting_duration)
+					.is_ok();
+			if !vested {
+				UnvestedRewards::<T>::insert(pool_id, who, actual_amount);
+			}
+			(actual_amount, vested)
+		} else {
+			// payout the reward to user from the pool. it should not affect the
+			// process, ignore the result to continue. if it fails, just the user will not
+			// be rewarded, there will not increase user balance.
+			let paid = T::Currency::transfer(currency_id, &Self::pool_account(pool_id), &who, actual_amount).is_ok();
+			(actual_amount, paid)
+		};
+
+		if paid {
+			Self::deposit_event(Event::RewardClaimed(
+				who.clone(),
+				pool_id,
+				currency_id,
+				actual_amount,
+				deduction,
+			));
+			if currency_id == T::IncentiveCurrencyId::get() {
+				Self::accrue_referral_reward(who, actual_amount);
+			}
+		}
+		deduction
+	}
+
+	/// Accrue the referral bonus of the incentive reward claimed by `who` to
+	/// the referrer of `who`, the bonus is not deducted from the reward.
+	fn accrue_referral_reward(who: &T::AccountId, reward: Balance) {
+		if let Some(referrer) = Self::referrers(who) {
+			let bonus = T::ReferralRewardRate::get().saturating_mul_int(reward);
+			if !bonus.is_zero() {
+				ReferralRewards::<T>::mutate(&referrer, |rewards| *rewards = rewards.saturating_add(bonus));
+			}
+		}
+	}
+
+	/// The pending rewards of `who` in the pool for each reward currency, the
+	/// actual rewards to receive and the deductions if claimed now.
+	pub fn pending_rewards(pool_id: PoolId, who: &T::AccountId) -> Vec<(CurrencyId, Balance, Balance)> {
+		let (share, withdrawn) = <orml_rewards::Module<T>>::share_and_withdrawn_reward(pool_id, who);
+		if share.is_zero() {
+			return vec![];
+		}
+
+		let pool_info = <orml_rewards::Module<T>>::pools(pool_id);
+		let reward = FixedU128::checked_from_rational(share, pool_info.total_shares)
+			.unwrap_or_default()
+			.saturating_mul_int(pool_info.total_rewards)
+			.saturating_sub(withdrawn)
+			.min(
+				pool_info
+					.total_rewards
+					.saturating_sub(pool_info.total_withdrawn_rewards),
+			);
+
+		let mut pending_rewards = vec![(Self::reward_currency_id(pool_id), reward)];
+		for (currency_id, info) in ExtraRewardInfos::<T>::iter_prefix(pool_id) {
+			pending_rewards.push((
+				currency_id,
+				Self::pending_extra_reward(who, pool_id, currency_id, &info, share, pool_info.total_shares),
+			));
+		}
+
+		pending_rewards
+			.into_iter()
+			.filter(|(_, reward)| !reward.is_zero())
+			.map(|(currency_id, reward)| {
+				let (actual_amount, deduction) = Self::split_deduction(pool_id, reward);
+				(currency_id, actual_amount, deduction)
+			})
+			.collect()
+	}
+
+	/// Whether `who` has shares or unvested claimed rewards in any pool.
+	fn has_participated(who: &T::AccountId) -> bool {
+		orml_rewards::Pools::<T>::iter_keys().any(|pool_id| {
+			orml_rewards::ShareAndWithdrawnReward::<T>::contains_key(pool_id, who)
+				|| UnvestedRewards::<T>::contains_key(pool_id, who)
+		})
+	}
+
+	/// The pools `who` participates in, with the share of `who`, the pending
+	/// rewards and the claim reward deduction rate of each pool.
+	#[allow(clippy::type_complexity)]
+	pub fn participated_pools(who: &T::AccountId) -> Vec<(PoolId, Balance, Vec<(CurrencyId, Balance, Balance)>, Rate)> {
+		orml_rewards::Pools::<T>::iter()
+			.filter_map(|(pool_id, _)| {
+				let (share, _) = <orml_rewards::Module<T>>::share_and_withdrawn_reward(pool_id, who);
+				if share.is_zero() {
+					return None;
+				}
+				Some((
+					pool_id,
+					share,
+					Self::pending_rewards(pool_id, who),
+					Self::claim_reward_deduction_rates(pool_id),
+				))
+			})
+			.collect()
+	}
+
+	/// The incentive rewards issued at the last `periods` accumulation periods
+	/// up to `now`, the latest first. The periods without the issued incentive
+	/// rewards are skipped.
+	pub fn last_incentive_emissions(now: T::BlockNumber, periods: u32) -> Vec<(T::BlockNumber, Balance)> {
+		let period = T::AccumulatePeriod::get();
+		let last = now - now % period;
+		(0..periods.min(T::EmissionHistoryDepth::get()))
+			.filter_map(|index| {
+				let at = last.checked_sub(&period.saturating_mul(index.into()))?;
+				Self::incentive_emissions(at).map(|amount| (at, amount))
+			})
+			.collect()
+	}
+
+	/// Add share to the pool, the rewards accumulated before are inflated
+	/// for the added share so that the share holder can't claim them.
+	fn add_share(who: &T::AccountId, pool_id: PoolId, amount: Balance) {
+		if amount.is_zero() {
+			return;
+		}
+
+		let total_shares = <orml_rewards::Module<T>>::pools(pool_id).total_shares;
+		if !total_shares.is_zero() {
+			for (currency_id, mut info) in ExtraRewardInfos::<T>::iter_prefix(pool_id).collect::<Vec<_>>() {
+				let reward_inflation =
+					multiply_by_rational(info.total_rewards, amount, total_shares).unwrap_or_default();
+				info.total_rewards = info.total_rewards.saturating_add(reward_inflation);
+				info.total_withdrawn_rewards = info.total_withdrawn_rewards.saturating_add(reward_inflation);
+				ExtraRewardInfos::<T>::insert(pool_id, currency_id, info);
+				ExtraWithdrawnRewards::<T>::mutate((pool_id, who.clone()), currency_id, |withdrawn| {
+					*withdrawn = withdrawn.saturating_add(reward_inflation)
+				});
+			}
+		}
+
+		<orml_rewards::Module<T>>::add_share(who, pool_id, amount);
+	}
+
+	/// Remove share from the pool, the rewards are claimed firstly.
+	fn remove_share(who: &T::AccountId, pool_id: PoolId, amount: Balance) {
+		if amount.is_zero() {
+			return;
+		}
+
+		Self::claim_extra_rewards(who, pool_id);
+		let share = <orml_rewards::Module<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		let remove_amount = amount.min(share);
+		if !remove_amount.is_zero() {
+			let total_shares = <orml_rewards::Module<T>>::pools(pool_id).total_shares;
+			for (currency_id, mut info) in ExtraRewardInfos::<T>::iter_prefix(pool_id).collect::<Vec<_>>() {
+				let withdrawn = ExtraWithdrawnRewards::<T>::take((pool_id, who.clone()), currency_id);
+				let withdrawn_to_remove = multiply_by_rational(withdrawn, remove_amount, share).unwrap_or_default();
+				info.total_rewards = info.total_rewards.saturating_sub(withdrawn_to_remove);
+				info.total_withdrawn_rewards = info.total_withdrawn_rewards.saturating_sub(withdrawn_to_remove);
+
+				// the pool is removed as orml-rewards does if there's no share remaining
+				if remove_amount < total_shares {
+					ExtraRewardInfos::<T>::insert(pool_id, currency_id, info);
+				} else {
+					ExtraRewardInfos::<T>::remove(pool_id, currency_id);
+				}
+				if remove_amount < share {
+					ExtraWithdrawnRewards::<T>::insert(
+						(pool_id, who.clone()),
+						currency_id,
+						withdrawn.saturating_sub(withdrawn_to_remove),
+					);
+				}
+			}
+		}
+
+		<orml_rewards::Module<T>>::remove_share(who, pool_id, amount);
+	}
+
+	fn set_share(who: &T::AccountId, pool_id: PoolId, new_share: Balance) {
+		let share = <orml_rewards::Module<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		if new_share > share {
+			Self::add_share(who, pool_id, new_share.saturating_sub(share));
+		} else {
+			Self::remove_share(who, pool_id, share.saturating_sub(new_share));
+		}
+	}
+
+	fn do_compound(who: &T::AccountId, pool_id: PoolId) -> DispatchResult {
+		let lp_currency_id = match pool_id {
+			PoolId::DexIncentive(currency_id) | PoolId::DexSaving(currency_id) => currency_id,
+			_ => return Err(Error::<T>::InvalidPoolId.into()),
+		};
+		let (currency_id_a, currency_id_b) = lp_currency_id
+			.split_dex_share_currency_id()
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		let native_currency_id = T::NativeCurrencyId::get();
+		ensure!(
+			Self::reward_vesting_durations(pool_id).is_zero()
+				|| (currency_id_a != native_currency_id && currency_id_b != native_currency_id),
+			Error::<T>::VestedRewardsNotCompoundable
+		);
+
+		let balance_a = T::Currency::free_balance(currency_id_a, who);
+		let balance_b = T::Currency::free_balance(currency_id_b, who);
+		Self::do_claim_rewards(who, pool_id);
+		let mut amount_a = T::Currency::free_balance(currency_id_a, who).saturating_sub(balance_a);
+		let mut amount_b = T::Currency::free_balance(currency_id_b, who).saturating_sub(balance_b);
+
+		// the rewards are in only one currency of the trading pair, swap half of them to
+		// the other currency
+		let price_impact_limit = Some(T::CompoundPriceImpactLimit::get());
+		if amount_b.is_zero() && !amount_a.is_zero() {
+			let supply_amount = amount_a / 2;
+			amount_b = T::DEX::swap_with_exact_supply(
+				who,
+				&[currency_id_a, currency_id_b],
+				supply_amount,
+				Zero::zero(),
+				price_impact_limit,
+			)?;
+			amount_a = amount_a.saturating_sub(supply_amount);
+		} else if amount_a.is_zero() && !amount_b.is_zero() {
+			let supply_amount = amount_b / 2;
+			amount_a = T::DEX::swap_with_exact_supply(
+				who,
+				&[currency_id_b, currency_id_a],
+				supply_amount,
+				Zero::zero(),
+				price_impact_limit,
+			)?;
+			amount_b = amount_b.saturating_sub(supply_amount);
+		}
+
+		if !amount_a.is_zero() && !amount_b.is_zero() {
+			T::DEX::add_liquidity(who, currency_id_a, currency_id_b, amount_a, amount_b, true)?;
+			Self::deposit_event(Event::RewardCompounded(who.clone(), pool_id, amount_a, amount_b));
+		}
+		Ok(())
+	}
+
+	/// Claim the rewards of all the reward currencies of the pool.
+	pub fn do_claim_rewards(who: &T::AccountId, pool_id: PoolId) {
+		<orml_rewards::Module<T>>::claim_rewards(who, pool_id);
+		Self::claim_extra_rewards(who, pool_id);
+	}
+
+	fn claim_extra_rewards(who: &T::AccountId, pool_id: PoolId) {
+		let share = <orml_rewards::Module<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		if share.is_zero() {
+			return;
+		}
+
+		let total_shares = <orml_rewards::Module<T>>::pools(pool_id).total_shares;
+		for (currency_id, mut info) in ExtraRewardInfos::<T>::iter_prefix(pool_id).collect::<Vec<_>>() {
+			let reward_to_withdraw = Self::pending_extra_reward(who, pool_id, currency_id, &info, share, total_shares);
+			if reward_to_withdraw.is_zero() {
+				continue;
+			}
+
+			ExtraWithdrawnRewards::<T>::mutate((pool_id, who.clone()), currency_id, |withdrawn| {
+				*withdrawn = withdrawn.saturating_add(reward_to_withdraw)
+			});
+			let deduction = Self::payout_reward(who, pool_id, currency_id, reward_to_withdraw);
+
+			// the deduction is distributed to the pool at once
+			info.total_withdrawn_rewards = info.total_withdrawn_rewards.saturating_add(reward_to_withdraw);
+			info.total_rewards = info.total_rewards.saturating_add(deduction);
+			ExtraRewardInfos::<T>::insert(pool_id, currency_id, info);
+		}
+	}
+
+	fn pending_extra_reward(
+		who: &T::AccountId,
+		pool_id: PoolId,
+		currency_id: CurrencyId,
+		info: &ExtraRewardInfo,
+		share: Balance,
+		total_shares: Balance,
+	) -> Balance {
+		multiply_by_rational(info.total_rewards, share, total_shares)
+			.unwrap_or_default()
+			.saturating_sub(Self::extra_withdrawn_rewards((pool_id, who.clone()), currency_id))
+			.min(info.total_rewards.saturating_sub(info.total_withdrawn_rewards))
+	}
+
+	/// Update the incentive rewards per period of the pools by their emission
+	/// schedules at the period rollover.
+	fn apply_emission_schedules() {
+		for (pool_id, schedule) in EmissionSchedules::<T>::iter() {
+			let elapsed_periods = ElapsedEmissionPeriods::<T>::mutate(pool_id, |elapsed_periods| {
+				*elapsed_periods = elapsed_periods.saturating_add(1);
+				*elapsed_periods
+			});
+			let next_reward = |reward: &mut Balance| match schedule {
+				EmissionSchedule::Fixed => {}
+				EmissionSchedule::LinearDecay { decrement } => *reward = reward.saturating_sub(decrement),
+				EmissionSchedule::Halving { periods } => {
+					if elapsed_periods % periods.max(1) == 0 {
+						*reward /= 2;
+					}
+				}
+			};
+
+			match pool_id {
+				PoolId::Loans(currency_id) => LoansIncentiveRewards::<T>::mutate(currency_id, next_reward),
+				PoolId::DexIncentive(currency_id) => DEXIncentiveRewards::<T>::mutate(currency_id, next_reward),
+				PoolId::Homa => HomaIncentiveReward::<T>::mutate(next_reward),
+				PoolId::DexSaving(_) => {}
+			}
+		}
+	}
+
+	/// Take the donated rewards of the pool to distribute for this period.
+	fn take_donated_rewards(pool_id: PoolId) -> Vec<(CurrencyId, Balance)> {
+		let mut donated_rewards: Vec<(CurrencyId, Balance)> = vec![];
+		for (currency_id, mut donation) in RewardDonations::<T>::iter_prefix(pool_id).collect::<Vec<_>>() {
+			let reward = donation.remaining_amount / Balance::from(donation.remaining_periods.max(1));
+			donation.remaining_amount = donation.remaining_amount.saturating_sub(reward);
+			donation.remaining_periods = donation.remaining_periods.saturating_sub(1);
+
+			if donation.remaining_periods.is_zero() {
+				RewardDonations::<T>::remove(pool_id, currency_id);
+			} else {
+				RewardDonations::<T>::insert(pool_id, currency_id, donation);
+			}
+			if !reward.is_zero() {
+				donated_rewards.push((currency_id, reward));
+			}
+		}
+		donated_rewards
+	}
+
+	/// Accumulate the extra rewards of the pool for this period, return the
+	/// accumulated extra rewards.
+	fn accumulate_extra_rewards(pool_id: PoolId) -> Vec<(CurrencyId, Balance)> {
+		let mut accumulated_rewards: Vec<(CurrencyId, Balance)> = vec![];
+		for (currency_id, reward) in ExtraIncentiveRewards::<T>::iter_prefix(pool_id) {
+			// the extra rewards are transferred from the module account rather than issued
+			if !reward.is_zero()
+				&& T::Currency::transfer(currency_id, &Self::account_id(), &Self::pool_account(pool_id), reward).is_ok()
+			{
+				ExtraRewardInfos::<T>::mutate(pool_id, currency_id, |info| {
+					info.total_rewards = info.total_rewards.saturating_add(reward)
+				});
+				Self::deposit_event(Event::RewardAccumulated(pool_id, currency_id, reward));
+				accumulated_rewards.push((currency_id, reward));
+			}
+		}
+		accumulated_rewards
+	}
 }
 
 impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
@@ -241,12 +1337,12 @@ impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 		ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
 
 		T::Currency::transfer(lp_currency_id, who, &Self::account_id(), amount)?;
-		<orml_rewards::Module<T>>::add_share(
+		Self::add_share(
 			who,
 			PoolId::DexIncentive(lp_currency_id),
 			amount.unique_saturated_into(),
 		);
-		<orml_rewards::Module<T>>::add_share(who, PoolId::DexSaving(lp_currency_id), amount.unique_saturated_into());
+		Self::add_share(who, PoolId::DexSaving(lp_currency_id), amount.unique_saturated_into());
 
 		Self::deposit_event(Event::DepositDEXShare(who.clone(), lp_currency_id, amount));
 		Ok(())
@@ -262,12 +1358,12 @@ impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 		);
 
 		T::Currency::transfer(lp_currency_id, &Self::account_id(), &who, amount)?;
-		<orml_rewards::Module<T>>::remove_share(
+		Self::remove_share(
 			who,
 			PoolId::DexIncentive(lp_currency_id),
 			amount.unique_saturated_into(),
 		);
-		<orml_rewards::Module<T>>::remove_share(who, PoolId::DexSaving(lp_currency_id), amount.unique_saturated_into());
+		Self::remove_share(who, PoolId::DexSaving(lp_currency_id), amount.unique_saturated_into());
 
 		Self::deposit_event(Event::WithdrawDEXShare(who.clone(), lp_currency_id, amount));
 		Ok(())
@@ -288,7 +1384,7 @@ impl<T: Config> Happened<(T::AccountId, CurrencyId, Amount, Balance)> for OnUpda
 				previous_amount.saturating_sub(adjustment_abs)
 			};
 
-			<orml_rewards::Module<T>>::set_share(who, PoolId::Loans(*currency_id), new_share_amount);
+			Pallet::<T>::set_share(who, PoolId::Loans(*currency_id), new_share_amount);
 		}
 	}
 }
@@ -302,14 +1398,21 @@ impl<T: Config> RewardHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
 	fn accumulate_reward(now: T::BlockNumber, mut callback: impl FnMut(PoolId, Balance)) -> Vec<(CurrencyId, Balance)> {
 		let mut accumulated_rewards: Vec<(CurrencyId, Balance)> = vec![];
 
+		// distribute the deductions of the claimed rewards to the pools
+		for (pool_id, deduction) in PendingDeductions::<T>::drain() {
+			callback(pool_id, deduction);
+		}
+
 		if !T::EmergencyShutdown::is_shutdown() && now % T::AccumulatePeriod::get() == Zero::zero() {
 			let mut accumulated_incentive: Balance = Zero::zero();
 			let mut accumulated_saving: Balance = Zero::zero();
+			let mut accumulated_extra: Vec<(CurrencyId, Balance)> = vec![];
+			let mut saving_pools: Vec<(PoolId, Balance)> = vec![];
 			let incentive_currency_id = T::IncentiveCurrencyId::get();
 			let saving_currency_id = T::SavingCurrencyId::get();
 
 			for (pool_id, pool_info) in orml_rewards::Pools::<T>::iter() {
-				if !pool_info.total_shares.is_zero() {
+				if !pool_info.total_shares.is_zero() && Self::is_active(pool_id, now) {
 					match pool_id {
 						PoolId::Loans(currency_id) => {
 							let incentive_reward = Self::loans_incentive_rewards(currency_id);
@@ -324,6 +1427,11 @@ impl<T: Config> RewardHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
 								.is_ok()
 							{
 								callback(pool_id, incentive_reward);
+								Self::deposit_event(Event::RewardAccumulated(
+									pool_id,
+									incentive_currency_id,
+									incentive_reward,
+								));
 								accumulated_incentive = accumulated_incentive.saturating_add(incentive_reward);
 							}
 						}
@@ -341,37 +1449,35 @@ impl<T: Config> RewardHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
 								.is_ok()
 							{
 								callback(pool_id, incentive_reward);
+								Self::deposit_event(Event::RewardAccumulated(
+									pool_id,
+									incentive_currency_id,
+									incentive_reward,
+								));
 								accumulated_incentive = accumulated_incentive.saturating_add(incentive_reward);
 							}
 						}
 
 						PoolId::DexSaving(currency_id) => {
+							// accumulate saving reward only for liquidity pool of saving currency id
+							let saving_currency_amount = Self::saving_currency_amount(currency_id);
+							if !saving_currency_amount.is_zero() {
+								saving_pools.push((pool_id, saving_currency_amount));
+							}
+
 							let dex_saving_rate = Self::dex_saving_rates(currency_id);
-							if !dex_saving_rate.is_zero() {
-								if let Some((currency_id_a, currency_id_b)) = currency_id.split_dex_share_currency_id()
+							if !dex_saving_rate.is_zero() && !saving_currency_amount.is_zero() {
+								let saving_reward = dex_saving_rate.saturating_mul_int(saving_currency_amount);
+								if T::CDPTreasury::issue_debit(&T::DexIncentivePool::get(), saving_reward, false)
+									.is_ok()
 								{
-									// accumulate saving reward only for liquidity pool of saving currency id
-									let saving_currency_amount = if currency_id_a == saving_currency_id {
-										T::DEX::get_liquidity_pool(saving_currency_id, currency_id_b).0
-									} else if currency_id_b == saving_currency_id {
-										T::DEX::get_liquidity_pool(saving_currency_id, currency_id_a).0
-									} else {
-										Zero::zero()
-									};
-
-									if !saving_currency_amount.is_zero() {
-										let saving_reward = dex_saving_rate.saturating_mul_int(saving_currency_amount);
-										if T::CDPTreasury::issue_debit(
-											&T::DexIncentivePool::get(),
-											saving_reward,
-											false,
-										)
-										.is_ok()
-										{
-											callback(pool_id, saving_reward);
-											accumulated_saving = accumulated_saving.saturating_add(saving_reward);
-										}
-									}
+									callback(pool_id, saving_reward);
+									Self::deposit_event(Event::RewardAccumulated(
+										pool_id,
+										saving_currency_id,
+										saving_reward,
+									));
+									accumulated_saving = accumulated_saving.saturating_add(saving_reward);
 								}
 							}
 						}
@@ -389,35 +1495,96 @@ impl<T: Config> RewardHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
 								.is_ok()
 							{
 								callback(pool_id, incentive_reward);
+								Self::deposit_event(Event::RewardAccumulated(
+									pool_id,
+									incentive_currency_id,
+									incentive_reward,
+								));
 								accumulated_incentive = accumulated_incentive.saturating_add(incentive_reward);
 							}
 						}
 					}
+
+					accumulated_extra.extend(Self::accumulate_extra_rewards(pool_id));
+
+					// the donated rewards are kept by the vault account of the pool already
+					for (currency_id, reward) in Self::take_donated_rewards(pool_id) {
+						if currency_id == Self::reward_currency_id(pool_id) {
+							callback(pool_id, reward);
+						} else {
+							ExtraRewardInfos::<T>::mutate(pool_id, currency_id, |info| {
+								info.total_rewards = info.total_rewards.saturating_add(reward)
+							});
+						}
+						Self::deposit_event(Event::RewardAccumulated(pool_id, currency_id, reward));
+						accumulated_extra.push((currency_id, reward));
+					}
+				}
+			}
+
+			// distribute the surplus rewards to the DexSaving pools by their saving currency amount
+			let surplus_reward_rate = Self::surplus_reward_rate();
+			let total_saving_currency_amount = saving_pools.iter().fold(Zero::zero(), |total: Balance, (_, amount)| {
+				total.saturating_add(*amount)
+			});
+			if !surplus_reward_rate.is_zero() && !total_saving_currency_amount.is_zero() {
+				let surplus_reward = surplus_reward_rate.saturating_mul_int(T::CDPTreasury::get_surplus_pool());
+				if !surplus_reward.is_zero()
+					&& T::CDPTreasury::withdraw_surplus(&T::DexIncentivePool::get(), surplus_reward).is_ok()
+				{
+					for (pool_id, saving_currency_amount) in saving_pools {
+						let reward =
+							multiply_by_rational(surplus_reward, saving_currency_amount, total_saving_currency_amount)
+								.unwrap_or_default();
+						if !reward.is_zero() {
+							callback(pool_id, reward);
+							Self::deposit_event(Event::RewardAccumulated(pool_id, saving_currency_id, reward));
+							accumulated_saving = accumulated_saving.saturating_add(reward);
+						}
+					}
 				}
 			}
 
 			if !accumulated_incentive.is_zero() {
 				accumulated_rewards.push((incentive_currency_id, accumulated_incentive));
+				IncentiveEmissions::<T>::insert(now, accumulated_incentive);
+			}
+			if let Some(expired) =
+				now.checked_sub(&T::AccumulatePeriod::get().saturating_mul(T::EmissionHistoryDepth::get().into()))
+			{
+				IncentiveEmissions::<T>::remove(expired);
 			}
 			if !accumulated_saving.is_zero() {
 				accumulated_rewards.push((saving_currency_id, accumulated_saving));
 			}
+			for (currency_id, reward) in accumulated_extra {
+				match accumulated_rewards.iter_mut().find(|(id, _)| *id == currency_id) {
+					Some((_, accumulated)) => *accumulated = accumulated.saturating_add(reward),
+					None => accumulated_rewards.push((currency_id, reward)),
+				}
+			}
+
+			Self::apply_emission_schedules();
+
+			let grace_period = T::ClaimGracePeriod::get();
+			PoolLifetimes::<T>::iter()
+				.filter(|(_, lifetime)| now >= lifetime.end.saturating_add(grace_period))
+				.map(|(pool_id, _)| pool_id)
+				.collect::<Vec<_>>()
+				.into_iter()
+				.for_each(Self::clean_up_pool);
 		}
 
 		accumulated_rewards
 	}
 
 	fn payout(who: &T::AccountId, pool_id: PoolId, amount: Balance) {
-		let (pool_account, currency_id) = match pool_id {
-			PoolId::Loans(_) => (T::LoansIncentivePool::get(), T::IncentiveCurrencyId::get()),
-			PoolId::DexIncentive(_) => (T::DexIncentivePool::get(), T::IncentiveCurrencyId::get()),
-			PoolId::DexSaving(_) => (T::DexIncentivePool::get(), T::SavingCurrencyId::get()),
-			PoolId::Homa => (T::HomaIncentivePool::get(), T::IncentiveCurrencyId::get()),
-		};
+		let deduction = Self::payout_reward(who, pool_id, Self::reward_currency_id(pool_id), amount);
 
-		// payout the reward to user from the pool. it should not affect the
-		// process, ignore the result to continue. if it fails, just the user will not
-		// be rewarded, there will not increase user balance.
-		let _ = T::Currency::transfer(currency_id, &pool_account, &who, amount);
+		// the pool info of orml-rewards is being updated by the claim, so the deduction is
+		// distributed to the pool at the next accumulation.
+		if !deduction.is_zero() {
+			PendingDeductions::<T>::mutate(pool_id, |pending| *pending = pending.saturating_add(deduction));
+		}
 	}
 }
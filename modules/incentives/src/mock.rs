@@ -22,6 +22,8 @@ pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const CDP_TREASURY: AccountId = 20;
+pub const TREASURY: AccountId = 30;
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
@@ -84,7 +86,7 @@ impl CDPTreasury<AccountId> for MockCDPTreasury {
 	type CurrencyId = CurrencyId;
 
 	fn get_surplus_pool() -> Balance {
-		unimplemented!()
+		TokensModule::free_balance(AUSD, &CDP_TREASURY)
 	}
 
 	fn get_debit_pool() -> Balance {
@@ -119,6 +121,10 @@ impl CDPTreasury<AccountId> for MockCDPTreasury {
 		unimplemented!()
 	}
 
+	fn withdraw_surplus(to: &AccountId, surplus: Balance) -> DispatchResult {
+		<TokensModule as MultiCurrency<AccountId>>::transfer(AUSD, &CDP_TREASURY, to, surplus)
+	}
+
 	fn deposit_collateral(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
 		unimplemented!()
 	}
@@ -140,6 +146,10 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 		}
 	}
 
+	fn get_twap_price(_: CurrencyId, _: CurrencyId) -> Option<Price> {
+		unimplemented!()
+	}
+
 	fn get_swap_target_amount(_: &[CurrencyId], _: Balance, _: Option<Ratio>) -> Option<Balance> {
 		unimplemented!()
 	}
@@ -148,14 +158,20 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 		unimplemented!()
 	}
 
+	// swap at 1:1 without price impact
 	fn swap_with_exact_supply(
-		_: &AccountId,
-		_: &[CurrencyId],
-		_: Balance,
-		_: Balance,
+		who: &AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		min_target_amount: Balance,
 		_: Option<Ratio>,
 	) -> sp_std::result::Result<Balance, DispatchError> {
-		unimplemented!()
+		if supply_amount < min_target_amount {
+			return Err(DispatchError::Other("below min target amount"));
+		}
+		TokensModule::withdraw(path[0], who, supply_amount)?;
+		TokensModule::deposit(path[path.len() - 1], who, supply_amount)?;
+		Ok(supply_amount)
 	}
 
 	fn swap_with_exact_target(
@@ -167,6 +183,30 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 	) -> sp_std::result::Result<Balance, DispatchError> {
 		unimplemented!()
 	}
+
+	// add liquidity at 1:1, the share increment equals to the amount of each currency
+	fn add_liquidity(
+		who: &AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		deposit_increment_share: bool,
+	) -> DispatchResult {
+		let lp_currency_id = match (currency_id_a, currency_id_b) {
+			(BTC, AUSD) | (AUSD, BTC) => BTC_AUSD_LP,
+			(DOT, AUSD) | (AUSD, DOT) => DOT_AUSD_LP,
+			_ => return Err(DispatchError::Other("invalid trading pair")),
+		};
+		let amount = max_amount_a.min(max_amount_b);
+		TokensModule::withdraw(currency_id_a, who, amount)?;
+		TokensModule::withdraw(currency_id_b, who, amount)?;
+		TokensModule::deposit(lp_currency_id, who, amount)?;
+		if deposit_increment_share {
+			IncentivesModule::do_deposit_dex_share(who, lp_currency_id, amount)?;
+		}
+		Ok(())
+	}
 }
 
 thread_local! {
@@ -184,6 +224,32 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+thread_local! {
+	static VESTED_TRANSFERS: RefCell<Vec<(AccountId, Balance, BlockNumber)>> = RefCell::new(vec![]);
+}
+
+pub fn vested_transfers() -> Vec<(AccountId, Balance, BlockNumber)> {
+	VESTED_TRANSFERS.with(|v| v.borrow().clone())
+}
+
+pub struct MockVestedTransfer;
+impl VestedTransfer<AccountId, Balance, BlockNumber> for MockVestedTransfer {
+	fn vested_transfer_with_cliff(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+		_cliff: BlockNumber,
+		duration: BlockNumber,
+	) -> DispatchResult {
+		if amount < 10 {
+			return Err(DispatchError::Other("below min vested transfer"));
+		}
+		<TokensModule as MultiCurrency<AccountId>>::transfer(ACA, from, to, amount)?;
+		VESTED_TRANSFERS.with(|v| v.borrow_mut().push((*to, amount, duration)));
+		Ok(())
+	}
+}
+
 impl orml_rewards::Config for Runtime {
 	type Share = Balance;
 	type Balance = Balance;
@@ -197,9 +263,16 @@ parameter_types! {
 	pub const DexIncentivePool: AccountId = 11;
 	pub const HomaIncentivePool: AccountId = 12;
 	pub const AccumulatePeriod: BlockNumber = 10;
+	pub const ClaimGracePeriod: BlockNumber = 20;
+	pub ReferralRewardRate: Rate = Rate::saturating_from_rational(10, 100);
+	pub const EmissionHistoryDepth: u32 = 2;
+	pub const MaxDonationPeriods: u32 = 10;
+	pub const MaxCleanUpBatch: u32 = 1;
+	pub const TreasuryAccount: AccountId = TREASURY;
 	pub const IncentiveCurrencyId: CurrencyId = ACA;
 	pub const SavingCurrencyId: CurrencyId = AUSD;
 	pub const IncentivesModuleId: ModuleId = ModuleId(*b"aca/inct");
+	pub CompoundPriceImpactLimit: Ratio = Ratio::saturating_from_rational(5, 100);
 }
 
 ord_parameter_types! {
@@ -218,7 +291,16 @@ impl Config for Runtime {
 	type CDPTreasury = MockCDPTreasury;
 	type Currency = TokensModule;
 	type DEX = MockDEX;
+	type CompoundPriceImpactLimit = CompoundPriceImpactLimit;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type NativeCurrencyId = IncentiveCurrencyId;
+	type VestedTransfer = MockVestedTransfer;
+	type ClaimGracePeriod = ClaimGracePeriod;
+	type ReferralRewardRate = ReferralRewardRate;
+	type EmissionHistoryDepth = EmissionHistoryDepth;
+	type MaxDonationPeriods = MaxDonationPeriods;
+	type MaxCleanUpBatch = MaxCleanUpBatch;
+	type TreasuryAccount = TreasuryAccount;
 	type ModuleId = IncentivesModuleId;
 	type WeightInfo = ();
 }
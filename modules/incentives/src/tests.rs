@@ -7,6 +7,7 @@ use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
 use orml_rewards::PoolInfo;
 use orml_traits::MultiCurrency;
+use primitives::TokenSymbol;
 use sp_runtime::{traits::BadOrigin, FixedPointNumber};
 
 #[test]
@@ -293,6 +294,128 @@ fn update_dex_saving_rates_works() {
 	});
 }
 
+#[test]
+fn update_extra_incentive_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IncentivesModule::update_extra_incentive_rewards(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+
+		assert_noop!(
+			IncentivesModule::update_extra_incentive_rewards(
+				Origin::signed(4),
+				vec![(PoolId::Loans(BTC), BTC_AUSD_LP, 100)],
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+
+		assert_ok!(IncentivesModule::update_extra_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::Loans(BTC), DOT, 100), (PoolId::Loans(BTC), AUSD, 200)],
+		));
+		assert_eq!(IncentivesModule::extra_incentive_rewards(PoolId::Loans(BTC), DOT), 100);
+		assert_eq!(IncentivesModule::extra_incentive_rewards(PoolId::Loans(BTC), AUSD), 200);
+
+		assert_ok!(IncentivesModule::update_extra_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::Loans(BTC), DOT, 0)],
+		));
+		assert!(!ExtraIncentiveRewards::<Runtime>::contains_key(PoolId::Loans(BTC), DOT));
+		assert_eq!(IncentivesModule::extra_incentive_rewards(PoolId::Loans(BTC), AUSD), 200);
+	});
+}
+
+#[test]
+fn update_claim_reward_deduction_rates_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IncentivesModule::update_claim_reward_deduction_rates(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+
+		assert_noop!(
+			IncentivesModule::update_claim_reward_deduction_rates(
+				Origin::signed(4),
+				vec![(PoolId::Homa, Rate::saturating_from_rational(101, 100))],
+			),
+			Error::<Runtime>::InvalidRate
+		);
+
+		assert_ok!(IncentivesModule::update_claim_reward_deduction_rates(
+			Origin::signed(4),
+			vec![
+				(PoolId::Homa, Rate::saturating_from_rational(1, 10)),
+				(PoolId::Loans(BTC), Rate::one()),
+			],
+		));
+		assert_eq!(
+			IncentivesModule::claim_reward_deduction_rates(PoolId::Homa),
+			Rate::saturating_from_rational(1, 10)
+		);
+		assert_eq!(
+			IncentivesModule::claim_reward_deduction_rates(PoolId::Loans(BTC)),
+			Rate::one()
+		);
+
+		assert_ok!(IncentivesModule::update_claim_reward_deduction_rates(
+			Origin::signed(4),
+			vec![(PoolId::Homa, Rate::zero())],
+		));
+		assert!(!ClaimRewardDeductionRates::<Runtime>::contains_key(PoolId::Homa));
+	});
+}
+
+#[test]
+fn update_emission_schedules_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(
+				Origin::signed(4),
+				vec![(PoolId::DexSaving(BTC_AUSD_LP), Some(EmissionSchedule::Fixed))],
+			),
+			Error::<Runtime>::InvalidPoolId
+		);
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(
+				Origin::signed(4),
+				vec![(PoolId::Homa, Some(EmissionSchedule::Halving { periods: 0 }))],
+			),
+			Error::<Runtime>::InvalidEmissionSchedule
+		);
+
+		assert_ok!(IncentivesModule::update_emission_schedules(
+			Origin::signed(4),
+			vec![
+				(PoolId::Homa, Some(EmissionSchedule::Halving { periods: 2 })),
+				(
+					PoolId::Loans(BTC),
+					Some(EmissionSchedule::LinearDecay { decrement: 100 })
+				),
+			],
+		));
+		assert_eq!(
+			IncentivesModule::emission_schedules(PoolId::Homa),
+			Some(EmissionSchedule::Halving { periods: 2 })
+		);
+		assert_eq!(
+			IncentivesModule::emission_schedules(PoolId::Loans(BTC)),
+			Some(EmissionSchedule::LinearDecay { decrement: 100 })
+		);
+
+		assert_ok!(IncentivesModule::update_emission_schedules(
+			Origin::signed(4),
+			vec![(PoolId::Homa, None)],
+		));
+		assert_eq!(IncentivesModule::emission_schedules(PoolId::Homa), None);
+	});
+}
+
 #[test]
 fn on_update_loan_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -432,6 +555,10 @@ fn accumulate_reward_works() {
 
 		RewardsModule::add_share(&ALICE, PoolId::Loans(DOT), 1);
 		assert_eq!(IncentivesModule::accumulate_reward(30, |_, _| {}), vec![(ACA, 3000)]);
+		assert_eq!(
+			IncentivesModule::last_incentive_emissions(35, 3),
+			vec![(30, 3000), (20, 1000)]
+		);
 
 		RewardsModule::add_share(&ALICE, PoolId::DexIncentive(BTC_AUSD_LP), 1);
 		RewardsModule::add_share(&ALICE, PoolId::DexSaving(BTC_AUSD_LP), 1);
@@ -439,6 +566,12 @@ fn accumulate_reward_works() {
 			IncentivesModule::accumulate_reward(40, |_, _| {}),
 			vec![(ACA, 3100), (AUSD, 5)]
 		);
+		// only the last `EmissionHistoryDepth` periods are kept
+		assert_eq!(IncentivesModule::incentive_emissions(20), None);
+		assert_eq!(
+			IncentivesModule::last_incentive_emissions(40, 3),
+			vec![(40, 3100), (30, 3000)]
+		);
 
 		RewardsModule::add_share(&ALICE, PoolId::DexIncentive(DOT_AUSD_LP), 1);
 		RewardsModule::add_share(&ALICE, PoolId::DexSaving(DOT_AUSD_LP), 1);
@@ -459,3 +592,828 @@ fn accumulate_reward_works() {
 		assert_eq!(IncentivesModule::accumulate_reward(60, |_, _| {}), vec![]);
 	});
 }
+
+#[test]
+fn extra_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::Loans(BTC);
+		assert_ok!(TokensModule::deposit(DOT, &IncentivesModule::account_id(), 300));
+		assert_ok!(IncentivesModule::update_loans_incentive_rewards(
+			Origin::signed(4),
+			vec![(BTC, 1000)],
+		));
+		assert_ok!(IncentivesModule::update_extra_incentive_rewards(
+			Origin::signed(4),
+			vec![(pool_id, DOT, 100)],
+		));
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		assert_eq!(
+			IncentivesModule::accumulate_reward(10, |_, _| {}),
+			vec![(ACA, 1000), (DOT, 100)]
+		);
+		let accumulate_event = Event::incentives(crate::Event::RewardAccumulated(pool_id, DOT, 100));
+		assert!(System::events().iter().any(|record| record.event == accumulate_event));
+		assert_eq!(
+			IncentivesModule::extra_reward_infos(pool_id, DOT),
+			ExtraRewardInfo {
+				total_rewards: 100,
+				total_withdrawn_rewards: 0,
+			}
+		);
+		assert_eq!(TokensModule::free_balance(DOT, &LoansIncentivePool::get()), 100);
+
+		// the extra rewards accumulated before are inflated for the added share
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 100, 0));
+		assert_eq!(
+			IncentivesModule::extra_reward_infos(pool_id, DOT),
+			ExtraRewardInfo {
+				total_rewards: 200,
+				total_withdrawn_rewards: 100,
+			}
+		);
+		assert_eq!(IncentivesModule::extra_withdrawn_rewards((pool_id, BOB), DOT), 100);
+
+		assert_eq!(
+			IncentivesModule::accumulate_reward(20, |_, _| {}),
+			vec![(ACA, 1000), (DOT, 100)]
+		);
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		let claim_event = Event::incentives(crate::Event::RewardClaimed(ALICE, pool_id, DOT, 150, 0));
+		assert!(System::events().iter().any(|record| record.event == claim_event));
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE), 150);
+		assert_eq!(IncentivesModule::extra_withdrawn_rewards((pool_id, ALICE), DOT), 150);
+
+		// the extra rewards are claimed before removing share
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, -100, 100));
+		assert_eq!(TokensModule::free_balance(DOT, &BOB), 50);
+		assert_eq!(IncentivesModule::extra_withdrawn_rewards((pool_id, BOB), DOT), 0);
+		assert_eq!(
+			IncentivesModule::extra_reward_infos(pool_id, DOT),
+			ExtraRewardInfo {
+				total_rewards: 150,
+				total_withdrawn_rewards: 150,
+			}
+		);
+		assert_eq!(TokensModule::free_balance(DOT, &LoansIncentivePool::get()), 0);
+
+		// no extra rewards accumulated if the module account is not funded enough
+		assert_ok!(IncentivesModule::update_extra_incentive_rewards(
+			Origin::signed(4),
+			vec![(pool_id, DOT, 200)],
+		));
+		assert_eq!(IncentivesModule::accumulate_reward(30, |_, _| {}), vec![(ACA, 1000)]);
+	});
+}
+
+#[test]
+fn claim_reward_deduction_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::Loans(BTC);
+		assert_ok!(IncentivesModule::update_loans_incentive_rewards(
+			Origin::signed(4),
+			vec![(BTC, 1000)],
+		));
+		assert_ok!(IncentivesModule::update_claim_reward_deduction_rates(
+			Origin::signed(4),
+			vec![(pool_id, Rate::saturating_from_rational(20, 100))],
+		));
+		let accumulate = |now| {
+			IncentivesModule::accumulate_reward(now, |pool_id, reward| {
+				orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+					pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward)
+				});
+			})
+		};
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 100, 0));
+		assert_eq!(accumulate(10), vec![(ACA, 1000)]);
+		assert_eq!(
+			IncentivesModule::pending_rewards(pool_id, &ALICE),
+			vec![(ACA, 400, 100)]
+		);
+
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		let claim_event = Event::incentives(crate::Event::RewardClaimed(ALICE, pool_id, ACA, 400, 100));
+		assert!(System::events().iter().any(|record| record.event == claim_event));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 400);
+		assert_eq!(IncentivesModule::pending_deductions(pool_id), 100);
+		assert_eq!(IncentivesModule::pending_rewards(pool_id, &ALICE), vec![]);
+
+		// the deduction is distributed to the pool at the next accumulation
+		assert_eq!(accumulate(11), vec![]);
+		assert_eq!(IncentivesModule::pending_deductions(pool_id), 0);
+		assert_eq!(
+			RewardsModule::pools(pool_id),
+			PoolInfo {
+				total_shares: 200,
+				total_rewards: 1100,
+				total_withdrawn_rewards: 500,
+			}
+		);
+		assert_eq!(IncentivesModule::pending_rewards(pool_id, &ALICE), vec![(ACA, 40, 10)]);
+		assert_eq!(IncentivesModule::pending_rewards(pool_id, &BOB), vec![(ACA, 440, 110)]);
+	});
+}
+
+#[test]
+fn participated_pools_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_loans_incentive_rewards(
+			Origin::signed(4),
+			vec![(BTC, 1000)],
+		));
+		assert_ok!(IncentivesModule::update_claim_reward_deduction_rates(
+			Origin::signed(4),
+			vec![(PoolId::Loans(BTC), Rate::saturating_from_rational(20, 100))],
+		));
+		assert_eq!(IncentivesModule::participated_pools(&ALICE), vec![]);
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, DOT, 50, 0));
+		OnUpdateLoan::<Runtime>::happened(&(BOB, DOT, 50, 0));
+		IncentivesModule::accumulate_reward(10, |pool_id, reward| {
+			orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+				pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward)
+			});
+		});
+
+		let participated_pools = IncentivesModule::participated_pools(&ALICE);
+		assert_eq!(participated_pools.len(), 2);
+		assert!(participated_pools.contains(&(
+			PoolId::Loans(BTC),
+			100,
+			vec![(ACA, 800, 200)],
+			Rate::saturating_from_rational(20, 100)
+		)));
+		assert!(participated_pools.contains(&(PoolId::Loans(DOT), 50, vec![], Rate::zero())));
+		assert_eq!(
+			IncentivesModule::participated_pools(&BOB),
+			vec![(PoolId::Loans(DOT), 50, vec![], Rate::zero())]
+		);
+	});
+}
+
+#[test]
+fn emission_schedules_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_loans_incentive_rewards(
+			Origin::signed(4),
+			vec![(BTC, 1000), (DOT, 1000)],
+		));
+		assert_ok!(IncentivesModule::update_dex_incentive_rewards(
+			Origin::signed(4),
+			vec![(BTC_AUSD_LP, 1000)],
+		));
+		assert_ok!(IncentivesModule::update_emission_schedules(
+			Origin::signed(4),
+			vec![
+				(
+					PoolId::Loans(BTC),
+					Some(EmissionSchedule::LinearDecay { decrement: 400 })
+				),
+				(PoolId::Loans(DOT), Some(EmissionSchedule::Fixed)),
+				(
+					PoolId::DexIncentive(BTC_AUSD_LP),
+					Some(EmissionSchedule::Halving { periods: 2 })
+				),
+			],
+		));
+		RewardsModule::add_share(&ALICE, PoolId::Loans(BTC), 1);
+
+		// not at the period rollover
+		assert_eq!(IncentivesModule::accumulate_reward(5, |_, _| {}), vec![]);
+		assert_eq!(IncentivesModule::elapsed_emission_periods(PoolId::Loans(BTC)), 0);
+
+		assert_eq!(IncentivesModule::accumulate_reward(10, |_, _| {}), vec![(ACA, 1000)]);
+		assert_eq!(IncentivesModule::loans_incentive_rewards(BTC), 600);
+		assert_eq!(IncentivesModule::loans_incentive_rewards(DOT), 1000);
+		assert_eq!(IncentivesModule::dex_incentive_rewards(BTC_AUSD_LP), 1000);
+		assert_eq!(IncentivesModule::elapsed_emission_periods(PoolId::Loans(BTC)), 1);
+
+		assert_eq!(IncentivesModule::accumulate_reward(20, |_, _| {}), vec![(ACA, 600)]);
+		assert_eq!(IncentivesModule::loans_incentive_rewards(BTC), 200);
+		assert_eq!(IncentivesModule::dex_incentive_rewards(BTC_AUSD_LP), 500);
+
+		assert_eq!(IncentivesModule::accumulate_reward(30, |_, _| {}), vec![(ACA, 200)]);
+		assert_eq!(IncentivesModule::loans_incentive_rewards(BTC), 0);
+		assert_eq!(IncentivesModule::loans_incentive_rewards(DOT), 1000);
+		assert_eq!(IncentivesModule::dex_incentive_rewards(BTC_AUSD_LP), 500);
+
+		assert_eq!(IncentivesModule::accumulate_reward(40, |_, _| {}), vec![]);
+		assert_eq!(IncentivesModule::dex_incentive_rewards(BTC_AUSD_LP), 250);
+	});
+}
+
+#[test]
+fn add_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::Loans(BTC);
+		assert_ok!(TokensModule::deposit(DOT, &BOB, 1000));
+
+		assert_noop!(
+			IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, BTC_AUSD_LP, 100, 1),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, DOT, 0, 1),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, DOT, 100, 0),
+			Error::<Runtime>::InvalidPeriods
+		);
+		assert_noop!(
+			IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, DOT, 100, 11),
+			Error::<Runtime>::InvalidPeriods
+		);
+		assert_noop!(
+			IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, DOT, 1001, 1),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, DOT, 300, 3));
+		let donate_event = Event::incentives(crate::Event::RewardDonated(BOB, pool_id, DOT, 300, 3));
+		assert!(System::events().iter().any(|record| record.event == donate_event));
+		assert_eq!(TokensModule::free_balance(DOT, &BOB), 700);
+		assert_eq!(TokensModule::free_balance(DOT, &LoansIncentivePool::get()), 300);
+		assert_eq!(
+			IncentivesModule::reward_donations(pool_id, DOT),
+			Some(RewardDonation {
+				remaining_amount: 300,
+				remaining_periods: 3,
+			})
+		);
+
+		// merged with the remaining donation
+		assert_ok!(IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, DOT, 200, 2));
+		assert_eq!(
+			IncentivesModule::reward_donations(pool_id, DOT),
+			Some(RewardDonation {
+				remaining_amount: 500,
+				remaining_periods: 3,
+			})
+		);
+
+		// the remaining periods are not extended
+		assert_ok!(IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, DOT, 1, 10));
+		let donate_event = Event::incentives(crate::Event::RewardDonated(BOB, pool_id, DOT, 1, 3));
+		assert!(System::events().iter().any(|record| record.event == donate_event));
+		assert_eq!(
+			IncentivesModule::reward_donations(pool_id, DOT),
+			Some(RewardDonation {
+				remaining_amount: 501,
+				remaining_periods: 3,
+			})
+		);
+	});
+}
+
+#[test]
+fn donated_rewards_distribution_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = PoolId::Loans(BTC);
+		assert_ok!(TokensModule::deposit(DOT, &BOB, 1000));
+		assert_ok!(TokensModule::deposit(ACA, &BOB, 1000));
+		let accumulate = |now| {
+			IncentivesModule::accumulate_reward(now, |pool_id, reward| {
+				orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+					pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward)
+				});
+			})
+		};
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		assert_ok!(IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, DOT, 300, 2));
+		assert_ok!(IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, ACA, 100, 1));
+		assert_ok!(IncentivesModule::add_rewards(
+			Origin::signed(BOB),
+			PoolId::Loans(DOT),
+			DOT,
+			100,
+			1
+		));
+
+		assert_eq!(accumulate(5), vec![]);
+		let accumulated_rewards = accumulate(10);
+		assert_eq!(accumulated_rewards.len(), 2);
+		assert!(accumulated_rewards.contains(&(ACA, 100)));
+		assert!(accumulated_rewards.contains(&(DOT, 150)));
+		assert_eq!(RewardsModule::pools(pool_id).total_rewards, 100);
+		assert_eq!(IncentivesModule::extra_reward_infos(pool_id, DOT).total_rewards, 150);
+		assert_eq!(IncentivesModule::reward_donations(pool_id, ACA), None);
+		assert_eq!(
+			IncentivesModule::reward_donations(pool_id, DOT),
+			Some(RewardDonation {
+				remaining_amount: 150,
+				remaining_periods: 1,
+			})
+		);
+		// not distributed to the pool without shares
+		assert_eq!(
+			IncentivesModule::reward_donations(PoolId::Loans(DOT), DOT),
+			Some(RewardDonation {
+				remaining_amount: 100,
+				remaining_periods: 1,
+			})
+		);
+
+		assert_eq!(accumulate(20), vec![(DOT, 150)]);
+		assert_eq!(IncentivesModule::reward_donations(pool_id, DOT), None);
+
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 100);
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE), 300);
+	});
+}
+
+#[test]
+fn compound_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::DexIncentive(BTC_AUSD_LP);
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 100));
+		assert_ok!(TokensModule::deposit(BTC, &BOB, 1000));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			100
+		));
+		assert_ok!(IncentivesModule::add_rewards(Origin::signed(BOB), pool_id, BTC, 200, 1));
+		IncentivesModule::accumulate_reward(10, |pool_id, reward| {
+			orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+				pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward)
+			});
+		});
+
+		assert_noop!(
+			IncentivesModule::compound(Origin::signed(ALICE), PoolId::Loans(BTC)),
+			Error::<Runtime>::InvalidPoolId
+		);
+
+		// half of the rewards are swapped to AUSD and added to the liquidity
+		assert_ok!(IncentivesModule::compound(Origin::signed(ALICE), pool_id));
+		let compound_event = Event::incentives(crate::Event::RewardCompounded(ALICE, pool_id, 100, 100));
+		assert!(System::events().iter().any(|record| record.event == compound_event));
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(pool_id, ALICE).0, 200);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexSaving(BTC_AUSD_LP), ALICE).0,
+			200
+		);
+		assert_eq!(TokensModule::free_balance(BTC, &ALICE), 0);
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 0);
+		assert_eq!(TokensModule::free_balance(BTC_AUSD_LP, &ALICE), 0);
+		assert_eq!(
+			TokensModule::free_balance(BTC_AUSD_LP, &IncentivesModule::account_id()),
+			300
+		);
+
+		// nothing to compound
+		assert_ok!(IncentivesModule::compound(Origin::signed(ALICE), pool_id));
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(pool_id, ALICE).0, 200);
+	});
+}
+
+#[test]
+fn compound_vested_rewards_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let aca_pool_id = PoolId::DexIncentive(CurrencyId::DEXShare(TokenSymbol::ACA, TokenSymbol::AUSD));
+		let btc_pool_id = PoolId::DexIncentive(BTC_AUSD_LP);
+		assert_ok!(IncentivesModule::update_reward_vesting_durations(
+			Origin::signed(4),
+			vec![(aca_pool_id, 100), (btc_pool_id, 100)],
+		));
+
+		// the vested native currency rewards are locked
+		assert_noop!(
+			IncentivesModule::compound(Origin::signed(ALICE), aca_pool_id),
+			Error::<Runtime>::VestedRewardsNotCompoundable
+		);
+
+		// the rewards in the currencies of the trading pair are not vested
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 100));
+		assert_ok!(TokensModule::deposit(BTC, &BOB, 1000));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			100
+		));
+		assert_ok!(IncentivesModule::add_rewards(
+			Origin::signed(BOB),
+			btc_pool_id,
+			BTC,
+			200,
+			1
+		));
+		IncentivesModule::accumulate_reward(10, |pool_id, reward| {
+			orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+				pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward)
+			});
+		});
+		assert_ok!(IncentivesModule::compound(Origin::signed(ALICE), btc_pool_id));
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(btc_pool_id, ALICE).0, 200);
+		assert_eq!(vested_transfers(), vec![]);
+	});
+}
+
+#[test]
+fn update_reward_vesting_durations_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IncentivesModule::update_reward_vesting_durations(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+
+		assert_ok!(IncentivesModule::update_reward_vesting_durations(
+			Origin::signed(4),
+			vec![(PoolId::Homa, 100), (PoolId::Loans(BTC), 200)],
+		));
+		assert_eq!(IncentivesModule::reward_vesting_durations(PoolId::Homa), 100);
+		assert_eq!(IncentivesModule::reward_vesting_durations(PoolId::Loans(BTC)), 200);
+
+		assert_ok!(IncentivesModule::update_reward_vesting_durations(
+			Origin::signed(4),
+			vec![(PoolId::Homa, 0)],
+		));
+		assert!(!RewardVestingDurations::<Runtime>::contains_key(PoolId::Homa));
+	});
+}
+
+#[test]
+fn vest_claimed_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_id = PoolId::Loans(BTC);
+		assert_ok!(TokensModule::deposit(ACA, &LoansIncentivePool::get(), 1000));
+		assert_ok!(IncentivesModule::update_reward_vesting_durations(
+			Origin::signed(4),
+			vec![(pool_id, 100)],
+		));
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+
+		// the reward below the min vested transfer is kept until the next claim
+		orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| pool_info.total_rewards += 5);
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		assert_eq!(IncentivesModule::unvested_rewards(pool_id, ALICE), 5);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 0);
+		assert_eq!(vested_transfers(), vec![]);
+
+		orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| pool_info.total_rewards += 100);
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		assert_eq!(IncentivesModule::unvested_rewards(pool_id, ALICE), 0);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 105);
+		assert_eq!(vested_transfers(), vec![(ALICE, 105, 100)]);
+	});
+}
+
+#[test]
+fn update_pool_lifetimes_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IncentivesModule::update_pool_lifetimes(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_pool_lifetimes(Origin::signed(4), vec![(PoolId::Homa, Some((10, 10)))]),
+			Error::<Runtime>::InvalidPoolLifetime
+		);
+
+		assert_ok!(IncentivesModule::update_pool_lifetimes(
+			Origin::signed(4),
+			vec![(PoolId::Homa, Some((10, 100)))],
+		));
+		assert_eq!(
+			IncentivesModule::pool_lifetimes(PoolId::Homa),
+			Some(PoolLifetime { start: 10, end: 100 })
+		);
+
+		assert_ok!(IncentivesModule::update_pool_lifetimes(
+			Origin::signed(4),
+			vec![(PoolId::Homa, None)],
+		));
+		assert_eq!(IncentivesModule::pool_lifetimes(PoolId::Homa), None);
+
+		// the expired pool is re-opened
+		ExpiredPools::<Runtime>::insert(PoolId::Homa, true);
+		assert_ok!(IncentivesModule::update_pool_lifetimes(
+			Origin::signed(4),
+			vec![(PoolId::Homa, Some((10, 100)))],
+		));
+		assert!(!IncentivesModule::expired_pools(PoolId::Homa));
+	});
+}
+
+#[test]
+fn pool_lifetime_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::Loans(BTC);
+		let accumulate = |now| {
+			IncentivesModule::accumulate_reward(now, |pool_id, reward| {
+				orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+					pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward)
+				});
+			})
+		};
+
+		assert_ok!(TokensModule::deposit(ACA, &LoansIncentivePool::get(), 1000));
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		assert_ok!(IncentivesModule::update_loans_incentive_rewards(
+			Origin::signed(4),
+			vec![(BTC, 100)],
+		));
+		assert_ok!(IncentivesModule::update_pool_lifetimes(
+			Origin::signed(4),
+			vec![(pool_id, Some((20, 40)))],
+		));
+
+		// the rewards are only accumulated during the lifetime
+		assert_eq!(accumulate(10), vec![]);
+		assert_eq!(accumulate(20), vec![(ACA, 100)]);
+		assert_eq!(accumulate(30), vec![(ACA, 100)]);
+		assert_eq!(accumulate(40), vec![]);
+		assert_eq!(RewardsModule::pools(pool_id).total_rewards, 200);
+
+		// the rewards can be claimed in the grace period
+		System::set_block_number(50);
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 200);
+
+		assert_eq!(accumulate(50), vec![]);
+		assert!(!IncentivesModule::expired_pools(pool_id));
+
+		System::set_block_number(60);
+		assert_noop!(
+			IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id),
+			Error::<Runtime>::PoolExpired
+		);
+
+		assert_eq!(accumulate(60), vec![]);
+		assert!(IncentivesModule::expired_pools(pool_id));
+		assert_eq!(IncentivesModule::pool_lifetimes(pool_id), None);
+		assert_eq!(IncentivesModule::loans_incentive_rewards(BTC), 0);
+		assert_eq!(RewardsModule::pools(pool_id).total_rewards, 0);
+		assert_eq!(RewardsModule::pools(pool_id).total_withdrawn_rewards, 0);
+
+		// the accounts are cleaned up in batches, the pool can't be re-opened until
+		// it's done
+		assert_eq!(
+			IncentivesModule::pool_clean_up_cursors(pool_id),
+			Some(CleanUpCursor::UnvestedRewards(None))
+		);
+		assert_noop!(
+			IncentivesModule::update_pool_lifetimes(Origin::signed(4), vec![(pool_id, Some((70, 80)))]),
+			Error::<Runtime>::PoolCleaningUp
+		);
+		<IncentivesModule as Hooks<u64>>::on_initialize(61);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(pool_id, ALICE), (100, 0));
+		<IncentivesModule as Hooks<u64>>::on_initialize(62);
+		assert_eq!(IncentivesModule::pool_clean_up_cursors(pool_id), None);
+		let clean_up_event = Event::incentives(crate::Event::PoolCleanedUp(pool_id));
+		assert!(System::events().iter().any(|record| record.event == clean_up_event));
+		assert_eq!(
+			ExtraWithdrawnRewards::<Runtime>::iter_prefix((pool_id, ALICE)).count(),
+			0
+		);
+		assert_noop!(
+			IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id),
+			Error::<Runtime>::PoolExpired
+		);
+
+		// the pool is re-opened with the fresh reward accounting
+		assert_ok!(IncentivesModule::update_pool_lifetimes(
+			Origin::signed(4),
+			vec![(pool_id, Some((70, 80)))],
+		));
+		assert!(!IncentivesModule::expired_pools(pool_id));
+		assert_ok!(IncentivesModule::update_loans_incentive_rewards(
+			Origin::signed(4),
+			vec![(BTC, 100)],
+		));
+		assert_eq!(accumulate(70), vec![(ACA, 100)]);
+		System::set_block_number(70);
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 300);
+	});
+}
+
+#[test]
+fn clean_up_pool_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::Loans(BTC);
+		let accumulate = |now| {
+			IncentivesModule::accumulate_reward(now, |pool_id, reward| {
+				orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+					pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward)
+				});
+			})
+		};
+
+		assert_ok!(TokensModule::deposit(ACA, &LoansIncentivePool::get(), 1000));
+		assert_ok!(TokensModule::deposit(DOT, &LoansIncentivePool::get(), 1000));
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 100, 0));
+		assert_ok!(IncentivesModule::update_reward_vesting_durations(
+			Origin::signed(4),
+			vec![(pool_id, 100)],
+		));
+		assert_ok!(IncentivesModule::update_pool_lifetimes(
+			Origin::signed(4),
+			vec![(pool_id, Some((1, 10)))],
+		));
+
+		// ALICE's reward below the min vested transfer is kept unvested
+		orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| pool_info.total_rewards += 10);
+		ExtraRewardInfos::<Runtime>::mutate(pool_id, DOT, |info| info.total_rewards += 100);
+		RewardDonations::<Runtime>::insert(
+			pool_id,
+			DOT,
+			RewardDonation {
+				remaining_amount: 30,
+				remaining_periods: 3,
+			},
+		);
+		PendingDeductions::<Runtime>::insert(pool_id, 7);
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		assert_eq!(IncentivesModule::unvested_rewards(pool_id, ALICE), 5);
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE), 50);
+
+		// the rewards not claimed by BOB, the deductions and the donations are
+		// returned to the treasury
+		assert_eq!(accumulate(30), vec![]);
+		assert_eq!(TokensModule::free_balance(ACA, &TREASURY), 12);
+		assert_eq!(TokensModule::free_balance(DOT, &TREASURY), 80);
+		let return_event = Event::incentives(crate::Event::LeftoverRewardsReturned(pool_id, DOT, 50));
+		assert!(System::events().iter().any(|record| record.event == return_event));
+		assert_eq!(IncentivesModule::reward_donations(pool_id, DOT), None);
+		assert_eq!(IncentivesModule::extra_reward_infos(pool_id, DOT), Default::default());
+
+		// the unvested reward is paid out liquidly as it still fails to be vested
+		<IncentivesModule as Hooks<u64>>::on_initialize(31);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 5);
+		assert_eq!(IncentivesModule::unvested_rewards(pool_id, ALICE), 0);
+		assert!(matches!(
+			IncentivesModule::pool_clean_up_cursors(pool_id),
+			Some(CleanUpCursor::UnvestedRewards(Some(_)))
+		));
+
+		// one account per block
+		<IncentivesModule as Hooks<u64>>::on_initialize(32);
+		<IncentivesModule as Hooks<u64>>::on_initialize(33);
+		assert!(matches!(
+			IncentivesModule::pool_clean_up_cursors(pool_id),
+			Some(CleanUpCursor::Shares(Some(_)))
+		));
+		<IncentivesModule as Hooks<u64>>::on_initialize(34);
+		assert_eq!(IncentivesModule::pool_clean_up_cursors(pool_id), None);
+		assert_eq!(IncentivesModule::reward_vesting_durations(pool_id), 0);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(pool_id, ALICE), (100, 0));
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(pool_id, BOB), (100, 0));
+		let clean_up_event = Event::incentives(crate::Event::PoolCleanedUp(pool_id));
+		assert!(System::events().iter().any(|record| record.event == clean_up_event));
+	});
+}
+
+#[test]
+fn update_surplus_reward_rate_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IncentivesModule::update_surplus_reward_rate(Origin::signed(ALICE), Rate::zero()),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_surplus_reward_rate(Origin::signed(4), Rate::saturating_from_rational(101, 100)),
+			Error::<Runtime>::InvalidRate
+		);
+
+		assert_ok!(IncentivesModule::update_surplus_reward_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 10)
+		));
+		assert_eq!(
+			IncentivesModule::surplus_reward_rate(),
+			Rate::saturating_from_rational(1, 10)
+		);
+	});
+}
+
+#[test]
+fn surplus_rewards_distribution_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(AUSD, &CDP_TREASURY, 1000));
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 100));
+		assert_ok!(TokensModule::deposit(DOT_AUSD_LP, &BOB, 100));
+		let accumulate = |now| {
+			IncentivesModule::accumulate_reward(now, |pool_id, reward| {
+				orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+					pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward)
+				});
+			})
+		};
+
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			100
+		));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(BOB),
+			DOT_AUSD_LP,
+			100
+		));
+		assert_eq!(accumulate(10), vec![]);
+
+		// the surplus reward is distributed by the AUSD amount of the liquidity pools
+		assert_ok!(IncentivesModule::update_surplus_reward_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 10)
+		));
+		assert_eq!(accumulate(20), vec![(AUSD, 99)]);
+		assert_eq!(RewardsModule::pools(PoolId::DexSaving(BTC_AUSD_LP)).total_rewards, 55);
+		assert_eq!(RewardsModule::pools(PoolId::DexSaving(DOT_AUSD_LP)).total_rewards, 44);
+		assert_eq!(RewardsModule::pools(PoolId::DexIncentive(BTC_AUSD_LP)).total_rewards, 0);
+		assert_eq!(TokensModule::free_balance(AUSD, &CDP_TREASURY), 900);
+		assert_eq!(TokensModule::free_balance(AUSD, &DexIncentivePool::get()), 100);
+
+		// adjusted with the surplus
+		assert_eq!(accumulate(30), vec![(AUSD, 90)]);
+		assert_eq!(TokensModule::free_balance(AUSD, &CDP_TREASURY), 810);
+
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexSaving(BTC_AUSD_LP)
+		));
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 105);
+	});
+}
+
+#[test]
+fn set_referrer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::set_referrer(Origin::signed(ALICE), ALICE),
+			Error::<Runtime>::InvalidReferrer
+		);
+
+		assert_ok!(IncentivesModule::set_referrer(Origin::signed(ALICE), BOB));
+		let referrer_event = Event::incentives(crate::Event::ReferrerSet(ALICE, BOB));
+		assert!(System::events().iter().any(|record| record.event == referrer_event));
+		assert_eq!(IncentivesModule::referrers(ALICE), Some(BOB));
+
+		assert_noop!(
+			IncentivesModule::set_referrer(Origin::signed(ALICE), CDP_TREASURY),
+			Error::<Runtime>::ReferrerAlreadySet
+		);
+
+		// the referral cycle
+		assert_ok!(IncentivesModule::set_referrer(Origin::signed(CDP_TREASURY), ALICE));
+		assert_noop!(
+			IncentivesModule::set_referrer(Origin::signed(BOB), CDP_TREASURY),
+			Error::<Runtime>::InvalidReferrer
+		);
+
+		// the account has shares already
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 100, 0));
+		assert_noop!(
+			IncentivesModule::set_referrer(Origin::signed(BOB), 3),
+			Error::<Runtime>::AlreadyParticipated
+		);
+	});
+}
+
+#[test]
+fn referral_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::Loans(BTC);
+		assert_ok!(TokensModule::deposit(ACA, &LoansIncentivePool::get(), 1000));
+		assert_ok!(TokensModule::deposit(DOT, &LoansIncentivePool::get(), 1000));
+		assert_ok!(IncentivesModule::set_referrer(Origin::signed(ALICE), BOB));
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+
+		assert_noop!(
+			IncentivesModule::claim_referral_rewards(Origin::signed(BOB)),
+			Error::<Runtime>::NoReferralRewards
+		);
+
+		// only the rewards in the incentive currency accrue the referral bonus
+		orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| pool_info.total_rewards += 500);
+		ExtraRewardInfos::<Runtime>::mutate(pool_id, DOT, |info| info.total_rewards += 100);
+		assert_ok!(IncentivesModule::claim_rewards(Origin::signed(ALICE), pool_id));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 500);
+		assert_eq!(TokensModule::free_balance(DOT, &ALICE), 100);
+		assert_eq!(IncentivesModule::referral_rewards(BOB), 50);
+
+		assert_ok!(IncentivesModule::claim_referral_rewards(Origin::signed(BOB)));
+		let claim_event = Event::incentives(crate::Event::ReferralRewardsClaimed(BOB, 50));
+		assert!(System::events().iter().any(|record| record.event == claim_event));
+		assert_eq!(TokensModule::free_balance(ACA, &BOB), 50);
+		assert_eq!(IncentivesModule::referral_rewards(BOB), 0);
+	});
+}
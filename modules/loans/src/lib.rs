@@ -207,6 +207,9 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// transfer whole loan of `from` to `to`
+	///
+	/// Ensured atomic.
+	#[transactional]
 	pub fn transfer_loan(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
 		// get `from` position data
 		let Position { collateral, debit } = Self::positions(currency_id, from);
@@ -217,10 +220,8 @@ impl<T: Config> Pallet<T> {
 		} = Self::positions(currency_id, to);
 		let new_to_collateral_balance = to_collateral
 			.checked_add(collateral)
-			.expect("existing collateral balance cannot overflow; qed");
-		let new_to_debit_balance = to_debit
-			.checked_add(debit)
-			.expect("existing debit balance cannot overflow; qed");
+			.ok_or(Error::<T>::CollateralOverflow)?;
+		let new_to_debit_balance = to_debit.checked_add(debit).ok_or(Error::<T>::DebitOverflow)?;
 
 		// check new position
 		T::RiskManager::check_position_valid(currency_id, new_to_collateral_balance, new_to_debit_balance)?;
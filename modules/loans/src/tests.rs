@@ -140,6 +140,38 @@ fn transfer_loan_should_work() {
 	});
 }
 
+#[test]
+fn transfer_loan_overflow_should_not_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LoansModule::update_loan(&ALICE, BTC, 400, 500));
+		Positions::<Runtime>::insert(
+			BTC,
+			&BOB,
+			Position {
+				collateral: Balance::max_value(),
+				debit: 0,
+			},
+		);
+		assert_noop!(
+			LoansModule::transfer_loan(&ALICE, &BOB, BTC),
+			Error::<Runtime>::CollateralOverflow,
+		);
+
+		Positions::<Runtime>::insert(
+			BTC,
+			&BOB,
+			Position {
+				collateral: 0,
+				debit: Balance::max_value(),
+			},
+		);
+		assert_noop!(
+			LoansModule::transfer_loan(&ALICE, &BOB, BTC),
+			Error::<Runtime>::DebitOverflow,
+		);
+	});
+}
+
 #[test]
 fn confiscate_collateral_and_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
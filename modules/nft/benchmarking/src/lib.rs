@@ -14,7 +14,7 @@ use sp_runtime::traits::{AccountIdConversion, StaticLookup, UniqueSaturatedInto}
 
 use module_nft::*;
 use orml_traits::BasicCurrencyExtended;
-use primitives::Balance;
+use primitives::{Balance, CurrencyId, TokenSymbol};
 
 pub struct Module<T: Config>(module_nft::Module<T>);
 
@@ -22,6 +22,8 @@ pub trait Config: module_nft::Config + orml_nft::Config + pallet_proxy::Config +
 
 const SEED: u32 = 0;
 
+const NATIVE: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
+
 fn dollar(d: u32) -> Balance {
 	let d: Balance = d.into();
 	d.saturating_mul(1_000_000_000_000_000_000)
@@ -34,7 +36,7 @@ benchmarks! {
 		let base_currency_amount = dollar(1000);
 
 		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
-	}: _(RawOrigin::Signed(caller), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))
+	}: _(RawOrigin::Signed(caller.clone()), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), Some(Royalty { beneficiary: caller, per_mille: T::MaxRoyaltyPerMille::get() }))
 
 	// mint NFT token
 	mint {
@@ -48,9 +50,24 @@ benchmarks! {
 		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
 
 		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
-		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+	}: _(RawOrigin::Signed(module_account), to_lookup, 0u32.into(), vec![vec![1]; i as usize], i, None)
+
+	// mint NFT token to each of the recipients
+	mint_to_many {
+		let i in 1 .. T::MaxMintRecipients::get();
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let recipients: Vec<T::AccountId> = (0..i).map(|j| account("to", j, SEED)).collect();
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
 		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
-	}: _(RawOrigin::Signed(module_account), to_lookup, 0u32.into(), vec![1], i)
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), recipients, vec![1], None)
 
 	// transfer NFT token to another account
 	transfer {
@@ -63,9 +80,9 @@ benchmarks! {
 		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
 
 		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
-		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
 		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
-		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, None)?;
 	}: _(RawOrigin::Signed(to), caller_lookup, (0u32.into(), 0u32.into()))
 
 	// burn NFT token
@@ -78,11 +95,28 @@ benchmarks! {
 		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
 
 		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
-		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
 		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
-		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, None)?;
 	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()))
 
+	// reap expired NFT token
+	reap {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller.clone()).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+		frame_system::Module::<T>::set_block_number(1u32.into());
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, Some(2u32.into()))?;
+		frame_system::Module::<T>::set_block_number(2u32.into());
+	}: _(RawOrigin::Signed(caller), (0u32.into(), 0u32.into()))
+
 	// destroy NFT class
 	destroy_class {
 		let caller: T::AccountId = account("caller", 0, SEED);
@@ -94,8 +128,199 @@ benchmarks! {
 		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
 
 		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
-		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), to_lookup)
+
+	// list NFT token for sale
+	list {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, None)?;
+	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()), dollar(10), NATIVE)
+
+	// cancel the listing of NFT token
+	unlist {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, None)?;
+		module_nft::Module::<T>::list(RawOrigin::Signed(to.clone()).into(), (0u32.into(), 0u32.into()), dollar(10), NATIVE)?;
+	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()))
+
+	// buy NFT token and pay the royalty and the marketplace fee
+	buy {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&buyer, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller.clone()).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), Some(Royalty { beneficiary: caller, per_mille: T::MaxRoyaltyPerMille::get() }))?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, None)?;
+		module_nft::Module::<T>::list(RawOrigin::Signed(to).into(), (0u32.into(), 0u32.into()), dollar(10), NATIVE)?;
+	}: _(RawOrigin::Signed(buyer), (0u32.into(), 0u32.into()), dollar(10))
+
+	// start auction of NFT token
+	create_auction {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, None)?;
+		module_nft::Module::<T>::list(RawOrigin::Signed(to.clone()).into(), (0u32.into(), 0u32.into()), dollar(10), NATIVE)?;
+	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()), NATIVE, dollar(10), 100u32.into())
+
+	// set attribute of NFT class
+	set_class_attribute {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let key = vec![1u8; T::MaxAttributeKeyLength::get() as usize];
+		let value = vec![1u8; T::MaxAttributeValueLength::get() as usize];
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), key, value)
+
+	// clear attribute of NFT class
+	clear_class_attribute {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let key = vec![1u8; T::MaxAttributeKeyLength::get() as usize];
+		let value = vec![1u8; T::MaxAttributeValueLength::get() as usize];
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+		module_nft::Module::<T>::set_class_attribute(RawOrigin::Signed(module_account.clone()).into(), 0u32.into(), key.clone(), value)?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), key)
+
+	// set attribute of NFT token
+	set_token_attribute {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+		let key = vec![1u8; T::MaxAttributeKeyLength::get() as usize];
+		let value = vec![1u8; T::MaxAttributeValueLength::get() as usize];
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, None)?;
+	}: _(RawOrigin::Signed(module_account), (0u32.into(), 0u32.into()), key, value)
+
+	// clear attribute of NFT token
+	clear_token_attribute {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+		let key = vec![1u8; T::MaxAttributeKeyLength::get() as usize];
+		let value = vec![1u8; T::MaxAttributeValueLength::get() as usize];
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&module_account, base_currency_amount.unique_saturated_into())?;
+		module_nft::Module::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![vec![1]], 1, None)?;
+		module_nft::Module::<T>::set_token_attribute(RawOrigin::Signed(module_account.clone()).into(), (0u32.into(), 0u32.into()), key.clone(), value)?;
+	}: _(RawOrigin::Signed(module_account), (0u32.into(), 0u32.into()), key)
+
+	// revoke properties of NFT class
+	update_class_properties {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), Properties(ClassProperty::Burnable.into()))
+
+	// lock properties of NFT class
+	lock_class_properties {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into())
+
+	// transfer ownership of NFT class
+	transfer_class_ownership {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
 	}: _(RawOrigin::Signed(module_account), 0u32.into(), to_lookup)
+
+	// add issuer of NFT class
+	add_issuer {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let issuer: T::AccountId = account("issuer", 0, SEED);
+		let issuer_lookup = T::Lookup::unlookup(issuer);
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), issuer_lookup)
+
+	// remove issuer of NFT class
+	remove_issuer {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let issuer: T::AccountId = account("issuer", 0, SEED);
+		let issuer_lookup = T::Lookup::unlookup(issuer);
+
+		let base_currency_amount = dollar(1000);
+		<T as module_currencies::Config>::NativeCurrency::update_balance(&caller, base_currency_amount.unique_saturated_into())?;
+
+		let module_account: T::AccountId = T::ModuleId::get().into_sub_account(orml_nft::Module::<T>::next_class_id());
+		module_nft::Module::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable), None)?;
+		module_nft::Module::<T>::add_issuer(RawOrigin::Signed(module_account.clone()).into(), 0u32.into(), issuer_lookup.clone())?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), issuer_lookup)
 }
 
 #[cfg(test)]
@@ -118,6 +343,13 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_mint_to_many() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_mint_to_many::<Runtime>());
+		});
+	}
+
 	#[test]
 	fn test_transfer() {
 		new_test_ext().execute_with(|| {
@@ -132,10 +364,108 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_reap() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_reap::<Runtime>());
+		});
+	}
+
 	#[test]
 	fn test_destroy_class() {
 		new_test_ext().execute_with(|| {
 			assert_ok!(test_benchmark_destroy_class::<Runtime>());
 		});
 	}
+
+	#[test]
+	fn test_list() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_list::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_unlist() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unlist::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_buy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_buy::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_create_auction() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_auction::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_class_attribute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_class_attribute::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_clear_class_attribute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_clear_class_attribute::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_token_attribute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_token_attribute::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_clear_token_attribute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_clear_token_attribute::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_update_class_properties() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_class_properties::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_lock_class_properties() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_lock_class_properties::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_transfer_class_ownership() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_class_ownership::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_add_issuer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_issuer::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_remove_issuer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_issuer::<Runtime>());
+		});
+	}
 }
@@ -12,7 +12,7 @@ use frame_support::{
 	RuntimeDebug,
 };
 use orml_traits::parameter_type_with_key;
-use primitives::{evm::EvmAddress, mocks::MockAddressMapping, Amount, BlockNumber, CurrencyId};
+use primitives::{evm::EvmAddress, mocks::MockAddressMapping, Amount, AuctionId, BlockNumber, CurrencyId};
 use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
 	testing::Header,
@@ -202,19 +202,47 @@ impl module_currencies::Config for Runtime {
 	type EVMBridge = MockEVMBridge;
 }
 
+pub type Currencies = module_currencies::Module<Runtime>;
+
+impl orml_auction::Config for Runtime {
+	type Event = ();
+	type Balance = Balance;
+	type AuctionId = AuctionId;
+	type Handler = NFTModule;
+	type WeightInfo = ();
+}
+pub type AuctionModule = orml_auction::Module<Runtime>;
+
 parameter_types! {
 	pub const CreateClassDeposit: Balance = 200;
 	pub const CreateTokenDeposit: Balance = 100;
+	pub const MaxRoyaltyPerMille: u16 = 100;
+	pub const MarketFeePerMille: u16 = 20;
+	pub const DataDepositPerByte: Balance = 10;
+	pub const MaxAttributeKeyLength: u32 = 8;
+	pub const MaxAttributeValueLength: u32 = 16;
+	pub const MaxMintRecipients: u32 = 1000;
 	pub const NftModuleId: ModuleId = ModuleId(*b"aca/aNFT");
+	pub const TreasuryAccount: AccountId = AccountId::new([10u8; 32]);
 }
 impl module_nft::Config for Runtime {
 	type Event = ();
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type ModuleId = NftModuleId;
+	type MaxRoyaltyPerMille = MaxRoyaltyPerMille;
 	type Currency = NativeCurrency;
+	type MultiCurrency = Currencies;
+	type Auction = AuctionModule;
+	type MarketFeePerMille = MarketFeePerMille;
+	type TreasuryAccount = TreasuryAccount;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxMintRecipients = MaxMintRecipients;
 	type WeightInfo = ();
 }
+pub type NFTModule = module_nft::Module<Runtime>;
 
 impl orml_nft::Config for Runtime {
 	type ClassId = u32;
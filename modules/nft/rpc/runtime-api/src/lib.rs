@@ -0,0 +1,21 @@
+//! Runtime API definition for nft module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait NFTApi<AccountId, ClassId, TokenId> where
+		AccountId: Codec,
+		ClassId: Codec,
+		TokenId: Codec,
+	{
+		/// The NFT tokens owned by `owner`, skipping the first `start` ones
+		/// and returning at most `limit`.
+		fn nfts_by_owner(owner: AccountId, start: u32, limit: u32) -> Vec<(ClassId, TokenId)>;
+	}
+}
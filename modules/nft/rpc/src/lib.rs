@@ -0,0 +1,77 @@
+//! RPC interface for the nft module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as NFTClient;
+pub use module_nft_rpc_runtime_api::NFTApi as NFTRuntimeApi;
+
+#[rpc]
+pub trait NFTApi<BlockHash, AccountId, ResponseType> {
+	#[rpc(name = "nft_nftsByOwner")]
+	fn nfts_by_owner(&self, owner: AccountId, start: u32, limit: u32, at: Option<BlockHash>) -> Result<ResponseType>;
+}
+
+/// A struct that implements the [`NFTApi`].
+pub struct NFT<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> NFT<C, B> {
+	/// Create new `NFT` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		NFT {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, ClassId, TokenId> NFTApi<<Block as BlockT>::Hash, AccountId, Vec<(ClassId, TokenId)>>
+	for NFT<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: NFTRuntimeApi<Block, AccountId, ClassId, TokenId>,
+	AccountId: Codec,
+	ClassId: Codec,
+	TokenId: Codec,
+{
+	fn nfts_by_owner(
+		&self,
+		owner: AccountId,
+		start: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(ClassId, TokenId)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.nfts_by_owner(&at, owner, start, limit).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get nfts by owner.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
@@ -5,18 +5,22 @@
 use enumflags2::BitFlags;
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, ExistenceRequirement::KeepAlive},
+	traits::{BalanceStatus, Currency, ExistenceRequirement::KeepAlive},
 	transactional,
 };
 use frame_system::pallet_prelude::*;
-use orml_traits::{BasicCurrency, BasicReservableCurrency, NFT};
-use primitives::{Balance, NFTBalance};
+use orml_traits::{
+	Auction, AuctionHandler, BasicCurrency, BasicReservableCurrency, Change, MultiCurrency, MultiReservableCurrency,
+	OnNewBidResult, NFT,
+};
+use primitives::{AuctionId, Balance, CurrencyId, NFTBalance};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
 	traits::{AccountIdConversion, StaticLookup, Zero},
-	DispatchResult, ModuleId, RuntimeDebug,
+	DispatchResult, ModuleId, Permill, RuntimeDebug,
 };
+use sp_std::vec::Vec;
 
 mod default_weight;
 mod mock;
@@ -27,12 +31,27 @@ pub use module::*;
 pub trait WeightInfo {
 	fn create_class() -> Weight;
 	fn mint(i: u32) -> Weight;
+	fn mint_to_many(i: u32) -> Weight;
 	fn transfer() -> Weight;
 	fn burn() -> Weight;
 	fn destroy_class() -> Weight;
+	fn list() -> Weight;
+	fn unlist() -> Weight;
+	fn buy() -> Weight;
+	fn create_auction() -> Weight;
+	fn set_class_attribute() -> Weight;
+	fn clear_class_attribute() -> Weight;
+	fn set_token_attribute() -> Weight;
+	fn clear_token_attribute() -> Weight;
+	fn update_class_properties() -> Weight;
+	fn lock_class_properties() -> Weight;
+	fn reap() -> Weight;
+	fn transfer_class_ownership() -> Weight;
+	fn add_issuer() -> Weight;
+	fn remove_issuer() -> Weight;
 }
 
-pub type CID = sp_std::vec::Vec<u8>;
+pub type CID = Vec<u8>;
 
 #[repr(u8)]
 #[derive(Encode, Decode, Clone, Copy, BitFlags, RuntimeDebug, PartialEq, Eq)]
@@ -41,6 +60,8 @@ pub enum ClassProperty {
 	Transferable = 0b00000001,
 	/// Token can be burned
 	Burnable = 0b00000010,
+	/// Token can be minted
+	Mintable = 0b00000100,
 }
 
 #[derive(Clone, Copy, PartialEq, Default, RuntimeDebug)]
@@ -78,8 +99,45 @@ pub struct TokenData {
 	pub deposit: Balance,
 }
 
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Royalty<AccountId> {
+	/// The account which receives the royalty
+	pub beneficiary: AccountId,
+	/// The royalty rate of the sale price, in per-mille
+	pub per_mille: u16,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Listing {
+	/// The currency to pay the price
+	pub currency_id: CurrencyId,
+	/// The sale price
+	pub price: Balance,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct NFTAuction<AccountId, ClassId, TokenId> {
+	/// The owner of the token in auction
+	pub seller: AccountId,
+	/// (class_id, token_id)
+	pub token: (ClassId, TokenId),
+	/// The currency to bid
+	pub currency_id: CurrencyId,
+	/// The minimum bid price
+	pub reserve_price: Balance,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Attribute {
+	/// The attribute value
+	pub value: Vec<u8>,
+	/// The storage deposit of the attribute, reserved from the class owner
+	pub deposit: Balance,
+}
+
 pub type TokenIdOf<T> = <T as orml_nft::Config>::TokenId;
 pub type ClassIdOf<T> = <T as orml_nft::Config>::ClassId;
+pub type NFTAuctionOf<T> = NFTAuction<<T as frame_system::Config>::AccountId, ClassIdOf<T>, TokenIdOf<T>>;
 
 #[frame_support::pallet]
 pub mod module {
@@ -103,10 +161,43 @@ pub mod module {
 		#[pallet::constant]
 		type ModuleId: Get<ModuleId>;
 
+		/// The max royalty rate of class, in per-mille
+		#[pallet::constant]
+		type MaxRoyaltyPerMille: Get<u16>;
+
 		///  Currency type for reserve/unreserve balance to
 		/// create_class/mint/burn/destroy_class
 		type Currency: BasicReservableCurrency<Self::AccountId, Balance = Balance>;
 
+		/// Currency to pay for the tokens on sale and hold the bids in reserve
+		type MultiCurrency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// Auction to run the English auctions of tokens
+		type Auction: Auction<Self::AccountId, Self::BlockNumber, AuctionId = AuctionId, Balance = Balance>;
+
+		/// The marketplace fee rate of the sale price, in per-mille
+		#[pallet::constant]
+		type MarketFeePerMille: Get<u16>;
+
+		/// The account which receives the marketplace fee
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The storage deposit per byte of class and token attributes
+		#[pallet::constant]
+		type DataDepositPerByte: Get<Balance>;
+
+		/// The max length of attribute key
+		#[pallet::constant]
+		type MaxAttributeKeyLength: Get<u32>;
+
+		/// The max length of attribute value
+		#[pallet::constant]
+		type MaxAttributeValueLength: Get<u32>;
+
+		/// The max count of recipients to mint tokens to in a batch
+		#[pallet::constant]
+		type MaxMintRecipients: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -121,13 +212,53 @@ pub mod module {
 		NoPermission,
 		/// Quantity is invalid. need >= 1
 		InvalidQuantity,
+		/// The count of metadata is neither one nor the quantity
+		InvalidMetadata,
+		/// The count of recipients exceeds the max count in a batch
+		TooManyRecipients,
 		/// Property of class don't support transfer
 		NonTransferable,
 		/// Property of class don't support burn
 		NonBurnable,
+		/// Property of class don't support mint
+		NonMintable,
+		/// The new properties of class are not a subset of the current ones
+		CannotGrantProperties,
 		/// Can not destroy class
 		/// Total issuance is not 0
 		CannotDestroyClass,
+		/// The royalty rate exceeds the max royalty rate
+		RoyaltyTooHigh,
+		/// The token is not for sale
+		NotForSale,
+		/// The sale price exceeds the max price of buyer
+		PriceTooHigh,
+		/// The token is in auction
+		TokenInAuction,
+		/// The auction duration is zero
+		InvalidAuctionDuration,
+		/// The auction is not found
+		AuctionNotExists,
+		/// The bid price is below the reserve price or the last bid
+		InvalidBidPrice,
+		/// The attribute key exceeds the max length
+		AttributeKeyTooLong,
+		/// The attribute value exceeds the max length
+		AttributeValueTooLong,
+		/// The attribute is not found
+		AttributeNotFound,
+		/// The expiry block is not in the future
+		InvalidExpiry,
+		/// The token is expired
+		TokenExpired,
+		/// The token is not expired
+		TokenNotExpired,
+		/// The account is already an issuer of the class
+		IssuerAlreadyExists,
+		/// The account is not an issuer of the class
+		IssuerNotFound,
+		/// The properties of class are locked and can not be updated
+		ClassPropertiesLocked,
 	}
 
 	#[pallet::event]
@@ -137,19 +268,156 @@ pub mod module {
 		CreatedClass(T::AccountId, ClassIdOf<T>),
 		/// Minted NFT token. \[from, to, class_id, quantity\]
 		MintedToken(T::AccountId, T::AccountId, ClassIdOf<T>, u32),
+		/// Minted NFT token to each of the recipients. \[from, class_id,
+		/// quantity\]
+		MintedTokenToMany(T::AccountId, ClassIdOf<T>, u32),
 		/// Transferred NFT token. \[from, to, class_id, token_id\]
 		TransferredToken(T::AccountId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
 		/// Burned NFT token. \[owner, class_id, token_id\]
 		BurnedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
 		/// Destroyed NFT class. \[owner, class_id, dest\]
 		DestroyedClass(T::AccountId, ClassIdOf<T>, T::AccountId),
+		/// Updated properties of NFT class. \[owner, class_id, properties\]
+		UpdatedClassProperties(T::AccountId, ClassIdOf<T>, Properties),
+		/// Locked properties of NFT class. \[owner, class_id\]
+		LockedClassProperties(T::AccountId, ClassIdOf<T>),
+		/// Listed NFT token for sale. \[owner, class_id, token_id,
+		/// currency_id, price\]
+		Listed(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, CurrencyId, Balance),
+		/// Cancelled the listing of NFT token. \[owner, class_id, token_id\]
+		Unlisted(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Sold NFT token. \[seller, buyer, class_id, token_id, currency_id,
+		/// price, royalty, fee\]
+		SoldToken(
+			T::AccountId,
+			T::AccountId,
+			ClassIdOf<T>,
+			TokenIdOf<T>,
+			CurrencyId,
+			Balance,
+			Balance,
+			Balance,
+		),
+		/// Created auction for NFT token. \[seller, class_id, token_id,
+		/// auction_id, currency_id, reserve_price\]
+		AuctionCreated(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, AuctionId, CurrencyId, Balance),
+		/// The auction ended without sale, the token is released to the
+		/// seller. \[auction_id\]
+		AuctionUnsold(AuctionId),
+		/// Set attribute of NFT class. \[class_id, key, value\]
+		ClassAttributeSet(ClassIdOf<T>, Vec<u8>, Vec<u8>),
+		/// Cleared attribute of NFT class. \[class_id, key\]
+		ClassAttributeCleared(ClassIdOf<T>, Vec<u8>),
+		/// Set attribute of NFT token. \[class_id, token_id, key, value\]
+		TokenAttributeSet(ClassIdOf<T>, TokenIdOf<T>, Vec<u8>, Vec<u8>),
+		/// Cleared attribute of NFT token. \[class_id, token_id, key\]
+		TokenAttributeCleared(ClassIdOf<T>, TokenIdOf<T>, Vec<u8>),
+		/// Reaped expired NFT token. \[who, class_id, token_id\]
+		ReapedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Transferred ownership of NFT class. \[class_id, new_owner\]
+		TransferredClassOwnership(ClassIdOf<T>, T::AccountId),
+		/// Added issuer of NFT class. \[class_id, issuer\]
+		AddedIssuer(ClassIdOf<T>, T::AccountId),
+		/// Removed issuer of NFT class. \[class_id, issuer\]
+		RemovedIssuer(ClassIdOf<T>, T::AccountId),
 	}
 
+	/// The royalty paid to the creator of class on the sales of its tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn class_royalties)]
+	pub type ClassRoyalties<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, Royalty<T::AccountId>, OptionQuery>;
+
+	/// The NFT tokens listed for sale at fixed price by their owners.
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	pub type Listings<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, Listing, OptionQuery>;
+
+	/// The auctions of NFT tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Twox64Concat, AuctionId, NFTAuctionOf<T>, OptionQuery>;
+
+	/// The NFT tokens in auction, which can not be transferred or burned
+	/// until the auction ended.
+	#[pallet::storage]
+	#[pallet::getter(fn token_auctions)]
+	pub type TokenAuctions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, AuctionId, OptionQuery>;
+
+	/// The expiry block of NFT tokens, from which the tokens can not be
+	/// transferred and can be reaped by anyone.
+	#[pallet::storage]
+	#[pallet::getter(fn token_expiry)]
+	pub type TokenExpiries<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, T::BlockNumber, OptionQuery>;
+
+	/// The accounts permitted to mint into NFT classes besides the class
+	/// owner.
+	#[pallet::storage]
+	#[pallet::getter(fn class_issuers)]
+	pub type ClassIssuers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// The attributes of NFT classes.
+	#[pallet::storage]
+	#[pallet::getter(fn class_attributes)]
+	pub type ClassAttributes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Blake2_128Concat, Vec<u8>, Attribute, OptionQuery>;
+
+	/// The attributes of NFT tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn token_attributes)]
+	pub type TokenAttributes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(ClassIdOf<T>, TokenIdOf<T>),
+		Blake2_128Concat,
+		Vec<u8>,
+		Attribute,
+		OptionQuery,
+	>;
+
+	/// The NFT classes whose properties are locked and can never be
+	/// revoked, e.g. to be accepted as collateral.
+	#[pallet::storage]
+	#[pallet::getter(fn locked_classes)]
+	pub type LockedClasses<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, (), OptionQuery>;
+
+	/// Whether the classes created before `Mintable` is introduced have been
+	/// granted `Mintable`.
+	#[pallet::storage]
+	pub(crate) type MintableGranted<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Grant `Mintable` to the classes created before it's introduced, as
+		/// it's required to mint. It only runs once, so the classes revoked
+		/// `Mintable` later are kept.
+		fn on_runtime_upgrade() -> Weight {
+			if MintableGranted::<T>::get() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let class_ids = orml_nft::Classes::<T>::iter()
+				.map(|(class_id, _)| class_id)
+				.collect::<Vec<_>>();
+			for class_id in &class_ids {
+				orml_nft::Classes::<T>::mutate(class_id, |maybe_class_info| {
+					if let Some(class_info) = maybe_class_info {
+						class_info.data.properties.0.insert(ClassProperty::Mintable);
+					}
+				});
+			}
+			MintableGranted::<T>::put(true);
+
+			let count = class_ids.len() as Weight;
+			T::DbWeight::get().reads_writes(count.saturating_mul(2).saturating_add(1), count.saturating_add(1))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -157,10 +425,24 @@ pub mod module {
 		///
 		/// - `metadata`: external metadata
 		/// - `properties`: class property, include `Transferable` `Burnable`
+		///   `Mintable`
+		/// - `royalty`: the royalty paid on the sales of tokens, `None` means
+		///   no royalty
 		#[pallet::weight(<T as Config>::WeightInfo::create_class())]
 		#[transactional]
-		pub fn create_class(origin: OriginFor<T>, metadata: CID, properties: Properties) -> DispatchResultWithPostInfo {
+		pub fn create_class(
+			origin: OriginFor<T>,
+			metadata: CID,
+			properties: Properties,
+			royalty: Option<Royalty<T::AccountId>>,
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			if let Some(royalty) = &royalty {
+				ensure!(
+					royalty.per_mille <= T::MaxRoyaltyPerMille::get(),
+					Error::<T>::RoyaltyTooHigh
+				);
+			}
 			let next_id = orml_nft::Module::<T>::next_class_id();
 			let owner: T::AccountId = T::ModuleId::get().into_sub_account(next_id);
 			let deposit = T::CreateClassDeposit::get();
@@ -175,46 +457,98 @@ pub mod module {
 			<T as pallet_proxy::Config>::Currency::transfer(&who, &owner, proxy_deposit, KeepAlive)?;
 			<pallet_proxy::Module<T>>::add_proxy_delegate(&owner, who, Default::default(), Zero::zero())?;
 
+			// classes created by now have the `Mintable` property on demand
+			if !MintableGranted::<T>::get() {
+				MintableGranted::<T>::put(true);
+			}
+
 			let data = ClassData { deposit, properties };
 			orml_nft::Module::<T>::create_class(&owner, metadata, data)?;
+			if let Some(royalty) = royalty {
+				ClassRoyalties::<T>::insert(next_id, royalty);
+			}
 
 			Self::deposit_event(Event::CreatedClass(owner, next_id));
 			Ok(().into())
 		}
 
-		/// Mint NFT token
+		/// Mint NFT tokens with sequential token ids
 		///
 		/// - `to`: the token owner's account
 		/// - `class_id`: token belong to the class id
-		/// - `metadata`: external metadata
+		/// - `metadata`: external metadata, either one shared by all the
+		///   tokens, or one for each token in the order of token ids
 		/// - `quantity`: token quantity
+		/// - `expiry`: the block from which the tokens are expired, `None`
+		///   means never expire
 		#[pallet::weight(<T as Config>::WeightInfo::mint(*quantity))]
 		#[transactional]
 		pub fn mint(
 			origin: OriginFor<T>,
 			to: <T::Lookup as StaticLookup>::Source,
 			class_id: ClassIdOf<T>,
-			metadata: CID,
+			metadata: Vec<CID>,
 			quantity: u32,
+			expiry: Option<T::BlockNumber>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(to)?;
 			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
-			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
-			ensure!(who == class_info.owner, Error::<T>::NoPermission);
-			let deposit = T::CreateTokenDeposit::get();
-			let total_deposit = deposit * (quantity as u128);
-			<T as Config>::Currency::reserve(&class_info.owner, total_deposit)?;
+			ensure!(
+				metadata.len() == 1 || metadata.len() == quantity as usize,
+				Error::<T>::InvalidMetadata
+			);
+			let data = Self::reserve_mint_deposit(&who, class_id, quantity, expiry)?;
 
-			let data = TokenData { deposit };
-			for _ in 0..quantity {
-				orml_nft::Module::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+			for token_metadata in metadata.iter().cycle().take(quantity as usize) {
+				let token_id = orml_nft::Module::<T>::mint(&to, class_id, token_metadata.clone(), data.clone())?;
+				if let Some(expiry) = expiry {
+					TokenExpiries::<T>::insert(class_id, token_id, expiry);
+				}
 			}
 
 			Self::deposit_event(Event::MintedToken(who, to, class_id, quantity));
 			Ok(().into())
 		}
 
+		/// Mint one NFT token to each of the recipients, e.g. to airdrop
+		/// tokens to the attendees of an event
+		///
+		/// - `class_id`: token belong to the class id
+		/// - `recipients`: the token owners' accounts, at most
+		///   `MaxMintRecipients` in a batch
+		/// - `metadata`: external metadata shared by all the tokens
+		/// - `expiry`: the block from which the tokens are expired, `None`
+		///   means never expire
+		#[pallet::weight(<T as Config>::WeightInfo::mint_to_many(recipients.len() as u32))]
+		#[transactional]
+		pub fn mint_to_many(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			recipients: Vec<T::AccountId>,
+			metadata: CID,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!recipients.is_empty(), Error::<T>::InvalidQuantity);
+			ensure!(
+				recipients.len() <= T::MaxMintRecipients::get() as usize,
+				Error::<T>::TooManyRecipients
+			);
+			let quantity = recipients.len() as u32;
+			let data = Self::reserve_mint_deposit(&who, class_id, quantity, expiry)?;
+
+			for to in recipients.iter() {
+				let token_id = orml_nft::Module::<T>::mint(to, class_id, metadata.clone(), data.clone())?;
+				if let Some(expiry) = expiry {
+					TokenExpiries::<T>::insert(class_id, token_id, expiry);
+				}
+			}
+
+			Self::deposit_event(Event::MintedTokenToMany(who, class_id, quantity));
+			Ok(().into())
+		}
+
 		/// Transfer NFT token to another account
 		///
 		/// - `to`: the token owner's account
@@ -248,8 +582,12 @@ pub mod module {
 
 			let token_info = orml_nft::Module::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 			ensure!(who == token_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				!TokenAuctions::<T>::contains_key(token.0, token.1),
+				Error::<T>::TokenInAuction
+			);
 
-			orml_nft::Module::<T>::burn(&who, token)?;
+			Self::do_burn(&who, token)?;
 			let owner: T::AccountId = T::ModuleId::get().into_sub_account(token.0);
 			let data = token_info.data;
 			// `repatriate_reserved` will check `to` account exist and return `DeadAccount`.
@@ -261,6 +599,32 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Reap the expired NFT token, the deposits of the token are
+		/// refunded to the class owner
+		///
+		/// The dispatch origin of this call must be `Signed`, anyone can
+		/// reap.
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::reap())]
+		#[transactional]
+		pub fn reap(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token_info = orml_nft::Module::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+			ensure!(Self::is_expired(token), Error::<T>::TokenNotExpired);
+			ensure!(
+				!TokenAuctions::<T>::contains_key(token.0, token.1),
+				Error::<T>::TokenInAuction
+			);
+
+			Self::do_burn(&token_info.owner, token)?;
+			let owner: T::AccountId = T::ModuleId::get().into_sub_account(token.0);
+			<T as Config>::Currency::unreserve(&owner, token_info.data.deposit);
+
+			Self::deposit_event(Event::ReapedToken(who, token.0, token.1));
+			Ok(().into())
+		}
+
 		/// Destroy NFT class
 		///
 		/// - `class_id`: destroy class id
@@ -283,23 +647,455 @@ pub mod module {
 
 			let owner: T::AccountId = T::ModuleId::get().into_sub_account(class_id);
 			let data = class_info.data;
+			let deposit = ClassAttributes::<T>::drain_prefix(class_id)
+				.fold(data.deposit, |total: Balance, (_, attribute)| {
+					total.saturating_add(attribute.deposit)
+				});
 			// `repatriate_reserved` will check `to` account exist and return `DeadAccount`.
 			// `transfer` not do this check.
-			<T as Config>::Currency::unreserve(&owner, data.deposit);
-			<T as Config>::Currency::transfer(&owner, &dest, data.deposit)?;
+			<T as Config>::Currency::unreserve(&owner, deposit);
+			<T as Config>::Currency::transfer(&owner, &dest, deposit)?;
 
 			// transfer all free from origin to dest
 			orml_nft::Module::<T>::destroy_class(&who, class_id)?;
+			ClassRoyalties::<T>::remove(class_id);
+			ClassIssuers::<T>::remove_prefix(class_id);
+			LockedClasses::<T>::remove(class_id);
 
 			Self::deposit_event(Event::DestroyedClass(who, class_id, dest));
 			Ok(().into())
 		}
+
+		/// Update properties of NFT class, the properties can only be revoked
+		/// and never granted again, e.g. revoke `Mintable` to fix the supply,
+		/// revoke `Transferable` to make the tokens soul-bound.
+		///
+		/// - `class_id`: the class id
+		/// - `properties`: the new class property, must be a subset of the
+		///   current one
+		#[pallet::weight(<T as Config>::WeightInfo::update_class_properties())]
+		#[transactional]
+		pub fn update_class_properties(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			properties: Properties,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			orml_nft::Classes::<T>::try_mutate(class_id, |maybe_class_info| -> DispatchResult {
+				let class_info = maybe_class_info.as_mut().ok_or(Error::<T>::ClassIdNotFound)?;
+				ensure!(who == class_info.owner, Error::<T>::NoPermission);
+				ensure!(
+					!LockedClasses::<T>::contains_key(class_id),
+					Error::<T>::ClassPropertiesLocked
+				);
+				ensure!(
+					class_info.data.properties.0.contains(properties.0),
+					Error::<T>::CannotGrantProperties
+				);
+
+				class_info.data.properties = properties;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::UpdatedClassProperties(who, class_id, properties));
+			Ok(().into())
+		}
+
+		/// Lock properties of NFT class, the properties can never be updated
+		/// again, e.g. to guarantee the tokens locked as collateral stay
+		/// transferable.
+		///
+		/// - `class_id`: the class id
+		#[pallet::weight(<T as Config>::WeightInfo::lock_class_properties())]
+		#[transactional]
+		pub fn lock_class_properties(origin: OriginFor<T>, class_id: ClassIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				!LockedClasses::<T>::contains_key(class_id),
+				Error::<T>::ClassPropertiesLocked
+			);
+
+			LockedClasses::<T>::insert(class_id, ());
+			Self::deposit_event(Event::LockedClassProperties(who, class_id));
+			Ok(().into())
+		}
+
+		/// Transfer ownership of NFT class, the proxies of the class owner
+		/// account are replaced by `new_owner`, e.g. to rotate operational
+		/// keys
+		///
+		/// - `class_id`: the class id
+		/// - `new_owner`: the account to control the class owner account
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_class_ownership())]
+		#[transactional]
+		pub fn transfer_class_ownership(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			new_owner: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let new_owner = T::Lookup::lookup(new_owner)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			for proxy in <pallet_proxy::Module<T>>::proxies(&who).0 {
+				<pallet_proxy::Module<T>>::remove_proxy_delegate(&who, proxy.delegate, proxy.proxy_type, proxy.delay)?;
+			}
+			<pallet_proxy::Module<T>>::add_proxy_delegate(&who, new_owner.clone(), Default::default(), Zero::zero())?;
+
+			Self::deposit_event(Event::TransferredClassOwnership(class_id, new_owner));
+			Ok(().into())
+		}
+
+		/// Add issuer of NFT class, which is permitted to mint into the
+		/// class, the deposit of tokens is still paid by the class owner
+		///
+		/// - `class_id`: the class id
+		/// - `issuer`: the issuer's account
+		#[pallet::weight(<T as Config>::WeightInfo::add_issuer())]
+		#[transactional]
+		pub fn add_issuer(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			issuer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				!ClassIssuers::<T>::contains_key(class_id, &issuer),
+				Error::<T>::IssuerAlreadyExists
+			);
+
+			ClassIssuers::<T>::insert(class_id, &issuer, ());
+			Self::deposit_event(Event::AddedIssuer(class_id, issuer));
+			Ok(().into())
+		}
+
+		/// Remove issuer of NFT class
+		///
+		/// - `class_id`: the class id
+		/// - `issuer`: the issuer's account
+		#[pallet::weight(<T as Config>::WeightInfo::remove_issuer())]
+		#[transactional]
+		pub fn remove_issuer(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			issuer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				ClassIssuers::<T>::contains_key(class_id, &issuer),
+				Error::<T>::IssuerNotFound
+			);
+
+			ClassIssuers::<T>::remove(class_id, &issuer);
+			Self::deposit_event(Event::RemovedIssuer(class_id, issuer));
+			Ok(().into())
+		}
+
+		/// List NFT token for sale at fixed price, the listing is cancelled
+		/// once the token changes hands
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `price`: the sale price
+		/// - `currency_id`: the currency to pay the price
+		#[pallet::weight(<T as Config>::WeightInfo::list())]
+		#[transactional]
+		pub fn list(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			#[pallet::compact] price: Balance,
+			currency_id: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_tradable(&who, token)?;
+
+			Listings::<T>::insert(token.0, token.1, Listing { currency_id, price });
+
+			Self::deposit_event(Event::Listed(who, token.0, token.1, currency_id, price));
+			Ok(().into())
+		}
+
+		/// Cancel the listing of NFT token
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::unlist())]
+		#[transactional]
+		pub fn unlist(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token_info = orml_nft::Module::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+			ensure!(who == token_info.owner, Error::<T>::NoPermission);
+			ensure!(Listings::<T>::contains_key(token.0, token.1), Error::<T>::NotForSale);
+
+			Listings::<T>::remove(token.0, token.1);
+
+			Self::deposit_event(Event::Unlisted(who, token.0, token.1));
+			Ok(().into())
+		}
+
+		/// Buy NFT token at the listing price, the royalty of class and the
+		/// marketplace fee are paid out of the price
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `max_price`: the max price the buyer is willing to pay
+		#[pallet::weight(<T as Config>::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			#[pallet::compact] max_price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let listing = Self::listings(token.0, token.1).ok_or(Error::<T>::NotForSale)?;
+			ensure!(listing.price <= max_price, Error::<T>::PriceTooHigh);
+			let seller = orml_nft::Module::<T>::tokens(token.0, token.1)
+				.ok_or(Error::<T>::TokenIdNotFound)?
+				.owner;
+
+			let (royalty, fee) = Self::pay_sale(&who, &seller, token.0, listing.currency_id, listing.price, false)?;
+			Self::do_transfer(&seller, &who, token)?;
+
+			Self::deposit_event(Event::SoldToken(
+				seller,
+				who,
+				token.0,
+				token.1,
+				listing.currency_id,
+				listing.price,
+				royalty,
+				fee,
+			));
+			Ok(().into())
+		}
+
+		/// Start an English auction of NFT token, the token can not be
+		/// transferred until the auction ended. The bids are held in reserve
+		/// of bidders, and the highest bid is paid to the seller when the
+		/// auction ended, after the royalty of class and the marketplace fee.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `currency_id`: the currency to bid
+		/// - `reserve_price`: the minimum bid price
+		/// - `duration`: the blocks from now until the auction ended
+		#[pallet::weight(<T as Config>::WeightInfo::create_auction())]
+		#[transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			currency_id: CurrencyId,
+			#[pallet::compact] reserve_price: Balance,
+			duration: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_create_auction(&who, token, currency_id, reserve_price, duration)?;
+			Ok(().into())
+		}
+
+		/// Set attribute of NFT class, the storage deposit is reserved from
+		/// the class owner and refunded when the attribute is cleared
+		///
+		/// - `class_id`: the class id
+		/// - `key`: the attribute key
+		/// - `value`: the attribute value
+		#[pallet::weight(<T as Config>::WeightInfo::set_class_attribute())]
+		#[transactional]
+		pub fn set_class_attribute(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			let old_deposit = Self::class_attributes(class_id, &key).map(|attribute| attribute.deposit);
+			let deposit = Self::update_attribute_deposit(&who, old_deposit, &key, &value)?;
+			ClassAttributes::<T>::insert(
+				class_id,
+				&key,
+				Attribute {
+					value: value.clone(),
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::ClassAttributeSet(class_id, key, value));
+			Ok(().into())
+		}
+
+		/// Clear attribute of NFT class and refund the storage deposit
+		///
+		/// - `class_id`: the class id
+		/// - `key`: the attribute key
+		#[pallet::weight(<T as Config>::WeightInfo::clear_class_attribute())]
+		#[transactional]
+		pub fn clear_class_attribute(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			key: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			let attribute = ClassAttributes::<T>::take(class_id, &key).ok_or(Error::<T>::AttributeNotFound)?;
+			<T as Config>::Currency::unreserve(&who, attribute.deposit);
+
+			Self::deposit_event(Event::ClassAttributeCleared(class_id, key));
+			Ok(().into())
+		}
+
+		/// Set attribute of NFT token, the storage deposit is reserved from
+		/// the class owner and refunded when the attribute is cleared or the
+		/// token is burned
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `key`: the attribute key
+		/// - `value`: the attribute value
+		#[pallet::weight(<T as Config>::WeightInfo::set_token_attribute())]
+		#[transactional]
+		pub fn set_token_attribute(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				orml_nft::Module::<T>::tokens(token.0, token.1).is_some(),
+				Error::<T>::TokenIdNotFound
+			);
+
+			let old_deposit = Self::token_attributes(token, &key).map(|attribute| attribute.deposit);
+			let deposit = Self::update_attribute_deposit(&who, old_deposit, &key, &value)?;
+			TokenAttributes::<T>::insert(
+				token,
+				&key,
+				Attribute {
+					value: value.clone(),
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::TokenAttributeSet(token.0, token.1, key, value));
+			Ok(().into())
+		}
+
+		/// Clear attribute of NFT token and refund the storage deposit
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `key`: the attribute key
+		#[pallet::weight(<T as Config>::WeightInfo::clear_token_attribute())]
+		#[transactional]
+		pub fn clear_token_attribute(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			key: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			let attribute = TokenAttributes::<T>::take(token, &key).ok_or(Error::<T>::AttributeNotFound)?;
+			<T as Config>::Currency::unreserve(&who, attribute.deposit);
+
+			Self::deposit_event(Event::TokenAttributeCleared(token.0, token.1, key));
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
 	/// Ensured atomic.
 	#[transactional]
+	/// Check that `who` is the owner or an issuer of the mintable class and
+	/// `expiry` is in the future, reserve the deposit of `quantity` tokens
+	/// from the class owner, returns the data of each token.
+	fn reserve_mint_deposit(
+		who: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		quantity: u32,
+		expiry: Option<T::BlockNumber>,
+	) -> sp_std::result::Result<TokenData, DispatchError> {
+		if let Some(expiry) = expiry {
+			ensure!(
+				expiry > frame_system::Module::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+		}
+		let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(
+			*who == class_info.owner || ClassIssuers::<T>::contains_key(class_id, who),
+			Error::<T>::NoPermission
+		);
+		ensure!(
+			class_info.data.properties.0.contains(ClassProperty::Mintable),
+			Error::<T>::NonMintable
+		);
+		let deposit = T::CreateTokenDeposit::get();
+		let total_deposit = deposit * (quantity as u128);
+		<T as Config>::Currency::reserve(&class_info.owner, total_deposit)?;
+		Ok(TokenData { deposit })
+	}
+
+	/// Burn the token of `owner`, remove its listing and expiry, and
+	/// refund the deposit of its attributes to the class owner.
+	fn do_burn(owner: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
+		orml_nft::Module::<T>::burn(owner, token)?;
+		Listings::<T>::remove(token.0, token.1);
+		TokenExpiries::<T>::remove(token.0, token.1);
+		let class_owner: T::AccountId = T::ModuleId::get().into_sub_account(token.0);
+		let attributes_deposit = TokenAttributes::<T>::drain_prefix(token)
+			.fold(Zero::zero(), |total: Balance, (_, attribute)| {
+				total.saturating_add(attribute.deposit)
+			});
+		<T as Config>::Currency::unreserve(&class_owner, attributes_deposit);
+		Ok(())
+	}
+
+	/// Get the tokens owned by `owner` from the owner index, skipping the
+	/// first `start` ones and taking at most `limit`. The order is the
+	/// storage order of the index, which is stable as long as the holdings
+	/// don't change.
+	pub fn tokens_by_owner(owner: &T::AccountId, start: u32, limit: u32) -> Vec<(ClassIdOf<T>, TokenIdOf<T>)> {
+		orml_nft::TokensByOwner::<T>::iter_prefix(owner)
+			.skip(start as usize)
+			.take(limit as usize)
+			.map(|(token, _)| token)
+			.collect()
+	}
+
+	/// Check whether the tokens of class are transferable and the properties
+	/// are locked, so they stay transferable forever.
+	pub fn is_always_transferable(class_id: ClassIdOf<T>) -> bool {
+		LockedClasses::<T>::contains_key(class_id)
+			&& orml_nft::Module::<T>::classes(class_id).map_or(false, |class_info| {
+				class_info.data.properties.0.contains(ClassProperty::Transferable)
+			})
+	}
+
+	/// Check whether new tokens can be minted into the class.
+	pub fn is_mintable(class_id: ClassIdOf<T>) -> bool {
+		orml_nft::Module::<T>::classes(class_id).map_or(false, |class_info| {
+			class_info.data.properties.0.contains(ClassProperty::Mintable)
+		})
+	}
+
+	/// Check whether the token is expired at the current block.
+	pub fn is_expired(token: (ClassIdOf<T>, TokenIdOf<T>)) -> bool {
+		Self::token_expiry(token.0, token.1).map_or(false, |expiry| expiry <= frame_system::Module::<T>::block_number())
+	}
+
 	fn do_transfer(from: &T::AccountId, to: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
 		let class_info = orml_nft::Module::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
 		let data = class_info.data;
@@ -310,12 +1106,270 @@ impl<T: Config> Pallet<T> {
 
 		let token_info = orml_nft::Module::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 		ensure!(*from == token_info.owner, Error::<T>::NoPermission);
+		ensure!(!Self::is_expired(token), Error::<T>::TokenExpired);
+		ensure!(
+			!TokenAuctions::<T>::contains_key(token.0, token.1),
+			Error::<T>::TokenInAuction
+		);
 
 		orml_nft::Module::<T>::transfer(from, to, token)?;
+		// the listing is cancelled once the token changes hands
+		Listings::<T>::remove(token.0, token.1);
 
 		Self::deposit_event(Event::TransferredToken(from.clone(), to.clone(), token.0, token.1));
 		Ok(())
 	}
+
+	/// Reserve the storage deposit of attribute from the class owner, the
+	/// difference to `old_deposit` is reserved or refunded when the
+	/// attribute is updated. Returns the new deposit.
+	fn update_attribute_deposit(
+		owner: &T::AccountId,
+		old_deposit: Option<Balance>,
+		key: &[u8],
+		value: &[u8],
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		ensure!(
+			key.len() <= T::MaxAttributeKeyLength::get() as usize,
+			Error::<T>::AttributeKeyTooLong
+		);
+		ensure!(
+			value.len() <= T::MaxAttributeValueLength::get() as usize,
+			Error::<T>::AttributeValueTooLong
+		);
+
+		let deposit = T::DataDepositPerByte::get().saturating_mul((key.len() + value.len()) as Balance);
+		let old_deposit = old_deposit.unwrap_or_else(Zero::zero);
+		if deposit > old_deposit {
+			<T as Config>::Currency::reserve(owner, deposit - old_deposit)?;
+		} else {
+			<T as Config>::Currency::unreserve(owner, old_deposit - deposit);
+		}
+		Ok(deposit)
+	}
+
+	/// Start an English auction of the token owned by `who`. Returns the
+	/// auction id.
+	pub fn do_create_auction(
+		who: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+		currency_id: CurrencyId,
+		reserve_price: Balance,
+		duration: T::BlockNumber,
+	) -> sp_std::result::Result<AuctionId, DispatchError> {
+		ensure!(!duration.is_zero(), Error::<T>::InvalidAuctionDuration);
+		Self::ensure_tradable(who, token)?;
+
+		let now = <frame_system::Module<T>>::block_number();
+		let auction_id = T::Auction::new_auction(now, Some(now + duration))?;
+		Auctions::<T>::insert(
+			auction_id,
+			NFTAuction {
+				seller: who.clone(),
+				token,
+				currency_id,
+				reserve_price,
+			},
+		);
+		TokenAuctions::<T>::insert(token.0, token.1, auction_id);
+		Listings::<T>::remove(token.0, token.1);
+
+		Self::deposit_event(Event::AuctionCreated(
+			who.clone(),
+			token.0,
+			token.1,
+			auction_id,
+			currency_id,
+			reserve_price,
+		));
+		Ok(auction_id)
+	}
+
+	/// Ensure `who` owns the token which is transferable, not expired and not
+	/// in auction.
+	fn ensure_tradable(who: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
+		let class_info = orml_nft::Module::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(
+			class_info.data.properties.0.contains(ClassProperty::Transferable),
+			Error::<T>::NonTransferable
+		);
+		let token_info = orml_nft::Module::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		ensure!(*who == token_info.owner, Error::<T>::NoPermission);
+		ensure!(!Self::is_expired(token), Error::<T>::TokenExpired);
+		ensure!(
+			!TokenAuctions::<T>::contains_key(token.0, token.1),
+			Error::<T>::TokenInAuction
+		);
+		Ok(())
+	}
+
+	/// Pay `price` from `buyer` for the token of `class_id`, the royalty of
+	/// class and the marketplace fee are deducted from the proceeds of
+	/// `seller`. Pay from the reserved balance of `buyer` if `from_reserved`.
+	/// Returns `(royalty, fee)`.
+	fn pay_sale(
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		currency_id: CurrencyId,
+		price: Balance,
+		from_reserved: bool,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let mut royalty: Balance = Zero::zero();
+		if let Some(class_royalty) = Self::class_royalties(class_id) {
+			royalty = Self::per_mille_of(class_royalty.per_mille, price);
+			Self::pay(currency_id, buyer, &class_royalty.beneficiary, royalty, from_reserved)?;
+		}
+		let fee = Self::per_mille_of(T::MarketFeePerMille::get(), price).min(price.saturating_sub(royalty));
+		Self::pay(currency_id, buyer, &T::TreasuryAccount::get(), fee, from_reserved)?;
+		Self::pay(
+			currency_id,
+			buyer,
+			seller,
+			price.saturating_sub(royalty).saturating_sub(fee),
+			from_reserved,
+		)?;
+		Ok((royalty, fee))
+	}
+
+	fn pay(
+		currency_id: CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Balance,
+		from_reserved: bool,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		if from_reserved {
+			let remaining = T::MultiCurrency::repatriate_reserved(currency_id, from, to, amount, BalanceStatus::Free)?;
+			ensure!(remaining.is_zero(), Error::<T>::InvalidBidPrice);
+			Ok(())
+		} else {
+			T::MultiCurrency::transfer(currency_id, from, to, amount)
+		}
+	}
+
+	fn per_mille_of(per_mille: u16, amount: Balance) -> Balance {
+		Permill::from_rational_approximation(u32::from(per_mille), 1000u32) * amount
+	}
+
+	/// Handles the new bid of NFT auction, the bid is held in reserve of the
+	/// bidder, and the last bid is released.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	fn auction_bid_handler(
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> DispatchResult {
+		let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let (new_bidder, new_bid_price) = new_bid;
+		ensure!(new_bidder != auction.seller, Error::<T>::NoPermission);
+		ensure!(
+			new_bid_price >= auction.reserve_price
+				&& last_bid.as_ref().map_or(true, |(_, price)| new_bid_price > *price),
+			Error::<T>::InvalidBidPrice
+		);
+
+		if let Some((last_bidder, last_bid_price)) = last_bid {
+			T::MultiCurrency::unreserve(auction.currency_id, &last_bidder, last_bid_price);
+		}
+		T::MultiCurrency::reserve(auction.currency_id, &new_bidder, new_bid_price)
+	}
+
+	/// Pay the winner bid to the seller and transfer the token to the winner.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	fn settle_auction(auction: &NFTAuctionOf<T>, winner: &T::AccountId, price: Balance) -> DispatchResult {
+		let (royalty, fee) = Self::pay_sale(
+			winner,
+			&auction.seller,
+			auction.token.0,
+			auction.currency_id,
+			price,
+			true,
+		)?;
+		Self::do_transfer(&auction.seller, winner, auction.token)?;
+
+		Self::deposit_event(Event::SoldToken(
+			auction.seller.clone(),
+			winner.clone(),
+			auction.token.0,
+			auction.token.1,
+			auction.currency_id,
+			price,
+			royalty,
+			fee,
+		));
+		Ok(())
+	}
+}
+
+impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId> for Pallet<T> {
+	fn on_new_bid(
+		_now: T::BlockNumber,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> OnNewBidResult<T::BlockNumber> {
+		OnNewBidResult {
+			accept_bid: Self::auction_bid_handler(id, new_bid, last_bid).is_ok(),
+			auction_end_change: Change::NoChange,
+		}
+	}
+
+	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
+		if let Some(auction) = Auctions::<T>::take(id) {
+			TokenAuctions::<T>::remove(auction.token.0, auction.token.1);
+
+			let sold = winner.map_or(false, |(winner, price)| {
+				let settled = Self::settle_auction(&auction, &winner, price).is_ok();
+				if !settled {
+					T::MultiCurrency::unreserve(auction.currency_id, &winner, price);
+				}
+				settled
+			});
+			if !sold {
+				Self::deposit_event(Event::AuctionUnsold(id));
+			}
+		}
+	}
+}
+
+/// Route the events of orml auction to the NFT module for NFT auctions, and
+/// to `Others` for the rest.
+pub struct AuctionHandlerWithNFT<T, Others>(PhantomData<(T, Others)>);
+
+impl<T, Others> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId> for AuctionHandlerWithNFT<T, Others>
+where
+	T: Config,
+	Others: AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>,
+{
+	fn on_new_bid(
+		now: T::BlockNumber,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> OnNewBidResult<T::BlockNumber> {
+		if Auctions::<T>::contains_key(id) {
+			Pallet::<T>::on_new_bid(now, id, new_bid, last_bid)
+		} else {
+			Others::on_new_bid(now, id, new_bid, last_bid)
+		}
+	}
+
+	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
+		if Auctions::<T>::contains_key(id) {
+			Pallet::<T>::on_auction_ended(id, winner)
+		} else {
+			Others::on_auction_ended(id, winner)
+		}
+	}
 }
 
 impl<T: Config> NFT<T::AccountId> for Pallet<T> {
@@ -11,7 +11,7 @@ use frame_support::{
 	RuntimeDebug,
 };
 use orml_traits::parameter_type_with_key;
-use primitives::{evm::EvmAddress, mocks::MockAddressMapping, Amount, BlockNumber, CurrencyId};
+use primitives::{evm::EvmAddress, mocks::MockAddressMapping, Amount, AuctionId, BlockNumber, CurrencyId, TokenSymbol};
 use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
 	testing::Header,
@@ -176,17 +176,41 @@ impl module_currencies::Config for Runtime {
 	type EVMBridge = MockEVMBridge;
 }
 
+impl orml_auction::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AuctionId = AuctionId;
+	type Handler = NFTModule;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const CreateClassDeposit: Balance = 200;
 	pub const CreateTokenDeposit: Balance = 100;
+	pub const MaxRoyaltyPerMille: u16 = 100;
+	pub const MarketFeePerMille: u16 = 20;
+	pub const DataDepositPerByte: Balance = 10;
+	pub const MaxAttributeKeyLength: u32 = 8;
+	pub const MaxAttributeValueLength: u32 = 16;
+	pub const MaxMintRecipients: u32 = 3;
 	pub const NftModuleId: ModuleId = ModuleId(*b"aca/aNFT");
+	pub const TreasuryAccount: AccountId = TREASURY;
 }
 impl Config for Runtime {
 	type Event = Event;
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type ModuleId = NftModuleId;
+	type MaxRoyaltyPerMille = MaxRoyaltyPerMille;
 	type Currency = NativeCurrency;
+	type MultiCurrency = Currency;
+	type Auction = AuctionModule;
+	type MarketFeePerMille = MarketFeePerMille;
+	type TreasuryAccount = TreasuryAccount;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxMintRecipients = MaxMintRecipients;
 	type WeightInfo = ();
 }
 
@@ -209,18 +233,22 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		NFTModule: nft::{Module, Call, Event<T>},
+		NFTModule: nft::{Module, Call, Storage, Event<T>},
 		OrmlNFT: orml_nft::{Module, Storage, Config<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
 		Utility: pallet_utility::{Module, Call, Event},
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
 		Currency: module_currencies::{Module, Call, Event<T>},
+		AuctionModule: orml_auction::{Module, Call, Storage, Event<T>},
 	}
 );
 
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+pub const TREASURY: AccountId = AccountId::new([10u8; 32]);
+pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const CLASS_ID: <Runtime as orml_nft::Config>::ClassId = 0;
 pub const CLASS_ID_NOT_EXIST: <Runtime as orml_nft::Config>::ClassId = 1;
 pub const TOKEN_ID: <Runtime as orml_nft::Config>::TokenId = 0;
@@ -245,6 +273,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: vec![(ALICE, AUSD, 100000), (CHARLIE, AUSD, 100000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnFinalize};
 use mock::{Event, *};
 
 fn free_balance(who: &AccountId) -> Balance {
@@ -24,7 +24,8 @@ fn create_class_should_work() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Default::default()
+			Default::default(),
+			None
 		));
 		let event = Event::nft(crate::Event::CreatedClass(class_id_account(), CLASS_ID));
 		assert_eq!(last_event(), event);
@@ -43,7 +44,8 @@ fn create_class_should_fail() {
 			NFTModule::create_class(
 				Origin::signed(BOB),
 				vec![1],
-				Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+				None
 			),
 			pallet_balances::Error::<Runtime, _>::InsufficientBalance
 		);
@@ -56,7 +58,8 @@ fn mint_should_work() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
 		));
 		let event = Event::nft(crate::Event::CreatedClass(class_id_account(), CLASS_ID));
 		assert_eq!(last_event(), event);
@@ -70,8 +73,9 @@ fn mint_should_work() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			2
+			vec![vec![1]],
+			2,
+			None
 		));
 		let event = Event::nft(crate::Event::MintedToken(class_id_account(), BOB, CLASS_ID, 2));
 		assert_eq!(last_event(), event);
@@ -85,26 +89,130 @@ fn mint_should_work() {
 	});
 }
 
+#[test]
+fn mint_with_token_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+
+		assert_noop!(
+			NFTModule::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![], 3, None),
+			Error::<Runtime>::InvalidMetadata
+		);
+		assert_noop!(
+			NFTModule::mint(
+				Origin::signed(class_id_account()),
+				BOB,
+				CLASS_ID,
+				vec![vec![1], vec![2]],
+				3,
+				None
+			),
+			Error::<Runtime>::InvalidMetadata
+		);
+
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![vec![1], vec![2], vec![3]],
+			3,
+			None
+		));
+		for token_id in 0..3 {
+			assert_eq!(
+				OrmlNFT::tokens(CLASS_ID, token_id).map(|token_info| token_info.metadata),
+				Some(vec![token_id as u8 + 1])
+			);
+		}
+	});
+}
+
+#[test]
+fn mint_to_many_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+
+		assert_noop!(
+			NFTModule::mint_to_many(Origin::signed(class_id_account()), CLASS_ID, vec![], vec![1], None),
+			Error::<Runtime>::InvalidQuantity
+		);
+		assert_noop!(
+			NFTModule::mint_to_many(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				vec![ALICE, BOB, CHARLIE, ALICE],
+				vec![1],
+				None
+			),
+			Error::<Runtime>::TooManyRecipients
+		);
+		assert_noop!(
+			NFTModule::mint_to_many(Origin::signed(BOB), CLASS_ID, vec![ALICE, BOB], vec![1], None),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::mint_to_many(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			vec![ALICE, BOB, CHARLIE],
+			vec![1],
+			None
+		));
+		let event = Event::nft(crate::Event::MintedTokenToMany(class_id_account(), CLASS_ID, 3));
+		assert_eq!(last_event(), event);
+		for (token_id, owner) in vec![ALICE, BOB, CHARLIE].into_iter().enumerate() {
+			assert_eq!(NFTModule::owner((CLASS_ID, token_id as u64)), Some(owner));
+		}
+		assert_eq!(
+			reserved_balance(&class_id_account()),
+			<Runtime as Config>::CreateClassDeposit::get()
+				+ 3 * <Runtime as Config>::CreateTokenDeposit::get()
+				+ Proxy::deposit(1u32)
+		);
+	});
+}
+
 #[test]
 fn mint_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
 		));
 		assert_noop!(
-			NFTModule::mint(Origin::signed(ALICE), BOB, CLASS_ID_NOT_EXIST, vec![1], 2),
+			NFTModule::mint(Origin::signed(ALICE), BOB, CLASS_ID_NOT_EXIST, vec![vec![1]], 2, None),
 			Error::<Runtime>::ClassIdNotFound
 		);
 
 		assert_noop!(
-			NFTModule::mint(Origin::signed(BOB), BOB, CLASS_ID, vec![1], 0),
+			NFTModule::mint(Origin::signed(BOB), BOB, CLASS_ID, vec![vec![1]], 0, None),
 			Error::<Runtime>::InvalidQuantity
 		);
 
 		assert_noop!(
-			NFTModule::mint(Origin::signed(BOB), BOB, CLASS_ID, vec![1], 2),
+			NFTModule::mint(Origin::signed(BOB), BOB, CLASS_ID, vec![vec![1]], 2, None),
 			Error::<Runtime>::NoPermission
 		);
 
@@ -117,19 +225,179 @@ fn mint_should_fail() {
 			true
 		);
 		assert_noop!(
-			NFTModule::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 2),
+			NFTModule::mint(
+				Origin::signed(class_id_account()),
+				BOB,
+				CLASS_ID,
+				vec![vec![1]],
+				2,
+				None
+			),
 			orml_nft::Error::<Runtime>::NoAvailableTokenId
 		);
 	});
 }
 
+#[test]
+fn update_class_properties_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+			None
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![vec![1]],
+			1,
+			None
+		));
+
+		assert_noop!(
+			NFTModule::update_class_properties(
+				Origin::signed(ALICE),
+				CLASS_ID,
+				Properties(ClassProperty::Transferable.into())
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::update_class_properties(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			),
+			Error::<Runtime>::CannotGrantProperties
+		);
+
+		// fix the supply
+		assert_ok!(NFTModule::update_class_properties(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Properties(ClassProperty::Transferable.into())
+		));
+		let event = Event::nft(crate::Event::UpdatedClassProperties(
+			class_id_account(),
+			CLASS_ID,
+			Properties(ClassProperty::Transferable.into()),
+		));
+		assert_eq!(last_event(), event);
+		assert_noop!(
+			NFTModule::mint(
+				Origin::signed(class_id_account()),
+				BOB,
+				CLASS_ID,
+				vec![vec![1]],
+				1,
+				None
+			),
+			Error::<Runtime>::NonMintable
+		);
+
+		// make the tokens soul-bound
+		assert_ok!(NFTModule::update_class_properties(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Default::default()
+		));
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NonTransferable
+		);
+		assert_noop!(
+			NFTModule::update_class_properties(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				Properties(ClassProperty::Transferable.into())
+			),
+			Error::<Runtime>::CannotGrantProperties
+		);
+	});
+}
+
+#[test]
+fn lock_class_properties_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+			None
+		));
+		assert!(!NFTModule::is_always_transferable(CLASS_ID));
+
+		assert_noop!(
+			NFTModule::lock_class_properties(Origin::signed(ALICE), CLASS_ID),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::lock_class_properties(
+			Origin::signed(class_id_account()),
+			CLASS_ID
+		));
+		let event = Event::nft(crate::Event::LockedClassProperties(class_id_account(), CLASS_ID));
+		assert_eq!(last_event(), event);
+		assert!(NFTModule::is_always_transferable(CLASS_ID));
+
+		assert_noop!(
+			NFTModule::lock_class_properties(Origin::signed(class_id_account()), CLASS_ID),
+			Error::<Runtime>::ClassPropertiesLocked
+		);
+		assert_noop!(
+			NFTModule::update_class_properties(Origin::signed(class_id_account()), CLASS_ID, Default::default()),
+			Error::<Runtime>::ClassPropertiesLocked
+		);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_should_grant_mintable() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable.into()),
+			None
+		));
+		// the class created before `Mintable` is introduced
+		MintableGranted::<Runtime>::kill();
+
+		<NFTModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(
+			OrmlNFT::classes(CLASS_ID).unwrap().data.properties,
+			Properties(ClassProperty::Transferable | ClassProperty::Mintable)
+		);
+		assert_eq!(MintableGranted::<Runtime>::get(), true);
+
+		// only runs once
+		assert_ok!(NFTModule::update_class_properties(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Properties(ClassProperty::Transferable.into())
+		));
+		<NFTModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(
+			OrmlNFT::classes(CLASS_ID).unwrap().data.properties,
+			Properties(ClassProperty::Transferable.into())
+		);
+	});
+}
+
 #[test]
 fn transfer_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
 		));
 		assert_eq!(
 			Balances::deposit_into_existing(&class_id_account(), 2 * <Runtime as Config>::CreateTokenDeposit::get())
@@ -140,8 +408,9 @@ fn transfer_should_work() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			2
+			vec![vec![1]],
+			2,
+			None
 		));
 
 		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
@@ -160,7 +429,8 @@ fn transfer_should_fail() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
 		));
 		assert_eq!(
 			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
@@ -171,8 +441,9 @@ fn transfer_should_fail() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			1
+			vec![vec![1]],
+			1,
+			None
 		));
 		assert_noop!(
 			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID_NOT_EXIST, TOKEN_ID)),
@@ -192,7 +463,8 @@ fn transfer_should_fail() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Default::default()
+			Properties(ClassProperty::Mintable.into()),
+			None
 		));
 		assert_eq!(
 			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
@@ -203,8 +475,9 @@ fn transfer_should_fail() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			1
+			vec![vec![1]],
+			1,
+			None
 		));
 		assert_noop!(
 			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
@@ -219,7 +492,8 @@ fn burn_should_work() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
 		));
 		assert_eq!(
 			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
@@ -230,8 +504,9 @@ fn burn_should_work() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			1
+			vec![vec![1]],
+			1,
+			None
 		));
 		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
 		let event = Event::nft(crate::Event::BurnedToken(BOB, CLASS_ID, TOKEN_ID));
@@ -250,7 +525,8 @@ fn burn_should_fail() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
 		));
 		assert_eq!(
 			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
@@ -261,8 +537,9 @@ fn burn_should_fail() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			1
+			vec![vec![1]],
+			1,
+			None
 		));
 		assert_noop!(
 			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID_NOT_EXIST)),
@@ -287,7 +564,8 @@ fn burn_should_fail() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Default::default()
+			Properties(ClassProperty::Mintable.into()),
+			None
 		));
 		assert_eq!(
 			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
@@ -298,8 +576,9 @@ fn burn_should_fail() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			1
+			vec![vec![1]],
+			1,
+			None
 		));
 		assert_noop!(
 			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)),
@@ -314,7 +593,8 @@ fn destroy_class_should_work() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
 		));
 		assert_eq!(
 			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
@@ -325,8 +605,9 @@ fn destroy_class_should_work() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			1
+			vec![vec![1]],
+			1,
+			None
 		));
 		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
 		assert_ok!(NFTModule::destroy_class(
@@ -349,7 +630,8 @@ fn destroy_class_should_fail() {
 		assert_ok!(NFTModule::create_class(
 			Origin::signed(ALICE),
 			vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
 		));
 		assert_eq!(
 			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
@@ -360,8 +642,9 @@ fn destroy_class_should_fail() {
 			Origin::signed(class_id_account()),
 			BOB,
 			CLASS_ID,
-			vec![1],
-			1
+			vec![vec![1]],
+			1,
+			None
 		));
 		assert_noop!(
 			NFTModule::destroy_class(Origin::signed(class_id_account()), CLASS_ID_NOT_EXIST, BOB),
@@ -386,3 +669,664 @@ fn destroy_class_should_fail() {
 		));
 	});
 }
+
+#[test]
+fn create_class_with_royalty_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTModule::create_class(
+				Origin::signed(ALICE),
+				vec![1],
+				Default::default(),
+				Some(Royalty {
+					beneficiary: CHARLIE,
+					per_mille: 101
+				})
+			),
+			Error::<Runtime>::RoyaltyTooHigh
+		);
+
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Default::default(),
+			Some(Royalty {
+				beneficiary: CHARLIE,
+				per_mille: 100
+			})
+		));
+		assert_eq!(
+			NFTModule::class_royalties(CLASS_ID),
+			Some(Royalty {
+				beneficiary: CHARLIE,
+				per_mille: 100
+			})
+		);
+
+		assert_ok!(NFTModule::destroy_class(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			BOB
+		));
+		assert_eq!(NFTModule::class_royalties(CLASS_ID), None);
+	});
+}
+
+fn mint_token_to_bob(royalty: Option<Royalty<AccountId>>) {
+	assert_ok!(NFTModule::create_class(
+		Origin::signed(ALICE),
+		vec![1],
+		Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+		royalty
+	));
+	assert_eq!(
+		Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+			.is_ok(),
+		true
+	);
+	assert_ok!(NFTModule::mint(
+		Origin::signed(class_id_account()),
+		BOB,
+		CLASS_ID,
+		vec![vec![1]],
+		1,
+		None
+	));
+}
+
+#[test]
+fn list_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_token_to_bob(None);
+
+		assert_noop!(
+			NFTModule::list(Origin::signed(BOB), (CLASS_ID, TOKEN_ID_NOT_EXIST), 1000, AUSD),
+			Error::<Runtime>::TokenIdNotFound
+		);
+		assert_noop!(
+			NFTModule::list(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), 1000, AUSD),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::unlist(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NotForSale
+		);
+
+		assert_ok!(NFTModule::list(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), 1000, AUSD));
+		let event = Event::nft(crate::Event::Listed(BOB, CLASS_ID, TOKEN_ID, AUSD, 1000));
+		assert_eq!(last_event(), event);
+		assert_eq!(
+			NFTModule::listings(CLASS_ID, TOKEN_ID),
+			Some(Listing {
+				currency_id: AUSD,
+				price: 1000
+			})
+		);
+
+		assert_noop!(
+			NFTModule::unlist(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::unlist(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		let event = Event::nft(crate::Event::Unlisted(BOB, CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::listings(CLASS_ID, TOKEN_ID), None);
+
+		// the listing is cancelled after transfer
+		assert_ok!(NFTModule::list(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), 1000, AUSD));
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::listings(CLASS_ID, TOKEN_ID), None);
+	});
+}
+
+#[test]
+fn buy_should_pay_royalty_and_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_token_to_bob(Some(Royalty {
+			beneficiary: CHARLIE,
+			per_mille: 100,
+		}));
+
+		assert_noop!(
+			NFTModule::buy(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), 1000),
+			Error::<Runtime>::NotForSale
+		);
+		assert_ok!(NFTModule::list(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), 1000, AUSD));
+		assert_noop!(
+			NFTModule::buy(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), 999),
+			Error::<Runtime>::PriceTooHigh
+		);
+
+		assert_ok!(NFTModule::buy(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), 1000));
+		let event = Event::nft(crate::Event::SoldToken(
+			BOB, ALICE, CLASS_ID, TOKEN_ID, AUSD, 1000, 100, 20,
+		));
+		assert_eq!(last_event(), event);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 99000);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 880);
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 100100);
+		assert_eq!(Tokens::free_balance(AUSD, &TREASURY), 20);
+		assert_eq!(NFTModule::owner((CLASS_ID, TOKEN_ID)), Some(ALICE));
+		assert_eq!(NFTModule::listings(CLASS_ID, TOKEN_ID), None);
+	});
+}
+
+#[test]
+fn create_auction_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_token_to_bob(None);
+
+		assert_noop!(
+			NFTModule::create_auction(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), AUSD, 500, 0),
+			Error::<Runtime>::InvalidAuctionDuration
+		);
+		assert_noop!(
+			NFTModule::create_auction(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), AUSD, 500, 10),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::list(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), 1000, AUSD));
+		assert_ok!(NFTModule::create_auction(
+			Origin::signed(BOB),
+			(CLASS_ID, TOKEN_ID),
+			AUSD,
+			500,
+			10
+		));
+		let event = Event::nft(crate::Event::AuctionCreated(BOB, CLASS_ID, TOKEN_ID, 0, AUSD, 500));
+		assert_eq!(last_event(), event);
+		assert_eq!(
+			NFTModule::auctions(0),
+			Some(NFTAuction {
+				seller: BOB,
+				token: (CLASS_ID, TOKEN_ID),
+				currency_id: AUSD,
+				reserve_price: 500,
+			})
+		);
+		assert_eq!(NFTModule::token_auctions(CLASS_ID, TOKEN_ID), Some(0));
+		assert_eq!(NFTModule::listings(CLASS_ID, TOKEN_ID), None);
+
+		// the token is locked in auction
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenInAuction
+		);
+		assert_noop!(
+			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenInAuction
+		);
+		assert_noop!(
+			NFTModule::list(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), 1000, AUSD),
+			Error::<Runtime>::TokenInAuction
+		);
+		assert_noop!(
+			NFTModule::create_auction(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), AUSD, 500, 10),
+			Error::<Runtime>::TokenInAuction
+		);
+	});
+}
+
+#[test]
+fn auction_bid_should_reserve() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_token_to_bob(None);
+		assert_ok!(NFTModule::create_auction(
+			Origin::signed(BOB),
+			(CLASS_ID, TOKEN_ID),
+			AUSD,
+			500,
+			10
+		));
+
+		// below the reserve price
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(ALICE), 0, 499),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+		// the seller can not bid
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(BOB), 0, 500),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 500));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 99500);
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 500);
+
+		assert_ok!(AuctionModule::bid(Origin::signed(CHARLIE), 0, 600));
+		assert_eq!(Tokens::reserved_balance(AUSD, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 100000);
+		assert_eq!(Tokens::reserved_balance(AUSD, &CHARLIE), 600);
+	});
+}
+
+#[test]
+fn auction_ended_should_settle() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_token_to_bob(Some(Royalty {
+			beneficiary: ALICE,
+			per_mille: 50,
+		}));
+		assert_ok!(NFTModule::create_auction(
+			Origin::signed(BOB),
+			(CLASS_ID, TOKEN_ID),
+			AUSD,
+			500,
+			10
+		));
+		assert_ok!(AuctionModule::bid(Origin::signed(CHARLIE), 0, 1000));
+
+		AuctionModule::on_finalize(11);
+		let event = Event::nft(crate::Event::SoldToken(
+			BOB, CHARLIE, CLASS_ID, TOKEN_ID, AUSD, 1000, 50, 20,
+		));
+		assert_eq!(last_event(), event);
+		assert_eq!(Tokens::reserved_balance(AUSD, &CHARLIE), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 99000);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 100050);
+		assert_eq!(Tokens::free_balance(AUSD, &TREASURY), 20);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 930);
+		assert_eq!(NFTModule::owner((CLASS_ID, TOKEN_ID)), Some(CHARLIE));
+		assert_eq!(NFTModule::auctions(0), None);
+		assert_eq!(NFTModule::token_auctions(CLASS_ID, TOKEN_ID), None);
+	});
+}
+
+#[test]
+fn auction_ended_without_bid_should_release_token() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_token_to_bob(None);
+		assert_ok!(NFTModule::create_auction(
+			Origin::signed(BOB),
+			(CLASS_ID, TOKEN_ID),
+			AUSD,
+			500,
+			10
+		));
+
+		AuctionModule::on_finalize(11);
+		let event = Event::nft(crate::Event::AuctionUnsold(0));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::owner((CLASS_ID, TOKEN_ID)), Some(BOB));
+		assert_eq!(NFTModule::token_auctions(CLASS_ID, TOKEN_ID), None);
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn class_attribute_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Default::default(),
+			None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), 100).is_ok(), true);
+
+		assert_noop!(
+			NFTModule::set_class_attribute(Origin::signed(ALICE), CLASS_ID, b"k".to_vec(), b"v".to_vec()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::set_class_attribute(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				b"too long key".to_vec(),
+				b"v".to_vec()
+			),
+			Error::<Runtime>::AttributeKeyTooLong
+		);
+		assert_noop!(
+			NFTModule::set_class_attribute(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				b"k".to_vec(),
+				b"value is too long".to_vec()
+			),
+			Error::<Runtime>::AttributeValueTooLong
+		);
+
+		assert_ok!(NFTModule::set_class_attribute(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			b"k".to_vec(),
+			b"v1".to_vec()
+		));
+		let event = Event::nft(crate::Event::ClassAttributeSet(CLASS_ID, b"k".to_vec(), b"v1".to_vec()));
+		assert_eq!(last_event(), event);
+		assert_eq!(
+			NFTModule::class_attributes(CLASS_ID, b"k".to_vec()),
+			Some(Attribute {
+				value: b"v1".to_vec(),
+				deposit: 30
+			})
+		);
+		assert_eq!(
+			reserved_balance(&class_id_account()),
+			<Runtime as Config>::CreateClassDeposit::get() + 30
+		);
+
+		// update the deposit by the length of new value
+		assert_ok!(NFTModule::set_class_attribute(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			b"k".to_vec(),
+			b"v".to_vec()
+		));
+		assert_eq!(
+			reserved_balance(&class_id_account()),
+			<Runtime as Config>::CreateClassDeposit::get() + 20
+		);
+
+		assert_noop!(
+			NFTModule::clear_class_attribute(Origin::signed(class_id_account()), CLASS_ID, b"x".to_vec()),
+			Error::<Runtime>::AttributeNotFound
+		);
+		assert_ok!(NFTModule::clear_class_attribute(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			b"k".to_vec()
+		));
+		let event = Event::nft(crate::Event::ClassAttributeCleared(CLASS_ID, b"k".to_vec()));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::class_attributes(CLASS_ID, b"k".to_vec()), None);
+		assert_eq!(
+			reserved_balance(&class_id_account()),
+			<Runtime as Config>::CreateClassDeposit::get()
+		);
+
+		// the deposit of attributes is refunded when the class is destroyed
+		assert_ok!(NFTModule::set_class_attribute(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			b"k".to_vec(),
+			b"v".to_vec()
+		));
+		assert_ok!(NFTModule::destroy_class(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			BOB
+		));
+		assert_eq!(NFTModule::class_attributes(CLASS_ID, b"k".to_vec()), None);
+		assert_eq!(free_balance(&BOB), <Runtime as Config>::CreateClassDeposit::get() + 20);
+	});
+}
+
+#[test]
+fn token_attribute_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_token_to_bob(None);
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), 100).is_ok(), true);
+		let reserved = reserved_balance(&class_id_account());
+
+		assert_noop!(
+			NFTModule::set_token_attribute(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), b"k".to_vec(), b"v".to_vec()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::set_token_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID_NOT_EXIST),
+				b"k".to_vec(),
+				b"v".to_vec()
+			),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		assert_ok!(NFTModule::set_token_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			b"k".to_vec(),
+			b"v1".to_vec()
+		));
+		let event = Event::nft(crate::Event::TokenAttributeSet(
+			CLASS_ID,
+			TOKEN_ID,
+			b"k".to_vec(),
+			b"v1".to_vec(),
+		));
+		assert_eq!(last_event(), event);
+		assert_eq!(reserved_balance(&class_id_account()), reserved + 30);
+
+		assert_ok!(NFTModule::clear_token_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			b"k".to_vec()
+		));
+		let event = Event::nft(crate::Event::TokenAttributeCleared(CLASS_ID, TOKEN_ID, b"k".to_vec()));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::token_attributes((CLASS_ID, TOKEN_ID), b"k".to_vec()), None);
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+
+		// the deposit of attributes is refunded when the token is burned
+		assert_ok!(NFTModule::set_token_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			b"k".to_vec(),
+			b"v1".to_vec()
+		));
+		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::token_attributes((CLASS_ID, TOKEN_ID), b"k".to_vec()), None);
+		assert_eq!(
+			reserved_balance(&class_id_account()),
+			reserved - <Runtime as Config>::CreateTokenDeposit::get()
+		);
+	});
+}
+
+#[test]
+fn expiring_token_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+			None
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(
+				&class_id_account(),
+				1 * <Runtime as Config>::CreateTokenDeposit::get() + 100
+			)
+			.is_ok(),
+			true
+		);
+		let reserved = reserved_balance(&class_id_account());
+
+		assert_noop!(
+			NFTModule::mint(
+				Origin::signed(class_id_account()),
+				BOB,
+				CLASS_ID,
+				vec![vec![1]],
+				1,
+				Some(1)
+			),
+			Error::<Runtime>::InvalidExpiry
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![vec![1]],
+			1,
+			Some(5)
+		));
+		assert_eq!(NFTModule::token_expiry(CLASS_ID, TOKEN_ID), Some(5));
+		assert_ok!(NFTModule::set_token_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			b"k".to_vec(),
+			b"v1".to_vec()
+		));
+
+		// transferable and can not be reaped before the expiry
+		System::set_block_number(4);
+		assert_noop!(
+			NFTModule::reap(Origin::signed(CHARLIE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenNotExpired
+		);
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+
+		System::set_block_number(5);
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(ALICE), BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenExpired
+		);
+		assert_noop!(
+			NFTModule::list(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), 1000, AUSD),
+			Error::<Runtime>::TokenExpired
+		);
+		assert_noop!(
+			NFTModule::reap(Origin::signed(CHARLIE), (CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		// anyone can reap, the deposits are refunded to the class owner
+		assert_ok!(NFTModule::reap(Origin::signed(CHARLIE), (CLASS_ID, TOKEN_ID)));
+		let event = Event::nft(crate::Event::ReapedToken(CHARLIE, CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::owner((CLASS_ID, TOKEN_ID)), None);
+		assert_eq!(NFTModule::token_expiry(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(NFTModule::token_attributes((CLASS_ID, TOKEN_ID), b"k".to_vec()), None);
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+	});
+}
+
+#[test]
+fn transfer_class_ownership_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+			None
+		));
+		let reserved = reserved_balance(&class_id_account());
+
+		assert_noop!(
+			NFTModule::transfer_class_ownership(Origin::signed(BOB), CLASS_ID, BOB),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::transfer_class_ownership(Origin::signed(class_id_account()), CLASS_ID_NOT_EXIST, BOB),
+			Error::<Runtime>::ClassIdNotFound
+		);
+
+		assert_ok!(NFTModule::transfer_class_ownership(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			BOB
+		));
+		let event = Event::nft(crate::Event::TransferredClassOwnership(CLASS_ID, BOB));
+		assert_eq!(last_event(), event);
+		let delegates: Vec<AccountId> = Proxy::proxies(class_id_account())
+			.0
+			.into_iter()
+			.map(|proxy| proxy.delegate)
+			.collect();
+		assert_eq!(delegates, vec![BOB]);
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+	});
+}
+
+#[test]
+fn class_issuers_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+			None
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		let reserved = reserved_balance(&class_id_account());
+
+		assert_noop!(
+			NFTModule::add_issuer(Origin::signed(BOB), CLASS_ID, CHARLIE),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::add_issuer(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			CHARLIE
+		));
+		let event = Event::nft(crate::Event::AddedIssuer(CLASS_ID, CHARLIE));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::class_issuers(CLASS_ID, CHARLIE), Some(()));
+		assert_noop!(
+			NFTModule::add_issuer(Origin::signed(class_id_account()), CLASS_ID, CHARLIE),
+			Error::<Runtime>::IssuerAlreadyExists
+		);
+
+		// the issuer mints, the class owner pays the deposit
+		assert_ok!(NFTModule::mint(
+			Origin::signed(CHARLIE),
+			BOB,
+			CLASS_ID,
+			vec![vec![1]],
+			1,
+			None
+		));
+		assert_eq!(NFTModule::owner((CLASS_ID, TOKEN_ID)), Some(BOB));
+		assert_eq!(
+			reserved_balance(&class_id_account()),
+			reserved + <Runtime as Config>::CreateTokenDeposit::get()
+		);
+
+		assert_ok!(NFTModule::remove_issuer(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			CHARLIE
+		));
+		let event = Event::nft(crate::Event::RemovedIssuer(CLASS_ID, CHARLIE));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::class_issuers(CLASS_ID, CHARLIE), None);
+		assert_noop!(
+			NFTModule::remove_issuer(Origin::signed(class_id_account()), CLASS_ID, CHARLIE),
+			Error::<Runtime>::IssuerNotFound
+		);
+		assert_noop!(
+			NFTModule::mint(Origin::signed(CHARLIE), BOB, CLASS_ID, vec![vec![1]], 1, None),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn tokens_by_owner_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			None
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![vec![1]],
+			3,
+			None
+		));
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, 1)));
+		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, 2)));
+
+		assert_eq!(NFTModule::tokens_by_owner(&ALICE, 0, 10), vec![(CLASS_ID, 1)]);
+		assert_eq!(NFTModule::tokens_by_owner(&BOB, 0, 10), vec![(CLASS_ID, 0)]);
+		assert_eq!(NFTModule::tokens_by_owner(&BOB, 1, 10), vec![]);
+		assert_eq!(NFTModule::tokens_by_owner(&ALICE, 0, 0), vec![]);
+	});
+}
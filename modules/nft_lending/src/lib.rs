@@ -0,0 +1,407 @@
+//! # NFT Lending Module
+//!
+//! ## Overview
+//!
+//! Holders of NFT tokens of the whitelisted classes, e.g. LP position NFTs,
+//! can lock their tokens as collateral and borrow stable currency against
+//! them. Only the classes with locked properties and revoked `Mintable` can
+//! be whitelisted, so the locked tokens can always be sold in liquidation,
+//! and no new tokens can be minted to borrow against. The valuation of a class
+//! is the floor price set by `UpdateOrigin`, which could be fed by an oracle
+//! council or the prices discovered by the NFT marketplace.
+//!
+//! The borrowed stable currency is issued by CDP treasury, backed by the
+//! locked token. The total debit of the loans of each class is capped, and
+//! the borrow fee of the class is added to the debit and issued to CDP
+//! treasury as surplus. When the debit of a loan exceeds the liquidation threshold
+//! of the floor price, or the class is removed from the whitelist, anyone can
+//! liquidate the loan: the debit is taken over by CDP treasury as system
+//! debit, and the token is sold in an English auction of the NFT
+//! marketplace with a reserve price derived from the floor price, the
+//! proceeds are deposited to the surplus pool of CDP treasury.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{debug, pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use module_nft::{ClassIdOf, TokenIdOf};
+use orml_traits::{MultiCurrency, NFT};
+use primitives::{AuctionId, Balance, CurrencyId};
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	DispatchResult, FixedPointNumber, ModuleId, RuntimeDebug,
+};
+use support::{CDPTreasury, Rate, Ratio};
+
+mod default_weight;
+mod mock;
+mod tests;
+
+pub use module::*;
+
+pub trait WeightInfo {
+	fn set_collateral_params() -> Weight;
+	fn borrow() -> Weight;
+	fn repay() -> Weight;
+	fn liquidate() -> Weight;
+}
+
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct CollateralParams {
+	/// The valuation of each token of the class, in stable currency
+	pub floor_price: Balance,
+	/// The max ratio of debit to the floor price when borrowing
+	pub max_ltv: Ratio,
+	/// The ratio of debit to the floor price above which the loan is unsafe
+	pub liquidation_threshold: Ratio,
+	/// The max total debit of the loans of the class
+	pub maximum_total_debit: Balance,
+	/// The fee rate charged on the borrowed amount, added to the debit
+	pub borrow_fee_rate: Rate,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Loan<AccountId> {
+	/// The owner of the locked token
+	pub owner: AccountId,
+	/// The borrowed stable currency amount
+	pub debit: Balance,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + module_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// CDP treasury to issue and burn stable currency, take over the
+		/// debit of liquidated loans and receive the proceeds of liquidation
+		type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
+		#[pallet::constant]
+		/// Stablecoin currency id
+		type GetStableCurrencyId: Get<CurrencyId>;
+
+		/// The origin which may update the collateral params of classes.
+		/// Root can always do this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		#[pallet::constant]
+		/// The duration of the auction to sell the liquidated token
+		type LiquidationAuctionDuration: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The ratio of the reserve price of the liquidation auction to the
+		/// floor price of class
+		type LiquidationReservePriceRatio: Get<Ratio>;
+
+		#[pallet::constant]
+		/// The NFT lending module id, keep the locked tokens.
+		type ModuleId: Get<ModuleId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The class is not whitelisted as collateral
+		ClassNotWhitelisted,
+		/// The max ratio of debit exceeds the liquidation threshold
+		InvalidCollateralParams,
+		/// The class is not transferable or its properties are not locked
+		ClassPropertiesNotLocked,
+		/// New tokens can still be minted into the class
+		ClassMintable,
+		/// The amount is zero
+		InvalidAmount,
+		/// The token expires and can not be collateral
+		ExpiringToken,
+		/// The debit exceeds the max ratio to the floor price
+		ExceedBorrowLimit,
+		/// The total debit of the class exceeds the max total debit
+		ExceedDebitCeiling,
+		/// The loan is not found
+		LoanNotFound,
+		/// The caller is not the owner of the loan
+		NoPermission,
+		/// The loan is safe and can not be liquidated
+		LoanIsSafe,
+		/// The token is neither locked in an unsafe loan nor unsold in the
+		/// last liquidation
+		NotLiquidatable,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The collateral params of class updated, `None` means removed from
+		/// the whitelist. \[class_id, params\]
+		CollateralParamsUpdated(ClassIdOf<T>, Option<CollateralParams>),
+		/// Borrowed stable currency against the token. \[who, class_id,
+		/// token_id, amount, fee\]
+		Borrowed(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, Balance, Balance),
+		/// Repaid stable currency of the loan. \[who, class_id, token_id,
+		/// amount\]
+		Repaid(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, Balance),
+		/// Liquidated the loan and started auction of the token. \[class_id,
+		/// token_id, debit, auction_id\]
+		Liquidated(ClassIdOf<T>, TokenIdOf<T>, Balance, AuctionId),
+	}
+
+	/// The collateral params of the whitelisted classes.
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_params)]
+	pub type CollateralParamsOf<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, CollateralParams, OptionQuery>;
+
+	/// The loans against the locked tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn loans)]
+	pub type Loans<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, Loan<T::AccountId>, OptionQuery>;
+
+	/// The total debit of the loans of the classes.
+	#[pallet::storage]
+	#[pallet::getter(fn total_debits)]
+	pub type TotalDebits<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Deposit the proceeds of the liquidation auctions settled in the
+		/// last block to CDP treasury.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let proceeds = Self::proceeds();
+			if proceeds.is_zero() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			if let Err(e) = T::CDPTreasury::deposit_surplus(&Self::account_id(), proceeds) {
+				debug::warn!("deposit the proceeds of liquidation failed: {:?}", e);
+			}
+			T::DbWeight::get().reads_writes(3, 2)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Update the collateral params of class
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `class_id`: the class id.
+		/// - `params`: the new collateral params, `None` means remove the
+		///   class from the whitelist, the existing loans become unsafe. The
+		///   class must be transferable with locked properties and revoked
+		///   `Mintable` to be whitelisted.
+		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_collateral_params(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			params: Option<CollateralParams>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(params) = params {
+				ensure!(
+					params.max_ltv <= params.liquidation_threshold,
+					Error::<T>::InvalidCollateralParams
+				);
+				ensure!(
+					module_nft::Module::<T>::is_always_transferable(class_id),
+					Error::<T>::ClassPropertiesNotLocked
+				);
+				ensure!(
+					!module_nft::Module::<T>::is_mintable(class_id),
+					Error::<T>::ClassMintable
+				);
+			}
+
+			CollateralParamsOf::<T>::mutate_exists(class_id, |maybe_params| *maybe_params = params);
+			Self::deposit_event(Event::CollateralParamsUpdated(class_id, params));
+			Ok(().into())
+		}
+
+		/// Borrow stable currency against the token, the token is locked
+		/// until the loan is repaid. The borrow fee is added to the debit,
+		/// and issued to CDP treasury as surplus.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `token`: (class_id, token_id).
+		/// - `amount`: stable currency amount to borrow.
+		#[pallet::weight(<T as Config>::WeightInfo::borrow())]
+		#[transactional]
+		pub fn borrow(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			let params = Self::collateral_params(token.0).ok_or(Error::<T>::ClassNotWhitelisted)?;
+			let fee = params.borrow_fee_rate.saturating_mul_int(amount);
+			let debit_increment = amount.checked_add(fee).ok_or(Error::<T>::ExceedBorrowLimit)?;
+
+			Loans::<T>::try_mutate_exists(token.0, token.1, |maybe_loan| -> DispatchResult {
+				if maybe_loan.is_none() {
+					ensure!(
+						module_nft::Module::<T>::token_expiry(token.0, token.1).is_none(),
+						Error::<T>::ExpiringToken
+					);
+					// lock the token
+					<module_nft::Module<T> as NFT<T::AccountId>>::transfer(&who, &Self::account_id(), token)?;
+					*maybe_loan = Some(Loan {
+						owner: who.clone(),
+						debit: Zero::zero(),
+					});
+				}
+				let loan = maybe_loan.as_mut().ok_or(Error::<T>::LoanNotFound)?;
+				ensure!(loan.owner == who, Error::<T>::NoPermission);
+
+				let debit = loan
+					.debit
+					.checked_add(debit_increment)
+					.filter(|debit| *debit <= params.max_ltv.saturating_mul_int(params.floor_price))
+					.ok_or(Error::<T>::ExceedBorrowLimit)?;
+				loan.debit = debit;
+				Ok(())
+			})?;
+			TotalDebits::<T>::try_mutate(token.0, |total_debit| -> DispatchResult {
+				*total_debit = total_debit
+					.checked_add(debit_increment)
+					.filter(|total_debit| *total_debit <= params.maximum_total_debit)
+					.ok_or(Error::<T>::ExceedDebitCeiling)?;
+				Ok(())
+			})?;
+			T::CDPTreasury::issue_debit(&who, amount, true)?;
+			if !fee.is_zero() {
+				T::CDPTreasury::on_system_surplus(fee)?;
+			}
+
+			Self::deposit_event(Event::Borrowed(who, token.0, token.1, amount, fee));
+			Ok(().into())
+		}
+
+		/// Repay stable currency of the loan, the token is unlocked once
+		/// the debit is fully repaid.
+		///
+		/// The dispatch origin of this call must be `Signed` by the owner of
+		/// the loan.
+		///
+		/// - `token`: (class_id, token_id).
+		/// - `amount`: stable currency amount to repay, capped by the debit.
+		#[pallet::weight(<T as Config>::WeightInfo::repay())]
+		#[transactional]
+		pub fn repay(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let (repaid, closed) = Loans::<T>::try_mutate_exists(
+				token.0,
+				token.1,
+				|maybe_loan| -> sp_std::result::Result<(Balance, bool), DispatchError> {
+					let loan = maybe_loan.as_mut().ok_or(Error::<T>::LoanNotFound)?;
+					ensure!(loan.owner == who, Error::<T>::NoPermission);
+
+					let repaid = amount.min(loan.debit);
+					loan.debit -= repaid;
+					let closed = loan.debit.is_zero();
+					if closed {
+						*maybe_loan = None;
+					}
+					Ok((repaid, closed))
+				},
+			)?;
+			TotalDebits::<T>::mutate(token.0, |total_debit| *total_debit = total_debit.saturating_sub(repaid));
+			T::CDPTreasury::burn_debit(&who, repaid)?;
+			if closed {
+				// unlock the token
+				<module_nft::Module<T> as NFT<T::AccountId>>::transfer(&Self::account_id(), &who, token)?;
+			}
+
+			Self::deposit_event(Event::Repaid(who, token.0, token.1, repaid));
+			Ok(().into())
+		}
+
+		/// Liquidate the unsafe loan, and sell the token in auction. The
+		/// token unsold in the last liquidation can be auctioned again. The
+		/// reserve price is `LiquidationReservePriceRatio` of the floor price,
+		/// or zero if the class is removed from the whitelist.
+		///
+		/// The dispatch origin of this call must be `Signed`, anyone can
+		/// liquidate.
+		///
+		/// - `token`: (class_id, token_id).
+		#[pallet::weight(<T as Config>::WeightInfo::liquidate())]
+		#[transactional]
+		pub fn liquidate(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let account_id = Self::account_id();
+
+			let debit = match Self::loans(token.0, token.1) {
+				Some(loan) => {
+					ensure!(Self::is_unsafe(token.0, loan.debit), Error::<T>::LoanIsSafe);
+					Loans::<T>::remove(token.0, token.1);
+					TotalDebits::<T>::mutate(token.0, |total_debit| {
+						*total_debit = total_debit.saturating_sub(loan.debit)
+					});
+					T::CDPTreasury::on_system_debit(loan.debit)?;
+					loan.debit
+				}
+				None => {
+					ensure!(
+						<module_nft::Module<T> as NFT<T::AccountId>>::owner(token) == Some(account_id.clone()),
+						Error::<T>::NotLiquidatable
+					);
+					Zero::zero()
+				}
+			};
+
+			let reserve_price = Self::collateral_params(token.0).map_or(Zero::zero(), |params| {
+				T::LiquidationReservePriceRatio::get().saturating_mul_int(params.floor_price)
+			});
+			let auction_id = module_nft::Module::<T>::do_create_auction(
+				&account_id,
+				token,
+				T::GetStableCurrencyId::get(),
+				reserve_price,
+				T::LiquidationAuctionDuration::get(),
+			)?;
+
+			Self::deposit_event(Event::Liquidated(token.0, token.1, debit, auction_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		<T as Config>::ModuleId::get().into_account()
+	}
+
+	/// Check whether the debit exceeds the liquidation threshold of the
+	/// floor price of class. The loans of the classes removed from the
+	/// whitelist are always unsafe.
+	pub fn is_unsafe(class_id: ClassIdOf<T>, debit: Balance) -> bool {
+		Self::collateral_params(class_id).map_or(true, |params| {
+			debit > params.liquidation_threshold.saturating_mul_int(params.floor_price)
+		})
+	}
+
+	/// The proceeds of the settled liquidation auctions, which are paid to
+	/// the module account.
+	fn proceeds() -> Balance {
+		<T as module_nft::Config>::MultiCurrency::free_balance(T::GetStableCurrencyId::get(), &Self::account_id())
+	}
+}
@@ -0,0 +1,400 @@
+//! Unit tests for the NFT lending module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, OnFinalize, OnInitialize},
+};
+use mock::{Event, *};
+use module_nft::{ClassProperty, Properties};
+use sp_runtime::traits::BadOrigin;
+
+const TOKEN: (u32, u64) = (CLASS_ID, TOKEN_ID);
+
+fn class_id_account() -> AccountId {
+	<Runtime as module_nft::Config>::ModuleId::get().into_sub_account(CLASS_ID)
+}
+
+fn revoke_mintable() {
+	assert_ok!(NFTModule::update_class_properties(
+		Origin::signed(class_id_account()),
+		CLASS_ID,
+		Properties(ClassProperty::Transferable.into())
+	));
+}
+
+fn mint_token_to_bob() {
+	assert_ok!(NFTModule::create_class(
+		Origin::signed(ALICE),
+		vec![1],
+		Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+		None
+	));
+	assert_ok!(Balances::deposit_into_existing(
+		&class_id_account(),
+		<Runtime as module_nft::Config>::CreateTokenDeposit::get()
+	));
+	assert_ok!(NFTModule::mint(
+		Origin::signed(class_id_account()),
+		BOB,
+		CLASS_ID,
+		vec![vec![1]],
+		1,
+		None
+	));
+}
+
+fn mint_locked_token_to_bob() {
+	mint_token_to_bob();
+	revoke_mintable();
+	assert_ok!(NFTModule::lock_class_properties(
+		Origin::signed(class_id_account()),
+		CLASS_ID
+	));
+}
+
+fn collateral_params(floor_price: Balance) -> CollateralParams {
+	CollateralParams {
+		floor_price,
+		max_ltv: Ratio::saturating_from_rational(1, 2),
+		liquidation_threshold: Ratio::saturating_from_rational(4, 5),
+		maximum_total_debit: 10000,
+		borrow_fee_rate: Rate::zero(),
+	}
+}
+
+#[test]
+fn set_collateral_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTLendingModule::set_collateral_params(Origin::signed(BOB), CLASS_ID, Some(collateral_params(1000))),
+			BadOrigin
+		);
+		assert_noop!(
+			NFTLendingModule::set_collateral_params(
+				Origin::signed(ALICE),
+				CLASS_ID,
+				Some(CollateralParams {
+					floor_price: 1000,
+					max_ltv: Ratio::saturating_from_rational(4, 5),
+					liquidation_threshold: Ratio::saturating_from_rational(1, 2),
+					maximum_total_debit: 10000,
+					borrow_fee_rate: Rate::zero(),
+				})
+			),
+			Error::<Runtime>::InvalidCollateralParams
+		);
+
+		// the properties of class must be locked
+		mint_token_to_bob();
+		assert_noop!(
+			NFTLendingModule::set_collateral_params(Origin::signed(ALICE), CLASS_ID, Some(collateral_params(1000))),
+			Error::<Runtime>::ClassPropertiesNotLocked
+		);
+
+		// the class locked with `Mintable` can not be whitelisted
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+			None
+		));
+		assert_ok!(NFTModule::lock_class_properties(
+			Origin::signed(<Runtime as module_nft::Config>::ModuleId::get().into_sub_account(1)),
+			1
+		));
+		assert_noop!(
+			NFTLendingModule::set_collateral_params(Origin::signed(ALICE), 1, Some(collateral_params(1000))),
+			Error::<Runtime>::ClassMintable
+		);
+
+		revoke_mintable();
+		assert_ok!(NFTModule::lock_class_properties(
+			Origin::signed(class_id_account()),
+			CLASS_ID
+		));
+
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(collateral_params(1000))
+		));
+		let update_event = Event::nft_lending(crate::Event::CollateralParamsUpdated(
+			CLASS_ID,
+			Some(collateral_params(1000)),
+		));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			NFTLendingModule::collateral_params(CLASS_ID),
+			Some(collateral_params(1000))
+		);
+
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			None
+		));
+		assert_eq!(NFTLendingModule::collateral_params(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn borrow_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_locked_token_to_bob();
+		assert_noop!(
+			NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 100),
+			Error::<Runtime>::ClassNotWhitelisted
+		);
+
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(collateral_params(1000))
+		));
+		assert_noop!(
+			NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 0),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 501),
+			Error::<Runtime>::ExceedBorrowLimit
+		);
+		assert_noop!(
+			NFTLendingModule::borrow(Origin::signed(CHARLIE), TOKEN, 100),
+			module_nft::Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 300));
+		let borrow_event = Event::nft_lending(crate::Event::Borrowed(BOB, CLASS_ID, TOKEN_ID, 300, 0));
+		assert!(System::events().iter().any(|record| record.event == borrow_event));
+		assert_eq!(
+			NFTLendingModule::loans(CLASS_ID, TOKEN_ID),
+			Some(Loan { owner: BOB, debit: 300 })
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 300);
+		assert_eq!(NFTLendingModule::total_debits(CLASS_ID), 300);
+		// the token is locked
+		assert_eq!(NFTModule::owner(TOKEN), Some(NFTLendingModule::account_id()));
+
+		// borrow more against the locked token
+		assert_noop!(
+			NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 201),
+			Error::<Runtime>::ExceedBorrowLimit
+		);
+		assert_noop!(
+			NFTLendingModule::borrow(Origin::signed(CHARLIE), TOKEN, 100),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 200));
+		assert_eq!(
+			NFTLendingModule::loans(CLASS_ID, TOKEN_ID),
+			Some(Loan { owner: BOB, debit: 500 })
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 500);
+		assert_eq!(NFTLendingModule::total_debits(CLASS_ID), 500);
+	});
+}
+
+#[test]
+fn borrow_with_fee_and_debit_ceiling() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_locked_token_to_bob();
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(CollateralParams {
+				maximum_total_debit: 400,
+				borrow_fee_rate: Rate::saturating_from_rational(1, 10),
+				..collateral_params(1000)
+			})
+		));
+
+		// the fee is added to the debit and issued to CDP treasury
+		assert_ok!(NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 300));
+		let borrow_event = Event::nft_lending(crate::Event::Borrowed(BOB, CLASS_ID, TOKEN_ID, 300, 30));
+		assert!(System::events().iter().any(|record| record.event == borrow_event));
+		assert_eq!(
+			NFTLendingModule::loans(CLASS_ID, TOKEN_ID),
+			Some(Loan { owner: BOB, debit: 330 })
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 300);
+		assert_eq!(MockCDPTreasury::get_surplus_pool(), 30);
+		assert_eq!(NFTLendingModule::total_debits(CLASS_ID), 330);
+
+		// the total debit of the class is capped
+		assert_noop!(
+			NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 70),
+			Error::<Runtime>::ExceedDebitCeiling
+		);
+		assert_ok!(NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 60));
+		assert_eq!(NFTLendingModule::total_debits(CLASS_ID), 396);
+	});
+}
+
+#[test]
+fn borrow_against_expiring_token_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_locked_token_to_bob();
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(collateral_params(1000))
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			<Runtime as module_nft::Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![vec![1]],
+			1,
+			Some(100)
+		));
+
+		assert_noop!(
+			NFTLendingModule::borrow(Origin::signed(BOB), (CLASS_ID, 1), 100),
+			Error::<Runtime>::ExpiringToken
+		);
+	});
+}
+
+#[test]
+fn repay_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_locked_token_to_bob();
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(collateral_params(1000))
+		));
+		assert_noop!(
+			NFTLendingModule::repay(Origin::signed(BOB), TOKEN, 100),
+			Error::<Runtime>::LoanNotFound
+		);
+
+		assert_ok!(NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 300));
+		assert_noop!(
+			NFTLendingModule::repay(Origin::signed(CHARLIE), TOKEN, 100),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTLendingModule::repay(Origin::signed(BOB), TOKEN, 100));
+		let repay_event = Event::nft_lending(crate::Event::Repaid(BOB, CLASS_ID, TOKEN_ID, 100));
+		assert!(System::events().iter().any(|record| record.event == repay_event));
+		assert_eq!(
+			NFTLendingModule::loans(CLASS_ID, TOKEN_ID),
+			Some(Loan { owner: BOB, debit: 200 })
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 200);
+		assert_eq!(NFTLendingModule::total_debits(CLASS_ID), 200);
+		assert_eq!(NFTModule::owner(TOKEN), Some(NFTLendingModule::account_id()));
+
+		// the repaid amount is capped by the debit
+		assert_ok!(NFTLendingModule::repay(Origin::signed(BOB), TOKEN, 1000));
+		let repay_event = Event::nft_lending(crate::Event::Repaid(BOB, CLASS_ID, TOKEN_ID, 200));
+		assert!(System::events().iter().any(|record| record.event == repay_event));
+		assert_eq!(NFTLendingModule::loans(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 0);
+		assert_eq!(NFTLendingModule::total_debits(CLASS_ID), 0);
+		// the token is unlocked
+		assert_eq!(NFTModule::owner(TOKEN), Some(BOB));
+	});
+}
+
+#[test]
+fn liquidate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_locked_token_to_bob();
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(collateral_params(1000))
+		));
+		assert_noop!(
+			NFTLendingModule::liquidate(Origin::signed(CHARLIE), TOKEN),
+			Error::<Runtime>::NotLiquidatable
+		);
+
+		assert_ok!(NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 500));
+		assert_noop!(
+			NFTLendingModule::liquidate(Origin::signed(CHARLIE), TOKEN),
+			Error::<Runtime>::LoanIsSafe
+		);
+
+		// the floor price drops below the liquidation threshold
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(collateral_params(600))
+		));
+		assert_ok!(NFTLendingModule::liquidate(Origin::signed(CHARLIE), TOKEN));
+		let liquidate_event = Event::nft_lending(crate::Event::Liquidated(CLASS_ID, TOKEN_ID, 500, 0));
+		assert!(System::events().iter().any(|record| record.event == liquidate_event));
+		assert_eq!(NFTLendingModule::loans(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(MockCDPTreasury::get_debit_pool(), 500);
+		assert_eq!(NFTLendingModule::total_debits(CLASS_ID), 0);
+		assert_eq!(NFTModule::token_auctions(CLASS_ID, TOKEN_ID), Some(0));
+		// the borrower keeps the borrowed stable currency
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 500);
+
+		// the reserve price is half of the floor price
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(CHARLIE), 0, 299),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+		assert_ok!(AuctionModule::bid(Origin::signed(CHARLIE), 0, 700));
+		AuctionModule::on_finalize(11);
+		assert_eq!(NFTModule::owner(TOKEN), Some(CHARLIE));
+		assert_eq!(Tokens::free_balance(AUSD, &TREASURY), 14);
+		assert_eq!(Tokens::free_balance(AUSD, &NFTLendingModule::account_id()), 686);
+
+		// the proceeds are deposited to CDP treasury
+		NFTLendingModule::on_initialize(12);
+		assert_eq!(Tokens::free_balance(AUSD, &NFTLendingModule::account_id()), 0);
+		assert_eq!(MockCDPTreasury::get_surplus_pool(), 686);
+
+		assert_noop!(
+			NFTLendingModule::liquidate(Origin::signed(CHARLIE), TOKEN),
+			Error::<Runtime>::NotLiquidatable
+		);
+	});
+}
+
+#[test]
+fn liquidate_removed_class_and_unsold_token() {
+	ExtBuilder::default().build().execute_with(|| {
+		mint_locked_token_to_bob();
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(collateral_params(1000))
+		));
+		assert_ok!(NFTLendingModule::borrow(Origin::signed(BOB), TOKEN, 100));
+
+		// the loans of the class removed from the whitelist are unsafe
+		assert_ok!(NFTLendingModule::set_collateral_params(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			None
+		));
+		assert_ok!(NFTLendingModule::liquidate(Origin::signed(CHARLIE), TOKEN));
+		assert_eq!(MockCDPTreasury::get_debit_pool(), 100);
+
+		// the unsold token is returned to the module account
+		AuctionModule::on_finalize(11);
+		assert_eq!(NFTModule::owner(TOKEN), Some(NFTLendingModule::account_id()));
+		assert_eq!(NFTModule::token_auctions(CLASS_ID, TOKEN_ID), None);
+
+		// and can be auctioned again
+		assert_ok!(NFTLendingModule::liquidate(Origin::signed(CHARLIE), TOKEN));
+		let liquidate_event = Event::nft_lending(crate::Event::Liquidated(CLASS_ID, TOKEN_ID, 0, 1));
+		assert!(System::events().iter().any(|record| record.event == liquidate_event));
+		assert_eq!(NFTModule::token_auctions(CLASS_ID, TOKEN_ID), Some(1));
+		assert_eq!(MockCDPTreasury::get_debit_pool(), 100);
+	});
+}
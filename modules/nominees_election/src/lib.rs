@@ -8,10 +8,10 @@ use orml_traits::{BasicCurrency, BasicLockableCurrency};
 use primitives::{Balance, EraIndex};
 use sp_runtime::{
 	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
-	RuntimeDebug, SaturatedConversion,
+	FixedPointNumber, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{fmt::Debug, prelude::*};
-use support::{NomineesProvider, OnNewEra};
+use support::{NomineesProvider, OnNewEra, PolkadotValidatorStatus, Ratio};
 
 mod mock;
 mod tests;
@@ -96,12 +96,37 @@ impl BondingLedger {
 	}
 }
 
+/// The reward and slash history of a nominated validator.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
+pub struct ValidatorPerformance {
+	/// The number of eras in which the validator was nominated.
+	pub nominated_eras: u32,
+	/// The total reward points earned in the nominated eras.
+	pub total_reward_points: u64,
+	/// The number of slashes detected.
+	pub slash_count: u32,
+	/// The number of the latest consecutive eras in which the validator was
+	/// underperforming.
+	pub underperforming_eras: u32,
+}
+
+/// The reason why a validator is removed from the election.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum RemovalReason {
+	/// The validator was slashed on the relaychain.
+	Slashed,
+	/// The validator was underperforming for `MaxUnderperformingEras`
+	/// consecutive eras.
+	Underperforming,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type Currency: BasicLockableCurrency<Self::AccountId, Moment = Self::BlockNumber, Balance = Balance>;
 		type PolkadotAccountId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
 		#[pallet::constant]
@@ -112,6 +137,20 @@ pub mod module {
 		type NominateesCount: Get<u32>;
 		#[pallet::constant]
 		type MaxUnlockingChunks: Get<u32>;
+		/// The status of validators on the relaychain, slashed validators are
+		/// removed from the election and inactive validators are skipped.
+		type ValidatorStatus: PolkadotValidatorStatus<Self::PolkadotAccountId, EraIndex>;
+		/// The origin which may reinstate removed validators. Root can always
+		/// do this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+		/// A nominee earning reward points below this ratio of the average of
+		/// all nominees in an era is underperforming in the era.
+		#[pallet::constant]
+		type UnderperformingRewardRatio: Get<Ratio>;
+		/// The number of consecutive underperforming eras after which the
+		/// nominee is removed from the election.
+		#[pallet::constant]
+		type MaxUnderperformingEras: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -121,6 +160,18 @@ pub mod module {
 		TooManyChunks,
 		NoBonded,
 		NoUnlockChunk,
+		TargetRemoved,
+		NotRemoved,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The validator is removed from the election. \[validator,
+		/// era_index, reason\]
+		ValidatorRemoved(T::PolkadotAccountId, EraIndex, RemovalReason),
+		/// The removed validator is reinstated. \[validator\]
+		ValidatorReinstated(T::PolkadotAccountId),
 	}
 
 	#[pallet::storage]
@@ -143,6 +194,25 @@ pub mod module {
 	#[pallet::getter(fn current_era)]
 	pub type CurrentEra<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
 
+	/// The validators removed from the election because of slash or
+	/// underperformance, and the era of the removal.
+	#[pallet::storage]
+	#[pallet::getter(fn removed_validators)]
+	pub type RemovedValidators<T: Config> = StorageMap<_, Twox64Concat, T::PolkadotAccountId, EraIndex, OptionQuery>;
+
+	/// The latest slash era of the validator which has been forgiven when it
+	/// was reinstated.
+	#[pallet::storage]
+	#[pallet::getter(fn forgiven_slash_era)]
+	pub type ForgivenSlashEra<T: Config> = StorageMap<_, Twox64Concat, T::PolkadotAccountId, EraIndex, OptionQuery>;
+
+	/// The reward and slash history of the validators which have been
+	/// nominated.
+	#[pallet::storage]
+	#[pallet::getter(fn performances)]
+	pub type Performances<T: Config> =
+		StorageMap<_, Twox64Concat, T::PolkadotAccountId, ValidatorPerformance, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -250,6 +320,10 @@ pub mod module {
 
 			let ledger = Self::ledger(&who);
 			ensure!(!ledger.total.is_zero(), Error::<T>::NoBonded);
+			ensure!(
+				!targets.iter().any(RemovedValidators::<T>::contains_key),
+				Error::<T>::TargetRemoved,
+			);
 
 			let mut targets = targets;
 			targets.sort();
@@ -275,6 +349,34 @@ pub mod module {
 			Nominations::<T>::remove(&who);
 			Ok(().into())
 		}
+
+		/// Reinstate a validator removed because of slash or
+		/// underperformance, the slashes up to now are forgiven and the
+		/// underperforming eras are reset.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `validator`: the removed validator.
+		#[pallet::weight((10000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn reinstate_validator(
+			origin: OriginFor<T>,
+			validator: T::PolkadotAccountId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				RemovedValidators::<T>::take(&validator).is_some(),
+				Error::<T>::NotRemoved
+			);
+			if let Some(slash_era) = T::ValidatorStatus::last_slash_era(&validator) {
+				ForgivenSlashEra::<T>::insert(&validator, slash_era);
+			}
+			Performances::<T>::mutate(&validator, |performance| {
+				performance.underperforming_eras = Zero::zero()
+			});
+			Self::deposit_event(Event::ValidatorReinstated(validator));
+			Ok(().into())
+		}
 	}
 }
 
@@ -309,8 +411,78 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Remove the validator if it was slashed after the last forgiven
+	/// slash, and check whether it can be elected.
+	fn is_electable(validator: &T::PolkadotAccountId) -> bool {
+		if RemovedValidators::<T>::contains_key(validator) {
+			return false;
+		}
+
+		if let Some(slash_era) = T::ValidatorStatus::last_slash_era(validator) {
+			if Some(slash_era) > Self::forgiven_slash_era(validator) {
+				RemovedValidators::<T>::insert(validator, slash_era);
+				Performances::<T>::mutate(validator, |performance| {
+					performance.slash_count = performance.slash_count.saturating_add(1)
+				});
+				Self::deposit_event(Event::ValidatorRemoved(
+					validator.clone(),
+					slash_era,
+					RemovalReason::Slashed,
+				));
+				return false;
+			}
+		}
+
+		T::ValidatorStatus::is_active(validator)
+	}
+
+	/// Record the reward points of the nominees in the ended era, and remove
+	/// the nominees which have been underperforming for too long.
+	fn record_performances(ended_era: EraIndex) {
+		let nominees = Nominees::<T>::get();
+		if nominees.is_empty() {
+			return;
+		}
+
+		let reward_points = nominees
+			.iter()
+			.map(|nominee| T::ValidatorStatus::reward_points(nominee, ended_era))
+			.collect::<Vec<_>>();
+		let total_points = reward_points
+			.iter()
+			.fold(0u64, |total, points| total.saturating_add((*points).into()));
+		let average_points = total_points / (nominees.len() as u64);
+		let threshold = T::UnderperformingRewardRatio::get().saturating_mul_int(average_points);
+
+		for (nominee, points) in nominees.into_iter().zip(reward_points) {
+			let underperforming_eras = Performances::<T>::mutate(&nominee, |performance| {
+				performance.nominated_eras = performance.nominated_eras.saturating_add(1);
+				performance.total_reward_points = performance.total_reward_points.saturating_add(points.into());
+				if u64::from(points) < threshold {
+					performance.underperforming_eras = performance.underperforming_eras.saturating_add(1);
+				} else {
+					performance.underperforming_eras = Zero::zero();
+				}
+				performance.underperforming_eras
+			});
+
+			if underperforming_eras >= T::MaxUnderperformingEras::get()
+				&& !RemovedValidators::<T>::contains_key(&nominee)
+			{
+				RemovedValidators::<T>::insert(&nominee, ended_era);
+				Self::deposit_event(Event::ValidatorRemoved(
+					nominee,
+					ended_era,
+					RemovalReason::Underperforming,
+				));
+			}
+		}
+	}
+
 	fn rebalance() {
-		let mut voters = Votes::<T>::iter().collect::<Vec<(T::PolkadotAccountId, Balance)>>();
+		let mut voters = Votes::<T>::iter()
+			.filter(|(nominee, _)| Self::is_electable(nominee))
+			.collect::<Vec<(T::PolkadotAccountId, Balance)>>();
 
 		voters.sort_by(|a, b| b.1.cmp(&a.1));
 
@@ -333,6 +505,7 @@ impl<T: Config> NomineesProvider<T::PolkadotAccountId> for Pallet<T> {
 
 impl<T: Config> OnNewEra<EraIndex> for Pallet<T> {
 	fn on_new_era(era: EraIndex) {
+		Self::record_performances(Self::current_era());
 		CurrentEra::<T>::put(era);
 		Self::rebalance();
 	}
@@ -6,11 +6,14 @@ use super::*;
 
 use crate as nominees;
 use crate::mock::sp_api_hidden_includes_construct_runtime::hidden_include::inherent::BlockT;
-use frame_support::{construct_runtime, parameter_types};
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
 use orml_traits::parameter_type_with_key;
 use primitives::{Amount, CurrencyId, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
+use std::{cell::RefCell, collections::BTreeMap};
+use support::Ratio;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -100,15 +103,59 @@ parameter_types! {
 	pub const BondingDuration: EraIndex = 4;
 	pub const NominateesCount: u32 = 5;
 	pub const MaxUnlockingChunks: u32 = 3;
+	pub UnderperformingRewardRatio: Ratio = Ratio::saturating_from_rational(1, 2);
+	pub const MaxUnderperformingEras: u32 = 2;
+}
+
+thread_local! {
+	static INACTIVE_VALIDATORS: RefCell<Vec<AccountId>> = RefCell::new(vec![]);
+	static SLASH_ERAS: RefCell<BTreeMap<AccountId, EraIndex>> = RefCell::new(BTreeMap::new());
+	static REWARD_POINTS: RefCell<BTreeMap<(AccountId, EraIndex), u32>> = RefCell::new(BTreeMap::new());
+}
+
+pub fn mock_set_validator_inactive(validator: AccountId) {
+	INACTIVE_VALIDATORS.with(|v| v.borrow_mut().push(validator));
+}
+
+pub fn mock_slash_validator(validator: AccountId, era: EraIndex) {
+	SLASH_ERAS.with(|v| v.borrow_mut().insert(validator, era));
+}
+
+pub fn mock_set_reward_points(validator: AccountId, era: EraIndex, points: u32) {
+	REWARD_POINTS.with(|v| v.borrow_mut().insert((validator, era), points));
+}
+
+pub struct MockValidatorStatus;
+impl PolkadotValidatorStatus<AccountId, EraIndex> for MockValidatorStatus {
+	fn is_active(validator: &AccountId) -> bool {
+		!INACTIVE_VALIDATORS.with(|v| v.borrow().contains(validator))
+	}
+
+	fn last_slash_era(validator: &AccountId) -> Option<EraIndex> {
+		SLASH_ERAS.with(|v| v.borrow().get(validator).copied())
+	}
+
+	fn reward_points(validator: &AccountId, era: EraIndex) -> u32 {
+		REWARD_POINTS.with(|v| v.borrow().get(&(*validator, era)).copied().unwrap_or_default())
+	}
+}
+
+ord_parameter_types! {
+	pub const Ten: AccountId = 10;
 }
 
 impl Config for Runtime {
+	type Event = Event;
 	type Currency = LDOTCurrency;
 	type PolkadotAccountId = AccountId;
 	type MinBondThreshold = MinBondThreshold;
 	type BondingDuration = BondingDuration;
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type ValidatorStatus = MockValidatorStatus;
+	type UpdateOrigin = EnsureSignedBy<Ten, AccountId>;
+	type UnderperformingRewardRatio = UnderperformingRewardRatio;
+	type MaxUnderperformingEras = MaxUnderperformingEras;
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -121,7 +168,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		NomineesElectionModule: nominees::{Module, Call, Storage},
+		NomineesElectionModule: nominees::{Module, Call, Storage, Event<T>},
 		TokensModule: orml_tokens::{Module, Storage, Event<T>, Config<T>},
 		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		OrmlCurrencies: orml_currencies::{Module, Call, Event<T>},
@@ -4,7 +4,8 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::*;
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn bond_below_min_bond_threshold() {
@@ -216,6 +217,140 @@ fn rebalance_work() {
 	});
 }
 
+#[test]
+fn rebalance_skip_inactive_validators() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(
+			Origin::signed(ALICE),
+			vec![1, 2, 3, 4, 5]
+		));
+		mock_set_validator_inactive(3);
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees().len(), 4);
+		assert_eq!(NomineesElectionModule::nominees().contains(&3), false);
+		assert_eq!(NomineesElectionModule::removed_validators(3), None);
+	});
+}
+
+#[test]
+fn rebalance_remove_slashed_validators() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(
+			Origin::signed(ALICE),
+			vec![1, 2, 3, 4, 5]
+		));
+		mock_slash_validator(2, 1);
+		NomineesElectionModule::on_new_era(2);
+		assert_eq!(NomineesElectionModule::nominees().len(), 4);
+		assert_eq!(NomineesElectionModule::nominees().contains(&2), false);
+		assert_eq!(NomineesElectionModule::removed_validators(2), Some(1));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 500));
+		assert_noop!(
+			NomineesElectionModule::nominate(Origin::signed(BOB), vec![1, 2]),
+			Error::<Runtime>::TargetRemoved,
+		);
+	});
+}
+
+#[test]
+fn reinstate_validator_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1, 2]));
+		assert_noop!(
+			NomineesElectionModule::reinstate_validator(Origin::signed(10), 2),
+			Error::<Runtime>::NotRemoved,
+		);
+
+		mock_slash_validator(2, 1);
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees(), vec![1]);
+		assert_noop!(
+			NomineesElectionModule::reinstate_validator(Origin::signed(ALICE), 2),
+			BadOrigin,
+		);
+		assert_ok!(NomineesElectionModule::reinstate_validator(Origin::signed(10), 2));
+		assert_eq!(NomineesElectionModule::removed_validators(2), None);
+		assert_eq!(NomineesElectionModule::forgiven_slash_era(2), Some(1));
+		assert_eq!(NomineesElectionModule::nominees().len(), 2);
+
+		// removed again by the new slash
+		mock_slash_validator(2, 3);
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees(), vec![1]);
+		assert_eq!(NomineesElectionModule::removed_validators(2), Some(3));
+	});
+}
+
+#[test]
+fn rotate_out_underperforming_validators() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1, 2, 3]));
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees().len(), 3);
+
+		// the average reward points is 70, 3 is underperforming
+		mock_set_reward_points(1, 0, 100);
+		mock_set_reward_points(2, 0, 100);
+		mock_set_reward_points(3, 0, 10);
+		NomineesElectionModule::on_new_era(1);
+		assert_eq!(NomineesElectionModule::performances(3).underperforming_eras, 1);
+		assert_eq!(NomineesElectionModule::removed_validators(3), None);
+		assert!(NomineesElectionModule::nominees().contains(&3));
+
+		// rotated out after underperforming for 2 consecutive eras
+		mock_set_reward_points(1, 1, 100);
+		mock_set_reward_points(2, 1, 100);
+		mock_set_reward_points(3, 1, 10);
+		NomineesElectionModule::on_new_era(2);
+		let remove_event = Event::nominees(crate::Event::ValidatorRemoved(3, 1, RemovalReason::Underperforming));
+		assert!(System::events().iter().any(|record| record.event == remove_event));
+		assert_eq!(NomineesElectionModule::removed_validators(3), Some(1));
+		assert!(!NomineesElectionModule::nominees().contains(&3));
+		assert_eq!(
+			NomineesElectionModule::performances(1),
+			ValidatorPerformance {
+				nominated_eras: 2,
+				total_reward_points: 200,
+				slash_count: 0,
+				underperforming_eras: 0,
+			}
+		);
+		assert_eq!(
+			NomineesElectionModule::performances(3),
+			ValidatorPerformance {
+				nominated_eras: 2,
+				total_reward_points: 20,
+				slash_count: 0,
+				underperforming_eras: 2,
+			}
+		);
+
+		assert_ok!(NomineesElectionModule::reinstate_validator(Origin::signed(10), 3));
+		let reinstate_event = Event::nominees(crate::Event::ValidatorReinstated(3));
+		assert!(System::events().iter().any(|record| record.event == reinstate_event));
+		assert_eq!(NomineesElectionModule::performances(3).underperforming_eras, 0);
+	});
+}
+
+#[test]
+fn slashed_validator_removed_event() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1, 2]));
+		mock_slash_validator(2, 1);
+		NomineesElectionModule::rebalance();
+		let remove_event = Event::nominees(crate::Event::ValidatorRemoved(2, 1, RemovalReason::Slashed));
+		assert!(System::events().iter().any(|record| record.event == remove_event));
+		assert_eq!(NomineesElectionModule::performances(2).slash_count, 1);
+	});
+}
+
 #[test]
 fn update_votes_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -0,0 +1,18 @@
+//! Runtime API definition for oracle staking module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait OracleStakingApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The sorted members of the oracle whose feeds count.
+		fn active_members() -> Vec<AccountId>;
+	}
+}
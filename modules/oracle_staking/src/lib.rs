@@ -0,0 +1,579 @@
+//! # Oracle Staking Module
+//!
+//! ## Overview
+//!
+//! Oracle operators must be approved by the governance and bond native
+//! currency on top of it to be a member of the oracle, instead of being
+//! trusted by membership alone. The bond is slashable:
+//!   - every price fed by an operator is checked against the accepted median
+//!     of the oracle at the beginning of the next block, and the operator is
+//!     slashed if the price deviates more than the max deviation.
+//!   - the operator who has not fed any price for the max absence can be
+//!     removed from the oracle by anyone.
+//!
+//! The operator whose bond falls below the min bond after slashing, or who is
+//! removed as absent, starts unbonding and can withdraw the remaining bond
+//! after the unbonding delay.
+//!
+//! The membership of the oracle rotates with grace periods counted in
+//! sessions. A new operator observes for `ObservationSessions`, it can feed
+//! and its feeds are checked, but they don't count in the combined price of
+//! the oracle. An operator who unbonds departs for `DepartureSessions`, its
+//! fed prices keep counting until it's removed from the oracle at the end of
+//! the departure, so the oracle doesn't lose the feeds at once. The operators
+//! removed for slashing or absence leave immediately.
+//!
+//! The members of the oracle are seeded with the operators approved by the
+//! governance at genesis, or by the migration on the chains that have them
+//! already, so they are kept in the oracle until they bond. The operator
+//! disapproved by the governance is removed from the oracle immediately, and
+//! starts unbonding if it has bonded.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{ChangeMembers, Contains, Currency, InitializeMembers, OnUnbalanced, ReservableCurrency},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::{DataProvider, OnNewData};
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{Saturating, StaticLookup, Zero},
+	FixedPointNumber, RuntimeDebug,
+};
+use sp_staking::SessionIndex;
+use sp_std::prelude::*;
+use support::{Price, Ratio};
+
+mod default_weight;
+mod mock;
+mod tests;
+
+pub use module::*;
+
+pub trait WeightInfo {
+	fn bond() -> Weight;
+	fn unbond() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn remove_absent() -> Weight;
+}
+
+type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+/// The registry info of a bonded oracle operator
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct OperatorInfo<BlockNumber> {
+	/// The bonded native currency amount
+	pub bond: Balance,
+	/// The block number at which the operator fed the last price, or joined
+	/// the oracle if it has fed none
+	pub last_fed_at: BlockNumber,
+	/// The block number after which the bond can be withdrawn, `None` means
+	/// the operator is a member of the oracle
+	pub unlock_at: Option<BlockNumber>,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency for bonding of oracle operators
+		type Currency: ReservableCurrency<Self::AccountId, Balance = Balance>;
+
+		/// The accepted median prices of the oracle
+		type Source: DataProvider<CurrencyId, Price>;
+
+		/// The oracle whose members are the bonded operators
+		type OnMembersChanged: ChangeMembers<Self::AccountId>;
+
+		/// The operators approved by the governance, only they can bond
+		type ApprovedOperators: Contains<Self::AccountId>;
+
+		/// The index of the current session
+		type CurrentSession: Get<SessionIndex>;
+
+		/// Handler for the slashed bond
+		type OnSlash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		#[pallet::constant]
+		/// The native currency amount an operator must bond to be a member of
+		/// the oracle
+		type MinBond: Get<Balance>;
+
+		#[pallet::constant]
+		/// The delay before an unbonding operator can withdraw the bond, it
+		/// stays slashable for the prices fed before unbonding
+		type UnbondingDelay: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The max deviation of a fed price from the accepted median, the
+		/// operator feeds a price deviates more is slashed
+		type MaxDeviation: Get<Ratio>;
+
+		#[pallet::constant]
+		/// The ratio of the bond slashed for each deviated price
+		type DeviationSlashRatio: Get<Ratio>;
+
+		#[pallet::constant]
+		/// The max number of blocks an operator may feed no price before it
+		/// can be removed
+		type MaxAbsence: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The max number of the members of the oracle
+		type MaxOperators: Get<u32>;
+
+		#[pallet::constant]
+		/// The number of sessions a new operator observes before its feeds
+		/// count
+		type ObservationSessions: Get<SessionIndex>;
+
+		#[pallet::constant]
+		/// The number of sessions the feeds of an unbonding operator still
+		/// count before it's removed from the oracle
+		type DepartureSessions: Get<SessionIndex>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The total bond is below the min bond
+		BelowMinBond,
+		/// The operator has not bonded
+		NotBonded,
+		/// The operator is unbonding already
+		AlreadyUnbonding,
+		/// The bond of the operator is still locked
+		BondStillLocked,
+		/// The oracle has the max number of members already
+		TooManyOperators,
+		/// The operator has fed price within the max absence
+		NotAbsent,
+		/// The operator is not approved by the governance
+		NotApproved,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The operator bonded and is a member of the oracle. \[who,
+		/// total_bond\]
+		Bonded(T::AccountId, Balance),
+		/// The operator left the oracle and started unbonding. \[who,
+		/// unlock_at\]
+		Unbonding(T::AccountId, T::BlockNumber),
+		/// The operator withdrew the bond. \[who, amount\]
+		Withdrawn(T::AccountId, Balance),
+		/// The operator is slashed for the deviated price. \[who,
+		/// currency_id, fed_price, slashed_amount\]
+		Slashed(T::AccountId, CurrencyId, Price, Balance),
+		/// The absent operator is removed from the oracle. \[who\]
+		AbsentOperatorRemoved(T::AccountId),
+		/// The observation of the operator ended, its feeds count from now.
+		/// \[who\]
+		ObservationEnded(T::AccountId),
+		/// The departing operator is removed from the oracle. \[who\]
+		Departed(T::AccountId),
+	}
+
+	/// The registry info of oracle operators.
+	#[pallet::storage]
+	#[pallet::getter(fn operators)]
+	pub type Operators<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, OperatorInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The sorted members of the oracle.
+	#[pallet::storage]
+	#[pallet::getter(fn members)]
+	pub type Members<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The observing operators whose feeds don't count yet, and the session
+	/// from which their feeds count.
+	#[pallet::storage]
+	#[pallet::getter(fn observers)]
+	pub type Observers<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, OptionQuery>;
+
+	/// The departing operators whose feeds still count, and the session from
+	/// which they are removed from the oracle.
+	#[pallet::storage]
+	#[pallet::getter(fn departures)]
+	pub type Departures<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, OptionQuery>;
+
+	/// The session at which the membership rotated last time.
+	#[pallet::storage]
+	#[pallet::getter(fn last_rotated_session)]
+	pub type LastRotatedSession<T: Config> = StorageValue<_, SessionIndex, ValueQuery>;
+
+	/// The prices fed in the current block, checked at the beginning of the
+	/// next block.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_feeds)]
+	pub type PendingFeeds<T: Config> = StorageValue<_, Vec<(T::AccountId, CurrencyId, Price)>, ValueQuery>;
+
+	/// Whether the members of the oracle have been seeded with the operators
+	/// approved by the governance.
+	#[pallet::storage]
+	pub(crate) type MembersSeeded<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Seed the members of the oracle with the operators approved by the
+		/// governance before the members are managed by this module, or the
+		/// first bonded operator replaces them in the oracle. It only runs
+		/// once.
+		fn on_runtime_upgrade() -> Weight {
+			if MembersSeeded::<T>::get() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let seeded = Self::seed_members(&T::ApprovedOperators::sorted_members()) as Weight;
+			T::DbWeight::get().reads_writes(seeded.saturating_add(3), 3)
+		}
+
+		/// Check the prices fed in the last block against the accepted
+		/// medians, and rotate the membership at the new session.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let feeds = PendingFeeds::<T>::take();
+			let count = feeds.len() as Weight;
+			for (who, currency_id, price) in feeds {
+				Self::check_feed(who, currency_id, price);
+			}
+			let weight =
+				T::DbWeight::get().reads_writes(count.saturating_mul(2).saturating_add(2), count.saturating_add(1));
+
+			let session = T::CurrentSession::get();
+			if session == Self::last_rotated_session() {
+				return weight;
+			}
+			let rotated = Self::rotate_members(session) as Weight;
+			weight.saturating_add(T::DbWeight::get().reads_writes(
+				(T::MaxOperators::get() as Weight).saturating_mul(2),
+				rotated.saturating_mul(2).saturating_add(1),
+			))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Bond native currency, the operator approved by the governance
+		/// becomes a member of the oracle once the total bond reaches
+		/// `MinBond`.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `amount`: native currency amount to bond.
+		#[pallet::weight(<T as Config>::WeightInfo::bond())]
+		#[transactional]
+		pub fn bond(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(T::ApprovedOperators::contains(&who), Error::<T>::NotApproved);
+			let now = <frame_system::Module<T>>::block_number();
+			let mut info = Self::operators(&who).unwrap_or(OperatorInfo {
+				bond: Zero::zero(),
+				last_fed_at: now,
+				unlock_at: None,
+			});
+			ensure!(info.unlock_at.is_none(), Error::<T>::AlreadyUnbonding);
+			info.bond = info.bond.saturating_add(amount);
+			ensure!(info.bond >= T::MinBond::get(), Error::<T>::BelowMinBond);
+
+			<T as Config>::Currency::reserve(&who, amount)?;
+			if !Self::is_member(&who) {
+				ensure!(
+					(Self::members().len() as u32) < T::MaxOperators::get(),
+					Error::<T>::TooManyOperators
+				);
+				info.last_fed_at = now;
+				Self::add_member(&who);
+				if !T::ObservationSessions::get().is_zero() {
+					Observers::<T>::insert(
+						&who,
+						T::CurrentSession::get().saturating_add(T::ObservationSessions::get()),
+					);
+				}
+			}
+			let total_bond = info.bond;
+			Operators::<T>::insert(&who, info);
+
+			Self::deposit_event(Event::Bonded(who, total_bond));
+			Ok(().into())
+		}
+
+		/// Leave the oracle and start unbonding, the bond can be withdrawn
+		/// after `UnbondingDelay`. The fed prices of the operator still count
+		/// for `DepartureSessions`, unless it's still observing.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(<T as Config>::WeightInfo::unbond())]
+		#[transactional]
+		pub fn unbond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_unbond(&who, true)?;
+			Ok(().into())
+		}
+
+		/// Withdraw the bond after the unbonding delay.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_unbonded())]
+		#[transactional]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let info = Self::operators(&who).ok_or(Error::<T>::NotBonded)?;
+			ensure!(
+				info.unlock_at.map_or(false, |unlock_at| unlock_at
+					<= <frame_system::Module<T>>::block_number()),
+				Error::<T>::BondStillLocked
+			);
+
+			<T as Config>::Currency::unreserve(&who, info.bond);
+			Self::remove_member(&who);
+			Operators::<T>::remove(&who);
+			Self::deposit_event(Event::Withdrawn(who, info.bond));
+			Ok(().into())
+		}
+
+		/// Remove the operator who has fed no price for `MaxAbsence` from the
+		/// oracle, the operator starts unbonding.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `operator`: the absent operator.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_absent())]
+		#[transactional]
+		pub fn remove_absent(
+			origin: OriginFor<T>,
+			operator: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			let info = Self::operators(&operator).ok_or(Error::<T>::NotBonded)?;
+			ensure!(info.unlock_at.is_none(), Error::<T>::AlreadyUnbonding);
+			ensure!(
+				<frame_system::Module<T>>::block_number().saturating_sub(info.last_fed_at) > T::MaxAbsence::get(),
+				Error::<T>::NotAbsent
+			);
+
+			Self::do_unbond(&operator, false)?;
+			Self::deposit_event(Event::AbsentOperatorRemoved(operator));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn is_member(who: &T::AccountId) -> bool {
+		Self::members().binary_search(who).is_ok()
+	}
+
+	/// The sorted members of the oracle whose feeds count, including the
+	/// departing ones.
+	pub fn active_members() -> Vec<T::AccountId> {
+		Self::members()
+			.into_iter()
+			.filter(|who| !Observers::<T>::contains_key(who))
+			.collect()
+	}
+
+	/// Add the approved operators to the members of the oracle, returns
+	/// the number of the members
+	fn seed_members(approved: &[T::AccountId]) -> u32 {
+		let mut members = Self::members();
+		let mut incoming = Vec::new();
+		for who in approved {
+			if let Err(index) = members.binary_search(who) {
+				members.insert(index, who.clone());
+				incoming.push(who.clone());
+			}
+		}
+		incoming.sort();
+		T::OnMembersChanged::change_members_sorted(&incoming, &[], &members);
+		let count = members.len() as u32;
+		Members::<T>::put(members);
+		MembersSeeded::<T>::put(true);
+		count
+	}
+
+	fn add_member(who: &T::AccountId) {
+		let mut members = Self::members();
+		if let Err(index) = members.binary_search(who) {
+			members.insert(index, who.clone());
+			T::OnMembersChanged::change_members_sorted(&[who.clone()], &[], &members);
+			Members::<T>::put(members);
+		}
+	}
+
+	fn remove_member(who: &T::AccountId) {
+		Observers::<T>::remove(who);
+		Departures::<T>::remove(who);
+		let mut members = Self::members();
+		if let Ok(index) = members.binary_search(who) {
+			members.remove(index);
+			T::OnMembersChanged::change_members_sorted(&[], &[who.clone()], &members);
+			Members::<T>::put(members);
+		}
+	}
+
+	/// Start unbonding, the operator departs from the oracle if `gracefully`
+	/// and it's not observing, otherwise it's removed immediately
+	fn do_unbond(who: &T::AccountId, gracefully: bool) -> DispatchResult {
+		let unlock_at = Operators::<T>::try_mutate(who, |maybe_info| -> Result<T::BlockNumber, DispatchError> {
+			let info = maybe_info.as_mut().ok_or(Error::<T>::NotBonded)?;
+			ensure!(info.unlock_at.is_none(), Error::<T>::AlreadyUnbonding);
+			let unlock_at = <frame_system::Module<T>>::block_number().saturating_add(T::UnbondingDelay::get());
+			info.unlock_at = Some(unlock_at);
+			Ok(unlock_at)
+		})?;
+		if gracefully
+			&& !T::DepartureSessions::get().is_zero()
+			&& Self::is_member(who)
+			&& !Observers::<T>::contains_key(who)
+		{
+			Departures::<T>::insert(
+				who,
+				T::CurrentSession::get().saturating_add(T::DepartureSessions::get()),
+			);
+		} else {
+			Self::remove_member(who);
+		}
+		Self::deposit_event(Event::Unbonding(who.clone(), unlock_at));
+		Ok(())
+	}
+
+	/// End the observations and departures due at `session`, returns the
+	/// number of the rotated operators
+	fn rotate_members(session: SessionIndex) -> u32 {
+		LastRotatedSession::<T>::put(session);
+
+		let observed = Observers::<T>::iter()
+			.filter(|(_, count_from)| *count_from <= session)
+			.map(|(who, _)| who)
+			.collect::<Vec<_>>();
+		for who in observed.iter() {
+			Observers::<T>::remove(who);
+			Self::deposit_event(Event::ObservationEnded(who.clone()));
+		}
+
+		let departed = Departures::<T>::iter()
+			.filter(|(_, remove_from)| *remove_from <= session)
+			.map(|(who, _)| who)
+			.collect::<Vec<_>>();
+		for who in departed.iter() {
+			Self::remove_member(who);
+			Self::deposit_event(Event::Departed(who.clone()));
+		}
+
+		(observed.len() as u32).saturating_add(departed.len() as u32)
+	}
+
+	/// Slash the operator if the fed price deviates more than
+	/// `MaxDeviation` from the accepted median, the operator is removed from
+	/// the oracle if the remaining bond is below `MinBond`.
+	fn check_feed(who: T::AccountId, currency_id: CurrencyId, price: Price) {
+		let median = match T::Source::get(&currency_id) {
+			Some(median) => median,
+			None => return,
+		};
+		let deviation = if price > median {
+			price.saturating_sub(median)
+		} else {
+			median.saturating_sub(price)
+		};
+		if deviation <= T::MaxDeviation::get().saturating_mul(median) {
+			return;
+		}
+
+		let info = match Self::operators(&who) {
+			Some(info) => info,
+			None => return,
+		};
+		let amount = T::DeviationSlashRatio::get().saturating_mul_int(info.bond);
+		let (imbalance, remain) = <T as Config>::Currency::slash_reserved(&who, amount);
+		T::OnSlash::on_unbalanced(imbalance);
+		let slashed_amount = amount.saturating_sub(remain);
+		let bond = info.bond.saturating_sub(slashed_amount);
+		Operators::<T>::mutate(&who, |maybe_info| {
+			if let Some(info) = maybe_info {
+				info.bond = bond;
+			}
+		});
+		Self::deposit_event(Event::Slashed(who.clone(), currency_id, price, slashed_amount));
+
+		if bond < T::MinBond::get() {
+			if info.unlock_at.is_none() {
+				let _ = Self::do_unbond(&who, false);
+			} else {
+				// the departing operator is removed immediately
+				Self::remove_member(&who);
+			}
+		}
+	}
+}
+
+impl<T: Config> OnNewData<T::AccountId, CurrencyId, Price> for Pallet<T> {
+	/// Record the fed price to check, and the time the operator fed.
+	fn on_new_data(who: &T::AccountId, key: &CurrencyId, value: &Price) {
+		let now = <frame_system::Module<T>>::block_number();
+		Operators::<T>::mutate(who, |maybe_info| {
+			if let Some(info) = maybe_info {
+				info.last_fed_at = now;
+			}
+		});
+		PendingFeeds::<T>::append((who.clone(), *key, *value));
+	}
+}
+
+impl<T: Config> InitializeMembers<T::AccountId> for Pallet<T> {
+	/// Seed the members of the oracle with the genesis operators approved by
+	/// the governance.
+	fn initialize_members(members: &[T::AccountId]) {
+		Self::seed_members(members);
+	}
+}
+
+impl<T: Config> ChangeMembers<T::AccountId> for Pallet<T> {
+	/// The operators approved by the governance must bond to be a member of
+	/// the oracle, the disapproved ones are removed from the oracle
+	/// immediately, and start unbonding if they have bonded.
+	fn change_members_sorted(_incoming: &[T::AccountId], outgoing: &[T::AccountId], _new: &[T::AccountId]) {
+		for who in outgoing {
+			if Self::operators(who).map_or(false, |info| info.unlock_at.is_none()) {
+				let _ = Self::do_unbond(who, false);
+			} else {
+				Self::remove_member(who);
+			}
+		}
+	}
+}
+
+/// The observing operators of the oracle, whose feeds don't count yet.
+pub struct ObservingMembers<T>(PhantomData<T>);
+impl<T: Config> Contains<T::AccountId> for ObservingMembers<T> {
+	fn contains(who: &T::AccountId) -> bool {
+		Observers::<T>::contains_key(who)
+	}
+
+	fn sorted_members() -> Vec<T::AccountId> {
+		let mut observers = Observers::<T>::iter().map(|(who, _)| who).collect::<Vec<_>>();
+		observers.sort();
+		observers
+	}
+}
@@ -0,0 +1,184 @@
+//! Mocks for the oracle staking module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::sp_api_hidden_includes_construct_runtime::hidden_include::inherent::BlockT;
+use frame_support::{construct_runtime, parameter_types};
+use primitives::TokenSymbol;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+mod oracle_staking {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Module<Runtime>;
+	type MaxLocks = ();
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static MEDIAN_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::saturating_from_integer(100)));
+	static ORACLE_MEMBERS: RefCell<Vec<AccountId>> = RefCell::new(vec![]);
+	static SESSION_INDEX: RefCell<SessionIndex> = RefCell::new(0);
+}
+
+pub fn mock_set_session(session: SessionIndex) {
+	SESSION_INDEX.with(|v| *v.borrow_mut() = session);
+}
+
+pub fn mock_set_median_price(price: Option<Price>) {
+	MEDIAN_PRICE.with(|v| *v.borrow_mut() = price);
+}
+
+pub fn oracle_members() -> Vec<AccountId> {
+	ORACLE_MEMBERS.with(|v| v.borrow().clone())
+}
+
+pub struct MockSource;
+impl DataProvider<CurrencyId, Price> for MockSource {
+	fn get(_: &CurrencyId) -> Option<Price> {
+		MEDIAN_PRICE.with(|v| *v.borrow())
+	}
+}
+
+pub struct MockSession;
+impl Get<SessionIndex> for MockSession {
+	fn get() -> SessionIndex {
+		SESSION_INDEX.with(|v| *v.borrow())
+	}
+}
+
+pub struct MockApprovedOperators;
+impl Contains<AccountId> for MockApprovedOperators {
+	fn sorted_members() -> Vec<AccountId> {
+		vec![ALICE, BOB, CHARLIE]
+	}
+}
+
+pub struct MockOracle;
+impl ChangeMembers<AccountId> for MockOracle {
+	fn change_members_sorted(_incoming: &[AccountId], _outgoing: &[AccountId], new: &[AccountId]) {
+		ORACLE_MEMBERS.with(|v| *v.borrow_mut() = new.to_vec());
+	}
+}
+
+parameter_types! {
+	pub const MinBond: Balance = 100;
+	pub const UnbondingDelay: BlockNumber = 10;
+	pub MaxDeviation: Ratio = Ratio::saturating_from_rational(1, 10);
+	pub DeviationSlashRatio: Ratio = Ratio::saturating_from_rational(1, 5);
+	pub const MaxAbsence: BlockNumber = 100;
+	pub const MaxOperators: u32 = 2;
+	pub const ObservationSessions: SessionIndex = 1;
+	pub const DepartureSessions: SessionIndex = 2;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Source = MockSource;
+	type OnMembersChanged = MockOracle;
+	type ApprovedOperators = MockApprovedOperators;
+	type CurrentSession = MockSession;
+	type OnSlash = ();
+	type MinBond = MinBond;
+	type UnbondingDelay = UnbondingDelay;
+	type MaxDeviation = MaxDeviation;
+	type DeviationSlashRatio = DeviationSlashRatio;
+	type MaxAbsence = MaxAbsence;
+	type MaxOperators = MaxOperators;
+	type ObservationSessions = ObservationSessions;
+	type DepartureSessions = DepartureSessions;
+	type WeightInfo = ();
+}
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		OracleStakingModule: oracle_staking::{Module, Storage, Call, Event<T>},
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 1000), (BOB, 1000), (CHARLIE, 1000), (DAVE, 1000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,235 @@
+//! Unit tests for the oracle staking module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use mock::{Event, *};
+
+#[test]
+fn bond_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			OracleStakingModule::bond(Origin::signed(DAVE), 100),
+			Error::<Runtime>::NotApproved
+		);
+		assert_noop!(
+			OracleStakingModule::bond(Origin::signed(ALICE), 99),
+			Error::<Runtime>::BelowMinBond
+		);
+		assert_noop!(
+			OracleStakingModule::bond(Origin::signed(ALICE), 1001),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(OracleStakingModule::bond(Origin::signed(ALICE), 100));
+		let bond_event = Event::oracle_staking(crate::Event::Bonded(ALICE, 100));
+		assert!(System::events().iter().any(|record| record.event == bond_event));
+		assert_eq!(
+			OracleStakingModule::operators(ALICE),
+			Some(OperatorInfo {
+				bond: 100,
+				last_fed_at: 1,
+				unlock_at: None
+			})
+		);
+		assert_eq!(Balances::reserved_balance(ALICE), 100);
+		assert_eq!(OracleStakingModule::members(), vec![ALICE]);
+		assert_eq!(oracle_members(), vec![ALICE]);
+
+		// top up the bond
+		assert_ok!(OracleStakingModule::bond(Origin::signed(ALICE), 50));
+		assert_eq!(OracleStakingModule::operators(ALICE).unwrap().bond, 150);
+		assert_eq!(Balances::reserved_balance(ALICE), 150);
+
+		assert_ok!(OracleStakingModule::bond(Origin::signed(BOB), 100));
+		assert_eq!(oracle_members(), vec![ALICE, BOB]);
+		assert_noop!(
+			OracleStakingModule::bond(Origin::signed(CHARLIE), 100),
+			Error::<Runtime>::TooManyOperators
+		);
+	});
+}
+
+#[test]
+fn unbond_and_withdraw_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			OracleStakingModule::unbond(Origin::signed(ALICE)),
+			Error::<Runtime>::NotBonded
+		);
+		assert_ok!(OracleStakingModule::bond(Origin::signed(ALICE), 100));
+
+		assert_ok!(OracleStakingModule::unbond(Origin::signed(ALICE)));
+		let unbond_event = Event::oracle_staking(crate::Event::Unbonding(ALICE, 11));
+		assert!(System::events().iter().any(|record| record.event == unbond_event));
+		assert_eq!(OracleStakingModule::members(), vec![]);
+		assert_eq!(oracle_members(), vec![]);
+		assert_noop!(
+			OracleStakingModule::unbond(Origin::signed(ALICE)),
+			Error::<Runtime>::AlreadyUnbonding
+		);
+		assert_noop!(
+			OracleStakingModule::bond(Origin::signed(ALICE), 100),
+			Error::<Runtime>::AlreadyUnbonding
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			OracleStakingModule::withdraw_unbonded(Origin::signed(ALICE)),
+			Error::<Runtime>::BondStillLocked
+		);
+
+		System::set_block_number(11);
+		assert_ok!(OracleStakingModule::withdraw_unbonded(Origin::signed(ALICE)));
+		let withdraw_event = Event::oracle_staking(crate::Event::Withdrawn(ALICE, 100));
+		assert!(System::events().iter().any(|record| record.event == withdraw_event));
+		assert_eq!(OracleStakingModule::operators(ALICE), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 1000);
+	});
+}
+
+#[test]
+fn slash_deviated_feed_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(OracleStakingModule::bond(Origin::signed(ALICE), 120));
+		assert_ok!(OracleStakingModule::bond(Origin::signed(BOB), 100));
+
+		System::set_block_number(2);
+		OracleStakingModule::on_new_data(&ALICE, &DOT, &Price::saturating_from_integer(111));
+		OracleStakingModule::on_new_data(&BOB, &DOT, &Price::saturating_from_integer(110));
+		assert_eq!(OracleStakingModule::operators(ALICE).unwrap().last_fed_at, 2);
+		assert_eq!(OracleStakingModule::pending_feeds().len(), 2);
+
+		OracleStakingModule::on_initialize(3);
+		assert_eq!(OracleStakingModule::pending_feeds(), vec![]);
+		let slash_event = Event::oracle_staking(crate::Event::Slashed(
+			ALICE,
+			DOT,
+			Price::saturating_from_integer(111),
+			24,
+		));
+		assert!(System::events().iter().any(|record| record.event == slash_event));
+		// the bond is below the min bond after slashing
+		assert_eq!(
+			OracleStakingModule::operators(ALICE),
+			Some(OperatorInfo {
+				bond: 96,
+				last_fed_at: 2,
+				unlock_at: Some(12)
+			})
+		);
+		assert_eq!(Balances::reserved_balance(ALICE), 96);
+		assert_eq!(Balances::total_balance(&ALICE), 976);
+		// the price within the max deviation is not slashed
+		assert_eq!(OracleStakingModule::operators(BOB).unwrap().bond, 100);
+		assert_eq!(oracle_members(), vec![BOB]);
+
+		// no slash without the accepted median
+		mock_set_median_price(None);
+		OracleStakingModule::on_new_data(&BOB, &DOT, &Price::saturating_from_integer(200));
+		OracleStakingModule::on_initialize(4);
+		assert_eq!(OracleStakingModule::operators(BOB).unwrap().bond, 100);
+	});
+}
+
+#[test]
+fn remove_absent_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			OracleStakingModule::remove_absent(Origin::signed(BOB), ALICE),
+			Error::<Runtime>::NotBonded
+		);
+		assert_ok!(OracleStakingModule::bond(Origin::signed(ALICE), 100));
+
+		System::set_block_number(101);
+		assert_noop!(
+			OracleStakingModule::remove_absent(Origin::signed(BOB), ALICE),
+			Error::<Runtime>::NotAbsent
+		);
+		OracleStakingModule::on_new_data(&ALICE, &DOT, &Price::saturating_from_integer(100));
+
+		System::set_block_number(202);
+		assert_ok!(OracleStakingModule::remove_absent(Origin::signed(BOB), ALICE));
+		let remove_event = Event::oracle_staking(crate::Event::AbsentOperatorRemoved(ALICE));
+		assert!(System::events().iter().any(|record| record.event == remove_event));
+		assert_eq!(OracleStakingModule::operators(ALICE).unwrap().unlock_at, Some(212));
+		assert_eq!(oracle_members(), vec![]);
+		assert_noop!(
+			OracleStakingModule::remove_absent(Origin::signed(BOB), ALICE),
+			Error::<Runtime>::AlreadyUnbonding
+		);
+	});
+}
+
+#[test]
+fn rotate_members_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(OracleStakingModule::bond(Origin::signed(ALICE), 100));
+		assert_eq!(OracleStakingModule::observers(ALICE), Some(1));
+		assert_eq!(ObservingMembers::<Runtime>::sorted_members(), vec![ALICE]);
+		// the observing operator can feed but its feeds don't count
+		assert_eq!(oracle_members(), vec![ALICE]);
+		assert_eq!(OracleStakingModule::active_members(), vec![]);
+
+		OracleStakingModule::on_initialize(2);
+		assert_eq!(OracleStakingModule::observers(ALICE), Some(1));
+
+		mock_set_session(1);
+		OracleStakingModule::on_initialize(3);
+		let observation_event = Event::oracle_staking(crate::Event::ObservationEnded(ALICE));
+		assert!(System::events().iter().any(|record| record.event == observation_event));
+		assert_eq!(OracleStakingModule::last_rotated_session(), 1);
+		assert_eq!(OracleStakingModule::observers(ALICE), None);
+		assert_eq!(OracleStakingModule::active_members(), vec![ALICE]);
+
+		// the feeds of the departing operator still count
+		assert_ok!(OracleStakingModule::unbond(Origin::signed(ALICE)));
+		assert_eq!(OracleStakingModule::departures(ALICE), Some(3));
+		assert_eq!(oracle_members(), vec![ALICE]);
+		assert_eq!(OracleStakingModule::active_members(), vec![ALICE]);
+
+		mock_set_session(2);
+		OracleStakingModule::on_initialize(4);
+		assert_eq!(OracleStakingModule::departures(ALICE), Some(3));
+
+		mock_set_session(3);
+		OracleStakingModule::on_initialize(5);
+		let departure_event = Event::oracle_staking(crate::Event::Departed(ALICE));
+		assert!(System::events().iter().any(|record| record.event == departure_event));
+		assert_eq!(OracleStakingModule::departures(ALICE), None);
+		assert_eq!(OracleStakingModule::members(), vec![]);
+		assert_eq!(oracle_members(), vec![]);
+	});
+}
+
+#[test]
+fn seed_and_disapprove_members_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(OracleStakingModule::bond(Origin::signed(ALICE), 100));
+		assert_eq!(oracle_members(), vec![ALICE]);
+
+		// the approved operators are kept in the oracle
+		<OracleStakingModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(OracleStakingModule::members(), vec![ALICE, BOB, CHARLIE]);
+		assert_eq!(oracle_members(), vec![ALICE, BOB, CHARLIE]);
+		assert_eq!(OracleStakingModule::operators(BOB), None);
+
+		// only runs once
+		assert_ok!(OracleStakingModule::unbond(Origin::signed(ALICE)));
+		<OracleStakingModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(oracle_members(), vec![BOB, CHARLIE]);
+
+		// the seeded operator bonds without observation
+		assert_ok!(OracleStakingModule::bond(Origin::signed(BOB), 100));
+		assert_eq!(OracleStakingModule::observers(BOB), None);
+		assert_eq!(oracle_members(), vec![BOB, CHARLIE]);
+
+		// the disapproved operators are removed, and unbond if bonded
+		OracleStakingModule::change_members_sorted(&[], &[BOB, CHARLIE], &[ALICE]);
+		assert_eq!(OracleStakingModule::operators(BOB).unwrap().unlock_at, Some(11));
+		assert_eq!(OracleStakingModule::members(), vec![]);
+		assert_eq!(oracle_members(), vec![]);
+	});
+}
@@ -12,7 +12,7 @@ use sp_runtime::{
 use sp_std::{fmt::Debug, prelude::*};
 use support::{
 	OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingLedger,
-	PolkadotUnlockChunk, Rate,
+	PolkadotUnlockChunk, PolkadotValidatorStatus, Rate,
 };
 
 pub use module::*;
@@ -66,6 +66,22 @@ pub mod module {
 	#[pallet::getter(fn sub_accounts)]
 	pub type SubAccounts<T: Config> = StorageMap<_, Twox64Concat, u32, SubAccountStatus, ValueQuery>;
 
+	/// The validators which are out of the active validator set.
+	#[pallet::storage]
+	#[pallet::getter(fn inactive_validators)]
+	pub type InactiveValidators<T: Config> = StorageMap<_, Twox64Concat, T::PolkadotAccountId, bool, ValueQuery>;
+
+	/// The latest era in which the validator was slashed.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_slash_era)]
+	pub type ValidatorSlashEra<T: Config> = StorageMap<_, Twox64Concat, T::PolkadotAccountId, EraIndex, OptionQuery>;
+
+	/// The reward points earned by the validator in the era.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_reward_points)]
+	pub type ValidatorRewardPoints<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::PolkadotAccountId, u32, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -197,6 +213,45 @@ pub mod module {
 			Ok(().into())
 		}
 
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn simulate_validator_active(
+			origin: OriginFor<T>,
+			validator: T::PolkadotAccountId,
+			is_active: bool,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			if is_active {
+				InactiveValidators::<T>::remove(&validator);
+			} else {
+				InactiveValidators::<T>::insert(&validator, true);
+			}
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn simulate_slash_validator(
+			origin: OriginFor<T>,
+			validator: T::PolkadotAccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ValidatorSlashEra::<T>::insert(&validator, Self::current_era());
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn simulate_validator_reward_points(
+			origin: OriginFor<T>,
+			validator: T::PolkadotAccountId,
+			points: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ValidatorRewardPoints::<T>::insert(Self::current_era(), &validator, points);
+			Ok(().into())
+		}
+
 		#[pallet::weight(10_000)]
 		#[transactional]
 		pub fn force_era(origin: OriginFor<T>, at: T::BlockNumber) -> DispatchResultWithPostInfo {
@@ -434,4 +489,18 @@ impl<T: Config> PolkadotBridgeState<Balance, EraIndex> for Pallet<T> {
 	}
 }
 
+impl<T: Config> PolkadotValidatorStatus<T::PolkadotAccountId, EraIndex> for Pallet<T> {
+	fn is_active(validator: &T::PolkadotAccountId) -> bool {
+		!Self::inactive_validators(validator)
+	}
+
+	fn last_slash_era(validator: &T::PolkadotAccountId) -> Option<EraIndex> {
+		Self::validator_slash_era(validator)
+	}
+
+	fn reward_points(validator: &T::PolkadotAccountId, era: EraIndex) -> u32 {
+		Self::validator_reward_points(era, validator)
+	}
+}
+
 impl<T: Config> PolkadotBridge<T::AccountId, T::BlockNumber, Balance, EraIndex> for Pallet<T> {}
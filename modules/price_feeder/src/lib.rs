@@ -0,0 +1,253 @@
+//! # Price Feeder Module
+//!
+//! ## Overview
+//!
+//! The reference implementation of the oracle price feeder, which is run by
+//! the offchain worker of the node of an oracle operator rather than a
+//! separate bot. The offchain worker is only enabled with the
+//! `offchain-feeder` feature.
+//!
+//! Every `FeedInterval` blocks, the offchain worker fetches the price of
+//! each currency from the HTTPS endpoint configured in the offchain storage
+//! of the node, and submits the fetched prices to the oracle by a transaction
+//! signed with the feeder key in the keystore of the node. Nothing is fed if
+//! there's no endpoint configured or no feeder key inserted.
+//!
+//! The endpoints are configured by the SCALE encoded
+//! `Vec<(CurrencyId, Vec<u8>)>` of the currencies and their urls at the key
+//! `acala/price-feeder/endpoints/` of the persistent offchain storage, e.g.
+//! by the `offchain_localStorageSet` rpc. An endpoint must respond the price
+//! of the currency in USD as a decimal string, e.g. `12.345`. The feeder key
+//! is a session key with the key type `pfdr`, generated and rotated along
+//! with the other session keys by the `author_rotateKeys` rpc, and the
+//! account of the key should be a member of the oracle.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, traits::OneSessionHandler};
+use frame_system::{
+	offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+	pallet_prelude::*,
+};
+use primitives::CurrencyId;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+	offchain::{
+		http,
+		storage::StorageValueRef,
+		storage_lock::{StorageLock, Time},
+		Duration,
+	},
+	traits::{Convert, One, Zero},
+	FixedPointNumber, RuntimeDebug,
+};
+use sp_std::prelude::*;
+use support::Price;
+
+mod mock;
+mod tests;
+
+pub use module::*;
+
+/// The key type of the feeder key.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"pfdr");
+
+pub const OFFCHAIN_WORKER_ENDPOINTS: &[u8] = b"acala/price-feeder/endpoints/";
+pub const OFFCHAIN_WORKER_LOCK: &[u8] = b"acala/price-feeder/lock/";
+pub const LOCK_DURATION: u64 = 5_000;
+pub const FETCH_TIMEOUT: u64 = 2_000;
+
+/// The feeder key signing the feed transactions.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_application_crypto::{app_crypto, sr25519};
+	use sp_runtime::{MultiSignature, MultiSigner};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct AuthorityId;
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthorityId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+#[derive(RuntimeDebug, PartialEq, Eq)]
+pub enum FeederErr {
+	OffchainLock,
+	NoFeederKey,
+	InvalidEndpoint,
+	HttpFetch,
+	InvalidPrice,
+	NoPriceFetched,
+	SubmitTransaction,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + CreateSignedTransaction<<Self as frame_system::Config>::Call> {
+		/// The feeder key signing the feed transactions
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// Create the oracle call to feed the fetched prices
+		type CreateFeedCall: Convert<Vec<(CurrencyId, Price)>, <Self as frame_system::Config>::Call>;
+
+		#[pallet::constant]
+		/// The interval in blocks to feed the prices
+		type FeedInterval: Get<Self::BlockNumber>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Runs after every block. Start offchain worker to fetch the prices
+		/// and submit the signed tx to feed them to the oracle.
+		#[cfg(feature = "offchain-feeder")]
+		fn offchain_worker(now: T::BlockNumber) {
+			if let Err(e) = Self::_offchain_worker(now) {
+				debug::info!(
+					target: "price-feeder offchain worker",
+					"cannot run offchain worker at {:?}: {:?}",
+					now,
+					e,
+				);
+			} else {
+				debug::debug!(
+					target: "price-feeder offchain worker",
+					"offchain worker start at block: {:?} already done!",
+					now,
+				);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+#[cfg_attr(not(feature = "offchain-feeder"), allow(dead_code))]
+impl<T: Config> Pallet<T> {
+	fn _offchain_worker(now: T::BlockNumber) -> Result<(), FeederErr> {
+		if !(now % T::FeedInterval::get().max(One::one())).is_zero() {
+			return Ok(());
+		}
+
+		let endpoints = StorageValueRef::persistent(&OFFCHAIN_WORKER_ENDPOINTS)
+			.get::<Vec<(CurrencyId, Vec<u8>)>>()
+			.flatten()
+			.unwrap_or_default();
+		if endpoints.is_empty() {
+			return Ok(());
+		}
+
+		let signer = Signer::<T, T::AuthorityId>::any_account();
+		if !signer.can_sign() {
+			return Err(FeederErr::NoFeederKey);
+		}
+
+		// acquire offchain worker lock
+		let lock_expiration = Duration::from_millis(LOCK_DURATION);
+		let mut lock = StorageLock::<'_, Time>::with_deadline(&OFFCHAIN_WORKER_LOCK, lock_expiration);
+		let mut guard = lock.try_lock().map_err(|_| FeederErr::OffchainLock)?;
+
+		let mut values: Vec<(CurrencyId, Price)> = vec![];
+		for (currency_id, url) in endpoints {
+			match Self::fetch_price(&url) {
+				Ok(price) => values.push((currency_id, price)),
+				Err(e) => {
+					// skip the currency, the others can still be fed
+					debug::warn!(
+						target: "price-feeder offchain worker",
+						"fetch price of {:?} failed: {:?}",
+						currency_id,
+						e,
+					);
+				}
+			}
+			guard.extend_lock().map_err(|_| FeederErr::OffchainLock)?;
+		}
+		if values.is_empty() {
+			return Err(FeederErr::NoPriceFetched);
+		}
+
+		match signer.send_signed_transaction(|_account| T::CreateFeedCall::convert(values.clone())) {
+			Some((_, Ok(()))) => Ok(()),
+			_ => Err(FeederErr::SubmitTransaction),
+		}
+	}
+
+	fn fetch_price(url: &[u8]) -> Result<Price, FeederErr> {
+		let url = sp_std::str::from_utf8(url).map_err(|_| FeederErr::InvalidEndpoint)?;
+		if !url.starts_with("https://") {
+			return Err(FeederErr::InvalidEndpoint);
+		}
+
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT));
+		let pending = http::Request::get(url)
+			.deadline(deadline)
+			.send()
+			.map_err(|_| FeederErr::HttpFetch)?;
+		let response = pending
+			.try_wait(deadline)
+			.map_err(|_| FeederErr::HttpFetch)?
+			.map_err(|_| FeederErr::HttpFetch)?;
+		if response.code != 200 {
+			return Err(FeederErr::HttpFetch);
+		}
+
+		parse_price(&response.body().collect::<Vec<u8>>()).ok_or(FeederErr::InvalidPrice)
+	}
+}
+
+impl<T: Config> sp_runtime::BoundToRuntimeAppPublic for Pallet<T> {
+	type Public = crypto::Public;
+}
+
+/// The feeder key is only used by the offchain worker of the node, nothing is
+/// tracked on chain when the session changes.
+impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
+	type Key = crypto::Public;
+
+	fn on_genesis_session<'a, I: 'a>(_validators: I)
+	where
+		I: Iterator<Item = (&'a T::AccountId, Self::Key)>,
+	{
+	}
+
+	fn on_new_session<'a, I: 'a>(_changed: bool, _validators: I, _queued_validators: I)
+	where
+		I: Iterator<Item = (&'a T::AccountId, Self::Key)>,
+	{
+	}
+
+	fn on_disabled(_validator_index: usize) {}
+}
+
+/// Parse the price from the decimal string, e.g. `12.345`. The digits beyond
+/// the accuracy of the price are truncated, and zero is not a valid price.
+pub fn parse_price(body: &[u8]) -> Option<Price> {
+	let body = sp_std::str::from_utf8(body).ok()?.trim();
+	let (integer, fraction) = match body.find('.') {
+		Some(index) => (&body[..index], &body[index + 1..]),
+		None => (body, ""),
+	};
+	if integer.is_empty() || !integer.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+		return None;
+	}
+
+	let mut unit = Price::accuracy();
+	let mut inner = integer.parse::<u128>().ok()?.checked_mul(unit)?;
+	for digit in fraction.bytes() {
+		unit /= 10;
+		inner = inner.checked_add(u128::from(digit - b'0').saturating_mul(unit))?;
+	}
+
+	Some(Price::from_inner(inner)).filter(|price| !price.is_zero())
+}
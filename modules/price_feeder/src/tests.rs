@@ -0,0 +1,124 @@
+//! Unit tests for the price feeder module.
+
+#![cfg(test)]
+
+use super::*;
+use codec::Decode;
+use mock::*;
+use parking_lot::RwLock;
+use sp_core::offchain::{
+	testing::{self, OffchainState, PendingRequest, PoolState},
+	OffchainExt, TransactionPoolExt,
+};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+use sp_std::sync::Arc;
+
+const PHRASE: &str = "news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+
+fn offchain_ext(
+	with_feeder_key: bool,
+) -> (
+	sp_io::TestExternalities,
+	Arc<RwLock<OffchainState>>,
+	Arc<RwLock<PoolState>>,
+) {
+	let mut ext = ExtBuilder::default().build();
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	let keystore = KeyStore::new();
+	if with_feeder_key {
+		SyncCryptoStore::sr25519_generate_new(&keystore, KEY_TYPE, Some(&format!("{}/feeder", PHRASE))).unwrap();
+	}
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+
+	(ext, offchain_state, pool_state)
+}
+
+fn set_endpoints(endpoints: Vec<(CurrencyId, &str)>) {
+	let endpoints = endpoints
+		.into_iter()
+		.map(|(currency_id, url)| (currency_id, url.as_bytes().to_vec()))
+		.collect::<Vec<_>>();
+	StorageValueRef::persistent(&OFFCHAIN_WORKER_ENDPOINTS).set(&endpoints);
+}
+
+fn expect_price_request(state: &Arc<RwLock<OffchainState>>, url: &str, body: &[u8]) {
+	state.write().expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: url.into(),
+		response: Some(body.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+#[test]
+fn parse_price_work() {
+	assert_eq!(
+		parse_price(b"12.345"),
+		Some(Price::saturating_from_rational(12345, 1000))
+	);
+	assert_eq!(parse_price(b" 100\n"), Some(Price::saturating_from_integer(100)));
+	assert_eq!(parse_price(b"0.000000000000000001"), Some(Price::from_inner(1)));
+	// the digits beyond the accuracy are truncated
+	assert_eq!(parse_price(b"1.0000000000000000009"), Some(Price::one()));
+
+	assert_eq!(parse_price(b""), None);
+	assert_eq!(parse_price(b".5"), None);
+	assert_eq!(parse_price(b"1.2.3"), None);
+	assert_eq!(parse_price(b"-1"), None);
+	assert_eq!(parse_price(b"1e3"), None);
+	assert_eq!(parse_price(b"0.0"), None);
+	assert_eq!(parse_price(b"340282366920938463464"), None);
+}
+
+#[test]
+fn feed_prices_work() {
+	let (mut ext, offchain_state, pool_state) = offchain_ext(true);
+	ext.execute_with(|| {
+		set_endpoints(vec![
+			(DOT, "https://prices.example/dot"),
+			(XBTC, "http://prices.example/xbtc"),
+		]);
+
+		// not at the feed interval
+		assert_eq!(PriceFeederModule::_offchain_worker(4), Ok(()));
+		assert!(pool_state.read().transactions.is_empty());
+
+		// the non HTTPS endpoint is skipped
+		expect_price_request(&offchain_state, "https://prices.example/dot", b"12.5");
+		assert_eq!(PriceFeederModule::_offchain_worker(5), Ok(()));
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature.unwrap().0, 0);
+		assert_eq!(
+			tx.call,
+			Call::Oracle(orml_oracle::Call::feed_values(vec![(
+				DOT,
+				Price::saturating_from_rational(25, 2)
+			)]))
+		);
+
+		// nothing to feed if all the fetches fail
+		expect_price_request(&offchain_state, "https://prices.example/dot", b"unavailable");
+		assert_eq!(PriceFeederModule::_offchain_worker(10), Err(FeederErr::NoPriceFetched));
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn feed_nothing_without_endpoints_or_feeder_key() {
+	let (mut ext, _, pool_state) = offchain_ext(false);
+	ext.execute_with(|| {
+		assert_eq!(PriceFeederModule::_offchain_worker(5), Ok(()));
+
+		set_endpoints(vec![(DOT, "https://prices.example/dot")]);
+		assert_eq!(PriceFeederModule::_offchain_worker(5), Err(FeederErr::NoFeederKey));
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
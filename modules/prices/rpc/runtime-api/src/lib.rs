@@ -0,0 +1,28 @@
+//! Runtime API definition for prices module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait PricesApi<CurrencyId, Price, Moment> where
+		CurrencyId: Codec,
+		Price: Codec,
+		Moment: Codec,
+	{
+		/// The prices of the currencies in USD, in the same order.
+		fn get_prices(currency_ids: Vec<CurrencyId>) -> Vec<Option<Price>>;
+
+		/// The unix times in milliseconds when the underlying feeds updated
+		/// the prices of the currencies, in the same order.
+		fn get_feeds_updated_at(currency_ids: Vec<CurrencyId>) -> Vec<Vec<Moment>>;
+
+		/// The price between the minimal units of the currencies, normalized
+		/// by their decimals.
+		fn get_relative_price(base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price>;
+	}
+}
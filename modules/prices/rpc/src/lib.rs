@@ -0,0 +1,128 @@
+//! RPC interface for the prices module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as PricesClient;
+pub use module_prices_rpc_runtime_api::PricesApi as PricesRuntimeApi;
+
+/// The price of currency with the update times of its underlying feeds.
+#[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceInfo<Price, Moment> {
+	/// The price in USD, `None` if it's unavailable.
+	pub price: Option<Price>,
+	/// The unix times in milliseconds when the underlying feeds updated the
+	/// price.
+	pub feeds_updated_at: Vec<Moment>,
+}
+
+#[rpc]
+pub trait PricesApi<BlockHash, CurrencyId, Price, ResponseType> {
+	#[rpc(name = "prices_getPrices")]
+	fn get_prices(&self, currency_ids: Vec<CurrencyId>, at: Option<BlockHash>) -> Result<ResponseType>;
+
+	#[rpc(name = "prices_getRelativePrice")]
+	fn get_relative_price(
+		&self,
+		base_currency_id: CurrencyId,
+		quote_currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Price>>;
+}
+
+/// A struct that implements the [`PricesApi`].
+pub struct Prices<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Prices<C, B> {
+	/// Create new `Prices` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Prices {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to get prices.".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, CurrencyId, Price, Moment>
+	PricesApi<<Block as BlockT>::Hash, CurrencyId, Price, Vec<PriceInfo<Price, Moment>>> for Prices<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: PricesRuntimeApi<Block, CurrencyId, Price, Moment>,
+	CurrencyId: Codec + Clone,
+	Price: Codec,
+	Moment: Codec,
+{
+	fn get_prices(
+		&self,
+		currency_ids: Vec<CurrencyId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PriceInfo<Price, Moment>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		let prices = api
+			.get_prices(&at, currency_ids.clone())
+			.map_err(runtime_error_into_rpc_err)?;
+		let feeds_updated_at = api
+			.get_feeds_updated_at(&at, currency_ids)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(prices
+			.into_iter()
+			.zip(feeds_updated_at)
+			.map(|(price, feeds_updated_at)| PriceInfo {
+				price,
+				feeds_updated_at,
+			})
+			.collect())
+	}
+
+	fn get_relative_price(
+		&self,
+		base_currency_id: CurrencyId,
+		quote_currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Price>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_relative_price(&at, base_currency_id, quote_currency_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
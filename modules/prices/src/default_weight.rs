@@ -15,4 +15,29 @@ impl crate::WeightInfo for () {
 	fn unlock_price() -> Weight {
 		(48_900_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
 	}
+	fn set_twap_window() -> Weight {
+		(36_512_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_price_age() -> Weight {
+		(31_204_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_feed_aggregation() -> Weight {
+		(32_871_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_price_source_priority() -> Weight {
+		(33_458_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_price_source_weights() -> Weight {
+		(35_117_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
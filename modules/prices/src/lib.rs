@@ -5,18 +5,42 @@
 //! The data from Oracle cannot be used in business, prices module will do some
 //! process and feed prices for Acala. Process include:
 //!   - specify a fixed price for stable currency
-//!   - feed price in USD or related price bewteen two currencies
-//!   - lock/unlock the price data get from oracle
+//!   - derive the price of liquid currency from the price of staking currency
+//!     and the liquid/staking exchange rate, which is unavailable if the
+//!     exchange rate is stale
+//!   - feed price in USD or related price bewteen two currencies, the related
+//!     price is between the minimal units of the currencies, normalized by
+//!     their decimals
+//!   - lock/unlock the price data get from oracle, the last sampled price is
+//!     locked if the oracle has no price during incidents
+//!   - aggregate the prices of currency from multiple independent feeds into
+//!     their median, rejecting the outliers, if configured for the currency,
+//!     and provide the widest confidence interval of the accepted feeds
+//!   - fall back to the secondary source when the oracle price of currency is
+//!     older than its max age, or feed no price if the secondary source has
+//!     none either
+//!   - smooth the price by the time weighted average of the source prices
+//!     sampled in the recent blocks, over a window configured per currency
+//!   - get the price of currency from the sources in the priority order
+//!     configured per currency, e.g. the DEX for the currencies without oracle
+//!     feed
+//!   - combine the prices of currency from the sources by the weights
+//!     configured per currency, e.g. 70% oracle median and 30% DEX TWAP
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{pallet_prelude::*, transactional};
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, traits::UnixTime, transactional};
 use frame_system::pallet_prelude::*;
-use orml_traits::{DataFeeder, DataProvider};
-use primitives::CurrencyId;
-use sp_runtime::traits::{CheckedDiv, CheckedMul};
-use support::{ExchangeRateProvider, Price, PriceProvider};
+use orml_traits::{DataFeeder, DataProvider, GetByKey};
+use primitives::{CurrencyId, Moment};
+use sp_runtime::{
+	traits::{CheckedDiv, CheckedMul, One, SaturatedConversion, Saturating, Zero},
+	FixedPointNumber, RuntimeDebug,
+};
+use sp_std::prelude::*;
+use support::{ExchangeRateProvider, Price, PriceProvider, Ratio};
 
 mod default_weight;
 mod mock;
@@ -27,6 +51,48 @@ pub use module::*;
 pub trait WeightInfo {
 	fn lock_price() -> Weight;
 	fn unlock_price() -> Weight;
+	fn set_twap_window() -> Weight;
+	fn set_max_price_age() -> Weight;
+	fn set_feed_aggregation() -> Weight;
+	fn set_price_source_priority() -> Weight;
+	fn set_price_source_weights() -> Weight;
+}
+
+/// The price of currency from a feed.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct FeedPrice {
+	/// The price in USD
+	pub price: Price,
+	/// The confidence interval of the price, the max deviation of the real
+	/// price from it, `None` if the feed doesn't provide it
+	pub confidence: Option<Price>,
+	/// The unix time in milliseconds when the price was updated
+	pub updated_at: Moment,
+}
+
+/// The independent feeds of prices, e.g. the oracle pallet instances.
+pub trait PriceFeeds {
+	/// The prices of currency from all the feeds which have the price.
+	fn get_prices(currency_id: &CurrencyId) -> Vec<FeedPrice>;
+}
+
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct FeedAggregationParams {
+	/// The min number of the feeds to aggregate
+	pub min_feeds: u32,
+	/// The max deviation of a price from the median, the price deviates more
+	/// is rejected as outlier
+	pub max_deviation: Ratio,
+}
+
+/// The sources the price of currency can be got from.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum PriceSource {
+	/// The aggregated price from `Feeds` if configured, otherwise the price
+	/// from `Source`, unavailable if it's stale.
+	Oracle,
+	/// The price from `FallbackSource`, e.g. the TWAP of DEX.
+	Fallback,
 }
 
 #[frame_support::pallet]
@@ -56,17 +122,67 @@ pub mod module {
 		/// The liquid currency id, it should be LDOT in Acala.
 		type GetLiquidCurrencyId: Get<CurrencyId>;
 
-		/// The origin which may lock and unlock prices feed to system.
+		/// The origin which may lock and unlock prices feed to system, and
+		/// update the TWAP windows, max price ages, feed aggregations, price
+		/// source priorities and weights.
 		type LockOrigin: EnsureOrigin<Self::Origin>;
 
 		/// The provider of the exchange rate between liquid currency and
 		/// staking currency.
 		type LiquidStakingExchangeRateProvider: ExchangeRateProvider;
 
+		/// The block number at which the liquid staking exchange rate is last
+		/// updated, `None` means it has never been updated.
+		type LiquidStakingExchangeRateUpdatedAt: Get<Option<Self::BlockNumber>>;
+
+		#[pallet::constant]
+		/// The max number of blocks since the last update of the liquid
+		/// staking exchange rate, the price of liquid currency is unavailable
+		/// once the exchange rate is older than it.
+		type MaxLiquidStakingExchangeRateAge: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The max number of blocks in the TWAP window of a currency.
+		type MaxTwapWindow: Get<u32>;
+
+		/// The unix time in milliseconds when the price of currency from
+		/// `Source` was updated.
+		type SourceUpdatedAt: GetByKey<CurrencyId, Option<Moment>>;
+
+		/// The secondary source of prices, used when the price from `Source`
+		/// is stale.
+		type FallbackSource: DataProvider<CurrencyId, Price>;
+
+		/// Time used for checking the age of the prices from `Source`
+		type UnixTime: UnixTime;
+
+		/// The independent feeds aggregated into the price of currency
+		/// configured with feed aggregation, instead of `Source`.
+		type Feeds: PriceFeeds;
+
+		/// The decimals of currency, i.e. the number of the decimal places of
+		/// its minimal unit.
+		type CurrencyDecimals: GetByKey<CurrencyId, u8>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The TWAP window exceeds the max window
+		InvalidTwapWindow,
+		/// The min number of the feeds to aggregate is zero
+		InvalidFeedAggregationParams,
+		/// There's neither source price nor sampled price to lock
+		NoPriceToLock,
+		/// The price sources contain duplicates
+		InvalidPriceSourcePriority,
+		/// The price source weights contain duplicates or zero weight, or
+		/// don't sum up to one
+		InvalidPriceSourceWeights,
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -74,6 +190,20 @@ pub mod module {
 		LockPrice(CurrencyId, Price),
 		/// Unlock price. \[currency_id\]
 		UnlockPrice(CurrencyId),
+		/// The TWAP window of currency updated. \[currency_id, window\]
+		TwapWindowUpdated(CurrencyId, u32),
+		/// The max age of the price of currency updated. \[currency_id,
+		/// max_age\]
+		MaxPriceAgeUpdated(CurrencyId, Option<Moment>),
+		/// The feed aggregation of currency updated. \[currency_id,
+		/// params\]
+		FeedAggregationUpdated(CurrencyId, Option<FeedAggregationParams>),
+		/// The price source priority of currency updated. \[currency_id,
+		/// sources\]
+		PriceSourcePriorityUpdated(CurrencyId, Vec<PriceSource>),
+		/// The price source weights of currency updated. \[currency_id,
+		/// weights\]
+		PriceSourceWeightsUpdated(CurrencyId, Vec<(PriceSource, Ratio)>),
 	}
 
 	/// Mapping from currency id to it's locked price
@@ -81,15 +211,66 @@ pub mod module {
 	#[pallet::getter(fn locked_price)]
 	pub type LockedPrice<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
 
+	/// The number of recent blocks whose source prices are averaged into the
+	/// TWAP of currency.
+	#[pallet::storage]
+	#[pallet::getter(fn twap_window)]
+	pub type TwapWindow<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, u32, ValueQuery>;
+
+	/// The source prices of currency sampled in the recent blocks, the
+	/// oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn price_samples)]
+	pub type PriceSamples<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Vec<Price>, ValueQuery>;
+
+	/// The max age in milliseconds of the price of currency from `Source`,
+	/// the price older than it is stale. `None` means the price never gets
+	/// stale.
+	#[pallet::storage]
+	#[pallet::getter(fn max_price_age)]
+	pub type MaxPriceAge<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Moment, OptionQuery>;
+
+	/// The params of aggregating the prices of currency from `Feeds`,
+	/// `None` means the price is from `Source`.
+	#[pallet::storage]
+	#[pallet::getter(fn feed_aggregation)]
+	pub type FeedAggregation<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, FeedAggregationParams, OptionQuery>;
+
+	/// The sources of the price of currency, the first one with price is
+	/// used. Empty means the price is from the oracle, falling back to
+	/// `FallbackSource` only if the oracle price is stale.
+	#[pallet::storage]
+	#[pallet::getter(fn price_source_priority)]
+	pub type PriceSourcePriority<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Vec<PriceSource>, ValueQuery>;
+
+	/// The weights of the sources combined into the price of currency, which
+	/// is unavailable if any of the sources has no price. Empty means the
+	/// price isn't combined, and precedes the price source priority if not.
+	#[pallet::storage]
+	#[pallet::getter(fn price_source_weights)]
+	pub type PriceSourceWeights<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, Vec<(PriceSource, Ratio)>, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Sample the source prices of the currencies with TWAP window.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let mut count: Weight = 0;
+			for (currency_id, window) in TwapWindow::<T>::iter() {
+				Self::sample_price(currency_id, window);
+				count += 1;
+			}
+			T::DbWeight::get().reads_writes(count.saturating_mul(3).saturating_add(1), count)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Lock the price and feed it to system.
+		/// Lock the price and feed it to system, the liquidations of the
+		/// collateral are paused until it's unlocked.
 		///
 		/// The dispatch origin of this call must be `LockOrigin`.
 		///
@@ -99,6 +280,7 @@ pub mod module {
 		pub fn lock_price(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
 			T::LockOrigin::ensure_origin(origin)?;
 			<Pallet<T> as PriceProvider<CurrencyId>>::lock_price(currency_id);
+			ensure!(Self::locked_price(currency_id).is_some(), Error::<T>::NoPriceToLock);
 			Ok(().into())
 		}
 
@@ -114,16 +296,384 @@ pub mod module {
 			<Pallet<T> as PriceProvider<CurrencyId>>::unlock_price(currency_id);
 			Ok(().into())
 		}
+
+		/// Update the TWAP window of currency, the price samples out of the
+		/// new window are dropped.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `window`: the number of recent blocks to average, 0 means the
+		///   TWAP is the spot price.
+		#[pallet::weight((T::WeightInfo::set_twap_window(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_twap_window(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			window: u32,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+			ensure!(window <= T::MaxTwapWindow::get(), Error::<T>::InvalidTwapWindow);
+
+			if window.is_zero() {
+				TwapWindow::<T>::remove(currency_id);
+				PriceSamples::<T>::remove(currency_id);
+			} else {
+				TwapWindow::<T>::insert(currency_id, window);
+				PriceSamples::<T>::mutate(currency_id, |samples| {
+					let excess = samples.len().saturating_sub(window as usize);
+					samples.drain(..excess);
+				});
+			}
+			Self::deposit_event(Event::TwapWindowUpdated(currency_id, window));
+			Ok(().into())
+		}
+
+		/// Update the max age of the price of currency from `Source`
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `max_age`: the max age in milliseconds, `None` means the price
+		///   never gets stale.
+		#[pallet::weight((T::WeightInfo::set_max_price_age(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_max_price_age(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_age: Option<Moment>,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+			MaxPriceAge::<T>::mutate_exists(currency_id, |age| *age = max_age);
+			Self::deposit_event(Event::MaxPriceAgeUpdated(currency_id, max_age));
+			Ok(().into())
+		}
+
+		/// Update the feed aggregation of currency
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `params`: the aggregation params, `None` means the price is from
+		///   `Source`.
+		#[pallet::weight((T::WeightInfo::set_feed_aggregation(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_feed_aggregation(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: Option<FeedAggregationParams>,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+			if let Some(params) = params {
+				ensure!(!params.min_feeds.is_zero(), Error::<T>::InvalidFeedAggregationParams);
+			}
+			FeedAggregation::<T>::mutate_exists(currency_id, |old_params| *old_params = params);
+			Self::deposit_event(Event::FeedAggregationUpdated(currency_id, params));
+			Ok(().into())
+		}
+
+		/// Update the price source priority of currency
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `sources`: the sources in the priority order, empty means the
+		///   default order.
+		#[pallet::weight((T::WeightInfo::set_price_source_priority(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_price_source_priority(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			sources: Vec<PriceSource>,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+			for (i, source) in sources.iter().enumerate() {
+				ensure!(!sources[..i].contains(source), Error::<T>::InvalidPriceSourcePriority);
+			}
+
+			if sources.is_empty() {
+				PriceSourcePriority::<T>::remove(currency_id);
+			} else {
+				PriceSourcePriority::<T>::insert(currency_id, sources.clone());
+			}
+			Self::deposit_event(Event::PriceSourcePriorityUpdated(currency_id, sources));
+			Ok(().into())
+		}
+
+		/// Update the price source weights of currency
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `weights`: the sources with their non-zero weights summing up to
+		///   one, empty means the price isn't combined.
+		#[pallet::weight((T::WeightInfo::set_price_source_weights(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_price_source_weights(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			weights: Vec<(PriceSource, Ratio)>,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+
+			if weights.is_empty() {
+				PriceSourceWeights::<T>::remove(currency_id);
+			} else {
+				let mut total_weight = Ratio::zero();
+				for (i, (source, weight)) in weights.iter().enumerate() {
+					ensure!(
+						!weight.is_zero() && !weights[..i].iter().any(|(s, _)| s == source),
+						Error::<T>::InvalidPriceSourceWeights
+					);
+					total_weight = total_weight.saturating_add(*weight);
+				}
+				ensure!(total_weight == Ratio::one(), Error::<T>::InvalidPriceSourceWeights);
+				PriceSourceWeights::<T>::insert(currency_id, weights.clone());
+			}
+			Self::deposit_event(Event::PriceSourceWeightsUpdated(currency_id, weights));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether the liquid staking exchange rate is too old to derive the
+	/// price of liquid currency.
+	pub fn is_liquid_staking_exchange_rate_stale() -> bool {
+		match T::LiquidStakingExchangeRateUpdatedAt::get() {
+			Some(updated_at) => {
+				<frame_system::Module<T>>::block_number().saturating_sub(updated_at)
+					> T::MaxLiquidStakingExchangeRateAge::get()
+			}
+			None => true,
+		}
+	}
+
+	/// Whether the price of currency updated at `updated_at` is older than
+	/// its max age, the price without update time is always stale.
+	fn is_stale(currency_id: CurrencyId, updated_at: Option<Moment>) -> bool {
+		match Self::max_price_age(currency_id) {
+			Some(max_age) => match updated_at {
+				Some(updated_at) => {
+					let now: Moment = T::UnixTime::now().as_millis().saturated_into();
+					now.saturating_sub(updated_at) > max_age
+				}
+				None => true,
+			},
+			None => false,
+		}
+	}
+
+	/// Whether the price of currency from `Source` is older than its max
+	/// age.
+	pub fn is_source_price_stale(currency_id: CurrencyId) -> bool {
+		Self::is_stale(currency_id, T::SourceUpdatedAt::get(&currency_id))
+	}
+
+	/// The unix times in milliseconds when the feeds updated the prices of
+	/// currency.
+	pub fn get_feeds_updated_at(currency_id: CurrencyId) -> Vec<Moment> {
+		T::Feeds::get_prices(&currency_id)
+			.into_iter()
+			.map(|feed_price| feed_price.updated_at)
+			.collect()
+	}
+
+	/// The median of the prices, the average of the middle two if the
+	/// number of the prices is even.
+	fn median(mut prices: Vec<Price>) -> Option<Price> {
+		prices.sort();
+		let mid = prices.len() / 2;
+		match prices.len() {
+			0 => None,
+			len if len % 2 == 1 => Some(prices[mid]),
+			_ => prices[mid - 1]
+				.saturating_add(prices[mid])
+				.checked_div(&Price::saturating_from_integer(2)),
+		}
+	}
+
+	/// The fresh prices of currency from `Feeds` accepted by the
+	/// aggregation, rejecting the outliers deviating from their median.
+	///
+	/// Returns `Err` if the fresh prices are not enough, or `Ok(None)` if
+	/// the prices are not enough after rejecting the outliers.
+	fn get_accepted_feed_prices(
+		currency_id: CurrencyId,
+		params: FeedAggregationParams,
+	) -> Result<Option<Vec<FeedPrice>>, ()> {
+		let min_feeds = params.min_feeds as usize;
+		let feed_prices: Vec<FeedPrice> = T::Feeds::get_prices(&currency_id)
+			.into_iter()
+			.filter(|feed_price| !Self::is_stale(currency_id, Some(feed_price.updated_at)))
+			.collect();
+		if feed_prices.len() < min_feeds {
+			return Err(());
+		}
+
+		let median = Self::median(feed_prices.iter().map(|feed_price| feed_price.price).collect()).ok_or(())?;
+		let max_deviation = params.max_deviation.saturating_mul(median);
+		let feed_prices: Vec<FeedPrice> = feed_prices
+			.into_iter()
+			.filter(|feed_price| {
+				let deviation = if feed_price.price > median {
+					feed_price.price.saturating_sub(median)
+				} else {
+					median.saturating_sub(feed_price.price)
+				};
+				deviation <= max_deviation
+			})
+			.collect();
+		if feed_prices.len() < min_feeds {
+			return Ok(None);
+		}
+		Ok(Some(feed_prices))
+	}
+
+	/// Aggregate the fresh prices of currency from `Feeds` into their
+	/// median after rejecting the outliers.
+	///
+	/// Returns `Err` if the fresh prices are not enough, or `Ok(None)` if
+	/// the prices are not enough after rejecting the outliers.
+	fn get_aggregated_price(currency_id: CurrencyId, params: FeedAggregationParams) -> Result<Option<Price>, ()> {
+		Ok(Self::get_accepted_feed_prices(currency_id, params)?
+			.and_then(|feed_prices| Self::median(feed_prices.iter().map(|feed_price| feed_price.price).collect())))
+	}
+
+	/// The widest confidence interval of the accepted prices of currency
+	/// from `Feeds`, in proportion to their median. `None` if none of them
+	/// provides the confidence interval.
+	fn get_aggregated_confidence(currency_id: CurrencyId, params: FeedAggregationParams) -> Option<Ratio> {
+		let feed_prices = Self::get_accepted_feed_prices(currency_id, params).ok().flatten()?;
+		let median = Self::median(feed_prices.iter().map(|feed_price| feed_price.price).collect())?;
+		feed_prices
+			.iter()
+			.filter_map(|feed_price| feed_price.confidence)
+			.max()?
+			.checked_div(&median)
+	}
+
+	/// The aggregated price from `Feeds` if configured, otherwise the price
+	/// from `Source`. Falls back to `FallbackSource` if the price is stale.
+	///
+	/// If the price source weights of currency are configured, the price is
+	/// combined from the sources instead, otherwise if the price source
+	/// priority is configured, it's from the first source with price.
+	fn get_oracle_price(currency_id: CurrencyId) -> Option<Price> {
+		let weights = Self::price_source_weights(currency_id);
+		if !weights.is_empty() {
+			return weights.into_iter().try_fold(Price::zero(), |total, (source, weight)| {
+				Self::get_price_from(currency_id, source)
+					.map(|price| total.saturating_add(price.saturating_mul(weight)))
+			});
+		}
+
+		let priority = Self::price_source_priority(currency_id);
+		if !priority.is_empty() {
+			return priority
+				.into_iter()
+				.find_map(|source| Self::get_price_from(currency_id, source));
+		}
+
+		if let Some(params) = Self::feed_aggregation(currency_id) {
+			Self::get_aggregated_price(currency_id, params).unwrap_or_else(|_| T::FallbackSource::get(&currency_id))
+		} else if Self::is_source_price_stale(currency_id) {
+			T::FallbackSource::get(&currency_id)
+		} else {
+			T::Source::get(&currency_id)
+		}
+	}
+
+	/// The price of currency from the source.
+	fn get_price_from(currency_id: CurrencyId, source: PriceSource) -> Option<Price> {
+		match source {
+			PriceSource::Oracle => Self::get_fresh_oracle_price(currency_id),
+			PriceSource::Fallback => T::FallbackSource::get(&currency_id),
+		}
+	}
+
+	/// The aggregated price from `Feeds` if configured, otherwise the price
+	/// from `Source`, `None` if the price is stale.
+	fn get_fresh_oracle_price(currency_id: CurrencyId) -> Option<Price> {
+		if let Some(params) = Self::feed_aggregation(currency_id) {
+			Self::get_aggregated_price(currency_id, params).ok().flatten()
+		} else if Self::is_source_price_stale(currency_id) {
+			None
+		} else {
+			T::Source::get(&currency_id)
+		}
+	}
+
+	/// The unlocked price from the source, derived for liquid currency.
+	fn get_source_price(currency_id: CurrencyId) -> Option<Price> {
+		if currency_id == T::GetLiquidCurrencyId::get() {
+			Self::get_liquid_currency_price()
+		} else {
+			Self::get_oracle_price(currency_id)
+		}
+	}
+
+	/// Append the source price of currency to its samples, keep at most
+	/// `window` recent ones.
+	fn sample_price(currency_id: CurrencyId, window: u32) {
+		if let Some(price) = Self::get_source_price(currency_id) {
+			PriceSamples::<T>::mutate(currency_id, |samples| {
+				samples.push(price);
+				let excess = samples.len().saturating_sub(window as usize);
+				samples.drain(..excess);
+			});
+		}
+	}
+
+	/// The average of the sampled source prices of currency, `None` if
+	/// there's no sample.
+	fn get_average_price(currency_id: CurrencyId) -> Option<Price> {
+		let samples = Self::price_samples(currency_id);
+		if samples.is_empty() {
+			return None;
+		}
+		let total = samples
+			.iter()
+			.fold(Price::zero(), |total, price| total.saturating_add(*price));
+		total.checked_div(&Price::saturating_from_integer(samples.len() as u128))
+	}
+
+	/// Convert the relative price between the whole units of currencies into
+	/// the one between their minimal units by the difference of decimals.
+	fn normalize_decimals(price: Price, base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
+		let base_decimals = T::CurrencyDecimals::get(&base_currency_id);
+		let quote_decimals = T::CurrencyDecimals::get(&quote_currency_id);
+		if base_decimals >= quote_decimals {
+			let adjustment = 10u128.checked_pow(base_decimals.saturating_sub(quote_decimals).into())?;
+			price.checked_div(&Price::checked_from_integer(adjustment)?)
+		} else {
+			let adjustment = 10u128.checked_pow(quote_decimals.saturating_sub(base_decimals).into())?;
+			price.checked_mul(&Price::checked_from_integer(adjustment)?)
+		}
+	}
+
+	/// The product of staking currency price and liquid/staking exchange
+	/// rate, `None` if the exchange rate is stale.
+	fn get_liquid_currency_price() -> Option<Price> {
+		if Self::is_liquid_staking_exchange_rate_stale() {
+			return None;
+		}
+
+		Self::get_price(T::GetStakingCurrencyId::get())
+			.and_then(|n| n.checked_mul(&T::LiquidStakingExchangeRateProvider::get_exchange_rate()))
 	}
 }
 
 impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
-	/// get relative price between two currency types
+	/// get relative price between the minimal units of two currency types,
+	/// normalized by their decimals
 	fn get_relative_price(base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
 		if let (Some(base_price), Some(quote_price)) =
 			(Self::get_price(base_currency_id), Self::get_price(quote_currency_id))
 		{
-			base_price.checked_div(&quote_price)
+			base_price
+				.checked_div(&quote_price)
+				.and_then(|price| Self::normalize_decimals(price, base_currency_id, quote_currency_id))
 		} else {
 			None
 		}
@@ -135,19 +685,42 @@ impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
 			// if is stable currency, return fixed price
 			Some(T::StableCurrencyFixedPrice::get())
 		} else if currency_id == T::GetLiquidCurrencyId::get() {
-			// if is homa liquid currency, return the product of staking currency price and
-			// liquid/staking exchange rate.
-			Self::get_price(T::GetStakingCurrencyId::get())
-				.and_then(|n| n.checked_mul(&T::LiquidStakingExchangeRateProvider::get_exchange_rate()))
+			// if is homa liquid currency, return the locked price or the derived price.
+			Self::locked_price(currency_id).or_else(Self::get_liquid_currency_price)
+		} else {
+			// if locked price exists, return it, otherwise return latest price from oracle,
+			// or from the fallback source if the oracle price is stale.
+			Self::locked_price(currency_id).or_else(|| Self::get_oracle_price(currency_id))
+		}
+	}
+
+	/// get the time weighted average price in USD over the TWAP window of
+	/// currency, the spot price if there's no window or sample
+	fn get_twap_price(currency_id: CurrencyId) -> Option<Price> {
+		if currency_id == T::GetStableCurrencyId::get() {
+			Some(T::StableCurrencyFixedPrice::get())
+		} else if let Some(locked_price) = Self::locked_price(currency_id) {
+			Some(locked_price)
+		} else if Self::twap_window(currency_id).is_zero() {
+			Self::get_price(currency_id)
 		} else {
-			// if locked price exists, return it, otherwise return latest price from oracle.
-			Self::locked_price(currency_id).or_else(|| T::Source::get(&currency_id))
+			Self::get_average_price(currency_id).or_else(|| Self::get_price(currency_id))
 		}
 	}
 
+	/// get relative price between the time weighted average prices of the
+	/// minimal units of two currency types, normalized by their decimals
+	fn get_relative_twap_price(base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
+		Self::get_twap_price(base_currency_id)?
+			.checked_div(&Self::get_twap_price(quote_currency_id)?)
+			.and_then(|price| Self::normalize_decimals(price, base_currency_id, quote_currency_id))
+	}
+
 	fn lock_price(currency_id: CurrencyId) {
-		// lock price when get valid price from source
-		if let Some(val) = T::Source::get(&currency_id) {
+		// lock price when get valid price from source, or the last sampled price
+		if let Some(val) =
+			Self::get_source_price(currency_id).or_else(|| Self::price_samples(currency_id).last().copied())
+		{
 			LockedPrice::<T>::insert(currency_id, val);
 			<Pallet<T>>::deposit_event(Event::LockPrice(currency_id, val));
 		}
@@ -157,4 +730,21 @@ impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
 		LockedPrice::<T>::remove(currency_id);
 		<Pallet<T>>::deposit_event(Event::UnlockPrice(currency_id));
 	}
+
+	fn is_price_locked(currency_id: CurrencyId) -> bool {
+		LockedPrice::<T>::contains_key(currency_id)
+	}
+
+	/// get the confidence interval of the aggregated feed price, `None` for
+	/// the stable currency and locked price
+	fn get_price_confidence(currency_id: CurrencyId) -> Option<Ratio> {
+		if currency_id == T::GetStableCurrencyId::get() || Self::is_price_locked(currency_id) {
+			None
+		} else if currency_id == T::GetLiquidCurrencyId::get() {
+			// the exchange rate doesn't change the relative confidence interval
+			Self::get_price_confidence(T::GetStakingCurrencyId::get())
+		} else {
+			Self::feed_aggregation(currency_id).and_then(|params| Self::get_aggregated_confidence(currency_id, params))
+		}
+	}
 }
@@ -4,12 +4,13 @@
 
 use super::*;
 use crate::mock::sp_api_hidden_includes_construct_runtime::hidden_include::inherent::BlockT;
-use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types, traits::UnixTime};
 use frame_system::EnsureSignedBy;
 use orml_traits::DataFeeder;
 use primitives::TokenSymbol;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, FixedPointNumber};
+use sp_std::{cell::RefCell, time::Duration};
 use support::ExchangeRate;
 
 pub type AccountId = u128;
@@ -20,6 +21,7 @@ pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
+pub const RENBTC: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
 
 mod prices {
 	pub use super::super::*;
@@ -59,9 +61,20 @@ impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	fn get(currency_id: &CurrencyId) -> Option<Price> {
 		match currency_id {
 			&AUSD => Some(Price::saturating_from_rational(99, 100)),
-			&BTC => Some(Price::saturating_from_integer(5000)),
+			&BTC => BTC_PRICE.with(|v| *v.borrow()),
 			&DOT => Some(Price::saturating_from_integer(100)),
 			&ACA => Some(Price::zero()),
+			&RENBTC => Some(Price::saturating_from_integer(5000)),
+			_ => None,
+		}
+	}
+}
+
+pub struct MockFallbackDataProvider;
+impl DataProvider<CurrencyId, Price> for MockFallbackDataProvider {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		match currency_id {
+			&BTC => Some(Price::saturating_from_integer(4000)),
 			_ => None,
 		}
 	}
@@ -80,6 +93,90 @@ impl ExchangeRateProvider for MockLiquidStakingExchangeProvider {
 	}
 }
 
+thread_local! {
+	static EXCHANGE_RATE_UPDATED_AT: RefCell<Option<BlockNumber>> = RefCell::new(Some(0));
+	static BTC_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::saturating_from_integer(5000)));
+	static SOURCE_UPDATED_AT: RefCell<Option<Moment>> = RefCell::new(Some(0));
+	static NOW_MILLIS: RefCell<Moment> = RefCell::new(0);
+	static FEED_PRICES: RefCell<Vec<FeedPrice>> = RefCell::new(vec![]);
+}
+
+pub fn mock_set_feed_prices(prices: Vec<(Price, Moment)>) {
+	mock_set_feed_prices_with_confidence(
+		prices
+			.into_iter()
+			.map(|(price, updated_at)| (price, None, updated_at))
+			.collect(),
+	);
+}
+
+pub fn mock_set_feed_prices_with_confidence(prices: Vec<(Price, Option<Price>, Moment)>) {
+	FEED_PRICES.with(|v| {
+		*v.borrow_mut() = prices
+			.into_iter()
+			.map(|(price, confidence, updated_at)| FeedPrice {
+				price,
+				confidence,
+				updated_at,
+			})
+			.collect()
+	});
+}
+
+pub struct MockPriceFeeds;
+impl PriceFeeds for MockPriceFeeds {
+	fn get_prices(_: &CurrencyId) -> Vec<FeedPrice> {
+		FEED_PRICES.with(|v| v.borrow().clone())
+	}
+}
+
+pub fn mock_set_source_updated_at(updated_at: Option<Moment>) {
+	SOURCE_UPDATED_AT.with(|v| *v.borrow_mut() = updated_at);
+}
+
+pub fn mock_set_now_millis(millis: Moment) {
+	NOW_MILLIS.with(|v| *v.borrow_mut() = millis);
+}
+
+pub struct MockSourceUpdatedAt;
+impl GetByKey<CurrencyId, Option<Moment>> for MockSourceUpdatedAt {
+	fn get(_: &CurrencyId) -> Option<Moment> {
+		SOURCE_UPDATED_AT.with(|v| *v.borrow())
+	}
+}
+
+pub struct MockCurrencyDecimals;
+impl GetByKey<CurrencyId, u8> for MockCurrencyDecimals {
+	fn get(currency_id: &CurrencyId) -> u8 {
+		match currency_id {
+			&RENBTC => 8,
+			_ => 12,
+		}
+	}
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> Duration {
+		Duration::from_millis(NOW_MILLIS.with(|v| *v.borrow()))
+	}
+}
+
+pub fn mock_set_btc_price(price: Option<Price>) {
+	BTC_PRICE.with(|v| *v.borrow_mut() = price);
+}
+
+pub fn mock_set_exchange_rate_updated_at(updated_at: Option<BlockNumber>) {
+	EXCHANGE_RATE_UPDATED_AT.with(|v| *v.borrow_mut() = updated_at);
+}
+
+pub struct MockLiquidStakingExchangeRateUpdatedAt;
+impl Get<Option<BlockNumber>> for MockLiquidStakingExchangeRateUpdatedAt {
+	fn get() -> Option<BlockNumber> {
+		EXCHANGE_RATE_UPDATED_AT.with(|v| *v.borrow())
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -89,6 +186,8 @@ parameter_types! {
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
 	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
 	pub StableCurrencyFixedPrice: Price = Price::one();
+	pub const MaxLiquidStakingExchangeRateAge: BlockNumber = 10;
+	pub const MaxTwapWindow: u32 = 3;
 }
 
 impl Config for Runtime {
@@ -100,6 +199,14 @@ impl Config for Runtime {
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
 	type LockOrigin = EnsureSignedBy<One, AccountId>;
 	type LiquidStakingExchangeRateProvider = MockLiquidStakingExchangeProvider;
+	type LiquidStakingExchangeRateUpdatedAt = MockLiquidStakingExchangeRateUpdatedAt;
+	type MaxLiquidStakingExchangeRateAge = MaxLiquidStakingExchangeRateAge;
+	type MaxTwapWindow = MaxTwapWindow;
+	type SourceUpdatedAt = MockSourceUpdatedAt;
+	type FallbackSource = MockFallbackDataProvider;
+	type UnixTime = MockUnixTime;
+	type Feeds = MockPriceFeeds;
+	type CurrencyDecimals = MockCurrencyDecimals;
 	type WeightInfo = ();
 }
 
@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
 use mock::{Event, *};
 use sp_runtime::{traits::BadOrigin, FixedPointNumber};
 
@@ -30,6 +30,41 @@ fn get_price_of_liquid_currency_id() {
 	});
 }
 
+#[test]
+fn get_price_of_liquid_currency_id_with_stale_exchange_rate() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(10);
+		assert!(!PricesModule::is_liquid_staking_exchange_rate_stale());
+		assert_eq!(PricesModule::get_price(LDOT), Some(Price::saturating_from_integer(50)));
+
+		System::set_block_number(11);
+		assert!(PricesModule::is_liquid_staking_exchange_rate_stale());
+		assert_eq!(PricesModule::get_price(LDOT), None);
+		assert_eq!(PricesModule::get_relative_price(LDOT, DOT), None);
+
+		mock_set_exchange_rate_updated_at(Some(5));
+		assert_eq!(PricesModule::get_price(LDOT), Some(Price::saturating_from_integer(50)));
+
+		mock_set_exchange_rate_updated_at(None);
+		assert_eq!(PricesModule::get_price(LDOT), None);
+	});
+}
+
+#[test]
+fn lock_price_of_liquid_currency_id_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(PricesModule::lock_price(Origin::signed(1), LDOT));
+		assert_eq!(
+			PricesModule::locked_price(LDOT),
+			Some(Price::saturating_from_integer(50))
+		);
+
+		// the locked price is still available when the exchange rate is stale
+		System::set_block_number(11);
+		assert_eq!(PricesModule::get_price(LDOT), Some(Price::saturating_from_integer(50)));
+	});
+}
+
 #[test]
 fn get_relative_price_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -53,6 +88,30 @@ fn get_relative_price_work() {
 	});
 }
 
+#[test]
+fn get_relative_price_normalize_decimals() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the price between the minimal units of RENBTC (8 decimals) and AUSD (12
+		// decimals)
+		assert_eq!(
+			PricesModule::get_relative_price(RENBTC, AUSD),
+			Some(Price::saturating_from_integer(50_000_000))
+		);
+		assert_eq!(
+			PricesModule::get_relative_price(AUSD, RENBTC),
+			Some(Price::saturating_from_rational(1, 50_000_000))
+		);
+		assert_eq!(
+			PricesModule::get_relative_price(RENBTC, BTC),
+			Some(Price::saturating_from_integer(10_000))
+		);
+		assert_eq!(
+			PricesModule::get_relative_twap_price(RENBTC, AUSD),
+			Some(Price::saturating_from_integer(50_000_000))
+		);
+	});
+}
+
 #[test]
 fn lock_price_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -92,3 +151,542 @@ fn unlock_price_call_work() {
 		assert_eq!(PricesModule::locked_price(BTC), None);
 	});
 }
+
+#[test]
+fn set_twap_window_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(PricesModule::set_twap_window(Origin::signed(5), BTC, 2), BadOrigin);
+		assert_noop!(
+			PricesModule::set_twap_window(Origin::signed(1), BTC, 4),
+			Error::<Runtime>::InvalidTwapWindow
+		);
+
+		assert_ok!(PricesModule::set_twap_window(Origin::signed(1), BTC, 3));
+		let update_event = Event::prices(crate::Event::TwapWindowUpdated(BTC, 3));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(PricesModule::twap_window(BTC), 3);
+
+		PricesModule::on_initialize(2);
+		mock_set_btc_price(Some(Price::saturating_from_integer(6000)));
+		PricesModule::on_initialize(3);
+		mock_set_btc_price(Some(Price::saturating_from_integer(7000)));
+		PricesModule::on_initialize(4);
+		assert_eq!(
+			PricesModule::price_samples(BTC),
+			vec![
+				Price::saturating_from_integer(5000),
+				Price::saturating_from_integer(6000),
+				Price::saturating_from_integer(7000)
+			]
+		);
+
+		// the samples out of the new window are dropped
+		assert_ok!(PricesModule::set_twap_window(Origin::signed(1), BTC, 1));
+		assert_eq!(
+			PricesModule::price_samples(BTC),
+			vec![Price::saturating_from_integer(7000)]
+		);
+
+		assert_ok!(PricesModule::set_twap_window(Origin::signed(1), BTC, 0));
+		assert_eq!(PricesModule::twap_window(BTC), 0);
+		assert_eq!(PricesModule::price_samples(BTC), vec![]);
+	});
+}
+
+#[test]
+fn get_twap_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the TWAP is the spot price without window
+		assert_eq!(
+			PricesModule::get_twap_price(BTC),
+			Some(Price::saturating_from_integer(5000))
+		);
+		assert_eq!(PricesModule::get_twap_price(AUSD), Some(Price::one()));
+
+		assert_ok!(PricesModule::set_twap_window(Origin::signed(1), BTC, 2));
+		assert_ok!(PricesModule::set_twap_window(Origin::signed(1), LDOT, 2));
+		// the spot price before the first sample
+		assert_eq!(
+			PricesModule::get_twap_price(BTC),
+			Some(Price::saturating_from_integer(5000))
+		);
+
+		PricesModule::on_initialize(1);
+		mock_set_btc_price(Some(Price::saturating_from_integer(8000)));
+		assert_eq!(
+			PricesModule::get_twap_price(BTC),
+			Some(Price::saturating_from_integer(5000))
+		);
+		assert_eq!(
+			PricesModule::get_twap_price(LDOT),
+			Some(Price::saturating_from_integer(50))
+		);
+
+		PricesModule::on_initialize(2);
+		assert_eq!(
+			PricesModule::get_twap_price(BTC),
+			Some(Price::saturating_from_integer(6500))
+		);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(8000)));
+		assert_eq!(
+			PricesModule::get_relative_twap_price(BTC, AUSD),
+			Some(Price::saturating_from_integer(6500))
+		);
+
+		// the window slides
+		PricesModule::on_initialize(3);
+		assert_eq!(
+			PricesModule::get_twap_price(BTC),
+			Some(Price::saturating_from_integer(8000))
+		);
+
+		// no sample is taken when the source price is unavailable
+		mock_set_btc_price(None);
+		PricesModule::on_initialize(4);
+		assert_eq!(PricesModule::price_samples(BTC).len(), 2);
+
+		// the locked price takes precedence
+		LockedPrice::<Runtime>::insert(BTC, Price::saturating_from_integer(9000));
+		assert_eq!(
+			PricesModule::get_twap_price(BTC),
+			Some(Price::saturating_from_integer(9000))
+		);
+	});
+}
+
+#[test]
+fn set_max_price_age_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PricesModule::set_max_price_age(Origin::signed(5), BTC, Some(1000)),
+			BadOrigin
+		);
+		assert_ok!(PricesModule::set_max_price_age(Origin::signed(1), BTC, Some(1000)));
+		let update_event = Event::prices(crate::Event::MaxPriceAgeUpdated(BTC, Some(1000)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(PricesModule::max_price_age(BTC), Some(1000));
+
+		assert_ok!(PricesModule::set_max_price_age(Origin::signed(1), BTC, None));
+		assert_eq!(PricesModule::max_price_age(BTC), None);
+	});
+}
+
+#[test]
+fn get_price_with_stale_source_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_set_now_millis(2000);
+		// the price never gets stale without max age
+		assert!(!PricesModule::is_source_price_stale(BTC));
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+
+		assert_ok!(PricesModule::set_max_price_age(Origin::signed(1), BTC, Some(2000)));
+		assert_ok!(PricesModule::set_max_price_age(Origin::signed(1), DOT, Some(2000)));
+		assert!(!PricesModule::is_source_price_stale(BTC));
+
+		// fall back to the secondary source
+		mock_set_now_millis(2001);
+		assert!(PricesModule::is_source_price_stale(BTC));
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4000)));
+		assert_eq!(
+			PricesModule::get_relative_price(BTC, AUSD),
+			Some(Price::saturating_from_integer(4000))
+		);
+
+		// no price if the secondary source has none either
+		assert_eq!(PricesModule::get_price(DOT), None);
+		assert_eq!(PricesModule::get_price(LDOT), None);
+		assert_eq!(PricesModule::get_relative_price(DOT, AUSD), None);
+
+		// the locked price is not affected
+		LockedPrice::<Runtime>::insert(DOT, Price::saturating_from_integer(80));
+		assert_eq!(PricesModule::get_price(DOT), Some(Price::saturating_from_integer(80)));
+
+		mock_set_source_updated_at(Some(1000));
+		assert!(!PricesModule::is_source_price_stale(BTC));
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+
+		// the price without update time is stale
+		mock_set_source_updated_at(None);
+		assert!(PricesModule::is_source_price_stale(BTC));
+	});
+}
+
+fn feed_aggregation(min_feeds: u32) -> FeedAggregationParams {
+	FeedAggregationParams {
+		min_feeds,
+		max_deviation: Ratio::saturating_from_rational(1, 10),
+	}
+}
+
+#[test]
+fn set_feed_aggregation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PricesModule::set_feed_aggregation(Origin::signed(5), BTC, Some(feed_aggregation(2))),
+			BadOrigin
+		);
+		assert_noop!(
+			PricesModule::set_feed_aggregation(Origin::signed(1), BTC, Some(feed_aggregation(0))),
+			Error::<Runtime>::InvalidFeedAggregationParams
+		);
+
+		assert_ok!(PricesModule::set_feed_aggregation(
+			Origin::signed(1),
+			BTC,
+			Some(feed_aggregation(2))
+		));
+		let update_event = Event::prices(crate::Event::FeedAggregationUpdated(BTC, Some(feed_aggregation(2))));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(PricesModule::feed_aggregation(BTC), Some(feed_aggregation(2)));
+
+		assert_ok!(PricesModule::set_feed_aggregation(Origin::signed(1), BTC, None));
+		assert_eq!(PricesModule::feed_aggregation(BTC), None);
+	});
+}
+
+#[test]
+fn get_aggregated_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_set_feed_prices(vec![
+			(Price::saturating_from_integer(5100), 0),
+			(Price::saturating_from_integer(4900), 0),
+			(Price::saturating_from_integer(5000), 0),
+		]);
+		assert_ok!(PricesModule::set_feed_aggregation(
+			Origin::signed(1),
+			BTC,
+			Some(feed_aggregation(2))
+		));
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+
+		// the median of even number of prices
+		mock_set_feed_prices(vec![
+			(Price::saturating_from_integer(5100), 0),
+			(Price::saturating_from_integer(4900), 0),
+		]);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+
+		// the outlier is rejected
+		mock_set_feed_prices(vec![
+			(Price::saturating_from_integer(5100), 0),
+			(Price::saturating_from_integer(9000), 0),
+			(Price::saturating_from_integer(5000), 0),
+		]);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5050)));
+
+		// no price if the prices are not enough after rejecting the outliers
+		mock_set_feed_prices(vec![
+			(Price::saturating_from_integer(5000), 0),
+			(Price::saturating_from_integer(9000), 0),
+		]);
+		assert_eq!(PricesModule::get_price(BTC), None);
+
+		// fall back to the secondary source if the fresh prices are not enough
+		assert_ok!(PricesModule::set_max_price_age(Origin::signed(1), BTC, Some(1000)));
+		mock_set_now_millis(2000);
+		mock_set_feed_prices(vec![
+			(Price::saturating_from_integer(5000), 1000),
+			(Price::saturating_from_integer(5100), 900),
+		]);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4000)));
+		mock_set_feed_prices(vec![]);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4000)));
+	});
+}
+
+#[test]
+fn lock_price_without_source_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_set_btc_price(None);
+		assert_noop!(
+			PricesModule::lock_price(Origin::signed(1), BTC),
+			Error::<Runtime>::NoPriceToLock
+		);
+		assert!(!PricesModule::is_price_locked(BTC));
+
+		// lock the last sampled price
+		PriceSamples::<Runtime>::insert(
+			BTC,
+			vec![
+				Price::saturating_from_integer(4900),
+				Price::saturating_from_integer(5100),
+			],
+		);
+		assert_ok!(PricesModule::lock_price(Origin::signed(1), BTC));
+		assert_eq!(
+			PricesModule::locked_price(BTC),
+			Some(Price::saturating_from_integer(5100))
+		);
+		assert!(PricesModule::is_price_locked(BTC));
+
+		assert_ok!(PricesModule::unlock_price(Origin::signed(1), BTC));
+		assert!(!PricesModule::is_price_locked(BTC));
+	});
+}
+
+#[test]
+fn set_price_source_priority_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PricesModule::set_price_source_priority(Origin::signed(5), BTC, vec![PriceSource::Fallback]),
+			BadOrigin
+		);
+		assert_noop!(
+			PricesModule::set_price_source_priority(
+				Origin::signed(1),
+				BTC,
+				vec![PriceSource::Fallback, PriceSource::Oracle, PriceSource::Fallback]
+			),
+			Error::<Runtime>::InvalidPriceSourcePriority
+		);
+
+		assert_ok!(PricesModule::set_price_source_priority(
+			Origin::signed(1),
+			BTC,
+			vec![PriceSource::Fallback, PriceSource::Oracle]
+		));
+		let update_event = Event::prices(crate::Event::PriceSourcePriorityUpdated(
+			BTC,
+			vec![PriceSource::Fallback, PriceSource::Oracle],
+		));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			PricesModule::price_source_priority(BTC),
+			vec![PriceSource::Fallback, PriceSource::Oracle]
+		);
+
+		assert_ok!(PricesModule::set_price_source_priority(Origin::signed(1), BTC, vec![]));
+		assert_eq!(PricesModule::price_source_priority(BTC), vec![]);
+	});
+}
+
+#[test]
+fn get_price_by_source_priority() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(PricesModule::set_price_source_priority(
+			Origin::signed(1),
+			BTC,
+			vec![PriceSource::Fallback, PriceSource::Oracle]
+		));
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4000)));
+
+		assert_ok!(PricesModule::set_price_source_priority(
+			Origin::signed(1),
+			BTC,
+			vec![PriceSource::Oracle, PriceSource::Fallback]
+		));
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+
+		// the next source is used if the oracle has no price
+		mock_set_btc_price(None);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4000)));
+
+		// or the oracle price is stale
+		mock_set_btc_price(Some(Price::saturating_from_integer(5000)));
+		assert_ok!(PricesModule::set_max_price_age(Origin::signed(1), BTC, Some(1000)));
+		mock_set_source_updated_at(Some(1000));
+		mock_set_now_millis(2001);
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4000)));
+
+		assert_ok!(PricesModule::set_price_source_priority(
+			Origin::signed(1),
+			BTC,
+			vec![PriceSource::Oracle]
+		));
+		assert_eq!(PricesModule::get_price(BTC), None);
+
+		// the default priority only falls back if the oracle price is stale
+		assert_ok!(PricesModule::set_price_source_priority(Origin::signed(1), BTC, vec![]));
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4000)));
+		mock_set_now_millis(2000);
+		mock_set_btc_price(None);
+		assert_eq!(PricesModule::get_price(BTC), None);
+	});
+}
+
+#[test]
+fn set_price_source_weights_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let weights = vec![
+			(PriceSource::Oracle, Ratio::saturating_from_rational(7, 10)),
+			(PriceSource::Fallback, Ratio::saturating_from_rational(3, 10)),
+		];
+		assert_noop!(
+			PricesModule::set_price_source_weights(Origin::signed(5), BTC, weights.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			PricesModule::set_price_source_weights(
+				Origin::signed(1),
+				BTC,
+				vec![
+					(PriceSource::Oracle, Ratio::saturating_from_rational(7, 10)),
+					(PriceSource::Fallback, Ratio::saturating_from_rational(2, 10)),
+				]
+			),
+			Error::<Runtime>::InvalidPriceSourceWeights
+		);
+		assert_noop!(
+			PricesModule::set_price_source_weights(
+				Origin::signed(1),
+				BTC,
+				vec![
+					(PriceSource::Oracle, Ratio::one()),
+					(PriceSource::Fallback, Ratio::zero()),
+				]
+			),
+			Error::<Runtime>::InvalidPriceSourceWeights
+		);
+		assert_noop!(
+			PricesModule::set_price_source_weights(
+				Origin::signed(1),
+				BTC,
+				vec![
+					(PriceSource::Oracle, Ratio::saturating_from_rational(1, 2)),
+					(PriceSource::Oracle, Ratio::saturating_from_rational(1, 2)),
+				]
+			),
+			Error::<Runtime>::InvalidPriceSourceWeights
+		);
+
+		assert_ok!(PricesModule::set_price_source_weights(
+			Origin::signed(1),
+			BTC,
+			weights.clone()
+		));
+		let update_event = Event::prices(crate::Event::PriceSourceWeightsUpdated(BTC, weights.clone()));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(PricesModule::price_source_weights(BTC), weights);
+
+		assert_ok!(PricesModule::set_price_source_weights(Origin::signed(1), BTC, vec![]));
+		let update_event = Event::prices(crate::Event::PriceSourceWeightsUpdated(BTC, vec![]));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(PricesModule::price_source_weights(BTC), vec![]);
+	});
+}
+
+#[test]
+fn get_price_by_source_weights() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(PricesModule::set_price_source_priority(
+			Origin::signed(1),
+			BTC,
+			vec![PriceSource::Fallback]
+		));
+		assert_ok!(PricesModule::set_price_source_weights(
+			Origin::signed(1),
+			BTC,
+			vec![
+				(PriceSource::Oracle, Ratio::saturating_from_rational(7, 10)),
+				(PriceSource::Fallback, Ratio::saturating_from_rational(3, 10)),
+			]
+		));
+		// the weights precede the priority
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(4700)));
+
+		// unavailable if any of the sources has no price
+		mock_set_btc_price(None);
+		assert_eq!(PricesModule::get_price(BTC), None);
+
+		assert_ok!(PricesModule::set_price_source_weights(
+			Origin::signed(1),
+			DOT,
+			vec![
+				(PriceSource::Oracle, Ratio::saturating_from_rational(1, 2)),
+				(PriceSource::Fallback, Ratio::saturating_from_rational(1, 2)),
+			]
+		));
+		assert_eq!(PricesModule::get_price(DOT), None);
+		assert_ok!(PricesModule::set_price_source_weights(
+			Origin::signed(1),
+			DOT,
+			vec![(PriceSource::Oracle, Ratio::one())]
+		));
+		assert_eq!(PricesModule::get_price(DOT), Some(Price::saturating_from_integer(100)));
+	});
+}
+
+#[test]
+fn get_feeds_updated_at_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(PricesModule::get_feeds_updated_at(BTC), vec![]);
+
+		mock_set_feed_prices(vec![
+			(Price::saturating_from_integer(5000), 1000),
+			(Price::saturating_from_integer(5100), 900),
+		]);
+		assert_eq!(PricesModule::get_feeds_updated_at(BTC), vec![1000, 900]);
+	});
+}
+
+#[test]
+fn get_price_confidence_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_set_feed_prices_with_confidence(vec![
+			(
+				Price::saturating_from_integer(5000),
+				Some(Price::saturating_from_integer(50)),
+				0,
+			),
+			(
+				Price::saturating_from_integer(5000),
+				Some(Price::saturating_from_integer(100)),
+				0,
+			),
+			(
+				Price::saturating_from_integer(9000),
+				Some(Price::saturating_from_integer(900)),
+				0,
+			),
+		]);
+		// the confidence interval is only from the aggregated feeds
+		assert_eq!(PricesModule::get_price_confidence(BTC), None);
+
+		assert_ok!(PricesModule::set_feed_aggregation(
+			Origin::signed(1),
+			BTC,
+			Some(feed_aggregation(2))
+		));
+		// the widest confidence interval of the accepted feeds
+		assert_eq!(
+			PricesModule::get_price_confidence(BTC),
+			Some(Ratio::saturating_from_rational(2, 100))
+		);
+
+		// none of the feeds provides the confidence interval
+		mock_set_feed_prices(vec![
+			(Price::saturating_from_integer(5000), 0),
+			(Price::saturating_from_integer(5000), 0),
+		]);
+		assert_eq!(PricesModule::get_price_confidence(BTC), None);
+
+		// the liquid currency has the confidence interval of staking currency
+		mock_set_feed_prices_with_confidence(vec![
+			(
+				Price::saturating_from_integer(100),
+				Some(Price::saturating_from_integer(5)),
+				0,
+			),
+			(Price::saturating_from_integer(100), None, 0),
+		]);
+		assert_ok!(PricesModule::set_feed_aggregation(
+			Origin::signed(1),
+			DOT,
+			Some(feed_aggregation(2))
+		));
+		assert_eq!(
+			PricesModule::get_price_confidence(LDOT),
+			Some(Ratio::saturating_from_rational(5, 100))
+		);
+
+		// no confidence interval for the fixed and locked prices
+		assert_eq!(PricesModule::get_price_confidence(AUSD), None);
+		assert_ok!(PricesModule::lock_price(Origin::signed(1), DOT));
+		assert_eq!(PricesModule::get_price_confidence(DOT), None);
+	});
+}
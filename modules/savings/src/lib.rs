@@ -0,0 +1,265 @@
+//! # Savings Module
+//!
+//! ## Overview
+//!
+//! Stable currency holders can deposit into the savings pot of this module
+//! and earn the savings rate set by governance. Deposits are recorded as
+//! shares, and the value of a share is tracked by an exchange rate index
+//! which grows continuously by the savings rate per second. The index is
+//! checkpointed lazily whenever the pot is touched, and the accrued interest
+//! is paid to the pot from the surplus pool of CDP treasury, so the accrual
+//! is limited to the available surplus.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, traits::UnixTime, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{AccountIdConversion, One, UniqueSaturatedInto, Zero},
+	DispatchResult, FixedPointNumber, ModuleId,
+};
+use support::{CDPTreasury, ExchangeRate, Rate};
+
+mod default_weight;
+mod mock;
+mod tests;
+
+pub use module::*;
+
+pub trait WeightInfo {
+	fn deposit() -> Weight;
+	fn withdraw() -> Weight;
+	fn set_savings_rate() -> Weight;
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency for transfer stable currency in and out of the pot
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		#[pallet::constant]
+		/// Stablecoin currency id
+		type GetStableCurrencyId: Get<CurrencyId>;
+
+		/// CDP treasury to pay the accrued interest from its surplus
+		type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
+		/// The origin which may update the savings rate. Root can always do
+		/// this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Time used for accruing the savings rate per second
+		type UnixTime: UnixTime;
+
+		#[pallet::constant]
+		/// The savings module id, keep the deposited stable currency and the
+		/// accrued interest.
+		type ModuleId: Get<ModuleId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The amount is too small to get any share
+		InvalidAmount,
+		/// The shares of the account are not enough
+		SharesNotEnough,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Deposit stable currency into the savings pot. \[who,
+		/// deposit_amount, received_shares\]
+		Deposited(T::AccountId, Balance, Balance),
+		/// Withdraw stable currency from the savings pot. \[who,
+		/// redeemed_shares, withdrawn_amount\]
+		Withdrawn(T::AccountId, Balance, Balance),
+		/// The savings rate updated. \[new_savings_rate\]
+		SavingsRateUpdated(Rate),
+	}
+
+	/// The savings rate per second.
+	#[pallet::storage]
+	#[pallet::getter(fn savings_rate)]
+	pub type SavingsRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+
+	/// The exchange rate from shares to stable currency at the last
+	/// accumulation, `None` means one.
+	#[pallet::storage]
+	#[pallet::getter(fn savings_exchange_rate)]
+	pub type SavingsExchangeRate<T: Config> = StorageValue<_, ExchangeRate, OptionQuery>;
+
+	/// The unix time in seconds of the last accumulation.
+	#[pallet::storage]
+	#[pallet::getter(fn last_accumulation_secs)]
+	pub type LastAccumulationSecs<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The savings shares of accounts.
+	#[pallet::storage]
+	#[pallet::getter(fn shares)]
+	pub type Shares<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The total savings shares.
+	#[pallet::storage]
+	#[pallet::getter(fn total_shares)]
+	pub type TotalShares<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Deposit stable currency into the savings pot.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `amount`: stable currency amount to deposit.
+		#[pallet::weight(<T as Config>::WeightInfo::deposit())]
+		#[transactional]
+		pub fn deposit(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::accumulate_interest();
+
+			let shares = Self::get_exchange_rate()
+				.reciprocal()
+				.unwrap_or_default()
+				.saturating_mul_int(amount);
+			ensure!(!shares.is_zero(), Error::<T>::InvalidAmount);
+
+			T::Currency::transfer(T::GetStableCurrencyId::get(), &who, &Self::account_id(), amount)?;
+			Shares::<T>::mutate(&who, |balance| *balance = balance.saturating_add(shares));
+			TotalShares::<T>::mutate(|total| *total = total.saturating_add(shares));
+
+			Self::deposit_event(Event::Deposited(who, amount, shares));
+			Ok(().into())
+		}
+
+		/// Redeem shares for stable currency from the savings pot.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `shares`: shares amount to redeem.
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw())]
+		#[transactional]
+		pub fn withdraw(origin: OriginFor<T>, #[pallet::compact] shares: Balance) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::accumulate_interest();
+
+			Shares::<T>::try_mutate(&who, |balance| -> DispatchResult {
+				*balance = balance.checked_sub(shares).ok_or(Error::<T>::SharesNotEnough)?;
+				Ok(())
+			})?;
+			TotalShares::<T>::mutate(|total| *total = total.saturating_sub(shares));
+
+			let amount = Self::get_exchange_rate().saturating_mul_int(shares);
+			T::Currency::transfer(T::GetStableCurrencyId::get(), &Self::account_id(), &who, amount)?;
+
+			Self::deposit_event(Event::Withdrawn(who, shares, amount));
+			Ok(().into())
+		}
+
+		/// Update the savings rate per second
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `savings_rate`: the new savings rate.
+		#[pallet::weight((<T as Config>::WeightInfo::set_savings_rate(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_savings_rate(origin: OriginFor<T>, savings_rate: Rate) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			// accrue the interest at the old rate before the update
+			Self::accumulate_interest();
+			SavingsRate::<T>::put(savings_rate);
+			Self::deposit_event(Event::SavingsRateUpdated(savings_rate));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	/// Get the exchange rate from shares to stable currency, accrued up to
+	/// now.
+	pub fn get_exchange_rate() -> ExchangeRate {
+		let exchange_rate = Self::savings_exchange_rate().unwrap_or_else(ExchangeRate::one);
+		let last_accumulation_secs = Self::last_accumulation_secs();
+		let now_secs = T::UnixTime::now().as_secs();
+		let savings_rate = Self::savings_rate();
+		if last_accumulation_secs.is_zero()
+			|| now_secs <= last_accumulation_secs
+			|| savings_rate.is_zero()
+			|| Self::total_shares().is_zero()
+		{
+			return exchange_rate;
+		}
+
+		let elapsed_secs: usize = (now_secs - last_accumulation_secs).unique_saturated_into();
+		let accumulated_rate = Rate::one().saturating_add(savings_rate).saturating_pow(elapsed_secs);
+		let accrued_exchange_rate = exchange_rate.saturating_mul(accumulated_rate);
+
+		// the interest is paid from the surplus pool, accrue no more than it
+		let total_shares = Self::total_shares();
+		let surplus = T::CDPTreasury::get_surplus_pool();
+		if accrued_exchange_rate
+			.saturating_sub(exchange_rate)
+			.saturating_mul_int(total_shares)
+			> surplus
+		{
+			exchange_rate.saturating_add(ExchangeRate::checked_from_rational(surplus, total_shares).unwrap_or_default())
+		} else {
+			accrued_exchange_rate
+		}
+	}
+
+	/// Get the stable currency value of the savings of `who`, accrued up to
+	/// now.
+	pub fn get_savings_balance(who: &T::AccountId) -> Balance {
+		Self::get_exchange_rate().saturating_mul_int(Self::shares(who))
+	}
+
+	/// Checkpoint the exchange rate, pay the interest accrued since the last
+	/// checkpoint from the surplus pool to the savings pot.
+	pub fn accumulate_interest() {
+		let now_secs = T::UnixTime::now().as_secs();
+		if now_secs <= Self::last_accumulation_secs() {
+			return;
+		}
+
+		let exchange_rate = Self::savings_exchange_rate().unwrap_or_else(ExchangeRate::one);
+		let new_exchange_rate = Self::get_exchange_rate();
+		if new_exchange_rate > exchange_rate {
+			let interest = new_exchange_rate
+				.saturating_sub(exchange_rate)
+				.saturating_mul_int(Self::total_shares())
+				.min(T::CDPTreasury::get_surplus_pool());
+
+			// pay interest to the pot, keep the checkpoint to retry at the
+			// next touch if it fails
+			if T::CDPTreasury::withdraw_surplus(&Self::account_id(), interest).is_err() {
+				return;
+			}
+			SavingsExchangeRate::<T>::put(new_exchange_rate);
+		}
+		LastAccumulationSecs::<T>::put(now_secs);
+	}
+}
@@ -0,0 +1,211 @@
+//! Mocks for the savings module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::sp_api_hidden_includes_construct_runtime::hidden_include::inherent::BlockT;
+use frame_support::{
+	construct_runtime, dispatch::DispatchResult, ord_parameter_types, parameter_types, traits::UnixTime,
+};
+use frame_system::EnsureSignedBy;
+use orml_traits::{parameter_type_with_key, MultiCurrency};
+use primitives::{Amount, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::{cell::RefCell, time::Duration};
+use support::Ratio;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CDP_TREASURY: AccountId = 10;
+pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+
+mod savings {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+pub struct MockCDPTreasury;
+impl CDPTreasury<AccountId> for MockCDPTreasury {
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+
+	fn get_surplus_pool() -> Balance {
+		TokensModule::free_balance(AUSD, &CDP_TREASURY)
+	}
+
+	fn get_debit_pool() -> Balance {
+		unimplemented!()
+	}
+
+	fn get_total_collaterals(_: CurrencyId) -> Balance {
+		unimplemented!()
+	}
+
+	fn get_debit_proportion(_: Balance) -> Ratio {
+		unimplemented!()
+	}
+
+	fn on_system_debit(_: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn on_system_surplus(_: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn issue_debit(_: &AccountId, _: Balance, _: bool) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn burn_debit(_: &AccountId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn deposit_surplus(_: &AccountId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn withdraw_surplus(to: &AccountId, surplus: Balance) -> DispatchResult {
+		TokensModule::transfer(AUSD, &CDP_TREASURY, to, surplus)
+	}
+
+	fn deposit_collateral(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn withdraw_collateral(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+}
+
+thread_local! {
+	static NOW_SECS: RefCell<u64> = RefCell::new(0);
+}
+
+pub fn mock_set_now_secs(secs: u64) {
+	NOW_SECS.with(|v| *v.borrow_mut() = secs)
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> Duration {
+		Duration::from_secs(NOW_SECS.with(|v| *v.borrow()))
+	}
+}
+
+ord_parameter_types! {
+	pub const Four: AccountId = 4;
+}
+
+parameter_types! {
+	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub const SavingsModuleId: ModuleId = ModuleId(*b"aca/savp");
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = TokensModule;
+	type GetStableCurrencyId = GetStableCurrencyId;
+	type CDPTreasury = MockCDPTreasury;
+	type UpdateOrigin = EnsureSignedBy<Four, AccountId>;
+	type UnixTime = MockUnixTime;
+	type ModuleId = SavingsModuleId;
+	type WeightInfo = ();
+}
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Storage, Config, Event<T>},
+		SavingsModule: savings::{Module, Storage, Call, Event<T>},
+		TokensModule: orml_tokens::{Module, Storage, Event<T>, Config<T>},
+	}
+);
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![(ALICE, AUSD, 1000), (BOB, AUSD, 1000), (CDP_TREASURY, AUSD, 1000)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn surplus_pool(mut self, surplus: Balance) -> Self {
+		self.endowed_accounts.retain(|(who, _, _)| *who != CDP_TREASURY);
+		self.endowed_accounts.push((CDP_TREASURY, AUSD, surplus));
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
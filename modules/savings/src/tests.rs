@@ -0,0 +1,165 @@
+//! Unit tests for the savings module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn set_savings_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			SavingsModule::set_savings_rate(Origin::signed(ALICE), Rate::saturating_from_rational(1, 4)),
+			BadOrigin
+		);
+		assert_ok!(SavingsModule::set_savings_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 4)
+		));
+		let update_event = Event::savings(crate::Event::SavingsRateUpdated(Rate::saturating_from_rational(1, 4)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(SavingsModule::savings_rate(), Rate::saturating_from_rational(1, 4));
+	});
+}
+
+#[test]
+fn deposit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_set_now_secs(100);
+		assert_noop!(
+			SavingsModule::deposit(Origin::signed(ALICE), 0),
+			Error::<Runtime>::InvalidAmount
+		);
+
+		assert_ok!(SavingsModule::deposit(Origin::signed(ALICE), 100));
+		let deposit_event = Event::savings(crate::Event::Deposited(ALICE, 100, 100));
+		assert!(System::events().iter().any(|record| record.event == deposit_event));
+		assert_eq!(SavingsModule::shares(ALICE), 100);
+		assert_eq!(SavingsModule::total_shares(), 100);
+		assert_eq!(SavingsModule::last_accumulation_secs(), 100);
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 900);
+		assert_eq!(TokensModule::free_balance(AUSD, &SavingsModule::account_id()), 100);
+	});
+}
+
+#[test]
+fn accumulate_interest_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_set_now_secs(100);
+		assert_ok!(SavingsModule::set_savings_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 4)
+		));
+		assert_ok!(SavingsModule::deposit(Origin::signed(ALICE), 100));
+		assert_eq!(SavingsModule::get_exchange_rate(), ExchangeRate::one());
+
+		mock_set_now_secs(101);
+		assert_eq!(
+			SavingsModule::get_exchange_rate(),
+			ExchangeRate::saturating_from_rational(5, 4)
+		);
+		assert_eq!(SavingsModule::get_savings_balance(&ALICE), 125);
+		assert_eq!(SavingsModule::savings_exchange_rate(), None);
+
+		SavingsModule::accumulate_interest();
+		assert_eq!(
+			SavingsModule::savings_exchange_rate(),
+			Some(ExchangeRate::saturating_from_rational(5, 4))
+		);
+		assert_eq!(SavingsModule::last_accumulation_secs(), 101);
+		assert_eq!(TokensModule::free_balance(AUSD, &SavingsModule::account_id()), 125);
+
+		// deposit at the accrued exchange rate
+		assert_ok!(SavingsModule::deposit(Origin::signed(BOB), 125));
+		assert_eq!(SavingsModule::shares(BOB), 100);
+		assert_eq!(SavingsModule::total_shares(), 200);
+
+		// accrue the interest at the old rate before the update
+		mock_set_now_secs(102);
+		assert_ok!(SavingsModule::set_savings_rate(Origin::signed(4), Rate::zero()));
+		assert_eq!(
+			SavingsModule::savings_exchange_rate(),
+			Some(ExchangeRate::saturating_from_rational(25, 16))
+		);
+		assert_eq!(TokensModule::free_balance(AUSD, &SavingsModule::account_id()), 312);
+		assert_eq!(TokensModule::free_balance(AUSD, &CDP_TREASURY), 913);
+
+		mock_set_now_secs(200);
+		assert_eq!(
+			SavingsModule::get_exchange_rate(),
+			ExchangeRate::saturating_from_rational(25, 16)
+		);
+	});
+}
+
+#[test]
+fn accumulate_interest_limited_by_surplus_pool() {
+	ExtBuilder::default().surplus_pool(10).build().execute_with(|| {
+		mock_set_now_secs(100);
+		assert_ok!(SavingsModule::set_savings_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 4)
+		));
+		assert_ok!(SavingsModule::deposit(Origin::signed(ALICE), 100));
+
+		mock_set_now_secs(101);
+		assert_eq!(
+			SavingsModule::get_exchange_rate(),
+			ExchangeRate::saturating_from_rational(11, 10)
+		);
+		assert_eq!(SavingsModule::get_savings_balance(&ALICE), 110);
+
+		SavingsModule::accumulate_interest();
+		assert_eq!(
+			SavingsModule::savings_exchange_rate(),
+			Some(ExchangeRate::saturating_from_rational(11, 10))
+		);
+		assert_eq!(TokensModule::free_balance(AUSD, &SavingsModule::account_id()), 110);
+		assert_eq!(TokensModule::free_balance(AUSD, &CDP_TREASURY), 0);
+
+		// no surplus left to pay interest
+		mock_set_now_secs(102);
+		assert_eq!(
+			SavingsModule::get_exchange_rate(),
+			ExchangeRate::saturating_from_rational(11, 10)
+		);
+		SavingsModule::accumulate_interest();
+		assert_eq!(SavingsModule::last_accumulation_secs(), 102);
+		assert_eq!(TokensModule::free_balance(AUSD, &SavingsModule::account_id()), 110);
+	});
+}
+
+#[test]
+fn withdraw_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_set_now_secs(100);
+		assert_ok!(SavingsModule::set_savings_rate(
+			Origin::signed(4),
+			Rate::saturating_from_rational(1, 4)
+		));
+		assert_ok!(SavingsModule::deposit(Origin::signed(ALICE), 100));
+
+		mock_set_now_secs(101);
+		assert_noop!(
+			SavingsModule::withdraw(Origin::signed(ALICE), 101),
+			Error::<Runtime>::SharesNotEnough
+		);
+		assert_ok!(SavingsModule::withdraw(Origin::signed(ALICE), 40));
+		let withdraw_event = Event::savings(crate::Event::Withdrawn(ALICE, 40, 50));
+		assert!(System::events().iter().any(|record| record.event == withdraw_event));
+		assert_eq!(SavingsModule::shares(ALICE), 60);
+		assert_eq!(SavingsModule::total_shares(), 60);
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 950);
+
+		assert_ok!(SavingsModule::withdraw(Origin::signed(ALICE), 60));
+		assert_eq!(SavingsModule::shares(ALICE), 0);
+		assert_eq!(SavingsModule::total_shares(), 0);
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 1025);
+		assert_eq!(TokensModule::free_balance(AUSD, &SavingsModule::account_id()), 0);
+	});
+}
@@ -6,6 +6,7 @@
 #![allow(clippy::unnecessary_mut_passed)]
 
 use codec::{Codec, Decode, Encode};
+use primitives::EraIndex;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
@@ -22,6 +23,40 @@ pub struct BalanceInfo<Balance> {
 	pub amount: Balance,
 }
 
+/// A pending redemption request of the account.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RedemptionRequest<Balance> {
+	/// The era from which the redemption can be withdrawn.
+	pub claimable_era: EraIndex,
+	/// The staking currency amount to redeem.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub amount: Balance,
+}
+
+/// The redemption requests of the account and the liquidity for instant
+/// redemption.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RedemptionInfo<Balance> {
+	/// Current era index of Polkadot.
+	pub current_era: EraIndex,
+	/// The pending redemption requests, sorted by the claimable era.
+	pub requests: Vec<RedemptionRequest<Balance>>,
+	/// The staking currency amount of free pool available for instant
+	/// redemption.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub free_pool_available: Balance,
+}
+
 #[cfg(feature = "std")]
 fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
 	serializer.serialize_str(&t.to_string())
@@ -44,5 +79,9 @@ sp_api::decl_runtime_apis! {
 		) -> BalanceInfo<Balance>;
 
 		fn get_liquid_staking_exchange_rate() -> support::ExchangeRate;
+
+		fn get_redemption_info(
+			account: AccountId
+		) -> RedemptionInfo<Balance>;
 	}
 }
@@ -3,7 +3,7 @@
 use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use module_staking_pool_rpc_runtime_api::BalanceInfo;
+use module_staking_pool_rpc_runtime_api::{BalanceInfo, RedemptionInfo};
 use module_support::ExchangeRate;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -17,12 +17,15 @@ pub use self::gen_client::Client as StakingPoolClient;
 pub use module_staking_pool_rpc_runtime_api::StakingPoolApi as StakingPoolRuntimeApi;
 
 #[rpc]
-pub trait StakingPoolApi<BlockHash, AccountId, ResponseType> {
+pub trait StakingPoolApi<BlockHash, AccountId, ResponseType, RedemptionInfoType> {
 	#[rpc(name = "stakingPool_getAvailableUnbonded")]
 	fn get_available_unbonded(&self, account: AccountId, at: Option<BlockHash>) -> Result<ResponseType>;
 
 	#[rpc(name = "stakingPool_getLiquidStakingExchangeRate")]
 	fn get_liquid_staking_exchange_rate(&self, at: Option<BlockHash>) -> Result<ExchangeRate>;
+
+	#[rpc(name = "stakingPool_getRedemptionInfo")]
+	fn get_redemption_info(&self, account: AccountId, at: Option<BlockHash>) -> Result<RedemptionInfoType>;
 }
 
 /// A struct that implements the [`StakingPoolApi`].
@@ -53,7 +56,8 @@ impl From<Error> for i64 {
 	}
 }
 
-impl<C, Block, AccountId, Balance> StakingPoolApi<<Block as BlockT>::Hash, AccountId, BalanceInfo<Balance>>
+impl<C, Block, AccountId, Balance>
+	StakingPoolApi<<Block as BlockT>::Hash, AccountId, BalanceInfo<Balance>, RedemptionInfo<Balance>>
 	for StakingPool<C, Block>
 where
 	Block: BlockT,
@@ -93,4 +97,22 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn get_redemption_info(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<RedemptionInfo<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_redemption_info(&at, account).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get redemption info.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
 }
@@ -8,7 +8,7 @@ use primitives::{Balance, CurrencyId, EraIndex};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{AccountIdConversion, CheckedDiv, Saturating, Zero},
+	traits::{AccountIdConversion, CheckedDiv, One, Saturating, Zero},
 	DispatchError, DispatchResult, FixedPointNumber, ModuleId, RuntimeDebug,
 };
 use sp_std::prelude::*;
@@ -39,6 +39,35 @@ pub struct Params {
 	pub base_fee_rate: Rate,
 }
 
+/// The policy of re-bonding the staking rewards of each era.
+///
+/// The rewards are unbonded to the free pool for redemptions once the
+/// liquidity ratio falls below `target_liquidity_ratio - hysteresis`, and are
+/// kept bonded again once it rises above `target_liquidity_ratio +
+/// hysteresis`. Within the band, the previous decision is kept.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct CompoundingPolicy {
+	/// The target ratio of the free pool and unbonding_to_free to the total
+	/// communal DOT.
+	pub target_liquidity_ratio: Ratio,
+	/// The half width of the band around the target liquidity ratio.
+	pub hysteresis: Ratio,
+}
+
+impl CompoundingPolicy {
+	/// Whether to unbond the rewards at `liquidity_ratio`, `is_unbonding` is
+	/// the previous decision.
+	fn should_unbond(&self, liquidity_ratio: Ratio, is_unbonding: bool) -> bool {
+		if liquidity_ratio < self.target_liquidity_ratio.saturating_sub(self.hysteresis) {
+			true
+		} else if liquidity_ratio > self.target_liquidity_ratio.saturating_add(self.hysteresis) {
+			false
+		} else {
+			is_unbonding
+		}
+	}
+}
+
 /// The ledger of staking pool.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
 pub struct Ledger {
@@ -78,6 +107,16 @@ impl Ledger {
 		Ratio::checked_from_rational(self.free_pool, self.total_belong_to_liquid_holders()).unwrap_or_default()
 	}
 
+	/// The ratio of `free_pool` and `unbonding_to_free` in
+	/// `total_belong_to_liquid_holders`.
+	fn liquidity_ratio(&self) -> Ratio {
+		Ratio::checked_from_rational(
+			self.free_pool.saturating_add(self.unbonding_to_free),
+			self.total_belong_to_liquid_holders(),
+		)
+		.unwrap_or_default()
+	}
+
 	/// The ratio of `unbonding_to_free` in
 	/// `total_belong_to_liquid_holders`.
 	fn unbonding_to_free_ratio(&self) -> Ratio {
@@ -148,6 +187,29 @@ pub mod module {
 
 		/// The currency for managing assets related to Homa protocol.
 		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The maximum number of redemption requests waiting for unbonding
+		/// that a mint can be matched with.
+		#[pallet::constant]
+		type MaxFastMatchCount: Get<u32>;
+
+		/// The treasury account receives the commission on staking rewards.
+		#[pallet::constant]
+		type HomaTreasuryAccount: Get<Self::AccountId>;
+
+		/// The cap of the commission ratio on staking rewards.
+		#[pallet::constant]
+		type MaxRewardFeeRatio: Get<Ratio>;
+
+		/// The weight budget per block for withdrawing the matured unbonding
+		/// of sub accounts on polkadot bridge.
+		#[pallet::constant]
+		type MaxWithdrawUnbondedWeight: Get<Weight>;
+
+		/// The weight of withdrawing the matured unbonding of one sub account
+		/// on polkadot bridge.
+		#[pallet::constant]
+		type WithdrawUnbondedWeight: Get<Weight>;
 	}
 
 	#[pallet::error]
@@ -160,6 +222,10 @@ pub mod module {
 		GetFeeFailed,
 		/// Invalid config.
 		InvalidConfig,
+		/// The commission ratio exceeds the cap.
+		ExceedMaxRewardFeeRatio,
+		/// There's no bonded to unbond.
+		NothingToUnbond,
 	}
 
 	#[pallet::event]
@@ -181,6 +247,46 @@ pub mod module {
 		/// the unbonding_to_free of specific era. \[who, target_era,
 		/// fee_in_staking, liquid_amount_burned, staking_amount_redeemed\]
 		RedeemByClaimUnbonding(T::AccountId, EraIndex, Balance, Balance, Balance),
+		/// A share of staking rewards is contributed to the insurance fund.
+		/// \[contribution_amount\]
+		InsuranceContributed(Balance),
+		/// The slash on relaychain is compensated by the insurance fund.
+		/// \[slashed_amount, compensated_amount\]
+		SlashCompensated(Balance, Balance),
+		/// The insurance contribution rate updated. \[new_contribution_rate\]
+		InsuranceContributionRateUpdated(Rate),
+		/// The staking currency(DOT) of mint is paid to the redemption request
+		/// waiting for unbonding directly. \[minter, redeemer,
+		/// matched_staking_amount, fee_in_staking\]
+		FastMatched(T::AccountId, T::AccountId, Balance, Balance),
+		/// The fast match fee rate updated. \[new_fee_rate\]
+		FastMatchFeeRateUpdated(Rate),
+		/// The commission on staking rewards is charged to the treasury.
+		/// \[reward_amount, fee_amount\]
+		RewardFeeCharged(Balance, Balance),
+		/// The commission ratio on staking rewards updated.
+		/// \[new_reward_fee_ratio\]
+		RewardFeeRatioUpdated(Ratio),
+		/// The bonded is unbonded immediately for emergency. \[unbond_amount,
+		/// expired_era_index\]
+		EmergencyUnbonded(Balance, EraIndex),
+		/// The emergency unbonding is in progress. \[unbond_amount,
+		/// expired_era_index, remaining_eras\]
+		EmergencyUnbondProgressed(Balance, EraIndex, EraIndex),
+		/// The emergency unbonding is completed and withdrawn to free pool.
+		/// \[unbond_amount, expired_era_index\]
+		EmergencyUnbondCompleted(Balance, EraIndex),
+		/// The max exposure ratio of a single validator updated.
+		/// \[new_max_exposure_ratio\]
+		MaxValidatorExposureRatioUpdated(Option<Ratio>),
+		/// The reward compounding policy updated. \[new_policy\]
+		RewardCompoundingPolicyUpdated(Option<CompoundingPolicy>),
+		/// The staking rewards started or stopped being unbonded to free pool.
+		/// \[is_unbonding\]
+		RewardUnbondingSwitched(bool),
+		/// The staking rewards of the era are unbonded to free pool.
+		/// \[reward_amount\]
+		RewardUnbonded(Balance),
 	}
 
 	/// Current era index of Polkadot.
@@ -213,6 +319,65 @@ pub mod module {
 	#[pallet::getter(fn staking_pool_ledger)]
 	pub type StakingPoolLedger<T: Config> = StorageValue<_, Ledger, ValueQuery>;
 
+	/// The share of staking rewards contributed to the insurance fund.
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_contribution_rate)]
+	pub type InsuranceContributionRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+
+	/// The commission ratio on staking rewards.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_fee_ratio)]
+	pub type RewardFeeRatio<T: Config> = StorageValue<_, Ratio, ValueQuery>;
+
+	/// The policy of re-bonding the staking rewards, `None` means the rewards
+	/// are always kept bonded.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_compounding_policy)]
+	pub type RewardCompoundingPolicy<T: Config> = StorageValue<_, CompoundingPolicy, OptionQuery>;
+
+	/// Whether the staking rewards are being unbonded to free pool under the
+	/// reward compounding policy.
+	#[pallet::storage]
+	#[pallet::getter(fn is_reward_unbonding)]
+	pub type IsRewardUnbonding<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The emergency unbonding which has not completed.
+	/// ExpiredEraIndex => UnbondAmount
+	#[pallet::storage]
+	#[pallet::getter(fn emergency_unbonds)]
+	pub type EmergencyUnbonds<T: Config> = StorageMap<_, Twox64Concat, EraIndex, Balance, ValueQuery>;
+
+	/// The max ratio of the bonded of staking pool which can be nominated to
+	/// a single validator, `None` means all sub accounts nominate all targets.
+	#[pallet::storage]
+	#[pallet::getter(fn max_validator_exposure_ratio)]
+	pub type MaxValidatorExposureRatio<T: Config> = StorageValue<_, Ratio, OptionQuery>;
+
+	/// The block number at which the staking pool is last rebalanced, the
+	/// liquid exchange rate has accounted the rewards and slashes on
+	/// relaychain up to then.
+	#[pallet::storage]
+	#[pallet::getter(fn last_rebalanced_at)]
+	pub type LastRebalancedAt<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// The sub accounts which are waiting to withdraw the matured unbonding
+	/// on polkadot bridge.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_withdraw_unbonded)]
+	pub type PendingWithdrawUnbonded<T: Config> = StorageValue<_, Vec<u32>, ValueQuery>;
+
+	/// The eras whose matured unbonding has not been received from polkadot
+	/// bridge to staking pool yet, the redemption expired at these eras
+	/// can not be withdrawn until then.
+	#[pallet::storage]
+	#[pallet::getter(fn unsettled_unbonding_eras)]
+	pub type UnsettledUnbondingEras<T: Config> = StorageValue<_, Vec<EraIndex>, ValueQuery>;
+
+	/// The fee rate of fast match, split between minter and redeemer.
+	#[pallet::storage]
+	#[pallet::getter(fn fast_match_fee_rate)]
+	pub type FastMatchFeeRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+
 	/// The params of staking pool.
 	#[pallet::storage]
 	#[pallet::getter(fn staking_pool_params)]
@@ -252,7 +417,13 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Continue withdrawing the matured unbonding which is not finished
+		/// within the weight budget of previous blocks.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			Self::sweep_unbonded(T::MaxWithdrawUnbondedWeight::get())
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -295,6 +466,106 @@ pub mod module {
 			})?;
 			Ok(().into())
 		}
+
+		/// Update the share of staking rewards contributed to the insurance
+		/// fund
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `contribution_rate`: the new contribution rate.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_insurance_contribution_rate(
+			origin: OriginFor<T>,
+			contribution_rate: Rate,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(contribution_rate <= Rate::one(), Error::<T>::InvalidConfig);
+			InsuranceContributionRate::<T>::put(contribution_rate);
+			Self::deposit_event(Event::InsuranceContributionRateUpdated(contribution_rate));
+			Ok(().into())
+		}
+
+		/// Update the fee rate of fast match
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `fee_rate`: the new fee rate.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_fast_match_fee_rate(origin: OriginFor<T>, fee_rate: Rate) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(fee_rate <= Rate::one(), Error::<T>::InvalidConfig);
+			FastMatchFeeRate::<T>::put(fee_rate);
+			Self::deposit_event(Event::FastMatchFeeRateUpdated(fee_rate));
+			Ok(().into())
+		}
+
+		/// Update the commission ratio on staking rewards
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `reward_fee_ratio`: the new commission ratio, capped by
+		///   `MaxRewardFeeRatio`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_reward_fee_ratio(origin: OriginFor<T>, reward_fee_ratio: Ratio) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				reward_fee_ratio <= T::MaxRewardFeeRatio::get(),
+				Error::<T>::ExceedMaxRewardFeeRatio
+			);
+			RewardFeeRatio::<T>::put(reward_fee_ratio);
+			Self::deposit_event(Event::RewardFeeRatioUpdated(reward_fee_ratio));
+			Ok(().into())
+		}
+
+		/// Update the max ratio of the bonded which can be nominated to a
+		/// single validator
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `max_exposure_ratio`: the new max exposure ratio, `None` means no
+		///   cap.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_max_validator_exposure_ratio(
+			origin: OriginFor<T>,
+			max_exposure_ratio: Option<Ratio>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(ratio) = max_exposure_ratio {
+				ensure!(!ratio.is_zero() && ratio <= Ratio::one(), Error::<T>::InvalidConfig);
+			}
+			MaxValidatorExposureRatio::<T>::set(max_exposure_ratio);
+			Self::deposit_event(Event::MaxValidatorExposureRatioUpdated(max_exposure_ratio));
+			Ok(().into())
+		}
+
+		/// Update the policy of re-bonding the staking rewards
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `policy`: the new policy, `None` means the rewards are always kept
+		///   bonded.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_reward_compounding_policy(
+			origin: OriginFor<T>,
+			policy: Option<CompoundingPolicy>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(policy) = policy {
+				ensure!(
+					policy.hysteresis <= policy.target_liquidity_ratio
+						&& policy.target_liquidity_ratio.saturating_add(policy.hysteresis) < Ratio::one(),
+					Error::<T>::InvalidConfig
+				);
+			}
+			RewardCompoundingPolicy::<T>::set(policy);
+			Self::deposit_event(Event::RewardCompoundingPolicyUpdated(policy));
+			Ok(().into())
+		}
 	}
 }
 
@@ -443,6 +714,71 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Queue all sub accounts to withdraw the matured unbonding of
+	/// `current_era` on polkadot bridge.
+	pub fn schedule_withdraw_unbonded(current_era: EraIndex) {
+		PendingWithdrawUnbonded::<T>::put(T::PoolAccountIndexes::get());
+		if Unbonding::<T>::contains_key(current_era) && !Self::unsettled_unbonding_eras().contains(&current_era) {
+			UnsettledUnbondingEras::<T>::append(current_era);
+		}
+	}
+
+	/// Require polkadot bridge to withdraw unbonded for the queued sub
+	/// accounts as many as `max_weight` allows. Once all of them have been
+	/// withdrawn, the matured unbonding is received to staking pool. Returns
+	/// the consumed weight.
+	pub fn sweep_unbonded(max_weight: Weight) -> Weight {
+		let mut weight = T::DbWeight::get().reads(2);
+		let mut pending = Self::pending_withdraw_unbonded();
+		if pending.is_empty() && Self::unsettled_unbonding_eras().is_empty() {
+			return weight;
+		}
+
+		let withdraw_weight = T::WithdrawUnbondedWeight::get();
+		let mut withdrawn_count: usize = 0;
+		for sub_account_index in pending.iter() {
+			if weight.saturating_add(withdraw_weight) > max_weight {
+				break;
+			}
+			T::Bridge::withdraw_unbonded(*sub_account_index);
+			weight = weight.saturating_add(withdraw_weight);
+			withdrawn_count += 1;
+		}
+
+		if withdrawn_count > 0 {
+			pending = pending.split_off(withdrawn_count);
+			PendingWithdrawUnbonded::<T>::put(&pending);
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+		}
+
+		let settle_weight = withdraw_weight.saturating_add(T::DbWeight::get().reads_writes(3, 3));
+		if pending.is_empty() && weight.saturating_add(settle_weight) <= max_weight {
+			Self::settle_unbonded();
+			weight = weight.saturating_add(settle_weight);
+		}
+
+		weight
+	}
+
+	/// Receive the available staking currency from polkadot bridge to staking
+	/// pool, and settle the matured unbonding of unsettled eras.
+	fn settle_unbonded() {
+		StakingPoolLedger::<T>::mutate(|ledger| {
+			let available_on_polkadot_bridge = Self::balance().saturating_sub(Self::staking_ledger().total);
+			if Self::receive_from_bridge(&Self::account_id(), available_on_polkadot_bridge).is_ok() {
+				let mut claimed_unbonded: Balance = Zero::zero();
+				for era_index in UnsettledUnbondingEras::<T>::take() {
+					let (total, claimed, _) = Unbonding::<T>::take(era_index);
+					ledger.unbonding_to_free = ledger.unbonding_to_free.saturating_sub(total.saturating_sub(claimed));
+					claimed_unbonded = claimed_unbonded.saturating_add(claimed);
+				}
+				ledger.free_pool = ledger
+					.free_pool
+					.saturating_add(available_on_polkadot_bridge.saturating_sub(claimed_unbonded));
+			}
+		});
+	}
+
 	/// Require polkadot bridge to get staking rewards on Polkadot.
 	pub fn payout_nominator() {
 		for sub_account_index in T::PoolAccountIndexes::get() {
@@ -451,9 +787,49 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Require polkadot bridge to nominate validators of Polkadot.
+	///
+	/// If the max validator exposure ratio is set, each target is only
+	/// nominated by the sub accounts whose total bonded is within the cap,
+	/// preferring the sub accounts with fewer targets. A sub account whose
+	/// bonded exceeds the cap alone nominates nothing.
 	pub fn nominate(targets: Vec<PolkadotAccountIdOf<T>>) {
-		for sub_account_index in T::PoolAccountIndexes::get() {
-			T::Bridge::nominate(sub_account_index, targets.clone());
+		let max_exposure_ratio = match Self::max_validator_exposure_ratio() {
+			Some(ratio) => ratio,
+			None => {
+				for sub_account_index in T::PoolAccountIndexes::get() {
+					T::Bridge::nominate(sub_account_index, targets.clone());
+				}
+				return;
+			}
+		};
+
+		let mut sub_accounts = T::PoolAccountIndexes::get()
+			.into_iter()
+			.map(|account_index| (account_index, T::Bridge::staking_ledger(account_index).active, vec![]))
+			.collect::<Vec<(u32, Balance, Vec<PolkadotAccountIdOf<T>>)>>();
+		let total_bonded = sub_accounts
+			.iter()
+			.fold(Zero::zero(), |total: Balance, (_, active, _)| {
+				total.saturating_add(*active)
+			});
+		let max_exposure = max_exposure_ratio.saturating_mul_int(total_bonded);
+
+		for target in targets {
+			let mut order = (0..sub_accounts.len()).collect::<Vec<_>>();
+			order.sort_by_key(|index| (sub_accounts[*index].2.len(), sub_accounts[*index].1));
+
+			let mut exposure: Balance = Zero::zero();
+			for index in order {
+				let (_, active, sub_account_targets) = &mut sub_accounts[index];
+				if exposure.saturating_add(*active) <= max_exposure {
+					exposure = exposure.saturating_add(*active);
+					sub_account_targets.push(target.clone());
+				}
+			}
+		}
+
+		for (account_index, _, sub_account_targets) in sub_accounts {
+			T::Bridge::nominate(account_index, sub_account_targets);
 		}
 	}
 
@@ -510,6 +886,16 @@ impl<T: Config> Pallet<T> {
 		T::ModuleId::get().into_account()
 	}
 
+	/// The account keeps the staking currency of the insurance fund
+	pub fn insurance_account_id() -> T::AccountId {
+		T::ModuleId::get().into_sub_account("insurance")
+	}
+
+	/// The staking currency amount of the insurance fund
+	pub fn insurance_fund() -> Balance {
+		T::Currency::free_balance(T::StakingCurrencyId::get(), &Self::insurance_account_id())
+	}
+
 	/// Get the exchange rate for liquid currency to staking currency.
 	pub fn liquid_exchange_rate() -> ExchangeRate {
 		let exchange_rate = ExchangeRate::checked_from_rational(
@@ -525,43 +911,206 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Whether the redemption expired at `era_index` can be withdrawn, the
+	/// matured unbonding of the era must have been received from polkadot
+	/// bridge.
+	fn is_withdrawable(era_index: EraIndex) -> bool {
+		era_index <= Self::current_era() && !Self::unsettled_unbonding_eras().contains(&era_index)
+	}
+
 	/// Get how much available unbonded of `who` in current era.
 	pub fn get_available_unbonded(who: &T::AccountId) -> Balance {
 		Unbondings::<T>::iter_prefix(who)
-			.filter(|(era_index, _)| era_index <= &Self::current_era())
+			.filter(|(era_index, _)| Self::is_withdrawable(*era_index))
 			.fold(Zero::zero(), |available_unbonded, (_, unbonded)| {
 				available_unbonded.saturating_add(unbonded)
 			})
 	}
 
-	pub fn update_ledger_with_bridge(current_era: EraIndex) {
-		// require polkadot bridge to withdraw unbonded.
-		Self::withdraw_unbonded();
+	/// Get the pending redemption requests of `who`, and the era from
+	/// which each of them can be withdrawn, sorted by the era.
+	pub fn get_redemption_requests(who: &T::AccountId) -> Vec<(EraIndex, Balance)> {
+		let mut requests: Vec<(EraIndex, Balance)> = Unbondings::<T>::iter_prefix(who)
+			.filter(|(_, unbonded)| !unbonded.is_zero())
+			.collect();
+
+		// the redemption claimed in current era will unbond at the beginning of
+		// next era.
+		let next_era_unbond = Self::next_era_unbonds(who);
+		if !next_era_unbond.is_zero() {
+			let expired_era_index = Self::current_era()
+				.saturating_add(One::one())
+				.saturating_add(<<T as Config>::Bridge as PolkadotBridgeType<_, _>>::BondingDuration::get());
+			requests.push((expired_era_index, next_era_unbond));
+		}
+
+		requests.sort_by_key(|(era_index, _)| *era_index);
+		requests
+	}
+
+	/// Get the staking currency amount of free pool which is available for
+	/// instant redemption.
+	pub fn get_available_free_pool() -> Balance {
+		Self::available_free_pool(&Self::staking_pool_ledger())
+	}
 
-		// require polkadot bridge to payout nominator.
-		// TODO: record the balances of bridge before and after payout_nominator,
-		// and oncommision to homa treasury according to `RewardFeeRatio`.
+	/// The free pool above the target min free unbonded ratio.
+	fn available_free_pool(ledger: &Ledger) -> Balance {
+		ledger.free_pool.saturating_sub(
+			Self::staking_pool_params()
+				.target_min_free_unbonded_ratio
+				.saturating_mul_int(ledger.total_belong_to_liquid_holders()),
+		)
+	}
+
+	/// Update the staking pool ledger to the ledger of polkadot bridge,
+	/// returns the staking rewards after the commission and the insurance
+	/// contribution.
+	pub fn update_ledger_with_bridge(current_era: EraIndex) -> Balance {
+		// require polkadot bridge to payout nominator, and record the rewards.
+		let balance_before_payout = Self::balance();
 		Self::payout_nominator();
+		let reward = Self::balance().saturating_sub(balance_before_payout);
 
-		StakingPoolLedger::<T>::mutate(|ledger| {
+		// require polkadot bridge to withdraw unbonded within the weight budget of
+		// this block, the rest is continued at the beginning of next blocks.
+		Self::schedule_withdraw_unbonded(current_era);
+		Self::sweep_unbonded(T::MaxWithdrawUnbondedWeight::get());
+
+		StakingPoolLedger::<T>::mutate(|ledger| -> Balance {
 			let polkadot_bridge_ledger = Self::staking_ledger();
-			let available_on_polkadot_bridge = Self::balance().saturating_sub(polkadot_bridge_ledger.total);
+
+			// the bonded which is missing on polkadot bridge has been slashed.
+			let slashed = ledger
+				.bonded
+				.saturating_add(reward)
+				.saturating_sub(polkadot_bridge_ledger.active);
 
 			// update bonded of staking pool to the active(bonded) of polkadot bridge
 			// ledger.
 			ledger.bonded = polkadot_bridge_ledger.active;
 
-			// withdraw available staking currency from polkadot bridge to staking pool.
-			if Self::receive_from_bridge(&Self::account_id(), available_on_polkadot_bridge).is_ok() {
-				let (total_unbonded, claimed_unbonded, _) = Unbonding::<T>::take(current_era);
-				ledger.unbonding_to_free = ledger
-					.unbonding_to_free
-					.saturating_sub(total_unbonded.saturating_sub(claimed_unbonded));
-				ledger.free_pool = ledger
-					.free_pool
-					.saturating_add(available_on_polkadot_bridge.saturating_sub(claimed_unbonded));
+			let fee = Self::charge_reward_fee(ledger, reward);
+			let contribution = Self::contribute_insurance(ledger, reward);
+			Self::compensate_slash(ledger, slashed);
+
+			reward.saturating_sub(fee).saturating_sub(contribution)
+		})
+	}
+
+	/// Move the commission on staking rewards from free pool to the homa
+	/// treasury, returns the charged amount.
+	fn charge_reward_fee(ledger: &mut Ledger, reward: Balance) -> Balance {
+		let fee = Self::reward_fee_ratio()
+			.saturating_mul_int(reward)
+			.min(ledger.free_pool);
+
+		if !fee.is_zero()
+			&& T::Currency::transfer(
+				T::StakingCurrencyId::get(),
+				&Self::account_id(),
+				&T::HomaTreasuryAccount::get(),
+				fee,
+			)
+			.is_ok()
+		{
+			ledger.free_pool = ledger.free_pool.saturating_sub(fee);
+			Self::deposit_event(Event::RewardFeeCharged(reward, fee));
+			fee
+		} else {
+			Zero::zero()
+		}
+	}
+
+	/// Move the share of staking rewards from free pool to the insurance
+	/// fund, returns the contributed amount.
+	fn contribute_insurance(ledger: &mut Ledger, reward: Balance) -> Balance {
+		let contribution = Self::insurance_contribution_rate()
+			.saturating_mul_int(reward)
+			.min(ledger.free_pool);
+
+		if !contribution.is_zero()
+			&& T::Currency::transfer(
+				T::StakingCurrencyId::get(),
+				&Self::account_id(),
+				&Self::insurance_account_id(),
+				contribution,
+			)
+			.is_ok()
+		{
+			ledger.free_pool = ledger.free_pool.saturating_sub(contribution);
+			Self::deposit_event(Event::InsuranceContributed(contribution));
+			contribution
+		} else {
+			Zero::zero()
+		}
+	}
+
+	/// Refill the free pool with the insurance fund up to the slashed amount
+	/// so that the exchange rate of liquid currency is not impacted.
+	fn compensate_slash(ledger: &mut Ledger, slashed: Balance) {
+		if slashed.is_zero() {
+			return;
+		}
+
+		let mut compensation = slashed.min(Self::insurance_fund());
+		if !compensation.is_zero()
+			&& T::Currency::transfer(
+				T::StakingCurrencyId::get(),
+				&Self::insurance_account_id(),
+				&Self::account_id(),
+				compensation,
+			)
+			.is_ok()
+		{
+			ledger.free_pool = ledger.free_pool.saturating_add(compensation);
+		} else {
+			compensation = Zero::zero();
+		}
+		Self::deposit_event(Event::SlashCompensated(slashed, compensation));
+	}
+
+	/// The staking rewards of the era to unbond to free pool under the reward
+	/// compounding policy, the rewards are bonded on relaychain by payout.
+	fn reward_to_unbond(ledger: &Ledger, reward: Balance) -> Balance {
+		let policy = match Self::reward_compounding_policy() {
+			Some(policy) => policy,
+			None => return Zero::zero(),
+		};
+
+		let was_unbonding = Self::is_reward_unbonding();
+		let is_unbonding = policy.should_unbond(ledger.liquidity_ratio(), was_unbonding);
+		if is_unbonding != was_unbonding {
+			IsRewardUnbonding::<T>::put(is_unbonding);
+			Self::deposit_event(Event::RewardUnbondingSwitched(is_unbonding));
+		}
+
+		if is_unbonding && !reward.is_zero() {
+			Self::deposit_event(Event::RewardUnbonded(reward));
+			reward
+		} else {
+			Zero::zero()
+		}
+	}
+
+	/// Report the progress of the emergency unbonding, returns whether any
+	/// emergency unbonding is still in progress.
+	fn check_emergency_unbonds(current_era: EraIndex) -> bool {
+		let mut is_emergency_unbonding = false;
+		for (expired_era_index, amount) in EmergencyUnbonds::<T>::iter().collect::<Vec<_>>() {
+			if expired_era_index <= current_era {
+				EmergencyUnbonds::<T>::remove(expired_era_index);
+				Self::deposit_event(Event::EmergencyUnbondCompleted(amount, expired_era_index));
+			} else {
+				is_emergency_unbonding = true;
+				Self::deposit_event(Event::EmergencyUnbondProgressed(
+					amount,
+					expired_era_index,
+					expired_era_index.saturating_sub(current_era),
+				));
 			}
-		});
+		}
+		is_emergency_unbonding
 	}
 
 	pub fn rebalance(current_era: EraIndex) {
@@ -570,7 +1119,9 @@ impl<T: Config> Pallet<T> {
 
 		// require polkadot bridge to withdraw unbonded and withdraw payout and update
 		// staking pool ledger.
-		Self::update_ledger_with_bridge(current_era);
+		let reward = Self::update_ledger_with_bridge(current_era);
+		LastRebalancedAt::<T>::put(<frame_system::Module<T>>::block_number());
+		let is_emergency_unbonding = Self::check_emergency_unbonds(current_era);
 
 		// staking pool require polkadot bridge to bond and unbond according to ledger,
 		// and update related records.
@@ -581,9 +1132,14 @@ impl<T: Config> Pallet<T> {
 			let bond_rate = ledger
 				.free_pool_ratio()
 				.saturating_sub(staking_pool_params.target_max_free_unbonded_ratio);
-			let bond_amount = bond_rate
-				.saturating_mul_int(ledger.total_belong_to_liquid_holders())
-				.min(ledger.free_pool);
+			// the free pool is not bonded until the emergency unbonding is completed.
+			let bond_amount = if is_emergency_unbonding {
+				Zero::zero()
+			} else {
+				bond_rate
+					.saturating_mul_int(ledger.total_belong_to_liquid_holders())
+					.min(ledger.free_pool)
+			};
 
 			let unbond_to_free_rate = staking_pool_params
 				.target_unbonding_to_free_ratio
@@ -591,6 +1147,7 @@ impl<T: Config> Pallet<T> {
 				.min(staking_pool_params.unbonding_to_free_adjustment);
 			let unbond_to_free_amount = unbond_to_free_rate
 				.saturating_mul_int(ledger.total_belong_to_liquid_holders())
+				.max(Self::reward_to_unbond(ledger, reward))
 				.min(ledger.bonded_belong_to_liquid_holders());
 			total_unbond = total_unbond.saturating_add(unbond_to_free_amount);
 
@@ -626,6 +1183,62 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// Pay the staking currency of the minter to the redemption requests
+	/// waiting for unbonding directly, the matched requests no longer need
+	/// to unbond on relaychain. Returns the matched staking amount, the fee
+	/// of the minter and the total fee of redeemers.
+	fn fast_match(
+		ledger: &mut Ledger,
+		minter: &T::AccountId,
+		amount: Balance,
+	) -> sp_std::result::Result<(Balance, Balance, Balance), DispatchError> {
+		let fee_rate = Self::fast_match_fee_rate();
+		let mut remain_amount = amount;
+		let mut minter_fee: Balance = Zero::zero();
+		let mut redeemer_fee: Balance = Zero::zero();
+
+		let requests = NextEraUnbonds::<T>::iter()
+			.filter(|(redeemer, _)| redeemer != minter)
+			.take(T::MaxFastMatchCount::get() as usize)
+			.collect::<Vec<_>>();
+		for (redeemer, unbond) in requests {
+			if remain_amount.is_zero() {
+				break;
+			}
+
+			let matched = remain_amount.min(unbond);
+			let fee = fee_rate.saturating_mul_int(matched);
+			let fee_of_minter = fee / 2;
+			let fee_of_redeemer = fee.saturating_sub(fee_of_minter);
+
+			T::Currency::transfer(
+				T::StakingCurrencyId::get(),
+				minter,
+				&redeemer,
+				matched.saturating_sub(fee_of_redeemer),
+			)?;
+			if matched == unbond {
+				NextEraUnbonds::<T>::remove(&redeemer);
+			} else {
+				NextEraUnbonds::<T>::insert(&redeemer, unbond.saturating_sub(matched));
+			}
+			let (total_unbond, claimed_unbond) = ledger.to_unbond_next_era;
+			ledger.to_unbond_next_era = (
+				total_unbond.saturating_sub(matched),
+				claimed_unbond.saturating_sub(matched),
+			);
+
+			remain_amount = remain_amount.saturating_sub(matched);
+			minter_fee = minter_fee.saturating_add(fee_of_minter);
+			redeemer_fee = redeemer_fee.saturating_add(fee_of_redeemer);
+			Self::deposit_event(Event::FastMatched(minter.clone(), redeemer, matched, fee));
+		}
+
+		Ok((amount.saturating_sub(remain_amount), minter_fee, redeemer_fee))
+	}
+}
+
 impl<T: Config> OnNewEra<EraIndex> for Pallet<T> {
 	fn on_new_era(new_era: EraIndex) {
 		CurrentEra::<T>::put(new_era);
@@ -643,16 +1256,25 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 		}
 
 		StakingPoolLedger::<T>::try_mutate(|ledger| -> sp_std::result::Result<Self::Balance, DispatchError> {
-			let liquid_amount_to_issue = Self::liquid_exchange_rate()
+			let liquid_exchange_rate = Self::liquid_exchange_rate();
+			let (matched_amount, minter_fee, redeemer_fee) = Self::fast_match(ledger, who, amount)?;
+			let liquid_amount_to_issue = liquid_exchange_rate
 				.reciprocal()
 				.unwrap_or_default()
-				.checked_mul_int(amount)
+				.checked_mul_int(amount.saturating_sub(minter_fee))
 				.ok_or(Error::<T>::Overflow)?;
 
-			T::Currency::transfer(T::StakingCurrencyId::get(), who, &Self::account_id(), amount)?;
+			// the fee of redeemers is kept by the staking pool.
+			let deposit_to_free_pool = amount.saturating_sub(matched_amount).saturating_add(redeemer_fee);
+			T::Currency::transfer(
+				T::StakingCurrencyId::get(),
+				who,
+				&Self::account_id(),
+				deposit_to_free_pool,
+			)?;
 			T::Currency::deposit(T::LiquidCurrencyId::get(), who, liquid_amount_to_issue)?;
 
-			ledger.free_pool = ledger.free_pool.saturating_add(amount);
+			ledger.free_pool = ledger.free_pool.saturating_add(deposit_to_free_pool);
 
 			Self::deposit_event(Event::MintLiquid(who.clone(), amount, liquid_amount_to_issue));
 			Ok(liquid_amount_to_issue)
@@ -720,11 +1342,7 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 				.checked_mul_int(liquid_amount_to_burn)
 				.ok_or(Error::<T>::Overflow)?;
 			let staking_pool_params = Self::staking_pool_params();
-			let available_free_pool = ledger.free_pool.saturating_sub(
-				staking_pool_params
-					.target_min_free_unbonded_ratio
-					.saturating_mul_int(ledger.total_belong_to_liquid_holders()),
-			);
+			let available_free_pool = Self::available_free_pool(ledger);
 
 			if !demand_staking_amount.is_zero() && !available_free_pool.is_zero() {
 				// if available_free_pool is not enough, need re-calculate
@@ -863,7 +1481,7 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 		let mut withdrawn_amount: Balance = Zero::zero();
 
 		Unbondings::<T>::iter_prefix(who)
-			.filter(|(era_index, _)| era_index <= &Self::current_era())
+			.filter(|(era_index, _)| Self::is_withdrawable(*era_index))
 			.for_each(|(expired_era_index, unbonded)| {
 				withdrawn_amount = withdrawn_amount.saturating_add(unbonded);
 				Unbondings::<T>::remove(who, expired_era_index);
@@ -872,4 +1490,28 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 		T::Currency::transfer(T::StakingCurrencyId::get(), &Self::account_id(), who, withdrawn_amount)?;
 		Ok(withdrawn_amount)
 	}
+
+	#[transactional]
+	fn emergency_unbond(amount: Option<Self::Balance>) -> sp_std::result::Result<Self::Balance, DispatchError> {
+		StakingPoolLedger::<T>::try_mutate(|ledger| -> sp_std::result::Result<Self::Balance, DispatchError> {
+			// the bonded claimed by redemption requests will be unbonded at next era.
+			let bonded = ledger.bonded_belong_to_liquid_holders();
+			let amount = amount.unwrap_or(bonded).min(bonded);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToUnbond);
+
+			Self::unbond(amount)?;
+			let expired_era_index = Self::current_era()
+				.saturating_add(<<T as Config>::Bridge as PolkadotBridgeType<_, _>>::BondingDuration::get());
+			Unbonding::<T>::mutate(expired_era_index, |(total_unbond, _, _)| {
+				*total_unbond = total_unbond.saturating_add(amount)
+			});
+			EmergencyUnbonds::<T>::mutate(expired_era_index, |unbond| *unbond = unbond.saturating_add(amount));
+
+			ledger.bonded = ledger.bonded.saturating_sub(amount);
+			ledger.unbonding_to_free = ledger.unbonding_to_free.saturating_add(amount);
+
+			Self::deposit_event(Event::EmergencyUnbonded(amount, expired_era_index));
+			Ok(amount)
+		})
+	}
 }
@@ -124,6 +124,7 @@ pub struct Status {
 
 thread_local! {
 	pub static BRIDGE_STATUS: RefCell<HashMap<u32, Status>> = RefCell::new(HashMap::new());
+	pub static BRIDGE_NOMINATIONS: RefCell<HashMap<u32, Vec<PolkadotAccountId>>> = RefCell::new(HashMap::new());
 }
 
 pub struct MockBridge;
@@ -203,7 +204,9 @@ impl PolkadotBridgeCall<AccountId, BlockNumber, Balance, EraIndex> for MockBridg
 		});
 	}
 
-	fn nominate(_account_index: u32, _targets: Vec<Self::PolkadotAccountId>) {}
+	fn nominate(account_index: u32, targets: Vec<Self::PolkadotAccountId>) {
+		BRIDGE_NOMINATIONS.with(|v| v.borrow_mut().insert(account_index, targets));
+	}
 
 	fn payout_nominator(account_index: u32) {
 		BRIDGE_STATUS.with(|v| {
@@ -324,6 +327,11 @@ parameter_types! {
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub const StakingPoolModuleId: ModuleId = ModuleId(*b"aca/stkp");
 	pub PoolAccountIndexes: Vec<u32> = vec![1, 2, 3, 4];
+	pub const MaxFastMatchCount: u32 = 3;
+	pub const HomaTreasuryAccount: AccountId = 5;
+	pub MaxRewardFeeRatio: Ratio = Ratio::saturating_from_rational(20, 100);
+	pub const MaxWithdrawUnbondedWeight: Weight = 1_000_000;
+	pub const WithdrawUnbondedWeight: Weight = 1_000;
 }
 
 ord_parameter_types! {
@@ -342,6 +350,11 @@ impl Config for Runtime {
 	type Nominees = MockNomineesProvider;
 	type Bridge = MockBridge;
 	type Currency = CurrenciesModule;
+	type MaxFastMatchCount = MaxFastMatchCount;
+	type HomaTreasuryAccount = HomaTreasuryAccount;
+	type MaxRewardFeeRatio = MaxRewardFeeRatio;
+	type MaxWithdrawUnbondedWeight = MaxWithdrawUnbondedWeight;
+	type WithdrawUnbondedWeight = WithdrawUnbondedWeight;
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
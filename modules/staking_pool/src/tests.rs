@@ -5,8 +5,8 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	BondingDuration, CurrenciesModule, Event, ExtBuilder, One, Origin, Runtime, StakingPoolModule, Status, System,
-	ALICE, BOB, BRIDGE_STATUS, DOT, LDOT,
+	BondingDuration, CurrenciesModule, Event, ExtBuilder, HomaTreasuryAccount, One, Origin, Runtime, StakingPoolModule,
+	Status, System, ALICE, BOB, BRIDGE_NOMINATIONS, BRIDGE_STATUS, DOT, LDOT,
 };
 use sp_runtime::traits::BadOrigin;
 
@@ -616,6 +616,75 @@ fn payout_nominator_work() {
 	});
 }
 
+#[test]
+fn nominate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		for account_index in 1..=4 {
+			BRIDGE_STATUS.with(|v| {
+				v.borrow_mut().insert(
+					account_index,
+					Status {
+						bonded: 100,
+						..Default::default()
+					},
+				);
+			});
+		}
+		let nominations = || BRIDGE_NOMINATIONS.with(|v| v.borrow().clone());
+
+		StakingPoolModule::nominate(vec![1, 2, 3]);
+		for account_index in 1..=4 {
+			assert_eq!(nominations().get(&account_index), Some(&vec![1, 2, 3]));
+		}
+
+		// each validator can be nominated by only one sub account
+		MaxValidatorExposureRatio::<Runtime>::put(Ratio::saturating_from_rational(30, 100));
+		StakingPoolModule::nominate(vec![1, 2, 3, 4, 5]);
+		assert_eq!(nominations().get(&1), Some(&vec![1, 5]));
+		assert_eq!(nominations().get(&2), Some(&vec![2]));
+		assert_eq!(nominations().get(&3), Some(&vec![3]));
+		assert_eq!(nominations().get(&4), Some(&vec![4]));
+
+		// each validator can be nominated by two sub accounts
+		MaxValidatorExposureRatio::<Runtime>::put(Ratio::saturating_from_rational(60, 100));
+		StakingPoolModule::nominate(vec![1, 2, 3]);
+		assert_eq!(nominations().get(&1), Some(&vec![1, 3]));
+		assert_eq!(nominations().get(&2), Some(&vec![1, 3]));
+		assert_eq!(nominations().get(&3), Some(&vec![2]));
+		assert_eq!(nominations().get(&4), Some(&vec![2]));
+	});
+}
+
+#[test]
+fn set_max_validator_exposure_ratio_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StakingPoolModule::set_max_validator_exposure_ratio(
+				Origin::signed(ALICE),
+				Some(Ratio::saturating_from_rational(30, 100))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			StakingPoolModule::set_max_validator_exposure_ratio(Origin::signed(One::get()), Some(Ratio::zero())),
+			Error::<Runtime>::InvalidConfig
+		);
+		assert_ok!(StakingPoolModule::set_max_validator_exposure_ratio(
+			Origin::signed(One::get()),
+			Some(Ratio::saturating_from_rational(30, 100))
+		));
+		let update_event = Event::staking_pool(crate::Event::MaxValidatorExposureRatioUpdated(Some(
+			Ratio::saturating_from_rational(30, 100),
+		)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			StakingPoolModule::max_validator_exposure_ratio(),
+			Some(Ratio::saturating_from_rational(30, 100))
+		);
+	});
+}
+
 #[test]
 fn staking_pool_ledger_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -689,6 +758,47 @@ fn get_available_unbonded_work() {
 	});
 }
 
+#[test]
+fn get_redemption_requests_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(StakingPoolModule::get_redemption_requests(&ALICE), vec![]);
+
+		CurrentEra::<Runtime>::put(2);
+		Unbondings::<Runtime>::insert(ALICE, 3, 300);
+		Unbondings::<Runtime>::insert(ALICE, 1, 100);
+		NextEraUnbonds::<Runtime>::insert(ALICE, 50);
+		assert_eq!(
+			StakingPoolModule::get_redemption_requests(&ALICE),
+			vec![(1, 100), (3, 300), (3 + BondingDuration::get(), 50)]
+		);
+		assert_eq!(StakingPoolModule::get_redemption_requests(&BOB), vec![]);
+	});
+}
+
+#[test]
+fn get_available_free_pool_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(StakingPoolModule::get_available_free_pool(), 0);
+
+		StakingPoolLedger::<Runtime>::put(Ledger {
+			bonded: 800,
+			unbonding_to_free: 0,
+			free_pool: 200,
+			to_unbond_next_era: (0, 0),
+		});
+		// keep 5% of total as the min free pool
+		assert_eq!(StakingPoolModule::get_available_free_pool(), 150);
+
+		StakingPoolLedger::<Runtime>::put(Ledger {
+			bonded: 1000,
+			unbonding_to_free: 0,
+			free_pool: 40,
+			to_unbond_next_era: (0, 0),
+		});
+		assert_eq!(StakingPoolModule::get_available_free_pool(), 0);
+	});
+}
+
 #[test]
 fn set_staking_pool_params_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -735,6 +845,256 @@ fn set_staking_pool_params_work() {
 	});
 }
 
+#[test]
+fn set_insurance_contribution_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StakingPoolModule::set_insurance_contribution_rate(
+				Origin::signed(5),
+				Rate::saturating_from_rational(1, 10)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			StakingPoolModule::set_insurance_contribution_rate(
+				Origin::signed(One::get()),
+				Rate::saturating_from_rational(11, 10)
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+		assert_ok!(StakingPoolModule::set_insurance_contribution_rate(
+			Origin::signed(One::get()),
+			Rate::saturating_from_rational(1, 10)
+		));
+		let update_event = Event::staking_pool(crate::Event::InsuranceContributionRateUpdated(
+			Rate::saturating_from_rational(1, 10),
+		));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			StakingPoolModule::insurance_contribution_rate(),
+			Rate::saturating_from_rational(1, 10)
+		);
+	});
+}
+
+#[test]
+fn set_reward_fee_ratio_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StakingPoolModule::set_reward_fee_ratio(Origin::signed(5), Ratio::saturating_from_rational(1, 10)),
+			BadOrigin
+		);
+		assert_noop!(
+			StakingPoolModule::set_reward_fee_ratio(
+				Origin::signed(One::get()),
+				Ratio::saturating_from_rational(21, 100)
+			),
+			Error::<Runtime>::ExceedMaxRewardFeeRatio
+		);
+		assert_ok!(StakingPoolModule::set_reward_fee_ratio(
+			Origin::signed(One::get()),
+			Ratio::saturating_from_rational(20, 100)
+		));
+		let update_event = Event::staking_pool(crate::Event::RewardFeeRatioUpdated(Ratio::saturating_from_rational(
+			20, 100,
+		)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			StakingPoolModule::reward_fee_ratio(),
+			Ratio::saturating_from_rational(20, 100)
+		);
+	});
+}
+
+#[test]
+fn charge_reward_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingPoolModule::set_reward_fee_ratio(
+			Origin::signed(One::get()),
+			Ratio::saturating_from_rational(20, 100)
+		));
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 500));
+		StakingPoolLedger::<Runtime>::put(Ledger {
+			bonded: 1000,
+			free_pool: 500,
+			..Default::default()
+		});
+		BRIDGE_STATUS.with(|v| {
+			v.borrow_mut().insert(
+				1,
+				Status {
+					bonded: 1000,
+					..Default::default()
+				},
+			);
+		});
+
+		StakingPoolModule::update_ledger_with_bridge(1);
+		let charge_event = Event::staking_pool(crate::Event::RewardFeeCharged(10, 2));
+		assert!(System::events().iter().any(|record| record.event == charge_event));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &HomaTreasuryAccount::get()), 2);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().bonded, 1010);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().free_pool, 498);
+	});
+}
+
+#[test]
+fn set_reward_compounding_policy_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let policy = CompoundingPolicy {
+			target_liquidity_ratio: Ratio::saturating_from_rational(20, 100),
+			hysteresis: Ratio::saturating_from_rational(5, 100),
+		};
+		assert_noop!(
+			StakingPoolModule::set_reward_compounding_policy(Origin::signed(ALICE), Some(policy)),
+			BadOrigin
+		);
+		assert_noop!(
+			StakingPoolModule::set_reward_compounding_policy(
+				Origin::signed(One::get()),
+				Some(CompoundingPolicy {
+					target_liquidity_ratio: Ratio::saturating_from_rational(5, 100),
+					hysteresis: Ratio::saturating_from_rational(10, 100),
+				})
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+		assert_ok!(StakingPoolModule::set_reward_compounding_policy(
+			Origin::signed(One::get()),
+			Some(policy)
+		));
+		let update_event = Event::staking_pool(crate::Event::RewardCompoundingPolicyUpdated(Some(policy)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(StakingPoolModule::reward_compounding_policy(), Some(policy));
+
+		assert_ok!(StakingPoolModule::set_reward_compounding_policy(
+			Origin::signed(One::get()),
+			None
+		));
+		assert_eq!(StakingPoolModule::reward_compounding_policy(), None);
+	});
+}
+
+#[test]
+fn compounding_policy_hysteresis_work() {
+	let policy = CompoundingPolicy {
+		target_liquidity_ratio: Ratio::saturating_from_rational(20, 100),
+		hysteresis: Ratio::saturating_from_rational(5, 100),
+	};
+	assert!(policy.should_unbond(Ratio::saturating_from_rational(14, 100), false));
+	assert!(policy.should_unbond(Ratio::saturating_from_rational(20, 100), true));
+	assert!(!policy.should_unbond(Ratio::saturating_from_rational(20, 100), false));
+	assert!(!policy.should_unbond(Ratio::saturating_from_rational(26, 100), true));
+}
+
+#[test]
+fn rebalance_unbond_reward_under_compounding_policy() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		StakingPoolParams::<Runtime>::mutate(|params| params.unbonding_to_free_adjustment = Rate::zero());
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 100));
+		StakingPoolLedger::<Runtime>::put(Ledger {
+			bonded: 1000,
+			free_pool: 100,
+			..Default::default()
+		});
+		BRIDGE_STATUS.with(|v| {
+			v.borrow_mut().insert(
+				1,
+				Status {
+					bonded: 1000,
+					..Default::default()
+				},
+			);
+		});
+
+		// the rewards are kept bonded without policy
+		assert_eq!(StakingPoolModule::last_rebalanced_at(), None);
+		CurrentEra::<Runtime>::put(1);
+		StakingPoolModule::rebalance(1);
+		assert_eq!(StakingPoolModule::last_rebalanced_at(), Some(1));
+		assert_eq!(StakingPoolModule::staking_pool_ledger().bonded, 1010);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().unbonding_to_free, 0);
+
+		assert_ok!(StakingPoolModule::set_reward_compounding_policy(
+			Origin::signed(One::get()),
+			Some(CompoundingPolicy {
+				target_liquidity_ratio: Ratio::saturating_from_rational(20, 100),
+				hysteresis: Ratio::saturating_from_rational(5, 100),
+			})
+		));
+
+		// the liquidity ratio is below the band, unbond the rewards to free pool
+		CurrentEra::<Runtime>::put(2);
+		StakingPoolModule::rebalance(2);
+		let switch_event = Event::staking_pool(crate::Event::RewardUnbondingSwitched(true));
+		assert!(System::events().iter().any(|record| record.event == switch_event));
+		let unbond_event = Event::staking_pool(crate::Event::RewardUnbonded(10));
+		assert!(System::events().iter().any(|record| record.event == unbond_event));
+		assert!(StakingPoolModule::is_reward_unbonding());
+		assert_eq!(StakingPoolModule::staking_pool_ledger().bonded, 1010);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().unbonding_to_free, 10);
+		assert_eq!(StakingPoolModule::unbonding(2 + BondingDuration::get()), (10, 0, 0));
+	});
+}
+
+#[test]
+fn insurance_fund_compensate_slash_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingPoolModule::set_insurance_contribution_rate(
+			Origin::signed(One::get()),
+			Rate::saturating_from_rational(50, 100)
+		));
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 500));
+		StakingPoolLedger::<Runtime>::put(Ledger {
+			bonded: 1000,
+			free_pool: 500,
+			..Default::default()
+		});
+		BRIDGE_STATUS.with(|v| {
+			v.borrow_mut().insert(
+				1,
+				Status {
+					bonded: 1000,
+					..Default::default()
+				},
+			);
+		});
+
+		// contribute the half of the reward
+		StakingPoolModule::update_ledger_with_bridge(1);
+		let contribute_event = Event::staking_pool(crate::Event::InsuranceContributed(5));
+		assert!(System::events().iter().any(|record| record.event == contribute_event));
+		assert_eq!(StakingPoolModule::insurance_fund(), 5);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().bonded, 1010);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().free_pool, 495);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			495
+		);
+
+		// slash 110 on relaychain, compensated by the whole insurance fund
+		BRIDGE_STATUS.with(|v| {
+			v.borrow_mut().get_mut(&1).unwrap().bonded = 900;
+		});
+		StakingPoolModule::update_ledger_with_bridge(2);
+		let compensate_event = Event::staking_pool(crate::Event::SlashCompensated(110, 9));
+		assert!(System::events().iter().any(|record| record.event == compensate_event));
+		assert_eq!(StakingPoolModule::insurance_fund(), 0);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().bonded, 909);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().free_pool, 500);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			500
+		);
+	});
+}
+
 #[test]
 fn mint_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -871,6 +1231,90 @@ fn redeem_by_unbond_work() {
 	});
 }
 
+#[test]
+fn set_fast_match_fee_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StakingPoolModule::set_fast_match_fee_rate(Origin::signed(5), Rate::saturating_from_rational(1, 10)),
+			BadOrigin
+		);
+		assert_noop!(
+			StakingPoolModule::set_fast_match_fee_rate(
+				Origin::signed(One::get()),
+				Rate::saturating_from_rational(11, 10)
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+		assert_ok!(StakingPoolModule::set_fast_match_fee_rate(
+			Origin::signed(One::get()),
+			Rate::saturating_from_rational(1, 10)
+		));
+		let update_event = Event::staking_pool(crate::Event::FastMatchFeeRateUpdated(Rate::saturating_from_rational(
+			1, 10,
+		)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			StakingPoolModule::fast_match_fee_rate(),
+			Rate::saturating_from_rational(1, 10)
+		);
+	});
+}
+
+#[test]
+fn mint_fast_match_redemption_requests() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&BOB, 1000), Ok(10000));
+		assert_ok!(StakingPoolModule::transfer_to_bridge(
+			&StakingPoolModule::account_id(),
+			500
+		));
+		assert_ok!(StakingPoolModule::bond_extra(500));
+		StakingPoolLedger::<Runtime>::mutate(|ledger| {
+			ledger.free_pool = ledger.free_pool.saturating_sub(500);
+			ledger.bonded = ledger.bonded.saturating_add(500);
+		});
+		assert_ok!(CurrenciesModule::transfer(Origin::signed(BOB), ALICE, LDOT, 1000));
+		assert_ok!(StakingPoolModule::redeem_by_unbond(&ALICE, 1000));
+		assert_eq!(StakingPoolModule::next_era_unbonds(&ALICE), 100);
+		assert_ok!(StakingPoolModule::set_fast_match_fee_rate(
+			Origin::signed(One::get()),
+			Rate::saturating_from_rational(10, 100)
+		));
+
+		// the redemption request of the minter self is not matched
+		assert_ok!(CurrenciesModule::deposit(DOT, &ALICE, 10));
+		assert_eq!(StakingPoolModule::mint(&ALICE, 10), Ok(100));
+		assert_eq!(StakingPoolModule::next_era_unbonds(&ALICE), 100);
+		assert_ok!(CurrenciesModule::transfer(Origin::signed(ALICE), BOB, LDOT, 100));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 1000);
+
+		assert_ok!(CurrenciesModule::deposit(DOT, &3, 150));
+		assert_eq!(StakingPoolModule::mint(&3, 150), Ok(1450));
+		let fast_matched_event = Event::staking_pool(crate::Event::FastMatched(3, ALICE, 100, 10));
+		assert!(System::events().iter().any(|record| record.event == fast_matched_event));
+
+		assert_eq!(StakingPoolModule::next_era_unbonds(&ALICE), 0);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 1095);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &3), 0);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &3), 1450);
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 500,
+				unbonding_to_free: 0,
+				free_pool: 565,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			565
+		);
+	});
+}
+
 #[test]
 fn redeem_by_free_unbonded_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1359,3 +1803,143 @@ fn rebalance_work() {
 		assert_eq!(StakingPoolModule::unbondings(&ALICE, 13), 212);
 	});
 }
+
+#[test]
+fn emergency_unbond_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			<StakingPoolModule as HomaProtocol<_, _, _>>::emergency_unbond(None),
+			Error::<Runtime>::NothingToUnbond
+		);
+
+		assert_ok!(CurrenciesModule::deposit(DOT, &ALICE, 100000));
+		assert_eq!(StakingPoolModule::mint(&ALICE, 100000), Ok(1000000));
+		CurrentEra::<Runtime>::put(1);
+		StakingPoolModule::rebalance(1);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().bonded, 90000);
+
+		assert_eq!(
+			<StakingPoolModule as HomaProtocol<_, _, _>>::emergency_unbond(Some(50000)),
+			Ok(50000)
+		);
+		let unbond_event = Event::staking_pool(crate::Event::EmergencyUnbonded(50000, 5));
+		assert!(System::events().iter().any(|record| record.event == unbond_event));
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 40000,
+				unbonding_to_free: 50000,
+				free_pool: 10000,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+		assert_eq!(StakingPoolModule::unbonding(5), (50000, 0, 0));
+		assert_eq!(StakingPoolModule::emergency_unbonds(5), 50000);
+
+		// the free pool is not bonded while the emergency unbonding is in progress
+		CurrentEra::<Runtime>::put(2);
+		StakingPoolModule::rebalance(2);
+		let progress_event = Event::staking_pool(crate::Event::EmergencyUnbondProgressed(50000, 5, 3));
+		assert!(System::events().iter().any(|record| record.event == progress_event));
+		assert!(StakingPoolModule::staking_pool_ledger().free_pool >= 10000);
+
+		CurrentEra::<Runtime>::put(5);
+		StakingPoolModule::rebalance(5);
+		let complete_event = Event::staking_pool(crate::Event::EmergencyUnbondCompleted(50000, 5));
+		assert!(System::events().iter().any(|record| record.event == complete_event));
+		assert_eq!(StakingPoolModule::emergency_unbonds(5), 0);
+		assert_eq!(StakingPoolModule::unbonding(5), (0, 0, 0));
+	});
+}
+
+#[test]
+fn sweep_unbonded_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		BRIDGE_STATUS.with(|v| {
+			let mut old_map = v.borrow().clone();
+			old_map.insert(
+				1,
+				Status {
+					bonded: 300,
+					free: 0,
+					unlocking: vec![(3, 100)],
+				},
+			);
+			old_map.insert(
+				2,
+				Status {
+					bonded: 100,
+					free: 0,
+					unlocking: vec![(3, 50)],
+				},
+			);
+			old_map.insert(
+				4,
+				Status {
+					bonded: 0,
+					free: 0,
+					unlocking: vec![(3, 50), (5, 100)],
+				},
+			);
+			*v.borrow_mut() = old_map;
+		});
+		StakingPoolLedger::<Runtime>::put(Ledger {
+			bonded: 400,
+			unbonding_to_free: 220,
+			free_pool: 0,
+			to_unbond_next_era: (0, 0),
+		});
+		Unbonding::<Runtime>::insert(3, (200, 80, 80));
+		Unbondings::<Runtime>::insert(BOB, 3, 80);
+
+		CurrentEra::<Runtime>::put(3);
+		StakingPoolModule::schedule_withdraw_unbonded(3);
+		assert_eq!(StakingPoolModule::pending_withdraw_unbonded(), vec![1, 2, 3, 4]);
+		assert_eq!(StakingPoolModule::unsettled_unbonding_eras(), vec![3]);
+		assert_eq!(StakingPoolModule::get_available_unbonded(&BOB), 0);
+
+		// only withdraw the sub accounts within the weight budget
+		assert_eq!(StakingPoolModule::sweep_unbonded(2000), 2000);
+		assert_eq!(StakingPoolModule::pending_withdraw_unbonded(), vec![3, 4]);
+		assert_eq!(
+			*BRIDGE_STATUS
+				.with(|v| v.borrow().clone())
+				.get(&1)
+				.unwrap_or(&Default::default()),
+			Status {
+				bonded: 300,
+				free: 100,
+				unlocking: vec![],
+			}
+		);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().free_pool, 0);
+
+		// not enough weight left to settle after withdrawing the rest
+		assert_eq!(StakingPoolModule::sweep_unbonded(2000), 2000);
+		assert!(StakingPoolModule::pending_withdraw_unbonded().is_empty());
+		assert_eq!(StakingPoolModule::unsettled_unbonding_eras(), vec![3]);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()), 0);
+
+		assert_eq!(StakingPoolModule::sweep_unbonded(1000), 1000);
+		assert!(StakingPoolModule::unsettled_unbonding_eras().is_empty());
+		assert_eq!(StakingPoolModule::unbonding(3), (0, 0, 0));
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 400,
+				unbonding_to_free: 100,
+				free_pool: 120,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			200
+		);
+		assert_eq!(StakingPoolModule::get_available_unbonded(&BOB), 80);
+
+		// nothing to sweep
+		assert_eq!(StakingPoolModule::sweep_unbonded(1000), 0);
+	});
+}
@@ -14,6 +14,16 @@ pub trait NomineesProvider<AccountId> {
 	fn nominees() -> Vec<AccountId>;
 }
 
+/// The status of validators on the relaychain.
+pub trait PolkadotValidatorStatus<PolkadotAccountId, EraIndex> {
+	/// Whether the validator is in the active validator set.
+	fn is_active(validator: &PolkadotAccountId) -> bool;
+	/// The latest era in which the validator was slashed.
+	fn last_slash_era(validator: &PolkadotAccountId) -> Option<EraIndex>;
+	/// The reward points earned by the validator in the era.
+	fn reward_points(validator: &PolkadotAccountId, era: EraIndex) -> u32;
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct PolkadotUnlockChunk<Balance, EraIndex> {
 	pub value: Balance,
@@ -75,4 +85,7 @@ pub trait HomaProtocol<AccountId, Balance, EraIndex> {
 	fn redeem_by_free_unbonded(who: &AccountId, amount: Balance) -> DispatchResult;
 	fn redeem_by_claim_unbonding(who: &AccountId, amount: Balance, target_era: EraIndex) -> DispatchResult;
 	fn withdraw_redemption(who: &AccountId) -> sp_std::result::Result<Balance, DispatchError>;
+	/// Unbond `amount` of the bonded on relaychain immediately, `None`
+	/// means all. Returns the unbonded amount.
+	fn emergency_unbond(amount: Option<Balance>) -> sp_std::result::Result<Balance, DispatchError>;
 }
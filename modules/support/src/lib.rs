@@ -5,7 +5,7 @@ use frame_support::pallet_prelude::Weight;
 use primitives::evm::{CallInfo, EvmAddress};
 use sp_core::H160;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize},
+	traits::{AtLeast32BitUnsigned, CheckedDiv, MaybeSerializeDeserialize, Zero},
 	transaction_validity::TransactionValidityError,
 	DispatchError, DispatchResult, FixedU128, RuntimeDebug,
 };
@@ -18,7 +18,7 @@ use sp_std::{
 pub mod homa;
 pub use homa::{
 	HomaProtocol, NomineesProvider, OnCommission, OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState,
-	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk,
+	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk, PolkadotValidatorStatus,
 };
 
 pub type Price = FixedU128;
@@ -82,6 +82,9 @@ pub trait AuctionManager<AccountId> {
 pub trait DEXManager<AccountId, CurrencyId, Balance> {
 	fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance);
 
+	/// The time weighted average price of currency_id_a in currency_id_b.
+	fn get_twap_price(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Option<Price>;
+
 	fn get_swap_target_amount(
 		path: &[CurrencyId],
 		supply_amount: Balance,
@@ -109,6 +112,17 @@ pub trait DEXManager<AccountId, CurrencyId, Balance> {
 		max_supply_amount: Balance,
 		price_impact_limit: Option<Ratio>,
 	) -> sp_std::result::Result<Balance, DispatchError>;
+
+	/// Add liquidity to the pool of the trading pair, the share increment is
+	/// deposited to the DEX incentives if `deposit_increment_share` is true.
+	fn add_liquidity(
+		who: &AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		deposit_increment_share: bool,
+	) -> DispatchResult;
 }
 
 impl<AccountId, CurrencyId, Balance> DEXManager<AccountId, CurrencyId, Balance> for ()
@@ -119,6 +133,10 @@ where
 		Default::default()
 	}
 
+	fn get_twap_price(_currency_id_a: CurrencyId, _currency_id_b: CurrencyId) -> Option<Price> {
+		None
+	}
+
 	fn get_swap_target_amount(
 		_path: &[CurrencyId],
 		_supply_amount: Balance,
@@ -154,6 +172,17 @@ where
 	) -> sp_std::result::Result<Balance, DispatchError> {
 		Ok(Default::default())
 	}
+
+	fn add_liquidity(
+		_who: &AccountId,
+		_currency_id_a: CurrencyId,
+		_currency_id_b: CurrencyId,
+		_max_amount_a: Balance,
+		_max_amount_b: Balance,
+		_deposit_increment_share: bool,
+	) -> DispatchResult {
+		Ok(())
+	}
 }
 
 /// An abstraction of cdp treasury for Honzon Protocol.
@@ -190,6 +219,9 @@ pub trait CDPTreasury<AccountId> {
 	/// deposit surplus(stable currency) to cdp treasury by `from`
 	fn deposit_surplus(from: &AccountId, surplus: Self::Balance) -> DispatchResult;
 
+	/// withdraw surplus(stable currency) of cdp treasury to `to`
+	fn withdraw_surplus(to: &AccountId, surplus: Self::Balance) -> DispatchResult;
+
 	/// deposit collateral assets to cdp treasury by `who`
 	fn deposit_collateral(from: &AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult;
 
@@ -221,11 +253,56 @@ pub trait CDPTreasuryExtended<AccountId>: CDPTreasury<AccountId> {
 	) -> DispatchResult;
 }
 
+/// An abstraction of Honzon loans for other protocols, e.g. EVM contracts.
+pub trait HonzonManager<AccountId, CurrencyId, Amount, Balance> {
+	/// Adjust the loan of `who` under `currency_id`
+	fn adjust_loan(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> DispatchResult;
+	/// Get the `(collateral, debit)` of the loan of `who` under
+	/// `currency_id`
+	fn get_position(who: &AccountId, currency_id: CurrencyId) -> (Balance, Balance);
+	/// Get the debit value of the loan of `who` under `currency_id`
+	fn get_debit_value(who: &AccountId, currency_id: CurrencyId) -> Balance;
+	/// Get the total debit value of all loans under `currency_id`
+	fn get_total_debit_value(currency_id: CurrencyId) -> Balance;
+	/// Check if `to` is `from` or has the authorization of `from` under
+	/// `currency_id`
+	fn check_authorization(from: &AccountId, to: &AccountId, currency_id: CurrencyId) -> DispatchResult;
+}
+
 pub trait PriceProvider<CurrencyId> {
 	fn get_relative_price(base: CurrencyId, quote: CurrencyId) -> Option<Price>;
 	fn get_price(currency_id: CurrencyId) -> Option<Price>;
 	fn lock_price(currency_id: CurrencyId);
 	fn unlock_price(currency_id: CurrencyId);
+
+	/// Whether the price is locked, the dependents should pause the risky
+	/// operations relying on the price.
+	fn is_price_locked(_currency_id: CurrencyId) -> bool {
+		false
+	}
+
+	/// The time weighted average price, resists the spikes of single
+	/// feeds. Defaults to the spot price.
+	fn get_twap_price(currency_id: CurrencyId) -> Option<Price> {
+		Self::get_price(currency_id)
+	}
+
+	/// The relative price between the time weighted average prices of two
+	/// currencies.
+	fn get_relative_twap_price(base: CurrencyId, quote: CurrencyId) -> Option<Price> {
+		Self::get_twap_price(base)?.checked_div(&Self::get_twap_price(quote)?)
+	}
+
+	/// The confidence interval of the price in proportion to it, i.e. the
+	/// relative max deviation of the real price. `None` if it's unknown.
+	fn get_price_confidence(_currency_id: CurrencyId) -> Option<Ratio> {
+		None
+	}
 }
 
 pub trait ExchangeRateProvider {
@@ -236,6 +313,12 @@ pub trait EmergencyShutdown {
 	fn is_shutdown() -> bool;
 }
 
+impl EmergencyShutdown for () {
+	fn is_shutdown() -> bool {
+		false
+	}
+}
+
 pub trait DEXIncentives<AccountId, CurrencyId, Balance> {
 	fn do_deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
 	fn do_withdraw_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
@@ -251,6 +334,28 @@ impl<AccountId, CurrencyId, Balance> DEXIncentives<AccountId, CurrencyId, Balanc
 	}
 }
 
+/// Transfer the native currency with the vesting schedule.
+pub trait VestedTransfer<AccountId, Balance, BlockNumber> {
+	/// Transfer `amount` from `from` to `to`, which is vested linearly over
+	/// `duration` blocks.
+	fn vested_transfer(from: &AccountId, to: &AccountId, amount: Balance, duration: BlockNumber) -> DispatchResult
+	where
+		BlockNumber: Zero,
+	{
+		Self::vested_transfer_with_cliff(from, to, amount, Zero::zero(), duration)
+	}
+
+	/// Transfer `amount` from `from` to `to`, which is locked for `cliff`
+	/// blocks and then vested linearly over `duration` blocks.
+	fn vested_transfer_with_cliff(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+		cliff: BlockNumber,
+		duration: BlockNumber,
+	) -> DispatchResult;
+}
+
 /// Return true if the call of EVM precompile contract is allowed.
 pub trait PrecompileCallerFilter {
 	fn is_allowed(caller: H160) -> bool;
@@ -324,6 +429,26 @@ pub trait EVMStateRentTrait<AccountId, Balance> {
 	fn transfer_maintainer(from: AccountId, contract: H160, new_maintainer: H160) -> DispatchResult;
 }
 
+/// The native balance held by the EVM addresses linked to an account, which
+/// is kept by different accounts on the Substrate side.
+pub trait LinkedEvmBalance<AccountId, Balance> {
+	/// The free native balance of the EVM addresses linked to `who`.
+	fn free_balance(who: &AccountId) -> Balance;
+	/// Move `amount` free native balance of the EVM addresses linked to `who`
+	/// to `who`.
+	fn pull(who: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+impl<AccountId, Balance: Default> LinkedEvmBalance<AccountId, Balance> for () {
+	fn free_balance(_who: &AccountId) -> Balance {
+		Default::default()
+	}
+
+	fn pull(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Ok(())
+	}
+}
+
 pub trait TransactionPayment<AccountId, Balance, NegativeImbalance> {
 	fn reserve_fee(who: &AccountId, weight: Weight) -> DispatchResult;
 	fn unreserve_and_charge_fee(
@@ -0,0 +1,52 @@
+//! Runtime API definition for transaction payment module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use frame_support::weights::{DispatchClass, Weight};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{FixedU128, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// The fee of a call, including the currency swap with DEX triggered by the
+/// fee payment.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeDetails<Balance, CurrencyId> {
+	/// Weight of the call.
+	pub weight: Weight,
+	/// Class of the call.
+	pub class: DispatchClass,
+	/// The inclusion fee in native currency after the fee discount, zero if
+	/// the call is free by the free transaction quota.
+	pub inclusion_fee: Balance,
+	/// The currency swapped with DEX to pay the fee, `None` if no swap is
+	/// needed or no currency can be swapped.
+	pub swap_currency_id: Option<CurrencyId>,
+	/// The amount of `swap_currency_id` supplied to the swap, including the
+	/// DEX conversion spread.
+	pub swap_supply_amount: Balance,
+	/// The amount of native currency targeted by the swap.
+	pub swap_target_amount: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait TransactionPaymentFeeApi<AccountId, Balance, CurrencyId> where
+		AccountId: Codec,
+		Balance: Codec,
+		CurrencyId: Codec,
+	{
+		/// The fee of the encoded call sent by `who` with the encoded extrinsic
+		/// length `len`, `None` if the call can't be decoded.
+		fn query_call_fee(who: AccountId, encoded_call: Vec<u8>, len: u32) -> Option<FeeDetails<Balance, CurrencyId>>;
+
+		/// The fee multiplier adjusted by the congestion of the chain, applied
+		/// to the weight fee of the transactions in the next block.
+		fn next_fee_multiplier() -> FixedU128;
+	}
+}
@@ -3,7 +3,57 @@
 //! ## Overview
 //!
 //! Transaction payment module is responsible for charge fee and tip in
-//! different currencies
+//! different currencies.
+//!
+//! If the native currency is not enough to pay the fee, the account can pay
+//! it by other currencies, e.g. the default fee token set by the account. The
+//! module swaps just enough native currency with DEX to pay the fee and keep
+//! the account alive, so that an account holding no native currency can
+//! still make transactions. The native balance held by the EVM addresses
+//! linked to the account, which is kept by different accounts on the
+//! Substrate side, is pulled to make up the shortfall in place of the native
+//! currency.
+//!
+//! The `FeeBurnRatio` share of the collected fees is moved to the fee burn
+//! account rather than `OnTransactionPayment`. Every `FeeBurnPeriod` blocks,
+//! the currencies held by the fee burn account are swapped to the native
+//! currency with DEX, and all the native currency of it is burned.
+//!
+//! The account with locked native currency gets a discount on the inclusion
+//! fee by the highest `FeeDiscountTiers` that it reaches. The tip is not
+//! discounted.
+//!
+//! The account with at least `FreeTransactionMinLocked` locked native
+//! currency can make `FreeTransactionQuota` transactions without tip for free
+//! every `FreeTransactionPeriod` blocks, the quota is renewed at the start of
+//! each period.
+//!
+//! The brand-new account arriving via a verified channel, e.g. the airdrop
+//! and the bridge deposit of a minimum amount, is onboarded by
+//! `OnboardNewAccount`. The existential deposit and the fees of the first
+//! few transactions without tip of the account are paid by the onboarding
+//! subsidy funded by governance, up to a cap per account. The number of the
+//! accounts onboarded per `OnboardingPeriod` is limited against sybil
+//! attacks.
+//!
+//! A sponsor can allow an account to make the calls wrapped by
+//! `with_fee_paid_by` with the fees paid by the sponsor, up to the spending
+//! cap set by the sponsor. The spending cap is consumed by the fee withdrawn
+//! before dispatch and restored by the refund after dispatch, and the
+//! account dispatching the call pays nothing. The sponsored call can not be
+//! tipped.
+//!
+//! The coefficients of the weight to fee polynomial can be updated by
+//! governance, so that the fee level can track the price of the native
+//! currency without runtime upgrade. The fees of the reference weights can
+//! change at most `MaxWeightToFeeStep` per update. `WeightToFee` is used
+//! until the coefficients are set.
+//!
+//! Governance can also set a fee multiplier for each dispatch class, e.g. to
+//! make the operational calls cheaper during an incident, without touching
+//! the weight to fee polynomial. The multiplier applies to the inclusion fee
+//! only, and is bounded by `MinDispatchClassFeeMultiplier` and
+//! `MaxDispatchClassFeeMultiplier`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -11,25 +61,33 @@
 use frame_support::{
 	dispatch::{DispatchResult, Dispatchable},
 	pallet_prelude::*,
-	traits::{Currency, ExistenceRequirement, Imbalance, OnUnbalanced, ReservableCurrency, WithdrawReasons},
-	weights::{DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, WeightToFeePolynomial},
+	traits::{
+		Currency, ExistenceRequirement, Happened, Imbalance, IsSubType, OnUnbalanced, ReservableCurrency,
+		WithdrawReasons,
+	},
+	transactional,
+	weights::{
+		extract_actual_weight, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, WeightToFeeCoefficient,
+		WeightToFeeCoefficients, WeightToFeePolynomial,
+	},
 };
 use frame_system::pallet_prelude::*;
+pub use module_transaction_payment_rpc_runtime_api::FeeDetails;
 use orml_traits::MultiCurrency;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{
 	traits::{
-		CheckedSub, Convert, DispatchInfoOf, PostDispatchInfoOf, SaturatedConversion, Saturating, SignedExtension,
-		UniqueSaturatedInto, Zero,
+		AccountIdConversion, CheckedSub, Convert, DispatchInfoOf, One, PostDispatchInfoOf, SaturatedConversion,
+		Saturating, SignedExtension, UniqueSaturatedInto, Zero,
 	},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	FixedPointNumber, FixedPointOperand, FixedU128, Perquintill,
+	FixedPointNumber, FixedPointOperand, FixedU128, ModuleId, Perbill, Perquintill, RuntimeDebug,
 };
 use sp_std::{prelude::*, vec};
-use support::{DEXManager, Ratio, TransactionPayment};
+use support::{DEXManager, LinkedEvmBalance, Ratio, TransactionPayment};
 
 mod default_weight;
 mod mock;
@@ -40,6 +98,51 @@ pub use module::*;
 pub trait WeightInfo {
 	fn on_finalize() -> Weight;
 	fn set_default_fee_token() -> Weight;
+	fn set_fee_burn_ratio() -> Weight;
+	fn burn_fees(c: u32) -> Weight;
+	fn set_fee_discount_tiers(c: u32) -> Weight;
+	fn set_sponsorship() -> Weight;
+	fn with_fee_paid_by() -> Weight;
+	fn set_weight_to_fee_coefficients() -> Weight;
+	fn set_dispatch_class_fee_multiplier() -> Weight;
+}
+
+/// The max number of the terms of the weight to fee polynomial.
+pub const MAX_WEIGHT_TO_FEE_COEFFICIENTS: usize = 4;
+
+/// A term of the weight to fee polynomial, see `WeightToFeeCoefficient`.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct FeeCoefficient {
+	/// The integral part of the coefficient.
+	pub coeff_integer: Balance,
+	/// The fractional part of the coefficient.
+	pub coeff_frac: Perbill,
+	/// Should the coefficient be subtracted.
+	pub negative: bool,
+	/// The degree/exponent of the term.
+	pub degree: u8,
+}
+
+/// The weight to fee polynomial by the coefficients updated by governance, or
+/// `WeightToFee` if not set.
+pub struct CurrentWeightToFee<T>(PhantomData<T>);
+impl<T: Config> WeightToFeePolynomial for CurrentWeightToFee<T> {
+	type Balance = PalletBalanceOf<T>;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		match Module::<T>::weight_to_fee_coefficients() {
+			Some(coefficients) => coefficients
+				.into_iter()
+				.map(|coefficient| WeightToFeeCoefficient {
+					coeff_integer: coefficient.coeff_integer.unique_saturated_into(),
+					coeff_frac: coefficient.coeff_frac,
+					negative: coefficient.negative,
+					degree: coefficient.degree,
+				})
+				.collect(),
+			None => T::WeightToFee::polynomial(),
+		}
+	}
 }
 
 /// Fee multiplier.
@@ -49,7 +152,8 @@ type PalletBalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_syste
 type NegativeImbalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 
-/// A struct to update the weight multiplier per block. It implements
+/// A struct to update the weight multiplier per block by a targeted block
+/// fullness controller with bounded adjustment. It implements
 /// `Convert<Multiplier, Multiplier>`, meaning that it can convert the
 /// previous multiplier to the next one. This should be called on
 /// `on_finalize` of a block, prior to potentially cleaning the weight data
@@ -63,21 +167,23 @@ type NegativeImbalanceOf<T> =
 ///     v = 0.00001
 ///     t1 = (v * diff)
 ///     t2 = (v * diff)^2 / 2
+///     d = max step per block
 /// then:
-///     next_multiplier = prev_multiplier * (1 + t1 + t2)
+///     next_multiplier = prev_multiplier * (1 + clamp(t1 + t2, -d, d))
 ///
-/// Where `(s', v)` must be given as the `Get` implementation of the `T`
-/// generic type. Moreover, `M` must provide the minimum allowed value for
-/// the multiplier. Note that a runtime should ensure with tests that the
-/// combination of this `M` and `V` is not such that the multiplier can drop
-/// to zero and never recover.
+/// and the next multiplier is clamped to `[min, max]`.
+///
+/// Where `(s', v, d, min, max)` must be given as the `Get` implementation of
+/// the `S`, `V`, `D`, `M` and `X` generic types. Note that a runtime should
+/// ensure with tests that the combination of this `M` and `V` is not such
+/// that the multiplier can drop to zero and never recover.
 ///
 /// note that `s'` is interpreted as a portion in the _normal transaction_
 /// capacity of the block. For example, given `s' == 0.25` and
 /// `AvailableBlockRatio = 0.75`, then the target fullness is _0.25 of the
 /// normal capacity_ and _0.1875 of the entire block_.
 ///
-/// This implementation implies the bound:
+/// Without the max step, this implementation implies the bound:
 /// - `v ≤ p / k * (s − s')`
 /// - or, solving for `p`: `p >= v * k * (s - s')`
 ///
@@ -94,65 +200,88 @@ type NegativeImbalanceOf<T> =
 /// p >= 0.234
 ///
 /// Meaning that fees can change by around ~23% per day, given extreme
-/// congestion.
+/// congestion. The max step `d` bounds the change of a single block
+/// regardless of `v`, so the fees can change at most `(1 + d)^k` over `k`
+/// blocks.
 ///
 /// More info can be found at:
 /// https://w3f-research.readthedocs.io/en/latest/polkadot/Token%20Economics.html
-pub struct TargetedFeeAdjustment<T, S, V, M>(sp_std::marker::PhantomData<(T, S, V, M)>);
+pub struct BoundedFeeAdjustment<T, S, V, D, M, X>(sp_std::marker::PhantomData<(T, S, V, D, M, X)>);
 
 /// Something that can convert the current multiplier to the next one.
 pub trait MultiplierUpdate: Convert<Multiplier, Multiplier> {
 	/// Minimum multiplier
 	fn min() -> Multiplier;
+	/// Maximum multiplier
+	fn max() -> Multiplier;
 	/// Target block saturation level
 	fn target() -> Perquintill;
 	/// Variability factor
 	fn variability() -> Multiplier;
+	/// Maximum change ratio of the multiplier per block
+	fn max_step() -> Multiplier;
 }
 
 impl MultiplierUpdate for () {
 	fn min() -> Multiplier {
 		Default::default()
 	}
+	fn max() -> Multiplier {
+		Default::default()
+	}
 	fn target() -> Perquintill {
 		Default::default()
 	}
 	fn variability() -> Multiplier {
 		Default::default()
 	}
+	fn max_step() -> Multiplier {
+		Default::default()
+	}
 }
 
-impl<T, S, V, M> MultiplierUpdate for TargetedFeeAdjustment<T, S, V, M>
+impl<T, S, V, D, M, X> MultiplierUpdate for BoundedFeeAdjustment<T, S, V, D, M, X>
 where
 	T: frame_system::Config,
 	S: Get<Perquintill>,
 	V: Get<Multiplier>,
+	D: Get<Multiplier>,
 	M: Get<Multiplier>,
+	X: Get<Multiplier>,
 {
 	fn min() -> Multiplier {
 		M::get()
 	}
+	fn max() -> Multiplier {
+		X::get()
+	}
 	fn target() -> Perquintill {
 		S::get()
 	}
 	fn variability() -> Multiplier {
 		V::get()
 	}
+	fn max_step() -> Multiplier {
+		D::get()
+	}
 }
 
-impl<T, S, V, M> Convert<Multiplier, Multiplier> for TargetedFeeAdjustment<T, S, V, M>
+impl<T, S, V, D, M, X> Convert<Multiplier, Multiplier> for BoundedFeeAdjustment<T, S, V, D, M, X>
 where
 	T: frame_system::Config,
 	S: Get<Perquintill>,
 	V: Get<Multiplier>,
+	D: Get<Multiplier>,
 	M: Get<Multiplier>,
+	X: Get<Multiplier>,
 {
 	fn convert(previous: Multiplier) -> Multiplier {
-		// Defensive only. The multiplier in storage should always be at most positive.
-		// Nonetheless we recover here in case of errors, because any value below this
-		// would be stale and can never change.
+		// Defensive only. The multiplier in storage should always be within the
+		// bounds. Nonetheless we recover here in case of errors, because any value
+		// below the min would be stale and can never change.
 		let min_multiplier = M::get();
-		let previous = previous.max(min_multiplier);
+		let max_multiplier = X::get().max(min_multiplier);
+		let previous = previous.max(min_multiplier).min(max_multiplier);
 
 		let weights = T::BlockWeights::get();
 		// the computed ratio is only among the normal class.
@@ -183,12 +312,20 @@ where
 		let first_term = v.saturating_mul(diff);
 		let second_term = v_squared_2.saturating_mul(diff_squared);
 
+		// the change ratio of the block is bounded by the max step.
+		let max_step = D::get();
 		if positive {
-			let excess = first_term.saturating_add(second_term).saturating_mul(previous);
-			previous.saturating_add(excess).max(min_multiplier)
+			let excess = first_term
+				.saturating_add(second_term)
+				.min(max_step)
+				.saturating_mul(previous);
+			previous.saturating_add(excess).min(max_multiplier)
 		} else {
 			// Defensive-only: first_term > second_term. Safe subtraction.
-			let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
+			let negative = first_term
+				.saturating_sub(second_term)
+				.min(max_step)
+				.saturating_mul(previous);
 			previous.saturating_sub(negative).max(min_multiplier)
 		}
 	}
@@ -200,6 +337,14 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The call dispatched with the fee paid by the sponsor.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
 		/// All non-native currency ids in Acala.
 		#[pallet::constant]
 		type AllNonNativeCurrencyIds: Get<Vec<CurrencyId>>;
@@ -239,10 +384,89 @@ pub mod module {
 		/// DEX to exchange currencies.
 		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
+		/// The native balance of the EVM addresses linked to the account,
+		/// pulled to pay the fee when the native balance is not enough.
+		type LinkedEvmBalance: LinkedEvmBalance<Self::AccountId, Balance>;
+
 		/// The max slippage allowed when swap fee with DEX
 		#[pallet::constant]
 		type MaxSlippageSwapWithDEX: Get<Ratio>;
 
+		/// The max ratio of the fee change of the reference weights per update
+		/// of the weight to fee coefficients.
+		#[pallet::constant]
+		type MaxWeightToFeeStep: Get<Ratio>;
+
+		/// The min fee multiplier of the dispatch classes.
+		#[pallet::constant]
+		type MinDispatchClassFeeMultiplier: Get<Multiplier>;
+
+		/// The max fee multiplier of the dispatch classes.
+		#[pallet::constant]
+		type MaxDispatchClassFeeMultiplier: Get<Multiplier>;
+
+		/// The multiplier of the final fee as the virtual tip of the
+		/// operational transactions to get the priority.
+		#[pallet::constant]
+		type OperationalFeeMultiplier: Get<u8>;
+
+		/// The fee burn module id, keep the fees to burn.
+		#[pallet::constant]
+		type FeeBurnModuleId: Get<ModuleId>;
+
+		/// The period in blocks to burn the fees.
+		#[pallet::constant]
+		type FeeBurnPeriod: Get<Self::BlockNumber>;
+
+		/// The locked native currency of the account to get the fee discount.
+		type NativeLockedBalance: Convert<Self::AccountId, Balance>;
+
+		/// The number of the free transactions per period of the eligible
+		/// account.
+		#[pallet::constant]
+		type FreeTransactionQuota: Get<u32>;
+
+		/// The period in blocks to renew the free transaction quota.
+		#[pallet::constant]
+		type FreeTransactionPeriod: Get<Self::BlockNumber>;
+
+		/// The min locked native currency for the account to be eligible for
+		/// the free transaction quota.
+		#[pallet::constant]
+		type FreeTransactionMinLocked: Get<Balance>;
+
+		/// The max number of the fee discount tiers.
+		#[pallet::constant]
+		type MaxFeeDiscountTiers: Get<u32>;
+
+		/// The onboarding subsidy module id, keep the subsidies funded by
+		/// governance for the new accounts.
+		#[pallet::constant]
+		type OnboardingSubsidyModuleId: Get<ModuleId>;
+
+		/// The number of the first transactions of the onboarded account
+		/// whose fees are subsidized.
+		#[pallet::constant]
+		type OnboardingSubsidizedTransactions: Get<u32>;
+
+		/// The max total subsidy of the onboarded account, including the
+		/// existential deposit.
+		#[pallet::constant]
+		type OnboardingSubsidyCap: Get<Balance>;
+
+		/// The max number of the accounts onboarded per onboarding period.
+		#[pallet::constant]
+		type MaxOnboardingsPerPeriod: Get<u32>;
+
+		/// The period in blocks to limit the number of the onboarded
+		/// accounts.
+		#[pallet::constant]
+		type OnboardingPeriod: Get<Self::BlockNumber>;
+
+		/// The origin which may update the fee burn ratio and the fee discount
+		/// tiers.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -260,14 +484,131 @@ pub mod module {
 	#[pallet::getter(fn default_fee_currency_id)]
 	pub type DefaultFeeCurrencyId<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, CurrencyId, OptionQuery>;
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The fee token is not a valid currency to pay fee
+		InvalidFeeToken,
+		/// The fee burn ratio is greater than one
+		InvalidFeeBurnRatio,
+		/// The thresholds of the fee discount tiers are not strictly
+		/// increasing, or the discount is greater than one
+		InvalidFeeDiscountTiers,
+		/// The number of the fee discount tiers exceeds the limit
+		TooManyFeeDiscountTiers,
+		/// The account is not sponsored by the sponsor
+		NotSponsored,
+		/// The weight to fee coefficients are empty or too many
+		InvalidWeightToFeeCoefficients,
+		/// The fee change of the new weight to fee coefficients exceeds the
+		/// max step
+		WeightToFeeStepExceeded,
+		/// The fee multiplier of the dispatch class is out of the bounds
+		InvalidDispatchClassFeeMultiplier,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The fee burn ratio updated. \[new_ratio\]
+		FeeBurnRatioUpdated(Ratio),
+		/// The currency of the fee burn account is swapped to native currency.
+		/// \[currency_id, supply_amount, target_amount\]
+		FeeSwapped(CurrencyId, Balance, Balance),
+		/// The native currency of the fee burn account is burned. \[amount\]
+		FeeBurned(Balance),
+		/// The fee discount tiers updated. \[new_tiers\]
+		FeeDiscountTiersUpdated(Vec<(Balance, Ratio)>),
+		/// The free transaction quota is consumed. \[who, remaining_quota\]
+		FreeTransactionUsed(T::AccountId, u32),
+		/// The sponsorship of the account updated, `None` means removed.
+		/// \[sponsor, who, spending_cap\]
+		SponsorshipUpdated(T::AccountId, T::AccountId, Option<Balance>),
+		/// The fee is paid by the sponsor. \[sponsor, who, fee\]
+		FeePaidBySponsor(T::AccountId, T::AccountId, Balance),
+		/// The weight to fee coefficients updated. \[new_coefficients\]
+		WeightToFeeCoefficientsUpdated(Vec<FeeCoefficient>),
+		/// The fee multiplier of the dispatch class updated. \[class,
+		/// new_multiplier\]
+		DispatchClassFeeMultiplierUpdated(DispatchClass, Multiplier),
+		/// The new account is onboarded with the existential deposit paid by
+		/// the onboarding subsidy. \[who, existential_deposit\]
+		AccountOnboarded(T::AccountId, Balance),
+		/// The fee of the onboarded account is paid by the onboarding subsidy.
+		/// \[who, remaining_transactions\]
+		OnboardingSubsidyUsed(T::AccountId, u32),
+	}
+
+	/// The share of the collected fees to burn.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_burn_ratio)]
+	pub type FeeBurnRatio<T: Config> = StorageValue<_, Ratio, ValueQuery>;
+
+	/// The total amount of the native currency burned from the fees.
+	#[pallet::storage]
+	#[pallet::getter(fn total_fee_burned)]
+	pub type TotalFeeBurned<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The fee discount tiers, the locked native currency threshold and the
+	/// discount of the inclusion fee, sorted by the thresholds.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_discount_tiers)]
+	pub type FeeDiscountTiers<T: Config> = StorageValue<_, Vec<(Balance, Ratio)>, ValueQuery>;
+
+	/// The used free transaction quota of the account, the period index and
+	/// the number of the free transactions made in the period.
+	#[pallet::storage]
+	#[pallet::getter(fn free_transaction_usages)]
+	pub type FreeTransactionUsages<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (T::BlockNumber, u32), OptionQuery>;
+
+	/// The remaining spending cap of the sponsor for the fees of the
+	/// sponsored account.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsorships)]
+	pub type Sponsorships<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, Balance, OptionQuery>;
+
+	/// The coefficients of the weight to fee polynomial updated by
+	/// governance.
+	#[pallet::storage]
+	#[pallet::getter(fn weight_to_fee_coefficients)]
+	pub type WeightToFeeCoefficientsOf<T: Config> = StorageValue<_, Vec<FeeCoefficient>, OptionQuery>;
+
+	/// The fee multiplier of the dispatch class, applied to the inclusion fee
+	/// on top of `NextFeeMultiplier`.
+	#[pallet::storage]
+	#[pallet::getter(fn dispatch_class_fee_multiplier)]
+	pub type DispatchClassFeeMultipliers<T: Config> =
+		StorageMap<_, Twox64Concat, DispatchClass, Multiplier, ValueQuery, DefaultFeeMultiplier>;
+
+	/// The remaining subsidized transactions and the remaining subsidy of
+	/// the onboarded account.
+	#[pallet::storage]
+	#[pallet::getter(fn onboarding_subsidies)]
+	pub type OnboardingSubsidies<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (u32, Balance), OptionQuery>;
+
+	/// The number of the accounts onboarded in the period, the period index
+	/// and the number.
+	#[pallet::storage]
+	#[pallet::getter(fn onboarding_count)]
+	pub type OnboardingCount<T: Config> = StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		/// `on_initialize` to return the weight used in `on_finalize`.
-		fn on_initialize(_: T::BlockNumber) -> Weight {
-			<T as Config>::WeightInfo::on_finalize()
+		/// `on_initialize` to return the weight used in `on_finalize`, and
+		/// burn the fees at the fee burn period.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight = <T as Config>::WeightInfo::on_finalize();
+			if (now % T::FeeBurnPeriod::get().max(One::one())).is_zero() {
+				Self::burn_fees();
+				weight = weight.saturating_add(<T as Config>::WeightInfo::burn_fees(
+					T::AllNonNativeCurrencyIds::get().len() as u32,
+				));
+			}
+			weight
 		}
 
 		fn on_finalize(_: T::BlockNumber) {
@@ -292,6 +633,10 @@ pub mod module {
 			// we assert that if we collapse to minimum, the trend will be positive with a
 			// weight value which is 1% more than the target.
 			let min_value = T::FeeMultiplierUpdate::min();
+			assert!(
+				min_value <= T::FeeMultiplierUpdate::max(),
+				"The minimum bound of the multiplier is greater than the maximum bound."
+			);
 			let mut target = T::FeeMultiplierUpdate::target()
 				* T::BlockWeights::get().get(DispatchClass::Normal).max_total.expect(
 					"Setting `max_total` for `Normal` dispatch class is not compatible with \
@@ -330,12 +675,368 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 
 			if let Some(currency_id) = fee_token {
+				ensure!(
+					currency_id == T::NativeCurrencyId::get()
+						|| T::AllNonNativeCurrencyIds::get().contains(&currency_id),
+					Error::<T>::InvalidFeeToken
+				);
 				DefaultFeeCurrencyId::<T>::insert(&who, currency_id);
 			} else {
 				DefaultFeeCurrencyId::<T>::remove(&who);
 			}
 			Ok(().into())
 		}
+
+		/// Update the share of the collected fees to burn.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `ratio`: the new fee burn ratio, must not be greater than one.
+		#[pallet::weight((<T as Config>::WeightInfo::set_fee_burn_ratio(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_fee_burn_ratio(origin: OriginFor<T>, ratio: Ratio) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(ratio <= Ratio::one(), Error::<T>::InvalidFeeBurnRatio);
+			FeeBurnRatio::<T>::put(ratio);
+			Self::deposit_event(Event::FeeBurnRatioUpdated(ratio));
+			Ok(().into())
+		}
+
+		/// Update the fee discount tiers.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `tiers`: the locked native currency thresholds and the discounts
+		///   of the inclusion fee, the thresholds must be strictly increasing.
+		#[pallet::weight((<T as Config>::WeightInfo::set_fee_discount_tiers(tiers.len() as u32), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_fee_discount_tiers(
+			origin: OriginFor<T>,
+			tiers: Vec<(Balance, Ratio)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				tiers.len() <= T::MaxFeeDiscountTiers::get() as usize,
+				Error::<T>::TooManyFeeDiscountTiers
+			);
+			ensure!(
+				tiers.iter().all(|(_, discount)| *discount <= Ratio::one())
+					&& tiers.windows(2).all(|pair| pair[0].0 < pair[1].0),
+				Error::<T>::InvalidFeeDiscountTiers
+			);
+			FeeDiscountTiers::<T>::put(tiers.clone());
+			Self::deposit_event(Event::FeeDiscountTiersUpdated(tiers));
+			Ok(().into())
+		}
+
+		/// Update the coefficients of the weight to fee polynomial.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `coefficients`: the new coefficients, the fees of the base
+		///   extrinsic weight and the max block weight can change at most
+		///   `MaxWeightToFeeStep`.
+		#[pallet::weight((<T as Config>::WeightInfo::set_weight_to_fee_coefficients(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_weight_to_fee_coefficients(
+			origin: OriginFor<T>,
+			coefficients: Vec<FeeCoefficient>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				!coefficients.is_empty() && coefficients.len() <= MAX_WEIGHT_TO_FEE_COEFFICIENTS,
+				Error::<T>::InvalidWeightToFeeCoefficients
+			);
+
+			let block_weights = T::BlockWeights::get();
+			let max_step = T::MaxWeightToFeeStep::get();
+			let reference_weights = [
+				block_weights.get(DispatchClass::Normal).base_extrinsic,
+				block_weights.max_block,
+			];
+			for weight in reference_weights.iter() {
+				let current_fee: Balance = CurrentWeightToFee::<T>::calc(weight).unique_saturated_into();
+				let new_fee = Self::calc_weight_to_fee(&coefficients, *weight);
+				let step = max_step.saturating_mul_int(current_fee);
+				ensure!(
+					new_fee <= current_fee.saturating_add(step) && new_fee >= current_fee.saturating_sub(step),
+					Error::<T>::WeightToFeeStepExceeded
+				);
+			}
+
+			WeightToFeeCoefficientsOf::<T>::put(coefficients.clone());
+			Self::deposit_event(Event::WeightToFeeCoefficientsUpdated(coefficients));
+			Ok(().into())
+		}
+
+		/// Update the fee multiplier of the dispatch class.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `class`: the dispatch class.
+		/// - `multiplier`: the new multiplier of the inclusion fee of the
+		///   class, one means no change to the fee. It must be within
+		///   `MinDispatchClassFeeMultiplier` and `MaxDispatchClassFeeMultiplier`.
+		#[pallet::weight((<T as Config>::WeightInfo::set_dispatch_class_fee_multiplier(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_dispatch_class_fee_multiplier(
+			origin: OriginFor<T>,
+			class: DispatchClass,
+			multiplier: Multiplier,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				multiplier >= T::MinDispatchClassFeeMultiplier::get()
+					&& multiplier <= T::MaxDispatchClassFeeMultiplier::get(),
+				Error::<T>::InvalidDispatchClassFeeMultiplier
+			);
+			if multiplier == DefaultFeeMultiplier::get() {
+				DispatchClassFeeMultipliers::<T>::remove(class);
+			} else {
+				DispatchClassFeeMultipliers::<T>::insert(class, multiplier);
+			}
+			Self::deposit_event(Event::DispatchClassFeeMultiplierUpdated(class, multiplier));
+			Ok(().into())
+		}
+
+		/// Sponsor the fees of the calls made by `who` with
+		/// `with_fee_paid_by`.
+		///
+		/// The dispatch origin of this call must be `Signed` by the sponsor.
+		///
+		/// - `who`: the sponsored account.
+		/// - `spending_cap`: the max amount of the fees to pay for `who`,
+		///   `None` means removing the sponsorship.
+		#[pallet::weight(<T as Config>::WeightInfo::set_sponsorship())]
+		#[transactional]
+		pub fn set_sponsorship(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			spending_cap: Option<Balance>,
+		) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+
+			if let Some(cap) = spending_cap {
+				Sponsorships::<T>::insert(&sponsor, &who, cap);
+			} else {
+				Sponsorships::<T>::remove(&sponsor, &who);
+			}
+			Self::deposit_event(Event::SponsorshipUpdated(sponsor, who, spending_cap));
+			Ok(().into())
+		}
+
+		/// Dispatch `call` with the caller as the signed origin, the fee of
+		/// this extrinsic is paid by `sponsor` rather than the caller.
+		///
+		/// The dispatch origin of this call must be `Signed` by the account
+		/// sponsored by `sponsor`.
+		///
+		/// - `sponsor`: the sponsor to pay the fee.
+		/// - `call`: the call to dispatch.
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(<T as Config>::WeightInfo::with_fee_paid_by().saturating_add(dispatch_info.weight), dispatch_info.class)
+		})]
+		pub fn with_fee_paid_by(
+			origin: OriginFor<T>,
+			sponsor: T::AccountId,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Sponsorships::<T>::contains_key(&sponsor, &who),
+				Error::<T>::NotSponsored
+			);
+
+			let dispatch_info = call.get_dispatch_info();
+			let post_info = call
+				.dispatch(frame_system::RawOrigin::Signed(who).into())
+				.map_err(|e| e.error)?;
+
+			Ok(Some(
+				<T as Config>::WeightInfo::with_fee_paid_by()
+					.saturating_add(extract_actual_weight(&post_info, &dispatch_info)),
+			)
+			.into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Calculate the fee of the weight by the weight to fee coefficients, the
+	/// same as `WeightToFeePolynomial::calc`.
+	fn calc_weight_to_fee(coefficients: &[FeeCoefficient], weight: Weight) -> Balance {
+		coefficients.iter().fold(Zero::zero(), |acc: Balance, coefficient| {
+			let w = Balance::from(weight).saturating_pow(coefficient.degree.into());
+			let frac = coefficient.coeff_frac * w;
+			let integer = coefficient.coeff_integer.saturating_mul(w);
+			if coefficient.negative {
+				acc.saturating_sub(frac).saturating_sub(integer)
+			} else {
+				acc.saturating_add(frac).saturating_add(integer)
+			}
+		})
+	}
+
+	/// The remaining free transaction quota of the account in the current
+	/// period, zero if the account is not eligible.
+	pub fn free_transaction_remaining(who: &T::AccountId) -> u32 {
+		if T::NativeLockedBalance::convert(who.clone()) < T::FreeTransactionMinLocked::get() {
+			return 0;
+		}
+
+		let period_index = Self::free_transaction_period_index();
+		let used = match Self::free_transaction_usages(who) {
+			Some((index, used)) if index == period_index => used,
+			_ => 0,
+		};
+		T::FreeTransactionQuota::get().saturating_sub(used)
+	}
+
+	/// Consume one free transaction quota of the account, return false if
+	/// there's no quota remaining.
+	fn try_consume_free_transaction(who: &T::AccountId) -> bool {
+		let remaining = Self::free_transaction_remaining(who);
+		if remaining.is_zero() {
+			return false;
+		}
+
+		let used = T::FreeTransactionQuota::get().saturating_sub(remaining);
+		FreeTransactionUsages::<T>::insert(who, (Self::free_transaction_period_index(), used.saturating_add(1)));
+		Self::deposit_event(Event::FreeTransactionUsed(who.clone(), remaining.saturating_sub(1)));
+		true
+	}
+
+	fn free_transaction_period_index() -> T::BlockNumber {
+		<frame_system::Module<T>>::block_number() / T::FreeTransactionPeriod::get().max(One::one())
+	}
+
+	/// The account to keep the fees to burn.
+	pub fn fee_burn_account_id() -> T::AccountId {
+		T::FeeBurnModuleId::get().into_account()
+	}
+
+	/// The account to keep the onboarding subsidy.
+	pub fn onboarding_subsidy_account_id() -> T::AccountId {
+		T::OnboardingSubsidyModuleId::get().into_account()
+	}
+
+	/// Onboard the brand-new account arriving via a verified channel, i.e.
+	/// the account has never made a transaction and holds no native
+	/// currency. The existential deposit and the fees of the first
+	/// `OnboardingSubsidizedTransactions` transactions of the account are
+	/// paid by the onboarding subsidy, up to `OnboardingSubsidyCap` in total.
+	///
+	/// Return false if the account is not brand-new, the onboarding rate limit
+	/// of the period is reached or the subsidy is not enough.
+	pub fn onboard(who: &T::AccountId) -> bool {
+		if OnboardingSubsidies::<T>::contains_key(who)
+			|| !<frame_system::Module<T>>::account_nonce(who).is_zero()
+			|| !<T as Config>::Currency::total_balance(who).is_zero()
+		{
+			return false;
+		}
+
+		let period_index = <frame_system::Module<T>>::block_number() / T::OnboardingPeriod::get().max(One::one());
+		let onboarded = match Self::onboarding_count() {
+			(index, count) if index == period_index => count,
+			_ => 0,
+		};
+		if onboarded >= T::MaxOnboardingsPerPeriod::get() {
+			return false;
+		}
+
+		let existential_deposit = <T as Config>::Currency::minimum_balance();
+		let remaining_subsidy =
+			match T::OnboardingSubsidyCap::get().checked_sub(existential_deposit.unique_saturated_into()) {
+				Some(remaining_subsidy) => remaining_subsidy,
+				None => return false,
+			};
+		if !existential_deposit.is_zero()
+			&& <T as Config>::Currency::transfer(
+				&Self::onboarding_subsidy_account_id(),
+				who,
+				existential_deposit,
+				ExistenceRequirement::KeepAlive,
+			)
+			.is_err()
+		{
+			return false;
+		}
+
+		OnboardingSubsidies::<T>::insert(who, (T::OnboardingSubsidizedTransactions::get(), remaining_subsidy));
+		OnboardingCount::<T>::put((period_index, onboarded.saturating_add(1)));
+		Self::deposit_event(Event::AccountOnboarded(
+			who.clone(),
+			existential_deposit.unique_saturated_into(),
+		));
+		true
+	}
+
+	/// Consume one subsidized transaction of the onboarded account for the
+	/// `fee` paid by the onboarding subsidy.
+	fn consume_onboarding_subsidy(who: &T::AccountId, fee: Balance) {
+		OnboardingSubsidies::<T>::mutate_exists(who, |maybe_subsidy| {
+			if let Some((transactions, subsidy)) = maybe_subsidy.take() {
+				let transactions = transactions.saturating_sub(1);
+				if !transactions.is_zero() {
+					*maybe_subsidy = Some((transactions, subsidy.saturating_sub(fee)));
+				}
+				Self::deposit_event(Event::OnboardingSubsidyUsed(who.clone(), transactions));
+			}
+		});
+	}
+
+	/// Whether the onboarded account has subsidy remaining for `fee`.
+	fn has_onboarding_subsidy(who: &T::AccountId, fee: Balance) -> bool {
+		Self::onboarding_subsidies(who).map_or(false, |(transactions, subsidy)| {
+			!transactions.is_zero() && subsidy >= fee
+		})
+	}
+
+	/// The trading path to swap the currency to native currency with DEX.
+	fn fee_swap_path(currency_id: CurrencyId) -> Vec<CurrencyId> {
+		let native_currency_id = T::NativeCurrencyId::get();
+		let stable_currency_id = T::StableCurrencyId::get();
+		if currency_id == stable_currency_id {
+			vec![stable_currency_id, native_currency_id]
+		} else {
+			vec![currency_id, stable_currency_id, native_currency_id]
+		}
+	}
+
+	/// Swap the currencies of the fee burn account to native currency with
+	/// DEX, and burn all the native currency of the fee burn account.
+	fn burn_fees() {
+		let burn_account = Self::fee_burn_account_id();
+		let price_impact_limit = Some(T::MaxSlippageSwapWithDEX::get());
+
+		for currency_id in T::AllNonNativeCurrencyIds::get() {
+			let supply_amount = <T as Config>::MultiCurrency::free_balance(currency_id, &burn_account);
+			if supply_amount.is_zero() {
+				continue;
+			}
+
+			// the currency that cannot be swapped is kept until the next period
+			if let Ok(target_amount) = T::DEX::swap_with_exact_supply(
+				&burn_account,
+				&Self::fee_swap_path(currency_id),
+				supply_amount,
+				Zero::zero(),
+				price_impact_limit,
+			) {
+				Self::deposit_event(Event::FeeSwapped(currency_id, supply_amount, target_amount));
+			}
+		}
+
+		let burn_amount = <T as Config>::Currency::free_balance(&burn_account);
+		if !burn_amount.is_zero() {
+			// drop the imbalance to reduce the total issuance
+			let (burned, _) = <T as Config>::Currency::slash(&burn_account, burn_amount);
+			let burned: Balance = burned.peek().unique_saturated_into();
+			TotalFeeBurned::<T>::mutate(|total| *total = total.saturating_add(burned));
+			Self::deposit_event(Event::FeeBurned(burned));
+		}
 	}
 }
 
@@ -392,6 +1093,8 @@ where
 	///     transaction.
 	///   - `targeted_fee_adjustment`: This is a multiplier that can tune the
 	///     final fee based on the congestion of the network.
+	///   - `class_fee_multiplier`: The multiplier of the dispatch class set by
+	///     governance.
 	///   - (Optional) `tip`: If included in the transaction, the tip will be
 	///     added on top. Only signed transactions can have a tip.
 	///
@@ -400,7 +1103,7 @@ where
 	/// included in a block.
 	///
 	/// ```ignore
-	/// inclusion_fee = base_fee + len_fee + [targeted_fee_adjustment * weight_fee];
+	/// inclusion_fee = class_fee_multiplier * (base_fee + len_fee + [targeted_fee_adjustment * weight_fee]);
 	/// final_fee = inclusion_fee + tip;
 	/// ```
 	pub fn compute_fee(
@@ -457,9 +1160,13 @@ where
 			let adjusted_weight_fee = multiplier.saturating_mul_int(unadjusted_weight_fee);
 
 			let base_fee = Self::weight_to_fee(T::BlockWeights::get().get(class).base_extrinsic);
-			base_fee
+			let inclusion_fee = base_fee
 				.saturating_add(fixed_len_fee)
-				.saturating_add(adjusted_weight_fee)
+				.saturating_add(adjusted_weight_fee);
+
+			// the fee multiplier of the dispatch class, the tip is not multiplied.
+			Self::dispatch_class_fee_multiplier(class)
+				.saturating_mul_int(inclusion_fee)
 				.saturating_add(tip)
 		} else {
 			tip
@@ -470,12 +1177,51 @@ where
 		// cap the weight to the maximum defined in runtime, otherwise it will be the
 		// `Bounded` maximum of its data type, which is not desired.
 		let capped_weight = weight.min(T::BlockWeights::get().max_block);
-		T::WeightToFee::calc(&capped_weight)
+		CurrentWeightToFee::<T>::calc(&capped_weight)
 	}
 
-	fn ensure_can_charge_fee(who: &T::AccountId, fee: PalletBalanceOf<T>, reason: WithdrawReasons) {
+	/// The fee discount of the account by the highest tier its locked native
+	/// currency reaches.
+	pub fn fee_discount(who: &T::AccountId) -> Ratio {
+		let locked = T::NativeLockedBalance::convert(who.clone());
+		Self::fee_discount_tiers()
+			.into_iter()
+			.rev()
+			.find(|(threshold, _)| locked >= *threshold)
+			.map_or_else(Ratio::zero, |(_, discount)| discount)
+	}
+
+	/// Apply the fee discount of the account to the inclusion fee of `fee`,
+	/// the `tip` is not discounted.
+	pub fn apply_fee_discount(
+		who: &T::AccountId,
+		fee: PalletBalanceOf<T>,
+		tip: PalletBalanceOf<T>,
+	) -> PalletBalanceOf<T> {
+		let discount = Self::fee_discount(who);
+		if discount.is_zero() {
+			return fee;
+		}
+
+		let inclusion_fee = fee.saturating_sub(tip);
+		fee.saturating_sub(discount.saturating_mul_int(inclusion_fee))
+	}
+
+	/// Move the `FeeBurnRatio` share of the fee to the fee burn account, and
+	/// distribute the rest of the fee and the tip by `OnTransactionPayment`.
+	fn distribute_fee(fee: NegativeImbalanceOf<T>, tip: NegativeImbalanceOf<T>) {
+		let burn_amount = Self::fee_burn_ratio().saturating_mul_int(fee.peek());
+		let (burn, fee) = fee.split(burn_amount);
+		if !burn.peek().is_zero() {
+			<T as Config>::Currency::resolve_creating(&Self::fee_burn_account_id(), burn);
+		}
+
+		<T as Config>::OnTransactionPayment::on_unbalanceds(Some(fee).into_iter().chain(Some(tip)));
+	}
+
+	/// The order of the currencies to charge the fee of the account.
+	fn charge_fee_order(who: &T::AccountId) -> Vec<CurrencyId> {
 		let native_currency_id = T::NativeCurrencyId::get();
-		let stable_currency_id = T::StableCurrencyId::get();
 		let other_currency_ids = T::AllNonNativeCurrencyIds::get();
 		let mut charge_fee_order: Vec<CurrencyId> =
 			if let Some(default_fee_currency_id) = DefaultFeeCurrencyId::<T>::get(who) {
@@ -484,35 +1230,66 @@ where
 				vec![vec![native_currency_id], other_currency_ids].concat()
 			};
 		charge_fee_order.dedup();
+		charge_fee_order
+	}
 
+	/// The amount of native currency to swap to pay the fee.
+	///
+	/// Swap just enough native currency to pay the fee with the spendable
+	/// native currency, i.e. the free balance above the locks and the
+	/// existential deposit.
+	fn fee_swap_target_amount(who: &T::AccountId, fee: PalletBalanceOf<T>) -> PalletBalanceOf<T> {
+		let locked: PalletBalanceOf<T> = T::NativeLockedBalance::convert(who.clone()).unique_saturated_into();
+		let spendable = <T as Config>::Currency::free_balance(who)
+			.saturating_sub(locked.max(<T as Config>::Currency::minimum_balance()));
+		let shortfall = fee.saturating_sub(spendable);
+		if shortfall.is_zero() {
+			fee
+		} else {
+			shortfall
+		}
+	}
+
+	fn native_is_enough(who: &T::AccountId, fee: PalletBalanceOf<T>, reason: WithdrawReasons) -> bool {
+		<T as Config>::Currency::free_balance(who)
+			.checked_sub(&fee)
+			.map_or(false, |new_free_balance| {
+				<T as Config>::Currency::ensure_can_withdraw(who, fee, reason, new_free_balance).is_ok()
+			})
+	}
+
+	/// Whether the native balance of the linked EVM addresses is enough to
+	/// make up the shortfall of the native currency.
+	fn linked_evm_balance_is_enough(who: &T::AccountId, shortfall: PalletBalanceOf<T>) -> bool {
+		!shortfall.is_zero() && T::LinkedEvmBalance::free_balance(who) >= shortfall.unique_saturated_into()
+	}
+
+	fn ensure_can_charge_fee(who: &T::AccountId, fee: PalletBalanceOf<T>, reason: WithdrawReasons) {
+		let native_currency_id = T::NativeCurrencyId::get();
 		let price_impact_limit = Some(T::MaxSlippageSwapWithDEX::get());
+		let swap_target_amount = Self::fee_swap_target_amount(who, fee);
 
 		// iterator charge fee order to get enough fee
-		for currency_id in charge_fee_order {
+		for currency_id in Self::charge_fee_order(who) {
 			if currency_id == native_currency_id {
-				// check native balance if is enough
-				let native_is_enough =
-					<T as Config>::Currency::free_balance(who)
-						.checked_sub(&fee)
-						.map_or(false, |new_free_balance| {
-							<T as Config>::Currency::ensure_can_withdraw(who, fee, reason, new_free_balance).is_ok()
-						});
-				if native_is_enough {
+				if Self::native_is_enough(who, fee, reason) {
 					// native balance is enough, break iteration
 					break;
 				}
+
+				// pull the shortfall from the native balance of the linked EVM addresses
+				if Self::linked_evm_balance_is_enough(who, swap_target_amount)
+					&& T::LinkedEvmBalance::pull(who, swap_target_amount.unique_saturated_into()).is_ok()
+				{
+					break;
+				}
 			} else {
 				// try to use non-native currency to swap native currency by exchange with DEX
-				let trading_path = if currency_id == stable_currency_id {
-					vec![stable_currency_id, native_currency_id]
-				} else {
-					vec![currency_id, stable_currency_id, native_currency_id]
-				};
-
+				let trading_path = Self::fee_swap_path(currency_id);
 				if T::DEX::swap_with_exact_target(
 					who,
 					&trading_path,
-					fee.unique_saturated_into(),
+					swap_target_amount.unique_saturated_into(),
 					<T as Config>::MultiCurrency::free_balance(currency_id, who),
 					price_impact_limit,
 				)
@@ -524,6 +1301,78 @@ where
 			}
 		}
 	}
+
+	/// Query the fee of the `call` sent by `who`, including the currency swap
+	/// with DEX the fee payment would trigger, without changing the state.
+	///
+	/// The tip is not included, and the fee discount, the sponsorship, the
+	/// free transaction quota and the onboarding subsidy are applied as the
+	/// actual fee payment does.
+	pub fn query_call_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		len: u32,
+	) -> FeeDetails<PalletBalanceOf<T>, CurrencyId>
+	where
+		<T as frame_system::Config>::Call: Dispatchable<Info = DispatchInfo> + IsSubType<Call<T>>,
+	{
+		let dispatch_info = call.get_dispatch_info();
+		let sponsor = match call.is_sub_type() {
+			Some(Call::with_fee_paid_by(sponsor, _)) => Some(sponsor.clone()),
+			_ => None,
+		};
+		let payer = sponsor.clone().unwrap_or_else(|| who.clone());
+		let mut fee = Self::apply_fee_discount(
+			&payer,
+			Self::compute_fee(len, &dispatch_info, Zero::zero()),
+			Zero::zero(),
+		);
+		if sponsor.is_none()
+			&& (Self::free_transaction_remaining(who) > 0
+				|| Self::has_onboarding_subsidy(who, fee.unique_saturated_into()))
+		{
+			fee = Zero::zero();
+		}
+
+		let mut fee_details = FeeDetails {
+			weight: dispatch_info.weight,
+			class: dispatch_info.class,
+			inclusion_fee: fee,
+			swap_currency_id: None,
+			swap_supply_amount: Zero::zero(),
+			swap_target_amount: Zero::zero(),
+		};
+		if fee.is_zero() {
+			return fee_details;
+		}
+
+		let native_currency_id = T::NativeCurrencyId::get();
+		let price_impact_limit = Some(T::MaxSlippageSwapWithDEX::get());
+		let swap_target_amount = Self::fee_swap_target_amount(&payer, fee);
+
+		for currency_id in Self::charge_fee_order(&payer) {
+			if currency_id == native_currency_id {
+				if Self::native_is_enough(&payer, fee, WithdrawReasons::TRANSACTION_PAYMENT)
+					|| Self::linked_evm_balance_is_enough(&payer, swap_target_amount)
+				{
+					break;
+				}
+			} else if let Some(supply_amount) = T::DEX::get_swap_supply_amount(
+				&Self::fee_swap_path(currency_id),
+				swap_target_amount.unique_saturated_into(),
+				price_impact_limit,
+			)
+			.filter(|supply_amount| *supply_amount <= <T as Config>::MultiCurrency::free_balance(currency_id, &payer))
+			{
+				fee_details.swap_currency_id = Some(currency_id);
+				fee_details.swap_supply_amount = supply_amount.unique_saturated_into();
+				fee_details.swap_target_amount = swap_target_amount;
+				break;
+			}
+		}
+
+		fee_details
+	}
 }
 
 impl<T> Convert<Weight, PalletBalanceOf<T>> for Pallet<T>
@@ -542,6 +1391,15 @@ where
 	}
 }
 
+/// Onboard the new account arriving via the verified channel with the
+/// onboarding subsidy.
+pub struct OnboardNewAccount<T>(PhantomData<T>);
+impl<T: Config> Happened<T::AccountId> for OnboardNewAccount<T> {
+	fn happened(who: &T::AccountId) {
+		let _ = Pallet::<T>::onboard(who);
+	}
+}
+
 /// Require the transactor pay for themselves and maybe include a tip to
 /// gain additional priority in the queue.
 #[derive(Encode, Decode, Clone, Eq, PartialEq)]
@@ -560,7 +1418,8 @@ impl<T: Config + Send + Sync> sp_std::fmt::Debug for ChargeTransactionPayment<T>
 
 impl<T: Config + Send + Sync> ChargeTransactionPayment<T>
 where
-	<T as frame_system::Config>::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	<T as frame_system::Config>::Call:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<Call<T>>,
 	PalletBalanceOf<T>: Send + Sync + FixedPointOperand,
 {
 	/// utility constructor. Used only in client/factory code.
@@ -568,15 +1427,49 @@ where
 		Self(fee)
 	}
 
+	/// Withdraw the fee of the transaction, return the account paid the fee,
+	/// which is the sponsor for the sponsored call.
 	fn withdraw_fee(
 		&self,
 		who: &T::AccountId,
-		_call: &<T as frame_system::Config>::Call,
+		call: &<T as frame_system::Config>::Call,
 		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
 		len: usize,
-	) -> Result<(PalletBalanceOf<T>, Option<NegativeImbalanceOf<T>>), TransactionValidityError> {
+	) -> Result<(T::AccountId, PalletBalanceOf<T>, Option<NegativeImbalanceOf<T>>), TransactionValidityError> {
 		let tip = self.0;
-		let fee = Module::<T>::compute_fee(len as u32, info, tip);
+		let sponsor = match call.is_sub_type() {
+			Some(Call::with_fee_paid_by(sponsor, _)) => Some(sponsor.clone()),
+			_ => None,
+		};
+		let payer = sponsor.clone().unwrap_or_else(|| who.clone());
+		let fee = Module::<T>::apply_fee_discount(&payer, Module::<T>::compute_fee(len as u32, info, tip), tip);
+
+		if let Some(sponsor) = &sponsor {
+			// the sponsor never pays the tip
+			if !tip.is_zero() {
+				return Err(InvalidTransaction::Payment.into());
+			}
+			// the fee must be within the spending cap of the sponsor
+			let spending_cap = Module::<T>::sponsorships(sponsor, who).ok_or(InvalidTransaction::Payment)?;
+			if spending_cap < fee.unique_saturated_into() {
+				return Err(InvalidTransaction::Payment.into());
+			}
+		} else if !fee.is_zero() && tip.is_zero() && Module::<T>::try_consume_free_transaction(who) {
+			// the transaction without tip is free if there's free transaction quota
+			return Ok((payer, Zero::zero(), None));
+		}
+
+		// the fee of the transaction without tip of the onboarded account is paid by the
+		// onboarding subsidy
+		let subsidized = sponsor.is_none()
+			&& !fee.is_zero()
+			&& tip.is_zero()
+			&& Module::<T>::has_onboarding_subsidy(who, fee.unique_saturated_into());
+		let payer = if subsidized {
+			Module::<T>::onboarding_subsidy_account_id()
+		} else {
+			payer
+		};
 
 		let reason = if tip.is_zero() {
 			WithdrawReasons::TRANSACTION_PAYMENT
@@ -584,48 +1477,82 @@ where
 			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
 		};
 
-		Module::<T>::ensure_can_charge_fee(who, fee, reason);
+		Module::<T>::ensure_can_charge_fee(&payer, fee, reason);
 
 		// withdraw native currency as fee
-		match <T as Config>::Currency::withdraw(who, fee, reason, ExistenceRequirement::KeepAlive) {
-			Ok(imbalance) => Ok((fee, Some(imbalance))),
-			Err(_) => Err(InvalidTransaction::Payment.into()),
+		let imbalance = <T as Config>::Currency::withdraw(&payer, fee, reason, ExistenceRequirement::KeepAlive)
+			.map_err(|_| InvalidTransaction::Payment)?;
+
+		if let Some(sponsor) = sponsor {
+			let fee: Balance = fee.unique_saturated_into();
+			Sponsorships::<T>::mutate_exists(&sponsor, who, |maybe_cap| {
+				if let Some(cap) = maybe_cap {
+					*cap = cap.saturating_sub(fee);
+				}
+			});
+			Module::<T>::deposit_event(Event::FeePaidBySponsor(sponsor, who.clone(), fee));
+		}
+		if subsidized {
+			Module::<T>::consume_onboarding_subsidy(who, fee.unique_saturated_into());
 		}
+
+		Ok((payer, fee, Some(imbalance)))
 	}
 
-	/// Get an appropriate priority for a transaction with the given length
-	/// and info.
+	/// Get an appropriate priority for a transaction with the given dispatch
+	/// info, encoded length, tip and final fee.
 	///
-	/// This will try and optimise the `fee/weight` `fee/length`, whichever
-	/// is consuming more of the maximum corresponding limit.
+	/// The priority is based on the tip per unit of the limiting resource
+	/// (either weight or length) of the block, i.e. the tip is scaled by the
+	/// number of such transactions that fit in an empty block. The
+	/// transaction which consumes more resources with the same tip ends up
+	/// having lower priority.
 	///
-	/// For example, if a transaction consumed 1/4th of the block length and
-	/// half of the weight, its final priority is `fee * min(2, 4) = fee *
-	/// 2`. If it consumed `1/4th` of the block length and the entire block
-	/// weight `(1/1)`, its priority is `fee * min(1, 4) = fee * 1`. This
-	/// means  that the transaction which consumes more resources (either
-	/// length or weight) with the same `fee` ends up having lower priority.
+	/// The operational transaction additionally gets a virtual tip of
+	/// `final_fee * OperationalFeeMultiplier`, so that the operational
+	/// transaction, e.g. liquidation and emergency shutdown, can outbid the
+	/// normal transactions with a tip during congestion.
 	fn get_priority(
-		len: usize,
 		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		len: usize,
+		tip: PalletBalanceOf<T>,
 		final_fee: PalletBalanceOf<T>,
 	) -> TransactionPriority {
-		let weight_saturation = T::BlockWeights::get().max_block / info.weight.max(1);
-		let max_block_length = *T::BlockLength::get().max.get(DispatchClass::Normal);
-		let len_saturation = max_block_length as u64 / (len as u64).max(1);
-		let coefficient: PalletBalanceOf<T> = weight_saturation
-			.min(len_saturation)
-			.saturated_into::<PalletBalanceOf<T>>();
-		final_fee
-			.saturating_mul(coefficient)
-			.saturated_into::<TransactionPriority>()
+		// calculate how many such transactions could fit into an empty block and take
+		// the limiting factor.
+		let max_block_weight = T::BlockWeights::get().max_block;
+		let max_block_length = *T::BlockLength::get().max.get(info.class) as u64;
+
+		let bounded_weight = info.weight.max(1).min(max_block_weight);
+		let bounded_length = (len as u64).max(1).min(max_block_length);
+
+		let max_tx_per_block_weight = max_block_weight / bounded_weight;
+		let max_tx_per_block_length = max_block_length / bounded_length;
+		let max_tx_per_block: PalletBalanceOf<T> =
+			max_tx_per_block_length.min(max_tx_per_block_weight).saturated_into();
+		let max_reward = |val: PalletBalanceOf<T>| val.saturating_mul(max_tx_per_block);
+
+		// distribute the transactions without tip a little bit by increasing the tip
+		// by one.
+		let scaled_tip = max_reward(tip.saturating_add(One::one()));
+
+		match info.class {
+			DispatchClass::Normal | DispatchClass::Mandatory => scaled_tip,
+			DispatchClass::Operational => {
+				let fee_multiplier: PalletBalanceOf<T> = T::OperationalFeeMultiplier::get().saturated_into();
+				let virtual_tip = final_fee.saturating_mul(fee_multiplier);
+				scaled_tip.saturating_add(max_reward(virtual_tip))
+			}
+		}
+		.saturated_into::<TransactionPriority>()
 	}
 }
 
 impl<T: Config + Send + Sync> SignedExtension for ChargeTransactionPayment<T>
 where
 	PalletBalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
-	<T as frame_system::Config>::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	<T as frame_system::Config>::Call:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<Call<T>>,
 {
 	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
 	type AccountId = T::AccountId;
@@ -636,6 +1563,7 @@ where
 		Self::AccountId,
 		Option<NegativeImbalanceOf<T>>,
 		PalletBalanceOf<T>,
+		Option<Self::AccountId>,
 	);
 
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
@@ -649,9 +1577,9 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
+		let (_, fee, _) = self.withdraw_fee(who, call, info, len)?;
 		Ok(ValidTransaction {
-			priority: Self::get_priority(len, info, fee),
+			priority: Self::get_priority(info, len, self.0, fee),
 			..Default::default()
 		})
 	}
@@ -663,8 +1591,13 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.0, who.clone(), imbalance, fee))
+		let (payer, fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
+		// the sponsored account, whose spending cap is restored by the refund
+		let sponsored = match call.is_sub_type() {
+			Some(Call::with_fee_paid_by(..)) => Some(who.clone()),
+			_ => None,
+		};
+		Ok((self.0, payer, imbalance, fee, sponsored))
 	}
 
 	fn post_dispatch(
@@ -674,29 +1607,40 @@ where
 		len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		let (tip, who, imbalance, fee) = pre;
+		let (tip, who, imbalance, fee, sponsored) = pre;
 		if let Some(payed) = imbalance {
-			let actual_fee = Module::<T>::compute_actual_fee(len as u32, info, post_info, tip);
+			let actual_fee = Module::<T>::apply_fee_discount(
+				&who,
+				Module::<T>::compute_actual_fee(len as u32, info, post_info, tip),
+				tip,
+			);
 			let refund = fee.saturating_sub(actual_fee);
 			let actual_payment = match <T as Config>::Currency::deposit_into_existing(&who, refund) {
 				Ok(refund_imbalance) => {
 					// The refund cannot be larger than the up front payed max weight.
 					// `PostDispatchInfo::calc_unspent` guards against such a case.
-					match payed.offset(refund_imbalance) {
+					let actual_payment = match payed.offset(refund_imbalance) {
 						Ok(actual_payment) => actual_payment,
 						Err(_) => return Err(InvalidTransaction::Payment.into()),
+					};
+					if let Some(sponsored) = sponsored {
+						let refund: Balance = refund.unique_saturated_into();
+						Sponsorships::<T>::mutate_exists(&who, &sponsored, |maybe_cap| {
+							if let Some(cap) = maybe_cap {
+								*cap = cap.saturating_add(refund);
+							}
+						});
 					}
+					actual_payment
 				}
 				// We do not recreate the account using the refund. The up front payment
 				// is gone in that case.
 				Err(_) => payed,
 			};
-			let imbalances = actual_payment.split(tip);
+			let (tip, fee) = actual_payment.split(tip);
 
 			// distribute fee
-			<T as Config>::OnTransactionPayment::on_unbalanceds(
-				Some(imbalances.0).into_iter().chain(Some(imbalances.1)),
-			);
+			Module::<T>::distribute_fee(fee, tip);
 		}
 		Ok(())
 	}
@@ -749,10 +1693,10 @@ where
 			// is gone in that case.
 			Err(_) => payed,
 		};
-		let imbalances = actual_payment.split(Zero::zero());
+		let (tip, fee) = actual_payment.split(Zero::zero());
 
 		// distribute fee
-		<T as Config>::OnTransactionPayment::on_unbalanceds(Some(imbalances.0).into_iter().chain(Some(imbalances.1)));
+		Module::<T>::distribute_fee(fee, tip);
 
 		Ok(())
 	}
@@ -21,6 +21,8 @@ pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
+/// The padded account of the EVM address linked to `BOB`.
+pub const BOB_EVM: AccountId = AccountId::new([3u8; 32]);
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
@@ -152,6 +154,7 @@ parameter_types! {
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const TwapPeriod: BlockNumber = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, ACA), TradingPair::new(AUSD, DOT)];
 }
 
@@ -160,6 +163,7 @@ impl module_dex::Config for Runtime {
 	type Currency = Currencies;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TwapPeriod = TwapPeriod;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = ();
 	type WeightInfo = ();
@@ -171,9 +175,51 @@ parameter_types! {
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::one();
 	pub const StableCurrencyId: CurrencyId = AUSD;
 	pub static TransactionByteFee: u128 = 1;
+	pub MaxWeightToFeeStep: Ratio = Ratio::saturating_from_rational(20, 100);
+	pub MinDispatchClassFeeMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10);
+	pub MaxDispatchClassFeeMultiplier: Multiplier = Multiplier::saturating_from_integer(10);
+	pub const OperationalFeeMultiplier: u8 = 5;
+	pub const FeeBurnModuleId: ModuleId = ModuleId(*b"aca/fbrn");
+	pub const FeeBurnPeriod: BlockNumber = 10;
+	pub const FreeTransactionQuota: u32 = 2;
+	pub const FreeTransactionPeriod: BlockNumber = 10;
+	pub const FreeTransactionMinLocked: Balance = 1000;
+	pub const MaxFeeDiscountTiers: u32 = 3;
+	pub const OnboardingSubsidyModuleId: ModuleId = ModuleId(*b"aca/obsd");
+	pub const OnboardingSubsidizedTransactions: u32 = 2;
+	pub const OnboardingSubsidyCap: Balance = 1000;
+	pub const MaxOnboardingsPerPeriod: u32 = 2;
+	pub const OnboardingPeriod: BlockNumber = 10;
+}
+
+pub struct NativeLockedBalance;
+impl Convert<AccountId, Balance> for NativeLockedBalance {
+	fn convert(who: AccountId) -> Balance {
+		System::account(who).data.misc_frozen
+	}
+}
+
+pub struct MockLinkedEvmBalance;
+impl LinkedEvmBalance<AccountId, Balance> for MockLinkedEvmBalance {
+	fn free_balance(who: &AccountId) -> Balance {
+		if *who == BOB {
+			PalletBalances::free_balance(&BOB_EVM)
+		} else {
+			0
+		}
+	}
+
+	fn pull(who: &AccountId, amount: Balance) -> DispatchResult {
+		if *who != BOB {
+			return Err(DispatchError::Other("not linked"));
+		}
+		<PalletBalances as Currency<_>>::transfer(&BOB_EVM, who, amount, ExistenceRequirement::AllowDeath)
+	}
 }
 
 impl Config for Runtime {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = StableCurrencyId;
@@ -184,7 +230,25 @@ impl Config for Runtime {
 	type WeightToFee = WeightToFee;
 	type FeeMultiplierUpdate = ();
 	type DEX = DEXModule;
+	type LinkedEvmBalance = MockLinkedEvmBalance;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type MaxWeightToFeeStep = MaxWeightToFeeStep;
+	type MinDispatchClassFeeMultiplier = MinDispatchClassFeeMultiplier;
+	type MaxDispatchClassFeeMultiplier = MaxDispatchClassFeeMultiplier;
+	type OperationalFeeMultiplier = OperationalFeeMultiplier;
+	type FeeBurnModuleId = FeeBurnModuleId;
+	type FeeBurnPeriod = FeeBurnPeriod;
+	type NativeLockedBalance = NativeLockedBalance;
+	type FreeTransactionQuota = FreeTransactionQuota;
+	type FreeTransactionPeriod = FreeTransactionPeriod;
+	type FreeTransactionMinLocked = FreeTransactionMinLocked;
+	type MaxFeeDiscountTiers = MaxFeeDiscountTiers;
+	type OnboardingSubsidyModuleId = OnboardingSubsidyModuleId;
+	type OnboardingSubsidizedTransactions = OnboardingSubsidizedTransactions;
+	type OnboardingSubsidyCap = OnboardingSubsidyCap;
+	type MaxOnboardingsPerPeriod = MaxOnboardingsPerPeriod;
+	type OnboardingPeriod = OnboardingPeriod;
+	type UpdateOrigin = frame_system::EnsureSignedBy<Zero, AccountId>;
 	type WeightInfo = ();
 }
 
@@ -216,7 +280,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		TransactionPayment: transaction_payment::{Module, Call, Storage},
+		TransactionPayment: transaction_payment::{Module, Call, Storage, Event<T>},
 		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
 		Currencies: module_currencies::{Module, Call, Event<T>},
@@ -4,15 +4,17 @@
 
 use super::*;
 use frame_support::{
-	assert_ok,
+	assert_noop, assert_ok, parameter_types,
+	traits::{LockableCurrency, OnInitialize, WithdrawReasons},
 	weights::{DispatchClass, DispatchInfo, Pays},
 };
 use mock::{
-	AccountId, BlockWeights, Call, Currencies, DEXModule, ExtBuilder, Origin, Runtime, TransactionPayment, ACA, ALICE,
-	AUSD, BOB, DOT,
+	AccountId, BlockWeights, Call, Currencies, DEXModule, Event, ExtBuilder, Origin, PalletBalances, Runtime, System,
+	TransactionPayment, ACA, ALICE, AUSD, BOB, BOB_EVM, DOT,
 };
 use orml_traits::MultiCurrency;
-use sp_runtime::testing::TestXt;
+use primitives::TokenSymbol;
+use sp_runtime::{testing::TestXt, traits::BadOrigin};
 
 const CALL: &<Runtime as frame_system::Config>::Call =
 	&Call::Currencies(module_currencies::Call::transfer(BOB, AUSD, 12));
@@ -40,7 +42,7 @@ fn charges_fee() {
 				.validate(&ALICE, CALL, &INFO, 23)
 				.unwrap()
 				.priority,
-			fee
+			1
 		);
 		assert_eq!(Currencies::free_balance(ACA, &ALICE), (100000 - fee).into());
 
@@ -50,7 +52,7 @@ fn charges_fee() {
 				.validate(&ALICE, CALL2, &INFO, 18)
 				.unwrap()
 				.priority,
-			fee2
+			1
 		);
 		assert_eq!(
 			Currencies::free_balance(ACA, &ALICE),
@@ -103,13 +105,13 @@ fn charges_fee_when_validate_and_native_is_not_enough() {
 		));
 		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (10000, 1000));
 
-		let fee = 500 * 2 + 1000; // len * byte + weight
+		// the fee is 500 * 2 + 1000, len * byte + weight
 		assert_eq!(
 			ChargeTransactionPayment::<Runtime>::from(0)
 				.validate(&BOB, CALL2, &INFO, 500)
 				.unwrap()
 				.priority,
-			fee
+			1
 		);
 
 		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
@@ -118,6 +120,197 @@ fn charges_fee_when_validate_and_native_is_not_enough() {
 	});
 }
 
+#[test]
+fn charges_fee_only_swap_shortfall_when_native_is_not_enough() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 500));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+
+		// the fee is 500 * 2 + 1000, len * byte + weight
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0)
+				.validate(&BOB, CALL2, &INFO, 500)
+				.unwrap()
+				.priority,
+			1
+		);
+
+		// only swap the shortfall 1500 of native currency
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1000 - 177);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (10000 - 1500, 1177));
+	});
+}
+
+#[test]
+fn charges_fee_swap_shortfall_of_spendable_native_when_locked() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 500));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		PalletBalances::set_lock(*b"vesting ", &BOB, 300, WithdrawReasons::all());
+
+		let fee = 500 * 2 + 1000; // len * byte + weight
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0)
+				.validate(&BOB, CALL2, &INFO, 500)
+				.unwrap()
+				.priority,
+			fee
+		);
+
+		// swap the shortfall 1800 of the spendable native currency, the locked
+		// is kept
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 300);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1000 - 220);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (10000 - 1800, 1220));
+	});
+}
+
+#[test]
+fn charges_fee_from_linked_evm_balance_when_native_is_not_enough() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB_EVM, 3000));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		assert_eq!(
+			TransactionPayment::query_call_fee(&BOB, CALL2, 500).swap_currency_id,
+			None
+		);
+
+		// the fee is 500 * 2 + 1000, len * byte + weight
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.validate(&BOB, CALL2, &INFO, 500)
+			.is_ok());
+
+		// the fee is pulled from the linked EVM balance rather than swapped
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(Currencies::free_balance(ACA, &BOB_EVM), 1000);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (10000, 1000));
+	});
+}
+
+#[test]
+fn query_call_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let fee = TransactionPayment::compute_fee(500, &CALL2.get_dispatch_info(), 0);
+		assert_eq!(
+			TransactionPayment::query_call_fee(&ALICE, CALL2, 500),
+			FeeDetails {
+				weight: CALL2.get_dispatch_info().weight,
+				class: DispatchClass::Normal,
+				inclusion_fee: fee,
+				swap_currency_id: None,
+				swap_supply_amount: 0,
+				swap_target_amount: 0,
+			}
+		);
+
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &BOB, 500));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		// no currency can be swapped without liquidity
+		assert_eq!(
+			TransactionPayment::query_call_fee(&BOB, CALL2, 500).swap_currency_id,
+			None
+		);
+
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		let fee_details = TransactionPayment::query_call_fee(&BOB, CALL2, 500);
+		assert_eq!(fee_details.inclusion_fee, fee);
+		assert_eq!(fee_details.swap_currency_id, Some(AUSD));
+		// only the shortfall of native currency is swapped
+		assert_eq!(fee_details.swap_target_amount, fee - 500);
+
+		// the queried swap is the same as the actual fee payment
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, CALL2, &CALL2.get_dispatch_info(), 500)
+			.is_ok());
+		assert_eq!(
+			Currencies::free_balance(AUSD, &BOB),
+			1000 - fee_details.swap_supply_amount
+		);
+	});
+}
+
+#[test]
+fn priority_by_tip_per_weight_with_operational_boost() {
+	ExtBuilder::default().build().execute_with(|| {
+		let normal_info = DispatchInfo {
+			weight: 100,
+			class: DispatchClass::Normal,
+			pays_fee: Pays::Yes,
+		};
+		// 10 such transactions fit into the block by weight
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0)
+				.validate(&ALICE, CALL, &normal_info, 23)
+				.unwrap()
+				.priority,
+			10
+		);
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(10)
+				.validate(&ALICE, CALL, &normal_info, 23)
+				.unwrap()
+				.priority,
+			(10 + 1) * 10
+		);
+		// the transaction consuming more weight with the same tip has lower priority
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(10)
+				.validate(&ALICE, CALL, &INFO, 23)
+				.unwrap()
+				.priority,
+			10 + 1
+		);
+
+		// the operational transaction gets the virtual tip of final fee * 5
+		let operational_info = DispatchInfo {
+			weight: 100,
+			class: DispatchClass::Operational,
+			pays_fee: Pays::Yes,
+		};
+		let fee = 23 * 2 + 100 + 10; // len * byte + weight + tip
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(10)
+				.validate(&ALICE, CALL, &operational_info, 23)
+				.unwrap()
+				.priority,
+			(10 + 1) * 10 + fee * 5 * 10
+		);
+	});
+}
+
 #[test]
 fn set_default_fee_token_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -129,6 +322,13 @@ fn set_default_fee_token_work() {
 		assert_eq!(TransactionPayment::default_fee_currency_id(&ALICE), Some(AUSD));
 		assert_ok!(TransactionPayment::set_default_fee_token(Origin::signed(ALICE), None));
 		assert_eq!(TransactionPayment::default_fee_currency_id(&ALICE), None);
+		assert_noop!(
+			TransactionPayment::set_default_fee_token(
+				Origin::signed(ALICE),
+				Some(CurrencyId::Token(TokenSymbol::XBTC))
+			),
+			Error::<Runtime>::InvalidFeeToken
+		);
 	});
 }
 
@@ -164,13 +364,13 @@ fn charge_fee_by_default_fee_token() {
 		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(AUSD, &BOB), 0);
 		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(DOT, &BOB), 100);
 
-		let fee = 500 * 2 + 1000; // len * byte + weight
+		// the fee is 500 * 2 + 1000, len * byte + weight
 		assert_eq!(
 			ChargeTransactionPayment::<Runtime>::from(0)
 				.validate(&BOB, CALL2, &INFO, 500)
 				.unwrap()
 				.priority,
-			fee
+			1
 		);
 
 		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
@@ -181,6 +381,549 @@ fn charge_fee_by_default_fee_token() {
 	});
 }
 
+#[test]
+fn set_fee_burn_ratio_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TransactionPayment::set_fee_burn_ratio(Origin::signed(ALICE), Ratio::saturating_from_rational(1, 10)),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPayment::set_fee_burn_ratio(
+				Origin::signed(AccountId::new([0u8; 32])),
+				Ratio::saturating_from_rational(11, 10)
+			),
+			Error::<Runtime>::InvalidFeeBurnRatio
+		);
+
+		assert_ok!(TransactionPayment::set_fee_burn_ratio(
+			Origin::signed(AccountId::new([0u8; 32])),
+			Ratio::saturating_from_rational(1, 10)
+		));
+		let update_event = Event::transaction_payment(crate::Event::FeeBurnRatioUpdated(
+			Ratio::saturating_from_rational(1, 10),
+		));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			TransactionPayment::fee_burn_ratio(),
+			Ratio::saturating_from_rational(1, 10)
+		);
+	});
+}
+
+#[test]
+fn burn_fees_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let burn_account = TransactionPayment::fee_burn_account_id();
+		assert_ok!(TransactionPayment::set_fee_burn_ratio(
+			Origin::signed(AccountId::new([0u8; 32])),
+			Ratio::saturating_from_rational(1, 10)
+		));
+
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		// the share of the actual fee 846 is kept to burn
+		assert_eq!(Currencies::free_balance(ACA, &burn_account), 84);
+
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(
+			AUSD,
+			&ALICE,
+			&burn_account,
+			100
+		));
+		let total_issuance = PalletBalances::total_issuance();
+
+		// not at the fee burn period
+		TransactionPayment::on_initialize(9);
+		assert_eq!(Currencies::free_balance(ACA, &burn_account), 84);
+
+		TransactionPayment::on_initialize(10);
+		let swap_event = Event::transaction_payment(crate::Event::FeeSwapped(AUSD, 100, 909));
+		assert!(System::events().iter().any(|record| record.event == swap_event));
+		let burn_event = Event::transaction_payment(crate::Event::FeeBurned(993));
+		assert!(System::events().iter().any(|record| record.event == burn_event));
+		assert_eq!(Currencies::free_balance(ACA, &burn_account), 0);
+		assert_eq!(Currencies::free_balance(AUSD, &burn_account), 0);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (10000 - 909, 1100));
+		assert_eq!(PalletBalances::total_issuance(), total_issuance - 993);
+		assert_eq!(TransactionPayment::total_fee_burned(), 993);
+	});
+}
+
+#[test]
+fn set_fee_discount_tiers_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let tiers = vec![
+			(1000, Ratio::saturating_from_rational(10, 100)),
+			(5000, Ratio::saturating_from_rational(20, 100)),
+			(10000, Ratio::saturating_from_rational(30, 100)),
+		];
+		assert_noop!(
+			TransactionPayment::set_fee_discount_tiers(Origin::signed(ALICE), tiers.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPayment::set_fee_discount_tiers(
+				Origin::signed(AccountId::new([0u8; 32])),
+				vec![vec![(100, Ratio::one())], tiers.clone()].concat()
+			),
+			Error::<Runtime>::TooManyFeeDiscountTiers
+		);
+		assert_noop!(
+			TransactionPayment::set_fee_discount_tiers(
+				Origin::signed(AccountId::new([0u8; 32])),
+				vec![tiers[1], tiers[0]]
+			),
+			Error::<Runtime>::InvalidFeeDiscountTiers
+		);
+		assert_noop!(
+			TransactionPayment::set_fee_discount_tiers(
+				Origin::signed(AccountId::new([0u8; 32])),
+				vec![(1000, Ratio::saturating_from_rational(11, 10))]
+			),
+			Error::<Runtime>::InvalidFeeDiscountTiers
+		);
+
+		assert_ok!(TransactionPayment::set_fee_discount_tiers(
+			Origin::signed(AccountId::new([0u8; 32])),
+			tiers.clone()
+		));
+		let update_event = Event::transaction_payment(crate::Event::FeeDiscountTiersUpdated(tiers.clone()));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(TransactionPayment::fee_discount_tiers(), tiers);
+	});
+}
+
+#[test]
+fn charges_fee_with_discount_by_locked_native_currency() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TransactionPayment::set_fee_discount_tiers(
+			Origin::signed(AccountId::new([0u8; 32])),
+			vec![
+				(1000, Ratio::saturating_from_rational(10, 100)),
+				(5000, Ratio::saturating_from_rational(20, 100)),
+				(10000, Ratio::saturating_from_rational(30, 100)),
+			]
+		));
+		assert_eq!(TransactionPayment::fee_discount(&ALICE), Ratio::zero());
+		PalletBalances::set_lock(*b"vesting ", &ALICE, 5000, WithdrawReasons::all());
+		assert_eq!(
+			TransactionPayment::fee_discount(&ALICE),
+			Ratio::saturating_from_rational(20, 100)
+		);
+
+		// the tip is not discounted
+		let fee = 23 * 2 + 1000 + 5 - 209; // len * byte + weight + tip - discount
+		let pre = ChargeTransactionPayment::<Runtime>::from(5)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+
+		// the actual fee is discounted as well
+		let actual_fee = 23 * 2 + 800 + 5 - 169;
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - actual_fee);
+	});
+}
+
+#[test]
+fn free_transaction_quota_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let fee = 23 * 2 + 1000; // len * byte + weight
+
+		// not eligible without enough locked native currency
+		assert_eq!(TransactionPayment::free_transaction_remaining(&ALICE), 0);
+		PalletBalances::set_lock(*b"staking ", &ALICE, 999, WithdrawReasons::all());
+		assert_eq!(TransactionPayment::free_transaction_remaining(&ALICE), 0);
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+
+		PalletBalances::set_lock(*b"staking ", &ALICE, 1000, WithdrawReasons::all());
+		assert_eq!(TransactionPayment::free_transaction_remaining(&ALICE), 2);
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		let used_event = Event::transaction_payment(crate::Event::FreeTransactionUsed(ALICE, 1));
+		assert!(System::events().iter().any(|record| record.event == used_event));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+		assert_eq!(TransactionPayment::free_transaction_usages(&ALICE), Some((0, 1)));
+
+		// the transaction with tip is not free
+		assert!(ChargeTransactionPayment::<Runtime>::from(5)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee * 2 - 5);
+		assert_eq!(TransactionPayment::free_transaction_remaining(&ALICE), 1);
+
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.is_ok());
+		assert_eq!(TransactionPayment::free_transaction_remaining(&ALICE), 0);
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee * 3 - 5);
+
+		// the quota is renewed in the next period
+		System::set_block_number(10);
+		assert_eq!(TransactionPayment::free_transaction_remaining(&ALICE), 2);
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee * 3 - 5);
+		assert_eq!(TransactionPayment::free_transaction_usages(&ALICE), Some((1, 1)));
+	});
+}
+
+#[test]
+fn set_sponsorship_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(TransactionPayment::sponsorships(&ALICE, &BOB), None);
+		assert_ok!(TransactionPayment::set_sponsorship(
+			Origin::signed(ALICE),
+			BOB,
+			Some(2000)
+		));
+		let update_event = Event::transaction_payment(crate::Event::SponsorshipUpdated(ALICE, BOB, Some(2000)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(TransactionPayment::sponsorships(&ALICE, &BOB), Some(2000));
+
+		assert_ok!(TransactionPayment::set_sponsorship(Origin::signed(ALICE), BOB, None));
+		assert_eq!(TransactionPayment::sponsorships(&ALICE, &BOB), None);
+	});
+}
+
+#[test]
+fn charges_fee_from_sponsor_with_fee_paid_by() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let remark = Box::new(Call::System(frame_system::Call::remark(vec![])));
+		let call = Call::TransactionPayment(crate::Call::with_fee_paid_by(ALICE, remark.clone()));
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+
+		// not sponsored
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, &call, &INFO, 23)
+			.is_err());
+		assert_noop!(
+			TransactionPayment::with_fee_paid_by(Origin::signed(BOB), ALICE, remark.clone()),
+			Error::<Runtime>::NotSponsored
+		);
+
+		assert_ok!(TransactionPayment::set_sponsorship(
+			Origin::signed(ALICE),
+			BOB,
+			Some(2000)
+		));
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, &call, &INFO, 23)
+			.unwrap();
+		let paid_event = Event::transaction_payment(crate::Event::FeePaidBySponsor(ALICE, BOB, fee));
+		assert!(System::events().iter().any(|record| record.event == paid_event));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+		assert_eq!(TransactionPayment::sponsorships(&ALICE, &BOB), Some(2000 - fee));
+		assert_ok!(TransactionPayment::with_fee_paid_by(Origin::signed(BOB), ALICE, remark));
+
+		// the refund goes to the sponsor and restores the spending cap
+		let refund = 200; // 1000 - 800
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee + refund);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(
+			TransactionPayment::sponsorships(&ALICE, &BOB),
+			Some(2000 - fee + refund)
+		);
+
+		// the sponsor never pays the tip
+		assert!(ChargeTransactionPayment::<Runtime>::from(5)
+			.pre_dispatch(&BOB, &call, &INFO, 23)
+			.is_err());
+
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, &call, &INFO, 23)
+			.is_ok());
+		assert_eq!(
+			TransactionPayment::sponsorships(&ALICE, &BOB),
+			Some(2000 - 2 * fee + refund)
+		);
+
+		// exceed the spending cap
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, &call, &INFO, 23)
+			.is_err());
+	});
+}
+
+#[test]
+fn onboard_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pot = TransactionPayment::onboarding_subsidy_account_id();
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ACA, &ALICE, &pot, 3000));
+
+		// not brand-new
+		assert!(!TransactionPayment::onboard(&ALICE));
+		assert_eq!(TransactionPayment::onboarding_subsidies(&ALICE), None);
+
+		assert!(TransactionPayment::onboard(&BOB));
+		let onboard_event = Event::transaction_payment(crate::Event::AccountOnboarded(BOB, 0));
+		assert!(System::events().iter().any(|record| record.event == onboard_event));
+		assert_eq!(TransactionPayment::onboarding_subsidies(&BOB), Some((2, 1000)));
+		assert_eq!(TransactionPayment::onboarding_count(), (0, 1));
+		assert!(!TransactionPayment::onboard(&BOB));
+
+		// the fee is 0 * 2 + 1000, len * byte + weight
+		assert_eq!(TransactionPayment::query_call_fee(&BOB, CALL, 0).inclusion_fee, 0);
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, CALL, &INFO, 0)
+			.unwrap();
+		let used_event = Event::transaction_payment(crate::Event::OnboardingSubsidyUsed(BOB, 1));
+		assert!(System::events().iter().any(|record| record.event == used_event));
+		assert_eq!(Currencies::free_balance(ACA, &pot), 2000);
+		assert_eq!(TransactionPayment::onboarding_subsidies(&BOB), Some((1, 0)));
+
+		// the refund goes to the onboarding subsidy
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 0, &Ok(())).is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &pot), 2200);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+
+		// exceed the subsidy cap
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, CALL, &INFO, 0)
+			.is_err());
+		assert_eq!(Currencies::free_balance(ACA, &pot), 2200);
+	});
+}
+
+#[test]
+fn onboard_is_rate_limited() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert!(TransactionPayment::onboard(&AccountId::new([10u8; 32])));
+		assert!(TransactionPayment::onboard(&AccountId::new([11u8; 32])));
+		assert!(!TransactionPayment::onboard(&AccountId::new([12u8; 32])));
+		assert_eq!(TransactionPayment::onboarding_count(), (0, 2));
+
+		// the limit is renewed in the next period
+		System::set_block_number(10);
+		assert!(TransactionPayment::onboard(&AccountId::new([12u8; 32])));
+		assert_eq!(TransactionPayment::onboarding_count(), (1, 1));
+	});
+}
+
+#[test]
+fn set_weight_to_fee_coefficients_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let coefficients = vec![FeeCoefficient {
+			coeff_integer: 1,
+			coeff_frac: Perbill::from_percent(10),
+			negative: false,
+			degree: 1,
+		}];
+		assert_noop!(
+			TransactionPayment::set_weight_to_fee_coefficients(Origin::signed(ALICE), coefficients.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPayment::set_weight_to_fee_coefficients(Origin::signed(AccountId::new([0u8; 32])), vec![]),
+			Error::<Runtime>::InvalidWeightToFeeCoefficients
+		);
+		// the fee of the max block weight 1024 can change at most 20%
+		assert_noop!(
+			TransactionPayment::set_weight_to_fee_coefficients(
+				Origin::signed(AccountId::new([0u8; 32])),
+				vec![FeeCoefficient {
+					coeff_integer: 2,
+					coeff_frac: Perbill::zero(),
+					negative: false,
+					degree: 1,
+				}]
+			),
+			Error::<Runtime>::WeightToFeeStepExceeded
+		);
+
+		assert_ok!(TransactionPayment::set_weight_to_fee_coefficients(
+			Origin::signed(AccountId::new([0u8; 32])),
+			coefficients.clone()
+		));
+		let update_event =
+			Event::transaction_payment(crate::Event::WeightToFeeCoefficientsUpdated(coefficients.clone()));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(TransactionPayment::weight_to_fee_coefficients(), Some(coefficients));
+
+		let fee = 23 * 2 + 1100; // len * byte + weight * 1.1
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+	});
+}
+
+#[test]
+fn set_dispatch_class_fee_multiplier_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let multiplier = Multiplier::saturating_from_rational(1, 2);
+		assert_noop!(
+			TransactionPayment::set_dispatch_class_fee_multiplier(
+				Origin::signed(ALICE),
+				DispatchClass::Operational,
+				multiplier
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPayment::set_dispatch_class_fee_multiplier(
+				Origin::signed(AccountId::new([0u8; 32])),
+				DispatchClass::Operational,
+				Multiplier::saturating_from_rational(1, 100)
+			),
+			Error::<Runtime>::InvalidDispatchClassFeeMultiplier
+		);
+		assert_noop!(
+			TransactionPayment::set_dispatch_class_fee_multiplier(
+				Origin::signed(AccountId::new([0u8; 32])),
+				DispatchClass::Operational,
+				Multiplier::saturating_from_integer(11)
+			),
+			Error::<Runtime>::InvalidDispatchClassFeeMultiplier
+		);
+
+		assert_ok!(TransactionPayment::set_dispatch_class_fee_multiplier(
+			Origin::signed(AccountId::new([0u8; 32])),
+			DispatchClass::Operational,
+			multiplier
+		));
+		let update_event = Event::transaction_payment(crate::Event::DispatchClassFeeMultiplierUpdated(
+			DispatchClass::Operational,
+			multiplier,
+		));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(
+			TransactionPayment::dispatch_class_fee_multiplier(DispatchClass::Operational),
+			multiplier
+		);
+		assert_eq!(
+			TransactionPayment::dispatch_class_fee_multiplier(DispatchClass::Normal),
+			Multiplier::one()
+		);
+
+		// the inclusion fee of the operational call is halved, the tip is not
+		let operational_info = DispatchInfo {
+			weight: 1000,
+			class: DispatchClass::Operational,
+			pays_fee: Pays::Yes,
+		};
+		let fee = (23 * 2 + 1000) / 2 + 10; // (len * byte + weight) / 2 + tip
+		assert!(ChargeTransactionPayment::<Runtime>::from(10)
+			.pre_dispatch(&ALICE, CALL, &operational_info, 23)
+			.is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+
+		// the normal call is not affected
+		let fee2 = 23 * 2 + 1000; // len * byte + weight
+		assert!(ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee - fee2);
+
+		// reset to one removes the multiplier
+		assert_ok!(TransactionPayment::set_dispatch_class_fee_multiplier(
+			Origin::signed(AccountId::new([0u8; 32])),
+			DispatchClass::Operational,
+			Multiplier::one()
+		));
+		assert!(!DispatchClassFeeMultipliers::<Runtime>::contains_key(
+			DispatchClass::Operational
+		));
+	});
+}
+
+parameter_types! {
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100);
+	pub MaxMultiplierStep: Multiplier = Multiplier::saturating_from_rational(1, 200);
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 2);
+	pub MaximumMultiplier: Multiplier = Multiplier::saturating_from_integer(2);
+}
+
+type FeeAdjustment = BoundedFeeAdjustment<
+	Runtime,
+	TargetBlockFullness,
+	AdjustmentVariable,
+	MaxMultiplierStep,
+	MinimumMultiplier,
+	MaximumMultiplier,
+>;
+
+#[test]
+fn bounded_fee_adjustment_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the target is 25% of the normal max total weight 1024
+		System::set_block_consumed_resources(256, 0);
+		assert_eq!(FeeAdjustment::convert(Multiplier::one()), Multiplier::one());
+
+		// the increase of the full block is bounded by the max step
+		System::set_block_consumed_resources(1024, 0);
+		assert_eq!(
+			FeeAdjustment::convert(Multiplier::one()),
+			Multiplier::saturating_from_rational(1005, 1000)
+		);
+
+		// the decrease of the empty block is within the max step
+		System::set_block_consumed_resources(0, 0);
+		assert_eq!(
+			FeeAdjustment::convert(Multiplier::one()),
+			Multiplier::saturating_from_rational(997_503_125, 1_000_000_000)
+		);
+	});
+}
+
+#[test]
+fn bounded_fee_adjustment_within_floor_and_ceiling() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_consumed_resources(1024, 0);
+		assert_eq!(
+			FeeAdjustment::convert(Multiplier::saturating_from_integer(2)),
+			Multiplier::saturating_from_integer(2)
+		);
+		assert_eq!(
+			FeeAdjustment::convert(Multiplier::saturating_from_integer(10)),
+			Multiplier::saturating_from_integer(2)
+		);
+
+		System::set_block_consumed_resources(0, 0);
+		assert_eq!(
+			FeeAdjustment::convert(Multiplier::saturating_from_rational(1, 2)),
+			Multiplier::saturating_from_rational(1, 2)
+		);
+		assert_eq!(
+			FeeAdjustment::convert(Multiplier::zero()),
+			Multiplier::saturating_from_rational(1, 2)
+		);
+	});
+}
+
 #[test]
 fn query_info_works() {
 	ExtBuilder::default()
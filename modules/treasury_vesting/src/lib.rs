@@ -0,0 +1,144 @@
+//! # Treasury Vesting Module
+//!
+//! ## Overview
+//!
+//! Pay the grants and the team allocations approved by the council from the
+//! treasury as on-chain vesting schedules, rather than ad-hoc liquid
+//! transfers. Each payout is linked to the index of the council proposal
+//! approving it, and a proposal can only be paid once.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::Balance;
+use sp_runtime::{
+	traits::{StaticLookup, Zero},
+	RuntimeDebug,
+};
+use support::VestedTransfer;
+
+mod default_weight;
+mod mock;
+mod tests;
+
+pub use module::*;
+
+pub trait WeightInfo {
+	fn vested_transfer_from_treasury() -> Weight;
+}
+
+/// The index of the council proposal
+pub type ProposalIndex = u32;
+
+/// The vesting schedule of a payout from the treasury
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct PayoutSchedule<BlockNumber> {
+	/// The native currency amount paid to the beneficiary
+	pub amount: Balance,
+	/// The blocks the payout is locked before vesting
+	pub cliff: BlockNumber,
+	/// The blocks the payout is vested linearly over after the cliff
+	pub duration: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may pay from the treasury, i.e. the council
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Transfer the native currency with the vesting schedule
+		type VestedTransfer: VestedTransfer<Self::AccountId, Balance, Self::BlockNumber>;
+
+		/// The treasury account paying the vested transfers
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The payout amount is zero
+		ZeroAmount,
+		/// The proposal has already been paid
+		ProposalAlreadyPaid,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", T::BlockNumber = "BlockNumber")]
+	pub enum Event<T: Config> {
+		/// Paid from the treasury with the vesting schedule. \[proposal_index,
+		/// beneficiary, amount, cliff, duration\]
+		TreasuryVestedTransfer(ProposalIndex, T::AccountId, Balance, T::BlockNumber, T::BlockNumber),
+	}
+
+	/// The beneficiary and the amount of the payout of the proposals
+	///
+	/// Payouts: map ProposalIndex => Option<(AccountId, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn payouts)]
+	pub type Payouts<T: Config> = StorageMap<_, Twox64Concat, ProposalIndex, (T::AccountId, Balance), OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pay the beneficiary from the treasury with the vesting schedule,
+		/// for the council proposal at `proposal_index`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `beneficiary`: the account receives the vested payout.
+		/// - `proposal_index`: the index of the council proposal approving
+		///   the payout.
+		/// - `schedule`: the amount and the vesting of the payout.
+		#[pallet::weight(T::WeightInfo::vested_transfer_from_treasury())]
+		#[transactional]
+		pub fn vested_transfer_from_treasury(
+			origin: OriginFor<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] proposal_index: ProposalIndex,
+			schedule: PayoutSchedule<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(!schedule.amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(
+				!Payouts::<T>::contains_key(proposal_index),
+				Error::<T>::ProposalAlreadyPaid
+			);
+
+			T::VestedTransfer::vested_transfer_with_cliff(
+				&T::TreasuryAccount::get(),
+				&beneficiary,
+				schedule.amount,
+				schedule.cliff,
+				schedule.duration,
+			)?;
+			Payouts::<T>::insert(proposal_index, (beneficiary.clone(), schedule.amount));
+
+			Self::deposit_event(Event::TreasuryVestedTransfer(
+				proposal_index,
+				beneficiary,
+				schedule.amount,
+				schedule.cliff,
+				schedule.duration,
+			));
+			Ok(().into())
+		}
+	}
+}
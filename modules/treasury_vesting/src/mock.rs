@@ -0,0 +1,123 @@
+//! Mocks for the treasury vesting module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::sp_api_hidden_includes_construct_runtime::hidden_include::inherent::BlockT;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchResult};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const TREASURY: AccountId = 10;
+
+mod treasury_vesting {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+thread_local! {
+	static VESTED_TRANSFERS: RefCell<Vec<(AccountId, AccountId, Balance, BlockNumber, BlockNumber)>> = RefCell::new(vec![]);
+}
+
+pub fn vested_transfers() -> Vec<(AccountId, AccountId, Balance, BlockNumber, BlockNumber)> {
+	VESTED_TRANSFERS.with(|v| v.borrow().clone())
+}
+
+pub struct MockVestedTransfer;
+impl VestedTransfer<AccountId, Balance, BlockNumber> for MockVestedTransfer {
+	fn vested_transfer_with_cliff(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+		cliff: BlockNumber,
+		duration: BlockNumber,
+	) -> DispatchResult {
+		VESTED_TRANSFERS.with(|v| v.borrow_mut().push((*from, *to, amount, cliff, duration)));
+		Ok(())
+	}
+}
+
+ord_parameter_types! {
+	pub const Council: AccountId = 100;
+}
+
+parameter_types! {
+	pub const TreasuryAccount: AccountId = TREASURY;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = EnsureSignedBy<Council, AccountId>;
+	type VestedTransfer = MockVestedTransfer;
+	type TreasuryAccount = TreasuryAccount;
+	type WeightInfo = ();
+}
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		TreasuryVestingModule: treasury_vesting::{Module, Storage, Call, Event<T>},
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
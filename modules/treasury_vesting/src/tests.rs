@@ -0,0 +1,53 @@
+//! Unit tests for the treasury vesting module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn vested_transfer_from_treasury_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let schedule = PayoutSchedule {
+			amount: 1000,
+			cliff: 10,
+			duration: 100,
+		};
+		assert_noop!(
+			TreasuryVestingModule::vested_transfer_from_treasury(Origin::signed(ALICE), BOB, 0, schedule.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			TreasuryVestingModule::vested_transfer_from_treasury(
+				Origin::signed(Council::get()),
+				BOB,
+				0,
+				PayoutSchedule {
+					amount: 0,
+					cliff: 10,
+					duration: 100,
+				}
+			),
+			Error::<Runtime>::ZeroAmount
+		);
+
+		assert_ok!(TreasuryVestingModule::vested_transfer_from_treasury(
+			Origin::signed(Council::get()),
+			BOB,
+			0,
+			schedule.clone()
+		));
+		let payout_event = Event::treasury_vesting(crate::Event::TreasuryVestedTransfer(0, BOB, 1000, 10, 100));
+		assert!(System::events().iter().any(|record| record.event == payout_event));
+		assert_eq!(vested_transfers(), vec![(TREASURY, BOB, 1000, 10, 100)]);
+		assert_eq!(TreasuryVestingModule::payouts(0), Some((BOB, 1000)));
+
+		// a proposal is paid only once
+		assert_noop!(
+			TreasuryVestingModule::vested_transfer_from_treasury(Origin::signed(Council::get()), ALICE, 0, schedule),
+			Error::<Runtime>::ProposalAlreadyPaid
+		);
+	});
+}
@@ -0,0 +1,123 @@
+pragma solidity ^0.6.0;
+
+/// The ERC-20 style wrapper of the Honzon precompile, exposes the debit of the
+/// loans under a collateral type as a tokenized representation. The debit
+/// value of an account is the balance, and the total debit value of the
+/// collateral type is the total supply. The debit is not transferable, it's
+/// changed by adjusting the loan.
+contract Honzon {
+    address private constant PRECOMPILE = 0x0000000000000000000000000000000000000406;
+
+    uint256 private _currencyId;
+    string private _name;
+    string private _symbol;
+
+    constructor(uint256 currencyId_, string memory name_, string memory symbol_) public {
+        _currencyId = currencyId_;
+        _name = name_;
+        _symbol = symbol_;
+    }
+
+    function name() public view returns (string memory) {
+        return _name;
+    }
+
+    function symbol() public view returns (string memory) {
+        return _symbol;
+    }
+
+    function decimals() public pure returns (uint8) {
+        return 18;
+    }
+
+    /// The collateral currency id of the loans.
+    function currencyId() public view returns (uint256) {
+        return _currencyId;
+    }
+
+    /// The total debit value of the collateral type.
+    function totalSupply() public view returns (uint256) {
+        uint256[2] memory input;
+        input[0] = 2;
+        input[1] = _currencyId;
+
+        uint256[1] memory output;
+        address precompile = PRECOMPILE;
+        assembly {
+            if iszero(staticcall(gas(), precompile, input, 0x40, output, 0x20)) {
+                revert(0, 0)
+            }
+        }
+        return output[0];
+    }
+
+    /// The debit value of `account` under the collateral type.
+    function balanceOf(address account) public view returns (uint256) {
+        uint256[3] memory input;
+        input[0] = 1;
+        input[1] = uint256(account);
+        input[2] = _currencyId;
+
+        uint256[1] memory output;
+        address precompile = PRECOMPILE;
+        assembly {
+            if iszero(staticcall(gas(), precompile, input, 0x60, output, 0x20)) {
+                revert(0, 0)
+            }
+        }
+        return output[0];
+    }
+
+    /// The collateral and the debit of the loan of `account`.
+    function positionOf(address account) public view returns (uint256, uint256) {
+        uint256[3] memory input;
+        input[0] = 0;
+        input[1] = uint256(account);
+        input[2] = _currencyId;
+
+        uint256[2] memory output;
+        address precompile = PRECOMPILE;
+        assembly {
+            if iszero(staticcall(gas(), precompile, input, 0x60, output, 0x40)) {
+                revert(0, 0)
+            }
+        }
+        return (output[0], output[1]);
+    }
+
+    /// Adjust the loan of `who`, the caller must be `who` or have the honzon
+    /// authorization of `who` under the collateral type.
+    function adjustLoan(address who, int256 collateralAdjustment, int256 debitAdjustment) public returns (bool) {
+        uint256[6] memory input;
+        input[0] = 3;
+        input[1] = uint256(msg.sender);
+        input[2] = uint256(who);
+        input[3] = _currencyId;
+        input[4] = uint256(collateralAdjustment);
+        input[5] = uint256(debitAdjustment);
+
+        address precompile = PRECOMPILE;
+        assembly {
+            if iszero(call(gas(), precompile, 0, input, 0xc0, 0x00, 0x00)) {
+                revert(0, 0)
+            }
+        }
+        return true;
+    }
+
+    function allowance(address, address) public pure returns (uint256) {
+        return 0;
+    }
+
+    function transfer(address, uint256) public pure returns (bool) {
+        revert("Honzon: debit is not transferable");
+    }
+
+    function transferFrom(address, address, uint256) public pure returns (bool) {
+        revert("Honzon: debit is not transferable");
+    }
+
+    function approve(address, uint256) public pure returns (bool) {
+        revert("Honzon: debit is not transferable");
+    }
+}
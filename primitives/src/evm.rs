@@ -40,6 +40,22 @@ pub struct CallInfo {
 	pub used_gas: U256,
 	pub used_storage: i32,
 }
+
+/// The resources needed by an EVM call or create.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EstimateResourcesInfo<Balance> {
+	pub exit_reason: ExitReason,
+	pub output: Vec<u8>,
+	/// The gas limit needed.
+	pub gas: U256,
+	/// The storage limit needed in bytes, zero if the storage is freed.
+	pub storage: u32,
+	/// The storage deposit reserved for the storage limit.
+	pub storage_deposit: Balance,
+	/// The fee in native currency of the transaction with the needed limits.
+	pub fee: Balance,
+}
 /// A mapping between `AccountId` and `EvmAddress`.
 pub trait AddressMapping<AccountId> {
 	fn get_account_id(evm: &EvmAddress) -> AccountId;
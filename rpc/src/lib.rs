@@ -2,7 +2,7 @@
 
 #![warn(missing_docs)]
 
-use primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Nonce};
+use primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Moment, Nonce};
 use sc_client_api::light::{Fetcher, RemoteBlockchain};
 use sc_consensus_babe::{Config, Epoch};
 use sc_consensus_epochs::SharedEpochChanges;
@@ -20,6 +20,9 @@ pub use sc_rpc::SubscriptionTaskExecutor;
 
 pub use evm_rpc::{EVMApi, EVMApiServer, EVMRuntimeRPCApi};
 pub use sc_rpc::DenyUnsafe;
+pub use storage_maps::QUERYABLE_STORAGE_MAPS;
+
+mod storage_maps;
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
@@ -81,12 +84,20 @@ pub fn create_full<C, P, SC, B>(deps: FullDeps<C, P, SC, B>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: sc_client_api::StorageProvider<Block, B> + sc_client_api::BlockchainEvents<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, runtime_common::TimeStampedPrice>,
 	C::Api: module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>,
+	C::Api: module_honzon_rpc::HonzonRuntimeApi<Block, AccountId, Balance>,
+	C::Api: module_cdp_treasury_rpc::CDPTreasuryRuntimeApi<Block, Balance>,
+	C::Api: module_prices_rpc::PricesRuntimeApi<Block, CurrencyId, runtime_common::Price, Moment>,
+	C::Api: module_nft_rpc::NFTRuntimeApi<Block, AccountId, u32, u64>,
 	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: runtime_common_rpc::DryRunRuntimeApi<Block>,
+	C::Api: runtime_common_rpc::EventsRuntimeApi<Block>,
+	C::Api: sp_api::Metadata<Block>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
@@ -94,9 +105,15 @@ where
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
+	use jsonrpc_pubsub::manager::SubscriptionManager;
+	use module_cdp_treasury_rpc::{CDPTreasury, CDPTreasuryApi};
+	use module_honzon_rpc::{Honzon, HonzonApi};
+	use module_nft_rpc::{NFTApi, NFT};
+	use module_prices_rpc::{Prices, PricesApi};
 	use module_staking_pool_rpc::{StakingPool, StakingPoolApi};
 	use orml_oracle_rpc::{Oracle, OracleApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use runtime_common_rpc::{DryRun, DryRunApi, FilteredEvents, FilteredEventsApi, StorageMap, StorageMapApi};
 	use sc_consensus_babe_rpc::BabeRpcHandler;
 	use sc_finality_grandpa_rpc::{GrandpaApi, GrandpaRpcHandler};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
@@ -142,6 +159,10 @@ where
 		select_chain,
 		deny_unsafe,
 	)));
+	io.extend_with(FilteredEventsApi::to_delegate(FilteredEvents::new(
+		client.clone(),
+		SubscriptionManager::new(Arc::new(subscription_executor.clone())),
+	)));
 	io.extend_with(GrandpaApi::to_delegate(GrandpaRpcHandler::new(
 		shared_authority_set,
 		shared_voter_state,
@@ -151,6 +172,18 @@ where
 	)));
 	io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
 	io.extend_with(StakingPoolApi::to_delegate(StakingPool::new(client.clone())));
+	io.extend_with(HonzonApi::to_delegate(Honzon::new(client.clone())));
+	io.extend_with(CDPTreasuryApi::to_delegate(CDPTreasury::new(client.clone())));
+	io.extend_with(PricesApi::to_delegate(Prices::new(client.clone())));
+	io.extend_with(NFTApi::to_delegate(NFT::new(client.clone())));
+	io.extend_with(DryRunApi::to_delegate(DryRun::new(client.clone(), deny_unsafe)));
+	io.extend_with(StorageMapApi::to_delegate(StorageMap::new(
+		client.clone(),
+		QUERYABLE_STORAGE_MAPS
+			.iter()
+			.map(|(pallet, map, decode_entry)| (pallet.to_string(), map.to_string(), *decode_entry))
+			.collect(),
+	)));
 	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client)));
 
 	io
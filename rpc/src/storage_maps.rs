@@ -0,0 +1,69 @@
+//! The storage maps queryable by the `storageMap_getPage` rpc, with the
+//! decoders of their entries.
+
+use codec::Decode;
+use primitives::{evm::EvmAddress, AccountId, Balance, CurrencyId, TradingPair};
+use runtime_common_rpc::{DecodeEntry, EntryKeys};
+use serde_json::{json, Value};
+
+/// The `(pallet, map, decode_entry)` storage maps queryable by the
+/// `storageMap_getPage` rpc.
+pub const QUERYABLE_STORAGE_MAPS: &[(&str, &str, DecodeEntry)] = &[
+	("EvmAccounts", "Accounts", decode_evm_accounts),
+	("EvmAccounts", "EvmAddresses", decode_evm_addresses),
+	("Tokens", "Accounts", decode_tokens_accounts),
+	("Dex", "LiquidityPool", decode_liquidity_pool),
+	("Loans", "Positions", decode_positions),
+];
+
+fn decode_value<V: Decode>(value: &[u8]) -> Option<V> {
+	V::decode(&mut &value[..]).ok()
+}
+
+fn decode_evm_accounts(keys: &mut EntryKeys, value: &[u8]) -> Option<(Vec<Value>, Value)> {
+	let evm_address: EvmAddress = keys.decode()?;
+	let account_id: AccountId = decode_value(value)?;
+	Some((vec![json!(evm_address)], json!(account_id)))
+}
+
+fn decode_evm_addresses(keys: &mut EntryKeys, value: &[u8]) -> Option<(Vec<Value>, Value)> {
+	let account_id: AccountId = keys.decode()?;
+	let evm_address: EvmAddress = decode_value(value)?;
+	Some((vec![json!(account_id)], json!(evm_address)))
+}
+
+fn decode_tokens_accounts(keys: &mut EntryKeys, value: &[u8]) -> Option<(Vec<Value>, Value)> {
+	let account_id: AccountId = keys.decode()?;
+	let currency_id: CurrencyId = keys.decode()?;
+	let account_data: orml_tokens::AccountData<Balance> = decode_value(value)?;
+	Some((
+		vec![json!(account_id), json!(currency_id)],
+		json!({
+			"free": account_data.free.to_string(),
+			"reserved": account_data.reserved.to_string(),
+			"frozen": account_data.frozen.to_string(),
+		}),
+	))
+}
+
+fn decode_liquidity_pool(keys: &mut EntryKeys, value: &[u8]) -> Option<(Vec<Value>, Value)> {
+	let trading_pair: TradingPair = keys.decode()?;
+	let (pool_0, pool_1): (Balance, Balance) = decode_value(value)?;
+	Some((
+		vec![json!(trading_pair)],
+		json!([pool_0.to_string(), pool_1.to_string()]),
+	))
+}
+
+fn decode_positions(keys: &mut EntryKeys, value: &[u8]) -> Option<(Vec<Value>, Value)> {
+	let currency_id: CurrencyId = keys.decode()?;
+	let account_id: AccountId = keys.decode()?;
+	let position: module_loans::Position = decode_value(value)?;
+	Some((
+		vec![json!(currency_id), json!(account_id)],
+		json!({
+			"collateral": position.collateral.to_string(),
+			"debit": position.debit.to_string(),
+		}),
+	))
+}
@@ -13,13 +13,13 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
-use codec::Encode;
+use codec::{Decode, Encode};
 use hex_literal::hex;
 use sp_api::impl_runtime_apis;
 use sp_core::{
 	crypto::KeyTypeId,
 	u32_trait::{_1, _2, _3, _4},
-	OpaqueMetadata, H160,
+	OpaqueMetadata, H160, H256,
 };
 use sp_runtime::traits::{
 	BadOrigin, BlakeTwo256, Block as BlockT, NumberFor, OpaqueKeys, SaturatedConversion, StaticLookup,
@@ -39,11 +39,13 @@ use sp_version::RuntimeVersion;
 
 use frame_system::{EnsureOneOf, EnsureRoot, RawOrigin};
 use module_currencies::{BasicCurrencyAdapter, Currency};
-use module_evm::{CallInfo, CreateInfo};
+use module_evm::{CallInfo, CreateInfo, EstimateResourcesInfo};
 use module_evm_accounts::EvmAddressMapping;
-use module_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use module_transaction_payment::{BoundedFeeAdjustment, Multiplier};
 use orml_tokens::CurrencyAdapter;
-use orml_traits::{create_median_value_data_provider, parameter_type_with_key, DataFeeder, DataProviderExtended};
+use orml_traits::{
+	create_median_value_data_provider, parameter_type_with_key, DataFeeder, DataProviderExtended, GetByKey,
+};
 use pallet_grandpa::fg_primitives;
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
 use pallet_session::historical as pallet_session_historical;
@@ -74,8 +76,8 @@ pub use primitives::{
 	EraIndex, Hash, Moment, Nonce, Share, Signature, TokenSymbol, TradingPair,
 };
 pub use runtime_common::{
-	BlockLength, BlockWeights, CurveFeeModel, ExchangeRate, GasToWeight, OffchainSolutionWeightLimit, Price, Rate,
-	Ratio, SystemContractsFilter, TimeStampedPrice,
+	BlockLength, BlockWeights, CurveFeeModel, DexPriceProvider, ExchangeRate, GasToWeight, OffchainSolutionWeightLimit,
+	Price, Rate, Ratio, SystemContractsFilter, TimeStampedPrice,
 };
 
 mod authority;
@@ -121,6 +123,8 @@ parameter_types! {
 	pub const IncentivesModuleId: ModuleId = ModuleId(*b"aca/inct");
 	// Decentralized Sovereign Wealth Fund
 	pub const DSWFModuleId: ModuleId = ModuleId(*b"aca/dswf");
+	pub const FeeBurnModuleId: ModuleId = ModuleId(*b"aca/fbrn");
+	pub const OnboardingSubsidyModuleId: ModuleId = ModuleId(*b"aca/obsd");
 	pub const ElectionsPhragmenModuleId: LockIdentifier = *b"aca/phre";
 	pub const NftModuleId: ModuleId = ModuleId(*b"aca/aNFT");
 }
@@ -136,6 +140,8 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		HomaTreasuryModuleId::get().into_account(),
 		IncentivesModuleId::get().into_account(),
 		DSWFModuleId::get().into_account(),
+		FeeBurnModuleId::get().into_account(),
+		OnboardingSubsidyModuleId::get().into_account(),
 		ZeroAccountId::get(),
 	]
 }
@@ -262,9 +268,24 @@ impl pallet_balances::Config for Runtime {
 
 parameter_types! {
 	pub const TransactionByteFee: Balance = 10 * MILLICENTS;
+	pub MaxWeightToFeeStep: Ratio = Ratio::saturating_from_rational(20, 100);
+	pub MinDispatchClassFeeMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10);
+	pub MaxDispatchClassFeeMultiplier: Multiplier = Multiplier::saturating_from_integer(10);
+	pub const OperationalFeeMultiplier: u8 = 5;
+	pub const FeeBurnPeriod: BlockNumber = DAYS;
+	pub const FreeTransactionQuota: u32 = 5;
+	pub const FreeTransactionPeriod: BlockNumber = DAYS;
+	pub const FreeTransactionMinLocked: Balance = 100 * DOLLARS;
+	pub const MaxFeeDiscountTiers: u32 = 10;
+	pub const OnboardingSubsidizedTransactions: u32 = 3;
+	pub const OnboardingSubsidyCap: Balance = DOLLARS;
+	pub const MaxOnboardingsPerPeriod: u32 = 100;
+	pub const OnboardingPeriod: BlockNumber = HOURS;
 	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
 	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
 	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
+	pub MaximumMultiplier: Multiplier = Multiplier::saturating_from_integer(1_000);
+	pub MaxMultiplierStep: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -656,7 +677,7 @@ impl orml_auction::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
 	type AuctionId = AuctionId;
-	type Handler = AuctionManager;
+	type Handler = module_nft::AuctionHandlerWithNFT<Runtime, AuctionManager>;
 	type WeightInfo = ();
 }
 
@@ -763,6 +784,15 @@ impl orml_tokens::Config for Runtime {
 
 parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::saturating_from_rational(1, 1);
+	pub MaxLiquidStakingExchangeRateAge: BlockNumber = EraLength::get() * 2;
+	pub const MaxTwapWindow: u32 = 60;
+}
+
+parameter_type_with_key! {
+	pub CurrencyDecimals: |_currency_id: CurrencyId| -> u8 {
+		// all the currencies have the same decimals as DOLLARS
+		18
+	};
 }
 
 impl module_prices::Config for Runtime {
@@ -774,9 +804,44 @@ impl module_prices::Config for Runtime {
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
 	type LockOrigin = EnsureRootOrTwoThirdsGeneralCouncil;
 	type LiquidStakingExchangeRateProvider = LiquidStakingExchangeRateProvider;
+	type LiquidStakingExchangeRateUpdatedAt = LiquidStakingExchangeRateUpdatedAt;
+	type MaxLiquidStakingExchangeRateAge = MaxLiquidStakingExchangeRateAge;
+	type MaxTwapWindow = MaxTwapWindow;
+	type SourceUpdatedAt = PriceUpdatedAt;
+	type FallbackSource = DexPriceProvider<AccountId, Dex, GetStableCurrencyId, StableCurrencyFixedPrice>;
+	type UnixTime = Timestamp;
+	type Feeds = OraclePriceFeeds;
+	type CurrencyDecimals = CurrencyDecimals;
 	type WeightInfo = weights::prices::WeightInfo<Runtime>;
 }
 
+pub struct PriceUpdatedAt;
+impl GetByKey<CurrencyId, Option<Moment>> for PriceUpdatedAt {
+	fn get(currency_id: &CurrencyId) -> Option<Moment> {
+		<AggregatedDataProvider as DataProviderExtended<_, _>>::get_no_op(currency_id)
+			.map(|price: TimeStampedPrice| price.timestamp)
+	}
+}
+
+pub struct OraclePriceFeeds;
+impl module_prices::PriceFeeds for OraclePriceFeeds {
+	fn get_prices(currency_id: &CurrencyId) -> Vec<module_prices::FeedPrice> {
+		vec![
+			<AcalaOracle as DataProviderExtended<_, _>>::get_no_op(currency_id),
+			<BandOracle as DataProviderExtended<_, _>>::get_no_op(currency_id),
+		]
+		.into_iter()
+		.flatten()
+		.map(|price: TimeStampedPrice| module_prices::FeedPrice {
+			price: price.value,
+			// the oracles don't feed the confidence interval
+			confidence: None,
+			updated_at: price.timestamp,
+		})
+		.collect()
+	}
+}
+
 pub struct LiquidStakingExchangeRateProvider;
 impl module_support::ExchangeRateProvider for LiquidStakingExchangeRateProvider {
 	fn get_exchange_rate() -> ExchangeRate {
@@ -784,6 +849,13 @@ impl module_support::ExchangeRateProvider for LiquidStakingExchangeRateProvider
 	}
 }
 
+pub struct LiquidStakingExchangeRateUpdatedAt;
+impl Get<Option<BlockNumber>> for LiquidStakingExchangeRateUpdatedAt {
+	fn get() -> Option<BlockNumber> {
+		StakingPool::last_rebalanced_at()
+	}
+}
+
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 	pub const GetStableCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
@@ -835,6 +907,51 @@ impl orml_vesting::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// Vest the incentive rewards by orml-vesting, which only takes the vested
+/// transfer from the treasury.
+pub struct IncentiveRewardVesting;
+impl module_support::VestedTransfer<AccountId, Balance, BlockNumber> for IncentiveRewardVesting {
+	#[frame_support::transactional]
+	fn vested_transfer_with_cliff(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+		cliff: BlockNumber,
+		duration: BlockNumber,
+	) -> DispatchResult {
+		let per_period = amount / Balance::from(duration.max(1));
+		let schedule = orml_vesting::VestingSchedule {
+			start: System::block_number().saturating_add(cliff),
+			period: 1,
+			period_count: duration.max(1),
+			per_period,
+		};
+		let vested_amount = schedule
+			.total_amount()
+			.ok_or(orml_vesting::Error::<Runtime>::NumOverflow)?;
+		let treasury_account: AccountId = AcalaTreasuryModuleId::get().into_account();
+
+		<Currencies as orml_traits::MultiCurrency<AccountId>>::transfer(
+			GetNativeCurrencyId::get(),
+			from,
+			&treasury_account,
+			vested_amount,
+		)?;
+		Vesting::vested_transfer(
+			RawOrigin::Root.into(),
+			<Runtime as frame_system::Config>::Lookup::unlookup(to.clone()),
+			schedule,
+		)?;
+		// the remainder which can't be divided into the periods is transferred liquidly
+		<Currencies as orml_traits::MultiCurrency<AccountId>>::transfer(
+			GetNativeCurrencyId::get(),
+			from,
+			to,
+			amount.saturating_sub(vested_amount),
+		)
+	}
+}
+
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(10) *
 		BlockWeights::get().max_block;
@@ -867,6 +984,13 @@ parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(2, 100);
 	pub const AuctionTimeToClose: BlockNumber = 15 * MINUTES;
 	pub const AuctionDurationSoftCap: BlockNumber = 2 * HOURS;
+	pub const AntiSnipingWindow: BlockNumber = 5 * MINUTES;
+	pub const AntiSnipingExtension: BlockNumber = 5 * MINUTES;
+	pub const MaxAntiSnipingExtension: BlockNumber = HOURS;
+	pub const DutchAuctionDuration: BlockNumber = 2 * HOURS;
+	pub const MaxPartialBids: u32 = 50;
+	pub MinPartialBidProportion: Ratio = Ratio::saturating_from_rational(1, 100);
+	pub AuctionManagerMaxSettlementWeight: Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
 }
 
 impl module_auction_manager::Config for Runtime {
@@ -876,6 +1000,12 @@ impl module_auction_manager::Config for Runtime {
 	type MinimumIncrementSize = MinimumIncrementSize;
 	type AuctionTimeToClose = AuctionTimeToClose;
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
+	type AntiSnipingWindow = AntiSnipingWindow;
+	type AntiSnipingExtension = AntiSnipingExtension;
+	type MaxAntiSnipingExtension = MaxAntiSnipingExtension;
+	type DutchAuctionDuration = DutchAuctionDuration;
+	type MaxPartialBids = MaxPartialBids;
+	type MinPartialBidProportion = MinPartialBidProportion;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type CDPTreasury = CdpTreasury;
@@ -883,6 +1013,9 @@ impl module_auction_manager::Config for Runtime {
 	type PriceSource = Prices;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type MaxSettlementWeight = AuctionManagerMaxSettlementWeight;
+	type UpdateOrigin = EnsureRootOrHalfHonzonCouncil;
+	type CancelOrigin = EnsureRootOrHalfGeneralCouncil;
 	type WeightInfo = weights::auction_manager::WeightInfo<Runtime>;
 }
 
@@ -960,6 +1093,13 @@ parameter_types! {
 	pub DefaultLiquidationRatio: Ratio = Ratio::saturating_from_rational(110, 100);
 	pub DefaultDebitExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(1, 10);
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(5, 100);
+	pub const ParameterChangeDelay: BlockNumber = 2 * DAYS;
+	pub const LiquidatorBond: Balance = 10_000 * DOLLARS;
+	pub const LiquidatorExclusiveWindow: BlockNumber = 2;
+	pub const LiquidatorUnbondingDelay: BlockNumber = 7 * DAYS;
+	pub MaxPriceConfidence: Ratio = Ratio::saturating_from_rational(2, 100);
+	pub MaxLiquidationPenaltyStep: Rate = Rate::saturating_from_rational(5, 100);
+	pub KeeperRewardRatio: Rate = Rate::saturating_from_rational(1, 100);
 	pub const MinimumDebitValue: Balance = DOLLARS;
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(5, 100);
 }
@@ -971,6 +1111,9 @@ impl module_cdp_engine::Config for Runtime {
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
+	type ParameterChangeDelay = ParameterChangeDelay;
+	type MaxLiquidationPenaltyStep = MaxLiquidationPenaltyStep;
+	type KeeperRewardRatio = KeeperRewardRatio;
 	type MinimumDebitValue = MinimumDebitValue;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CdpTreasury;
@@ -979,11 +1122,23 @@ impl module_cdp_engine::Config for Runtime {
 	type DEX = Dex;
 	type UnsignedPriority = runtime_common::CdpEngineUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type UnixTime = Timestamp;
+	type Currency = Currency<Runtime, GetNativeCurrencyId>;
+	type LiquidatorBond = LiquidatorBond;
+	type LiquidatorExclusiveWindow = LiquidatorExclusiveWindow;
+	type LiquidatorUnbondingDelay = LiquidatorUnbondingDelay;
+	type MaxPriceConfidence = MaxPriceConfidence;
 	type WeightInfo = weights::cdp_engine::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const DepositPerAuthorization: Balance = DOLLARS;
+}
+
 impl module_honzon::Config for Runtime {
 	type Event = Event;
+	type Currency = Currency<Runtime, GetNativeCurrencyId>;
+	type DepositPerAuthorization = DepositPerAuthorization;
 	type WeightInfo = weights::honzon::WeightInfo<Runtime>;
 }
 
@@ -994,12 +1149,14 @@ impl module_emergency_shutdown::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRootOrHalfGeneralCouncil;
+	type OnShutdown = module_cdp_engine::OnEmergencyShutdown<Runtime>;
 	type WeightInfo = weights::emergency_shutdown::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 1000);	// 0.1%
 	pub const TradingPathLimit: u32 = 3;
+	pub const TwapPeriod: BlockNumber = 10 * MINUTES;
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::new(CurrencyId::Token(TokenSymbol::AUSD), CurrencyId::Token(TokenSymbol::DOT)),
 		TradingPair::new(CurrencyId::Token(TokenSymbol::AUSD), CurrencyId::Token(TokenSymbol::XBTC)),
@@ -1014,6 +1171,7 @@ impl module_dex::Config for Runtime {
 	type Currency = Currencies;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TwapPeriod = TwapPeriod;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::dex::WeightInfo<Runtime>;
@@ -1022,6 +1180,9 @@ impl module_dex::Config for Runtime {
 
 parameter_types! {
 	pub const MaxAuctionsCount: u32 = 100;
+	pub const BadDebtDelay: BlockNumber = DAYS;
+	pub const DebitAuctionFixedSize: Balance = 1_000 * DOLLARS;
+	pub const DebitAuctionInitialAmount: Balance = 10_000 * DOLLARS;
 }
 
 impl module_cdp_treasury::Config for Runtime {
@@ -1033,6 +1194,10 @@ impl module_cdp_treasury::Config for Runtime {
 	type DEX = Dex;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type BadDebtDelay = BadDebtDelay;
+	type DebitAuctionFixedSize = DebitAuctionFixedSize;
+	type DebitAuctionInitialAmount = DebitAuctionInitialAmount;
+	type EmergencyShutdown = EmergencyShutdown;
 	type WeightInfo = weights::cdp_treasury::WeightInfo<Runtime>;
 }
 
@@ -1042,6 +1207,8 @@ parameter_types! {
 }
 
 impl module_transaction_payment::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -1050,9 +1217,34 @@ impl module_transaction_payment::Config for Runtime {
 	type OnTransactionPayment = AcalaTreasury;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = WeightToFee;
-	type FeeMultiplierUpdate = TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+	type FeeMultiplierUpdate = BoundedFeeAdjustment<
+		Self,
+		TargetBlockFullness,
+		AdjustmentVariable,
+		MaxMultiplierStep,
+		MinimumMultiplier,
+		MaximumMultiplier,
+	>;
 	type DEX = Dex;
+	type LinkedEvmBalance = EvmAccounts;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type MaxWeightToFeeStep = MaxWeightToFeeStep;
+	type MinDispatchClassFeeMultiplier = MinDispatchClassFeeMultiplier;
+	type MaxDispatchClassFeeMultiplier = MaxDispatchClassFeeMultiplier;
+	type OperationalFeeMultiplier = OperationalFeeMultiplier;
+	type FeeBurnModuleId = FeeBurnModuleId;
+	type FeeBurnPeriod = FeeBurnPeriod;
+	type NativeLockedBalance = runtime_common::NativeLockedBalance<Runtime>;
+	type FreeTransactionQuota = FreeTransactionQuota;
+	type FreeTransactionPeriod = FreeTransactionPeriod;
+	type FreeTransactionMinLocked = FreeTransactionMinLocked;
+	type MaxFeeDiscountTiers = MaxFeeDiscountTiers;
+	type OnboardingSubsidyModuleId = OnboardingSubsidyModuleId;
+	type OnboardingSubsidizedTransactions = OnboardingSubsidizedTransactions;
+	type OnboardingSubsidyCap = OnboardingSubsidyCap;
+	type MaxOnboardingsPerPeriod = MaxOnboardingsPerPeriod;
+	type OnboardingPeriod = OnboardingPeriod;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
 	type WeightInfo = weights::transaction_payment::WeightInfo<Runtime>;
 }
 
@@ -1075,6 +1267,11 @@ impl orml_rewards::Config for Runtime {
 
 parameter_types! {
 	pub const AccumulatePeriod: BlockNumber = MINUTES;
+	pub const ClaimGracePeriod: BlockNumber = 7 * DAYS;
+	pub ReferralRewardRate: Rate = Rate::saturating_from_rational(5, 100);
+	pub const IncentiveEmissionHistoryDepth: u32 = 24 * 60; // a day of the accumulation periods
+	pub const MaxIncentiveDonationPeriods: u32 = 30 * 24 * 60; // 30 days of the accumulation periods
+	pub const MaxIncentiveCleanUpBatch: u32 = 200;
 }
 
 impl module_incentives::Config for Runtime {
@@ -1089,7 +1286,16 @@ impl module_incentives::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type Currency = Currencies;
 	type DEX = Dex;
+	type CompoundPriceImpactLimit = MaxSlippageSwapWithDEX;
 	type EmergencyShutdown = EmergencyShutdown;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type VestedTransfer = IncentiveRewardVesting;
+	type ClaimGracePeriod = ClaimGracePeriod;
+	type ReferralRewardRate = ReferralRewardRate;
+	type EmissionHistoryDepth = IncentiveEmissionHistoryDepth;
+	type MaxDonationPeriods = MaxIncentiveDonationPeriods;
+	type MaxCleanUpBatch = MaxIncentiveCleanUpBatch;
+	type TreasuryAccount = TreasuryModuleAccount;
 	type ModuleId = IncentivesModuleId;
 	type WeightInfo = weights::incentives::WeightInfo<Runtime>;
 }
@@ -1112,6 +1318,11 @@ parameter_types! {
 	pub const GetStakingCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub PoolAccountIndexes: Vec<u32> = vec![1, 2, 3, 4];
+	pub const MaxFastMatchCount: u32 = 10;
+	pub HomaTreasuryAccount: AccountId = HomaTreasuryModuleId::get().into_account();
+	pub MaxRewardFeeRatio: Ratio = Ratio::saturating_from_rational(20, 100);
+	pub StakingPoolMaxWithdrawUnbondedWeight: Weight = Perbill::from_percent(5) * BlockWeights::get().max_block;
+	pub StakingPoolWithdrawUnbondedWeight: Weight = RocksDbWeight::get().reads_writes(4, 3);
 }
 
 impl module_staking_pool::Config for Runtime {
@@ -1126,10 +1337,18 @@ impl module_staking_pool::Config for Runtime {
 	type Nominees = NomineesElection;
 	type Bridge = PolkadotBridge;
 	type Currency = Currencies;
+	type MaxFastMatchCount = MaxFastMatchCount;
+	type HomaTreasuryAccount = HomaTreasuryAccount;
+	type MaxRewardFeeRatio = MaxRewardFeeRatio;
+	type MaxWithdrawUnbondedWeight = StakingPoolMaxWithdrawUnbondedWeight;
+	type WithdrawUnbondedWeight = StakingPoolWithdrawUnbondedWeight;
 }
 
 impl module_homa::Config for Runtime {
+	type Event = Event;
 	type Homa = StakingPool;
+	type EmergencyOrigin = EnsureRootOrHalfHomaCouncil;
+	type MaxFastMatchCount = MaxFastMatchCount;
 	type WeightInfo = weights::homa::WeightInfo<Runtime>;
 }
 
@@ -1138,20 +1357,32 @@ parameter_types! {
 	pub const NominateesCount: u32 = 7;
 	pub const MaxUnlockingChunks: u32 = 7;
 	pub const NomineesElectionBondingDuration: EraIndex = 7;
+	pub NomineesUnderperformingRewardRatio: Ratio = Ratio::saturating_from_rational(1, 2);
+	pub const NomineesMaxUnderperformingEras: u32 = 3;
 }
 
 impl module_nominees_election::Config for Runtime {
+	type Event = Event;
 	type Currency = Currency<Runtime, GetLiquidCurrencyId>;
 	type PolkadotAccountId = AccountId;
 	type MinBondThreshold = MinCouncilBondThreshold;
 	type BondingDuration = NomineesElectionBondingDuration;
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type ValidatorStatus = PolkadotBridge;
+	type UpdateOrigin = EnsureRootOrHalfHomaCouncil;
+	type UnderperformingRewardRatio = NomineesUnderperformingRewardRatio;
+	type MaxUnderperformingEras = NomineesMaxUnderperformingEras;
 }
 
 parameter_types! {
 	pub const CreateClassDeposit: Balance = 500 * MILLICENTS;
 	pub const CreateTokenDeposit: Balance = 100 * MILLICENTS;
+	pub const MaxRoyaltyPerMille: u16 = 100;
+	pub const MarketFeePerMille: u16 = 20;
+	pub const MaxAttributeKeyLength: u32 = 64;
+	pub const MaxAttributeValueLength: u32 = 256;
+	pub const MaxMintRecipients: u32 = 1000;
 }
 
 impl module_nft::Config for Runtime {
@@ -1159,7 +1390,16 @@ impl module_nft::Config for Runtime {
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type ModuleId = NftModuleId;
+	type MaxRoyaltyPerMille = MaxRoyaltyPerMille;
 	type Currency = Currency<Runtime, GetNativeCurrencyId>;
+	type MultiCurrency = Currencies;
+	type Auction = Auction;
+	type MarketFeePerMille = MarketFeePerMille;
+	type TreasuryAccount = TreasuryModuleAccount;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxMintRecipients = MaxMintRecipients;
 	type WeightInfo = weights::nft::WeightInfo<Runtime>;
 }
 
@@ -1199,6 +1439,8 @@ impl pallet_proxy::Config for Runtime {
 parameter_types! {
 	pub const RENBTCCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
 	pub const RENBTCIdentifier: [u8; 32] = hex!["f6b5b360905f856404bd4cf39021b82209908faa44159e68ea207ab8a5e13197"];
+	// 0.001 renBTC
+	pub const RenVmMinOnboardingAmount: Balance = 100_000;
 }
 
 impl ecosystem_renvm_bridge::Config for Runtime {
@@ -1206,6 +1448,8 @@ impl ecosystem_renvm_bridge::Config for Runtime {
 	type Currency = Currency<Runtime, RENBTCCurrencyId>;
 	type CurrencyIdentifier = RENBTCIdentifier;
 	type UnsignedPriority = runtime_common::RenvmBridgeUnsignedPriority;
+	type OnMinted = module_transaction_payment::OnboardNewAccount<Runtime>;
+	type MinOnboardingAmount = RenVmMinOnboardingAmount;
 }
 
 parameter_types! {
@@ -1217,6 +1461,7 @@ parameter_types! {
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
 	pub const DeveloperDeposit: Balance = DOLLARS;
 	pub const DeploymentFee: Balance = DOLLARS;
+	pub const EvmTransactionIndexPeriod: BlockNumber = 28 * DAYS;
 }
 
 pub type MultiCurrencyPrecompile =
@@ -1238,6 +1483,7 @@ pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 >;
 
 pub type DexPrecompile = runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, Dex>;
+pub type HonzonPrecompile = runtime_common::HonzonPrecompile<AccountId, EvmAddressMapping<Runtime>, Honzon>;
 
 impl module_evm::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
@@ -1255,6 +1501,7 @@ impl module_evm::Config for Runtime {
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HonzonPrecompile,
 	>;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
@@ -1265,6 +1512,7 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryModuleAccount;
 	type FreeDeploymentOrigin = EnsureRootOrHalfGeneralCouncil;
+	type TransactionIndexPeriod = EvmTransactionIndexPeriod;
 	type WeightInfo = weights::evm::WeightInfo<Runtime>;
 }
 
@@ -1287,7 +1535,7 @@ construct_runtime!(
 		// Tokens & Related
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 
-		TransactionPayment: module_transaction_payment::{Module, Call, Storage},
+		TransactionPayment: module_transaction_payment::{Module, Call, Storage, Event<T>},
 		EvmAccounts: module_evm_accounts::{Module, Call, Storage, Event<T>},
 		Currencies: module_currencies::{Module, Call, Event<T>},
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
@@ -1355,14 +1603,14 @@ construct_runtime!(
 		EmergencyShutdown: module_emergency_shutdown::{Module, Storage, Call, Event<T>},
 
 		// Homa
-		Homa: module_homa::{Module, Call},
-		NomineesElection: module_nominees_election::{Module, Call, Storage},
+		Homa: module_homa::{Module, Call, Storage, Event<T>},
+		NomineesElection: module_nominees_election::{Module, Call, Storage, Event<T>},
 		StakingPool: module_staking_pool::{Module, Call, Storage, Event<T>, Config},
 		PolkadotBridge: module_polkadot_bridge::{Module, Call, Storage},
 
 		// Acala Other
 		Incentives: module_incentives::{Module, Storage, Call, Event<T>},
-		NFT: module_nft::{Module, Call, Event<T>},
+		NFT: module_nft::{Module, Call, Storage, Event<T>},
 
 		// Ecosystem modules
 		RenVmBridge: ecosystem_renvm_bridge::{Module, Call, Config, Storage, Event<T>, ValidateUnsigned},
@@ -1407,6 +1655,154 @@ pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExt
 pub type Executive =
 	frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllModules>;
 
+/// The health of the Honzon position of the account for the collateral
+/// currency.
+#[cfg(not(feature = "disable-runtime-api"))]
+fn loan_health(account: &AccountId, currency_id: CurrencyId) -> module_honzon_rpc_runtime_api::LoanHealth<Balance> {
+	let module_loans::Position { collateral, debit } = Loans::positions(currency_id, account);
+	module_honzon_rpc_runtime_api::LoanHealth {
+		collateral,
+		debit_value: CdpEngine::get_debit_value(currency_id, debit),
+		collateral_ratio: CdpEngine::get_collateral_ratio(currency_id, collateral, debit),
+		liquidation_price: CdpEngine::get_liquidation_price(currency_id, collateral, debit),
+		accrued_stability_fee: CdpEngine::get_accrued_stability_fee(currency_id, debit),
+		max_additional_borrow: CdpEngine::get_max_additional_debit_value(currency_id, collateral, debit),
+	}
+}
+
+/// Aggregate the portfolio of the account across the modules.
+#[cfg(not(feature = "disable-runtime-api"))]
+fn account_portfolio(
+	who: &AccountId,
+) -> runtime_common_rpc_runtime_api::AccountPortfolio<CurrencyId, module_incentives::PoolId, Balance> {
+	let mut balances = vec![(
+		GetNativeCurrencyId::get(),
+		Balances::free_balance(who),
+		Balances::reserved_balance(who),
+	)];
+	let mut lp_positions = vec![];
+	for (currency_id, data) in orml_tokens::Accounts::<Runtime>::iter_prefix(who) {
+		if data.free.is_zero() && data.reserved.is_zero() {
+			continue;
+		}
+		balances.push((currency_id, data.free, data.reserved));
+
+		if let Some((currency_id_0, currency_id_1)) = currency_id.split_dex_share_currency_id() {
+			let share = data.free.saturating_add(data.reserved);
+			let total_shares = <Tokens as orml_traits::MultiCurrency<AccountId>>::total_issuance(currency_id);
+			let proportion = Ratio::checked_from_rational(share, total_shares).unwrap_or_default();
+			let (pool_0, pool_1) =
+				<Dex as module_support::DEXManager<AccountId, CurrencyId, Balance>>::get_liquidity_pool(
+					currency_id_0,
+					currency_id_1,
+				);
+			lp_positions.push(runtime_common_rpc_runtime_api::LpPosition {
+				currency_id,
+				share,
+				underlying: vec![
+					(currency_id_0, proportion.saturating_mul_int(pool_0)),
+					(currency_id_1, proportion.saturating_mul_int(pool_1)),
+				],
+			});
+		}
+	}
+
+	let incentives = Incentives::participated_pools(who)
+		.into_iter()
+		.map(
+			|(pool_id, share, pending_rewards, _)| runtime_common_rpc_runtime_api::IncentivePosition {
+				pool_id,
+				share,
+				pending_rewards,
+			},
+		)
+		.collect();
+
+	let loans = CollateralCurrencyIds::get()
+		.into_iter()
+		.filter(|currency_id| {
+			let position = Loans::positions(currency_id, who);
+			!position.collateral.is_zero() || !position.debit.is_zero()
+		})
+		.map(|currency_id| (currency_id, loan_health(who, currency_id)))
+		.collect();
+
+	runtime_common_rpc_runtime_api::AccountPortfolio {
+		balances,
+		lp_positions,
+		incentives,
+		loans,
+	}
+}
+
+/// Collect the key metrics of the protocol across the modules.
+#[cfg(not(feature = "disable-runtime-api"))]
+fn protocol_health() -> runtime_common_rpc_runtime_api::ProtocolHealth<CurrencyId, Balance, ExchangeRate> {
+	let collaterals = CollateralCurrencyIds::get()
+		.into_iter()
+		.map(|currency_id| {
+			let module_loans::Position { collateral, debit } = Loans::total_positions(currency_id);
+			runtime_common_rpc_runtime_api::CollateralHealth {
+				currency_id,
+				collateral,
+				collateral_value: <Prices as module_support::PriceProvider<CurrencyId>>::get_relative_price(
+					currency_id,
+					GetStableCurrencyId::get(),
+				)
+				.map(|price| price.saturating_mul_int(collateral)),
+				debit_value: CdpEngine::get_debit_value(currency_id, debit),
+				collateral_in_auction: AuctionManager::total_collateral_in_auction(currency_id),
+			}
+		})
+		.collect::<Vec<_>>();
+	let total_debit_value = collaterals
+		.iter()
+		.fold(0, |total: Balance, health| total.saturating_add(health.debit_value));
+	let total_collateral_value = collaterals.iter().fold(0, |total: Balance, health| {
+		total.saturating_add(health.collateral_value.unwrap_or_default())
+	});
+
+	let ledger = StakingPool::staking_pool_ledger();
+	let mut unbonding = module_staking_pool::Unbonding::<Runtime>::iter()
+		.map(|(era, (total, claimed, _))| (era, total, claimed))
+		.collect::<Vec<_>>();
+	unbonding.sort_by_key(|(era, _, _)| *era);
+
+	// the ended auctions are kept until they're settled, they're counted in the
+	// pending settlements
+	let active_auctions = |auction_ids: &mut dyn Iterator<Item = AuctionId>| {
+		auction_ids
+			.filter(|auction_id| !module_auction_manager::PendingSettlements::<Runtime>::contains_key(auction_id))
+			.count() as u32
+	};
+
+	runtime_common_rpc_runtime_api::ProtocolHealth {
+		collaterals,
+		total_debit_value,
+		total_collateral_value,
+		surplus_pool: CdpTreasury::surplus_pool(),
+		debit_pool: CdpTreasury::debit_pool(),
+		auctions: (
+			active_auctions(&mut module_auction_manager::CollateralAuctions::<Runtime>::iter_keys()),
+			active_auctions(&mut module_auction_manager::DutchCollateralAuctions::<Runtime>::iter_keys()),
+			active_auctions(&mut module_auction_manager::DebitAuctions::<Runtime>::iter_keys()),
+			active_auctions(&mut module_auction_manager::SurplusAuctions::<Runtime>::iter_keys()),
+		),
+		pending_settlements: {
+			let (head, tail) = AuctionManager::settlement_queue_range();
+			tail.wrapping_sub(head)
+		},
+		target_in_auction: AuctionManager::total_target_in_auction(),
+		debit_in_auction: AuctionManager::total_debit_in_auction(),
+		surplus_in_auction: AuctionManager::total_surplus_in_auction(),
+		liquid_exchange_rate: StakingPool::liquid_exchange_rate(),
+		bonded: ledger.bonded,
+		unbonding_to_free: ledger.unbonding_to_free,
+		to_unbond_next_era: ledger.to_unbond_next_era.0,
+		unbonding,
+	}
+}
+
 #[cfg(not(feature = "disable-runtime-api"))]
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
@@ -1429,6 +1825,38 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl runtime_common_rpc_runtime_api::DryRunApi<Block> for Runtime {
+		fn dry_run(extrinsic: <Block as BlockT>::Extrinsic) -> runtime_common_rpc_runtime_api::DryRunResult {
+			frame_support::storage::with_transaction(|| {
+				// only the events of the extrinsic are returned
+				System::reset_events();
+				let weight_before = System::block_weight().total();
+				let result = Executive::apply_extrinsic(extrinsic);
+
+				sp_runtime::TransactionOutcome::Rollback(runtime_common_rpc_runtime_api::DryRunResult {
+					result,
+					events: System::events().into_iter().map(|record| record.event.encode()).collect(),
+					weight: System::block_weight().total().saturating_sub(weight_before),
+				})
+			})
+		}
+	}
+
+	impl runtime_common_rpc_runtime_api::EventsApi<Block> for Runtime {
+		fn events() -> Vec<(Option<u32>, Vec<u8>)> {
+			System::events()
+				.into_iter()
+				.map(|record| {
+					let extrinsic_index = match record.phase {
+						frame_system::Phase::ApplyExtrinsic(index) => Some(index),
+						_ => None,
+					};
+					(extrinsic_index, record.event.encode())
+				})
+				.collect()
+		}
+	}
+
 	impl sp_block_builder::BlockBuilder<Block> for Runtime {
 		fn apply_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> ApplyExtrinsicResult {
 			Executive::apply_extrinsic(extrinsic)
@@ -1576,6 +2004,57 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_transaction_payment_rpc_runtime_api::TransactionPaymentFeeApi<
+		Block,
+		AccountId,
+		Balance,
+		CurrencyId,
+	> for Runtime {
+		fn query_call_fee(who: AccountId, encoded_call: Vec<u8>, len: u32) -> Option<module_transaction_payment_rpc_runtime_api::FeeDetails<Balance, CurrencyId>> {
+			let call = Call::decode(&mut &*encoded_call).ok()?;
+			Some(TransactionPayment::query_call_fee(&who, &call, len))
+		}
+
+		fn next_fee_multiplier() -> Multiplier {
+			TransactionPayment::next_fee_multiplier()
+		}
+	}
+
+	impl module_incentives_rpc_runtime_api::IncentivesApi<
+		Block,
+		AccountId,
+		module_incentives::PoolId,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn claim_reward_deduction_rate(pool_id: module_incentives::PoolId) -> Rate {
+			Incentives::claim_reward_deduction_rates(pool_id)
+		}
+
+		fn pending_rewards(pool_id: module_incentives::PoolId, who: AccountId) -> Vec<(CurrencyId, Balance, Balance)> {
+			Incentives::pending_rewards(pool_id, &who)
+		}
+
+		fn participated_pools(
+			who: AccountId,
+		) -> Vec<(module_incentives::PoolId, Balance, Vec<(CurrencyId, Balance, Balance)>, Rate)> {
+			Incentives::participated_pools(&who)
+		}
+	}
+
+	impl module_auction_manager_rpc_runtime_api::AuctionManagerApi<
+		Block,
+		AuctionId,
+		module_auction_manager::ActiveAuctionInfo<AccountId, BlockNumber>,
+	> for Runtime {
+		fn active_auctions(
+			start: AuctionId,
+			limit: u32,
+		) -> Vec<module_auction_manager::ActiveAuctionInfo<AccountId, BlockNumber>> {
+			AuctionManager::active_auctions(start, limit)
+		}
+	}
+
 	impl orml_oracle_rpc_runtime_api::OracleApi<
 		Block,
 		DataProviderId,
@@ -1613,6 +2092,120 @@ impl_runtime_apis! {
 		fn get_liquid_staking_exchange_rate() -> ExchangeRate {
 			StakingPool::liquid_exchange_rate()
 		}
+
+		fn get_redemption_info(account: AccountId) -> module_staking_pool_rpc_runtime_api::RedemptionInfo<Balance> {
+			module_staking_pool_rpc_runtime_api::RedemptionInfo {
+				current_era: StakingPool::current_era(),
+				requests: StakingPool::get_redemption_requests(&account)
+					.into_iter()
+					.map(|(claimable_era, amount)| module_staking_pool_rpc_runtime_api::RedemptionRequest {
+						claimable_era,
+						amount,
+					})
+					.collect(),
+				free_pool_available: StakingPool::get_available_free_pool(),
+			}
+		}
+	}
+
+	impl module_honzon_rpc_runtime_api::HonzonApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn get_loan_health(account: AccountId, currency_id: CurrencyId) -> Option<module_honzon_rpc_runtime_api::LoanHealth<Balance>> {
+			Some(loan_health(&account, currency_id))
+		}
+	}
+
+	impl runtime_common_rpc_runtime_api::PortfolioApi<
+		Block,
+		AccountId,
+		CurrencyId,
+		module_incentives::PoolId,
+		Balance,
+	> for Runtime {
+		fn account_portfolio(
+			who: AccountId,
+		) -> runtime_common_rpc_runtime_api::AccountPortfolio<CurrencyId, module_incentives::PoolId, Balance> {
+			account_portfolio(&who)
+		}
+	}
+
+	impl runtime_common_rpc_runtime_api::HealthApi<Block, CurrencyId, Balance, ExchangeRate> for Runtime {
+		fn protocol_health() -> runtime_common_rpc_runtime_api::ProtocolHealth<CurrencyId, Balance, ExchangeRate> {
+			protocol_health()
+		}
+	}
+
+	impl runtime_common_rpc_runtime_api::IssuanceApi<Block, BlockNumber, Balance> for Runtime {
+		fn issuance_stats(periods: u32) -> runtime_common_rpc_runtime_api::IssuanceStats<BlockNumber, Balance> {
+			// the reward of the active era is not known until the era ends
+			let active_era = Staking::active_era().map(|era| era.index).unwrap_or_default();
+			// the rewards of the eras beyond the history depth are pruned
+			let staking_rewards = (1..=periods.min(active_era).min(Staking::history_depth()))
+				.filter_map(|index| {
+					let era = active_era.checked_sub(index)?;
+					Staking::eras_validator_reward(era).map(|reward| (era, reward))
+				})
+				.collect();
+
+			runtime_common_rpc_runtime_api::IssuanceStats {
+				total_issuance: Balances::total_issuance(),
+				fee_burned: TransactionPayment::total_fee_burned(),
+				treasury_balance: Balances::free_balance(TreasuryModuleAccount::get()),
+				staking_rewards,
+				incentive_emissions: Incentives::last_incentive_emissions(System::block_number(), periods),
+			}
+		}
+	}
+
+	impl module_cdp_treasury_rpc_runtime_api::CDPTreasuryApi<
+		Block,
+		Balance,
+	> for Runtime {
+		fn get_treasury_balance() -> module_cdp_treasury_rpc_runtime_api::TreasuryBalance<Balance> {
+			module_cdp_treasury_rpc_runtime_api::TreasuryBalance {
+				surplus_pool: CdpTreasury::surplus_pool(),
+				debit_pool: CdpTreasury::debit_pool(),
+				queued_debit: CdpTreasury::total_queued_debit(),
+				bad_debt: CdpTreasury::bad_debt(),
+				debit_in_auction: AuctionManager::total_debit_in_auction(),
+			}
+		}
+	}
+
+	impl module_prices_rpc_runtime_api::PricesApi<
+		Block,
+		CurrencyId,
+		Price,
+		Moment,
+	> for Runtime {
+		fn get_prices(currency_ids: Vec<CurrencyId>) -> Vec<Option<Price>> {
+			currency_ids
+				.into_iter()
+				.map(<Prices as module_support::PriceProvider<CurrencyId>>::get_price)
+				.collect()
+		}
+
+		fn get_feeds_updated_at(currency_ids: Vec<CurrencyId>) -> Vec<Vec<Moment>> {
+			currency_ids.into_iter().map(Prices::get_feeds_updated_at).collect()
+		}
+
+		fn get_relative_price(base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
+			<Prices as module_support::PriceProvider<CurrencyId>>::get_relative_price(base_currency_id, quote_currency_id)
+		}
+	}
+
+	impl module_nft_rpc_runtime_api::NFTApi<
+		Block,
+		AccountId,
+		u32,
+		u64,
+	> for Runtime {
+		fn nfts_by_owner(owner: AccountId, start: u32, limit: u32) -> Vec<(u32, u64)> {
+			NFT::tokens_by_owner(&owner, start, limit)
+		}
 	}
 
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
@@ -1671,6 +2264,72 @@ impl_runtime_apis! {
 			)
 		}
 
+		fn estimate_resources(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u32,
+			storage_limit: u32,
+		) -> Result<EstimateResourcesInfo<Balance>, sp_runtime::DispatchError> {
+			use frame_support::weights::GetDispatchInfo;
+			use sp_runtime::traits::UniqueSaturatedInto;
+
+			let mut config = <Runtime as module_evm::Config>::config().clone();
+			config.estimate = true;
+
+			let (exit_reason, output, used_gas, used_storage) = match to {
+				Some(to) => {
+					let info = module_evm::Runner::<Runtime>::call(
+						from,
+						from,
+						to,
+						data.clone(),
+						value,
+						gas_limit.into(),
+						storage_limit,
+						&config,
+					)?;
+					(info.exit_reason, info.output, info.used_gas, info.used_storage)
+				}
+				None => {
+					let info = module_evm::Runner::<Runtime>::create(
+						from,
+						data.clone(),
+						value,
+						gas_limit.into(),
+						storage_limit,
+						&config,
+					)?;
+					(info.exit_reason, info.output, info.used_gas, info.used_storage)
+				}
+			};
+
+			// no storage limit is needed if the storage is freed
+			let storage = used_storage.max(0) as u32;
+			let gas: u64 = used_gas.unique_saturated_into();
+			let call = match to {
+				Some(to) => Call::EVM(module_evm::Call::call(to, data, value, gas, storage)),
+				None => Call::EVM(module_evm::Call::create(data, value, gas, storage)),
+			};
+			// the length of the signature and the signed extensions is not included
+			let fee = TransactionPayment::compute_fee(call.encoded_size() as u32, &call.get_dispatch_info(), 0);
+
+			Ok(EstimateResourcesInfo {
+				exit_reason,
+				output,
+				gas: used_gas,
+				storage,
+				storage_deposit: <Runtime as module_evm::Config>::StorageDepositPerByte::get()
+					.saturating_mul(storage.into()),
+				fee,
+			})
+		}
+
+		fn transaction_by_hash(hash: H256) -> Option<(BlockNumber, u32)> {
+			EVM::transaction_index(hash)
+		}
+
 	}
 
 
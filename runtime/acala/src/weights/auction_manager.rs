@@ -24,4 +24,33 @@ impl<T: frame_system::Config> module_auction_manager::WeightInfo for WeightInfo<
 			.saturating_add(DbWeight::get().reads(9 as Weight))
 			.saturating_add(DbWeight::get().writes(7 as Weight))
 	}
+	fn set_dutch_auction_params() -> Weight {
+		(24_816_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn take_collateral_auction() -> Weight {
+		(198_604_000 as Weight)
+			.saturating_add(DbWeight::get().reads(10 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
+	}
+	fn set_minimum_increment() -> Weight {
+		(23_947_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn settle_auction() -> Weight {
+		(214_873_000 as Weight)
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+	}
+	fn relist() -> Weight {
+		(236_514_000 as Weight)
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().writes(13 as Weight))
+	}
+	fn set_no_bid_sale_max_slippage() -> Weight {
+		(22_615_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn partial_bid() -> Weight {
+		(118_341_000 as Weight)
+			.saturating_add(DbWeight::get().reads(11 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
+	}
 }
@@ -11,10 +11,11 @@ pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> module_emergency_shutdown::WeightInfo for WeightInfo<T> {
 	fn emergency_shutdown(c: u32) -> Weight {
 		(564_107_000 as Weight)
-			.saturating_add((32_606_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((71_284_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(DbWeight::get().reads(36 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
 			.saturating_add(DbWeight::get().writes(1 as Weight))
-			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
 	}
 	fn open_collateral_refund() -> Weight {
 		(157_252_000 as Weight)
@@ -4,10 +4,13 @@ use sp_std::marker::PhantomData;
 
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> module_homa::WeightInfo for WeightInfo<T> {
-	fn mint() -> Weight {
+	fn mint(c: u32) -> Weight {
 		(71_000_000 as Weight)
+			.saturating_add((32_000_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
 			.saturating_add(DbWeight::get().writes(6 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
 	}
 	fn redeem(strategy: &module_homa::RedeemStrategy) -> Weight {
 		match strategy {
@@ -27,4 +30,12 @@ impl<T: frame_system::Config> module_homa::WeightInfo for WeightInfo<T> {
 			.saturating_add(DbWeight::get().reads(6 as Weight))
 			.saturating_add(DbWeight::get().writes(2 as Weight))
 	}
+	fn emergency_unbond() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
+	}
+	fn unfreeze_mint() -> Weight {
+		(10_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,180 @@
+//! Runtime API definitions aggregated across modules: the account portfolio,
+//! the dry run of extrinsics, the events of the blocks, the issuance
+//! statistics of the native currency and the health of the protocol.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use frame_support::weights::Weight;
+pub use module_honzon_rpc_runtime_api::LoanHealth;
+use sp_runtime::{traits::Block as BlockT, ApplyExtrinsicResult, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// The DEX liquidity held by an account.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct LpPosition<CurrencyId, Balance> {
+	/// The DEX share currency.
+	pub currency_id: CurrencyId,
+	/// The DEX share amount held, including the reserved.
+	pub share: Balance,
+	/// The amounts of the currencies in the liquidity pool the share is
+	/// worth.
+	pub underlying: Vec<(CurrencyId, Balance)>,
+}
+
+/// The share of an account in an incentive pool.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct IncentivePosition<PoolId, CurrencyId, Balance> {
+	/// The incentive pool.
+	pub pool_id: PoolId,
+	/// The share of the account in the pool.
+	pub share: Balance,
+	/// The pending rewards for each reward currency, the actual rewards to
+	/// receive and the deductions if claimed now.
+	pub pending_rewards: Vec<(CurrencyId, Balance, Balance)>,
+}
+
+/// The portfolio of an account across the modules.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct AccountPortfolio<CurrencyId, PoolId, Balance> {
+	/// The free and the reserved balance of each currency held, the native
+	/// currency first.
+	pub balances: Vec<(CurrencyId, Balance, Balance)>,
+	/// The DEX liquidity held.
+	pub lp_positions: Vec<LpPosition<CurrencyId, Balance>>,
+	/// The shares and the pending rewards in the incentive pools.
+	pub incentives: Vec<IncentivePosition<PoolId, CurrencyId, Balance>>,
+	/// The Honzon positions with their health for each collateral currency.
+	pub loans: Vec<(CurrencyId, LoanHealth<Balance>)>,
+}
+
+/// The outcome of an extrinsic dry run against the current state.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct DryRunResult {
+	/// The result of applying the extrinsic, including the dispatch error if
+	/// the dispatch failed.
+	pub result: ApplyExtrinsicResult,
+	/// The SCALE encoded runtime events emitted by the extrinsic.
+	pub events: Vec<Vec<u8>>,
+	/// The weight consumed by the extrinsic.
+	pub weight: Weight,
+}
+
+/// The issuance statistics of the native currency.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct IssuanceStats<BlockNumber, Balance> {
+	/// The total issuance.
+	pub total_issuance: Balance,
+	/// The total amount burned from the fees.
+	pub fee_burned: Balance,
+	/// The free balance of the treasury.
+	pub treasury_balance: Balance,
+	/// The total staking rewards of the validators in the last eras, with the
+	/// era index, the latest first.
+	pub staking_rewards: Vec<(u32, Balance)>,
+	/// The incentive rewards issued in the last accumulation periods, with
+	/// the block of the period, the latest first.
+	pub incentive_emissions: Vec<(BlockNumber, Balance)>,
+}
+
+/// The CDP positions of a collateral currency.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct CollateralHealth<CurrencyId, Balance> {
+	/// The collateral currency.
+	pub currency_id: CurrencyId,
+	/// The total collateral of the positions.
+	pub collateral: Balance,
+	/// The value of the collateral in the stable currency, `None` if there's
+	/// no price.
+	pub collateral_value: Option<Balance>,
+	/// The total debit value of the positions in the stable currency.
+	pub debit_value: Balance,
+	/// The collateral in the auctions.
+	pub collateral_in_auction: Balance,
+}
+
+/// The key metrics of the protocol for monitoring.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct ProtocolHealth<CurrencyId, Balance, ExchangeRate> {
+	/// The CDP positions of each collateral currency.
+	pub collaterals: Vec<CollateralHealth<CurrencyId, Balance>>,
+	/// The total debit value of all the CDP positions.
+	pub total_debit_value: Balance,
+	/// The total value of the collateral with the price.
+	pub total_collateral_value: Balance,
+	/// The surplus pool of the CDP treasury.
+	pub surplus_pool: Balance,
+	/// The debit pool, i.e. the deficit, of the CDP treasury.
+	pub debit_pool: Balance,
+	/// The number of the active collateral, dutch collateral, debit and
+	/// surplus auctions, the ended auctions pending the settlement excluded.
+	pub auctions: (u32, u32, u32, u32),
+	/// The number of the ended auctions pending the settlement.
+	pub pending_settlements: u32,
+	/// The target of the active collateral auctions.
+	pub target_in_auction: Balance,
+	/// The debit of the active debit auctions.
+	pub debit_in_auction: Balance,
+	/// The surplus of the active surplus auctions.
+	pub surplus_in_auction: Balance,
+	/// The exchange rate of the liquid currency of Homa.
+	pub liquid_exchange_rate: ExchangeRate,
+	/// The total bonded of Homa.
+	pub bonded: Balance,
+	/// The total unbonding of Homa to the free pool.
+	pub unbonding_to_free: Balance,
+	/// The amount to unbond at the beginning of the next era.
+	pub to_unbond_next_era: Balance,
+	/// The total and the claimed unbonding of each era the unbonding expires
+	/// at, ordered by the era.
+	pub unbonding: Vec<(u32, Balance, Balance)>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait PortfolioApi<AccountId, CurrencyId, PoolId, Balance> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		PoolId: Codec,
+		Balance: Codec,
+	{
+		/// The balances, the DEX liquidity, the incentive shares with the
+		/// pending rewards and the Honzon positions of `who`, in one call.
+		fn account_portfolio(who: AccountId) -> AccountPortfolio<CurrencyId, PoolId, Balance>;
+	}
+
+	pub trait DryRunApi {
+		/// Apply the signed or unsigned extrinsic against the current state,
+		/// all the changes are discarded afterwards.
+		fn dry_run(extrinsic: <Block as BlockT>::Extrinsic) -> DryRunResult;
+	}
+
+	pub trait EventsApi {
+		/// The SCALE encoded runtime events of the block, with the index of
+		/// the extrinsic emitting the event, `None` if not emitted by an
+		/// extrinsic.
+		fn events() -> Vec<(Option<u32>, Vec<u8>)>;
+	}
+
+	pub trait IssuanceApi<BlockNumber, Balance> where
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// The issuance statistics with the emission of at most the last
+		/// `periods` eras of the staking and accumulation periods of the
+		/// incentives.
+		fn issuance_stats(periods: u32) -> IssuanceStats<BlockNumber, Balance>;
+	}
+
+	pub trait HealthApi<CurrencyId, Balance, ExchangeRate> where
+		CurrencyId: Codec,
+		Balance: Codec,
+		ExchangeRate: Codec,
+	{
+		/// The system debt against the collateral, the CDP treasury, the
+		/// auction backlog and the Homa unbonding pipeline, in one call.
+		fn protocol_health() -> ProtocolHealth<CurrencyId, Balance, ExchangeRate>;
+	}
+}
@@ -0,0 +1,268 @@
+//! Subscription of the runtime events filtered by the module, the variant and
+//! the account, which are pushed on the import of the new best blocks.
+
+use crate::map_query::{decoded, metadata_modules};
+use frame_metadata::DecodeDifferent;
+use futures::{future, StreamExt, TryStreamExt};
+use jsonrpc_core::{
+	futures::{Future, Sink},
+	Error as RpcError, ErrorCode, Result,
+};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use runtime_common_rpc_runtime_api::EventsApi as EventsRuntimeApi;
+use sc_client_api::BlockchainEvents;
+use serde::{Deserialize, Serialize};
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as FilteredEventsClient;
+
+/// The filter of the subscribed events.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilter {
+	/// The module emitting the events, e.g. `Dex`.
+	pub pallet: String,
+	/// The variant of the events, e.g. `Swap`, all the events of the module if
+	/// not set.
+	pub event: Option<String>,
+	/// The SCALE encoded account, only the events with the account in the
+	/// arguments if set.
+	pub account: Option<Bytes>,
+}
+
+/// An event matching the filter, with the types of the arguments in the
+/// runtime metadata to decode the arguments.
+#[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredEvent<BlockHash> {
+	/// The block emitting the event.
+	pub block_hash: BlockHash,
+	/// The index of the extrinsic emitting the event, `None` if not emitted
+	/// by an extrinsic.
+	pub extrinsic_index: Option<u32>,
+	/// The module of the event.
+	pub pallet: String,
+	/// The variant of the event.
+	pub event: String,
+	/// The types of the arguments.
+	pub arg_types: Vec<String>,
+	/// The SCALE encoded arguments.
+	pub args: Bytes,
+}
+
+#[rpc]
+pub trait FilteredEventsApi<BlockHash> {
+	/// RPC Metadata
+	type Metadata;
+
+	/// Subscribe the events matching the filter. The matched events of each
+	/// new best block are pushed together, and nothing is pushed for the
+	/// blocks without the matched events.
+	#[pubsub(subscription = "events_filtered", subscribe, name = "events_subscribeFiltered")]
+	fn subscribe_filtered(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<Vec<FilteredEvent<BlockHash>>>,
+		filter: EventFilter,
+	);
+
+	/// Unsubscribe the filtered events.
+	#[pubsub(subscription = "events_filtered", unsubscribe, name = "events_unsubscribeFiltered")]
+	fn unsubscribe_filtered(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
+}
+
+/// A struct that implements the [`FilteredEventsApi`].
+pub struct FilteredEvents<C, B> {
+	client: Arc<C>,
+	subscriptions: SubscriptionManager,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> FilteredEvents<C, B> {
+	/// Create new `FilteredEvents` with the given reference to the client and
+	/// the manager of the subscriptions.
+	pub fn new(client: Arc<C>, subscriptions: SubscriptionManager) -> Self {
+		FilteredEvents {
+			client,
+			subscriptions,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+	InvalidFilter,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidFilter => 2,
+		}
+	}
+}
+
+fn rpc_error(error: Error, message: &str, data: Option<String>) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(error.into()),
+		message: message.into(),
+		data: data.map(Into::into),
+	}
+}
+
+/// The filter resolved by the runtime metadata. The encoded runtime event
+/// starts with the index of the module and then the index of the variant.
+struct EventMatcher {
+	pallet: String,
+	module_index: u8,
+	/// The names and the argument types of the variants, ordered by the
+	/// variant index.
+	events: Vec<(String, Vec<String>)>,
+	variant_index: Option<u8>,
+	account: Option<Vec<u8>>,
+}
+
+impl EventMatcher {
+	fn new(metadata: &[u8], filter: EventFilter) -> Option<Self> {
+		let module = metadata_modules(metadata)?
+			.into_iter()
+			.find(|module| matches!(&module.name, DecodeDifferent::Decoded(name) if *name == filter.pallet))?;
+		let events = decoded(module.event?)?
+			.into_iter()
+			.map(|event| {
+				Some((
+					decoded(event.name)?,
+					decoded(event.arguments)?.into_iter().map(Into::into).collect(),
+				))
+			})
+			.collect::<Option<Vec<(String, Vec<String>)>>>()?;
+		let variant_index = match filter.event {
+			Some(event) => Some(events.iter().position(|(name, _)| *name == event)? as u8),
+			None => None,
+		};
+		let account = match filter.account {
+			Some(account) if account.is_empty() => return None,
+			account => account.map(|account| account.to_vec()),
+		};
+
+		Some(EventMatcher {
+			pallet: filter.pallet,
+			module_index: module.index,
+			events,
+			variant_index,
+			account,
+		})
+	}
+
+	fn filter<BlockHash: Copy>(
+		&self,
+		block_hash: BlockHash,
+		events: Vec<(Option<u32>, Vec<u8>)>,
+	) -> Vec<FilteredEvent<BlockHash>> {
+		events
+			.into_iter()
+			.filter_map(|(extrinsic_index, event)| {
+				if event.len() < 2 || event[0] != self.module_index {
+					return None;
+				}
+				if self.variant_index.map_or(false, |index| index != event[1]) {
+					return None;
+				}
+				let args = &event[2..];
+				if let Some(account) = &self.account {
+					if !args.windows(account.len()).any(|window| window == &account[..]) {
+						return None;
+					}
+				}
+				let (name, arg_types) = self.events.get(event[1] as usize)?;
+
+				Some(FilteredEvent {
+					block_hash,
+					extrinsic_index,
+					pallet: self.pallet.clone(),
+					event: name.clone(),
+					arg_types: arg_types.clone(),
+					args: args.to_vec().into(),
+				})
+			})
+			.collect()
+	}
+}
+
+impl<C, Block> FilteredEventsApi<<Block as BlockT>::Hash> for FilteredEvents<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+	C::Api: Metadata<Block> + EventsRuntimeApi<Block>,
+{
+	type Metadata = sc_rpc_api::Metadata;
+
+	fn subscribe_filtered(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<Vec<FilteredEvent<<Block as BlockT>::Hash>>>,
+		filter: EventFilter,
+	) {
+		// The filter is resolved by the runtime metadata of the best block when
+		// subscribing.
+		let at = BlockId::hash(self.client.info().best_hash);
+		let matcher = match self.client.runtime_api().metadata(&at) {
+			Ok(metadata) => match EventMatcher::new(&metadata, filter) {
+				Some(matcher) => matcher,
+				None => {
+					let _ = subscriber.reject(rpc_error(
+						Error::InvalidFilter,
+						"The module or the event is not found.",
+						None,
+					));
+					return;
+				}
+			},
+			Err(e) => {
+				let _ = subscriber.reject(rpc_error(
+					Error::RuntimeError,
+					"Unable to get the runtime metadata.",
+					Some(format!("{:?}", e)),
+				));
+				return;
+			}
+		};
+
+		let client = self.client.clone();
+		let stream = self
+			.client
+			.import_notification_stream()
+			.filter(|notification| future::ready(notification.is_new_best))
+			.filter_map(move |notification| {
+				let events = client
+					.runtime_api()
+					.events(&BlockId::hash(notification.hash))
+					.map_err(|e| log::warn!("Unable to get the events of {:?}: {:?}", notification.hash, e))
+					.ok()
+					.map(|events| matcher.filter(notification.hash, events))
+					.filter(|events| !events.is_empty());
+				future::ready(events)
+			})
+			.map(|events| Ok::<_, ()>(Ok(events)))
+			.compat();
+
+		self.subscriptions.add(subscriber, |sink| {
+			sink.sink_map_err(|e| log::warn!("Error sending notifications: {:?}", e))
+				.send_all(stream)
+				// we ignore the resulting Stream (if the first stream is over we are
+				// unsubscribed)
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_filtered(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
@@ -0,0 +1,96 @@
+//! RPC interface for the runtime APIs aggregated across modules, the
+//! paginated query of the storage maps and the subscription of the filtered
+//! events.
+
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sc_rpc_api::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+mod events;
+mod map_query;
+
+pub use self::gen_client::Client as DryRunClient;
+pub use events::{EventFilter, FilteredEvent, FilteredEvents, FilteredEventsApi, FilteredEventsClient};
+pub use map_query::{
+	DecodeEntry, EntryKeys, MapEntry, MapPage, StorageMap, StorageMapApi, StorageMapClient, MAX_PAGE_SIZE,
+};
+pub use runtime_common_rpc_runtime_api::{DryRunApi as DryRunRuntimeApi, DryRunResult, EventsApi as EventsRuntimeApi};
+
+#[rpc]
+pub trait DryRunApi<BlockHash> {
+	/// Dry run the SCALE encoded signed or unsigned extrinsic against the
+	/// state at the block, returns the SCALE encoded `DryRunResult` with the
+	/// result, the emitted events and the consumed weight.
+	#[rpc(name = "dryRun_dryRun")]
+	fn dry_run(&self, extrinsic: Bytes, at: Option<BlockHash>) -> Result<Bytes>;
+}
+
+/// A struct that implements the [`DryRunApi`].
+pub struct DryRun<C, B> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> DryRun<C, B> {
+	/// Create new `DryRun` with the given reference to the client.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		DryRun {
+			client,
+			deny_unsafe,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+	DecodeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+impl<C, Block> DryRunApi<<Block as BlockT>::Hash> for DryRun<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DryRunRuntimeApi<Block>,
+{
+	fn dry_run(&self, extrinsic: Bytes, at: Option<<Block as BlockT>::Hash>) -> Result<Bytes> {
+		// executing arbitrary extrinsics is expensive
+		self.deny_unsafe.check_if_safe()?;
+
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		let uxt: <Block as BlockT>::Extrinsic = Decode::decode(&mut &*extrinsic).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to decode the extrinsic.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		api.dry_run(&at, uxt)
+			.map(|result| result.encode().into())
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to dry run the extrinsic.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
@@ -0,0 +1,287 @@
+//! Paginated query of the whitelisted storage maps of the modules.
+//!
+//! The entries are decoded by the node and returned as JSON. The V12 runtime
+//! metadata only names the types of the keys and the value, so each
+//! whitelisted map comes with a [`DecodeEntry`] of its concrete types, the
+//! hashers of the keys are read from the runtime metadata.
+
+use codec::Decode;
+use frame_metadata::{
+	DecodeDifferent, ModuleMetadata, RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType, StorageHasher,
+};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sc_client_api::{Backend, StorageProvider};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey, Bytes};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as StorageMapClient;
+
+/// The max number of the entries of a page.
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Decode the keys and the value of an entry of a storage map to JSON,
+/// returns `None` if the entry doesn't decode.
+pub type DecodeEntry = fn(keys: &mut EntryKeys, value: &[u8]) -> Option<(Vec<Value>, Value)>;
+
+/// The hashed keys of an entry, i.e. the storage key without the prefix of
+/// the map, decoded one by one in order.
+pub struct EntryKeys<'a> {
+	hashers: std::slice::Iter<'a, StorageHasher>,
+	input: &'a [u8],
+}
+
+impl<'a> EntryKeys<'a> {
+	fn new(hashers: &'a [StorageHasher], input: &'a [u8]) -> Self {
+		EntryKeys {
+			hashers: hashers.iter(),
+			input,
+		}
+	}
+
+	/// Decode the next key, only the keys hashed by the `*Concat` and
+	/// `Identity` hashers can be decoded.
+	pub fn decode<K: Decode>(&mut self) -> Option<K> {
+		let hash_len = match self.hashers.next()? {
+			StorageHasher::Blake2_128Concat => 16,
+			StorageHasher::Twox64Concat => 8,
+			StorageHasher::Identity => 0,
+			_ => return None,
+		};
+		self.input = self.input.get(hash_len..)?;
+		K::decode(&mut self.input).ok()
+	}
+}
+
+/// An entry of a storage map.
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapEntry {
+	/// The full storage key of the entry, which is the `start_key` to query
+	/// the next page.
+	pub storage_key: Bytes,
+	/// The decoded keys of the entry.
+	pub key: Vec<Value>,
+	/// The decoded value of the entry.
+	pub value: Value,
+}
+
+/// A page of the decoded entries of a storage map, with the type names of
+/// the keys and the value in the runtime metadata.
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapPage {
+	/// The types of the keys.
+	pub key_types: Vec<String>,
+	/// The type of the value.
+	pub value_type: String,
+	/// The entries ordered by the storage key.
+	pub entries: Vec<MapEntry>,
+}
+
+#[rpc]
+pub trait StorageMapApi<BlockHash> {
+	/// At most `count` decoded entries of the whitelisted storage map `map`
+	/// of the module `pallet` after the storage key `start_key`.
+	#[rpc(name = "storageMap_getPage")]
+	fn get_page(
+		&self,
+		pallet: String,
+		map: String,
+		start_key: Option<Bytes>,
+		count: u32,
+		at: Option<BlockHash>,
+	) -> Result<MapPage>;
+}
+
+/// A struct that implements the [`StorageMapApi`].
+pub struct StorageMap<C, B, BE> {
+	client: Arc<C>,
+	whitelist: Vec<(String, String, DecodeEntry)>,
+	_marker: std::marker::PhantomData<(B, BE)>,
+}
+
+impl<C, B, BE> StorageMap<C, B, BE> {
+	/// Create new `StorageMap` with the given reference to the client and the
+	/// `(pallet, map, decode_entry)` whitelist of the queryable storage maps.
+	pub fn new(client: Arc<C>, whitelist: Vec<(String, String, DecodeEntry)>) -> Self {
+		StorageMap {
+			client,
+			whitelist,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+	NotWhitelisted,
+	MapNotFound,
+	TooLargePage,
+	DecodeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::NotWhitelisted => 2,
+			Error::MapNotFound => 3,
+			Error::TooLargePage => 4,
+			Error::DecodeError => 5,
+		}
+	}
+}
+
+fn rpc_error(error: Error, message: &str, data: Option<String>) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(error.into()),
+		message: message.into(),
+		data: data.map(Into::into),
+	}
+}
+
+pub(crate) fn decoded<B, O>(value: DecodeDifferent<B, O>) -> Option<O> {
+	match value {
+		DecodeDifferent::Decoded(value) => Some(value),
+		DecodeDifferent::Encode(_) => None,
+	}
+}
+
+/// The modules in the encoded runtime metadata, only the V12 metadata is
+/// supported.
+pub(crate) fn metadata_modules(metadata: &[u8]) -> Option<Vec<ModuleMetadata>> {
+	let RuntimeMetadataPrefixed(_, metadata) = Decode::decode(&mut &metadata[..]).ok()?;
+	match metadata {
+		RuntimeMetadata::V12(metadata) => decoded(metadata.modules),
+		_ => None,
+	}
+}
+
+/// Find the storage prefix and the type of the storage map in the runtime
+/// metadata, returns `None` if it's not a map.
+fn find_map(metadata: &[u8], pallet: &str, map: &str) -> Option<(String, StorageEntryType)> {
+	metadata_modules(metadata)?.into_iter().find_map(|module| {
+		let storage = decoded(module.storage?)?;
+		let prefix = decoded(storage.prefix)?;
+		if prefix != pallet {
+			return None;
+		}
+		decoded(storage.entries)?
+			.into_iter()
+			.find(|entry| matches!(&entry.name, DecodeDifferent::Decoded(name) if name == map))
+			.map(|entry| (prefix, entry.ty))
+	})
+}
+
+impl<C, Block, BE> StorageMapApi<<Block as BlockT>::Hash> for StorageMap<C, Block, BE>
+where
+	Block: BlockT,
+	BE: Backend<Block> + Send + Sync + 'static,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, BE>,
+	C::Api: Metadata<Block>,
+{
+	fn get_page(
+		&self,
+		pallet: String,
+		map: String,
+		start_key: Option<Bytes>,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<MapPage> {
+		let decode_entry = self
+			.whitelist
+			.iter()
+			.find(|(p, m, _)| *p == pallet && *m == map)
+			.map(|(_, _, decode_entry)| *decode_entry)
+			.ok_or_else(|| rpc_error(Error::NotWhitelisted, "The storage map is not whitelisted.", None))?;
+		if count > MAX_PAGE_SIZE {
+			return Err(rpc_error(
+				Error::TooLargePage,
+				"The count exceeds the max page size.",
+				Some(format!("{}", MAX_PAGE_SIZE)),
+			));
+		}
+
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+		let metadata = self.client.runtime_api().metadata(&at).map_err(|e| {
+			rpc_error(
+				Error::RuntimeError,
+				"Unable to get the runtime metadata.",
+				Some(format!("{:?}", e)),
+			)
+		})?;
+		let (prefix, ty) = find_map(&metadata, &pallet, &map)
+			.ok_or_else(|| rpc_error(Error::MapNotFound, "The storage map is not found.", None))?;
+		let (hashers, key_types, value_type) = match ty {
+			StorageEntryType::Map { hasher, key, value, .. } => (
+				vec![hasher],
+				vec![decoded(key).unwrap_or_default()],
+				decoded(value).unwrap_or_default(),
+			),
+			StorageEntryType::DoubleMap {
+				hasher,
+				key1,
+				key2,
+				value,
+				key2_hasher,
+			} => (
+				vec![hasher, key2_hasher],
+				vec![decoded(key1).unwrap_or_default(), decoded(key2).unwrap_or_default()],
+				decoded(value).unwrap_or_default(),
+			),
+			StorageEntryType::Plain(_) => {
+				return Err(rpc_error(Error::MapNotFound, "The storage map is not found.", None));
+			}
+		};
+
+		let map_prefix = [twox_128(prefix.as_bytes()), twox_128(map.as_bytes())].concat();
+		let prefix_key = StorageKey(map_prefix.clone());
+		let start_key = start_key.map(|key| StorageKey(key.to_vec()));
+		let storage_error = |e: sp_blockchain::Error| {
+			rpc_error(
+				Error::RuntimeError,
+				"Unable to read the storage.",
+				Some(format!("{:?}", e)),
+			)
+		};
+
+		let mut entries = vec![];
+		for storage_key in self
+			.client
+			.storage_keys_iter(&at, Some(&prefix_key), start_key.as_ref())
+			.map_err(storage_error)?
+			.take(count as usize)
+		{
+			if let Some(value) = self.client.storage(&at, &storage_key).map_err(storage_error)? {
+				let mut keys = EntryKeys::new(&hashers, &storage_key.0[map_prefix.len()..]);
+				let (key, value) = decode_entry(&mut keys, &value.0).ok_or_else(|| {
+					rpc_error(
+						Error::DecodeError,
+						"Unable to decode the storage map entry.",
+						Some(format!("{:?}", storage_key)),
+					)
+				})?;
+				entries.push(MapEntry {
+					storage_key: storage_key.0.into(),
+					key,
+					value,
+				});
+			}
+		}
+
+		Ok(MapPage {
+			key_types,
+			value_type,
+			entries,
+		})
+	}
+}
@@ -4,27 +4,32 @@
 
 use frame_support::{
 	parameter_types,
+	traits::{Contains, Get},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, WEIGHT_PER_SECOND},
 		DispatchClass, Weight,
 	},
 };
 use frame_system::limits;
+use module_support::DEXManager;
 pub use module_support::{ExchangeRate, PrecompileCallerFilter, Price, Rate, Ratio};
-use primitives::{PRECOMPILE_ADDRESS_START, PREDEPLOY_ADDRESS_START};
+use orml_oracle::TimestampedValueOf;
+use orml_traits::{CombineData, DataProvider};
+use primitives::{Balance, CurrencyId, PRECOMPILE_ADDRESS_START, PREDEPLOY_ADDRESS_START};
 use sp_core::H160;
 use sp_runtime::{
-	traits::{Convert, Saturating},
+	traits::{CheckedMul, Convert, Saturating, Zero},
 	transaction_validity::TransactionPriority,
 	FixedPointNumber, FixedPointOperand, Perbill,
 };
+use sp_std::{marker::PhantomData, prelude::*};
 
 use static_assertions::const_assert;
 
 pub mod precompile;
 pub use precompile::{
-	AllPrecompiles, DexPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile,
-	StateRentPrecompile,
+	AllPrecompiles, DexPrecompile, HonzonPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile,
+	ScheduleCallPrecompile, StateRentPrecompile,
 };
 
 pub type TimeStampedPrice = orml_oracle::TimestampedValue<Price, primitives::Moment>;
@@ -35,6 +40,7 @@ parameter_types! {
 	pub const RenvmBridgeUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 	pub const CdpEngineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
 	pub const AuctionManagerUnsignedPriority: TransactionPriority = TransactionPriority::max_value() - 1;
+	pub const AirdropUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 }
 
 parameter_types! {
@@ -293,6 +299,68 @@ impl Convert<u64, Weight> for GasToWeight {
 	}
 }
 
+/// The locked native currency of the account, i.e. the max amount of the
+/// locks on it, e.g. the vesting and the democracy locks.
+pub struct NativeLockedBalance<T>(PhantomData<T>);
+impl<T> Convert<T::AccountId, Balance> for NativeLockedBalance<T>
+where
+	T: frame_system::Config<AccountData = pallet_balances::AccountData<Balance>>,
+{
+	fn convert(who: T::AccountId) -> Balance {
+		let account_data = frame_system::Module::<T>::account(who).data;
+		account_data
+			.misc_frozen
+			.max(account_data.fee_frozen)
+			.min(account_data.free)
+	}
+}
+
+/// The price of currency derived from the time weighted average price of its
+/// liquidity pool with stable currency on DEX.
+pub struct DexPriceProvider<AccountId, DEX, GetStableCurrencyId, StableCurrencyFixedPrice>(
+	PhantomData<(AccountId, DEX, GetStableCurrencyId, StableCurrencyFixedPrice)>,
+);
+impl<AccountId, DEX, GetStableCurrencyId, StableCurrencyFixedPrice> DataProvider<CurrencyId, Price>
+	for DexPriceProvider<AccountId, DEX, GetStableCurrencyId, StableCurrencyFixedPrice>
+where
+	DEX: DEXManager<AccountId, CurrencyId, Balance>,
+	GetStableCurrencyId: Get<CurrencyId>,
+	StableCurrencyFixedPrice: Get<Price>,
+{
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		DEX::get_twap_price(*currency_id, GetStableCurrencyId::get())
+			.and_then(|price| price.checked_mul(&StableCurrencyFixedPrice::get()))
+	}
+}
+
+/// Combine the raw values fed by the members of the oracle except the
+/// observing ones, so the feeds of the new members don't count until their
+/// observation ends.
+pub struct ExcludeObserversCombineData<T, I, Observers, Inner>(PhantomData<(T, I, Observers, Inner)>);
+impl<T, I, Observers, Inner> CombineData<T::OracleKey, TimestampedValueOf<T, I>>
+	for ExcludeObserversCombineData<T, I, Observers, Inner>
+where
+	T: orml_oracle::Config<I>,
+	I: orml_oracle::Instance,
+	Observers: Contains<T::AccountId>,
+	Inner: CombineData<T::OracleKey, TimestampedValueOf<T, I>>,
+{
+	fn combine_data(
+		key: &T::OracleKey,
+		_values: Vec<TimestampedValueOf<T, I>>,
+		prev_value: Option<TimestampedValueOf<T, I>>,
+	) -> Option<TimestampedValueOf<T, I>> {
+		let values = orml_oracle::Module::<T, I>::members()
+			.0
+			.into_iter()
+			.filter(|who| !Observers::contains(who))
+			.chain(sp_std::iter::once(T::RootOperatorAccountId::get()))
+			.filter_map(|who| orml_oracle::Module::<T, I>::raw_values(&who, key))
+			.collect();
+		Inner::combine_data(key, values, prev_value)
+	}
+}
+
 pub const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_perthousand(25);
 /// We allow `Normal` extrinsics to fill up the block up to 75%, the rest can be
 /// used by  Operational  extrinsics.
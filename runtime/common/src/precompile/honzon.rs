@@ -0,0 +1,154 @@
+use super::input::{Input, InputT};
+use frame_support::debug;
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_support::HonzonManager;
+use primitives::{evm::AddressMapping as AddressMappingT, Amount, Balance, CurrencyId};
+use sp_core::U256;
+use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Honzon` impl precompile, exposes the debit of loans as a tokenized
+/// representation for EVM contracts, the debit value of `who` under a
+/// collateral type is the balance, and the total debit value of the
+/// collateral type is the total supply.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Get position. Rest `input` bytes: `who`, `currency_id`.
+/// - Get debit value. Rest `input` bytes: `who`, `currency_id`.
+/// - Get total debit value. Rest `input` bytes: `currency_id`.
+/// - Adjust loan. Rest `input` bytes: `caller`, `who`, `currency_id`,
+///   `collateral_adjustment`, `debit_adjustment`. `caller` is the original
+///   caller of the wrapper system contract, it must be `who` or have the
+///   honzon authorization of `who` under `currency_id`.
+pub struct HonzonPrecompile<AccountId, AddressMapping, Honzon>(PhantomData<(AccountId, AddressMapping, Honzon)>);
+
+enum Action {
+	GetPosition,
+	GetDebitValue,
+	GetTotalDebitValue,
+	AdjustLoan,
+}
+
+/// The gas cost of a storage read, same as `SLOAD`.
+const READ_COST: u64 = 800;
+/// The gas cost of a storage write, same as `SSTORE` of a new value.
+const WRITE_COST: u64 = 20_000;
+
+impl Action {
+	/// The gas cost of the action, charged by the storage reads and writes it
+	/// takes.
+	fn cost(&self) -> u64 {
+		let (reads, writes) = match self {
+			Action::GetPosition => (1, 0),
+			// the position and the debit exchange rate
+			Action::GetDebitValue | Action::GetTotalDebitValue => (2, 0),
+			// same as the `adjust_loan` call of honzon
+			Action::AdjustLoan => (24, 10),
+		};
+		READ_COST
+			.saturating_mul(reads)
+			.saturating_add(WRITE_COST.saturating_mul(writes))
+	}
+}
+
+impl TryFrom<u8> for Action {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Action::GetPosition),
+			1 => Ok(Action::GetDebitValue),
+			2 => Ok(Action::GetTotalDebitValue),
+			3 => Ok(Action::AdjustLoan),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, Honzon> Precompile for HonzonPrecompile<AccountId, AddressMapping, Honzon>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	Honzon: HonzonManager<AccountId, CurrencyId, Amount, Balance>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		debug::debug!(target: "evm", "input: {:?}", input);
+
+		let input = Input::<Action, AccountId, AddressMapping>::new(input);
+
+		let action = input.action()?;
+
+		let cost = action.cost();
+		if let Some(target_gas) = target_gas {
+			if cost > target_gas {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+
+		match action {
+			Action::GetPosition => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+
+				let (collateral, debit) = Honzon::get_position(&who, currency_id);
+
+				// output
+				let mut be_bytes = [0u8; 64];
+				U256::from(collateral).to_big_endian(&mut be_bytes[..32]);
+				U256::from(debit).to_big_endian(&mut be_bytes[32..64]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), cost))
+			}
+			Action::GetDebitValue => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+
+				let value = Honzon::get_debit_value(&who, currency_id);
+
+				// output
+				let mut be_bytes = [0u8; 32];
+				U256::from(value).to_big_endian(&mut be_bytes[..32]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), cost))
+			}
+			Action::GetTotalDebitValue => {
+				let currency_id = input.currency_id_at(1)?;
+
+				let value = Honzon::get_total_debit_value(currency_id);
+
+				// output
+				let mut be_bytes = [0u8; 32];
+				U256::from(value).to_big_endian(&mut be_bytes[..32]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), cost))
+			}
+			Action::AdjustLoan => {
+				// the precompile is only called by the system contracts, which pass the
+				// original caller rather than acting for themselves.
+				let caller = input.account_id_at(1)?;
+				let who = input.account_id_at(2)?;
+				let currency_id = input.currency_id_at(3)?;
+				let collateral_adjustment = input.amount_at(4)?;
+				let debit_adjustment = input.amount_at(5)?;
+
+				Honzon::check_authorization(&who, &caller, currency_id).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Honzon::adjust_loan(&who, currency_id, collateral_adjustment, debit_adjustment).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], cost))
+			}
+		}
+	}
+}
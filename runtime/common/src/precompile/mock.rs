@@ -9,18 +9,18 @@ use frame_support::{
 	RuntimeDebug,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
-use module_support::DEXIncentives;
-use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
+use module_support::{DEXIncentives, HonzonManager};
+use orml_traits::{parameter_type_with_key, Auction, AuctionInfo, MultiReservableCurrency};
 pub use primitives::{
-	evm::AddressMapping, mocks::MockAddressMapping, Amount, BlockNumber, CurrencyId, Header, Nonce, TokenSymbol,
-	TradingPair, PREDEPLOY_ADDRESS_START,
+	evm::AddressMapping, mocks::MockAddressMapping, Amount, AuctionId, BlockNumber, CurrencyId, Header, Nonce,
+	TokenSymbol, TradingPair, PREDEPLOY_ADDRESS_START,
 };
 use sp_core::{crypto::AccountId32, Bytes, H160, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, Convert, IdentityLookup},
-	DispatchResult, FixedPointNumber, FixedU128, ModuleId, Perbill,
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128, ModuleId, Perbill,
 };
-use sp_std::{collections::btree_map::BTreeMap, str::FromStr};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, str::FromStr};
 
 impl_outer_event! {
 	pub enum TestEvent for Test {
@@ -35,6 +35,7 @@ impl_outer_event! {
 		pallet_utility,
 		pallet_scheduler<T>,
 		module_dex<T>,
+		module_transaction_payment<T>,
 	}
 }
 
@@ -49,6 +50,7 @@ impl_outer_dispatch! {
 		pallet_proxy::Proxy,
 		pallet_utility::Utility,
 		module_evm::ModuleEVM,
+		module_transaction_payment::TransactionPayment,
 	}
 }
 
@@ -176,9 +178,35 @@ impl module_evm_bridge::Config for Test {
 }
 pub type EVMBridge = module_evm_bridge::Module<Test>;
 
+pub struct MockAuction;
+impl Auction<AccountId, BlockNumber> for MockAuction {
+	type AuctionId = AuctionId;
+	type Balance = Balance;
+
+	fn auction_info(_id: Self::AuctionId) -> Option<AuctionInfo<AccountId, Self::Balance, BlockNumber>> {
+		None
+	}
+
+	fn update_auction(_id: Self::AuctionId, _info: AuctionInfo<AccountId, Self::Balance, BlockNumber>) -> DispatchResult {
+		Ok(())
+	}
+
+	fn new_auction(_start: BlockNumber, _end: Option<BlockNumber>) -> Result<Self::AuctionId, DispatchError> {
+		Ok(Default::default())
+	}
+
+	fn remove_auction(_id: Self::AuctionId) {}
+}
+
 parameter_types! {
 	pub const CreateClassDeposit: Balance = 200;
 	pub const CreateTokenDeposit: Balance = 100;
+	pub const MaxRoyaltyPerMille: u16 = 100;
+	pub const MarketFeePerMille: u16 = 20;
+	pub const DataDepositPerByte: Balance = 10;
+	pub const MaxAttributeKeyLength: u32 = 8;
+	pub const MaxAttributeValueLength: u32 = 16;
+	pub const MaxMintRecipients: u32 = 3;
 	pub const NftModuleId: ModuleId = ModuleId(*b"aca/aNFT");
 }
 impl module_nft::Config for Test {
@@ -186,7 +214,16 @@ impl module_nft::Config for Test {
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type ModuleId = NftModuleId;
+	type MaxRoyaltyPerMille = MaxRoyaltyPerMille;
 	type Currency = AdaptedBasicCurrency;
+	type MultiCurrency = Currencies;
+	type Auction = MockAuction;
+	type MarketFeePerMille = MarketFeePerMille;
+	type TreasuryAccount = TreasuryAccount;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxMintRecipients = MaxMintRecipients;
 	type WeightInfo = ();
 }
 pub type NFTModule = module_nft::Module<Test>;
@@ -203,9 +240,26 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 	pub AllNonNativeCurrencyIds: Vec<CurrencyId> = vec![CurrencyId::Token(TokenSymbol::AUSD)];
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::one();
+	pub MaxWeightToFeeStep: Ratio = Ratio::saturating_from_rational(20, 100);
+	pub MinDispatchClassFeeMultiplier: module_transaction_payment::Multiplier = module_transaction_payment::Multiplier::saturating_from_rational(1, 10);
+	pub MaxDispatchClassFeeMultiplier: module_transaction_payment::Multiplier = module_transaction_payment::Multiplier::saturating_from_integer(10);
+	pub const OperationalFeeMultiplier: u8 = 5;
+	pub const FeeBurnModuleId: ModuleId = ModuleId(*b"aca/fbrn");
+	pub const FeeBurnPeriod: BlockNumber = 10;
+	pub const FreeTransactionQuota: u32 = 0;
+	pub const FreeTransactionPeriod: BlockNumber = 10;
+	pub const FreeTransactionMinLocked: Balance = 0;
+	pub const MaxFeeDiscountTiers: u32 = 3;
+	pub const OnboardingSubsidyModuleId: ModuleId = ModuleId(*b"aca/obsd");
+	pub const OnboardingSubsidizedTransactions: u32 = 0;
+	pub const OnboardingSubsidyCap: Balance = 0;
+	pub const MaxOnboardingsPerPeriod: u32 = 0;
+	pub const OnboardingPeriod: BlockNumber = 10;
 }
 
 impl module_transaction_payment::Config for Test {
+	type Event = TestEvent;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -216,9 +270,28 @@ impl module_transaction_payment::Config for Test {
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ();
 	type DEX = ();
+	type LinkedEvmBalance = ();
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type MaxWeightToFeeStep = MaxWeightToFeeStep;
+	type MinDispatchClassFeeMultiplier = MinDispatchClassFeeMultiplier;
+	type MaxDispatchClassFeeMultiplier = MaxDispatchClassFeeMultiplier;
+	type OperationalFeeMultiplier = OperationalFeeMultiplier;
+	type FeeBurnModuleId = FeeBurnModuleId;
+	type FeeBurnPeriod = FeeBurnPeriod;
+	type NativeLockedBalance = crate::NativeLockedBalance<Test>;
+	type FreeTransactionQuota = FreeTransactionQuota;
+	type FreeTransactionPeriod = FreeTransactionPeriod;
+	type FreeTransactionMinLocked = FreeTransactionMinLocked;
+	type MaxFeeDiscountTiers = MaxFeeDiscountTiers;
+	type OnboardingSubsidyModuleId = OnboardingSubsidyModuleId;
+	type OnboardingSubsidizedTransactions = OnboardingSubsidizedTransactions;
+	type OnboardingSubsidyCap = OnboardingSubsidyCap;
+	type MaxOnboardingsPerPeriod = MaxOnboardingsPerPeriod;
+	type OnboardingPeriod = OnboardingPeriod;
+	type UpdateOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = ();
 }
+pub type TransactionPayment = module_transaction_payment::Module<Test>;
 pub type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Test>;
 
 parameter_types! {
@@ -315,6 +388,7 @@ ord_parameter_types! {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const TwapPeriod: BlockNumber = 10;
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 }
 
@@ -323,6 +397,7 @@ impl module_dex::Config for Test {
 	type Currency = Tokens;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TwapPeriod = TwapPeriod;
 	type ModuleId = DEXModuleId;
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
@@ -331,6 +406,67 @@ impl module_dex::Config for Test {
 
 pub type DexModule = module_dex::Module<Test>;
 
+thread_local! {
+	static POSITIONS: RefCell<BTreeMap<(AccountId, CurrencyId), (Balance, Balance)>> = RefCell::new(BTreeMap::new());
+}
+
+pub struct MockHonzon;
+impl HonzonManager<AccountId, CurrencyId, Amount, Balance> for MockHonzon {
+	fn adjust_loan(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> DispatchResult {
+		let (collateral, debit) = Self::get_position(who, currency_id);
+		let collateral = (collateral as Amount)
+			.checked_add(collateral_adjustment)
+			.filter(|x| *x >= 0)
+			.ok_or("invalid collateral adjustment")?;
+		let debit = (debit as Amount)
+			.checked_add(debit_adjustment)
+			.filter(|x| *x >= 0)
+			.ok_or("invalid debit adjustment")?;
+		POSITIONS.with(|v| {
+			v.borrow_mut()
+				.insert((who.clone(), currency_id), (collateral as Balance, debit as Balance))
+		});
+		Ok(())
+	}
+
+	fn get_position(who: &AccountId, currency_id: CurrencyId) -> (Balance, Balance) {
+		POSITIONS.with(|v| {
+			v.borrow()
+				.get(&(who.clone(), currency_id))
+				.cloned()
+				.unwrap_or_default()
+		})
+	}
+
+	fn get_debit_value(who: &AccountId, currency_id: CurrencyId) -> Balance {
+		// the debit exchange rate is 1/10
+		Self::get_position(who, currency_id).1 / 10
+	}
+
+	fn get_total_debit_value(currency_id: CurrencyId) -> Balance {
+		POSITIONS.with(|v| {
+			v.borrow()
+				.iter()
+				.filter(|((_, id), _)| *id == currency_id)
+				.map(|(_, (_, debit))| debit / 10)
+				.sum()
+		})
+	}
+
+	fn check_authorization(from: &AccountId, to: &AccountId, _currency_id: CurrencyId) -> DispatchResult {
+		if from == to {
+			Ok(())
+		} else {
+			Err("no authorization".into())
+		}
+	}
+}
+
 pub type AdaptedBasicCurrency = module_currencies::BasicCurrencyAdapter<Test, Balances, Amount, BlockNumber>;
 
 pub type MultiCurrencyPrecompile = crate::MultiCurrencyPrecompile<AccountId, MockAddressMapping, Currencies>;
@@ -349,6 +485,7 @@ pub type ScheduleCallPrecompile = crate::ScheduleCallPrecompile<
 	Test,
 >;
 pub type DexPrecompile = crate::DexPrecompile<AccountId, MockAddressMapping, DexModule>;
+pub type HonzonPrecompile = crate::HonzonPrecompile<AccountId, MockAddressMapping, MockHonzon>;
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice();
@@ -362,6 +499,7 @@ ord_parameter_types! {
 	pub const StorageDepositPerByte: u64 = 10;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const TransactionIndexPeriod: BlockNumber = 10;
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub const ChainId: u64 = 1;
 }
@@ -389,6 +527,7 @@ impl module_evm::Config for Test {
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HonzonPrecompile,
 	>;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
@@ -398,6 +537,7 @@ impl module_evm::Config for Test {
 	type DeveloperDeposit = DeveloperDeposit;
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
+	type TransactionIndexPeriod = TransactionIndexPeriod;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type WeightInfo = ();
 }
@@ -1,5 +1,6 @@
 //! The precompiles for EVM, includes standard Ethereum precompiles, and more:
 //! - MultiCurrency at address `H160::from_low_u64_be(1024)`.
+//! - Honzon at address `H160::from_low_u64_be(1030)`.
 
 mod mock;
 mod tests;
@@ -16,6 +17,7 @@ use sp_core::H160;
 use sp_std::{marker::PhantomData, prelude::*};
 
 pub mod dex;
+pub mod honzon;
 pub mod input;
 pub mod multicurrency;
 pub mod nft;
@@ -24,6 +26,7 @@ pub mod schedule_call;
 pub mod state_rent;
 
 pub use dex::DexPrecompile;
+pub use honzon::HonzonPrecompile;
 pub use multicurrency::MultiCurrencyPrecompile;
 pub use nft::NFTPrecompile;
 pub use oracle::OraclePrecompile;
@@ -45,6 +48,7 @@ pub struct AllPrecompiles<
 	OraclePrecompile,
 	ScheduleCallPrecompile,
 	DexPrecompile,
+	HonzonPrecompile,
 >(
 	PhantomData<(
 		PrecompileCallerFilter,
@@ -54,6 +58,7 @@ pub struct AllPrecompiles<
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HonzonPrecompile,
 	)>,
 );
 
@@ -65,6 +70,7 @@ impl<
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HonzonPrecompile,
 	> Precompiles
 	for AllPrecompiles<
 		PrecompileCallerFilter,
@@ -74,6 +80,7 @@ impl<
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HonzonPrecompile,
 	> where
 	MultiCurrencyPrecompile: Precompile,
 	NFTPrecompile: Precompile,
@@ -82,6 +89,7 @@ impl<
 	ScheduleCallPrecompile: Precompile,
 	PrecompileCallerFilter: PrecompileCallerFilterT,
 	DexPrecompile: Precompile,
+	HonzonPrecompile: Precompile,
 {
 	#[allow(clippy::type_complexity)]
 	fn execute(
@@ -108,6 +116,8 @@ impl<
 				Some(ScheduleCallPrecompile::execute(input, target_gas, context))
 			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 5) {
 				Some(DexPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6) {
+				Some(HonzonPrecompile::execute(input, target_gas, context))
 			} else {
 				None
 			}
@@ -1,12 +1,13 @@
 #![cfg(test)]
 use super::*;
 use crate::precompile::mock::{
-	alice, bob, new_test_ext, run_to_block, Balances, DexModule, DexPrecompile, Oracle, OraclePrecompile, Origin,
-	Price, ScheduleCallPrecompile, Test, ACA_ERC20_ADDRESS, ALICE, AUSD, XBTC,
+	alice, bob, new_test_ext, run_to_block, Balances, DexModule, DexPrecompile, HonzonPrecompile, MockHonzon, Oracle,
+	OraclePrecompile, Origin, Price, ScheduleCallPrecompile, Test, ACA_ERC20_ADDRESS, ALICE, AUSD, XBTC,
 };
 use frame_support::{assert_noop, assert_ok};
 use hex_literal::hex;
 use module_evm::ExitError;
+use module_support::HonzonManager;
 use orml_traits::DataFeeder;
 use primitives::{evm::AddressMapping, PREDEPLOY_ADDRESS_START};
 use sp_core::{H160, H256, U256};
@@ -31,6 +32,7 @@ pub type WithSystemContractFilter = AllPrecompiles<
 	DummyPrecompile,
 	DummyPrecompile,
 	DummyPrecompile,
+	DummyPrecompile,
 >;
 
 #[test]
@@ -397,3 +399,103 @@ fn dex_precompile_swap_with_exact_supply_should_work() {
 		assert_eq!(used_gas, 0);
 	});
 }
+
+#[test]
+fn honzon_precompile_get_debit_should_work() {
+	new_test_ext().execute_with(|| {
+		let who = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice());
+		assert_ok!(MockHonzon::adjust_loan(&who, XBTC, 1_000, 5_000));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// action + who + currency_id
+		let mut input = [0u8; 96];
+		U256::from(0).to_big_endian(&mut input[..32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[32..64]);
+		U256::from_big_endian(&hex!("0300").to_vec()).to_big_endian(&mut input[64..96]);
+
+		let mut expected_output = [0u8; 64];
+		U256::from(1_000).to_big_endian(&mut expected_output[..32]);
+		U256::from(5_000).to_big_endian(&mut expected_output[32..64]);
+
+		let (reason, output, used_gas) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 800);
+
+		// get debit value
+		U256::from(1).to_big_endian(&mut input[..32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(500).to_big_endian(&mut expected_output[..32]);
+
+		let (reason, output, used_gas) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 1_600);
+
+		// action + currency_id
+		let mut input = [0u8; 64];
+		U256::from(2).to_big_endian(&mut input[..32]);
+		U256::from_big_endian(&hex!("0300").to_vec()).to_big_endian(&mut input[32..64]);
+
+		let (reason, output, used_gas) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 1_600);
+	});
+}
+
+#[test]
+fn honzon_precompile_adjust_loan_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// action + caller + who + currency_id + collateral_adjustment + debit_adjustment
+		let mut input = [0u8; 192];
+		U256::from(3).to_big_endian(&mut input[..32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[32..64]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[64..96]);
+		U256::from_big_endian(&hex!("0300").to_vec()).to_big_endian(&mut input[96..128]);
+		U256::from(1_000).to_big_endian(&mut input[128..160]);
+		U256::from(5_000).to_big_endian(&mut input[160..192]);
+
+		let (reason, output, used_gas) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, 219_200);
+		let who = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice());
+		assert_eq!(MockHonzon::get_position(&who, XBTC), (1_000, 5_000));
+
+		// not enough gas
+		assert_eq!(
+			HonzonPrecompile::execute(&input, Some(219_199), &context),
+			Err(ExitError::OutOfGas)
+		);
+
+		// the original caller can not adjust the loan of others without
+		// authorization, whichever system contract it calls through
+		U256::from(H256::from(bob()).to_fixed_bytes()).to_big_endian(&mut input[32..64]);
+		assert_eq!(
+			HonzonPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("no authorization".into()))
+		);
+
+		// can not pay back more than the debit
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[32..64]);
+		U256::from(0).to_big_endian(&mut input[128..160]);
+		U256::from(-6_000i128 as u128).to_big_endian(&mut input[160..192]);
+		assert_eq!(
+			HonzonPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("invalid debit adjustment".into()))
+		);
+	});
+}
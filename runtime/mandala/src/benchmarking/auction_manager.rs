@@ -1,15 +1,17 @@
 use crate::{
 	AcalaOracle, AccountId, AuctionId, AuctionManager, Balance, CdpTreasury, Currencies, CurrencyId, EmergencyShutdown,
-	GetNativeCurrencyId, GetStableCurrencyId, Price, Runtime, TokenSymbol, DOLLARS,
+	GetNativeCurrencyId, GetStableCurrencyId, MaxPartialBids, Price, Runtime, TokenSymbol, DOLLARS,
 };
 
 use super::utils::set_balance;
 use frame_benchmarking::account;
+use frame_support::weights::Weight;
 use frame_system::RawOrigin;
+use module_auction_manager::{AuctionType, DutchAuctionParam, MinimumIncrement, RelistParams};
 use module_support::AuctionManager as AuctionManagerTrait;
-use module_support::CDPTreasury;
+use module_support::{CDPTreasury, Rate, Ratio};
 use orml_benchmarking::runtime_benchmarks;
-use orml_traits::MultiCurrency;
+use orml_traits::{AuctionHandler, MultiCurrency};
 use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
 
@@ -20,6 +22,14 @@ fn dollar(d: u32) -> Balance {
 	DOLLARS.saturating_mul(d)
 }
 
+fn dutch_auction_param() -> DutchAuctionParam {
+	DutchAuctionParam {
+		start_price_ratio: Ratio::saturating_from_rational(6, 5),
+		decay_per_block: Ratio::saturating_from_rational(1, 1000),
+		floor_price_ratio: Ratio::saturating_from_rational(4, 5),
+	}
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_auction_manager }
 
@@ -90,6 +100,111 @@ runtime_benchmarks! {
 		// shutdown
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
 	}: cancel(RawOrigin::None, auction_id)
+
+	set_dutch_auction_params {
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT), Some(dutch_auction_param()))
+
+	// `take_collateral_auction` a dutch collateral auction, worst case:
+	// the target is reached and the remain collateral is refunded
+	take_collateral_auction {
+		let taker: AccountId = account("taker", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &taker, dollar(200))?;
+		Currencies::deposit(CurrencyId::Token(TokenSymbol::DOT), &funder, dollar(1))?;
+		CdpTreasury::deposit_collateral(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1))?;
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(CurrencyId::Token(TokenSymbol::DOT), Price::saturating_from_integer(120))])?;
+
+		// create dutch collateral auction
+		AuctionManager::set_dutch_auction_params(RawOrigin::Root.into(), CurrencyId::Token(TokenSymbol::DOT), Some(dutch_auction_param()))?;
+		AuctionManager::new_collateral_auction(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1), dollar(100))?;
+		let auction_id: AuctionId = Default::default();
+	}: _(RawOrigin::Signed(taker), auction_id)
+
+	set_minimum_increment {
+	}: _(RawOrigin::Root, AuctionType::Collateral, Some(MinimumIncrement { absolute: dollar(1), rate: Rate::saturating_from_rational(1, 20) }))
+
+	set_no_bid_sale_max_slippage {
+	}: _(RawOrigin::Root, Some(Ratio::saturating_from_rational(1, 10)))
+
+	// `partial_bid` a collateral auction, worst case:
+	// the partial bids are full and the lowest one is evicted by the bid
+	partial_bid {
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &bidder, dollar(80))?;
+		Currencies::deposit(CurrencyId::Token(TokenSymbol::DOT), &funder, dollar(1))?;
+		CdpTreasury::deposit_collateral(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1))?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1), dollar(100))?;
+		let auction_id: AuctionId = Default::default();
+
+		// partial bid collateral auction by other bidders
+		for i in 1 ..= MaxPartialBids::get() {
+			let other: AccountId = account("bidder", i, SEED);
+			Currencies::deposit(stable_currency_id, &other, dollar(10))?;
+			AuctionManager::partial_bid(RawOrigin::Signed(other).into(), auction_id, dollar(1), dollar(10))?;
+		}
+	}: _(RawOrigin::Signed(bidder), auction_id, dollar(1), dollar(80))
+
+	// settle an ended collateral auction, worst case:
+	// the bid doesn't reach the target and try to swap with DEX
+	settle_auction {
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &bidder, dollar(80))?;
+		Currencies::deposit(CurrencyId::Token(TokenSymbol::DOT), &funder, dollar(1))?;
+		CdpTreasury::deposit_collateral(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1))?;
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(CurrencyId::Token(TokenSymbol::DOT), Price::saturating_from_integer(120))])?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1), dollar(100))?;
+		let auction_id: AuctionId = Default::default();
+
+		// bid collateral auction
+		let _ = AuctionManager::collateral_auction_bid_handler(1, auction_id, (bidder.clone(), dollar(80)), None);
+
+		// end collateral auction
+		AuctionManager::on_auction_ended(auction_id, Some((bidder, dollar(80))));
+	}: {
+		AuctionManager::settle_pending_auctions(Weight::max_value());
+	}
+
+	// `relist` a collateral auction, worst case:
+	// auction have been already bid
+	relist {
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &bidder, dollar(80))?;
+		Currencies::deposit(CurrencyId::Token(TokenSymbol::DOT), &funder, dollar(1))?;
+		CdpTreasury::deposit_collateral(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1))?;
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(CurrencyId::Token(TokenSymbol::DOT), Price::saturating_from_integer(120))])?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1), dollar(100))?;
+		let auction_id: AuctionId = Default::default();
+
+		// bid collateral auction
+		let _ = AuctionManager::collateral_auction_bid_handler(1, auction_id, (bidder, dollar(80)), None);
+	}: _(RawOrigin::Root, auction_id, RelistParams::Collateral { target: dollar(200) })
 }
 
 #[cfg(test)]
@@ -124,4 +239,53 @@ mod tests {
 			assert_ok!(test_benchmark_cancel_collateral_auction());
 		});
 	}
+
+	#[test]
+	fn test_set_dutch_auction_params() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_dutch_auction_params());
+		});
+	}
+
+	#[test]
+	fn test_take_collateral_auction() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_take_collateral_auction());
+		});
+	}
+
+	#[test]
+	fn test_set_minimum_increment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_minimum_increment());
+		});
+	}
+
+	#[test]
+	fn test_set_no_bid_sale_max_slippage() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_no_bid_sale_max_slippage());
+		});
+	}
+
+	#[test]
+	fn test_partial_bid() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_partial_bid());
+		});
+	}
+
+	#[test]
+	fn test_settle_auction() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_settle_auction());
+		});
+	}
+
+	#[test]
+	fn test_relist() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_relist());
+		});
+	}
 }
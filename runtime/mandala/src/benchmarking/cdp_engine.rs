@@ -1,12 +1,14 @@
 use crate::{
 	AcalaOracle, AccountId, Amount, Balance, CdpEngine, CollateralCurrencyIds, CurrencyId, Dex, EmergencyShutdown,
-	GetStableCurrencyId, Indices, MaxSlippageSwapWithDEX, MinimumDebitValue, Price, Rate, Ratio, Runtime, TokenSymbol,
+	GetStableCurrencyId, Indices, LiquidatorBond, LiquidatorExclusiveWindow, LiquidatorUnbondingDelay,
+	MaxSlippageSwapWithDEX, MinimumDebitValue, ParameterChangeDelay, Price, Rate, Ratio, Runtime, System, TokenSymbol,
 	DOLLARS,
 };
 
-use super::utils::set_balance;
+use super::utils::{lookup_of_account, set_aca_balance, set_balance};
 use core::convert::TryInto;
 use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 use module_support::DEXManager;
 use orml_benchmarking::runtime_benchmarks;
@@ -66,6 +68,29 @@ runtime_benchmarks! {
 		Change::NewValue(dollar(100000))
 	)
 
+	schedule_set_collateral_params {
+	}: _(
+		RawOrigin::Root,
+		CurrencyId::Token(TokenSymbol::DOT),
+		Change::NewValue(Some(Rate::saturating_from_rational(1, 1000000))),
+		Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+		Change::NewValue(Some(Rate::saturating_from_rational(20, 100))),
+		Change::NewValue(Some(Ratio::saturating_from_rational(180, 100))),
+		Change::NewValue(dollar(100000))
+	)
+
+	cancel_collateral_params_change {
+		CdpEngine::schedule_set_collateral_params(
+			RawOrigin::Root.into(),
+			CurrencyId::Token(TokenSymbol::DOT),
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		)?;
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT))
+
 	set_global_params {
 	}: _(RawOrigin::Root, Rate::saturating_from_rational(1, 1000000))
 
@@ -102,7 +127,7 @@ runtime_benchmarks! {
 		CdpEngine::adjust_position(&owner, currency_id, collateral_amount.try_into().unwrap(), min_debit_amount)?;
 
 		// modify liquidation rate to make the cdp unsafe
-		CdpEngine::set_collateral_params(
+		CdpEngine::schedule_set_collateral_params(
 			RawOrigin::Root.into(),
 			currency_id,
 			Change::NoChange,
@@ -111,6 +136,11 @@ runtime_benchmarks! {
 			Change::NoChange,
 			Change::NoChange,
 		)?;
+		CdpEngine::on_initialize(System::block_number() + ParameterChangeDelay::get());
+
+		// pass the exclusive window of bonded liquidators
+		CdpEngine::liquidate(RawOrigin::None.into(), currency_id, owner_lookup.clone())?;
+		System::set_block_number(System::block_number() + LiquidatorExclusiveWindow::get());
 	}: liquidate(RawOrigin::None, currency_id, owner_lookup)
 
 	// `liquidate` by dex
@@ -153,7 +183,7 @@ runtime_benchmarks! {
 		CdpEngine::adjust_position(&owner, currency_id, collateral_amount.try_into().unwrap(), min_debit_amount)?;
 
 		// modify liquidation rate to make the cdp unsafe
-		CdpEngine::set_collateral_params(
+		CdpEngine::schedule_set_collateral_params(
 			RawOrigin::Root.into(),
 			currency_id,
 			Change::NoChange,
@@ -162,6 +192,11 @@ runtime_benchmarks! {
 			Change::NoChange,
 			Change::NoChange,
 		)?;
+		CdpEngine::on_initialize(System::block_number() + ParameterChangeDelay::get());
+
+		// pass the exclusive window of bonded liquidators
+		CdpEngine::liquidate(RawOrigin::None.into(), currency_id, owner_lookup.clone())?;
+		System::set_block_number(System::block_number() + LiquidatorExclusiveWindow::get());
 	}: liquidate(RawOrigin::None, currency_id, owner_lookup)
 	verify {
 		let (other_currency_amount, base_currency_amount) = Dex::get_liquidity_pool(currency_id, base_currency_id);
@@ -169,6 +204,31 @@ runtime_benchmarks! {
 		assert!(base_currency_amount < base_amount_in_dex);
 	}
 
+	bond_liquidator {
+		let liquidator: AccountId = account("liquidator", 0, SEED);
+		set_aca_balance(&liquidator, LiquidatorBond::get() * 2);
+	}: _(RawOrigin::Signed(liquidator))
+
+	unbond_liquidator {
+		let liquidator: AccountId = account("liquidator", 0, SEED);
+		set_aca_balance(&liquidator, LiquidatorBond::get() * 2);
+		CdpEngine::bond_liquidator(RawOrigin::Signed(liquidator.clone()).into())?;
+	}: _(RawOrigin::Signed(liquidator))
+
+	withdraw_liquidator_bond {
+		let liquidator: AccountId = account("liquidator", 0, SEED);
+		set_aca_balance(&liquidator, LiquidatorBond::get() * 2);
+		CdpEngine::bond_liquidator(RawOrigin::Signed(liquidator.clone()).into())?;
+		CdpEngine::unbond_liquidator(RawOrigin::Signed(liquidator.clone()).into())?;
+		System::set_block_number(System::block_number() + LiquidatorUnbondingDelay::get());
+	}: _(RawOrigin::Signed(liquidator))
+
+	slash_liquidator {
+		let liquidator: AccountId = account("liquidator", 0, SEED);
+		set_aca_balance(&liquidator, LiquidatorBond::get() * 2);
+		CdpEngine::bond_liquidator(RawOrigin::Signed(liquidator.clone()).into())?;
+	}: _(RawOrigin::Root, lookup_of_account(liquidator), LiquidatorBond::get())
+
 	settle {
 		let owner: AccountId = account("owner", 0, SEED);
 		let owner_lookup = Indices::unlookup(owner.clone());
@@ -224,6 +284,20 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_schedule_set_collateral_params() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_schedule_set_collateral_params());
+		});
+	}
+
+	#[test]
+	fn test_cancel_collateral_params_change() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_collateral_params_change());
+		});
+	}
+
 	#[test]
 	fn test_set_global_params() {
 		new_test_ext().execute_with(|| {
@@ -245,6 +319,34 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_bond_liquidator() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_bond_liquidator());
+		});
+	}
+
+	#[test]
+	fn test_unbond_liquidator() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unbond_liquidator());
+		});
+	}
+
+	#[test]
+	fn test_withdraw_liquidator_bond() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_withdraw_liquidator_bond());
+		});
+	}
+
+	#[test]
+	fn test_slash_liquidator() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_slash_liquidator());
+		});
+	}
+
 	#[test]
 	fn test_settle() {
 		new_test_ext().execute_with(|| {
@@ -32,6 +32,10 @@ runtime_benchmarks! {
 	set_collateral_auction_maximum_size {
 		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
 	}: _(RawOrigin::Root,currency_id, 200)
+
+	set_collateral_auction_maximum_target {
+		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+	}: _(RawOrigin::Root, currency_id, dollar(1000))
 }
 
 #[cfg(test)]
@@ -73,4 +77,11 @@ mod tests {
 			assert_ok!(test_benchmark_set_collateral_auction_maximum_size());
 		});
 	}
+
+	#[test]
+	fn test_set_collateral_auction_maximum_target() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_collateral_auction_maximum_target());
+		});
+	}
 }
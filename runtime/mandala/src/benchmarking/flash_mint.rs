@@ -0,0 +1,64 @@
+use crate::{AccountId, Call, FlashMint, Rate, Runtime};
+
+use super::utils::{dollars, set_ausd_balance};
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::FixedPointNumber;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+runtime_benchmarks! {
+	{ Runtime, module_flash_mint }
+
+	_ {}
+
+	flash_mint {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_ausd_balance(&caller, dollars(10u32));
+		FlashMint::set_max_flash_mint(RawOrigin::Root.into(), dollars(1000u32))?;
+		FlashMint::set_fee_rate(RawOrigin::Root.into(), Rate::saturating_from_rational(1, 100))?;
+		let call: Call = frame_system::Call::remark(vec![]).into();
+	}: _(RawOrigin::Signed(caller), dollars(1000u32), Box::new(call))
+
+	set_max_flash_mint {
+	}: _(RawOrigin::Root, dollars(1000u32))
+
+	set_fee_rate {
+	}: _(RawOrigin::Root, Rate::saturating_from_rational(1, 100))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_flash_mint() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_flash_mint());
+		});
+	}
+
+	#[test]
+	fn test_set_max_flash_mint() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_flash_mint());
+		});
+	}
+
+	#[test]
+	fn test_set_fee_rate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_fee_rate());
+		});
+	}
+}
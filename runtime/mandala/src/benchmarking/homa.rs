@@ -63,6 +63,19 @@ runtime_benchmarks! {
 	verify {
 		assert!(<Currencies as MultiCurrency<_>>::total_balance(GetStakingCurrencyId::get(), &caller) > 0);
 	}
+
+	emergency_unbond {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_balance(GetStakingCurrencyId::get(), &caller, dollars(1_000u128));
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), dollars(1_000u128))?;
+		PolkadotBridge::new_era(Default::default());
+	}: _(RawOrigin::Root, None)
+	verify {
+		assert!(Homa::is_mint_frozen());
+	}
+
+	unfreeze_mint {
+	}: _(RawOrigin::Root)
 }
 
 #[cfg(test)]
@@ -124,4 +137,18 @@ mod tests {
 			assert_ok!(test_benchmark_withdraw_redemption());
 		});
 	}
+
+	#[test]
+	fn test_emergency_unbond() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_emergency_unbond());
+		});
+	}
+
+	#[test]
+	fn test_unfreeze_mint() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unfreeze_mint());
+		});
+	}
 }
@@ -1,9 +1,9 @@
 use crate::{
-	AcalaOracle, AccountId, Amount, CdpEngine, CollateralCurrencyIds, CurrencyId, ExchangeRate, Honzon, Indices,
-	MinimumDebitValue, Price, Rate, Ratio, Runtime, TokenSymbol,
+	AcalaOracle, AccountId, Amount, Balance, CdpEngine, CollateralCurrencyIds, CurrencyId, Dex, ExchangeRate,
+	GetStableCurrencyId, Honzon, Indices, MinimumDebitValue, Price, Rate, Ratio, Runtime, TokenSymbol,
 };
 
-use super::utils::set_balance;
+use super::utils::{dollars, set_aca_balance, set_balance};
 use core::convert::TryInto;
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
@@ -17,6 +17,32 @@ use sp_std::prelude::*;
 
 const SEED: u32 = 0;
 
+fn inject_liquidity(
+	maker: AccountId,
+	currency_id: CurrencyId,
+	max_amount: Balance,
+	max_other_currency_amount: Balance,
+) -> Result<(), &'static str> {
+	let base_currency_id = GetStableCurrencyId::get();
+
+	// set balance
+	set_balance(currency_id, &maker, max_other_currency_amount.unique_saturated_into());
+	set_balance(base_currency_id, &maker, max_amount.unique_saturated_into());
+
+	let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), currency_id, base_currency_id);
+
+	Dex::add_liquidity(
+		RawOrigin::Signed(maker.clone()).into(),
+		base_currency_id,
+		currency_id,
+		max_amount,
+		max_other_currency_amount,
+		false,
+	)?;
+
+	Ok(())
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_honzon }
 
@@ -26,12 +52,18 @@ runtime_benchmarks! {
 		let caller: AccountId = account("caller", 0, SEED);
 		let to: AccountId = account("to", 0, SEED);
 		let to_lookup = Indices::unlookup(to);
+
+		// set balance for authorization deposit
+		set_aca_balance(&caller, dollars(10u32));
 	}: _(RawOrigin::Signed(caller), CurrencyId::Token(TokenSymbol::DOT), to_lookup)
 
 	unauthorize {
 		let caller: AccountId = account("caller", 0, SEED);
 		let to: AccountId = account("to", 0, SEED);
 		let to_lookup = Indices::unlookup(to);
+
+		// set balance for authorization deposit
+		set_aca_balance(&caller, dollars(10u32));
 		Honzon::authorize(
 			RawOrigin::Signed(caller.clone()).into(),
 			CurrencyId::Token(TokenSymbol::DOT),
@@ -40,21 +72,22 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Signed(caller), CurrencyId::Token(TokenSymbol::DOT), to_lookup)
 
 	unauthorize_all {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let c in 0 .. 100;
 
 		let caller: AccountId = account("caller", 0, SEED);
-		let currency_ids = CollateralCurrencyIds::get();
-		let to: AccountId = account("to", 0, SEED);
-		let to_lookup = Indices::unlookup(to);
+
+		// set balance for authorization deposits
+		set_aca_balance(&caller, dollars(10u32 * c + 10));
 
 		for i in 0 .. c {
+			let to: AccountId = account("to", i, SEED);
 			Honzon::authorize(
 				RawOrigin::Signed(caller.clone()).into(),
-				currency_ids[i as usize],
-				to_lookup.clone(),
+				CurrencyId::Token(TokenSymbol::DOT),
+				Indices::unlookup(to),
 			)?;
 		}
-	}: _(RawOrigin::Signed(caller))
+	}: _(RawOrigin::Signed(caller), c)
 
 	// `adjust_loan`, best case:
 	// adjust both collateral and debit
@@ -87,6 +120,43 @@ runtime_benchmarks! {
 		)?;
 	}: _(RawOrigin::Signed(caller), currency_id, collateral_amount.try_into().unwrap(), debit_amount)
 
+	adjust_loans {
+		let c in 1 .. CollateralCurrencyIds::get().len() as u32;
+
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_ids = CollateralCurrencyIds::get();
+		let min_debit_value = MinimumDebitValue::get();
+		let mut adjustments: Vec<(CurrencyId, Amount, Amount)> = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+			let min_debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_add(ExchangeRate::from_inner(1)).saturating_mul_int(min_debit_value);
+			let min_debit_amount: Amount = min_debit_amount.unique_saturated_into();
+			let debit_amount = min_debit_amount * 10;
+			let collateral_amount = (min_debit_value * 10 * 2).unique_saturated_into();
+
+			// set balance
+			set_balance(currency_id, &caller, collateral_amount);
+
+			// feed price
+			AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+
+			// set risk params
+			CdpEngine::set_collateral_params(
+				RawOrigin::Root.into(),
+				currency_id,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+				Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+				Change::NewValue(min_debit_value * 100),
+			)?;
+
+			adjustments.push((currency_id, collateral_amount.try_into().unwrap(), debit_amount));
+		}
+	}: _(RawOrigin::Signed(caller), adjustments)
+
 	transfer_loan_from {
 		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
 		let sender: AccountId = account("sender", 0, SEED);
@@ -102,6 +172,7 @@ runtime_benchmarks! {
 
 		// set balance
 		set_balance(currency_id, &sender, collateral_amount);
+		set_aca_balance(&sender, dollars(10u32));
 
 		// feed price
 		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
@@ -126,12 +197,89 @@ runtime_benchmarks! {
 		)?;
 
 		// authorize receiver
-		Honzon::authorize(
+		Honzon::authorize_transfer(
 			RawOrigin::Signed(sender.clone()).into(),
 			currency_id,
 			receiver_lookup,
 		)?;
 	}: _(RawOrigin::Signed(receiver), currency_id, sender_lookup)
+
+	adjust_loan_on_behalf {
+		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let owner: AccountId = account("owner", 0, SEED);
+		let owner_lookup = Indices::unlookup(owner.clone());
+		let caller: AccountId = account("caller", 0, SEED);
+		let caller_lookup = Indices::unlookup(caller.clone());
+		let min_debit_value = MinimumDebitValue::get();
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let min_debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_add(ExchangeRate::from_inner(1)).saturating_mul_int(min_debit_value);
+		let min_debit_amount: Amount = min_debit_amount.unique_saturated_into();
+		let debit_amount = min_debit_amount * 10;
+		let collateral_amount = (min_debit_value * 10 * 2).unique_saturated_into();
+
+		// set balance
+		set_balance(currency_id, &owner, collateral_amount);
+		set_aca_balance(&owner, dollars(10u32));
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(min_debit_value * 100),
+		)?;
+
+		// authorize caller
+		Honzon::authorize(
+			RawOrigin::Signed(owner).into(),
+			currency_id,
+			caller_lookup,
+		)?;
+	}: _(RawOrigin::Signed(caller), owner_lookup, currency_id, collateral_amount.try_into().unwrap(), debit_amount)
+
+	close_loan_by_dex {
+		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let sender: AccountId = account("sender", 0, SEED);
+		let maker: AccountId = account("maker", 0, SEED);
+		let min_debit_value = MinimumDebitValue::get();
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let min_debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_add(ExchangeRate::from_inner(1)).saturating_mul_int(min_debit_value);
+		let min_debit_amount: Amount = min_debit_amount.unique_saturated_into();
+		let debit_amount = min_debit_amount * 10;
+		let collateral_amount: Balance = min_debit_value * 10 * 2;
+
+		// set balance and inject liquidity
+		set_balance(currency_id, &sender, collateral_amount);
+		inject_liquidity(maker, currency_id, min_debit_value * 1000, min_debit_value * 1000)?;
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(min_debit_value * 100),
+		)?;
+
+		// initialize sender's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(sender.clone()).into(),
+			currency_id,
+			collateral_amount.try_into().unwrap(),
+			debit_amount,
+		)?;
+	}: _(RawOrigin::Signed(sender), currency_id, collateral_amount)
 }
 
 #[cfg(test)]
@@ -173,4 +321,25 @@ mod tests {
 			assert_ok!(test_benchmark_adjust_loan());
 		});
 	}
+
+	#[test]
+	fn test_adjust_loans() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_adjust_loans());
+		});
+	}
+
+	#[test]
+	fn test_adjust_loan_on_behalf() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_adjust_loan_on_behalf());
+		});
+	}
+
+	#[test]
+	fn test_close_loan_by_dex() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_close_loan_by_dex());
+		});
+	}
 }
@@ -1,13 +1,15 @@
 use crate::{
-	AccountId, Balance, CollateralCurrencyIds, CurrencyId, GetStableCurrencyId, Incentives, Rate, Rewards, Runtime,
-	TokenSymbol, DOLLARS,
+	AccountId, AccumulatePeriod, Balance, CollateralCurrencyIds, CurrencyId, Dex, GetStableCurrencyId, Incentives,
+	Rate, Rewards, Runtime, TokenSymbol, DAYS, DOLLARS,
 };
 
 use super::utils::set_balance;
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
-use module_incentives::PoolId;
+use module_incentives::{EmissionSchedule, PoolId};
 use orml_benchmarking::runtime_benchmarks;
+use orml_traits::RewardHandler;
+use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -99,6 +101,110 @@ runtime_benchmarks! {
 			values.push((lp_share_currency_id, Rate::default()));
 		}
 	}: _(RawOrigin::Root, values)
+
+	update_extra_incentive_rewards {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut values = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			values.push((PoolId::Loans(currency_id), GetStableCurrencyId::get(), dollar(100)));
+		}
+	}: _(RawOrigin::Root, values)
+
+	update_claim_reward_deduction_rates {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut values = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			values.push((PoolId::Loans(currency_id), Rate::saturating_from_rational(1, 10)));
+		}
+	}: _(RawOrigin::Root, values)
+
+	update_emission_schedules {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut values = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			values.push((PoolId::Loans(currency_id), Some(EmissionSchedule::Halving { periods: 10 })));
+		}
+	}: _(RawOrigin::Root, values)
+
+	add_rewards {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStableCurrencyId::get();
+		set_balance(currency_id, &caller, dollar(10000));
+	}: _(RawOrigin::Signed(caller), PoolId::Loans(CurrencyId::Token(TokenSymbol::DOT)), currency_id, dollar(10000), 100)
+
+	compound {
+		let maker: AccountId = account("maker", 0, SEED);
+		let caller: AccountId = account("caller", 0, SEED);
+		let donor: AccountId = account("donor", 0, SEED);
+		let currency_id_a = CurrencyId::Token(TokenSymbol::XBTC);
+		let currency_id_b = GetStableCurrencyId::get();
+		let pool_id = PoolId::DexIncentive(BTC_AUSD_LP);
+
+		// inject liquidity and deposit the share of the caller
+		set_balance(currency_id_a, &maker, dollar(100));
+		set_balance(currency_id_b, &maker, dollar(10000));
+		set_balance(currency_id_a, &caller, dollar(1));
+		set_balance(currency_id_b, &caller, dollar(100));
+		let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
+		Dex::add_liquidity(RawOrigin::Signed(maker).into(), currency_id_a, currency_id_b, dollar(100), dollar(10000), false)?;
+		Dex::add_liquidity(RawOrigin::Signed(caller.clone()).into(), currency_id_a, currency_id_b, dollar(1), dollar(100), true)?;
+
+		// rewards in only one currency of the trading pair need the swap
+		set_balance(currency_id_b, &donor, dollar(100));
+		Incentives::add_rewards(RawOrigin::Signed(donor).into(), pool_id, currency_id_b, dollar(100), 1)?;
+		Incentives::accumulate_reward(AccumulatePeriod::get(), |_, _| {});
+	}: _(RawOrigin::Signed(caller), pool_id)
+
+	update_reward_vesting_durations {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut values = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			values.push((PoolId::Loans(currency_id), 84 * DAYS));
+		}
+	}: _(RawOrigin::Root, values)
+
+	update_pool_lifetimes {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut values = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			values.push((PoolId::Loans(currency_id), Some((DAYS, 84 * DAYS))));
+		}
+	}: _(RawOrigin::Root, values)
+
+	update_surplus_reward_rate {
+	}: _(RawOrigin::Root, Rate::saturating_from_rational(1, 100))
+
+	// set referrer, worst case:
+	// the referrer chain is the longest allowed
+	set_referrer {
+		let caller: AccountId = account("caller", 0, SEED);
+		let referrer: AccountId = account("referrer", 0, SEED);
+		for i in 1 .. module_incentives::MAX_REFERRER_CHAIN_DEPTH {
+			let referee: AccountId = account("referrer", i - 1, SEED);
+			let ancestor: AccountId = account("referrer", i, SEED);
+			module_incentives::Referrers::<Runtime>::insert(&referee, &ancestor);
+		}
+	}: _(RawOrigin::Signed(caller), referrer)
+
+	claim_referral_rewards {
+		let referrer: AccountId = account("referrer", 0, SEED);
+		module_incentives::ReferralRewards::<Runtime>::insert(&referrer, dollar(100));
+	}: _(RawOrigin::Signed(referrer))
 }
 
 #[cfg(test)]
@@ -161,4 +267,74 @@ mod tests {
 			assert_ok!(test_benchmark_update_dex_saving_rates());
 		});
 	}
+
+	#[test]
+	fn test_update_extra_incentive_rewards() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_extra_incentive_rewards());
+		});
+	}
+
+	#[test]
+	fn test_update_claim_reward_deduction_rates() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_claim_reward_deduction_rates());
+		});
+	}
+
+	#[test]
+	fn test_update_emission_schedules() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_emission_schedules());
+		});
+	}
+
+	#[test]
+	fn test_add_rewards() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_rewards());
+		});
+	}
+
+	#[test]
+	fn test_compound() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_compound());
+		});
+	}
+
+	#[test]
+	fn test_update_reward_vesting_durations() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_reward_vesting_durations());
+		});
+	}
+
+	#[test]
+	fn test_update_pool_lifetimes() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_pool_lifetimes());
+		});
+	}
+
+	#[test]
+	fn test_update_surplus_reward_rate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_surplus_reward_rate());
+		});
+	}
+
+	#[test]
+	fn test_set_referrer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_referrer());
+		});
+	}
+
+	#[test]
+	fn test_claim_referral_rewards() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_referral_rewards());
+		});
+	}
 }
@@ -8,10 +8,12 @@ pub mod dex;
 pub mod emergency_shutdown;
 pub mod evm;
 pub mod evm_accounts;
+pub mod flash_mint;
 pub mod homa;
 pub mod honzon;
 pub mod incentives;
 pub mod prices;
+pub mod savings;
 pub mod transaction_payment;
 
 // orml benchmarking
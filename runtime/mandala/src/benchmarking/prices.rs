@@ -1,6 +1,8 @@
-use crate::{AcalaOracle, CollateralCurrencyIds, CurrencyId, Origin, Price, Prices, Runtime, TokenSymbol};
+use crate::{AcalaOracle, CollateralCurrencyIds, CurrencyId, Origin, Price, Prices, Ratio, Runtime, TokenSymbol};
 
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
+use module_prices::{FeedAggregationParams, PriceSource};
 use orml_benchmarking::runtime_benchmarks;
 use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
@@ -24,6 +26,31 @@ runtime_benchmarks! {
 		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
 		Prices::lock_price(Origin::root(), CurrencyId::Token(TokenSymbol::DOT))?;
 	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT))
+
+	set_twap_window {
+		let window = <Runtime as module_prices::Config>::MaxTwapWindow::get();
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT), window)
+
+	set_max_price_age {
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT), Some(60_000))
+
+	set_feed_aggregation {
+		let params = FeedAggregationParams {
+			min_feeds: 2,
+			max_deviation: Ratio::saturating_from_rational(1, 10),
+		};
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT), Some(params))
+
+	set_price_source_priority {
+		let sources = vec![PriceSource::Oracle, PriceSource::Fallback];
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT), sources)
+
+	set_price_source_weights {
+		let weights = vec![
+			(PriceSource::Oracle, Ratio::saturating_from_rational(7, 10)),
+			(PriceSource::Fallback, Ratio::saturating_from_rational(3, 10)),
+		];
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT), weights)
 }
 
 #[cfg(test)]
@@ -51,4 +78,39 @@ mod tests {
 			assert_ok!(test_benchmark_unlock_price());
 		});
 	}
+
+	#[test]
+	fn test_set_twap_window() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_twap_window());
+		});
+	}
+
+	#[test]
+	fn test_set_max_price_age() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_price_age());
+		});
+	}
+
+	#[test]
+	fn test_set_feed_aggregation() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_feed_aggregation());
+		});
+	}
+
+	#[test]
+	fn test_set_price_source_priority() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_price_source_priority());
+		});
+	}
+
+	#[test]
+	fn test_set_price_source_weights() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_price_source_weights());
+		});
+	}
 }
@@ -0,0 +1,67 @@
+use crate::{AccountId, Rate, Runtime, Savings};
+
+use super::utils::{dollars, set_ausd_balance};
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::FixedPointNumber;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+runtime_benchmarks! {
+	{ Runtime, module_savings }
+
+	_ {}
+
+	deposit {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_ausd_balance(&caller, dollars(1000u32));
+		Savings::set_savings_rate(RawOrigin::Root.into(), Rate::saturating_from_rational(1, 1_000_000_000))?;
+	}: _(RawOrigin::Signed(caller), dollars(1000u32))
+
+	withdraw {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_ausd_balance(&caller, dollars(1000u32));
+		Savings::set_savings_rate(RawOrigin::Root.into(), Rate::saturating_from_rational(1, 1_000_000_000))?;
+		Savings::deposit(RawOrigin::Signed(caller.clone()).into(), dollars(1000u32))?;
+		let shares = Savings::shares(&caller);
+	}: _(RawOrigin::Signed(caller), shares)
+
+	set_savings_rate {
+	}: _(RawOrigin::Root, Rate::saturating_from_rational(1, 1_000_000_000))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_deposit());
+		});
+	}
+
+	#[test]
+	fn test_withdraw() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_withdraw());
+		});
+	}
+
+	#[test]
+	fn test_set_savings_rate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_savings_rate());
+		});
+	}
+}
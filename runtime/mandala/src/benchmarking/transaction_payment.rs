@@ -1,9 +1,48 @@
-use crate::{Runtime, System, TransactionPayment};
+use crate::{
+	AccountId, AllNonNativeCurrencyIds, Balance, Call, CurrencyId, Dex, FeeBurnPeriod, GetNativeCurrencyId,
+	GetStableCurrencyId, MaxFeeDiscountTiers, Ratio, Runtime, System, TransactionPayment, WeightToFee,
+};
 
-use frame_support::traits::OnFinalize;
+use super::utils::{dollars, set_aca_balance, set_balance};
+use frame_benchmarking::account;
+use frame_support::{
+	traits::{OnFinalize, OnInitialize},
+	weights::{DispatchClass, WeightToFeePolynomial},
+};
+use frame_system::RawOrigin;
+use module_transaction_payment::{FeeCoefficient, Multiplier};
 use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
 
+const SEED: u32 = 0;
+
+fn inject_liquidity(
+	maker: AccountId,
+	currency_id: CurrencyId,
+	max_amount: Balance,
+	max_other_currency_amount: Balance,
+) -> Result<(), &'static str> {
+	let base_currency_id = GetStableCurrencyId::get();
+
+	// set balance
+	set_balance(currency_id, &maker, max_other_currency_amount);
+	set_balance(base_currency_id, &maker, max_amount);
+
+	let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), currency_id, base_currency_id);
+
+	Dex::add_liquidity(
+		RawOrigin::Signed(maker).into(),
+		base_currency_id,
+		currency_id,
+		max_amount,
+		max_other_currency_amount,
+		false,
+	)?;
+
+	Ok(())
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_transaction_payment }
 
@@ -13,6 +52,65 @@ runtime_benchmarks! {
 	}: {
 		TransactionPayment::on_finalize(System::block_number());
 	}
+
+	set_fee_burn_ratio {
+	}: _(RawOrigin::Root, Ratio::saturating_from_rational(10, 100))
+
+	set_fee_discount_tiers {
+		let c in 0 .. MaxFeeDiscountTiers::get();
+
+		let tiers = (0..c)
+			.map(|i| (dollars(1000u32 * (i + 1)), Ratio::saturating_from_rational(i + 1, 100)))
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Root, tiers)
+
+	set_weight_to_fee_coefficients {
+		// raise the fee level by 10%
+		let coefficients = WeightToFee::polynomial()
+			.into_iter()
+			.map(|coefficient| FeeCoefficient {
+				coeff_integer: coefficient.coeff_integer.saturating_mul(11) / 10,
+				coeff_frac: coefficient.coeff_frac,
+				negative: coefficient.negative,
+				degree: coefficient.degree,
+			})
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Root, coefficients)
+
+	set_dispatch_class_fee_multiplier {
+	}: _(RawOrigin::Root, DispatchClass::Operational, Multiplier::saturating_from_rational(1, 2))
+
+	set_sponsorship {
+		let sponsor: AccountId = account("sponsor", 0, SEED);
+		let who: AccountId = account("who", 0, SEED);
+	}: _(RawOrigin::Signed(sponsor), who, Some(dollars(100u32)))
+
+	with_fee_paid_by {
+		let sponsor: AccountId = account("sponsor", 0, SEED);
+		let who: AccountId = account("who", 0, SEED);
+		TransactionPayment::set_sponsorship(RawOrigin::Signed(sponsor.clone()).into(), who.clone(), Some(dollars(100u32)))?;
+		let call: Call = frame_system::Call::remark(vec![]).into();
+	}: _(RawOrigin::Signed(who), sponsor, Box::new(call))
+
+	// burn the fees with `c` non-native currencies to swap
+	burn_fees {
+		let c in 0 .. AllNonNativeCurrencyIds::get().len() as u32;
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let burn_account = TransactionPayment::fee_burn_account_id();
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		inject_liquidity(maker.clone(), GetNativeCurrencyId::get(), dollars(10_000u32), dollars(10_000u32))?;
+		for currency_id in AllNonNativeCurrencyIds::get().into_iter().take(c as usize) {
+			if currency_id != stable_currency_id {
+				inject_liquidity(maker.clone(), currency_id, dollars(10_000u32), dollars(10_000u32))?;
+			}
+			set_balance(currency_id, &burn_account, dollars(1u32));
+		}
+		set_aca_balance(&burn_account, dollars(1u32));
+	}: {
+		TransactionPayment::on_initialize(FeeBurnPeriod::get());
+	}
 }
 
 #[cfg(test)]
@@ -33,4 +131,53 @@ mod tests {
 			assert_ok!(test_benchmark_on_finalize());
 		});
 	}
+
+	#[test]
+	fn test_set_fee_burn_ratio() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_fee_burn_ratio());
+		});
+	}
+
+	#[test]
+	fn test_set_fee_discount_tiers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_fee_discount_tiers());
+		});
+	}
+
+	#[test]
+	fn test_set_weight_to_fee_coefficients() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_weight_to_fee_coefficients());
+		});
+	}
+
+	#[test]
+	fn test_set_dispatch_class_fee_multiplier() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_dispatch_class_fee_multiplier());
+		});
+	}
+
+	#[test]
+	fn test_set_sponsorship() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_sponsorship());
+		});
+	}
+
+	#[test]
+	fn test_with_fee_paid_by() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_with_fee_paid_by());
+		});
+	}
+
+	#[test]
+	fn test_burn_fees() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_burn_fees());
+		});
+	}
 }
@@ -27,4 +27,7 @@ impl<T: frame_system::Config> module_cdp_treasury::WeightInfo for WeightInfo<T>
 	fn set_collateral_auction_maximum_size() -> Weight {
 		(54_430_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
 	}
+	fn set_collateral_auction_maximum_target() -> Weight {
+		(54_430_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,23 @@
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> module_flash_mint::WeightInfo for WeightInfo<T> {
+	fn flash_mint() -> Weight {
+		(96_412_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn set_max_flash_mint() -> Weight {
+		(21_906_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_fee_rate() -> Weight {
+		(21_730_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+}
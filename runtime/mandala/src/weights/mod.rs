@@ -8,9 +8,14 @@ pub mod dex;
 pub mod emergency_shutdown;
 pub mod evm;
 pub mod evm_accounts;
+pub mod flash_mint;
 pub mod homa;
 pub mod honzon;
 pub mod incentives;
 pub mod nft;
+pub mod nft_lending;
+pub mod oracle_staking;
 pub mod prices;
+pub mod savings;
 pub mod transaction_payment;
+pub mod treasury_vesting;
@@ -17,4 +17,34 @@ impl<T: frame_system::Config> module_transaction_payment::WeightInfo for WeightI
 	fn set_default_fee_token() -> Weight {
 		(1_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
 	}
+	fn set_fee_burn_ratio() -> Weight {
+		(16_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn burn_fees(c: u32) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((94_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
+	fn set_fee_discount_tiers(c: u32) -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add((312_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_sponsorship() -> Weight {
+		(19_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn with_fee_paid_by() -> Weight {
+		(14_000_000 as Weight).saturating_add(DbWeight::get().reads(1 as Weight))
+	}
+	fn set_weight_to_fee_coefficients() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_dispatch_class_fee_multiplier() -> Weight {
+		(16_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -9,8 +9,8 @@ use frame_system::RawOrigin;
 use mandala_runtime::{
 	get_all_module_accounts, AccountId, AuthoritysOriginId, Balance, Balances, BlockNumber, Call, CreateClassDeposit,
 	CreateTokenDeposit, CurrencyId, DSWFModuleId, EnabledTradingPairs, Event, EvmAccounts, GetNativeCurrencyId,
-	NativeTokenExistentialDeposit, NftModuleId, Origin, OriginCaller, Perbill, Proxy, Runtime, SevenDays, System,
-	TokenSymbol, EVM, NFT,
+	NativeTokenExistentialDeposit, NftModuleId, Origin, OriginCaller, ParameterChangeDelay, Perbill, Proxy, Runtime,
+	SevenDays, System, TokenSymbol, EVM, NFT,
 };
 use module_cdp_engine::LiquidationStrategy;
 use module_support::{CDPTreasury, DEXManager, Price, Rate, Ratio, RiskManager};
@@ -383,7 +383,7 @@ fn liquidate_cdp() {
 			assert_eq!(CdpTreasuryModule::debit_pool(), 0);
 			assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
 
-			assert_ok!(CdpEngineModule::set_collateral_params(
+			assert_ok!(CdpEngineModule::schedule_set_collateral_params(
 				<Runtime as frame_system::Config>::Origin::root(),
 				CurrencyId::Token(TokenSymbol::XBTC),
 				Change::NoChange,
@@ -392,6 +392,7 @@ fn liquidate_cdp() {
 				Change::NewValue(Some(Ratio::saturating_from_rational(400, 100))),
 				Change::NoChange,
 			));
+			CdpEngineModule::on_initialize(SystemModule::block_number() + ParameterChangeDelay::get());
 
 			assert_ok!(CdpEngineModule::liquidate_unsafe_cdp(
 				AccountId::from(ALICE),
@@ -404,6 +405,7 @@ fn liquidate_cdp() {
 					AccountId::from(ALICE),
 					amount(10),
 					amount(50_000),
+					amount(10_000),
 					LiquidationStrategy::Auction,
 				));
 			assert!(SystemModule::events()
@@ -431,6 +433,7 @@ fn liquidate_cdp() {
 				AccountId::from(BOB),
 				amount(1),
 				amount(5_000),
+				amount(1_000),
 				LiquidationStrategy::Exchange,
 			));
 			assert!(SystemModule::events()
@@ -695,7 +698,7 @@ fn test_honzon_module() {
 				.is_ok(),
 				false
 			);
-			assert_ok!(CdpEngineModule::set_collateral_params(
+			assert_ok!(CdpEngineModule::schedule_set_collateral_params(
 				<Runtime as frame_system::Config>::Origin::root(),
 				CurrencyId::Token(TokenSymbol::XBTC),
 				Change::NoChange,
@@ -704,6 +707,7 @@ fn test_honzon_module() {
 				Change::NoChange,
 				Change::NoChange,
 			));
+			CdpEngineModule::on_initialize(SystemModule::block_number() + ParameterChangeDelay::get());
 			assert_ok!(CdpEngineModule::liquidate(
 				<Runtime as frame_system::Config>::Origin::none(),
 				CurrencyId::Token(TokenSymbol::XBTC),
@@ -1130,7 +1134,12 @@ fn test_nft_module() {
 			assert_ok!(NFT::create_class(
 				origin_of(AccountId::from(ALICE)),
 				vec![1],
-				module_nft::Properties(module_nft::ClassProperty::Transferable | module_nft::ClassProperty::Burnable)
+				module_nft::Properties(
+					module_nft::ClassProperty::Transferable
+						| module_nft::ClassProperty::Burnable
+						| module_nft::ClassProperty::Mintable
+				),
+				None
 			));
 			assert_eq!(
 				Balances::deposit_into_existing(&NftModuleId::get().into_sub_account(0), 1 * CreateTokenDeposit::get())
@@ -1141,8 +1150,9 @@ fn test_nft_module() {
 				origin_of(NftModuleId::get().into_sub_account(0)),
 				MultiAddress::Id(AccountId::from(BOB)),
 				0,
-				vec![1],
-				1
+				vec![vec![1]],
+				1,
+				None
 			));
 			assert_ok!(NFT::burn(origin_of(AccountId::from(BOB)), (0, 0)));
 			assert_eq!(Balances::free_balance(AccountId::from(BOB)), CreateTokenDeposit::get());